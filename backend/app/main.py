@@ -140,6 +140,9 @@ class JiraAnalysisRequest(BaseModel):
     text: Optional[str] = None
     # Project key for context-aware task generation (required for enhanced generation)
     project_key: str
+    # Background notes attached to the meeting in the desktop app, used as
+    # reference material only
+    background_context: Optional[str] = None
 
 class JiraIssueUpdate(BaseModel):
     """Model for updating Jira issue fields"""
@@ -872,7 +875,8 @@ async def analyze_jira_tasks(request: JiraAnalysisRequest):
             text,
             request.model,
             request.model_name,
-            project_context=project_context
+            project_context=project_context,
+            background_context=request.background_context,
         )
         
         return {"tasks": tasks}
@@ -1016,6 +1020,7 @@ class GenerateQuestionsRequest(BaseModel):
     model_name: str = "gpt-4o"
     text: Optional[str] = None  # Raw transcript text (optional)
     project_key: Optional[str] = None  # Jira project for context
+    background_context: Optional[str] = None  # Meeting's reference notes (optional)
 
 @app.websocket("/ws/extension")
 async def websocket_extension_endpoint(
@@ -1112,7 +1117,8 @@ async def generate_clarifying_questions(request: GenerateQuestionsRequest):
             text=text,
             model=request.model,
             model_name=request.model_name,
-            project_key=request.project_key
+            project_key=request.project_key,
+            background_context=request.background_context,
         )
         
         return {
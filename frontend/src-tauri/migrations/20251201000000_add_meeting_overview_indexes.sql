@@ -0,0 +1,6 @@
+-- Indexes backing the single-query meetings overview:
+--   1. transcripts(meeting_id, audio_end_time) serves both the per-meeting
+--      segment count and MAX(audio_end_time) as a covering index
+--   2. meetings(created_at) serves the newest-first ordering of the list
+CREATE INDEX IF NOT EXISTS idx_transcripts_meeting_id_end_time ON transcripts(meeting_id, audio_end_time);
+CREATE INDEX IF NOT EXISTS idx_meetings_created_at ON meetings(created_at);
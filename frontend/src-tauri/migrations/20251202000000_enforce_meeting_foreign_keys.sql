@@ -0,0 +1,77 @@
+-- Migration: Enforce referential integrity from child tables to meetings
+-- Databases imported from the legacy Python backend were created without
+-- FOREIGN KEY constraints (CREATE TABLE IF NOT EXISTS kept their old shape),
+-- so deleting a meeting could leave orphaned rows behind.
+-- SQLite cannot add constraints to an existing table, so each child table is
+-- rebuilt. Orphaned rows (meeting no longer exists) are dropped on the way.
+
+-- transcripts
+CREATE TABLE transcripts_new (
+    id TEXT PRIMARY KEY,
+    meeting_id TEXT NOT NULL,
+    transcript TEXT NOT NULL,
+    timestamp TEXT NOT NULL,
+    summary TEXT,
+    action_items TEXT,
+    key_points TEXT,
+    audio_start_time REAL,
+    audio_end_time REAL,
+    duration REAL,
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+INSERT INTO transcripts_new (id, meeting_id, transcript, timestamp, summary, action_items, key_points, audio_start_time, audio_end_time, duration)
+SELECT id, meeting_id, transcript, timestamp, summary, action_items, key_points, audio_start_time, audio_end_time, duration
+FROM transcripts
+WHERE meeting_id IN (SELECT id FROM meetings);
+
+DROP TABLE transcripts;
+ALTER TABLE transcripts_new RENAME TO transcripts;
+
+-- Recreate the overview index dropped with the old table
+CREATE INDEX IF NOT EXISTS idx_transcripts_meeting_id_end_time ON transcripts(meeting_id, audio_end_time);
+
+-- summary_processes
+CREATE TABLE summary_processes_new (
+    meeting_id TEXT PRIMARY KEY,
+    status TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    error TEXT,
+    result TEXT,
+    start_time TEXT,
+    end_time TEXT,
+    chunk_count INTEGER DEFAULT 0,
+    processing_time REAL DEFAULT 0.0,
+    metadata TEXT,
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+INSERT INTO summary_processes_new (meeting_id, status, created_at, updated_at, error, result, start_time, end_time, chunk_count, processing_time, metadata)
+SELECT meeting_id, status, created_at, updated_at, error, result, start_time, end_time, chunk_count, processing_time, metadata
+FROM summary_processes
+WHERE meeting_id IN (SELECT id FROM meetings);
+
+DROP TABLE summary_processes;
+ALTER TABLE summary_processes_new RENAME TO summary_processes;
+
+-- transcript_chunks
+CREATE TABLE transcript_chunks_new (
+    meeting_id TEXT PRIMARY KEY,
+    meeting_name TEXT,
+    transcript_text TEXT NOT NULL,
+    model TEXT NOT NULL,
+    model_name TEXT NOT NULL,
+    chunk_size INTEGER,
+    overlap INTEGER,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+INSERT INTO transcript_chunks_new (meeting_id, meeting_name, transcript_text, model, model_name, chunk_size, overlap, created_at)
+SELECT meeting_id, meeting_name, transcript_text, model, model_name, chunk_size, overlap, created_at
+FROM transcript_chunks
+WHERE meeting_id IN (SELECT id FROM meetings);
+
+DROP TABLE transcript_chunks;
+ALTER TABLE transcript_chunks_new RENAME TO transcript_chunks;
@@ -0,0 +1,5 @@
+-- Per-meeting defaults for summary generation. NULL means "use the global
+-- model config / default template".
+ALTER TABLE meetings ADD COLUMN preferred_template_id TEXT;
+ALTER TABLE meetings ADD COLUMN preferred_model_provider TEXT;
+ALTER TABLE meetings ADD COLUMN preferred_model_name TEXT;
@@ -0,0 +1,2 @@
+-- When the first-run setup wizard was completed (NULL = not yet)
+ALTER TABLE settings ADD COLUMN setupCompletedAt TEXT;
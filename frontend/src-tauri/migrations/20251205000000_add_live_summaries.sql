@@ -0,0 +1,16 @@
+-- Rolling summary drafts written while a meeting is being recorded. A draft
+-- belongs to a recording session; meeting_id is filled in once the recording
+-- is saved as a meeting, and the draft is removed when the final summary
+-- completes.
+CREATE TABLE IF NOT EXISTS live_summaries (
+    session_id TEXT PRIMARY KEY,
+    meeting_name TEXT,
+    meeting_id TEXT REFERENCES meetings(id) ON DELETE CASCADE,
+    draft TEXT NOT NULL,
+    passes INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_live_summaries_meeting_id ON live_summaries(meeting_id);
+CREATE INDEX IF NOT EXISTS idx_live_summaries_updated_at ON live_summaries(updated_at);
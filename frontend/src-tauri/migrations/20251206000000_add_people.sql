@@ -0,0 +1,13 @@
+-- People directory used to resolve action item owners ("Alex", "AK") to a
+-- person with an email and Jira account. aliases is a JSON array of strings.
+CREATE TABLE IF NOT EXISTS people (
+    id TEXT PRIMARY KEY,
+    display_name TEXT NOT NULL,
+    aliases TEXT NOT NULL DEFAULT '[]',
+    email TEXT,
+    jira_account_id TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_people_display_name ON people(display_name COLLATE NOCASE);
@@ -0,0 +1,13 @@
+-- Progress of saving a meeting's transcript. Segments are committed in
+-- batches and segments_done advances with each batch, so a save that fails
+-- partway can be resumed from the first segment that did not land.
+CREATE TABLE IF NOT EXISTS transcription_status (
+    meeting_id TEXT PRIMARY KEY REFERENCES meetings(id) ON DELETE CASCADE,
+    state TEXT NOT NULL,
+    segments_done INTEGER NOT NULL DEFAULT 0,
+    segments_total INTEGER NOT NULL DEFAULT 0,
+    error TEXT,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_transcription_status_state ON transcription_status(state);
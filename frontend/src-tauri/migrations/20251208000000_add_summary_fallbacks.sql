@@ -0,0 +1,2 @@
+-- Ordered JSON list of {provider, model} tried when the summary provider is unavailable
+ALTER TABLE settings ADD COLUMN summaryFallbacks TEXT;
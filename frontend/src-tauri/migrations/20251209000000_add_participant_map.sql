@@ -0,0 +1,4 @@
+-- JSON object mapping participant names to the pseudonyms ("Engineer A") used
+-- when the meeting is summarized anonymously. Kept so re-runs reuse the same
+-- pseudonyms and summaries can be de-anonymized for internal viewing.
+ALTER TABLE meetings ADD COLUMN participant_map TEXT;
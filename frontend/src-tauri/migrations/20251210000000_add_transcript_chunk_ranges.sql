@@ -0,0 +1,7 @@
+-- Chunk boundaries the summary pipeline split the transcript into, as JSON:
+-- [{"chunk_index": 1, "char_start": 0, "char_end": 1200}, ...]
+ALTER TABLE transcript_chunks ADD COLUMN chunk_ranges TEXT;
+
+-- meeting_name used to be set only by one of the two rename paths
+UPDATE transcript_chunks
+SET meeting_name = (SELECT title FROM meetings WHERE meetings.id = transcript_chunks.meeting_id);
@@ -0,0 +1,33 @@
+-- Full-text index over the markdown of stored summaries, one row per meeting.
+-- Triggers on summary_processes keep it in step with generated and edited
+-- summaries; results that aren't valid JSON or have no markdown aren't indexed.
+CREATE VIRTUAL TABLE summary_search USING fts5(
+    meeting_id UNINDEXED,
+    content,
+    tokenize = 'porter unicode61'
+);
+
+INSERT INTO summary_search (meeting_id, content)
+SELECT meeting_id, json_extract(CASE WHEN json_valid(result) THEN result END, '$.markdown')
+FROM summary_processes
+WHERE json_extract(CASE WHEN json_valid(result) THEN result END, '$.markdown') IS NOT NULL;
+
+CREATE TRIGGER summary_search_insert AFTER INSERT ON summary_processes
+BEGIN
+    INSERT INTO summary_search (meeting_id, content)
+    SELECT NEW.meeting_id, json_extract(CASE WHEN json_valid(NEW.result) THEN NEW.result END, '$.markdown')
+    WHERE json_extract(CASE WHEN json_valid(NEW.result) THEN NEW.result END, '$.markdown') IS NOT NULL;
+END;
+
+CREATE TRIGGER summary_search_update AFTER UPDATE OF result ON summary_processes
+BEGIN
+    DELETE FROM summary_search WHERE meeting_id = OLD.meeting_id;
+    INSERT INTO summary_search (meeting_id, content)
+    SELECT NEW.meeting_id, json_extract(CASE WHEN json_valid(NEW.result) THEN NEW.result END, '$.markdown')
+    WHERE json_extract(CASE WHEN json_valid(NEW.result) THEN NEW.result END, '$.markdown') IS NOT NULL;
+END;
+
+CREATE TRIGGER summary_search_delete AFTER DELETE ON summary_processes
+BEGIN
+    DELETE FROM summary_search WHERE meeting_id = OLD.meeting_id;
+END;
@@ -0,0 +1,7 @@
+-- Background notes attached to a meeting (agenda, project brief, glossary).
+-- Fed to the summary, question and Jira prompts as reference material only.
+-- The source is the file the text was read from, if any; the token count is
+-- the rough estimate taken when it was stored.
+ALTER TABLE meetings ADD COLUMN background_context TEXT;
+ALTER TABLE meetings ADD COLUMN background_context_source TEXT;
+ALTER TABLE meetings ADD COLUMN background_context_tokens INTEGER;
@@ -0,0 +1,14 @@
+-- Clarifying questions generated for a meeting, kept so users can review
+-- which questions were raised and which got answered. The context is the
+-- transcript chunk that triggered the question.
+CREATE TABLE IF NOT EXISTS clarifying_questions (
+    id TEXT PRIMARY KEY,
+    meeting_id TEXT NOT NULL REFERENCES meetings(id) ON DELETE CASCADE,
+    text TEXT NOT NULL,
+    category TEXT NOT NULL,
+    context TEXT NOT NULL,
+    answered INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_clarifying_questions_meeting_id ON clarifying_questions(meeting_id, created_at);
@@ -0,0 +1,14 @@
+-- Summaries generated with several templates in one run. Each template's
+-- result is kept as its own version next to the meeting's summary process,
+-- failed templates with their error.
+CREATE TABLE IF NOT EXISTS summary_versions (
+    id TEXT PRIMARY KEY,
+    meeting_id TEXT NOT NULL REFERENCES meetings(id) ON DELETE CASCADE,
+    template_id TEXT NOT NULL,
+    status TEXT NOT NULL,
+    result TEXT,
+    error TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_summary_versions_meeting_id ON summary_versions(meeting_id, created_at);
@@ -0,0 +1,12 @@
+-- Jira task analysis of a meeting, run in the background like summaries so
+-- the result survives a window reload. run_id identifies the latest run;
+-- a superseded run's result is dropped when it doesn't match.
+CREATE TABLE IF NOT EXISTS jira_analysis_processes (
+    meeting_id TEXT PRIMARY KEY REFERENCES meetings(id) ON DELETE CASCADE,
+    run_id TEXT NOT NULL,
+    status TEXT NOT NULL,
+    result TEXT,
+    error TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
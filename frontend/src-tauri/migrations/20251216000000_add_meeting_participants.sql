@@ -0,0 +1,11 @@
+-- Attendees found in a meeting's transcript, one row per person after
+-- merging the ways they were written. mentions is a JSON array of those
+-- spellings; position keeps the order they were first mentioned in.
+CREATE TABLE IF NOT EXISTS meeting_participants (
+    meeting_id TEXT NOT NULL REFERENCES meetings(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    role TEXT,
+    mentions TEXT NOT NULL DEFAULT '[]',
+    PRIMARY KEY (meeting_id, position)
+);
@@ -0,0 +1,4 @@
+-- What summary metrics aggregate besides timing: the kind of error a failed
+-- run hit and how many validation warnings a completed summary was left with
+ALTER TABLE summary_processes ADD COLUMN error_kind TEXT;
+ALTER TABLE summary_processes ADD COLUMN warning_count INTEGER;
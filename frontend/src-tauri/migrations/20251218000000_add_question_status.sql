@@ -0,0 +1,4 @@
+-- Whether a clarifying question was shown right away, held back while popups
+-- were suppressed, released once suppression lifted, or dropped as a
+-- duplicate of another held back question.
+ALTER TABLE clarifying_questions ADD COLUMN status TEXT NOT NULL DEFAULT 'shown';
@@ -0,0 +1,5 @@
+-- When the retention policy deleted a meeting's recording, and its
+-- transcript if transcripts aren't only compressed. Playback is disabled for
+-- meetings with audio_deleted_at set; summaries are kept either way.
+ALTER TABLE meetings ADD COLUMN audio_deleted_at TEXT;
+ALTER TABLE meetings ADD COLUMN transcript_deleted_at TEXT;
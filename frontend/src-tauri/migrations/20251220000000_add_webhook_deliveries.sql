@@ -0,0 +1,17 @@
+-- Every attempt to deliver a webhook, kept for the delivery log and for
+-- redelivery. payload holds the event data without the signed envelope, so
+-- a redelivery gets a fresh id and timestamp.
+CREATE TABLE IF NOT EXISTS webhook_deliveries (
+    id TEXT PRIMARY KEY,
+    webhook_id TEXT NOT NULL,
+    event TEXT NOT NULL,
+    meeting_id TEXT,
+    payload TEXT NOT NULL,
+    status INTEGER,
+    latency_ms INTEGER NOT NULL,
+    error TEXT,
+    redelivery_of TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_webhook ON webhook_deliveries(webhook_id, created_at);
@@ -0,0 +1,13 @@
+-- Meeting timestamps were written both as RFC3339 UTC and as naive
+-- "YYYY-MM-DD HH:MM:SS" UTC text, which don't sort or compare together.
+-- Rewrite every value not already RFC3339 UTC to that form; SQLite converts
+-- values with another offset to UTC and leaves ones it can't parse alone.
+UPDATE meetings
+SET created_at = strftime('%Y-%m-%dT%H:%M:%f+00:00', created_at)
+WHERE created_at NOT LIKE '____-__-__T%+00:00'
+  AND strftime('%Y-%m-%dT%H:%M:%f+00:00', created_at) IS NOT NULL;
+
+UPDATE meetings
+SET updated_at = strftime('%Y-%m-%dT%H:%M:%f+00:00', updated_at)
+WHERE updated_at NOT LIKE '____-__-__T%+00:00'
+  AND strftime('%Y-%m-%dT%H:%M:%f+00:00', updated_at) IS NOT NULL;
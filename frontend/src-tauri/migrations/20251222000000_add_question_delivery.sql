@@ -0,0 +1,9 @@
+-- Delivery of clarifying questions to the meeting chat. The delivery id goes
+-- to the browser extension with the question and comes back with its
+-- confirmation, which moves the status from 'sending' to 'sent' or 'failed'
+-- and records the chat platform; without one in time it becomes 'unknown'.
+ALTER TABLE clarifying_questions ADD COLUMN delivery_id TEXT;
+ALTER TABLE clarifying_questions ADD COLUMN delivery_platform TEXT;
+ALTER TABLE clarifying_questions ADD COLUMN delivery_requested_at TEXT;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_clarifying_questions_delivery_id ON clarifying_questions(delivery_id);
@@ -0,0 +1,28 @@
+-- Named presets of summary settings picked together, e.g. a quick skim vs
+-- detailed minutes. NULL settings are left to the request or the global
+-- settings; webhook_ids is a JSON array, empty meaning every enabled webhook.
+-- Built-in presets can be edited but not deleted.
+CREATE TABLE IF NOT EXISTS pipelines (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    template_id TEXT,
+    model_provider TEXT,
+    model_name TEXT,
+    compact_transcript INTEGER,
+    provenance INTEGER,
+    citations INTEGER,
+    anonymize INTEGER,
+    refine INTEGER,
+    output_format TEXT,
+    webhook_ids TEXT NOT NULL DEFAULT '[]',
+    built_in INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+INSERT OR IGNORE INTO pipelines (id, name, template_id, compact_transcript, refine, output_format, built_in, created_at, updated_at)
+VALUES
+    ('quick', 'Quick', 'standard_meeting', 1, 0, 'skim', 1,
+     strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now'), strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+    ('detailed', 'Detailed', 'standard_meeting', 0, 1, 'full', 1,
+     strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now'), strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now'));
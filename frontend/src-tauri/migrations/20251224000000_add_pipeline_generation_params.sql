@@ -0,0 +1,4 @@
+-- Generation params of a named pipeline. They are stored so presets keep
+-- them, but no provider request sends them yet; NULL means provider default.
+ALTER TABLE pipelines ADD COLUMN temperature REAL;
+ALTER TABLE pipelines ADD COLUMN max_tokens INTEGER;
@@ -1,23 +1,84 @@
 use log::{debug as log_debug, error as log_error, info as log_info, warn as log_warn};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use tauri::{AppHandle, Runtime};
+use tauri::{AppHandle, Emitter, Runtime};
 use tauri_plugin_store::StoreExt;
 
 use crate::{
+    http::client_factory,
+    logging::log_preview,
+    api::jira_analysis::{run_jira_analysis, JIRA_ANALYSIS_COMPLETED_EVENT},
+    api::jira_description::{self, IssueContext},
+    api::response_limits::{load_response_limits, save_response_limits, ResponseLimits},
     database::{
-        models::MeetingModel,
+        models::{
+            JiraAnalysisProcess, MeetingModel, MeetingPreferences, SummaryFallback, SummarySearchResult,
+            TranscriptionProgress,
+        },
         repositories::{
-            meeting::MeetingsRepository, setting::SettingsRepository,
-            transcript::TranscriptsRepository,
+            jira_analysis::JiraAnalysisRepository,
+            meeting::MeetingsRepository,
+            question::QuestionsRepository,
+            setting::SettingsRepository,
+            Repositories,
+            summary::SummaryProcessesRepository,
+            transcript::{TranscriptsRepository, SEGMENT_BATCH_SIZE},
+            transcript_chunk::{TranscriptChunksRebuild, TranscriptChunksRepository},
+            transcription_status::{
+                TranscriptionStatusRepository, STATE_COMPLETED, STATE_IN_PROGRESS,
+            },
+        },
+        segment_order::order_segments,
+        transcripts_json::TranscriptsJsonCache,
+    },
+    export::transcript::{render_transcript, TranscriptTextOptions},
+    audio::{
+        meeting_artifacts::{open_path, resolve_artifact, MeetingArtifact},
+        recording_level::{measure_level, RecordingAudioLevel},
+        meeting_folders::{
+            meeting_folder_state, relocate_meeting_folder, repair_meeting_folders,
+            FolderRepairReport, MeetingFolderState,
+        },
+        recording_preferences::{get_default_recordings_folder, load_recording_preferences},
+        transcription::{
+            benchmark::{
+                benchmark_recording, decode_recording, run_benchmark, TranscriptionBenchmark,
+                WhisperBenchmarkEngine, WHISPER_SAMPLE_RATE,
+            },
+            validate_whisper_model, WhisperModelSpec,
         },
     },
+    people::load_resolved_action_items,
+    retention::policy::ensure_recording_kept,
     state::AppState,
+    summary::{
+        action_items::ActionItem,
+        context::{prepare_context, BACKGROUND_CONTEXT_TAG, FIXED_CONTEXT_TOKENS},
+        question_delivery::{
+            apply_confirmations, load_question_delivery_config, save_question_delivery_config,
+            DeliveryConfirmation, DeliveryStatusResponse, QuestionDelivery, QuestionDeliveryConfig,
+        },
+        LLMProvider,
+    },
+    timezone::{parse_date, user_timezone},
 };
 
 // Hardcoded server URL
 const APP_SERVER_URL: &str = "http://localhost:5167";
 
+/// Prefix of the error every Jira command returns when the Python backend is
+/// down, so the UI can tell it apart from Jira auth/configuration errors
+pub const JIRA_BACKEND_UNAVAILABLE: &str = "Jira backend unavailable";
+
+/// How long `api_jira_health` waits for the backend
+const JIRA_HEALTH_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// How long other Jira requests wait for the backend
+const JIRA_REQUEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// The analysis runs an LLM over the whole meeting, so it gets far longer
+const JIRA_ANALYSIS_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(600);
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ApiResponse<T> {
     pub success: bool,
@@ -29,6 +90,27 @@ pub struct ApiResponse<T> {
 pub struct Meeting {
     pub id: String,
     pub title: String,
+    /// RFC3339 in UTC
+    pub created_at: String,
+    pub updated_at: String,
+    /// Day the meeting was created on in the user's time zone, YYYY-MM-DD
+    pub local_date: String,
+}
+
+/// Meeting list entry enriched with the data the list view renders per row
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MeetingOverview {
+    pub id: String,
+    pub title: String,
+    pub created_at: String,
+    pub updated_at: String,
+    /// Recording duration in seconds (latest transcript audio_end_time)
+    pub duration: Option<f64>,
+    pub segment_count: i64,
+    /// Latest summary status (pending/processing/completed/failed), None if never generated
+    pub summary_status: Option<String>,
+    /// Short excerpt of the summary's "Summary" section
+    pub summary_snippet: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -75,6 +157,9 @@ pub struct ModelConfig {
     pub api_key: Option<String>,
     #[serde(rename = "ollamaEndpoint")]
     pub ollama_endpoint: Option<String>,
+    /// Tried in order when the summary provider is unavailable
+    #[serde(rename = "summaryFallbacks", default)]
+    pub summary_fallbacks: Vec<SummaryFallback>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -121,6 +206,10 @@ pub struct MeetingDetails {
     pub title: String,
     pub created_at: String,
     pub updated_at: String,
+    /// Day the meeting was created on in the user's time zone, filled in
+    /// by `api_get_meeting`
+    #[serde(default)]
+    pub local_date: Option<String>,
     pub transcripts: Vec<MeetingTranscript>,
 }
 
@@ -156,7 +245,7 @@ pub struct SaveTranscriptRequest {
     pub transcripts: Vec<TranscriptSegment>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TranscriptSegment {
     pub id: String,
     pub text: String,
@@ -208,6 +297,27 @@ pub struct JiraTaskCreate {
     pub start_date: Option<String>,
 }
 
+/// Creates one Jira task per Action Item of a meeting
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JiraBulkCreateRequest {
+    pub meeting_id: String,
+    pub project_key: String,
+    pub issue_type: String,
+    #[serde(default)]
+    pub labels: Option<Vec<String>>,
+}
+
+/// Outcome of one task of a bulk create
+#[derive(Debug, Serialize)]
+pub struct JiraBulkCreateResult {
+    pub task: String,
+    pub owner: String,
+    /// Jira account the task was assigned to; None if the owner didn't resolve
+    pub assignee: Option<String>,
+    pub result: Option<serde_json::Value>,
+    pub error: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct JiraAnalysisRequest {
     pub meeting_id: String,
@@ -219,6 +329,9 @@ pub struct JiraAnalysisRequest {
     pub text: Option<String>,
     // Project key for context-aware task generation (required)
     pub project_key: String,
+    // The meeting's background notes; filled in from the meeting when not given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background_context: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -251,6 +364,48 @@ pub struct JiraTransitionRequest {
     pub comment: Option<String>,
 }
 
+/// Which Jira features can currently be used
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct JiraFeatures {
+    /// Saving/reading the Jira connection settings
+    pub configure: bool,
+    /// Task suggestions from a transcript (works without a Jira connection)
+    pub analyze_tasks: bool,
+    pub create_tasks: bool,
+    /// Projects, issue types, search and issue details
+    pub browse_issues: bool,
+    /// Editing, commenting on and transitioning issues
+    pub update_issues: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct JiraHealth {
+    pub backend_reachable: bool,
+    pub configured: bool,
+    pub features: JiraFeatures,
+    pub error: Option<String>,
+}
+
+/// Failure of a request to the Python backend
+#[derive(Debug)]
+enum ApiRequestError {
+    /// The backend isn't running or a proxy in front of it is down
+    Unreachable(String),
+    /// The backend accepted the request but didn't answer in time
+    TimedOut(String),
+    Failed(String),
+}
+
+impl std::fmt::Display for ApiRequestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApiRequestError::Unreachable(msg)
+            | ApiRequestError::TimedOut(msg)
+            | ApiRequestError::Failed(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
 // Helper function to get auth token from store (optional)
 #[allow(dead_code)]
 async fn get_auth_token<R: Runtime>(app: &AppHandle<R>) -> Option<String> {
@@ -286,17 +441,85 @@ async fn get_server_address<R: Runtime>(_app: &AppHandle<R>) -> Result<String, S
 // Generic API call function with optional authentication
 async fn make_api_request<R: Runtime, T: for<'de> Deserialize<'de>>(
     app: &AppHandle<R>,
+    limits: &ResponseLimits,
     endpoint: &str,
     method: &str,
     body: Option<&str>,
     additional_headers: Option<HashMap<String, String>>,
     auth_token: Option<String>, // Pass auth token from frontend
 ) -> Result<T, String> {
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(10))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
     let server_url = get_server_address(app).await?;
+    send_api_request(
+        &server_url,
+        endpoint,
+        method,
+        body,
+        additional_headers,
+        auth_token,
+        std::time::Duration::from_secs(10),
+        limits,
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Like `make_api_request`, but a backend that can't be reached is reported
+/// with the `JIRA_BACKEND_UNAVAILABLE` prefix
+async fn make_jira_request<R: Runtime, T: for<'de> Deserialize<'de>>(
+    app: &AppHandle<R>,
+    limits: &ResponseLimits,
+    endpoint: &str,
+    method: &str,
+    body: Option<&str>,
+    auth_token: Option<String>,
+) -> Result<T, String> {
+    let server_url = get_server_address(app).await?;
+    send_jira_request(
+        &server_url,
+        endpoint,
+        method,
+        body,
+        auth_token,
+        JIRA_REQUEST_TIMEOUT,
+        limits,
+    )
+    .await
+}
+
+async fn send_jira_request<T: for<'de> Deserialize<'de>>(
+    server_url: &str,
+    endpoint: &str,
+    method: &str,
+    body: Option<&str>,
+    auth_token: Option<String>,
+    timeout: std::time::Duration,
+    limits: &ResponseLimits,
+) -> Result<T, String> {
+    send_api_request(
+        server_url, endpoint, method, body, None, auth_token, timeout, limits,
+    )
+    .await
+    .map_err(|e| match e {
+        ApiRequestError::Unreachable(msg) => format!("{}: {}", JIRA_BACKEND_UNAVAILABLE, msg),
+        other => other.to_string(),
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn send_api_request<T: for<'de> Deserialize<'de>>(
+    server_url: &str,
+    endpoint: &str,
+    method: &str,
+    body: Option<&str>,
+    additional_headers: Option<HashMap<String, String>>,
+    auth_token: Option<String>,
+    timeout: std::time::Duration,
+    limits: &ResponseLimits,
+) -> Result<T, ApiRequestError> {
+    let client = client_factory::builder()
+        .timeout(timeout)
+        .build()
+        .map_err(|e| ApiRequestError::Failed(format!("Failed to create HTTP client: {}", e)))?;
 
     let url = format!("{}{}", server_url, endpoint);
     log_info!("Making {} request to: {}", method, url);
@@ -306,7 +529,12 @@ async fn make_api_request<R: Runtime, T: for<'de> Deserialize<'de>>(
         "POST" => client.post(&url),
         "PUT" => client.put(&url),
         "DELETE" => client.delete(&url),
-        _ => return Err(format!("Unsupported HTTP method: {}", method)),
+        _ => {
+            return Err(ApiRequestError::Failed(format!(
+                "Unsupported HTTP method: {}",
+                method
+            )))
+        }
     };
 
     // Add authorization header if auth token is provided
@@ -334,54 +562,182 @@ async fn make_api_request<R: Runtime, T: for<'de> Deserialize<'de>>(
     let response = request.send().await.map_err(|e| {
         let error_msg = format!("Request failed: {}", e);
         log_error!("{}", error_msg);
-        error_msg
+        if e.is_connect() {
+            ApiRequestError::Unreachable(error_msg)
+        } else if e.is_timeout() {
+            ApiRequestError::TimedOut(error_msg)
+        } else {
+            ApiRequestError::Failed(error_msg)
+        }
     })?;
 
     let status = response.status();
     log_info!("Response status: {}", status);
 
     if !status.is_success() {
-        let error_text = response
-            .text()
+        let error_text = read_response_body(response, limits)
             .await
             .unwrap_or_else(|_| "Unknown error".to_string());
         let error_msg = format!("HTTP {}: {}", status, error_text);
         log_error!("{}", error_msg);
-        return Err(error_msg);
+        // Gateway errors mean a proxy answered but the backend behind it didn't
+        return Err(match status.as_u16() {
+            502 | 503 | 504 => ApiRequestError::Unreachable(error_msg),
+            _ => ApiRequestError::Failed(error_msg),
+        });
     }
 
-    let response_text = response.text().await.map_err(|e| {
-        let error_msg = format!("Failed to read response: {}", e);
-        log_error!("{}", error_msg);
-        error_msg
+    let response_text = read_response_body(response, limits).await.map_err(|e| {
+        log_error!("{}", e);
+        e
     })?;
 
-    // Safely truncate response for logging, respecting UTF-8 character boundaries
-    let truncated = response_text.chars().take(200).collect::<String>();
-    log_info!("Response body: {}", truncated);
+    log_info!("Response body: {}", log_preview(&response_text));
 
     serde_json::from_str(&response_text).map_err(|e| {
         let error_msg = format!("Failed to parse JSON: {}", e);
         log_error!("{}", error_msg);
-        error_msg
+        ApiRequestError::Failed(error_msg)
     })
 }
 
+/// Reads the body chunk by chunk, giving up on a stalled read or a body
+/// larger than `limits` allow
+async fn read_response_body(
+    mut response: reqwest::Response,
+    limits: &ResponseLimits,
+) -> Result<String, ApiRequestError> {
+    let too_large = || {
+        ApiRequestError::Failed(format!(
+            "Response too large: the backend sent more than the {} byte limit",
+            limits.max_response_bytes
+        ))
+    };
+    // A declared length over the cap fails before anything is read
+    if response
+        .content_length()
+        .is_some_and(|length| length > limits.max_response_bytes)
+    {
+        return Err(too_large());
+    }
+
+    let mut body: Vec<u8> = Vec::new();
+    loop {
+        let chunk = tokio::time::timeout(limits.read_timeout(), response.chunk())
+            .await
+            .map_err(|_| {
+                ApiRequestError::TimedOut(format!(
+                    "Failed to read response: no data for {} seconds",
+                    limits.read_timeout_secs
+                ))
+            })?
+            .map_err(|e| {
+                let error_msg = format!("Failed to read response: {}", e);
+                if e.is_timeout() {
+                    ApiRequestError::TimedOut(error_msg)
+                } else {
+                    ApiRequestError::Failed(error_msg)
+                }
+            })?;
+        let Some(chunk) = chunk else {
+            break;
+        };
+        if (body.len() + chunk.len()) as u64 > limits.max_response_bytes {
+            return Err(too_large());
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
+/// Asks the backend for the Jira config and derives which features work
+async fn check_jira_health(
+    server_url: &str,
+    auth_token: Option<String>,
+    limits: &ResponseLimits,
+) -> JiraHealth {
+    let response = send_api_request::<serde_json::Value>(
+        server_url,
+        "/get-jira-config",
+        "GET",
+        None,
+        None,
+        auth_token,
+        JIRA_HEALTH_TIMEOUT,
+        limits,
+    )
+    .await;
+
+    match response {
+        Ok(config) => {
+            let configured = config
+                .get("url")
+                .and_then(|url| url.as_str())
+                .map_or(false, |url| !url.trim().is_empty());
+            JiraHealth {
+                backend_reachable: true,
+                configured,
+                features: JiraFeatures {
+                    configure: true,
+                    analyze_tasks: true,
+                    create_tasks: configured,
+                    browse_issues: configured,
+                    update_issues: configured,
+                },
+                error: None,
+            }
+        }
+        // Unlike regular requests, a timeout here counts as down: the config
+        // lookup is cheap, so a backend that can't answer it in time can't
+        // serve the other Jira features either
+        Err(ApiRequestError::Unreachable(msg)) | Err(ApiRequestError::TimedOut(msg)) => JiraHealth {
+            backend_reachable: false,
+            configured: false,
+            features: JiraFeatures::default(),
+            error: Some(format!("{}: {}", JIRA_BACKEND_UNAVAILABLE, msg)),
+        },
+        Err(ApiRequestError::Failed(msg)) => JiraHealth {
+            backend_reachable: true,
+            configured: false,
+            features: JiraFeatures {
+                configure: true,
+                analyze_tasks: true,
+                ..Default::default()
+            },
+            error: Some(msg),
+        },
+    }
+}
+
 // API Commands for Tauri
 
+/// Meetings newest first, optionally only those created from `from_date`
+/// through `to_date` (YYYY-MM-DD, both inclusive) in the user's time zone
 #[tauri::command]
 pub async fn api_get_meetings<R: Runtime>(
-    _app: AppHandle<R>,
+    app: AppHandle<R>,
     state: tauri::State<'_, AppState>,
     auth_token: Option<String>,
+    from_date: Option<String>,
+    to_date: Option<String>,
 ) -> Result<Vec<Meeting>, String> {
     log_info!(
         "api_get_meetings called with auth_token(native) : {}",
         auth_token.is_some()
     );
+    let timezone = user_timezone(&app);
+    let from_date = from_date.as_deref().map(parse_date).transpose()?;
+    let to_date = to_date.as_deref().map(parse_date).transpose()?;
+    if let (Some(from), Some(to)) = (from_date, to_date) {
+        if from > to {
+            return Err("The start date must not be after the end date".to_string());
+        }
+    }
+    let (from, to) = timezone.day_range(from_date, to_date);
+
     let pool = state.db_manager.pool();
     let meetings: Result<Vec<MeetingModel>, sqlx::Error> =
-        MeetingsRepository::get_meetings(pool).await;
+        MeetingsRepository::get_meetings_created_between(pool, from, to).await;
 
     match meetings {
         Ok(meeting_models) => {
@@ -390,8 +746,11 @@ pub async fn api_get_meetings<R: Runtime>(
             let result: Vec<Meeting> = meeting_models
                 .into_iter()
                 .map(|m| Meeting {
+                    local_date: timezone.format_local_date(m.created_at.0),
                     id: m.id,
                     title: m.title,
+                    created_at: m.created_at.0.to_rfc3339(),
+                    updated_at: m.updated_at.0.to_rfc3339(),
                 })
                 .collect();
             Ok(result)
@@ -403,6 +762,32 @@ pub async fn api_get_meetings<R: Runtime>(
     }
 }
 
+/// Returns the meetings list enriched with duration, segment count, latest
+/// summary status and a summary snippet, so the list view needs no per-row calls
+#[tauri::command]
+pub async fn api_get_meetings_overview<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    auth_token: Option<String>,
+) -> Result<Vec<MeetingOverview>, String> {
+    log_info!(
+        "api_get_meetings_overview called with auth_token(native) : {}",
+        auth_token.is_some()
+    );
+    let pool = state.db_manager.pool();
+
+    match MeetingsRepository::get_meetings_overview(pool).await {
+        Ok(overview) => {
+            log_info!("Successfully got overview for {} meetings", overview.len());
+            Ok(overview)
+        }
+        Err(e) => {
+            log_error!("Error getting meetings overview: {}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
 #[tauri::command]
 pub async fn api_search_transcripts<R: Runtime>(
     _app: AppHandle<R>,
@@ -433,6 +818,29 @@ pub async fn api_search_transcripts<R: Runtime>(
     }
 }
 
+/// Full-text search over meeting summaries rather than raw transcripts
+///
+/// All words must appear in a summary; matches come back best first with a
+/// snippet where each match is wrapped in `<mark>` tags.
+#[tauri::command]
+pub async fn api_search_summaries(
+    state: tauri::State<'_, AppState>,
+    query: String,
+) -> Result<Vec<SummarySearchResult>, String> {
+    log_info!("api_search_summaries called with query: '{}'", query);
+
+    match SummaryProcessesRepository::search_summaries(state.db_manager.pool(), &query).await {
+        Ok(results) => {
+            log_info!("Summary search found {} meeting(s)", results.len());
+            Ok(results)
+        }
+        Err(e) => {
+            log_error!("Error searching summaries for query '{}': {}", query, e);
+            Err(format!("Failed to search summaries: {}", e))
+        }
+    }
+}
+
 #[tauri::command]
 pub async fn api_get_profile<R: Runtime>(
     app: AppHandle<R>,
@@ -449,7 +857,7 @@ pub async fn api_get_profile<R: Runtime>(
     let profile_request = ProfileRequest { email, license_key };
     let body = serde_json::to_string(&profile_request).map_err(|e| e.to_string())?;
 
-    make_api_request::<R, Profile>(&app, "/get-profile", "POST", Some(&body), None, auth_token)
+    make_api_request::<R, Profile>(&app, &load_response_limits(&app), "/get-profile", "POST", Some(&body), None, auth_token)
         .await
 }
 
@@ -471,6 +879,7 @@ pub async fn api_save_profile<R: Runtime>(
 
     make_api_request::<R, serde_json::Value>(
         &app,
+        &load_response_limits(&app),
         "/save-profile",
         "POST",
         Some(&body),
@@ -505,6 +914,7 @@ pub async fn api_update_profile<R: Runtime>(
 
     make_api_request::<R, serde_json::Value>(
         &app,
+        &load_response_limits(&app),
         "/update-profile",
         "POST",
         Some(&body),
@@ -532,6 +942,9 @@ pub async fn api_get_model_config<R: Runtime>(
                 &config.whisper_model,
                 &config.ollama_endpoint
             );
+            let summary_fallbacks = SettingsRepository::get_summary_fallbacks(pool)
+                .await
+                .map_err(|e| e.to_string())?;
             match SettingsRepository::get_api_key(pool, &config.provider).await {
                 Ok(api_key) => {
                     log_info!("Successfully retrieved model config and API key.");
@@ -541,6 +954,7 @@ pub async fn api_get_model_config<R: Runtime>(
                         whisper_model: config.whisper_model,
                         api_key,
                         ollama_endpoint: config.ollama_endpoint,
+                        summary_fallbacks,
                     }))
                 }
                 Err(e) => {
@@ -573,6 +987,7 @@ pub async fn api_save_model_config<R: Runtime>(
     whisper_model: String,
     api_key: Option<String>,
     ollama_endpoint: Option<String>,
+    summary_fallbacks: Option<Vec<SummaryFallback>>,
     _auth_token: Option<String>,
 ) -> Result<serde_json::Value, String> {
     log_info!(
@@ -582,6 +997,10 @@ pub async fn api_save_model_config<R: Runtime>(
         &whisper_model,
         &ollama_endpoint
     );
+    let whisper_warning = validate_whisper_model(&whisper_model);
+    if let Some(warning) = &whisper_warning {
+        log_warn!("⚠️ {}", warning);
+    }
     let pool = state.db_manager.pool();
 
     if let Err(e) = SettingsRepository::save_model_config(
@@ -597,6 +1016,21 @@ pub async fn api_save_model_config<R: Runtime>(
         return Err(e.to_string());
     }
 
+    // None leaves the saved fallbacks as they are; an empty list clears them
+    if let Some(fallbacks) = &summary_fallbacks {
+        for fallback in fallbacks {
+            LLMProvider::from_str(&fallback.provider)?;
+            if fallback.model.trim().is_empty() {
+                return Err(format!("Fallback for {} needs a model", fallback.provider));
+            }
+        }
+        if let Err(e) = SettingsRepository::save_summary_fallbacks(pool, fallbacks).await {
+            log_error!("❌ Failed to save summary fallbacks: {}", e);
+            return Err(e.to_string());
+        }
+        log_info!("💾 Saved {} summary fallback(s)", fallbacks.len());
+    }
+
     // Clone api_key for use in sync payload (needed because we use it below)
     let api_key_for_sync = api_key.clone();
     
@@ -621,6 +1055,7 @@ pub async fn api_save_model_config<R: Runtime>(
     
     match make_api_request::<R, serde_json::Value>(
         &_app,
+        &load_response_limits(&_app),
         "/save-model-config",
         "POST",
         Some(&sync_payload.to_string()),
@@ -638,9 +1073,11 @@ pub async fn api_save_model_config<R: Runtime>(
     }
 
     log_info!("✅ Successfully saved model configuration to database");
-    Ok(
-        serde_json::json!({ "status": "success", "message": "Model configuration saved successfully" }),
-    )
+    Ok(serde_json::json!({
+        "status": "success",
+        "message": "Model configuration saved successfully",
+        "warning": whisper_warning,
+    }))
 }
 
 #[tauri::command]
@@ -732,6 +1169,14 @@ pub async fn api_save_transcript_config<R: Runtime>(
         "api_save_transcript_config called (native) for provider '{}'",
         &provider
     );
+    let model_warning = if provider == "localWhisper" {
+        validate_whisper_model(&model)
+    } else {
+        None
+    };
+    if let Some(warning) = &model_warning {
+        log_warn!("⚠️ {}", warning);
+    }
     let pool = state.db_manager.pool();
 
     if let Err(e) = SettingsRepository::save_transcript_config(pool, &provider, &model).await {
@@ -751,9 +1196,22 @@ pub async fn api_save_transcript_config<R: Runtime>(
     }
 
     log_info!("Successfully saved transcript configuration.");
-    Ok(
-        serde_json::json!({ "status": "success", "message": "Transcript configuration saved successfully" }),
-    )
+    Ok(serde_json::json!({
+        "status": "success",
+        "message": "Transcript configuration saved successfully",
+        "warning": model_warning,
+    }))
+}
+
+/// Download size and memory needs of a known Whisper model
+#[tauri::command]
+pub async fn api_get_whisper_model_info(name: String) -> Result<WhisperModelSpec, String> {
+    crate::audio::transcription::find_whisper_model(name.trim())
+        .cloned()
+        .ok_or_else(|| {
+            validate_whisper_model(&name)
+                .unwrap_or_else(|| "No Whisper model name given".to_string())
+        })
 }
 
 #[tauri::command]
@@ -852,7 +1310,7 @@ pub async fn api_delete_meeting<R: Runtime>(
 
 #[tauri::command]
 pub async fn api_get_meeting<R: Runtime>(
-    _app: AppHandle<R>,
+    app: AppHandle<R>,
     meeting_id: String,
     state: tauri::State<'_, AppState>,
     auth_token: Option<String>,
@@ -865,9 +1323,14 @@ pub async fn api_get_meeting<R: Runtime>(
 
     let pool = state.db_manager.pool();
 
-    match MeetingsRepository::get_meeting(pool, &meeting_id).await {
-        Ok(Some(meeting)) => {
+    match MeetingsRepository::get_meeting(pool, &meeting_id, &state.transcripts_json).await {
+        Ok(Some(mut meeting)) => {
             log_info!("Successfully retrieved meeting {}", meeting_id);
+            meeting.local_date = chrono::DateTime::parse_from_rfc3339(&meeting.created_at)
+                .ok()
+                .map(|created_at| {
+                    user_timezone(&app).format_local_date(created_at.with_timezone(&chrono::Utc))
+                });
             Ok(meeting)
         }
         Ok(None) => {
@@ -881,6 +1344,59 @@ pub async fn api_get_meeting<R: Runtime>(
     }
 }
 
+/// The meeting's stored transcript segments, or those of the
+/// `transcripts.json` in its recording folder when none are stored yet
+async fn meeting_transcript_segments(
+    pool: &sqlx::SqlitePool,
+    repos: &Repositories,
+    transcripts_json: &TranscriptsJsonCache,
+    meeting_id: &str,
+) -> Result<Vec<TranscriptSegment>, String> {
+    let segments = repos
+        .transcripts
+        .get_transcript_segments(meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load transcript: {}", e))?;
+    if !segments.is_empty() {
+        return Ok(segments);
+    }
+    let folder_path = MeetingsRepository::get_folder_path(pool, meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load transcript: {}", e))?;
+    Ok(folder_path
+        .map(|folder_path| transcripts_json.segments(meeting_id, &folder_path))
+        .unwrap_or_default())
+}
+
+/// The meeting's transcript rendered as one document, e.g. for copying
+///
+/// Options default to one line per segment with timestamps and speakers,
+/// the same layout summaries are generated from. Meetings whose segments
+/// were never imported are read from their `transcripts.json`.
+#[tauri::command]
+pub async fn api_get_meeting_transcript_text(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    options: Option<TranscriptTextOptions>,
+) -> Result<String, String> {
+    log_info!("api_get_meeting_transcript_text called for meeting_id: {}", meeting_id);
+    let options = options.unwrap_or_default();
+    if options
+        .merge_gap_seconds
+        .is_some_and(|gap| gap.is_nan() || gap < 0.0)
+    {
+        return Err("Merge gap must be zero or more seconds".to_string());
+    }
+    let segments = meeting_transcript_segments(
+        state.db_manager.pool(),
+        &state.repos,
+        &state.transcripts_json,
+        &meeting_id,
+    )
+    .await?;
+    Ok(render_transcript(&segments, &options))
+}
+
 #[tauri::command]
 pub async fn api_save_meeting_title<R: Runtime>(
     _app: AppHandle<R>,
@@ -911,6 +1427,93 @@ pub async fn api_save_meeting_title<R: Runtime>(
     }
 }
 
+#[tauri::command]
+pub async fn api_get_meeting_preferences<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<MeetingPreferences, String> {
+    log_info!("api_get_meeting_preferences called for meeting_id: {}", meeting_id);
+    let pool = state.db_manager.pool();
+    match MeetingsRepository::get_meeting_preferences(pool, &meeting_id).await {
+        Ok(Some(preferences)) => Ok(preferences),
+        Ok(None) => Err(format!("Meeting not found: {}", meeting_id)),
+        Err(e) => {
+            log_error!("Error loading meeting preferences: {}", e);
+            Err(format!("Failed to load meeting preferences: {}", e))
+        }
+    }
+}
+
+/// Stores the template and model used by default when this meeting's summary
+/// is (re)generated. Passing None for a value clears it.
+#[tauri::command]
+pub async fn api_set_meeting_preferences<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    template_id: Option<String>,
+    model_provider: Option<String>,
+    model_name: Option<String>,
+) -> Result<serde_json::Value, String> {
+    log_info!(
+        "api_set_meeting_preferences called for meeting_id: {}, template: {:?}, provider: {:?}, model: {:?}",
+        meeting_id,
+        template_id,
+        model_provider,
+        model_name
+    );
+
+    let non_empty = |value: Option<String>| value.filter(|v| !v.trim().is_empty());
+    let preferences = MeetingPreferences {
+        template_id: non_empty(template_id),
+        model_provider: non_empty(model_provider),
+        model_name: non_empty(model_name),
+    };
+
+    if preferences.model_provider.is_some() != preferences.model_name.is_some() {
+        return Err("Model provider and model name must be set together".to_string());
+    }
+    if let Some(provider) = &preferences.model_provider {
+        crate::summary::LLMProvider::from_str(provider)?;
+    }
+    if let Some(template_id) = &preferences.template_id {
+        crate::summary::templates::get_template(template_id)?;
+    }
+
+    let pool = state.db_manager.pool();
+    match MeetingsRepository::set_meeting_preferences(pool, &meeting_id, &preferences).await {
+        Ok(true) => {
+            log_info!("Successfully saved meeting preferences");
+            Ok(serde_json::json!({"message": "Meeting preferences saved successfully"}))
+        }
+        Ok(false) => {
+            log_warn!("Meeting not found: {}", meeting_id);
+            Err(format!("Meeting not found: {}", meeting_id))
+        }
+        Err(e) => {
+            log_error!("Error saving meeting preferences: {}", e);
+            Err(format!("Failed to save meeting preferences: {}", e))
+        }
+    }
+}
+
+/// Reports whether the backend serving Jira is up and which Jira features can
+/// be used, so the UI can disable Jira actions up front
+#[tauri::command]
+pub async fn api_jira_health<R: Runtime>(
+    app: AppHandle<R>,
+    auth_token: Option<String>,
+) -> Result<JiraHealth, String> {
+    log_info!("api_jira_health called");
+    let server_url = get_server_address(&app).await?;
+    let health = check_jira_health(&server_url, auth_token, &load_response_limits(&app)).await;
+    if !health.backend_reachable {
+        log_warn!("Jira backend is not reachable: {:?}", health.error);
+    }
+    Ok(health)
+}
+
 #[tauri::command]
 pub async fn api_save_jira_config<R: Runtime>(
     app: AppHandle<R>,
@@ -919,7 +1522,7 @@ pub async fn api_save_jira_config<R: Runtime>(
 ) -> Result<serde_json::Value, String> {
     log_info!("api_save_jira_config called");
     let body = serde_json::to_string(&config).map_err(|e| e.to_string())?;
-    make_api_request::<R, serde_json::Value>(&app, "/save-jira-config", "POST", Some(&body), None, auth_token).await
+    make_jira_request::<R, serde_json::Value>(&app, &load_response_limits(&app), "/save-jira-config", "POST", Some(&body), auth_token).await
 }
 
 #[tauri::command]
@@ -928,7 +1531,7 @@ pub async fn api_get_jira_config<R: Runtime>(
     auth_token: Option<String>,
 ) -> Result<serde_json::Value, String> {
     log_info!("api_get_jira_config called");
-    make_api_request::<R, serde_json::Value>(&app, "/get-jira-config", "GET", None, None, auth_token).await
+    make_jira_request::<R, serde_json::Value>(&app, &load_response_limits(&app), "/get-jira-config", "GET", None, auth_token).await
 }
 
 #[tauri::command]
@@ -939,14 +1542,109 @@ pub async fn api_create_jira_task<R: Runtime>(
 ) -> Result<serde_json::Value, String> {
     log_info!("api_create_jira_task called");
     let body = serde_json::to_string(&task).map_err(|e| e.to_string())?;
-    make_api_request::<R, serde_json::Value>(&app, "/create-jira-task", "POST", Some(&body), None, auth_token).await
+    make_jira_request::<R, serde_json::Value>(&app, &load_response_limits(&app), "/create-jira-task", "POST", Some(&body), auth_token).await
+}
+
+/// Jira task for an action item, assigned to the owner's Jira account when
+/// the owner resolved to exactly one person
+fn action_item_task(
+    item: &ActionItem,
+    project_key: &str,
+    issue_type: &str,
+    labels: Option<Vec<String>>,
+) -> JiraTaskCreate {
+    // The backend proxy takes plain text descriptions
+    let description = jira_description::to_plain_text(&IssueContext::new(item));
+
+    JiraTaskCreate {
+        project_key: project_key.to_string(),
+        summary: item.task.clone(),
+        description,
+        issue_type: issue_type.to_string(),
+        assignee: item
+            .resolved_person()
+            .and_then(|person| person.jira_account_id.clone()),
+        labels,
+        duedate: None,
+        start_date: None,
+    }
+}
+
+/// Creates a Jira task for every Action Item of the meeting's summary, with
+/// owners resolved against the people directory
+#[tauri::command]
+pub async fn api_bulk_create_jira_tasks<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    request: JiraBulkCreateRequest,
+    auth_token: Option<String>,
+) -> Result<Vec<JiraBulkCreateResult>, String> {
+    log_info!(
+        "api_bulk_create_jira_tasks called for meeting_id: {}, project: {}",
+        request.meeting_id,
+        request.project_key
+    );
+    let items = load_resolved_action_items(state.db_manager.pool(), &request.meeting_id).await?;
+    let limits = load_response_limits(&app);
+
+    let mut results = Vec::with_capacity(items.len());
+    for item in &items {
+        let task = action_item_task(
+            item,
+            &request.project_key,
+            &request.issue_type,
+            request.labels.clone(),
+        );
+        let assignee = task.assignee.clone();
+        let body = serde_json::to_string(&task).map_err(|e| e.to_string())?;
+        let outcome = make_jira_request::<R, serde_json::Value>(
+            &app,
+            &limits,
+            "/create-jira-task",
+            "POST",
+            Some(&body),
+            auth_token.clone(),
+        )
+        .await;
+
+        // No point trying the remaining tasks against a backend that is down
+        if let Err(e) = &outcome {
+            if e.starts_with(JIRA_BACKEND_UNAVAILABLE) {
+                return Err(e.clone());
+            }
+        }
+        let (result, error) = match outcome {
+            Ok(value) => (Some(value), None),
+            Err(e) => (None, Some(e)),
+        };
+        results.push(JiraBulkCreateResult {
+            task: item.task.clone(),
+            owner: item.owner.clone(),
+            assignee,
+            result,
+            error,
+        });
+    }
+
+    log_info!(
+        "✅ Bulk Jira create for {}: {}/{} tasks created",
+        request.meeting_id,
+        results.iter().filter(|r| r.error.is_none()).count(),
+        results.len()
+    );
+    Ok(results)
 }
 
+/// Starts the Jira task analysis of a meeting in the background
+///
+/// Returns as soon as the run is recorded; poll `api_get_jira_analysis_status`
+/// or listen for `jira-analysis-completed`. Starting another analysis of the
+/// same meeting supersedes the one still running.
 #[tauri::command]
 pub async fn api_analyze_jira_tasks<R: Runtime>(
     app: AppHandle<R>,
     state: tauri::State<'_, AppState>,
-    request: JiraAnalysisRequest,
+    mut request: JiraAnalysisRequest,
     auth_token: Option<String>,
 ) -> Result<serde_json::Value, String> {
     let has_text = request
@@ -965,6 +1663,8 @@ pub async fn api_analyze_jira_tasks<R: Runtime>(
         text_len
     );
 
+    let limits = load_response_limits(&app);
+
     // If using Gemini, ensure API key is synced to backend
     if request.model.to_lowercase() == "gemini" {
         let pool = state.db_manager.pool();
@@ -983,6 +1683,7 @@ pub async fn api_analyze_jira_tasks<R: Runtime>(
                     // Try to sync (non-blocking - don't fail if it doesn't work)
                     let _ = make_api_request::<R, serde_json::Value>(
                         &app,
+                        &limits,
                         "/save-model-config",
                         "POST",
                         Some(&sync_payload.to_string()),
@@ -994,8 +1695,61 @@ pub async fn api_analyze_jira_tasks<R: Runtime>(
         }
     }
 
+    if request.background_context.is_none() {
+        request.background_context =
+            background_context_for_backend(&state, &request.meeting_id).await;
+    }
+
     let body = serde_json::to_string(&request).map_err(|e| e.to_string())?;
-    make_api_request::<R, serde_json::Value>(&app, "/analyze-jira-tasks", "POST", Some(&body), None, auth_token).await
+    let server_url = get_server_address(&app).await?;
+    let pool = state.db_manager.pool().clone();
+    let meeting_id = request.meeting_id;
+    let run_id = JiraAnalysisRepository::start(&pool, &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to start Jira analysis: {}", e))?;
+
+    let task_meeting_id = meeting_id.clone();
+    tauri::async_runtime::spawn(async move {
+        let analysis = send_jira_request::<serde_json::Value>(
+            &server_url,
+            "/analyze-jira-tasks",
+            "POST",
+            Some(&body),
+            auth_token,
+            JIRA_ANALYSIS_TIMEOUT,
+            &limits,
+        );
+        match run_jira_analysis(&pool, &task_meeting_id, &run_id, analysis).await {
+            Ok(Some(status)) => {
+                let payload = serde_json::json!({
+                    "meeting_id": task_meeting_id,
+                    "status": status,
+                });
+                if let Err(e) = app.emit(JIRA_ANALYSIS_COMPLETED_EVENT, payload) {
+                    log_warn!("Failed to emit {}: {}", JIRA_ANALYSIS_COMPLETED_EVENT, e);
+                }
+            }
+            Ok(None) => {}
+            Err(e) => log_error!("❌ Failed to store Jira analysis for {}: {}", task_meeting_id, e),
+        }
+    });
+
+    log_info!("🚀 Jira analysis started for meeting_id: {}", meeting_id);
+    Ok(serde_json::json!({
+        "message": "Jira analysis started",
+        "process_id": meeting_id,
+    }))
+}
+
+/// Status of the meeting's latest Jira analysis; None if it was never analyzed
+#[tauri::command]
+pub async fn api_get_jira_analysis_status(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Option<JiraAnalysisProcess>, String> {
+    JiraAnalysisRepository::get(state.db_manager.pool(), &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load Jira analysis status: {}", e))
 }
 
 #[tauri::command]
@@ -1004,7 +1758,7 @@ pub async fn api_get_jira_projects<R: Runtime>(
     auth_token: Option<String>,
 ) -> Result<serde_json::Value, String> {
     log_info!("api_get_jira_projects called");
-    make_api_request::<R, serde_json::Value>(&app, "/get-jira-projects", "GET", None, None, auth_token).await
+    make_jira_request::<R, serde_json::Value>(&app, &load_response_limits(&app), "/get-jira-projects", "GET", None, auth_token).await
 }
 
 #[tauri::command]
@@ -1015,7 +1769,7 @@ pub async fn api_get_jira_issue_types<R: Runtime>(
 ) -> Result<serde_json::Value, String> {
     log_info!("api_get_jira_issue_types called for project: {}", project_key);
     let endpoint = format!("/get-jira-issue-types/{}", project_key);
-    make_api_request::<R, serde_json::Value>(&app, &endpoint, "GET", None, None, auth_token).await
+    make_jira_request::<R, serde_json::Value>(&app, &load_response_limits(&app), &endpoint, "GET", None, auth_token).await
 }
 
 #[tauri::command]
@@ -1026,7 +1780,7 @@ pub async fn api_get_jira_project_context<R: Runtime>(
 ) -> Result<serde_json::Value, String> {
     log_info!("api_get_jira_project_context called for project: {}", project_key);
     let endpoint = format!("/get-jira-project-context/{}", project_key);
-    make_api_request::<R, serde_json::Value>(&app, &endpoint, "GET", None, None, auth_token).await
+    make_jira_request::<R, serde_json::Value>(&app, &load_response_limits(&app), &endpoint, "GET", None, auth_token).await
 }
 
 #[tauri::command]
@@ -1039,7 +1793,7 @@ pub async fn api_search_jira_issues<R: Runtime>(
     log_info!("api_search_jira_issues called with JQL: {}", jql);
     let max = max_results.unwrap_or(50);
     let endpoint = format!("/search-jira-issues?jql={}&max_results={}", urlencoding::encode(&jql), max);
-    make_api_request::<R, serde_json::Value>(&app, &endpoint, "GET", None, None, auth_token).await
+    make_jira_request::<R, serde_json::Value>(&app, &load_response_limits(&app), &endpoint, "GET", None, auth_token).await
 }
 
 #[tauri::command]
@@ -1050,7 +1804,7 @@ pub async fn api_get_jira_issue<R: Runtime>(
 ) -> Result<serde_json::Value, String> {
     log_info!("api_get_jira_issue called for issue: {}", issue_key);
     let endpoint = format!("/get-jira-issue/{}", issue_key);
-    make_api_request::<R, serde_json::Value>(&app, &endpoint, "GET", None, None, auth_token).await
+    make_jira_request::<R, serde_json::Value>(&app, &load_response_limits(&app), &endpoint, "GET", None, auth_token).await
 }
 
 #[tauri::command]
@@ -1063,7 +1817,7 @@ pub async fn api_update_jira_issue<R: Runtime>(
     log_info!("api_update_jira_issue called for issue: {}", issue_key);
     let body = serde_json::to_string(&update).map_err(|e| e.to_string())?;
     let endpoint = format!("/update-jira-issue/{}", issue_key);
-    make_api_request::<R, serde_json::Value>(&app, &endpoint, "POST", Some(&body), None, auth_token).await
+    make_jira_request::<R, serde_json::Value>(&app, &load_response_limits(&app), &endpoint, "POST", Some(&body), auth_token).await
 }
 
 #[tauri::command]
@@ -1076,7 +1830,7 @@ pub async fn api_add_jira_comment<R: Runtime>(
     log_info!("api_add_jira_comment called for issue: {}", issue_key);
     let body = serde_json::to_string(&comment).map_err(|e| e.to_string())?;
     let endpoint = format!("/add-jira-comment/{}", issue_key);
-    make_api_request::<R, serde_json::Value>(&app, &endpoint, "POST", Some(&body), None, auth_token).await
+    make_jira_request::<R, serde_json::Value>(&app, &load_response_limits(&app), &endpoint, "POST", Some(&body), auth_token).await
 }
 
 #[tauri::command]
@@ -1087,7 +1841,7 @@ pub async fn api_get_jira_transitions<R: Runtime>(
 ) -> Result<serde_json::Value, String> {
     log_info!("api_get_jira_transitions called for issue: {}", issue_key);
     let endpoint = format!("/get-jira-transitions/{}", issue_key);
-    make_api_request::<R, serde_json::Value>(&app, &endpoint, "GET", None, None, auth_token).await
+    make_jira_request::<R, serde_json::Value>(&app, &load_response_limits(&app), &endpoint, "GET", None, auth_token).await
 }
 
 #[tauri::command]
@@ -1100,7 +1854,7 @@ pub async fn api_transition_jira_issue<R: Runtime>(
     log_info!("api_transition_jira_issue called for issue: {} with transition_id: {}", issue_key, transition.transition_id);
     let body = serde_json::to_string(&transition).map_err(|e| e.to_string())?;
     let endpoint = format!("/transition-jira-issue/{}", issue_key);
-    make_api_request::<R, serde_json::Value>(&app, &endpoint, "POST", Some(&body), None, auth_token).await
+    make_jira_request::<R, serde_json::Value>(&app, &load_response_limits(&app), &endpoint, "POST", Some(&body), auth_token).await
 }
 
 #[tauri::command]
@@ -1111,13 +1865,17 @@ pub async fn api_save_transcript<R: Runtime>(
     transcripts: Vec<serde_json::Value>,
     folder_path: Option<String>,
     auth_token: Option<String>,
+    resume_meeting_id: Option<String>,
+    live_session_id: Option<String>,
 ) -> Result<serde_json::Value, String> {
     log_info!(
-        "api_save_transcript called for meeting: {}, transcripts: {}, folder_path: {:?}, auth_token: {}",
+        "api_save_transcript called for meeting: {}, transcripts: {}, folder_path: {:?}, auth_token: {}, resume: {:?}, live session: {:?}",
         meeting_title,
         transcripts.len(),
         folder_path,
-        auth_token.is_some()
+        auth_token.is_some(),
+        resume_meeting_id,
+        live_session_id
     );
 
     // Log first transcript for debugging
@@ -1129,7 +1887,7 @@ pub async fn api_save_transcript<R: Runtime>(
     }
 
     // Convert serde_json::Value to TranscriptSegment
-    let transcripts_to_save: Vec<TranscriptSegment> = transcripts
+    let mut transcripts_to_save: Vec<TranscriptSegment> = transcripts
         .into_iter()
         .map(serde_json::from_value)
         .collect::<Result<Vec<_>, _>>()
@@ -1141,45 +1899,215 @@ pub async fn api_save_transcript<R: Runtime>(
     // Log parsed segments count and first segment details
     if let Some(first_seg) = transcripts_to_save.first() {
         log_debug!("First parsed segment: text='{}', audio_start_time={:?}, audio_end_time={:?}, duration={:?}",
-                   first_seg.text.chars().take(50).collect::<String>(),
+                   log_preview(&first_seg.text),
                    first_seg.audio_start_time,
                    first_seg.audio_end_time,
                    first_seg.duration);
     }
 
+    // Live capture can hand segments over out of order; a resumed save gets
+    // the same list and so the same order
+    let segment_order = order_segments(&mut transcripts_to_save);
+    if segment_order.anomaly {
+        log_warn!(
+            "⚠️ Segments of meeting '{}' arrived out of order: {} moved, up to {:.1}s back",
+            meeting_title,
+            segment_order.moved,
+            segment_order.max_backstep_secs
+        );
+    } else if segment_order.moved > 0 {
+        log_info!("Reordered {} segments of meeting '{}'", segment_order.moved, meeting_title);
+    }
+
     let pool = state.db_manager.pool();
+    // Segment saves are the hottest write path; status polling reads alongside
+    let _write_guard = state.db_manager.write_lock().await;
+
+    // Resuming continues a save that failed partway; otherwise a new meeting is created
+    let meeting_id = match resume_meeting_id {
+        Some(meeting_id) => {
+            match TranscriptionStatusRepository::get(pool, &meeting_id).await {
+                Ok(Some(progress)) if progress.state != STATE_COMPLETED => meeting_id,
+                Ok(Some(_)) => {
+                    return Err(format!("Transcript for meeting {} is already saved", meeting_id))
+                }
+                Ok(None) => {
+                    return Err(format!("No transcript save to resume for meeting {}", meeting_id))
+                }
+                Err(e) => return Err(format!("Failed to load transcription status: {}", e)),
+            }
+        }
+        None => TranscriptsRepository::create_meeting(
+            pool,
+            &meeting_title,
+            transcripts_to_save.len(),
+            folder_path,
+        )
+        .await
+        .map_err(|e| {
+            log_error!("Error creating meeting '{}': {}", meeting_title, e);
+            format!("Failed to save transcript: {}", e)
+        })?,
+    };
 
-    // Now, call the repository with the correctly typed data.
-    match TranscriptsRepository::save_transcript(
+    match TranscriptsRepository::save_segments(
         pool,
-        &meeting_title,
+        &meeting_id,
         &transcripts_to_save,
-        folder_path,
+        SEGMENT_BATCH_SIZE,
     )
     .await
     {
-        Ok(meeting_id) => {
+        Ok(saved) => {
             log_info!(
-                "Successfully saved transcript and created meeting with id: {}",
-                meeting_id
+                "Successfully saved transcript and created meeting with id: {} ({} repeated segments skipped)",
+                meeting_id,
+                saved.duplicates_skipped
             );
+            if let Some(session_id) = &live_session_id {
+                crate::summary::live::attach_draft(pool, session_id, &meeting_id).await;
+            }
             Ok(serde_json::json!({
                 "status": "success",
                 "message": "Transcript saved successfully",
-                "meeting_id": meeting_id
+                "meeting_id": meeting_id,
+                "segment_order": segment_order,
+                "duplicates_skipped": saved.duplicates_skipped
             }))
         }
         Err(e) => {
             log_error!(
-                "Error saving transcript for meeting '{}': {}",
+                "Error saving transcript for meeting '{}' ({}): {}",
                 meeting_title,
+                meeting_id,
                 e
             );
-            Err(format!("Failed to save transcript: {}", e))
+            // The meeting id lets the caller retry with `resume_meeting_id`
+            Err(format!(
+                "Failed to save transcript for meeting {}: {}",
+                meeting_id, e
+            ))
         }
     }
 }
 
+/// Transcription progress plus whether it looks stuck
+#[derive(Debug, Serialize)]
+pub struct TranscriptionStatusResponse {
+    #[serde(flatten)]
+    pub progress: TranscriptionProgress,
+    pub stalled: bool,
+}
+
+/// An in-progress save that hasn't advanced for this long is reported as stalled
+const TRANSCRIPTION_STALL_SECS: i64 = 120;
+
+#[tauri::command]
+pub async fn api_get_transcription_status(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Option<TranscriptionStatusResponse>, String> {
+    let progress = TranscriptionStatusRepository::get(state.db_manager.pool(), &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load transcription status: {}", e))?;
+    Ok(progress.map(|progress| {
+        let idle = chrono::Utc::now() - progress.updated_at;
+        let stalled = progress.state == STATE_IN_PROGRESS
+            && idle.num_seconds() > TRANSCRIPTION_STALL_SECS;
+        TranscriptionStatusResponse { progress, stalled }
+    }))
+}
+
+/// Renames recording folders whose names aren't valid on this platform and
+/// updates the meetings pointing at them; `dry_run` only reports
+#[tauri::command]
+pub async fn api_repair_meeting_folders(
+    state: tauri::State<'_, AppState>,
+    dry_run: bool,
+) -> Result<FolderRepairReport, String> {
+    log_info!("api_repair_meeting_folders called (dry_run: {})", dry_run);
+    let report = repair_meeting_folders(state.db_manager.pool(), dry_run).await?;
+    log_info!(
+        "📁 Meeting folder repair: {} rename(s), {} missing folder(s)",
+        report.renames.len(),
+        report.missing.len()
+    );
+    Ok(report)
+}
+
+/// Rewrites the transcript text kept for summarization from the saved
+/// segments, fixing stale text and meeting names
+#[tauri::command]
+pub async fn api_rebuild_transcript_chunks(
+    state: tauri::State<'_, AppState>,
+) -> Result<TranscriptChunksRebuild, String> {
+    log_info!("api_rebuild_transcript_chunks called");
+    let _write_guard = state.db_manager.write_lock().await;
+    let report = TranscriptChunksRepository::rebuild_from_transcripts(state.db_manager.pool())
+        .await
+        .map_err(|e| format!("Failed to rebuild transcript chunks: {}", e))?;
+    log_info!(
+        "🧩 Transcript chunks rebuilt: {} row(s) rewritten, {} removed",
+        report.rebuilt,
+        report.removed
+    );
+    Ok(report)
+}
+
+/// Folders a moved recording folder is searched under: the configured
+/// recordings folder and the default one
+async fn recordings_roots<R: Runtime>(app: &AppHandle<R>) -> Vec<std::path::PathBuf> {
+    let mut roots = Vec::new();
+    match load_recording_preferences(app).await {
+        Ok(preferences) => roots.push(preferences.save_folder),
+        Err(e) => log_warn!("Failed to load recording preferences: {}", e),
+    }
+    let default = get_default_recordings_folder();
+    if !roots.contains(&default) {
+        roots.push(default);
+    }
+    roots
+}
+
+/// Whether the meeting's recording folder is still where it was stored,
+/// with a folder it could be relocated to when it's missing
+#[tauri::command]
+pub async fn api_get_meeting_folder_state<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<MeetingFolderState, String> {
+    log_info!("api_get_meeting_folder_state called for meeting_id: {}", meeting_id);
+    let roots = recordings_roots(&app).await;
+    meeting_folder_state(state.db_manager.pool(), &meeting_id, &roots).await
+}
+
+/// Points the meeting at its moved recording folder and returns the new path
+///
+/// Without `new_path` the recordings folders are searched for a folder with
+/// the old name.
+#[tauri::command]
+pub async fn api_relocate_meeting_folder<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    new_path: Option<String>,
+) -> Result<String, String> {
+    log_info!(
+        "api_relocate_meeting_folder called for meeting_id: {}, new_path: {:?}",
+        meeting_id,
+        new_path
+    );
+    let roots = recordings_roots(&app).await;
+    relocate_meeting_folder(
+        state.db_manager.pool(),
+        &meeting_id,
+        new_path.as_deref(),
+        &roots,
+    )
+    .await
+}
+
 /// Opens the meeting's recording folder in the system file explorer
 #[tauri::command]
 pub async fn open_meeting_folder<R: Runtime>(
@@ -1209,7 +2137,10 @@ pub async fn open_meeting_folder<R: Runtime>(
                 let path = std::path::Path::new(&folder_path);
                 if !path.exists() {
                     log_warn!("Folder path does not exist: {}", folder_path);
-                    return Err(format!("Recording folder not found: {}", folder_path));
+                    return Err(format!(
+                        "Recording folder missing: {}. If it was moved, relocate it to open it",
+                        folder_path
+                    ));
                 }
 
                 // Open folder based on OS
@@ -1251,6 +2182,174 @@ pub async fn open_meeting_folder<R: Runtime>(
     }
 }
 
+/// Opens one file from the meeting's recording folder, or reveals it in the
+/// file manager when `reveal` is set
+///
+/// `artifact` is "recording", "transcript_json" or "summary_export". Returns
+/// the path that was opened.
+#[tauri::command]
+pub async fn api_open_meeting_artifact(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    artifact: String,
+    reveal: Option<bool>,
+) -> Result<String, String> {
+    log_info!(
+        "api_open_meeting_artifact called for meeting_id: {}, artifact: {}",
+        meeting_id,
+        artifact
+    );
+    let artifact = MeetingArtifact::from_str(&artifact)?;
+    if artifact == MeetingArtifact::Recording {
+        ensure_recording_kept(state.db_manager.pool(), &meeting_id).await?;
+    }
+
+    let meeting: Option<MeetingModel> = sqlx::query_as(
+        "SELECT id, title, created_at, updated_at, folder_path FROM meetings WHERE id = ?",
+    )
+    .bind(&meeting_id)
+    .fetch_optional(state.db_manager.pool())
+    .await
+    .map_err(|e| format!("Database error: {}", e))?;
+
+    let folder_path = meeting
+        .ok_or_else(|| "Meeting not found".to_string())?
+        .folder_path
+        .ok_or_else(|| "Recording folder path not available for this meeting".to_string())?;
+
+    let path = resolve_artifact(std::path::Path::new(&folder_path), artifact).map_err(|e| {
+        log_warn!("Cannot open {:?} for meeting {}: {}", artifact, meeting_id, e);
+        e
+    })?;
+    open_path(&path, reveal.unwrap_or(false))?;
+
+    log_info!("📂 Opened {}", path.display());
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Runs the meeting's saved recording through each Whisper model and returns
+/// their timings and transcripts; the stored transcript is left as it is
+///
+/// The model loaded before the benchmark is loaded again afterwards.
+#[tauri::command]
+pub async fn api_benchmark_transcription(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    models: Vec<String>,
+) -> Result<TranscriptionBenchmark, String> {
+    log_info!(
+        "api_benchmark_transcription called for meeting_id: {}, models: {:?}",
+        meeting_id,
+        models
+    );
+    if models.is_empty() {
+        return Err("Select at least one model to benchmark".to_string());
+    }
+    if crate::audio::recording_commands::is_recording().await {
+        return Err("Stop the recording before benchmarking transcription".to_string());
+    }
+
+    let meeting: Option<MeetingModel> = sqlx::query_as(
+        "SELECT id, title, created_at, updated_at, folder_path FROM meetings WHERE id = ?",
+    )
+    .bind(&meeting_id)
+    .fetch_optional(state.db_manager.pool())
+    .await
+    .map_err(|e| format!("Database error: {}", e))?;
+    let meeting = meeting.ok_or_else(|| "Meeting not found".to_string())?;
+    ensure_recording_kept(state.db_manager.pool(), &meeting_id).await?;
+
+    let audio_path = benchmark_recording(meeting.folder_path.as_deref())?;
+    let decode_path = audio_path.clone();
+    let audio = tauri::async_runtime::spawn_blocking(move || decode_recording(&decode_path))
+        .await
+        .map_err(|e| format!("Failed to decode recording: {}", e))??;
+
+    crate::whisper_engine::commands::whisper_init().await?;
+    let engine = crate::whisper_engine::commands::WHISPER_ENGINE
+        .lock()
+        .unwrap()
+        .as_ref()
+        .cloned()
+        .ok_or_else(|| "Whisper engine not initialized".to_string())?;
+    engine
+        .discover_models()
+        .await
+        .map_err(|e| format!("Failed to list Whisper models: {}", e))?;
+    let previous_model = engine.get_current_model().await;
+
+    let benchmark_engine =
+        WhisperBenchmarkEngine::new(engine.clone(), crate::get_language_preference_internal());
+    let results = run_benchmark(&benchmark_engine, &audio, &models).await;
+
+    match previous_model {
+        Some(model) => {
+            if let Err(e) = engine.load_model(&model).await {
+                log_warn!("⚠️ Failed to reload model '{}' after benchmark: {}", model, e);
+            }
+        }
+        None => {
+            engine.unload_model().await;
+        }
+    }
+
+    log_info!(
+        "✅ Benchmarked {} models on {}",
+        results.len(),
+        audio_path.display()
+    );
+    Ok(TranscriptionBenchmark {
+        audio_path: audio_path.to_string_lossy().to_string(),
+        audio_seconds: audio.len() as f64 / WHISPER_SAMPLE_RATE as f64,
+        results,
+    })
+}
+
+/// Measures the level of a meeting's saved recording, so a capture from the
+/// wrong or a muted device is noticed before it is transcribed
+#[tauri::command]
+pub async fn api_check_recording_audio(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<RecordingAudioLevel, String> {
+    log_info!(
+        "api_check_recording_audio called for meeting_id: {}",
+        meeting_id
+    );
+    let pool = state.db_manager.pool();
+    ensure_recording_kept(pool, &meeting_id).await?;
+    let folder_path = MeetingsRepository::get_folder_path(pool, &meeting_id)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    let audio_path = benchmark_recording(folder_path.as_deref())?;
+    let decode_path = audio_path.clone();
+    let audio = tauri::async_runtime::spawn_blocking(move || decode_recording(&decode_path))
+        .await
+        .map_err(|e| format!("Failed to decode recording: {}", e))??;
+
+    let level = RecordingAudioLevel {
+        path: Some(audio_path),
+        ..measure_level(&audio, WHISPER_SAMPLE_RATE)
+    };
+    if level.has_audio {
+        log_info!(
+            "✅ Recording of meeting {} has audio: RMS {:.4} ({:.1} dBFS)",
+            meeting_id,
+            level.rms,
+            level.rms_dbfs
+        );
+    } else {
+        log_warn!(
+            "🔇 Recording of meeting {} is silent: RMS {:.6} ({:.1} dBFS)",
+            meeting_id,
+            level.rms,
+            level.rms_dbfs
+        );
+    }
+    Ok(level)
+}
+
 // Simple test command to check backend connectivity
 #[tauri::command]
 pub async fn test_backend_connection<R: Runtime>(
@@ -1259,7 +2358,7 @@ pub async fn test_backend_connection<R: Runtime>(
 ) -> Result<String, String> {
     log_debug!("Testing backend connection...");
 
-    let client = reqwest::Client::new();
+    let client = client_factory::client();
     let server_url = get_server_address(&app).await?;
 
     log_debug!("Testing connection to: {}", server_url);
@@ -1301,7 +2400,7 @@ pub async fn debug_backend_connection<R: Runtime>(app: AppHandle<R>) -> Result<S
     };
 
     // Test 2: Make a simple HTTP request to the backend
-    let client = reqwest::Client::new();
+    let client = client_factory::client();
     let test_url = format!("{}/docs", server_url); // Try the docs endpoint which should be public
 
     log_debug!("Testing connection to: {}", test_url);
@@ -1355,6 +2454,12 @@ pub struct SendToChatRequest {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SendQuestionsToChatRequest {
     pub questions: Vec<String>,
+    /// Stored questions to send and track instead of `questions`
+    #[serde(default, skip_serializing)]
+    pub question_ids: Vec<String>,
+    /// Filled in for `question_ids`; the extension confirms each delivery id
+    #[serde(skip_deserializing, skip_serializing_if = "Vec::is_empty")]
+    pub deliveries: Vec<QuestionDelivery>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub delay_between: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1370,6 +2475,24 @@ pub struct GenerateQuestionsRequest {
     pub text: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub project_key: Option<String>,
+    /// The meeting's background notes; filled in from the meeting when not given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background_context: Option<String>,
+}
+
+/// The meeting's background notes, cleaned and capped for the backend prompts
+///
+/// Loading errors are logged and the prompt goes out without notes.
+async fn background_context_for_backend(state: &AppState, meeting_id: &str) -> Option<String> {
+    match MeetingsRepository::get_meeting_context(state.db_manager.pool(), meeting_id).await {
+        Ok(context) => context.and_then(|context| {
+            prepare_context(&context.text, FIXED_CONTEXT_TOKENS, &[BACKGROUND_CONTEXT_TAG])
+        }),
+        Err(e) => {
+            log_warn!("⚠️ Failed to load background context for {}: {}", meeting_id, e);
+            None
+        }
+    }
 }
 
 /// Get the current status of connected browser extensions
@@ -1379,7 +2502,7 @@ pub async fn api_get_extension_status<R: Runtime>(
     _auth_token: Option<String>,
 ) -> Result<serde_json::Value, String> {
     log_info!("api_get_extension_status called");
-    make_api_request::<R, serde_json::Value>(&app, "/extension/status", "GET", None, None, None).await
+    make_api_request::<R, serde_json::Value>(&app, &load_response_limits(&app), "/extension/status", "GET", None, None, None).await
 }
 
 /// Send a message to the meeting chat via browser extension
@@ -1391,31 +2514,193 @@ pub async fn api_send_to_chat<R: Runtime>(
 ) -> Result<serde_json::Value, String> {
     log_info!("api_send_to_chat called with message length: {}", request.message.len());
     let body = serde_json::to_string(&request).map_err(|e| e.to_string())?;
-    make_api_request::<R, serde_json::Value>(&app, "/extension/send-to-chat", "POST", Some(&body), None, None).await
+    make_api_request::<R, serde_json::Value>(&app, &load_response_limits(&app), "/extension/send-to-chat", "POST", Some(&body), None, None).await
 }
 
 /// Send multiple clarifying questions to the meeting chat
+///
+/// Questions given by `question_ids` get a delivery id each and their status
+/// follows the extension's confirmations: "sending", then "sent" or
+/// "failed", or "unknown" when none arrives within the configured timeout.
 #[tauri::command]
 pub async fn api_send_questions_to_chat<R: Runtime>(
     app: AppHandle<R>,
-    request: SendQuestionsToChatRequest,
+    state: tauri::State<'_, AppState>,
+    mut request: SendQuestionsToChatRequest,
     _auth_token: Option<String>,
 ) -> Result<serde_json::Value, String> {
-    log_info!("api_send_questions_to_chat called with {} questions", request.questions.len());
+    let pool = state.db_manager.pool().clone();
+    if !request.question_ids.is_empty() {
+        let started =
+            QuestionsRepository::start_delivery(&pool, &request.question_ids, chrono::Utc::now())
+                .await
+                .map_err(|e| format!("Failed to start question delivery: {}", e))?;
+        request.questions = started.iter().map(|question| question.text.clone()).collect();
+        request.deliveries = started
+            .into_iter()
+            .filter_map(|question| {
+                Some(QuestionDelivery {
+                    delivery_id: question.delivery_id?,
+                    text: question.text,
+                })
+            })
+            .collect();
+    }
+    log_info!(
+        "api_send_questions_to_chat called with {} questions ({} tracked)",
+        request.questions.len(),
+        request.deliveries.len()
+    );
+    let delivery_ids: Vec<String> = request
+        .deliveries
+        .iter()
+        .map(|delivery| delivery.delivery_id.clone())
+        .collect();
     let body = serde_json::to_string(&request).map_err(|e| e.to_string())?;
-    make_api_request::<R, serde_json::Value>(&app, "/extension/send-questions", "POST", Some(&body), None, None).await
+    let result = make_api_request::<R, serde_json::Value>(&app, &load_response_limits(&app), "/extension/send-questions", "POST", Some(&body), None, None).await;
+
+    match &result {
+        Ok(_) if !delivery_ids.is_empty() => track_question_deliveries(app, pool, delivery_ids),
+        Ok(_) => {}
+        // The extension never got them
+        Err(_) => {
+            let failed: Vec<DeliveryConfirmation> = delivery_ids
+                .into_iter()
+                .map(|delivery_id| DeliveryConfirmation {
+                    delivery_id,
+                    platform: None,
+                    success: false,
+                })
+                .collect();
+            if let Err(e) = apply_confirmations(&pool, &failed).await {
+                log_warn!("⚠️ {}", e);
+            }
+        }
+    }
+    result
+}
+
+/// Polls the backend for the extension's confirmations of `delivery_ids`
+/// until all arrived or the timeout passed, then marks deliveries still
+/// waiting, including ones left over from earlier sessions, as "unknown"
+fn track_question_deliveries<R: Runtime>(
+    app: AppHandle<R>,
+    pool: sqlx::SqlitePool,
+    mut delivery_ids: Vec<String>,
+) {
+    let config = load_question_delivery_config(&app);
+    let timeout = std::time::Duration::from_secs(config.confirmation_timeout_secs);
+    let poll_interval = std::time::Duration::from_secs(config.poll_interval_secs.max(1));
+    let limits = load_response_limits(&app);
+    tauri::async_runtime::spawn(async move {
+        let deadline = tokio::time::Instant::now() + timeout;
+        while !delivery_ids.is_empty() && tokio::time::Instant::now() < deadline {
+            tokio::time::sleep(poll_interval).await;
+            let endpoint = format!("/extension/delivery-status?ids={}", delivery_ids.join(","));
+            let response = match make_api_request::<R, DeliveryStatusResponse>(&app, &limits, &endpoint, "GET", None, None, None).await {
+                Ok(response) => response,
+                Err(e) => {
+                    log_warn!("⚠️ Failed to poll question delivery status: {}", e);
+                    continue;
+                }
+            };
+            match apply_confirmations(&pool, &response.deliveries).await {
+                Ok(confirmed) => delivery_ids.retain(|id| !confirmed.contains(id)),
+                Err(e) => log_warn!("⚠️ {}", e),
+            }
+        }
+
+        let older_than = chrono::Utc::now()
+            - chrono::Duration::seconds(config.confirmation_timeout_secs as i64);
+        match QuestionsRepository::expire_deliveries(&pool, older_than).await {
+            Ok(0) => {}
+            Ok(expired) => log_warn!("⏱️ {} question deliveries were never confirmed", expired),
+            Err(e) => log_warn!("⚠️ Failed to expire question deliveries: {}", e),
+        }
+    });
+}
+
+/// Records the extension's confirmation of a question delivery, relayed by
+/// the frontend; sets the question's status to "sent" or "failed"
+#[tauri::command]
+pub async fn api_confirm_question_delivered(
+    state: tauri::State<'_, AppState>,
+    delivery_id: String,
+    platform: String,
+    success: bool,
+) -> Result<(), String> {
+    log_info!(
+        "api_confirm_question_delivered called for {} on {}: {}",
+        delivery_id,
+        platform,
+        success
+    );
+    let confirmation = DeliveryConfirmation {
+        delivery_id,
+        platform: Some(platform),
+        success,
+    };
+    let confirmed = apply_confirmations(state.db_manager.pool(), std::slice::from_ref(&confirmation)).await?;
+    if confirmed.is_empty() {
+        Err(format!("Question delivery {} not found", confirmation.delivery_id))
+    } else {
+        Ok(())
+    }
+}
+
+#[tauri::command]
+pub async fn api_get_question_delivery_config<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<QuestionDeliveryConfig, String> {
+    Ok(load_question_delivery_config(&app))
+}
+
+/// Saves how long question deliveries wait for a confirmation
+#[tauri::command]
+pub async fn api_set_question_delivery_config<R: Runtime>(
+    app: AppHandle<R>,
+    config: QuestionDeliveryConfig,
+) -> Result<QuestionDeliveryConfig, String> {
+    log_info!("api_set_question_delivery_config called: {:?}", config);
+    config.validate()?;
+    save_question_delivery_config(&app, &config)?;
+    Ok(config)
+}
+
+#[tauri::command]
+pub async fn api_get_response_limits<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<ResponseLimits, String> {
+    Ok(load_response_limits(&app))
+}
+
+/// Saves the size cap and read timeout of backend responses
+#[tauri::command]
+pub async fn api_set_response_limits<R: Runtime>(
+    app: AppHandle<R>,
+    limits: ResponseLimits,
+) -> Result<ResponseLimits, String> {
+    log_info!("api_set_response_limits called: {:?}", limits);
+    limits.validate()?;
+    save_response_limits(&app, &limits)?;
+    Ok(limits)
 }
 
 /// Generate clarifying questions about tasks from meeting transcript
 #[tauri::command]
 pub async fn api_generate_clarifying_questions<R: Runtime>(
     app: AppHandle<R>,
-    request: GenerateQuestionsRequest,
+    state: tauri::State<'_, AppState>,
+    mut request: GenerateQuestionsRequest,
     _auth_token: Option<String>,
 ) -> Result<serde_json::Value, String> {
     log_info!("api_generate_clarifying_questions called for meeting: {}", request.meeting_id);
+    if request.background_context.is_none() {
+        request.background_context =
+            background_context_for_backend(&state, &request.meeting_id).await;
+    }
     let body = serde_json::to_string(&request).map_err(|e| e.to_string())?;
-    make_api_request::<R, serde_json::Value>(&app, "/extension/generate-questions", "POST", Some(&body), None, None).await
+    make_api_request::<R, serde_json::Value>(&app, &load_response_limits(&app), "/extension/generate-questions", "POST", Some(&body), None, None).await
 }
 
 /// Ping all connected browser extensions to check health
@@ -1425,5 +2710,245 @@ pub async fn api_ping_extensions<R: Runtime>(
     _auth_token: Option<String>,
 ) -> Result<serde_json::Value, String> {
     log_info!("api_ping_extensions called");
-    make_api_request::<R, serde_json::Value>(&app, "/extension/ping", "POST", None, None, None).await
+    make_api_request::<R, serde_json::Value>(&app, &load_response_limits(&app), "/extension/ping", "POST", None, None, None).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::{seed_meetings, test_pool};
+    use crate::http::test_server::{closed_url, mock_server, serve};
+
+    /// Like `mock_server`, but without a Content-Length; the body ends when
+    /// the connection closes
+    async fn mock_server_unsized(body: String) -> String {
+        let (url, _) = serve(move |_| {
+            format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nConnection: close\r\n\r\n{}",
+                body
+            )
+        })
+        .await;
+        url
+    }
+
+    #[tokio::test]
+    async fn test_transcript_text_with_and_without_timestamps() {
+        let pool = test_pool().await;
+        seed_meetings(&pool, 2, 2).await;
+        let repos = Repositories::sqlx(pool.clone());
+
+        // meeting-1 was never imported; its segments are only in the folder
+        sqlx::query("DELETE FROM transcripts WHERE meeting_id = 'meeting-1'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let folder = tempfile::tempdir().unwrap();
+        let segment = |id: &str, text: &str, start: f64, sequence_id: u64| {
+            serde_json::json!({
+                "id": id,
+                "text": text,
+                "audio_start_time": start,
+                "audio_end_time": start + 3.0,
+                "duration": 3.0,
+                "display_time": "[00:00]",
+                "confidence": 0.9,
+                "sequence_id": sequence_id,
+            })
+        };
+        let file = serde_json::json!({
+            "segments": [
+                segment("a", "Sam: We ship Friday.", 2.0, 0),
+                segment("b", "Ana: Agreed.", 65.0, 1),
+            ],
+        });
+        std::fs::write(folder.path().join("transcripts.json"), file.to_string()).unwrap();
+        let folder_path = folder.path().to_str().unwrap();
+        MeetingsRepository::set_folder_path(&pool, "meeting-1", folder_path)
+            .await
+            .unwrap();
+
+        let timed = TranscriptTextOptions::default();
+        let untimed = TranscriptTextOptions {
+            include_timestamps: false,
+            ..TranscriptTextOptions::default()
+        };
+        let transcripts_json = TranscriptsJsonCache::default();
+        let text = |meeting_id: &'static str, options: TranscriptTextOptions| {
+            let (pool, repos, transcripts_json) = (&pool, &repos, &transcripts_json);
+            async move {
+                let segments = meeting_transcript_segments(pool, repos, transcripts_json, meeting_id)
+                    .await
+                    .unwrap();
+                render_transcript(&segments, &options)
+            }
+        };
+
+        assert_eq!(
+            text("meeting-0", timed.clone()).await,
+            "[00:00:00] segment text\n[00:00:05] segment text"
+        );
+        assert_eq!(text("meeting-0", untimed.clone()).await, "segment text\nsegment text");
+        assert_eq!(
+            text("meeting-1", timed).await,
+            "[00:00:02] Sam: We ship Friday.\n[00:01:05] Ana: Agreed."
+        );
+        assert_eq!(text("meeting-1", untimed).await, "Sam: We ship Friday.\nAna: Agreed.");
+    }
+
+    #[tokio::test]
+    async fn test_jira_request_reports_unreachable_backend() {
+        let url = closed_url().await;
+
+        let err = send_jira_request::<serde_json::Value>(
+            &url,
+            "/get-jira-projects",
+            "GET",
+            None,
+            None,
+            JIRA_REQUEST_TIMEOUT,
+            &ResponseLimits::default(),
+        )
+        .await
+            .unwrap_err();
+
+        assert!(err.starts_with(JIRA_BACKEND_UNAVAILABLE), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_jira_auth_error_is_not_reported_as_unavailable() {
+        let (url, _) = mock_server(401, r#"{"detail":"Unauthorized"}"#).await;
+
+        let err = send_jira_request::<serde_json::Value>(
+            &url,
+            "/get-jira-projects",
+            "GET",
+            None,
+            None,
+            JIRA_REQUEST_TIMEOUT,
+            &ResponseLimits::default(),
+        )
+        .await
+            .unwrap_err();
+
+        assert!(!err.starts_with(JIRA_BACKEND_UNAVAILABLE), "{}", err);
+        assert!(err.starts_with("HTTP 401"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_oversized_response_is_rejected() {
+        let limits = ResponseLimits {
+            max_response_bytes: 1024,
+            ..Default::default()
+        };
+        let issues: Vec<String> = (0..100)
+            .map(|i| format!(r#"{{"key":"PROJ-{}"}}"#, i))
+            .collect();
+        let body = format!(r#"{{"issues":[{}]}}"#, issues.join(","));
+        assert!(body.len() > 1024);
+
+        // Rejected from the declared length and while reading an unsized body
+        for url in [
+            mock_server(200, body.clone()).await.0,
+            mock_server_unsized(body).await,
+        ] {
+            let err = send_jira_request::<serde_json::Value>(
+                &url,
+                "/search-jira-issues",
+                "GET",
+                None,
+                None,
+                JIRA_REQUEST_TIMEOUT,
+                &limits,
+            )
+            .await
+            .unwrap_err();
+            assert!(err.starts_with("Response too large"), "{}", err);
+        }
+
+        // A body under the cap still parses
+        let url = mock_server_unsized(r#"{"issues":[]}"#.to_string()).await;
+        let ok = send_jira_request::<serde_json::Value>(
+            &url,
+            "/search-jira-issues",
+            "GET",
+            None,
+            None,
+            JIRA_REQUEST_TIMEOUT,
+            &limits,
+        )
+        .await
+        .unwrap();
+        assert_eq!(ok, serde_json::json!({"issues": []}));
+    }
+
+    #[tokio::test]
+    async fn test_jira_health_with_backend_down() {
+        let url = closed_url().await;
+
+        let health = check_jira_health(&url, None, &ResponseLimits::default()).await;
+
+        assert!(!health.backend_reachable);
+        assert!(!health.configured);
+        assert_eq!(health.features, JiraFeatures::default());
+        assert!(health.error.unwrap().starts_with(JIRA_BACKEND_UNAVAILABLE));
+    }
+
+    #[tokio::test]
+    async fn test_jira_health_reports_features_by_configuration() {
+        let unconfigured = check_jira_health(
+            &mock_server(200, "{}").await.0,
+            None,
+            &ResponseLimits::default(),
+        )
+        .await;
+        assert!(unconfigured.backend_reachable);
+        assert!(!unconfigured.configured);
+        assert!(unconfigured.features.configure);
+        assert!(unconfigured.features.analyze_tasks);
+        assert!(!unconfigured.features.create_tasks);
+
+        let configured = check_jira_health(
+            &mock_server(200, r#"{"url":"https://acme.atlassian.net","email":"a@b.c","api_token":"********"}"#).await.0,
+            None,
+            &ResponseLimits::default(),
+        )
+        .await;
+        assert!(configured.configured);
+        assert!(configured.features.create_tasks);
+        assert!(configured.features.browse_issues);
+        assert!(configured.features.update_issues);
+        assert!(configured.error.is_none());
+    }
+
+    #[test]
+    fn test_bulk_task_uses_resolved_account_id() {
+        use crate::summary::action_items::{OwnerMatch, ResolvedPerson};
+
+        let person = ResolvedPerson {
+            person_id: "person-1".to_string(),
+            display_name: "Alex Kim".to_string(),
+            email: None,
+            jira_account_id: Some("5b10ac8d82e05b22cc7d4ef5".to_string()),
+        };
+        let mut item = ActionItem {
+            owner: "Alex".to_string(),
+            task: "Fix PROJ-404 login bug".to_string(),
+            due: "Friday".to_string(),
+            owner_match: Some(OwnerMatch::Resolved { person: person.clone() }),
+            ..Default::default()
+        };
+
+        let task = action_item_task(&item, "PROJ", "Task", None);
+        assert_eq!(task.assignee.as_deref(), Some("5b10ac8d82e05b22cc7d4ef5"));
+        assert_eq!(task.summary, "Fix PROJ-404 login bug");
+        assert_eq!(task.description, "Owner: Alex Kim\nDue: Friday");
+
+        item.owner_match = Some(OwnerMatch::Ambiguous {
+            candidates: vec![person],
+        });
+        let task = action_item_task(&item, "PROJ", "Task", None);
+        assert_eq!(task.assignee, None);
+        assert!(task.description.starts_with("Owner: Alex\n"));
+    }
 }
@@ -0,0 +1,142 @@
+//! Background runs of the Jira task analysis
+//!
+//! The analysis can take minutes on the backend, so `api_analyze_jira_tasks`
+//! only records a PENDING run and returns. The run is tracked per meeting in
+//! `jira_analysis_processes`; starting another run replaces the row's run id,
+//! and a run whose id no longer matches drops its result.
+
+use crate::database::repositories::jira_analysis::JiraAnalysisRepository;
+use log::{info as log_info, warn as log_warn};
+use serde_json::Value;
+use sqlx::SqlitePool;
+use std::future::Future;
+
+pub const STATUS_COMPLETED: &str = "completed";
+pub const STATUS_FAILED: &str = "failed";
+
+/// Event emitted when a run finishes, with `{meeting_id, status}`
+pub const JIRA_ANALYSIS_COMPLETED_EVENT: &str = "jira-analysis-completed";
+
+/// Runs `analysis` as run `run_id` of the meeting and stores its outcome
+///
+/// Returns the status the run ended with, or None if a newer run superseded
+/// it; a run superseded before it started never awaits `analysis`.
+pub async fn run_jira_analysis<F>(
+    pool: &SqlitePool,
+    meeting_id: &str,
+    run_id: &str,
+    analysis: F,
+) -> Result<Option<&'static str>, sqlx::Error>
+where
+    F: Future<Output = Result<Value, String>>,
+{
+    if !JiraAnalysisRepository::mark_processing(pool, meeting_id, run_id).await? {
+        log_info!("⏭️ Jira analysis run {} for {} was superseded before it started", run_id, meeting_id);
+        return Ok(None);
+    }
+
+    let (stored, status) = match analysis.await {
+        Ok(result) => (
+            JiraAnalysisRepository::complete(pool, meeting_id, run_id, &result).await?,
+            STATUS_COMPLETED,
+        ),
+        Err(e) => {
+            log_warn!("⚠️ Jira analysis for {} failed: {}", meeting_id, e);
+            (
+                JiraAnalysisRepository::fail(pool, meeting_id, run_id, &e).await?,
+                STATUS_FAILED,
+            )
+        }
+    };
+
+    if !stored {
+        log_info!("⏭️ Jira analysis run {} for {} was superseded, dropping its result", run_id, meeting_id);
+        return Ok(None);
+    }
+    log_info!("✅ Jira analysis for {} {}", meeting_id, status);
+    Ok(Some(status))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::{seed_meetings, test_pool};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_jira_analysis_status_lifecycle() {
+        let pool = test_pool().await;
+        seed_meetings(&pool, 1, 0).await;
+
+        let run_id = JiraAnalysisRepository::start(&pool, "meeting-0").await.unwrap();
+        let pending = JiraAnalysisRepository::get(&pool, "meeting-0").await.unwrap().unwrap();
+        assert_eq!(pending.status, "PENDING");
+
+        let analysis = async {
+            let running = JiraAnalysisRepository::get(&pool, "meeting-0").await.unwrap().unwrap();
+            assert_eq!(running.status, "processing");
+            Ok(json!({"tasks": [{"summary": "Fix login"}]}))
+        };
+        let status = run_jira_analysis(&pool, "meeting-0", &run_id, analysis).await.unwrap();
+        assert_eq!(status, Some(STATUS_COMPLETED));
+
+        let done = JiraAnalysisRepository::get(&pool, "meeting-0").await.unwrap().unwrap();
+        assert_eq!(done.status, STATUS_COMPLETED);
+        let result: Value = serde_json::from_str(done.result.as_deref().unwrap()).unwrap();
+        assert_eq!(result["tasks"][0]["summary"], "Fix login");
+
+        // A failed re-run clears the earlier result
+        let run_id = JiraAnalysisRepository::start(&pool, "meeting-0").await.unwrap();
+        let status = run_jira_analysis(&pool, "meeting-0", &run_id, async {
+            Err("HTTP 500: Internal Server Error".to_string())
+        })
+        .await
+        .unwrap();
+        assert_eq!(status, Some(STATUS_FAILED));
+        let failed = JiraAnalysisRepository::get(&pool, "meeting-0").await.unwrap().unwrap();
+        assert_eq!(failed.result, None);
+        assert_eq!(failed.error.as_deref(), Some("HTTP 500: Internal Server Error"));
+    }
+
+    #[tokio::test]
+    async fn test_rerun_supersedes_running_analysis() {
+        let pool = test_pool().await;
+        seed_meetings(&pool, 1, 0).await;
+
+        let first = JiraAnalysisRepository::start(&pool, "meeting-0").await.unwrap();
+        let mut second = None;
+        // The user starts another run while the first is still waiting
+        let analysis = async {
+            second = Some(JiraAnalysisRepository::start(&pool, "meeting-0").await.unwrap());
+            Ok(json!({"tasks": ["stale"]}))
+        };
+        let status = run_jira_analysis(&pool, "meeting-0", &first, analysis).await.unwrap();
+        assert_eq!(status, None);
+
+        let row = JiraAnalysisRepository::get(&pool, "meeting-0").await.unwrap().unwrap();
+        let second = second.unwrap();
+        assert_eq!(row.run_id, second);
+        assert_eq!(row.status, "PENDING");
+        assert_eq!(row.result, None);
+
+        // A superseded run that hasn't started yet never runs
+        let third = JiraAnalysisRepository::start(&pool, "meeting-0").await.unwrap();
+        let mut ran = false;
+        let analysis = async {
+            ran = true;
+            Ok(json!({"tasks": []}))
+        };
+        let status = run_jira_analysis(&pool, "meeting-0", &second, analysis).await.unwrap();
+        assert_eq!(status, None);
+        assert!(!ran);
+
+        let status = run_jira_analysis(&pool, "meeting-0", &third, async {
+            Ok(json!({"tasks": ["fresh"]}))
+        })
+        .await
+        .unwrap();
+        assert_eq!(status, Some(STATUS_COMPLETED));
+        let row = JiraAnalysisRepository::get(&pool, "meeting-0").await.unwrap().unwrap();
+        assert!(row.result.unwrap().contains("fresh"));
+    }
+}
@@ -0,0 +1,266 @@
+//! Jira issue descriptions for action items
+//!
+//! Jira Cloud's v3 API takes rich descriptions only as Atlassian Document
+//! Format (ADF); Jira Server and the backend proxy take plain text. Both
+//! are built from the same context: the action item, the transcript
+//! segment it came from, an excerpt of the summary section around it and a
+//! link back to the meeting.
+
+use crate::summary::action_items::ActionItem;
+use serde_json::{json, Value};
+
+/// What an issue description is built from
+#[derive(Debug, Clone, Copy)]
+pub struct IssueContext<'a> {
+    pub item: &'a ActionItem,
+    /// Text of the summary section the item was listed in
+    pub section_excerpt: Option<&'a str>,
+    pub meeting_title: Option<&'a str>,
+    /// Opens the meeting in the app
+    pub meeting_link: Option<&'a str>,
+}
+
+impl<'a> IssueContext<'a> {
+    pub fn new(item: &'a ActionItem) -> Self {
+        Self {
+            item,
+            section_excerpt: None,
+            meeting_title: None,
+            meeting_link: None,
+        }
+    }
+
+    /// The resolved person's name, or the owner as written in the summary
+    fn owner(&self) -> &'a str {
+        self.item
+            .resolved_person()
+            .map(|person| person.display_name.as_str())
+            .unwrap_or(&self.item.owner)
+    }
+
+    /// (label, value) pairs of the item's details, skipping empty ones
+    fn details(&self) -> Vec<(&'static str, &'a str)> {
+        [
+            ("Owner", self.owner()),
+            ("Due", self.item.due.as_str()),
+            ("Discussed at", self.item.timestamp.as_str()),
+        ]
+        .into_iter()
+        .filter(|(_, value)| !value.trim().is_empty())
+        .collect()
+    }
+
+    fn excerpt(&self) -> Option<&'a str> {
+        self.section_excerpt
+            .map(str::trim)
+            .filter(|excerpt| !excerpt.is_empty())
+    }
+
+    fn reference(&self) -> Option<&'a str> {
+        Some(self.item.reference.trim()).filter(|reference| !reference.is_empty())
+    }
+}
+
+fn text(content: &str) -> Value {
+    json!({ "type": "text", "text": content })
+}
+
+fn strong(content: &str) -> Value {
+    json!({ "type": "text", "text": content, "marks": [{ "type": "strong" }] })
+}
+
+fn paragraph(content: Vec<Value>) -> Value {
+    json!({ "type": "paragraph", "content": content })
+}
+
+/// The description as an ADF document
+pub fn to_adf(context: &IssueContext) -> Value {
+    let mut content = Vec::new();
+
+    let items: Vec<Value> = context
+        .details()
+        .into_iter()
+        .map(|(label, value)| {
+            json!({
+                "type": "listItem",
+                "content": [paragraph(vec![strong(&format!("{}: ", label)), text(value)])]
+            })
+        })
+        .collect();
+    if !items.is_empty() {
+        content.push(json!({ "type": "bulletList", "content": items }));
+    }
+
+    if let Some(reference) = context.reference() {
+        content.push(paragraph(vec![strong("From the transcript")]));
+        content.push(json!({
+            "type": "blockquote",
+            "content": [paragraph(vec![text(reference)])]
+        }));
+    }
+    if let Some(excerpt) = context.excerpt() {
+        content.push(paragraph(vec![strong("From the meeting summary")]));
+        content.extend(
+            excerpt
+                .split("\n\n")
+                .map(str::trim)
+                .filter(|block| !block.is_empty())
+                .map(|block| paragraph(vec![text(block)])),
+        );
+    }
+
+    let title = context.meeting_title.unwrap_or("the meeting");
+    let source = match context.meeting_link {
+        Some(href) => json!({
+            "type": "text",
+            "text": title,
+            "marks": [{ "type": "link", "attrs": { "href": href } }]
+        }),
+        None => text(title),
+    };
+    content.push(paragraph(vec![text("Created from "), source]));
+
+    json!({ "type": "doc", "version": 1, "content": content })
+}
+
+/// The description as plain text, for Jira Server and the backend proxy
+pub fn to_plain_text(context: &IssueContext) -> String {
+    let mut lines: Vec<String> = context
+        .details()
+        .into_iter()
+        .filter(|(label, _)| *label != "Discussed at")
+        .map(|(label, value)| format!("{}: {}", label, value))
+        .collect();
+    if let Some(reference) = context.reference() {
+        lines.push(format!("From the meeting: {}", reference));
+    }
+    if let Some(excerpt) = context.excerpt() {
+        lines.push(format!("\nFrom the meeting summary:\n{}", excerpt));
+    }
+    match (context.meeting_title, context.meeting_link) {
+        (title, Some(link)) => lines.push(format!(
+            "\nCreated from {}: {}",
+            title.unwrap_or("the meeting"),
+            link
+        )),
+        (Some(title), None) => lines.push(format!("\nCreated from {}", title)),
+        (None, None) => {}
+    }
+    lines.join("\n")
+}
+
+/// The `description` field value: ADF for Jira Cloud's v3 API, plain text
+/// for Jira Server's v2 API
+pub fn description_field(context: &IssueContext, adf: bool) -> Value {
+    if adf {
+        to_adf(context)
+    } else {
+        Value::String(to_plain_text(context))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item() -> ActionItem {
+        ActionItem {
+            owner: "Sam".to_string(),
+            task: "Fix Stripe webhook (PROJ-404)".to_string(),
+            due: "Friday".to_string(),
+            reference: "I'll take the webhook fix".to_string(),
+            timestamp: "00:01:10".to_string(),
+            start_seconds: Some(70.0),
+            end_seconds: None,
+            owner_match: None,
+        }
+    }
+
+    #[test]
+    fn test_adf_description_snapshot() {
+        let item = item();
+        let context = IssueContext {
+            section_excerpt: Some("The release waits for the fix.\n\nQA verifies it first."),
+            meeting_title: Some("Release sync"),
+            meeting_link: Some("str8_2task://meeting/m-1"),
+            ..IssueContext::new(&item)
+        };
+
+        assert_eq!(
+            to_adf(&context),
+            json!({
+                "type": "doc",
+                "version": 1,
+                "content": [
+                    {
+                        "type": "bulletList",
+                        "content": [
+                            { "type": "listItem", "content": [{ "type": "paragraph", "content": [
+                                { "type": "text", "text": "Owner: ", "marks": [{ "type": "strong" }] },
+                                { "type": "text", "text": "Sam" }
+                            ]}]},
+                            { "type": "listItem", "content": [{ "type": "paragraph", "content": [
+                                { "type": "text", "text": "Due: ", "marks": [{ "type": "strong" }] },
+                                { "type": "text", "text": "Friday" }
+                            ]}]},
+                            { "type": "listItem", "content": [{ "type": "paragraph", "content": [
+                                { "type": "text", "text": "Discussed at: ", "marks": [{ "type": "strong" }] },
+                                { "type": "text", "text": "00:01:10" }
+                            ]}]}
+                        ]
+                    },
+                    { "type": "paragraph", "content": [
+                        { "type": "text", "text": "From the transcript", "marks": [{ "type": "strong" }] }
+                    ]},
+                    { "type": "blockquote", "content": [{ "type": "paragraph", "content": [
+                        { "type": "text", "text": "I'll take the webhook fix" }
+                    ]}]},
+                    { "type": "paragraph", "content": [
+                        { "type": "text", "text": "From the meeting summary", "marks": [{ "type": "strong" }] }
+                    ]},
+                    { "type": "paragraph", "content": [
+                        { "type": "text", "text": "The release waits for the fix." }
+                    ]},
+                    { "type": "paragraph", "content": [
+                        { "type": "text", "text": "QA verifies it first." }
+                    ]},
+                    { "type": "paragraph", "content": [
+                        { "type": "text", "text": "Created from " },
+                        { "type": "text", "text": "Release sync", "marks": [
+                            { "type": "link", "attrs": { "href": "str8_2task://meeting/m-1" } }
+                        ]}
+                    ]}
+                ]
+            })
+        );
+        assert_eq!(
+            description_field(&context, false),
+            Value::String(
+                "Owner: Sam\nDue: Friday\nFrom the meeting: I'll take the webhook fix\n\nFrom the meeting summary:\nThe release waits for the fix.\n\nQA verifies it first.\n\nCreated from Release sync: str8_2task://meeting/m-1"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_empty_details_leave_no_empty_nodes() {
+        let item = ActionItem {
+            due: String::new(),
+            reference: " ".to_string(),
+            timestamp: String::new(),
+            ..item()
+        };
+        let context = IssueContext::new(&item);
+
+        let adf = to_adf(&context);
+        let content = adf["content"].as_array().unwrap();
+        assert_eq!(content.len(), 2);
+        assert_eq!(content[0]["content"].as_array().unwrap().len(), 1);
+        assert_eq!(content[1]["content"][1]["text"], "the meeting");
+        // ADF rejects empty text nodes
+        assert!(!adf.to_string().contains(r#""text":"""#));
+
+        // The proxy's plain text keeps its earlier shape
+        assert_eq!(to_plain_text(&context), "Owner: Sam");
+    }
+}
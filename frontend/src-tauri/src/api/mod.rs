@@ -1,5 +1,8 @@
 pub mod api;
 pub mod commands;
+pub mod jira_analysis;
+pub mod jira_description;
+pub mod response_limits;
 
 pub use api::*;
 // Don't re-export commands to avoid conflicts - lib.rs will import directly
@@ -0,0 +1,116 @@
+//! Limits on reading response bodies from the Python backend
+//!
+//! The overall request timeout covers a whole request, so a backend that
+//! sends the headers and then stalls, or streams an endless body, holds the
+//! command until it runs out. Bodies are read chunk by chunk instead: each
+//! read has its own timeout and the body is cut off once it grows past the
+//! cap. Jira searches are the requests that get close to it.
+
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+
+const CONFIG_STORE: &str = "backend-responses.json";
+const CONFIG_KEY: &str = "config";
+
+const MIN_RESPONSE_BYTES: u64 = 64 * 1024;
+const MAX_RESPONSE_BYTES: u64 = 512 * 1024 * 1024;
+const MAX_READ_TIMEOUT_SECS: u64 = 600;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResponseLimits {
+    /// Largest body accepted from the backend
+    pub max_response_bytes: u64,
+    /// How long to wait for the next chunk of a body
+    pub read_timeout_secs: u64,
+}
+
+impl Default for ResponseLimits {
+    fn default() -> Self {
+        Self {
+            max_response_bytes: 20 * 1024 * 1024,
+            read_timeout_secs: 30,
+        }
+    }
+}
+
+impl ResponseLimits {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(MIN_RESPONSE_BYTES..=MAX_RESPONSE_BYTES).contains(&self.max_response_bytes) {
+            return Err(format!(
+                "Maximum response size must be between {} KB and {} MB",
+                MIN_RESPONSE_BYTES / 1024,
+                MAX_RESPONSE_BYTES / (1024 * 1024)
+            ));
+        }
+        if !(1..=MAX_READ_TIMEOUT_SECS).contains(&self.read_timeout_secs) {
+            return Err(format!(
+                "Response read timeout must be between 1 and {} seconds",
+                MAX_READ_TIMEOUT_SECS
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn read_timeout(&self) -> Duration {
+        Duration::from_secs(self.read_timeout_secs.max(1))
+    }
+}
+
+pub fn load_response_limits<R: Runtime>(app: &AppHandle<R>) -> ResponseLimits {
+    let stored = app
+        .store(CONFIG_STORE)
+        .ok()
+        .and_then(|store| store.get(CONFIG_KEY));
+    match stored.map(serde_json::from_value::<ResponseLimits>) {
+        Some(Ok(limits)) => limits,
+        Some(Err(e)) => {
+            warn!(
+                "⚠️ Invalid backend response limits in store, using defaults: {}",
+                e
+            );
+            ResponseLimits::default()
+        }
+        None => ResponseLimits::default(),
+    }
+}
+
+pub fn save_response_limits<R: Runtime>(
+    app: &AppHandle<R>,
+    limits: &ResponseLimits,
+) -> Result<(), String> {
+    let store = app
+        .store(CONFIG_STORE)
+        .map_err(|e| format!("Failed to open backend response store: {}", e))?;
+    let value = serde_json::to_value(limits)
+        .map_err(|e| format!("Failed to serialize backend response limits: {}", e))?;
+    store.set(CONFIG_KEY, value);
+    store
+        .save()
+        .map_err(|e| format!("Failed to save backend response limits: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_limits_are_rejected() {
+        assert_eq!(ResponseLimits::default().validate(), Ok(()));
+        for limits in [
+            ResponseLimits {
+                max_response_bytes: 1024,
+                ..Default::default()
+            },
+            ResponseLimits {
+                read_timeout_secs: 0,
+                ..Default::default()
+            },
+        ] {
+            assert!(limits.validate().is_err(), "{:?}", limits);
+        }
+    }
+}
@@ -10,34 +10,22 @@ use std::path::PathBuf;
 use nnnoiseless::DenoiseState;
 
 use super::encode::encode_single_audio; // Correct path to encode module
-
-/// Sanitize a filename to be safe for filesystem use
-pub fn sanitize_filename(name: &str) -> String {
-    name.chars()
-        .map(|c| match c {
-            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
-            c if c.is_control() => '_',
-            c => c,
-        })
-        .collect::<String>()
-        .trim()
-        .to_string()
-}
+use super::meeting_folders::{create_unique_folder, sanitize_folder_name};
 
 /// Create a meeting folder with timestamp and return the path
 /// Creates structure: base_path/MeetingName_YYYY-MM-DD_HH-MM/
 ///                    ├── .checkpoints/  (for incremental saves)
+/// A second meeting with the same name in the same minute gets `-2`, `-3`...
 pub fn create_meeting_folder(
     base_path: &PathBuf,
     meeting_name: &str,
 ) -> Result<PathBuf> {
     let timestamp = Utc::now().format("%Y-%m-%d_%H-%M").to_string();
-    let sanitized_name = sanitize_filename(meeting_name);
+    let sanitized_name = sanitize_folder_name(meeting_name);
     let folder_name = format!("{}_{}", sanitized_name, timestamp);
-    let meeting_folder = base_path.join(folder_name);
 
     // Create main meeting folder
-    std::fs::create_dir_all(&meeting_folder)?;
+    let meeting_folder = create_unique_folder(base_path, &folder_name)?;
 
     // Create hidden .checkpoints subfolder for temporary checkpoint files
     let checkpoints_dir = meeting_folder.join(".checkpoints");
@@ -590,6 +578,90 @@ pub fn resample_audio(input: &[f32], from_sample_rate: u32, to_sample_rate: u32)
     }
 }
 
+/// Sinc parameters for a resampling ratio, the same choices `resample` makes
+fn adaptive_sinc_parameters(ratio: f64) -> SincInterpolationParameters {
+    let (sinc_len, interpolation, oversampling_factor) = if ratio >= 2.0 {
+        (512, SincInterpolationType::Cubic, 512)
+    } else if ratio >= 1.5 {
+        (384, SincInterpolationType::Cubic, 384)
+    } else if ratio > 1.0 {
+        (256, SincInterpolationType::Linear, 256)
+    } else if ratio <= 0.5 {
+        (512, SincInterpolationType::Cubic, 512)
+    } else {
+        (384, SincInterpolationType::Linear, 384)
+    };
+    SincInterpolationParameters {
+        sinc_len,
+        f_cutoff: 0.95,
+        interpolation,
+        oversampling_factor,
+        window: WindowFunction::BlackmanHarris2,
+    }
+}
+
+/// Persistent mono resampler for a live stream
+///
+/// Keeps its filter state across calls so chunk boundaries don't distort the
+/// signal, and buffers input until a full resampler chunk is available, so
+/// callers can feed chunks of any size. A call may return nothing while
+/// input is being buffered; that audio comes out with a later call.
+pub struct StreamResampler {
+    resampler: SincFixedIn<f32>,
+    input_buffer: Vec<f32>,
+    chunk_size: usize,
+    from_sample_rate: u32,
+    to_sample_rate: u32,
+}
+
+impl StreamResampler {
+    pub fn new(from_sample_rate: u32, to_sample_rate: u32, chunk_size: usize) -> Result<Self> {
+        let ratio = to_sample_rate as f64 / from_sample_rate as f64;
+        let resampler = SincFixedIn::<f32>::new(
+            ratio,
+            2.0, // Maximum relative deviation
+            adaptive_sinc_parameters(ratio),
+            chunk_size,
+            1, // Mono
+        )?;
+        Ok(Self {
+            resampler,
+            input_buffer: Vec::with_capacity(chunk_size * 2),
+            chunk_size,
+            from_sample_rate,
+            to_sample_rate,
+        })
+    }
+
+    pub fn from_sample_rate(&self) -> u32 {
+        self.from_sample_rate
+    }
+
+    pub fn to_sample_rate(&self) -> u32 {
+        self.to_sample_rate
+    }
+
+    /// Input samples waiting for a full chunk
+    pub fn buffered(&self) -> usize {
+        self.input_buffer.len()
+    }
+
+    /// Resamples every complete chunk of the buffered input plus `input`
+    pub fn process(&mut self, input: &[f32]) -> Result<Vec<f32>> {
+        self.input_buffer.extend_from_slice(input);
+        let mut output = Vec::new();
+        while self.input_buffer.len() >= self.chunk_size {
+            // Rubato expects input as Vec<Vec<f32>> (one Vec per channel)
+            let chunk: Vec<f32> = self.input_buffer.drain(0..self.chunk_size).collect();
+            let mut waves_out = self.resampler.process(&[chunk], None)?;
+            if let Some(channel) = waves_out.pop() {
+                output.extend_from_slice(&channel);
+            }
+        }
+        Ok(output)
+    }
+}
+
 pub fn write_audio_to_file(
     audio: &[f32],
     sample_rate: u32,
@@ -613,7 +685,7 @@ pub fn write_audio_to_file_with_meeting_name(
 
     // Create meeting folder if meeting name is provided
     let final_output_path = if let Some(name) = meeting_name {
-        let sanitized_meeting_name = sanitize_filename(name);
+        let sanitized_meeting_name = sanitize_folder_name(name);
         let meeting_folder = output_path.join(&sanitized_meeting_name);
 
         // Create the meeting folder if it doesn't exist
@@ -654,7 +726,7 @@ pub fn write_transcript_to_file(
 
     // Create meeting folder if meeting name is provided (same logic as audio)
     let final_output_path = if let Some(name) = meeting_name {
-        let sanitized_meeting_name = sanitize_filename(name);
+        let sanitized_meeting_name = sanitize_folder_name(name);
         let meeting_folder = output_path.join(&sanitized_meeting_name);
 
         // Create the meeting folder if it doesn't exist
@@ -689,7 +761,7 @@ pub fn write_transcript_json_to_file(
 
     // Create meeting folder if meeting name is provided
     let final_output_path = if let Some(name) = meeting_name {
-        let sanitized_meeting_name = sanitize_filename(name);
+        let sanitized_meeting_name = sanitize_folder_name(name);
         let meeting_folder = output_path.join(&sanitized_meeting_name);
 
         if !meeting_folder.exists() {
@@ -720,3 +792,34 @@ pub fn write_transcript_json_to_file(
 
     Ok(file_path.to_string_lossy().to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_resampler_output_matches_ratio() {
+        // One second of a 440 Hz tone, fed in uneven chunks
+        let input: Vec<f32> = (0..48000)
+            .map(|i| (2.0 * std::f32::consts::PI * 440.0 * i as f32 / 48000.0).sin() * 0.5)
+            .collect();
+        for to_rate in [16000u32, 24000, 44100] {
+            let mut resampler = StreamResampler::new(48000, to_rate, 512).unwrap();
+            let mut output = Vec::new();
+            for chunk in input.chunks(700) {
+                output.extend(resampler.process(chunk).unwrap());
+            }
+            // Everything but the last partial chunk has been resampled
+            let resampled_input = input.len() - resampler.buffered();
+            assert_eq!(resampled_input % 512, 0);
+            let expected_len = (resampled_input as f64 * to_rate as f64 / 48000.0).round() as usize;
+            assert!(
+                output.len().abs_diff(expected_len) <= 2,
+                "{} Hz: {} samples, expected {}",
+                to_rate,
+                output.len(),
+                expected_len
+            );
+        }
+    }
+}
@@ -446,7 +446,7 @@ impl FFmpegAudioMixer {
 }
 
 /// Calculate RMS (Root Mean Square) for audio samples
-fn calculate_rms(samples: &[f32]) -> f32 {
+pub(crate) fn calculate_rms(samples: &[f32]) -> f32 {
     if samples.is_empty() {
         return 0.0;
     }
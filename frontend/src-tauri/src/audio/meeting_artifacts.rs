@@ -0,0 +1,345 @@
+//! Files inside a meeting folder: finding the recording, transcript or
+//! summary export for a meeting, and building the command that opens it or
+//! reveals it in the system file manager.
+
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Written by `RecordingSaver` next to the audio and transcript
+const METADATA_FILE: &str = "metadata.json";
+const DEFAULT_AUDIO_FILE: &str = "audio.mp4";
+const DEFAULT_TRANSCRIPT_FILE: &str = "transcripts.json";
+
+/// Checkpoint parts of a recording that was never merged into one file
+pub const CHECKPOINTS_DIR: &str = ".checkpoints";
+/// FFmpeg concat list listing the parts in order, one `file '<path>'` per line
+const PARTS_MANIFEST: &str = "concat_list.txt";
+const PART_PREFIX: &str = "audio_chunk_";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MeetingArtifact {
+    Recording,
+    TranscriptJson,
+    SummaryExport,
+}
+
+impl MeetingArtifact {
+    pub fn from_str(value: &str) -> Result<Self, String> {
+        match value {
+            "recording" => Ok(Self::Recording),
+            "transcript_json" => Ok(Self::TranscriptJson),
+            "summary_export" => Ok(Self::SummaryExport),
+            other => Err(format!(
+                "Unknown meeting artifact '{}'; expected recording, transcript_json or summary_export",
+                other
+            )),
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Recording => "Recording",
+            Self::TranscriptJson => "Transcript file",
+            Self::SummaryExport => "Summary export",
+        }
+    }
+}
+
+/// File names recorded in `metadata.json`; only the fields needed here
+#[derive(Debug, Default, Deserialize)]
+struct ArtifactNames {
+    audio_file: Option<String>,
+    transcript_file: Option<String>,
+}
+
+fn read_artifact_names(folder: &Path) -> ArtifactNames {
+    std::fs::read_to_string(folder.join(METADATA_FILE))
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Keeps names from `metadata.json` inside the folder
+fn file_in_folder(folder: &Path, name: Option<String>, default: &str) -> PathBuf {
+    let name = name
+        .filter(|name| {
+            let path = Path::new(name);
+            path.file_name().map_or(false, |file| file == path.as_os_str())
+        })
+        .unwrap_or_else(|| default.to_string());
+    folder.join(name)
+}
+
+/// Parts of an unmerged recording, in recording order
+///
+/// The concat list is preferred since it holds the order FFmpeg would merge
+/// in; without it the part files are sorted by name.
+fn recording_parts(folder: &Path) -> Vec<PathBuf> {
+    let checkpoints = folder.join(CHECKPOINTS_DIR);
+    if let Ok(manifest) = std::fs::read_to_string(checkpoints.join(PARTS_MANIFEST)) {
+        let parts: Vec<PathBuf> = manifest
+            .lines()
+            .filter_map(|line| {
+                let quoted = line.trim().strip_prefix("file ")?;
+                let path = quoted.trim().trim_matches('\'');
+                let path = Path::new(path);
+                // Relative entries are relative to the list file
+                Some(if path.is_absolute() {
+                    path.to_path_buf()
+                } else {
+                    checkpoints.join(path)
+                })
+            })
+            .filter(|path| path.is_file())
+            .collect();
+        if !parts.is_empty() {
+            return parts;
+        }
+    }
+
+    let mut parts: Vec<PathBuf> = std::fs::read_dir(&checkpoints)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .map_or(false, |name| name.starts_with(PART_PREFIX))
+        })
+        .collect();
+    parts.sort();
+    parts
+}
+
+/// Most recently written Markdown file in the folder
+fn latest_markdown(folder: &Path) -> Option<PathBuf> {
+    std::fs::read_dir(folder)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            entry.path().is_file()
+                && entry
+                    .path()
+                    .extension()
+                    .map_or(false, |ext| ext.eq_ignore_ascii_case("md"))
+        })
+        .max_by_key(|entry| entry.metadata().and_then(|m| m.modified()).ok())
+        .map(|entry| entry.path())
+}
+
+/// Finds the file for `artifact` inside a meeting folder and checks it exists
+///
+/// A recording that was never merged resolves to its first part.
+pub fn resolve_artifact(folder: &Path, artifact: MeetingArtifact) -> Result<PathBuf, String> {
+    if !folder.is_dir() {
+        return Err(format!("Recording folder not found: {}", folder.display()));
+    }
+
+    let names = read_artifact_names(folder);
+    let path = match artifact {
+        MeetingArtifact::Recording => {
+            let audio = file_in_folder(folder, names.audio_file, DEFAULT_AUDIO_FILE);
+            if audio.is_file() {
+                Some(audio)
+            } else {
+                recording_parts(folder).into_iter().next()
+            }
+        }
+        MeetingArtifact::TranscriptJson => {
+            Some(file_in_folder(folder, names.transcript_file, DEFAULT_TRANSCRIPT_FILE))
+                .filter(|path| path.is_file())
+        }
+        MeetingArtifact::SummaryExport => latest_markdown(folder),
+    };
+
+    path.ok_or_else(|| format!("{} not found in {}", artifact.label(), folder.display()))
+}
+
+/// Every file holding the meeting's audio: the merged recording and any
+/// checkpoint parts left next to it
+pub fn recording_files(folder: &Path) -> Vec<PathBuf> {
+    let names = read_artifact_names(folder);
+    let mut files: Vec<PathBuf> =
+        Some(file_in_folder(folder, names.audio_file, DEFAULT_AUDIO_FILE))
+            .filter(|path| path.is_file())
+            .into_iter()
+            .collect();
+    files.extend(
+        std::fs::read_dir(folder.join(CHECKPOINTS_DIR))
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file()),
+    );
+    files
+}
+
+/// Where the meeting's transcript JSON is, or would be
+pub fn transcript_file(folder: &Path) -> PathBuf {
+    file_in_folder(
+        folder,
+        read_artifact_names(folder).transcript_file,
+        DEFAULT_TRANSCRIPT_FILE,
+    )
+}
+
+/// Platform whose file manager conventions the open command follows
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OpenPlatform {
+    MacOs,
+    Windows,
+    Linux,
+}
+
+impl OpenPlatform {
+    pub fn current() -> Self {
+        if cfg!(target_os = "macos") {
+            Self::MacOs
+        } else if cfg!(windows) {
+            Self::Windows
+        } else {
+            Self::Linux
+        }
+    }
+}
+
+/// Program and arguments that open `path` with its default handler, or
+/// reveal it selected in the file manager
+///
+/// Linux has no common way to select a file, so revealing opens the folder
+/// holding it.
+pub fn open_command(
+    path: &Path,
+    reveal: bool,
+    platform: OpenPlatform,
+) -> (&'static str, Vec<String>) {
+    let path_arg = path.to_string_lossy().to_string();
+    match (platform, reveal) {
+        (OpenPlatform::MacOs, false) => ("open", vec![path_arg]),
+        (OpenPlatform::MacOs, true) => ("open", vec!["-R".to_string(), path_arg]),
+        (OpenPlatform::Windows, false) => ("explorer", vec![path_arg]),
+        // explorer expects the path glued to the switch
+        (OpenPlatform::Windows, true) => ("explorer", vec![format!("/select,{}", path_arg)]),
+        (OpenPlatform::Linux, false) => ("xdg-open", vec![path_arg]),
+        (OpenPlatform::Linux, true) => {
+            let folder = path.parent().unwrap_or(path);
+            ("xdg-open", vec![folder.to_string_lossy().to_string()])
+        }
+    }
+}
+
+/// Opens or reveals `path` using the current platform's command
+pub fn open_path(path: &Path, reveal: bool) -> Result<(), String> {
+    let (program, args) = open_command(path, reveal, OpenPlatform::current());
+    std::process::Command::new(program)
+        .args(&args)
+        .spawn()
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_artifacts_in_meeting_folder() {
+        let dir = tempfile::tempdir().unwrap();
+        let folder = dir.path();
+
+        // Nothing recorded yet
+        let err = resolve_artifact(folder, MeetingArtifact::Recording).unwrap_err();
+        assert!(err.starts_with("Recording not found in"), "{}", err);
+        assert!(resolve_artifact(folder, MeetingArtifact::SummaryExport).is_err());
+        assert!(resolve_artifact(&folder.join("gone"), MeetingArtifact::Recording)
+            .unwrap_err()
+            .starts_with("Recording folder not found"));
+
+        // Unmerged parts: the manifest order wins over name order
+        let checkpoints = folder.join(CHECKPOINTS_DIR);
+        std::fs::create_dir(&checkpoints).unwrap();
+        for part in ["audio_chunk_000.mp4", "audio_chunk_001.mp4"] {
+            std::fs::write(checkpoints.join(part), b"part").unwrap();
+        }
+        assert_eq!(
+            resolve_artifact(folder, MeetingArtifact::Recording).unwrap(),
+            checkpoints.join("audio_chunk_000.mp4")
+        );
+        let second = checkpoints.join("audio_chunk_001.mp4");
+        std::fs::write(
+            checkpoints.join(PARTS_MANIFEST),
+            format!("file '{}'\nfile 'audio_chunk_000.mp4'\n", second.display()),
+        )
+        .unwrap();
+        assert_eq!(resolve_artifact(folder, MeetingArtifact::Recording).unwrap(), second);
+
+        // metadata.json names win; names that leave the folder are ignored
+        std::fs::write(
+            folder.join(METADATA_FILE),
+            r#"{"audio_file": "call.m4a", "transcript_file": "../../etc/passwd"}"#,
+        )
+        .unwrap();
+        std::fs::write(folder.join("call.m4a"), b"audio").unwrap();
+        std::fs::write(folder.join(DEFAULT_TRANSCRIPT_FILE), b"{}").unwrap();
+        assert_eq!(
+            resolve_artifact(folder, MeetingArtifact::Recording).unwrap(),
+            folder.join("call.m4a")
+        );
+        assert_eq!(
+            resolve_artifact(folder, MeetingArtifact::TranscriptJson).unwrap(),
+            folder.join(DEFAULT_TRANSCRIPT_FILE)
+        );
+
+        let mut files = recording_files(folder);
+        files.sort();
+        assert_eq!(
+            files,
+            [
+                checkpoints.join("audio_chunk_000.mp4"),
+                checkpoints.join("audio_chunk_001.mp4"),
+                checkpoints.join(PARTS_MANIFEST),
+                folder.join("call.m4a"),
+            ]
+        );
+        assert_eq!(
+            transcript_file(folder),
+            folder.join(DEFAULT_TRANSCRIPT_FILE)
+        );
+
+        std::fs::write(folder.join("Weekly sync.md"), b"# Summary").unwrap();
+        assert_eq!(
+            resolve_artifact(folder, MeetingArtifact::SummaryExport).unwrap(),
+            folder.join("Weekly sync.md")
+        );
+    }
+
+    #[test]
+    fn test_open_command_per_platform() {
+        let path = Path::new("/meetings/Weekly sync/audio.mp4");
+        let args = |platform, reveal| open_command(path, reveal, platform);
+
+        assert_eq!(
+            args(OpenPlatform::MacOs, false),
+            ("open", vec!["/meetings/Weekly sync/audio.mp4".to_string()])
+        );
+        assert_eq!(
+            args(OpenPlatform::MacOs, true),
+            ("open", vec!["-R".to_string(), "/meetings/Weekly sync/audio.mp4".to_string()])
+        );
+        assert_eq!(
+            args(OpenPlatform::Windows, true),
+            ("explorer", vec!["/select,/meetings/Weekly sync/audio.mp4".to_string()])
+        );
+        assert_eq!(
+            args(OpenPlatform::Linux, true),
+            ("xdg-open", vec!["/meetings/Weekly sync".to_string()])
+        );
+        assert_eq!(
+            MeetingArtifact::from_str("transcript_json"),
+            Ok(MeetingArtifact::TranscriptJson)
+        );
+        assert!(MeetingArtifact::from_str("video").is_err());
+    }
+}
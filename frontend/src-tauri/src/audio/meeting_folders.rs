@@ -0,0 +1,649 @@
+//! Meeting folder names: turning titles into folder names that are valid
+//! everywhere, claiming a folder without sharing it with another meeting,
+//! repairing folders created before names were sanitized, and finding
+//! folders users moved elsewhere.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::HashSet;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use unicode_normalization::UnicodeNormalization;
+
+use crate::database::repositories::meeting::MeetingsRepository;
+
+/// Longest sanitized title in bytes, leaving room for the timestamp and a
+/// collision suffix within the 255-byte name limit of most filesystems
+pub const MAX_FOLDER_NAME_BYTES: usize = 120;
+
+/// Used when nothing is left of the title after sanitizing
+const FALLBACK_FOLDER_NAME: &str = "Meeting";
+
+/// Device names Windows reserves, whatever the extension
+const WINDOWS_RESERVED_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Files `RecordingSaver` writes into every meeting folder; a folder holding
+/// neither isn't a recording folder
+const MEETING_FOLDER_FILES: &[&str] = &["metadata.json", "transcripts.json"];
+
+/// Levels below a recordings root searched for a moved folder
+const RELOCATE_SEARCH_DEPTH: usize = 3;
+
+/// `_YYYY-MM-DD_HH-MM` added by `create_meeting_folder`, plus any collision suffix
+static TIMESTAMP_SUFFIX_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"_\d{4}-\d{2}-\d{2}_\d{2}-\d{2}(?:-\d+)?$").unwrap());
+
+/// Platform whose naming rules a folder name must satisfy
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FolderRules {
+    Windows,
+    MacOs,
+    Unix,
+}
+
+impl FolderRules {
+    pub fn current() -> Self {
+        if cfg!(windows) {
+            Self::Windows
+        } else if cfg!(target_os = "macos") {
+            Self::MacOs
+        } else {
+            Self::Unix
+        }
+    }
+
+    fn is_reserved(self, c: char) -> bool {
+        if c.is_control() {
+            return true;
+        }
+        match self {
+            Self::Windows => matches!(c, '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*'),
+            // Finder shows ':' as '/'; '\\' is kept out so folders survive a sync to Windows
+            Self::MacOs => matches!(c, '/' | ':' | '\\'),
+            Self::Unix => matches!(c, '/' | '\\'),
+        }
+    }
+}
+
+/// Emoji and the joiners/selectors that build them; some filesystems and
+/// sync tools reject or mangle these
+fn is_emoji(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x1F000..=0x1FAFF // emoji, pictographs, flags
+            | 0x2600..=0x27BF // misc symbols, dingbats
+            | 0x2B00..=0x2BFF // arrows, stars
+            | 0xFE00..=0xFE0F // variation selectors
+            | 0x200D // zero-width joiner
+            | 0xE0020..=0xE007F // tag characters
+    )
+}
+
+/// Cuts `name` to at most `max_bytes` without splitting a character
+fn truncate_bytes(name: &str, max_bytes: usize) -> &str {
+    if name.len() <= max_bytes {
+        return name;
+    }
+    let mut end = max_bytes;
+    while !name.is_char_boundary(end) {
+        end -= 1;
+    }
+    &name[..end]
+}
+
+/// Turns a meeting title into a folder name for the current platform
+pub fn sanitize_folder_name(name: &str) -> String {
+    sanitize_folder_name_for(name, FolderRules::current())
+}
+
+/// Turns a meeting title into a folder name valid under `rules`
+///
+/// The title is NFC-normalized (macOS hands out decomposed names, which
+/// would otherwise not match the same title typed elsewhere), reserved and
+/// control characters become `_`, emoji are dropped, whitespace is
+/// collapsed, leading and trailing dots and spaces are trimmed and the
+/// result is cut to `MAX_FOLDER_NAME_BYTES`. Windows device names get a
+/// trailing `_`.
+pub fn sanitize_folder_name_for(name: &str, rules: FolderRules) -> String {
+    let mut sanitized = String::with_capacity(name.len());
+    for c in name.nfc() {
+        let c = if rules.is_reserved(c) {
+            '_'
+        } else if is_emoji(c) {
+            continue;
+        } else if c.is_whitespace() {
+            ' '
+        } else {
+            c
+        };
+        // Collapse runs left behind by replaced characters and spacing
+        if (c == ' ' || c == '_') && sanitized.ends_with(c) {
+            continue;
+        }
+        sanitized.push(c);
+    }
+
+    let trim = |s: &str| s.trim_matches(|c: char| c == ' ' || c == '.').to_string();
+    let mut sanitized = trim(truncate_bytes(&trim(&sanitized), MAX_FOLDER_NAME_BYTES));
+
+    if rules == FolderRules::Windows {
+        let stem_len = sanitized.find('.').unwrap_or(sanitized.len());
+        let stem = &sanitized[..stem_len];
+        if WINDOWS_RESERVED_NAMES
+            .iter()
+            .any(|reserved| reserved.eq_ignore_ascii_case(stem))
+        {
+            sanitized.insert(stem_len, '_');
+        }
+    }
+
+    if sanitized.is_empty() {
+        FALLBACK_FOLDER_NAME.to_string()
+    } else {
+        sanitized
+    }
+}
+
+/// Creates `base/name`, or `base/name-2`, `base/name-3`... when taken
+///
+/// The folder is claimed with a single `create_dir`, so two recordings
+/// started in the same minute under the same title can't end up sharing it.
+pub fn create_unique_folder(base: &Path, name: &str) -> std::io::Result<PathBuf> {
+    std::fs::create_dir_all(base)?;
+    let mut attempt = 1;
+    loop {
+        let candidate = if attempt == 1 {
+            base.join(name)
+        } else {
+            base.join(format!("{}-{}", name, attempt))
+        };
+        match std::fs::create_dir(&candidate) {
+            Ok(()) => return Ok(candidate),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => attempt += 1,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Conforming name for an existing folder, keeping its timestamp suffix
+fn conforming_name(name: &str, rules: FolderRules) -> String {
+    let (title, suffix) = match TIMESTAMP_SUFFIX_RE.find(name) {
+        Some(m) => (&name[..m.start()], m.as_str()),
+        None => (name, ""),
+    };
+    format!("{}{}", sanitize_folder_name_for(title, rules), suffix)
+}
+
+/// A folder to rename and the meetings pointing at it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FolderRename {
+    pub meeting_ids: Vec<String>,
+    pub from: String,
+    pub to: String,
+}
+
+/// What `repair_meeting_folders` renamed, or would rename on a dry run
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct FolderRepairReport {
+    pub dry_run: bool,
+    pub renames: Vec<FolderRename>,
+    /// Folder paths stored for meetings that no longer exist on disk
+    pub missing: Vec<String>,
+}
+
+/// Works out the renames for `(meeting_id, folder_path)` pairs
+///
+/// Meetings sharing a folder are renamed together. Targets are picked so
+/// they collide neither with folders on disk nor with each other.
+pub fn plan_folder_repairs(folders: &[(String, String)], rules: FolderRules) -> FolderRepairReport {
+    let mut report = FolderRepairReport::default();
+    let mut taken: HashSet<PathBuf> = HashSet::new();
+
+    for (meeting_id, folder_path) in folders {
+        if let Some(rename) = report.renames.iter_mut().find(|r| &r.from == folder_path) {
+            rename.meeting_ids.push(meeting_id.clone());
+            continue;
+        }
+        let path = Path::new(folder_path);
+        let (Some(parent), Some(name)) = (path.parent(), path.file_name()) else {
+            continue;
+        };
+        let name = name.to_string_lossy();
+        let target_name = conforming_name(&name, rules);
+        if target_name == name {
+            continue;
+        }
+        if !path.is_dir() {
+            if !report.missing.contains(folder_path) {
+                report.missing.push(folder_path.clone());
+            }
+            continue;
+        }
+
+        let mut attempt = 1;
+        let target = loop {
+            let candidate = if attempt == 1 {
+                parent.join(&target_name)
+            } else {
+                parent.join(format!("{}-{}", target_name, attempt))
+            };
+            if !candidate.exists() && !taken.contains(&candidate) {
+                break candidate;
+            }
+            attempt += 1;
+        };
+        taken.insert(target.clone());
+        report.renames.push(FolderRename {
+            meeting_ids: vec![meeting_id.clone()],
+            from: folder_path.clone(),
+            to: target.to_string_lossy().to_string(),
+        });
+    }
+    report
+}
+
+/// Puts already renamed folders back after a later step failed
+fn undo_renames(done: &[&FolderRename]) {
+    for rename in done.iter().rev() {
+        if let Err(e) = std::fs::rename(&rename.to, &rename.from) {
+            log::error!(
+                "❌ Failed to restore folder {} (now at {}): {}",
+                rename.from,
+                rename.to,
+                e
+            );
+        }
+    }
+}
+
+/// Renames meeting folders whose names don't conform to the current
+/// platform's rules and points the meetings at the new paths
+///
+/// With `dry_run` nothing is touched and the report lists what would change.
+/// Otherwise either every folder is renamed and every meeting updated, or
+/// the folders are put back and an error is returned.
+pub async fn repair_meeting_folders(
+    pool: &SqlitePool,
+    dry_run: bool,
+) -> Result<FolderRepairReport, String> {
+    let folders = MeetingsRepository::get_folder_paths(pool)
+        .await
+        .map_err(|e| format!("Failed to load meeting folders: {}", e))?;
+    let mut report = plan_folder_repairs(&folders, FolderRules::current());
+    report.dry_run = dry_run;
+    if dry_run || report.renames.is_empty() {
+        return Ok(report);
+    }
+
+    let mut done: Vec<&FolderRename> = Vec::new();
+    for rename in &report.renames {
+        if let Err(e) = std::fs::rename(&rename.from, &rename.to) {
+            undo_renames(&done);
+            return Err(format!(
+                "Failed to rename {} to {}: {}",
+                rename.from, rename.to, e
+            ));
+        }
+        done.push(rename);
+    }
+
+    let paths: Vec<(String, String)> = report
+        .renames
+        .iter()
+        .map(|rename| (rename.from.clone(), rename.to.clone()))
+        .collect();
+    if let Err(e) = MeetingsRepository::update_folder_paths(pool, &paths).await {
+        undo_renames(&done);
+        return Err(format!("Failed to update meeting folders: {}", e));
+    }
+
+    log::info!("📁 Repaired {} meeting folder(s)", report.renames.len());
+    Ok(report)
+}
+
+/// Where a meeting's recording folder stands
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum MeetingFolderState {
+    /// The meeting was never linked to a folder
+    NotSet,
+    Found { path: String },
+    /// The stored folder is gone; `candidate` is a folder found under the
+    /// recordings roots that it could be relocated to
+    Missing {
+        path: String,
+        candidate: Option<String>,
+    },
+}
+
+/// Only the field of `metadata.json` needed to tell meetings apart
+#[derive(Debug, Default, Deserialize)]
+struct FolderMetadata {
+    meeting_id: Option<String>,
+}
+
+/// Checks `path` is a recording folder that can belong to `meeting_id`
+///
+/// It has to hold the metadata or transcript file, and a meeting id in the
+/// metadata has to match.
+pub fn check_meeting_folder(path: &Path, meeting_id: &str) -> Result<(), String> {
+    if !path.is_dir() {
+        return Err(format!("Folder not found: {}", path.display()));
+    }
+    if !MEETING_FOLDER_FILES
+        .iter()
+        .any(|file| path.join(file).is_file())
+    {
+        return Err(format!(
+            "{} is not a recording folder: expected {}",
+            path.display(),
+            MEETING_FOLDER_FILES.join(" or ")
+        ));
+    }
+    let metadata: FolderMetadata = std::fs::read_to_string(path.join(MEETING_FOLDER_FILES[0]))
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+    match metadata.meeting_id {
+        Some(id) if id != meeting_id => Err(format!(
+            "{} holds the recording of another meeting ({})",
+            path.display(),
+            id
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Looks under `roots` for a folder named like `old_path` that passes
+/// `check_meeting_folder`, breadth first and at most
+/// `RELOCATE_SEARCH_DEPTH` levels down
+pub fn find_moved_folder(roots: &[PathBuf], old_path: &str, meeting_id: &str) -> Option<PathBuf> {
+    let name = Path::new(old_path).file_name()?;
+    let mut level: Vec<PathBuf> = roots.iter().filter(|root| root.is_dir()).cloned().collect();
+    for _ in 0..RELOCATE_SEARCH_DEPTH {
+        let mut next = Vec::new();
+        for dir in &level {
+            let Ok(entries) = std::fs::read_dir(dir) else {
+                continue;
+            };
+            for path in entries.filter_map(|entry| entry.ok().map(|entry| entry.path())) {
+                if !path.is_dir() {
+                    continue;
+                }
+                if path.file_name() == Some(name)
+                    && check_meeting_folder(&path, meeting_id).is_ok()
+                {
+                    return Some(path);
+                }
+                next.push(path);
+            }
+        }
+        level = next;
+    }
+    None
+}
+
+/// The state of the meeting's stored folder, searching `roots` when it's gone
+pub async fn meeting_folder_state(
+    pool: &SqlitePool,
+    meeting_id: &str,
+    roots: &[PathBuf],
+) -> Result<MeetingFolderState, String> {
+    let folder_path = MeetingsRepository::get_folder_path(pool, meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load meeting folder: {}", e))?;
+    Ok(match folder_path {
+        None => MeetingFolderState::NotSet,
+        Some(path) if Path::new(&path).is_dir() => MeetingFolderState::Found { path },
+        Some(path) => {
+            let candidate = find_moved_folder(roots, &path, meeting_id)
+                .map(|found| found.to_string_lossy().to_string());
+            MeetingFolderState::Missing { path, candidate }
+        }
+    })
+}
+
+/// Points the meeting at its moved recording folder and returns the new path
+///
+/// `new_path` is checked with `check_meeting_folder`; without it the folder
+/// is searched for under `roots`.
+pub async fn relocate_meeting_folder(
+    pool: &SqlitePool,
+    meeting_id: &str,
+    new_path: Option<&str>,
+    roots: &[PathBuf],
+) -> Result<String, String> {
+    let path = match new_path.map(str::trim).filter(|path| !path.is_empty()) {
+        Some(path) => {
+            let path = PathBuf::from(path);
+            check_meeting_folder(&path, meeting_id)?;
+            path
+        }
+        None => {
+            let old_path = MeetingsRepository::get_folder_path(pool, meeting_id)
+                .await
+                .map_err(|e| format!("Failed to load meeting folder: {}", e))?
+                .ok_or_else(|| "Recording folder path not available for this meeting".to_string())?;
+            find_moved_folder(roots, &old_path, meeting_id).ok_or_else(|| {
+                format!(
+                    "Recording folder {} was not found under the recordings folder; choose it manually",
+                    old_path
+                )
+            })?
+        }
+    };
+
+    let path = path.to_string_lossy().to_string();
+    let updated = MeetingsRepository::set_folder_path(pool, meeting_id, &path)
+        .await
+        .map_err(|e| format!("Failed to update meeting folder: {}", e))?;
+    if !updated {
+        return Err("Meeting not found".to_string());
+    }
+    log::info!("📁 Relocated recording folder of meeting {} to {}", meeting_id, path);
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::repositories::transcript::TranscriptsRepository;
+    use crate::database::test_utils::test_pool;
+
+    #[test]
+    fn test_windows_reserved_names_and_characters() {
+        let windows = FolderRules::Windows;
+        assert_eq!(sanitize_folder_name_for("CON", windows), "CON_");
+        assert_eq!(sanitize_folder_name_for("nul.txt", windows), "nul_.txt");
+        assert_eq!(sanitize_folder_name_for("Com1", windows), "Com1_");
+        assert_eq!(sanitize_folder_name_for("CONSOLE review", windows), "CONSOLE review");
+        assert_eq!(
+            sanitize_folder_name_for("Q3: Plan / Review?", windows),
+            "Q3_ Plan _ Review_"
+        );
+        assert_eq!(sanitize_folder_name_for("Retro...", windows), "Retro");
+        // Only Windows reserves device names
+        assert_eq!(sanitize_folder_name_for("CON", FolderRules::Unix), "CON");
+        assert_eq!(sanitize_folder_name_for("a:b", FolderRules::Unix), "a:b");
+        assert_eq!(sanitize_folder_name_for("a:b", FolderRules::MacOs), "a_b");
+    }
+
+    #[test]
+    fn test_long_titles_emoji_and_unicode() {
+        let long = "é".repeat(300);
+        let sanitized = sanitize_folder_name_for(&long, FolderRules::Unix);
+        assert_eq!(sanitized.len(), MAX_FOLDER_NAME_BYTES);
+        assert!(sanitized.chars().all(|c| c == 'é'));
+
+        assert_eq!(
+            sanitize_folder_name_for("🚀 Launch 👩‍💻  sync ✅", FolderRules::Unix),
+            "Launch sync"
+        );
+        // Decomposed "é" (macOS) becomes the same name as the composed one
+        assert_eq!(sanitize_folder_name_for("Cafe\u{301}", FolderRules::Unix), "Café");
+        assert_eq!(sanitize_folder_name_for("🎉", FolderRules::Unix), "Meeting");
+        assert_eq!(sanitize_folder_name_for("..", FolderRules::Unix), "Meeting");
+    }
+
+    #[test]
+    fn test_same_name_gets_numbered_folder() {
+        let base = tempfile::tempdir().unwrap();
+        let first = create_unique_folder(base.path(), "Standup_2025-01-06_09-00").unwrap();
+        let second = create_unique_folder(base.path(), "Standup_2025-01-06_09-00").unwrap();
+        let third = create_unique_folder(base.path(), "Standup_2025-01-06_09-00").unwrap();
+        assert_eq!(first, base.path().join("Standup_2025-01-06_09-00"));
+        assert_eq!(second, base.path().join("Standup_2025-01-06_09-00-2"));
+        assert_eq!(third, base.path().join("Standup_2025-01-06_09-00-3"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_repair_dry_run_reports_then_renames() {
+        let pool = test_pool().await;
+        let base = tempfile::tempdir().unwrap();
+        let path = |name: &str| base.path().join(name).to_string_lossy().to_string();
+
+        // Two meetings sharing a folder whose name needs fixing, one missing
+        // folder and one that already conforms
+        let bad = path("Plan\\Review 🚀_2025-01-06_09-00");
+        std::fs::create_dir(&bad).unwrap();
+        std::fs::write(Path::new(&bad).join("audio.mp4"), b"audio").unwrap();
+        // The sanitized name is already taken
+        std::fs::create_dir(path("Plan_Review_2025-01-06_09-00")).unwrap();
+        let good = path("Retro_2025-01-06_10-00");
+        std::fs::create_dir(&good).unwrap();
+        let missing = path("Gone\\Meeting_2025-01-06_11-00");
+
+        let first = TranscriptsRepository::create_meeting(&pool, "Plan", 0, Some(bad.clone()))
+            .await
+            .unwrap();
+        let second = TranscriptsRepository::create_meeting(&pool, "Plan", 0, Some(bad.clone()))
+            .await
+            .unwrap();
+        TranscriptsRepository::create_meeting(&pool, "Retro", 0, Some(good.clone()))
+            .await
+            .unwrap();
+        TranscriptsRepository::create_meeting(&pool, "Gone", 0, Some(missing.clone()))
+            .await
+            .unwrap();
+
+        let expected_target = path("Plan_Review_2025-01-06_09-00-2");
+        let report = repair_meeting_folders(&pool, true).await.unwrap();
+        assert!(report.dry_run);
+        assert_eq!(
+            report.renames,
+            vec![FolderRename {
+                meeting_ids: vec![first.clone(), second.clone()],
+                from: bad.clone(),
+                to: expected_target.clone(),
+            }]
+        );
+        assert_eq!(report.missing, vec![missing]);
+        // Dry run leaves disk and database alone
+        assert!(Path::new(&bad).is_dir());
+        let folders = MeetingsRepository::get_folder_paths(&pool).await.unwrap();
+        assert!(folders.iter().any(|(_, folder)| folder == &bad));
+
+        let report = repair_meeting_folders(&pool, false).await.unwrap();
+        assert!(!report.dry_run);
+        assert_eq!(report.renames.len(), 1);
+        assert!(!Path::new(&bad).exists());
+        assert!(Path::new(&expected_target).join("audio.mp4").is_file());
+        let folders = MeetingsRepository::get_folder_paths(&pool).await.unwrap();
+        for id in [&first, &second] {
+            assert!(folders.contains(&(id.clone(), expected_target.clone())));
+        }
+
+        // Nothing left to do
+        let report = repair_meeting_folders(&pool, false).await.unwrap();
+        assert!(report.renames.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_relocate_to_moved_folder() {
+        let pool = test_pool().await;
+        let base = tempfile::tempdir().unwrap();
+        let old = base.path().join("recordings").join("Standup_2025-01-06_09-00");
+        let meeting = TranscriptsRepository::create_meeting(
+            &pool,
+            "Standup",
+            0,
+            Some(old.to_string_lossy().to_string()),
+        )
+        .await
+        .unwrap();
+        let roots = vec![base.path().join("archive")];
+        assert_eq!(
+            meeting_folder_state(&pool, &meeting, &roots).await.unwrap(),
+            MeetingFolderState::Missing {
+                path: old.to_string_lossy().to_string(),
+                candidate: None,
+            }
+        );
+
+        // The user moved it into a subfolder of the archive
+        let moved = base.path().join("archive").join("2025").join("Standup_2025-01-06_09-00");
+        std::fs::create_dir_all(&moved).unwrap();
+        std::fs::write(
+            moved.join("metadata.json"),
+            format!(r#"{{"meeting_id": "{}"}}"#, meeting),
+        )
+        .unwrap();
+        let moved_path = moved.to_string_lossy().to_string();
+        assert_eq!(
+            meeting_folder_state(&pool, &meeting, &roots).await.unwrap(),
+            MeetingFolderState::Missing {
+                path: old.to_string_lossy().to_string(),
+                candidate: Some(moved_path.clone()),
+            }
+        );
+
+        assert_eq!(
+            relocate_meeting_folder(&pool, &meeting, None, &roots).await.unwrap(),
+            moved_path
+        );
+        assert_eq!(
+            meeting_folder_state(&pool, &meeting, &roots).await.unwrap(),
+            MeetingFolderState::Found { path: moved_path }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_relocate_rejects_folder_without_recording() {
+        let pool = test_pool().await;
+        let base = tempfile::tempdir().unwrap();
+        let old = base.path().join("Retro_2025-01-06_10-00").to_string_lossy().to_string();
+        let meeting = TranscriptsRepository::create_meeting(&pool, "Retro", 0, Some(old.clone()))
+            .await
+            .unwrap();
+
+        let empty = base.path().join("empty");
+        std::fs::create_dir(&empty).unwrap();
+        let err = relocate_meeting_folder(&pool, &meeting, Some(&empty.to_string_lossy()), &[])
+            .await
+            .unwrap_err();
+        assert!(err.contains("is not a recording folder"), "{}", err);
+
+        let other = base.path().join("other");
+        std::fs::create_dir(&other).unwrap();
+        std::fs::write(other.join("metadata.json"), r#"{"meeting_id": "meeting-x"}"#).unwrap();
+        let err = relocate_meeting_folder(&pool, &meeting, Some(&other.to_string_lossy()), &[])
+            .await
+            .unwrap_err();
+        assert!(err.contains("another meeting"), "{}", err);
+
+        // Nothing found to relocate to; the stored path is left alone
+        assert!(relocate_meeting_folder(&pool, &meeting, None, &[base.path().to_path_buf()])
+            .await
+            .is_err());
+        assert_eq!(
+            MeetingsRepository::get_folder_path(&pool, &meeting).await.unwrap(),
+            Some(old)
+        );
+    }
+}
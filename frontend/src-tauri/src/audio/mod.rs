@@ -22,8 +22,12 @@ pub mod stream;
 pub mod recording_manager;
 pub mod recording_commands;
 pub mod recording_preferences;
+pub mod recording_registry;
 pub mod recording_saver;
 pub mod incremental_saver;  // NEW: Incremental audio saving with checkpoints
+pub mod meeting_folders;
+pub mod meeting_artifacts;
+pub mod recording_level;
 pub mod level_monitor;
 pub mod simple_level_monitor;
 pub mod buffer_pool;
@@ -61,7 +65,8 @@ pub use system_detector::{
 
 // Export system audio commands
 pub use system_audio_commands::{
-    start_system_audio_capture_command, list_system_audio_devices_command,
+    start_system_audio_capture_command, stop_system_audio_capture_command,
+    list_system_audio_devices_command,
     check_system_audio_permissions_command, start_system_audio_monitoring,
     stop_system_audio_monitoring, get_system_audio_monitoring_status,
     init_system_audio_state
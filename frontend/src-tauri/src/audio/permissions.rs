@@ -1,6 +1,7 @@
 // macOS audio permissions handling
 use anyhow::Result;
 use log::{info, warn, error};
+use serde::Serialize;
 
 #[cfg(target_os = "macos")]
 use std::process::Command;
@@ -150,13 +151,297 @@ pub async fn trigger_system_audio_permission_command() -> Result<(), String> {
         .map_err(|e| e.to_string())
 }
 
+/// Where the user enables Audio Capture once they have declined the prompt
+pub const AUDIO_CAPTURE_SETTINGS_URL: &str =
+    "x-apple.systempreferences:com.apple.preference.security?Privacy_AudioCapture";
+
+/// State of the macOS Audio Capture permission needed for Core Audio taps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AudioCapturePermission {
+    /// The user was never asked; creating a tap shows the prompt
+    NotDetermined,
+    /// The user declined; macOS won't prompt again, only System Settings helps
+    Denied,
+    Granted,
+}
+
+/// Returned when system audio capture is started without Audio Capture permission
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "Audio Capture permission denied - enable it in System Settings → Privacy & Security → Audio Capture"
+)]
+pub struct PermissionDenied;
+
+/// Where the permission state comes from; mocked in tests
+pub trait AudioPermissionProbe {
+    /// What TCC reports, without prompting; None if it can't be asked
+    fn preflight(&self) -> Option<AudioCapturePermission>;
+
+    /// Creates and drops a Core Audio tap, which prompts if the user was
+    /// never asked
+    fn probe_tap(&self) -> Result<(), String>;
+}
+
+/// Asks the operating system
+pub struct SystemPermissionProbe;
+
+#[cfg(target_os = "macos")]
+mod tcc {
+    use super::AudioCapturePermission;
+    use std::os::raw::{c_char, c_int, c_void};
+
+    const TCC_FRAMEWORK: &[u8] = b"/System/Library/PrivateFrameworks/TCC.framework/TCC\0";
+    const PREFLIGHT_SYMBOL: &[u8] = b"TCCAccessPreflight\0";
+    const AUDIO_CAPTURE_SERVICE: &[u8] = b"kTCCServiceAudioCapture\0";
+    const RTLD_LAZY: c_int = 0x1;
+    const CF_STRING_ENCODING_UTF8: u32 = 0x0800_0100;
+
+    extern "C" {
+        fn dlopen(path: *const c_char, mode: c_int) -> *mut c_void;
+        fn dlsym(handle: *mut c_void, symbol: *const c_char) -> *mut c_void;
+    }
+
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        fn CFStringCreateWithCString(
+            alloc: *const c_void,
+            c_str: *const c_char,
+            encoding: u32,
+        ) -> *const c_void;
+        fn CFRelease(cf: *const c_void);
+    }
+
+    type PreflightFn =
+        unsafe extern "C" fn(service: *const c_void, options: *const c_void) -> c_int;
+
+    /// `TCCAccessPreflight` is private API, so it is looked up at runtime and
+    /// any answer other than granted (0), denied (1) or unknown (2) is ignored
+    pub fn preflight() -> Option<AudioCapturePermission> {
+        unsafe {
+            let framework = dlopen(TCC_FRAMEWORK.as_ptr() as *const c_char, RTLD_LAZY);
+            if framework.is_null() {
+                return None;
+            }
+            let symbol = dlsym(framework, PREFLIGHT_SYMBOL.as_ptr() as *const c_char);
+            if symbol.is_null() {
+                return None;
+            }
+            let preflight: PreflightFn = std::mem::transmute(symbol);
+            let service = CFStringCreateWithCString(
+                std::ptr::null(),
+                AUDIO_CAPTURE_SERVICE.as_ptr() as *const c_char,
+                CF_STRING_ENCODING_UTF8,
+            );
+            if service.is_null() {
+                return None;
+            }
+            let result = preflight(service, std::ptr::null());
+            CFRelease(service);
+            match result {
+                0 => Some(AudioCapturePermission::Granted),
+                1 => Some(AudioCapturePermission::Denied),
+                2 => Some(AudioCapturePermission::NotDetermined),
+                _ => None,
+            }
+        }
+    }
+}
+
+impl AudioPermissionProbe for SystemPermissionProbe {
+    #[cfg(target_os = "macos")]
+    fn preflight(&self) -> Option<AudioCapturePermission> {
+        tcc::preflight()
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    fn preflight(&self) -> Option<AudioCapturePermission> {
+        // No Audio Capture permission here; only device access matters
+        Some(if crate::audio::check_system_audio_permissions() {
+            AudioCapturePermission::Granted
+        } else {
+            AudioCapturePermission::Denied
+        })
+    }
+
+    #[cfg(target_os = "macos")]
+    fn probe_tap(&self) -> Result<(), String> {
+        crate::audio::capture::CoreAudioCapture::new(None)
+            .and_then(|capture| capture.stream())
+            .map(|_stream| ())
+            .map_err(|e| e.to_string())
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    fn probe_tap(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+fn is_permission_error(error: &str) -> bool {
+    let error = error.to_lowercase();
+    error.contains("permission") || error.contains("not authorized") || error.contains("denied")
+}
+
+/// Works out the permission state, preferring TCC over a tap probe
+///
+/// Without TCC a tap that opens counts as granted (a denied tap may still
+/// open and record silence), one that fails with a permission error as
+/// denied, and any other failure as not determined.
+pub fn resolve_audio_capture_permission(
+    probe: &dyn AudioPermissionProbe,
+) -> AudioCapturePermission {
+    if let Some(state) = probe.preflight() {
+        return state;
+    }
+    match probe.probe_tap() {
+        Ok(()) => AudioCapturePermission::Granted,
+        Err(e) if is_permission_error(&e) => AudioCapturePermission::Denied,
+        Err(e) => {
+            warn!("⚠️ Audio Capture permission unknown, tap probe failed: {}", e);
+            AudioCapturePermission::NotDetermined
+        }
+    }
+}
+
+/// Outcome of `api_request_audio_permission`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AudioPermissionRequest {
+    pub state: AudioCapturePermission,
+    /// True if the OS prompt was triggered; the answer arrives later
+    pub prompted: bool,
+    /// System Settings pane to open when the OS won't prompt again
+    pub settings_url: Option<String>,
+}
+
+/// Prompts for Audio Capture if the user was never asked, otherwise points
+/// at System Settings when it was denied
+pub fn request_audio_capture_permission(
+    probe: &dyn AudioPermissionProbe,
+) -> AudioPermissionRequest {
+    let state = resolve_audio_capture_permission(probe);
+    if state != AudioCapturePermission::NotDetermined {
+        return AudioPermissionRequest {
+            state,
+            prompted: false,
+            settings_url: (state == AudioCapturePermission::Denied)
+                .then(|| AUDIO_CAPTURE_SETTINGS_URL.to_string()),
+        };
+    }
+
+    info!("🔐 Prompting for Audio Capture permission...");
+    if let Err(e) = probe.probe_tap() {
+        warn!("⚠️ Audio Capture prompt tap failed: {}", e);
+    }
+    let state = probe.preflight().unwrap_or(AudioCapturePermission::NotDetermined);
+    AudioPermissionRequest {
+        state,
+        prompted: true,
+        settings_url: (state == AudioCapturePermission::Denied)
+            .then(|| AUDIO_CAPTURE_SETTINGS_URL.to_string()),
+    }
+}
+
+/// Tauri command to prompt for Audio Capture permission, or get the System
+/// Settings link when the prompt can't be shown again
+#[tauri::command]
+pub async fn api_request_audio_permission() -> Result<AudioPermissionRequest, String> {
+    tokio::task::spawn_blocking(|| request_audio_capture_permission(&SystemPermissionProbe))
+        .await
+        .map_err(|e| format!("Audio permission request failed: {}", e))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use std::cell::Cell;
+
     #[test]
     fn test_check_permission() {
         let has_permission = check_screen_recording_permission();
         println!("Has Screen Recording permission: {}", has_permission);
     }
+
+    /// Stands in for TCC and Core Audio; a tap grants the permission when
+    /// `grant_on_tap` is set, as accepting the prompt would
+    struct FakeProbe {
+        preflight: Cell<Option<AudioCapturePermission>>,
+        tap_error: Option<&'static str>,
+        grant_on_tap: bool,
+        taps: Cell<u32>,
+    }
+
+    impl FakeProbe {
+        fn new(preflight: Option<AudioCapturePermission>, tap_error: Option<&'static str>) -> Self {
+            Self {
+                preflight: Cell::new(preflight),
+                tap_error,
+                grant_on_tap: false,
+                taps: Cell::new(0),
+            }
+        }
+    }
+
+    impl AudioPermissionProbe for FakeProbe {
+        fn preflight(&self) -> Option<AudioCapturePermission> {
+            self.preflight.get()
+        }
+
+        fn probe_tap(&self) -> Result<(), String> {
+            self.taps.set(self.taps.get() + 1);
+            if self.grant_on_tap {
+                self.preflight.set(Some(AudioCapturePermission::Granted));
+            }
+            self.tap_error.map_or(Ok(()), |e| Err(e.to_string()))
+        }
+    }
+
+    #[test]
+    fn test_permission_state_from_preflight_or_tap() {
+        use AudioCapturePermission::*;
+
+        // TCC answers: no tap is created
+        for state in [NotDetermined, Denied, Granted] {
+            let probe = FakeProbe::new(Some(state), None);
+            assert_eq!(resolve_audio_capture_permission(&probe), state);
+            assert_eq!(probe.taps.get(), 0);
+        }
+
+        // Without TCC the tap decides
+        assert_eq!(resolve_audio_capture_permission(&FakeProbe::new(None, None)), Granted);
+        let probe = FakeProbe::new(None, Some("Audio capture not authorized (permission denied)"));
+        assert_eq!(resolve_audio_capture_permission(&probe), Denied);
+        let probe = FakeProbe::new(None, Some("No default output device"));
+        assert_eq!(resolve_audio_capture_permission(&probe), NotDetermined);
+    }
+
+    #[test]
+    fn test_request_prompts_only_when_never_asked() {
+        use AudioCapturePermission::*;
+
+        let probe = FakeProbe::new(Some(Granted), None);
+        assert_eq!(
+            request_audio_capture_permission(&probe),
+            AudioPermissionRequest { state: Granted, prompted: false, settings_url: None }
+        );
+        assert_eq!(probe.taps.get(), 0);
+
+        // Denied: the OS won't prompt again, so link to System Settings
+        let probe = FakeProbe::new(Some(Denied), None);
+        let request = request_audio_capture_permission(&probe);
+        assert_eq!(request.state, Denied);
+        assert!(!request.prompted);
+        assert_eq!(request.settings_url.as_deref(), Some(AUDIO_CAPTURE_SETTINGS_URL));
+        assert_eq!(probe.taps.get(), 0);
+
+        // Never asked: the tap shows the prompt
+        let mut probe = FakeProbe::new(Some(NotDetermined), None);
+        probe.grant_on_tap = true;
+        assert_eq!(
+            request_audio_capture_permission(&probe),
+            AudioPermissionRequest { state: Granted, prompted: true, settings_url: None }
+        );
+        assert_eq!(probe.taps.get(), 1);
+    }
 }
\ No newline at end of file
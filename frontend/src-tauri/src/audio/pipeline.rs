@@ -6,14 +6,47 @@ use anyhow::Result;
 use log::{debug, error, info, warn};
 use crate::{perf_debug, batch_audio_metric};
 use super::batch_processor::AudioMetricsBatcher;
-use rubato::{Resampler, SincFixedIn, SincInterpolationParameters, SincInterpolationType, WindowFunction};
+use std::sync::atomic::AtomicU32;
 
 use super::devices::AudioDevice;
 use super::recording_state::{AudioChunk, AudioError, RecordingState, DeviceType};
 use super::telemetry::{AudioTelemetryEvent, emit_telemetry_event};
-use super::audio_processing::{audio_to_mono, LoudnessNormalizer, NoiseSuppressionProcessor, HighPassFilter};
+use super::audio_processing::{audio_to_mono, LoudnessNormalizer, NoiseSuppressionProcessor, HighPassFilter, StreamResampler};
 use super::vad::{ContinuousVadProcessor};
 
+/// Rate the capture pipeline runs at unless configured otherwise
+pub const DEFAULT_CAPTURE_SAMPLE_RATE: u32 = 48000;
+
+/// Rates the pipeline can be configured to; 16 kHz is what the VAD and the
+/// transcription models use, so choosing it skips their own resampling
+pub const SUPPORTED_CAPTURE_SAMPLE_RATES: [u32; 4] = [16000, 24000, 32000, 48000];
+
+/// Configured capture rate, applied from the next recording on
+static CAPTURE_SAMPLE_RATE: AtomicU32 = AtomicU32::new(DEFAULT_CAPTURE_SAMPLE_RATE);
+
+/// Rate devices are resampled to at capture, and the rate mixing and
+/// recording run at
+pub fn capture_sample_rate() -> u32 {
+    CAPTURE_SAMPLE_RATE.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+/// Sets the capture rate for the next recording; unsupported rates are
+/// rejected and leave the current one
+pub fn set_capture_sample_rate(sample_rate: u32) -> Result<()> {
+    if !SUPPORTED_CAPTURE_SAMPLE_RATES.contains(&sample_rate) {
+        return Err(anyhow::anyhow!(
+            "Unsupported capture sample rate {} Hz (supported: {:?})",
+            sample_rate,
+            SUPPORTED_CAPTURE_SAMPLE_RATES
+        ));
+    }
+    let previous = CAPTURE_SAMPLE_RATE.swap(sample_rate, std::sync::atomic::Ordering::Relaxed);
+    if previous != sample_rate {
+        info!("🎚️ Capture sample rate set to {} Hz (was {} Hz)", sample_rate, previous);
+    }
+    Ok(())
+}
+
 /// Ring buffer for synchronized audio mixing
 /// Accumulates samples from mic and system streams until we have aligned windows
 struct AudioMixerRingBuffer {
@@ -229,16 +262,15 @@ pub struct AudioCapture {
     device: Arc<AudioDevice>,
     state: Arc<RecordingState>,
     sample_rate: u32,        // Original device sample rate
+    target_sample_rate: u32, // Rate chunks are sent to the pipeline at
     channels: u16,
     chunk_counter: Arc<std::sync::atomic::AtomicU64>,
     device_type: DeviceType,
     recording_sender: Option<mpsc::UnboundedSender<AudioChunk>>,
     needs_resampling: bool,  // Flag if resampling is required
     // CRITICAL FIX: Persistent resampler to preserve energy across chunks
-    resampler: Arc<std::sync::Mutex<Option<SincFixedIn<f32>>>>,
-    // Buffering for variable-size chunks → fixed-size resampler input
-    resampler_input_buffer: Arc<std::sync::Mutex<Vec<f32>>>,
-    resampler_chunk_size: usize,  // Fixed chunk size for resampler (512 samples)
+    // (buffers variable-size chunks into fixed-size resampler input)
+    resampler: Arc<std::sync::Mutex<Option<StreamResampler>>>,
     // Audio enhancement processors (microphone only)
     noise_suppressor: Arc<std::sync::Mutex<Option<NoiseSuppressionProcessor>>>,
     high_pass_filter: Arc<std::sync::Mutex<Option<HighPassFilter>>>,
@@ -257,9 +289,11 @@ impl AudioCapture {
         recording_sender: Option<mpsc::UnboundedSender<AudioChunk>>,
     ) -> Self {
         // CRITICAL FIX: Detect if resampling is needed
-        // Pipeline expects 48kHz, but Bluetooth devices often report 8kHz, 16kHz, or 44.1kHz
-        const TARGET_SAMPLE_RATE: u32 = 48000;
-        let needs_resampling = sample_rate != TARGET_SAMPLE_RATE;
+        // Pipeline expects the recording's capture rate (48kHz by default), but
+        // Bluetooth devices often report 8kHz, 16kHz, or 44.1kHz and system
+        // audio taps whatever the output device runs at
+        let target_sample_rate = state.capture_sample_rate();
+        let needs_resampling = sample_rate != target_sample_rate;
 
         // Detect device kind (Bluetooth vs Wired) for adaptive processing
         // Use reasonable defaults for buffer size (512 samples is typical)
@@ -271,15 +305,15 @@ impl AudioCapture {
             );
             warn!(
                 "🔄 [{:?}] Audio device '{}' ({:?}) reports {} Hz (pipeline expects {} Hz)",
-                device_type, device.name, device_kind, sample_rate, TARGET_SAMPLE_RATE
+                device_type, device.name, device_kind, sample_rate, target_sample_rate
             );
             warn!(
                 "🔄 Automatic resampling will be applied: {} Hz → {} Hz",
-                sample_rate, TARGET_SAMPLE_RATE
+                sample_rate, target_sample_rate
             );
 
             // Log which resampling strategy will be used
-            let ratio = TARGET_SAMPLE_RATE as f64 / sample_rate as f64;
+            let ratio = target_sample_rate as f64 / sample_rate as f64;
             let strategy = if ratio >= 2.0 {
                 "High-quality upsampling (sinc_len=512, Cubic interpolation)"
             } else if ratio >= 1.5 {
@@ -303,8 +337,9 @@ impl AudioCapture {
         // System audio doesn't need enhancement (already clean)
         let (noise_suppressor, high_pass_filter, normalizer) = if matches!(device_type, DeviceType::Microphone) {
             // Initialize noise suppression (RNNoise) at 48kHz - CONDITIONAL based on flag
+            // RNNoise only runs at 48kHz, so other capture rates go without it
             let ns = if super::ffmpeg_mixer::RNNOISE_APPLY_ENABLED {
-                match NoiseSuppressionProcessor::new(TARGET_SAMPLE_RATE) {
+                match NoiseSuppressionProcessor::new(target_sample_rate) {
                     Ok(processor) => {
                         info!("✅ RNNoise noise suppression ENABLED for microphone '{}' (10-15 dB reduction)", device.name);
                         Some(processor)
@@ -322,13 +357,13 @@ impl AudioCapture {
 
             // Initialize high-pass filter (removes rumble below 80 Hz)
             let hpf = {
-                let filter = HighPassFilter::new(TARGET_SAMPLE_RATE, 80.0);
+                let filter = HighPassFilter::new(target_sample_rate, 80.0);
                 info!("✅ High-pass filter initialized for microphone '{}' (cutoff: 80 Hz)", device.name);
                 Some(filter)
             };
 
             // Initialize EBU R128 normalizer (professional loudness standard)
-            let norm = match LoudnessNormalizer::new(1, TARGET_SAMPLE_RATE) {
+            let norm = match LoudnessNormalizer::new(1, target_sample_rate) {
                 Ok(normalizer) => {
                     info!("✅ EBU R128 normalizer initialized for microphone '{}' (target: -23 LUFS)", device.name);
                     Some(normalizer)
@@ -352,39 +387,10 @@ impl AudioCapture {
         const RESAMPLER_CHUNK_SIZE: usize = 512;
 
         let resampler = if needs_resampling {
-            let ratio = TARGET_SAMPLE_RATE as f64 / sample_rate as f64;
-
-            // Adaptive parameters based on sample rate ratio (same logic as resample_audio)
-            let (sinc_len, interpolation_type, oversampling) = if ratio >= 2.0 {
-                (512, SincInterpolationType::Cubic, 512)
-            } else if ratio >= 1.5 {
-                (384, SincInterpolationType::Cubic, 384)
-            } else if ratio > 1.0 {
-                (256, SincInterpolationType::Linear, 256)
-            } else if ratio <= 0.5 {
-                (512, SincInterpolationType::Cubic, 512)
-            } else {
-                (384, SincInterpolationType::Linear, 384)
-            };
-
-            let params = SincInterpolationParameters {
-                sinc_len,
-                f_cutoff: 0.95,
-                interpolation: interpolation_type,
-                oversampling_factor: oversampling,
-                window: WindowFunction::BlackmanHarris2,
-            };
-
-            match SincFixedIn::<f32>::new(
-                ratio,
-                2.0,  // Maximum relative deviation
-                params,
-                RESAMPLER_CHUNK_SIZE,
-                1,    // Mono
-            ) {
+            match StreamResampler::new(sample_rate, target_sample_rate, RESAMPLER_CHUNK_SIZE) {
                 Ok(resampler) => {
                     info!("✅ Persistent resampler initialized for '{}' ({}Hz → {}Hz, chunk_size={})",
-                          device.name, sample_rate, TARGET_SAMPLE_RATE, RESAMPLER_CHUNK_SIZE);
+                          device.name, sample_rate, target_sample_rate, RESAMPLER_CHUNK_SIZE);
                     info!("   Buffering enabled for variable-size chunks (e.g., 320, 512, 1024, etc.)");
                     Some(resampler)
                 }
@@ -401,14 +407,13 @@ impl AudioCapture {
             device,
             state,
             sample_rate,
+            target_sample_rate,
             channels,
             chunk_counter: Arc::new(std::sync::atomic::AtomicU64::new(0)),
             device_type,
             recording_sender,
             needs_resampling,
             resampler: Arc::new(std::sync::Mutex::new(resampler)),
-            resampler_input_buffer: Arc::new(std::sync::Mutex::new(Vec::with_capacity(RESAMPLER_CHUNK_SIZE * 2))),
-            resampler_chunk_size: RESAMPLER_CHUNK_SIZE,
             noise_suppressor: Arc::new(std::sync::Mutex::new(noise_suppressor)),
             high_pass_filter: Arc::new(std::sync::Mutex::new(high_pass_filter)),
             normalizer: Arc::new(std::sync::Mutex::new(normalizer)),
@@ -416,6 +421,11 @@ impl AudioCapture {
         }
     }
 
+    /// Rate the captured audio is sent to the pipeline at
+    pub fn target_sample_rate(&self) -> u32 {
+        self.target_sample_rate
+    }
+
     /// Process audio data directly from callback
     pub fn process_audio_data(&self, data: &[f32]) {
         // Check if still recording
@@ -430,14 +440,13 @@ impl AudioCapture {
             data.to_vec()
         };
 
-        // CRITICAL FIX: Resample to 48kHz if device uses different sample rate
+        // CRITICAL FIX: Resample to the capture rate if device uses a different one
         // This fixes Bluetooth devices (like Sony WH-1000XM4) that report 16kHz or 44.1kHz
         // Without this, audio is sped up 3x and VAD fails
         //
         // IMPORTANT: Uses PERSISTENT resampler with BUFFERING to preserve energy across chunks
         // Creating a new resampler per chunk causes energy amplification (173.5% RMS)
         // Buffering handles variable chunk sizes (320, 512, 1024, etc.) by accumulating to fixed 512-sample chunks
-        const TARGET_SAMPLE_RATE: u32 = 48000;
         if self.needs_resampling {
             let before_len = mono_data.len();
             let before_rms = if !mono_data.is_empty() {
@@ -449,39 +458,21 @@ impl AudioCapture {
             // Use persistent resampler with buffering to handle variable chunk sizes
             let mut resampled_output = Vec::new();
             let mut used_persistent_resampler = false;
-
-            if let Ok(mut buffer_lock) = self.resampler_input_buffer.lock() {
-                // Add new samples to buffer
-                buffer_lock.extend_from_slice(&mono_data);
-
-                // Process complete chunks through the resampler
-                if let Ok(mut resampler_lock) = self.resampler.lock() {
-                    if let Some(ref mut resampler) = *resampler_lock {
-                        used_persistent_resampler = true;
-
-                        // Process as many complete chunks as we have
-                        while buffer_lock.len() >= self.resampler_chunk_size {
-                            // Extract exactly chunk_size samples
-                            let chunk: Vec<f32> = buffer_lock.drain(0..self.resampler_chunk_size).collect();
-
-                            // Rubato expects input as Vec<Vec<f32>> (one Vec per channel)
-                            let waves_in = vec![chunk];
-
-                            match resampler.process(&waves_in, None) {
-                                Ok(mut waves_out) => {
-                                    if let Some(output) = waves_out.pop() {
-                                        resampled_output.extend_from_slice(&output);
-                                    }
-                                }
-                                Err(e) => {
-                                    warn!("⚠️ Persistent resampler processing failed: {}", e);
-                                    used_persistent_resampler = false;
-                                    break;
-                                }
-                            }
+            let mut buffer_size = 0;
+
+            if let Ok(mut resampler_lock) = self.resampler.lock() {
+                if let Some(ref mut resampler) = *resampler_lock {
+                    match resampler.process(&mono_data) {
+                        Ok(output) => {
+                            used_persistent_resampler = true;
+                            resampled_output = output;
+                        }
+                        Err(e) => {
+                            warn!("⚠️ Persistent resampler processing failed: {}", e);
                         }
-                        // Remaining samples in buffer will be processed in next iteration
                     }
+                    // Remaining samples in buffer will be processed in next iteration
+                    buffer_size = resampler.buffered();
                 }
             }
 
@@ -497,7 +488,7 @@ impl AudioCapture {
                 mono_data = super::audio_processing::resample_audio(
                     &mono_data,
                     self.sample_rate,
-                    TARGET_SAMPLE_RATE,
+                    self.target_sample_rate,
                 );
             } else {
                 // Buffering: samples are accumulating in buffer, waiting for 512-sample chunk
@@ -515,20 +506,14 @@ impl AudioCapture {
                 } else {
                     0.0
                 };
-                let ratio = TARGET_SAMPLE_RATE as f64 / self.sample_rate as f64;
+                let ratio = self.target_sample_rate as f64 / self.sample_rate as f64;
                 let rms_preservation = if before_rms > 0.0 { (after_rms / before_rms) * 100.0 } else { 100.0 };
 
-                let buffer_size = if let Ok(buf) = self.resampler_input_buffer.lock() {
-                    buf.len()
-                } else {
-                    0
-                };
-
                 info!(
                     "🔄 [{:?}] Persistent buffered resampler: {}Hz → {}Hz (ratio: {:.2}x)",
                     self.device_type,
                     self.sample_rate,
-                    TARGET_SAMPLE_RATE,
+                    self.target_sample_rate,
                     ratio
                 );
                 info!(
@@ -640,10 +625,10 @@ impl AudioCapture {
         let timestamp = self.state.get_recording_duration().unwrap_or(0.0);
 
         // RAW AUDIO CHUNK: No gain applied - will be mixed and gained downstream
-        // Use 48kHz if we resampled, otherwise use original rate
+        // Resampled (if needed) to the capture rate
         let audio_chunk = AudioChunk {
             data: mono_data,  // Raw audio (resampled if needed), no gain yet
-            sample_rate: if self.needs_resampling { 48000 } else { self.sample_rate },
+            sample_rate: self.target_sample_rate,
             timestamp,
             chunk_id,
             device_type: self.device_type.clone(),
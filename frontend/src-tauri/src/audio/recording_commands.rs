@@ -7,7 +7,6 @@ use anyhow::Result;
 use log::{error, info, warn};
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
-use sqlx::SqlitePool;
 use std::collections::VecDeque;
 use std::sync::{
     atomic::{AtomicBool, AtomicU64, Ordering},
@@ -18,8 +17,9 @@ use tauri::{AppHandle, Emitter, Manager, Runtime};
 use tokio::task::JoinHandle;
 
 use super::{parse_audio_device, DeviceEvent, DeviceMonitorType, RecordingManager};
+use crate::database::repositories::SettingsRepo;
 use crate::state::AppState;
-use crate::summary::question_generator;
+use crate::summary::{question_generator, question_suppression};
 
 // Import transcription modules
 use super::transcription::{
@@ -41,6 +41,10 @@ static IS_RECORDING: AtomicBool = AtomicBool::new(false);
 static RECORDING_MANAGER: Mutex<Option<RecordingManager>> = Mutex::new(None);
 static TRANSCRIPTION_TASK: Mutex<Option<JoinHandle<()>>> = Mutex::new(None);
 
+// Bumped on every recording start so a previous recording's status task stops
+static RECORDING_STATUS_GENERATION: AtomicU64 = AtomicU64::new(0);
+const RECORDING_STATUS_EVENT: &str = "recording-status";
+
 // Clarifying question generation controls (tuned to avoid spammy popups)
 const QUESTION_DEBOUNCE_MS: u64 = 8_000; // Wait at least 8s between questions
 const QUESTION_MIN_CHARS: usize = 40; // Require meaningful chunk size
@@ -58,6 +62,26 @@ pub struct RecordingArgs {
     pub save_path: String,
 }
 
+/// Authoritative state of the current recording, so the UI shows the
+/// backend's clock instead of counting on its own
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+pub struct RecordingStatus {
+    pub is_recording: bool,
+    pub is_paused: bool,
+    /// Seconds recorded, not counting pauses
+    pub elapsed_seconds: f64,
+    pub microphone_device: Option<String>,
+    pub system_device: Option<String>,
+    /// Audio chunks captured per device
+    pub microphone_chunks: u64,
+    pub system_chunks: u64,
+    /// Folder the recording's audio checkpoints are written to until it stops
+    pub file_path: Option<String>,
+    pub file_size_bytes: u64,
+    /// Latest audio telemetry warning of this recording
+    pub last_warning: Option<String>,
+}
+
 #[derive(Debug, Serialize, Clone)]
 pub struct TranscriptionStatus {
     pub chunks_in_queue: usize,
@@ -122,7 +146,7 @@ pub async fn start_recording_with_meeting_name<R: Runtime>(
             now.format("%Y-%m-%d_%H-%M-%S")
         )
     });
-    manager.set_meeting_name(Some(effective_meeting_name));
+    manager.set_meeting_name(Some(effective_meeting_name.clone()));
 
     // Set up error callback
     let app_for_error = app.clone();
@@ -179,18 +203,19 @@ pub async fn start_recording_with_meeting_name<R: Runtime>(
         *global_task = Some(task_handle);
     }
 
+    // Periodically fold new transcript into a draft summary
+    crate::summary::live::start_live_summary(&app, Some(effective_meeting_name));
+    start_recording_status_updates(&app);
+    crate::summary::question_suppression::set_recording(&app, true).await;
+
     // CRITICAL: Listen for transcript-update events and save to recording manager
     // This enables transcript history persistence for page reload sync
     let app_for_listener = app.clone();
-    let question_pool = app
-        .state::<AppState>()
-        .db_manager
-        .pool()
-        .clone();
+    let question_settings = app.state::<AppState>().repos.settings.clone();
     tokio::spawn(async move {
         use tauri::Listener;
 
-        let pool_for_listener = question_pool.clone();
+        let settings_for_listener = question_settings.clone();
         let listener_app = app_for_listener.clone();
         app_for_listener.listen("transcript-update", move |event: tauri::Event| {
             // Parse the transcript update from the event payload
@@ -221,7 +246,7 @@ pub async fn start_recording_with_meeting_name<R: Runtime>(
                 }
 
                 let question_app = listener_app.clone();
-                maybe_generate_clarifying_question(&question_app, &pool_for_listener, &update);
+                maybe_generate_clarifying_question(&question_app, &settings_for_listener, &update);
             }
         });
 
@@ -318,7 +343,7 @@ pub async fn start_recording_with_devices_and_meeting<R: Runtime>(
             now.format("%Y-%m-%d_%H-%M-%S")
         )
     });
-    manager.set_meeting_name(Some(effective_meeting_name));
+    manager.set_meeting_name(Some(effective_meeting_name.clone()));
 
     // Set up error callback
     let app_for_error = app.clone();
@@ -369,6 +394,11 @@ pub async fn start_recording_with_devices_and_meeting<R: Runtime>(
         *global_task = Some(task_handle);
     }
 
+    // Periodically fold new transcript into a draft summary
+    crate::summary::live::start_live_summary(&app, Some(effective_meeting_name));
+    start_recording_status_updates(&app);
+    crate::summary::question_suppression::set_recording(&app, true).await;
+
     // CRITICAL: Listen for transcript-update events and save to recording manager
     // This enables transcript history persistence for page reload sync
     let app_for_listener = app.clone();
@@ -473,6 +503,7 @@ pub async fn stop_recording<R: Runtime>(
         }
         Err(e) => {
             error!("❌ Failed to stop audio streams: {}", e);
+            crate::summary::live::stop_live_summary(&app);
             return Err(format!("Failed to stop audio streams: {}", e));
         }
     }
@@ -536,6 +567,9 @@ pub async fn stop_recording<R: Runtime>(
         info!("ℹ️ No transcription task found to wait for");
     }
 
+    // Every transcript is out now, so the draft's last pass sees all of it
+    let live_session_id = crate::summary::live::stop_live_summary(&app);
+
     // Step 3: Now safely unload Whisper model after ALL chunks are processed
     let _ = app.emit(
         "recording-shutdown-progress",
@@ -757,6 +791,8 @@ pub async fn stop_recording<R: Runtime>(
     // Set recording flag to false
     info!("🔍 Setting IS_RECORDING to false");
     IS_RECORDING.store(false, Ordering::SeqCst);
+    // Questions held back during the recording pop up now
+    crate::summary::question_suppression::set_recording(&app, false).await;
 
     // Step 4.5: Prepare metadata for frontend (NO database save)
     // NOTE: We do NOT save to database here. The frontend will save after all transcripts are displayed.
@@ -792,7 +828,8 @@ pub async fn stop_recording<R: Runtime>(
         serde_json::json!({
             "message": "Recording stopped - frontend will save after all transcripts received",
             "folder_path": folder_path_str,
-            "meeting_name": meeting_name_str
+            "meeting_name": meeting_name_str,
+            "live_session_id": live_session_id
         }),
     )
     .map_err(|e| e.to_string())?;
@@ -820,7 +857,7 @@ fn now_millis() -> u64 {
 
 fn maybe_generate_clarifying_question<R: Runtime>(
     app: &AppHandle<R>,
-    pool: &SqlitePool,
+    settings: &Arc<dyn SettingsRepo>,
     update: &TranscriptUpdate,
 ) {
     if update.is_partial {
@@ -867,8 +904,12 @@ fn maybe_generate_clarifying_question<R: Runtime>(
     drop(buffer);
 
     let sequence_id = update.sequence_id;
-    let pool = pool.clone();
+    let settings = settings.clone();
     let app_handle = app.clone();
+    let window_tokens =
+        crate::summary::pipeline_config::load_summary_pipeline_config(app).question_window_tokens;
+    let debug_log = question_generator::question_debug_log(app, None);
+    let prompt_config = crate::summary::question_prompt::load_question_prompt_config(app);
 
     tauri::async_runtime::spawn(async move {
         info!(
@@ -876,7 +917,17 @@ fn maybe_generate_clarifying_question<R: Runtime>(
             sequence_id
         );
 
-        match question_generator::generate_questions(&pool, &chunk_text, &context).await {
+        match question_generator::generate_questions(
+            settings.as_ref(),
+            &prompt_config,
+            &chunk_text,
+            &context,
+            "",
+            window_tokens,
+            debug_log.as_ref(),
+        )
+        .await
+        {
             Ok(questions) if !questions.is_empty() => {
                 info!(
                     "✅ [Question Flow] Generated {} question(s) for seq_id {}",
@@ -884,6 +935,18 @@ fn maybe_generate_clarifying_question<R: Runtime>(
                     sequence_id
                 );
 
+                if question_suppression::is_suppressed() {
+                    let pending = questions
+                        .into_iter()
+                        .map(|question| question_suppression::PendingQuestion {
+                            id: None,
+                            question,
+                        })
+                        .collect();
+                    question_suppression::hold_questions(&app_handle, pending).await;
+                    return;
+                }
+
                 let payload = serde_json::json!({
                     "sequence_id": sequence_id,
                     "questions": questions,
@@ -1075,6 +1138,89 @@ pub async fn get_recording_state() -> serde_json::Value {
     }
 }
 
+/// Total size of the files directly in `dir`; 0 if it can't be read
+fn folder_size(dir: &std::path::Path) -> u64 {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok()?.metadata().ok())
+                .filter(|metadata| metadata.is_file())
+                .map(|metadata| metadata.len())
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+fn current_recording_status() -> RecordingStatus {
+    let is_recording = IS_RECORDING.load(Ordering::SeqCst);
+    let (mut status, checkpoints) = {
+        let manager_guard = RECORDING_MANAGER.lock().unwrap();
+        let Some(manager) = manager_guard.as_ref() else {
+            return RecordingStatus {
+                is_recording,
+                ..Default::default()
+            };
+        };
+        let state = manager.get_state();
+        let stats = state.get_stats();
+        let status = RecordingStatus {
+            is_recording,
+            is_paused: manager.is_paused(),
+            elapsed_seconds: manager.get_active_recording_duration().unwrap_or(0.0),
+            microphone_device: state.get_microphone_device().map(|d| d.name.clone()),
+            system_device: state.get_system_device().map(|d| d.name.clone()),
+            microphone_chunks: stats.microphone_chunks,
+            system_chunks: stats.system_chunks,
+            file_path: None,
+            file_size_bytes: 0,
+            last_warning: super::telemetry::last_warning(),
+        };
+        let checkpoints = manager
+            .get_meeting_folder()
+            .map(|folder| folder.join(".checkpoints"));
+        (status, checkpoints)
+    };
+    // The directory is read after the manager lock is released
+    if let Some(checkpoints) = checkpoints {
+        status.file_size_bytes = folder_size(&checkpoints);
+        status.file_path = Some(checkpoints.to_string_lossy().to_string());
+    }
+    status
+}
+
+/// Elapsed time, devices, chunk counts, file and last warning of the
+/// current recording; the same payload is emitted as `recording-status`
+/// every second while recording
+#[tauri::command]
+pub async fn api_get_recording_status() -> RecordingStatus {
+    current_recording_status()
+}
+
+/// Emits `recording-status` once a second until the recording stops, then
+/// once more with the final state
+fn start_recording_status_updates<R: Runtime>(app: &AppHandle<R>) {
+    super::telemetry::clear_last_warning();
+    let generation = RECORDING_STATUS_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let app = app.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(1));
+        loop {
+            interval.tick().await;
+            if RECORDING_STATUS_GENERATION.load(Ordering::SeqCst) != generation {
+                return;
+            }
+            let status = current_recording_status();
+            let stopped = !status.is_recording;
+            if let Err(e) = app.emit(RECORDING_STATUS_EVENT, &status) {
+                warn!("⚠️ Failed to emit recording status: {}", e);
+            }
+            if stopped {
+                return;
+            }
+        }
+    });
+}
+
 /// Get the meeting folder path for the current recording
 /// Returns the path if a meeting name was set and folder structure initialized
 #[tauri::command]
@@ -0,0 +1,83 @@
+//! Whether a saved recording holds any audio at all
+//!
+//! A capture from the wrong or a muted device saves a file of near-digital
+//! silence, which only shows up later as an empty transcript or a failed
+//! summary. Measuring the level of the whole file catches it first.
+
+use super::ffmpeg_mixer::calculate_rms;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// RMS below which a recording is treated as silent, about -60 dBFS; quiet
+/// speech from a distant microphone still measures well above it
+pub const SILENCE_RMS_THRESHOLD: f32 = 0.001;
+
+/// Level reported for a recording of only zeros, instead of -inf
+const MIN_DBFS: f32 = -120.0;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RecordingAudioLevel {
+    /// False when the recording is below `SILENCE_RMS_THRESHOLD`
+    pub has_audio: bool,
+    pub rms: f32,
+    pub rms_dbfs: f32,
+    pub peak: f32,
+    pub duration_seconds: f64,
+    pub path: Option<PathBuf>,
+}
+
+/// Level of mono samples at `sample_rate`
+pub fn measure_level(samples: &[f32], sample_rate: u32) -> RecordingAudioLevel {
+    let rms = calculate_rms(samples);
+    let peak = samples.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+    let rms_dbfs = if rms > 0.0 {
+        (20.0 * rms.log10()).max(MIN_DBFS)
+    } else {
+        MIN_DBFS
+    };
+    RecordingAudioLevel {
+        has_audio: rms >= SILENCE_RMS_THRESHOLD,
+        rms,
+        rms_dbfs,
+        peak,
+        duration_seconds: samples.len() as f64 / sample_rate as f64,
+        path: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: u32 = 16000;
+
+    #[test]
+    fn test_silent_buffer_is_reported_without_audio() {
+        let silent = vec![0.0f32; SAMPLE_RATE as usize * 2];
+        let level = measure_level(&silent, SAMPLE_RATE);
+        assert!(!level.has_audio);
+        assert_eq!(level.rms_dbfs, MIN_DBFS);
+        assert_eq!(level.duration_seconds, 2.0);
+
+        // Dither and hiss from an unplugged input are still silence
+        let hiss: Vec<f32> = (0..SAMPLE_RATE)
+            .map(|i| if i % 2 == 0 { 0.0002 } else { -0.0002 })
+            .collect();
+        assert!(!measure_level(&hiss, SAMPLE_RATE).has_audio);
+    }
+
+    #[test]
+    fn test_tone_is_reported_as_audio() {
+        // A 440 Hz tone at half scale: RMS 0.5 / sqrt(2), about -9 dBFS
+        let tone: Vec<f32> = (0..SAMPLE_RATE)
+            .map(|i| {
+                0.5 * (2.0 * std::f32::consts::PI * 440.0 * i as f32 / SAMPLE_RATE as f32).sin()
+            })
+            .collect();
+        let level = measure_level(&tone, SAMPLE_RATE);
+        assert!(level.has_audio);
+        assert!((level.rms - 0.3536).abs() < 0.001, "{}", level.rms);
+        assert!((level.rms_dbfs + 9.03).abs() < 0.05, "{}", level.rms_dbfs);
+        assert!((level.peak - 0.5).abs() < 0.001);
+    }
+}
@@ -86,6 +86,13 @@ impl RecordingManager {
         // Set up transcription channel
         let (transcription_sender, transcription_receiver) = mpsc::unbounded_channel::<AudioChunk>();
 
+        // Every capture, the mixer and the saved file run at one rate, fixed
+        // for the whole recording
+        let capture_sample_rate = super::pipeline::capture_sample_rate();
+        self.state.set_capture_sample_rate(capture_sample_rate);
+        self.recording_saver.set_sample_rate(capture_sample_rate);
+        info!("🎚️ Capture sample rate for this recording: {} Hz", capture_sample_rate);
+
         // CRITICAL FIX: Create recording sender for pre-mixed audio from pipeline
         // Pipeline will mix mic + system audio professionally and send to this channel
         let recording_sender = self.recording_saver.start_accumulation();
@@ -124,7 +131,7 @@ impl RecordingManager {
             self.state.clone(),
             transcription_sender,
             0, // Ignored - using dynamic sizing internally
-            48000, // 48kHz sample rate
+            capture_sample_rate,
             Some(recording_sender), // CRITICAL: Pass recording sender to receive pre-mixed audio
             mic_name,
             mic_kind,
@@ -25,6 +25,14 @@ pub struct RecordingPreferences {
     /// If Some with app names, only captures audio from those apps
     #[serde(default)]
     pub filtered_apps: Option<Vec<String>>,
+    /// Rate microphone and system audio are resampled to at capture, and the
+    /// rate the recording is mixed and saved at (16000, 24000, 32000 or 48000)
+    #[serde(default = "default_capture_sample_rate")]
+    pub capture_sample_rate: u32,
+}
+
+fn default_capture_sample_rate() -> u32 {
+    crate::audio::pipeline::DEFAULT_CAPTURE_SAMPLE_RATE
 }
 
 impl Default for RecordingPreferences {
@@ -36,6 +44,7 @@ impl Default for RecordingPreferences {
             #[cfg(target_os = "macos")]
             system_audio_backend: Some("coreaudio".to_string()),
             filtered_apps: None, // Default: capture all apps
+            capture_sample_rate: default_capture_sample_rate(),
         }
     }
 }
@@ -141,8 +150,15 @@ pub async fn load_recording_preferences<R: Runtime>(
         }
     }
 
-    info!("Loaded recording preferences: save_folder={:?}, auto_save={}, format={}, filtered_apps={:?}",
-          prefs.save_folder, prefs.auto_save, prefs.file_format, prefs.filtered_apps);
+    // Apply the capture rate for the next recording, like the backend above
+    if let Err(e) = crate::audio::pipeline::set_capture_sample_rate(prefs.capture_sample_rate) {
+        warn!("⚠️ {}, using {} Hz", e, default_capture_sample_rate());
+        prefs.capture_sample_rate = default_capture_sample_rate();
+        let _ = crate::audio::pipeline::set_capture_sample_rate(prefs.capture_sample_rate);
+    }
+
+    info!("Loaded recording preferences: save_folder={:?}, auto_save={}, format={}, filtered_apps={:?}, capture_sample_rate={}",
+          prefs.save_folder, prefs.auto_save, prefs.file_format, prefs.filtered_apps, prefs.capture_sample_rate);
     Ok(prefs)
 }
 
@@ -154,6 +170,9 @@ pub async fn save_recording_preferences<R: Runtime>(
     info!("💾 Saving recording preferences: save_folder={:?}, auto_save={}, format={}, filtered_apps={:?}",
           preferences.save_folder, preferences.auto_save, preferences.file_format, preferences.filtered_apps);
 
+    // Reject an unsupported capture rate before anything is stored
+    crate::audio::pipeline::set_capture_sample_rate(preferences.capture_sample_rate)?;
+
     // Serialize preferences to JsonValue
     let json_value = serde_json::to_value(preferences)
         .map_err(|e| anyhow::anyhow!("Failed to serialize preferences: {}", e))?;
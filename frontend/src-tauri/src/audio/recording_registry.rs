@@ -0,0 +1,145 @@
+// audio/recording_registry.rs
+//
+// Registry of captures started outside the main recording flow, so a stream
+// started by one command can be stopped by another.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use log::{info, warn};
+use std::collections::HashMap;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// A running capture owned by the registry
+#[async_trait]
+pub trait RegisteredRecording: Send {
+    /// Short label for logs (e.g. "system-audio")
+    fn kind(&self) -> &'static str;
+
+    /// Stops the capture and releases its stream
+    async fn stop(self: Box<Self>) -> Result<()>;
+}
+
+/// Active captures keyed by recording id
+///
+/// The lock is only held to insert or remove an entry; stopping happens
+/// after the entry was taken out, so a slow stop never blocks other commands.
+#[derive(Default)]
+pub struct RecordingRegistry {
+    recordings: Mutex<HashMap<String, Box<dyn RegisteredRecording>>>,
+}
+
+impl RecordingRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Takes ownership of a started capture and returns its recording id
+    pub async fn register(&self, recording: Box<dyn RegisteredRecording>) -> String {
+        let id = Uuid::new_v4().to_string();
+        info!("📼 Registered {} recording {}", recording.kind(), id);
+        self.recordings.lock().await.insert(id.clone(), recording);
+        id
+    }
+
+    /// Stops and deregisters a capture
+    pub async fn stop(&self, id: &str) -> Result<(), String> {
+        let recording = self
+            .recordings
+            .lock()
+            .await
+            .remove(id)
+            .ok_or_else(|| format!("No active recording with id {}", id))?;
+        let kind = recording.kind();
+        recording
+            .stop()
+            .await
+            .map_err(|e| format!("Failed to stop {} recording {}: {}", kind, id, e))?;
+        info!("🛑 Stopped {} recording {}", kind, id);
+        Ok(())
+    }
+
+    pub async fn is_active(&self, id: &str) -> bool {
+        self.recordings.lock().await.contains_key(id)
+    }
+
+    pub async fn active_ids(&self) -> Vec<String> {
+        self.recordings.lock().await.keys().cloned().collect()
+    }
+
+    /// Stops everything; called when the app exits. Failures are logged and
+    /// skipped
+    pub async fn stop_all(&self) {
+        let recordings: Vec<_> = self.recordings.lock().await.drain().collect();
+        for (id, recording) in recordings {
+            let kind = recording.kind();
+            if let Err(e) = recording.stop().await {
+                warn!("Failed to stop {} recording {}: {}", kind, id, e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    struct MockRecording {
+        stopped: Arc<AtomicBool>,
+    }
+
+    #[async_trait]
+    impl RegisteredRecording for MockRecording {
+        fn kind(&self) -> &'static str {
+            "mock"
+        }
+
+        async fn stop(self: Box<Self>) -> Result<()> {
+            self.stopped.store(true, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    fn mock() -> (Box<MockRecording>, Arc<AtomicBool>) {
+        let stopped = Arc::new(AtomicBool::new(false));
+        (
+            Box::new(MockRecording {
+                stopped: stopped.clone(),
+            }),
+            stopped,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_start_registers_and_stop_deregisters() {
+        let registry = RecordingRegistry::new();
+        let (recording, stopped) = mock();
+
+        let id = registry.register(recording).await;
+        assert!(registry.is_active(&id).await);
+        assert_eq!(registry.active_ids().await, vec![id.clone()]);
+        assert!(!stopped.load(Ordering::SeqCst));
+
+        registry.stop(&id).await.unwrap();
+        assert!(stopped.load(Ordering::SeqCst));
+        assert!(!registry.is_active(&id).await);
+        assert!(registry.stop(&id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_stop_all_stops_every_recording() {
+        let registry = RecordingRegistry::new();
+        let (first, first_stopped) = mock();
+        let (second, second_stopped) = mock();
+        registry.register(first).await;
+        registry.register(second).await;
+
+        registry.stop_all().await;
+
+        assert!(first_stopped.load(Ordering::SeqCst));
+        assert!(second_stopped.load(Ordering::SeqCst));
+        assert!(registry.active_ids().await.is_empty());
+    }
+}
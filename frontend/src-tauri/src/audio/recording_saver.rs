@@ -56,6 +56,8 @@ pub struct RecordingSaver {
     transcript_segments: Arc<Mutex<Vec<TranscriptSegment>>>,
     chunk_receiver: Option<mpsc::UnboundedReceiver<AudioChunk>>,
     is_saving: Arc<Mutex<bool>>,
+    // Rate of the mixed audio the pipeline sends
+    sample_rate: u32,
 }
 
 impl RecordingSaver {
@@ -68,6 +70,7 @@ impl RecordingSaver {
             transcript_segments: Arc::new(Mutex::new(Vec::new())),
             chunk_receiver: None,
             is_saving: Arc::new(Mutex::new(false)),
+            sample_rate: super::pipeline::DEFAULT_CAPTURE_SAMPLE_RATE,
         }
     }
 
@@ -134,6 +137,11 @@ impl RecordingSaver {
         self.add_transcript_segment(segment);
     }
 
+    /// Set the rate of the mixed audio; call before `start_accumulation`
+    pub fn set_sample_rate(&mut self, sample_rate: u32) {
+        self.sample_rate = sample_rate;
+    }
+
     /// Start accumulation with incremental saving
     pub fn start_accumulation(&mut self) -> mpsc::UnboundedSender<AudioChunk> {
         info!("Initializing incremental audio saver for recording");
@@ -205,7 +213,8 @@ impl RecordingSaver {
         let meeting_folder = create_meeting_folder(&base_folder, meeting_name)?;
 
         // Initialize incremental saver
-        let incremental_saver = IncrementalAudioSaver::new(meeting_folder.clone(), 48000)?;
+        let incremental_saver =
+            IncrementalAudioSaver::new(meeting_folder.clone(), self.sample_rate)?;
 
         // Create initial metadata
         let metadata = MeetingMetadata {
@@ -221,7 +230,7 @@ impl RecordingSaver {
             },
             audio_file: "audio.mp4".to_string(),
             transcript_file: "transcripts.json".to_string(),
-            sample_rate: 48000,
+            sample_rate: self.sample_rate,
             status: "recording".to_string(),
         };
 
@@ -306,12 +315,12 @@ impl RecordingSaver {
     pub fn get_stats(&self) -> (usize, u32) {
         if let Some(ref saver) = self.incremental_saver {
             if let Ok(guard) = saver.try_lock() {
-                (guard.get_checkpoint_count() as usize, 48000)
+                (guard.get_checkpoint_count() as usize, self.sample_rate)
             } else {
-                (0, 48000)
+                (0, self.sample_rate)
             }
         } else {
-            (0, 48000)
+            (0, self.sample_rate)
         }
     }
 
@@ -1,6 +1,6 @@
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 use anyhow::Result;
 
@@ -87,6 +87,9 @@ impl AudioError {
 #[derive(Debug, Default)]
 pub struct RecordingStats {
     pub chunks_processed: u64,
+    /// `chunks_processed` split by the device the chunk came from
+    pub microphone_chunks: u64,
+    pub system_chunks: u64,
     pub total_duration: f64,
     pub last_activity: Option<Instant>,
 }
@@ -107,6 +110,8 @@ pub struct RecordingState {
 
     // Audio pipeline
     audio_sender: Mutex<Option<mpsc::UnboundedSender<AudioChunk>>>,
+    // Rate all captures of the current recording are resampled to
+    capture_sample_rate: AtomicU32,
 
     // Memory optimization
     buffer_pool: AudioBufferPool,
@@ -138,6 +143,7 @@ impl RecordingState {
             system_device: Mutex::new(None),
             disconnected_device: Mutex::new(None),
             audio_sender: Mutex::new(None),
+            capture_sample_rate: AtomicU32::new(super::pipeline::DEFAULT_CAPTURE_SAMPLE_RATE),
             buffer_pool: AudioBufferPool::new(16, 48000), // Pool of 16 buffers with 48kHz samples capacity
             error_count: AtomicU32::new(0),
             recoverable_error_count: AtomicU32::new(0),
@@ -152,14 +158,31 @@ impl RecordingState {
 
     // Recording control
     pub fn start_recording(&self) -> Result<()> {
+        self.start_recording_at(Instant::now())
+    }
+
+    /// `start_recording` with the clock read by the caller
+    pub fn start_recording_at(&self, now: Instant) -> Result<()> {
         self.is_recording.store(true, Ordering::SeqCst);
-        *self.recording_start.lock().unwrap() = Some(Instant::now());
+        *self.recording_start.lock().unwrap() = Some(now);
+        *self.pause_start.lock().unwrap() = None;
+        *self.total_pause_duration.lock().unwrap() = Duration::ZERO;
         self.error_count.store(0, Ordering::SeqCst);
         self.recoverable_error_count.store(0, Ordering::SeqCst);
         *self.last_error.lock().unwrap() = None;
         Ok(())
     }
 
+    /// Fixes the capture rate for the recording about to start, so streams
+    /// restarted mid-recording keep matching the pipeline
+    pub fn set_capture_sample_rate(&self, sample_rate: u32) {
+        self.capture_sample_rate.store(sample_rate, Ordering::SeqCst);
+    }
+
+    pub fn capture_sample_rate(&self) -> u32 {
+        self.capture_sample_rate.load(Ordering::SeqCst)
+    }
+
     pub fn stop_recording(&self) {
         self.is_recording.store(false, Ordering::SeqCst);
         self.is_paused.store(false, Ordering::SeqCst);
@@ -172,6 +195,10 @@ impl RecordingState {
     }
 
     pub fn pause_recording(&self) -> Result<()> {
+        self.pause_recording_at(Instant::now())
+    }
+
+    pub fn pause_recording_at(&self, now: Instant) -> Result<()> {
         if !self.is_recording() {
             return Err(anyhow::anyhow!("Cannot pause when not recording"));
         }
@@ -180,12 +207,16 @@ impl RecordingState {
         }
 
         self.is_paused.store(true, Ordering::SeqCst);
-        *self.pause_start.lock().unwrap() = Some(Instant::now());
+        *self.pause_start.lock().unwrap() = Some(now);
         log::info!("Recording paused");
         Ok(())
     }
 
     pub fn resume_recording(&self) -> Result<()> {
+        self.resume_recording_at(Instant::now())
+    }
+
+    pub fn resume_recording_at(&self, now: Instant) -> Result<()> {
         if !self.is_recording() {
             return Err(anyhow::anyhow!("Cannot resume when not recording"));
         }
@@ -195,7 +226,7 @@ impl RecordingState {
 
         // Calculate pause duration and add to total
         if let Some(pause_start) = self.pause_start.lock().unwrap().take() {
-            let pause_duration = pause_start.elapsed();
+            let pause_duration = now.saturating_duration_since(pause_start);
             *self.total_pause_duration.lock().unwrap() += pause_duration;
             log::info!("Recording resumed after pause of {:.2}s", pause_duration.as_secs_f64());
         }
@@ -293,11 +324,16 @@ impl RecordingState {
         }
 
         if let Some(sender) = self.audio_sender.lock().unwrap().as_ref() {
+            let device_type = chunk.device_type.clone();
             sender.send(chunk).map_err(|_| anyhow::anyhow!("Failed to send audio chunk"))?;
 
             // Update statistics
             let mut stats = self.stats.lock().unwrap();
             stats.chunks_processed += 1;
+            match device_type {
+                DeviceType::Microphone => stats.microphone_chunks += 1,
+                DeviceType::System => stats.system_chunks += 1,
+            }
             stats.last_activity = Some(Instant::now());
             Ok(())
         } else {
@@ -380,20 +416,28 @@ impl RecordingState {
     }
 
     pub fn get_active_recording_duration(&self) -> Option<f64> {
-        self.recording_start.lock().unwrap().map(|start| {
-            let total_duration = start.elapsed().as_secs_f64();
-            let pause_duration = self.get_total_pause_duration();
-            let current_pause = if self.is_paused() {
-                self.pause_start
-                    .lock()
-                    .unwrap()
-                    .map(|p| p.elapsed().as_secs_f64())
-                    .unwrap_or(0.0)
-            } else {
-                0.0
-            };
-            total_duration - pause_duration - current_pause
-        })
+        self.active_recording_duration_at(Instant::now())
+            .map(|duration| duration.as_secs_f64())
+    }
+
+    /// Time recorded up to `now`, not counting finished or ongoing pauses
+    pub fn active_recording_duration_at(&self, now: Instant) -> Option<Duration> {
+        let start = (*self.recording_start.lock().unwrap())?;
+        let paused = *self.total_pause_duration.lock().unwrap();
+        let current_pause = if self.is_paused() {
+            self.pause_start
+                .lock()
+                .unwrap()
+                .map(|pause_start| now.saturating_duration_since(pause_start))
+                .unwrap_or_default()
+        } else {
+            Duration::ZERO
+        };
+        Some(
+            now.saturating_duration_since(start)
+                .saturating_sub(paused)
+                .saturating_sub(current_pause),
+        )
     }
 
     pub fn get_total_pause_duration(&self) -> f64 {
@@ -449,6 +493,7 @@ impl Default for RecordingState {
             system_device: Mutex::new(None),
             disconnected_device: Mutex::new(None),
             audio_sender: Mutex::new(None),
+            capture_sample_rate: AtomicU32::new(super::pipeline::DEFAULT_CAPTURE_SAMPLE_RATE),
             buffer_pool: AudioBufferPool::new(16, 48000), // Pool of 16 buffers with 48kHz samples capacity
             error_count: AtomicU32::new(0),
             recoverable_error_count: AtomicU32::new(0),
@@ -467,8 +512,86 @@ impl Clone for RecordingStats {
     fn clone(&self) -> Self {
         Self {
             chunks_processed: self.chunks_processed,
+            microphone_chunks: self.microphone_chunks,
+            system_chunks: self.system_chunks,
             total_duration: self.total_duration,
             last_activity: self.last_activity,
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_active_duration_excludes_pauses() {
+        let state = RecordingState::new();
+        let t0 = Instant::now();
+        let at = |secs: u64| t0 + Duration::from_secs(secs);
+        assert_eq!(state.active_recording_duration_at(at(5)), None);
+
+        state.start_recording_at(t0).unwrap();
+        assert_eq!(
+            state.active_recording_duration_at(at(10)),
+            Some(Duration::from_secs(10))
+        );
+
+        // An ongoing pause stops the clock
+        state.pause_recording_at(at(10)).unwrap();
+        assert!(state.pause_recording_at(at(11)).is_err());
+        assert_eq!(
+            state.active_recording_duration_at(at(25)),
+            Some(Duration::from_secs(10))
+        );
+
+        // A finished one is taken off for good
+        state.resume_recording_at(at(30)).unwrap();
+        assert_eq!(state.get_total_pause_duration(), 20.0);
+        assert_eq!(
+            state.active_recording_duration_at(at(45)),
+            Some(Duration::from_secs(25))
+        );
+        state.pause_recording_at(at(50)).unwrap();
+        state.resume_recording_at(at(52)).unwrap();
+        assert_eq!(
+            state.active_recording_duration_at(at(60)),
+            Some(Duration::from_secs(38))
+        );
+
+        // A clock read before the start doesn't underflow
+        assert_eq!(state.active_recording_duration_at(t0), Some(Duration::ZERO));
+
+        // A new recording starts from zero
+        state.start_recording_at(at(100)).unwrap();
+        assert_eq!(
+            state.active_recording_duration_at(at(103)),
+            Some(Duration::from_secs(3))
+        );
+    }
+
+    #[test]
+    fn test_chunks_counted_per_device() {
+        let state = RecordingState::new();
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        state.set_audio_sender(sender);
+        let chunk = |device_type| AudioChunk {
+            data: vec![0.0; 160],
+            sample_rate: 16000,
+            timestamp: 0.0,
+            chunk_id: 0,
+            device_type,
+        };
+
+        state
+            .send_audio_chunk(chunk(DeviceType::Microphone))
+            .unwrap();
+        state
+            .send_audio_chunk(chunk(DeviceType::Microphone))
+            .unwrap();
+        state.send_audio_chunk(chunk(DeviceType::System)).unwrap();
+        let stats = state.get_stats();
+        assert_eq!(stats.chunks_processed, 3);
+        assert_eq!((stats.microphone_chunks, stats.system_chunks), (2, 1));
+    }
+}
@@ -1,9 +1,16 @@
 use tauri::{command, AppHandle, Emitter, State};
 use crate::audio::{
-    start_system_audio_capture, list_system_audio_devices, check_system_audio_permissions,
+    start_system_audio_capture, list_system_audio_devices,
     SystemAudioDetector, SystemAudioEvent, new_system_audio_callback, list_system_audio_using_apps
 };
 use crate::audio::recording_preferences::get_default_recordings_folder;
+use crate::audio::recording_registry::RegisteredRecording;
+use crate::audio::SystemAudioStream;
+use crate::audio::permissions::{
+    resolve_audio_capture_permission, AudioCapturePermission, SystemPermissionProbe,
+};
+use crate::state::AppState;
+use async_trait::async_trait;
 use std::sync::{Arc, Mutex};
 use anyhow::Result;
 use futures_util::StreamExt;
@@ -15,16 +22,72 @@ use log::{info, warn};
 // Global state for system audio detector
 type SystemAudioDetectorState = Arc<Mutex<Option<SystemAudioDetector>>>;
 
+/// Keeps a system audio stream alive by draining it until stopped
+struct SystemCaptureHandle {
+    stop: Arc<tokio::sync::Notify>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl SystemCaptureHandle {
+    fn spawn(mut stream: SystemAudioStream) -> Self {
+        let stop = Arc::new(tokio::sync::Notify::new());
+        let stop_signal = stop.clone();
+        let task = tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = stop_signal.notified() => break,
+                    sample = stream.next() => {
+                        if sample.is_none() {
+                            warn!("System audio stream ended before it was stopped");
+                            break;
+                        }
+                    }
+                }
+            }
+            // Dropping the stream stops the capture
+        });
+        Self { stop, task }
+    }
+}
+
+#[async_trait]
+impl RegisteredRecording for SystemCaptureHandle {
+    fn kind(&self) -> &'static str {
+        "system-audio"
+    }
+
+    async fn stop(self: Box<Self>) -> Result<()> {
+        // notify_one stores a permit, so this works even if the task isn't waiting yet
+        self.stop.notify_one();
+        self.task.await?;
+        Ok(())
+    }
+}
+
 /// Start system audio capture (for capturing system output audio)
+///
+/// Returns the recording id to pass to `stop_system_audio_capture_command`.
 #[command]
-pub async fn start_system_audio_capture_command() -> Result<String, String> {
-    match start_system_audio_capture().await {
-        Ok(_stream) => {
-            // TODO: Store the stream in global state if needed for management
-            Ok("System audio capture started successfully".to_string())
-        }
-        Err(e) => Err(format!("Failed to start system audio capture: {}", e))
-    }
+pub async fn start_system_audio_capture_command(
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let stream = start_system_audio_capture()
+        .await
+        .map_err(|e| format!("Failed to start system audio capture: {}", e))?;
+    let recording_id = state
+        .recordings
+        .register(Box::new(SystemCaptureHandle::spawn(stream)))
+        .await;
+    Ok(recording_id)
+}
+
+/// Stop a system audio capture started by `start_system_audio_capture_command`
+#[command]
+pub async fn stop_system_audio_capture_command(
+    state: State<'_, AppState>,
+    recording_id: String,
+) -> Result<(), String> {
+    state.recordings.stop(&recording_id).await
 }
 
 /// Diagnostic: Record 5 seconds of system audio from ALL programs (no filtering) and save as WAV
@@ -129,10 +192,16 @@ pub async fn list_system_audio_devices_command() -> Result<Vec<String>, String>
         .map_err(|e| format!("Failed to list system audio devices: {}", e))
 }
 
-/// Check if the app has permission to access system audio
+/// Check whether the app may capture system audio
+///
+/// On macOS this is the Audio Capture permission: never asked, denied or
+/// granted. Elsewhere it is granted when output devices can be listed.
 #[command]
-pub async fn check_system_audio_permissions_command() -> bool {
-    check_system_audio_permissions()
+pub async fn check_system_audio_permissions_command() -> AudioCapturePermission {
+    // May create a probe tap when TCC can't be asked
+    tokio::task::spawn_blocking(|| resolve_audio_capture_permission(&SystemPermissionProbe))
+        .await
+        .unwrap_or(AudioCapturePermission::NotDetermined)
 }
 
 /// Start monitoring system audio usage by other applications
@@ -249,8 +318,8 @@ mod tests {
 
     #[tokio::test]
     async fn test_check_permissions() {
-        let has_permission = check_system_audio_permissions_command().await;
-        println!("Has system audio permissions: {}", has_permission);
+        let permission = check_system_audio_permissions_command().await;
+        println!("System audio permission: {:?}", permission);
         // This is mainly a smoke test to ensure it doesn't crash
     }
 }
\ No newline at end of file
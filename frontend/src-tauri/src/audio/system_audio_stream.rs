@@ -11,6 +11,10 @@ use super::pipeline::AudioCapture;
 use super::recording_state::{RecordingState, DeviceType};
 use super::capture::{SystemAudioCapture, SystemAudioStream};
 use super::telemetry::{AudioTelemetryEvent, emit_telemetry_event};
+use super::permissions::{
+    resolve_audio_capture_permission, AudioCapturePermission, AudioPermissionProbe,
+    PermissionDenied, SystemPermissionProbe,
+};
 
 /// System audio stream implementation that integrates with existing pipeline
 pub struct SystemAudioStreamManager {
@@ -29,6 +33,15 @@ impl SystemAudioStreamManager {
     ) -> Result<Self> {
         info!("Creating system audio stream for device: {}", device.name);
 
+        // A denied tap fails or records silence, and the supervisor would keep
+        // restarting it; fail with an error the UI can explain instead
+        let permission = resolve_audio_capture_permission(&SystemPermissionProbe);
+        if permission == AudioCapturePermission::Denied {
+            error!("❌ Audio Capture permission denied, not starting system audio capture");
+            emit_telemetry_event(AudioTelemetryEvent::SystemCapturePermissionDenied);
+            return Err(PermissionDenied.into());
+        }
+
         // Build the initial Core Audio tap before starting the supervisor loop
         let initial_stream = SystemAudioCapture::new()?.start_system_audio_capture()?;
         info!("Initial system audio stream started at {} Hz", initial_stream.sample_rate());
@@ -241,6 +254,7 @@ async fn run_capture_loop(
                 info!("System audio capture stream ready ({} Hz)", stream.sample_rate());
                 emit_telemetry_event(AudioTelemetryEvent::SystemCaptureRecovered {
                     sample_rate: stream.sample_rate(),
+                    target_sample_rate: state.capture_sample_rate(),
                 });
                 restart_attempt = 0;
                 stream
@@ -253,6 +267,13 @@ async fn run_capture_loop(
                     break;
                 }
 
+                // Permission revoked mid-recording: retrying can't help
+                if SystemPermissionProbe.preflight() == Some(AudioCapturePermission::Denied) {
+                    error!("❌ Audio Capture permission denied, stopping system audio capture");
+                    emit_telemetry_event(AudioTelemetryEvent::SystemCapturePermissionDenied);
+                    break;
+                }
+
                 let delay = Duration::from_millis(backoff_ms);
                 warn!("Retrying system audio capture in {:?}...", delay);
                 emit_telemetry_event(AudioTelemetryEvent::SystemCaptureRestart {
@@ -39,10 +39,12 @@ const unlisten = await listen<string[]>('system-audio-started', (event) => {
   console.log('Apps using system audio:', event.payload);
 });
 
-// Check permissions
-const hasPermission = await invoke('check_system_audio_permissions_command');
-if (!hasPermission) {
-  console.warn('No system audio permissions');
+// Check permissions: 'not_determined' | 'denied' | 'granted'
+const permission = await invoke('check_system_audio_permissions_command');
+if (permission !== 'granted') {
+  // Prompts if never asked; otherwise returns the System Settings link
+  const request = await invoke('api_request_audio_permission');
+  console.warn('No system audio permission', request);
 }
 
 // List available devices
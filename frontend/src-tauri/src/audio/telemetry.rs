@@ -1,4 +1,5 @@
 use log::{error, info, warn};
+use std::sync::Mutex;
 
 use super::recording_state::DeviceType;
 
@@ -20,9 +21,35 @@ pub enum AudioTelemetryEvent {
         backoff_ms: u64,
     },
     SystemCaptureRecovered {
+        /// Rate the tap delivers
         sample_rate: u32,
+        /// Rate the capture is resampled to for the pipeline
+        target_sample_rate: u32,
     },
     SystemCaptureShutdown,
+    /// System capture refused to start (or stopped restarting) because
+    /// Audio Capture permission is denied
+    SystemCapturePermissionDenied,
+}
+
+/// Latest warning or error event of the current recording, for its status
+static LAST_WARNING: Mutex<Option<String>> = Mutex::new(None);
+
+fn remember_warning(message: String) {
+    if let Ok(mut last) = LAST_WARNING.lock() {
+        *last = Some(message);
+    }
+}
+
+pub fn last_warning() -> Option<String> {
+    LAST_WARNING.lock().ok().and_then(|last| last.clone())
+}
+
+/// Forgets the previous recording's warning
+pub fn clear_last_warning() {
+    if let Ok(mut last) = LAST_WARNING.lock() {
+        *last = None;
+    }
 }
 
 /// Emit a structured telemetry event to the log stream
@@ -46,6 +73,10 @@ pub fn emit_telemetry_event(event: AudioTelemetryEvent) {
                 "📡 [telemetry] buffer_overflow device={:?} current={} max={}",
                 device, current_samples, max_samples
             );
+            remember_warning(format!(
+                "{:?} audio buffer overflowed ({} of {} samples)",
+                device, current_samples, max_samples
+            ));
         }
         AudioTelemetryEvent::SystemCaptureRestart {
             attempt,
@@ -56,16 +87,27 @@ pub fn emit_telemetry_event(event: AudioTelemetryEvent) {
                 "📡 [telemetry] system_capture_restart attempt={} backoff_ms={} reason={}",
                 attempt, backoff_ms, error
             );
+            remember_warning(format!(
+                "System audio capture restarting (attempt {}): {}",
+                attempt, error
+            ));
         }
-        AudioTelemetryEvent::SystemCaptureRecovered { sample_rate } => {
+        AudioTelemetryEvent::SystemCaptureRecovered {
+            sample_rate,
+            target_sample_rate,
+        } => {
             info!(
-                "📡 [telemetry] system_capture_recovered sample_rate={}Hz",
-                sample_rate
+                "📡 [telemetry] system_capture_recovered sample_rate={}Hz target_sample_rate={}Hz",
+                sample_rate, target_sample_rate
             );
         }
         AudioTelemetryEvent::SystemCaptureShutdown => {
             info!("📡 [telemetry] system_capture_shutdown");
         }
+        AudioTelemetryEvent::SystemCapturePermissionDenied => {
+            error!("📡 [telemetry] system_capture_permission_denied");
+            remember_warning("System audio capture permission denied".to_string());
+        }
     }
 }
 
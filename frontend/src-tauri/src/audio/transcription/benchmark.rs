@@ -0,0 +1,244 @@
+// audio/transcription/benchmark.rs
+//
+// Replays a meeting's saved recording through several Whisper models so their
+// speed and output can be compared. The meeting's stored transcript is never
+// touched; the transcripts are only returned.
+
+use crate::audio::ffmpeg::find_ffmpeg_path;
+use crate::audio::meeting_artifacts::{resolve_artifact, MeetingArtifact};
+use async_trait::async_trait;
+use log::{info, warn};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Sample rate Whisper expects
+pub const WHISPER_SAMPLE_RATE: u32 = 16000;
+
+/// Timing and output of one model on the recording
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelBenchmark {
+    pub model: String,
+    pub load_ms: u64,
+    pub transcribe_ms: u64,
+    /// Transcription time over the recording's length; below 1 is faster
+    /// than real time
+    pub real_time_factor: f64,
+    pub transcript: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptionBenchmark {
+    pub audio_path: String,
+    pub audio_seconds: f64,
+    pub results: Vec<ModelBenchmark>,
+}
+
+/// Loads models and transcribes with them; the Whisper engine outside tests
+#[async_trait]
+pub trait BenchmarkEngine: Send + Sync {
+    async fn load(&self, model: &str) -> Result<(), String>;
+    async fn transcribe(&self, audio: Vec<f32>) -> Result<String, String>;
+}
+
+/// Benchmarks through the shared Whisper engine with the user's language
+pub struct WhisperBenchmarkEngine {
+    engine: Arc<crate::whisper_engine::WhisperEngine>,
+    language: Option<String>,
+}
+
+impl WhisperBenchmarkEngine {
+    pub fn new(engine: Arc<crate::whisper_engine::WhisperEngine>, language: Option<String>) -> Self {
+        Self { engine, language }
+    }
+}
+
+#[async_trait]
+impl BenchmarkEngine for WhisperBenchmarkEngine {
+    async fn load(&self, model: &str) -> Result<(), String> {
+        self.engine
+            .load_model(model)
+            .await
+            .map_err(|e| format!("Failed to load model: {}", e))
+    }
+
+    async fn transcribe(&self, audio: Vec<f32>) -> Result<String, String> {
+        self.engine
+            .transcribe_audio(audio, self.language.clone())
+            .await
+            .map(|text| text.trim().to_string())
+            .map_err(|e| format!("Transcription failed: {}", e))
+    }
+}
+
+/// The recording in the meeting's folder, the same lookup as opening it
+///
+/// A recording that was never merged is benchmarked on its first part.
+pub fn benchmark_recording(folder_path: Option<&str>) -> Result<PathBuf, String> {
+    let folder = folder_path
+        .ok_or_else(|| "Recording folder path not available for this meeting".to_string())?;
+    resolve_artifact(Path::new(folder), MeetingArtifact::Recording)
+}
+
+/// Decodes the recording to 16 kHz mono samples with FFmpeg
+pub fn decode_recording(path: &Path) -> Result<Vec<f32>, String> {
+    let ffmpeg = find_ffmpeg_path()
+        .ok_or_else(|| "FFmpeg not found. Please install FFmpeg to decode recordings.".to_string())?;
+
+    let mut command = Command::new(ffmpeg);
+    command.arg("-i").arg(path).args([
+        "-f",
+        "f32le",
+        "-ac",
+        "1",
+        "-ar",
+        &WHISPER_SAMPLE_RATE.to_string(),
+        "-loglevel",
+        "error",
+        "pipe:1",
+    ]);
+
+    // Hide console window on Windows
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let output = command
+        .output()
+        .map_err(|e| format!("Failed to run FFmpeg: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to decode {}: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let samples = samples_from_f32le(&output.stdout);
+    if samples.is_empty() {
+        return Err(format!("Recording {} contains no audio", path.display()));
+    }
+    Ok(samples)
+}
+
+fn samples_from_f32le(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
+
+/// Runs `audio` through each model in turn
+///
+/// A model that fails to load or transcribe gets an error entry; the others
+/// still run.
+pub async fn run_benchmark(
+    engine: &dyn BenchmarkEngine,
+    audio: &[f32],
+    models: &[String],
+) -> Vec<ModelBenchmark> {
+    let audio_seconds = audio.len() as f64 / WHISPER_SAMPLE_RATE as f64;
+    let mut results = Vec::with_capacity(models.len());
+
+    for model in models {
+        info!("⏱️ Benchmarking transcription with model '{}'", model);
+        let load_started = Instant::now();
+        let loaded = engine.load(model).await;
+        let load_ms = load_started.elapsed().as_millis() as u64;
+
+        let transcribe_started = Instant::now();
+        let outcome = match loaded {
+            Ok(()) => engine.transcribe(audio.to_vec()).await,
+            Err(e) => Err(e),
+        };
+        let transcribe_ms = match outcome {
+            Ok(_) => transcribe_started.elapsed().as_millis() as u64,
+            Err(_) => 0,
+        };
+
+        let (transcript, error) = match outcome {
+            Ok(text) => (Some(text), None),
+            Err(e) => {
+                warn!("⚠️ Benchmark with model '{}' failed: {}", model, e);
+                (None, Some(e))
+            }
+        };
+        results.push(ModelBenchmark {
+            model: model.clone(),
+            load_ms,
+            transcribe_ms,
+            real_time_factor: if audio_seconds > 0.0 {
+                transcribe_ms as f64 / 1000.0 / audio_seconds
+            } else {
+                0.0
+            },
+            transcript,
+            error,
+        });
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    struct MockEngine {
+        loaded: Mutex<Option<String>>,
+    }
+
+    #[async_trait]
+    impl BenchmarkEngine for MockEngine {
+        async fn load(&self, model: &str) -> Result<(), String> {
+            if model == "missing" {
+                return Err("Failed to load model: Model missing not found".to_string());
+            }
+            *self.loaded.lock().unwrap() = Some(model.to_string());
+            Ok(())
+        }
+
+        async fn transcribe(&self, audio: Vec<f32>) -> Result<String, String> {
+            let model = self.loaded.lock().unwrap().clone().unwrap();
+            Ok(format!("{} heard {} samples", model, audio.len()))
+        }
+    }
+
+    #[test]
+    fn test_benchmark_without_audio_errors() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let err = benchmark_recording(None).unwrap_err();
+        assert_eq!(err, "Recording folder path not available for this meeting");
+
+        let err = benchmark_recording(dir.path().to_str()).unwrap_err();
+        assert!(err.starts_with("Recording not found in"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_run_benchmark_per_model() {
+        let engine = MockEngine {
+            loaded: Mutex::new(None),
+        };
+        let audio = vec![0.0; WHISPER_SAMPLE_RATE as usize * 2];
+        let models = vec!["base".to_string(), "missing".to_string(), "small".to_string()];
+
+        let results = run_benchmark(&engine, &audio, &models).await;
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].transcript.as_deref(), Some("base heard 32000 samples"));
+        assert!(results[1].transcript.is_none());
+        assert!(results[1].error.as_deref().unwrap().contains("not found"));
+        assert_eq!(results[2].transcript.as_deref(), Some("small heard 32000 samples"));
+        assert_eq!(
+            samples_from_f32le(&[0, 0, 128, 63, 0, 0, 0, 191, 1]),
+            vec![1.0, -0.5]
+        );
+    }
+}
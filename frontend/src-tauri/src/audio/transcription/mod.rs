@@ -2,11 +2,13 @@
 //
 // Transcription module: Provider abstraction, engine management, and worker pool.
 
+pub mod benchmark;
 pub mod provider;
 pub mod whisper_provider;
 pub mod parakeet_provider;
 pub mod engine;
 pub mod worker;
+pub mod model_registry;
 
 // Re-export commonly used types
 pub use provider::{TranscriptionError, TranscriptionProvider, TranscriptResult};
@@ -18,6 +20,13 @@ pub use engine::{
     get_or_init_transcription_engine,
     get_or_init_whisper
 };
+pub use model_registry::{
+    find_whisper_model,
+    preflight_check,
+    validate_whisper_model,
+    HostResources,
+    WhisperModelSpec
+};
 pub use worker::{
     start_transcription_task,
     reset_speech_detected_flag,
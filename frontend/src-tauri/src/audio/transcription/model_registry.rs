@@ -0,0 +1,336 @@
+// audio/transcription/model_registry.rs
+//
+// Known Whisper models with their download size and memory needs, used to
+// list the models, validate the configured one and check the machine can
+// run it.
+
+use serde::Serialize;
+use std::path::Path;
+use sysinfo::{Disks, System};
+
+/// Free disk kept for the recording itself (audio files, transcripts)
+pub const RECORDING_DISK_HEADROOM_MB: u64 = 500;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WhisperModelSpec {
+    pub name: &'static str,
+    pub download_size_mb: u64,
+    /// Approximate memory needed to load and run the model
+    pub min_ram_mb: u64,
+    pub accuracy: &'static str,
+    pub speed: &'static str,
+    pub description: &'static str,
+}
+
+impl WhisperModelSpec {
+    /// GGML file name in the models folder
+    pub fn filename(&self) -> String {
+        format!("ggml-{}.bin", self.name)
+    }
+}
+
+const fn spec(
+    name: &'static str,
+    download_size_mb: u64,
+    min_ram_mb: u64,
+    accuracy: &'static str,
+    speed: &'static str,
+    description: &'static str,
+) -> WhisperModelSpec {
+    WhisperModelSpec {
+        name,
+        download_size_mb,
+        min_ram_mb,
+        accuracy,
+        speed,
+        description,
+    }
+}
+
+/// Models offered by `WhisperEngine::discover_models`, all standard
+/// ggerganov/whisper.cpp GGML models
+pub const WHISPER_MODELS: &[WhisperModelSpec] = &[
+    // Standard f16 models (full precision)
+    spec(
+        "tiny",
+        39,
+        400,
+        "Decent",
+        "Very Fast",
+        "Fastest processing, good for real-time use",
+    ),
+    spec(
+        "base",
+        142,
+        500,
+        "Good",
+        "Fast",
+        "Good balance of speed and accuracy",
+    ),
+    spec(
+        "small",
+        466,
+        1000,
+        "Good",
+        "Medium",
+        "Better accuracy, moderate speed",
+    ),
+    spec(
+        "medium",
+        1420,
+        2600,
+        "High",
+        "Slow",
+        "High accuracy for professional use",
+    ),
+    spec(
+        "large-v3-turbo",
+        809,
+        1600,
+        "High",
+        "Medium",
+        "Best accuracy with improved speed",
+    ),
+    spec(
+        "large-v3",
+        2870,
+        4000,
+        "High",
+        "Slow",
+        "Best accuracy, latest large model",
+    ),
+    // Q5_0 quantized models (balanced speed/accuracy)
+    spec(
+        "tiny-q5_0",
+        26,
+        300,
+        "Decent",
+        "Very Fast",
+        "Quantized tiny model, ~50% faster processing",
+    ),
+    spec(
+        "base-q5_0",
+        85,
+        400,
+        "Good",
+        "Fast",
+        "Quantized base model, good speed/accuracy balance",
+    ),
+    spec(
+        "small-q5_0",
+        280,
+        700,
+        "Good",
+        "Fast",
+        "Quantized small model, faster than f16 version",
+    ),
+    spec(
+        "medium-q5_0",
+        852,
+        1600,
+        "High",
+        "Medium",
+        "Quantized medium model, professional quality",
+    ),
+    spec(
+        "large-v3-turbo-q5_0",
+        574,
+        1200,
+        "High",
+        "Medium",
+        "Quantized large model, best balance",
+    ),
+    spec(
+        "large-v3-q5_0",
+        1050,
+        2000,
+        "High",
+        "Slow",
+        "Quantized large model, high accuracy",
+    ),
+];
+
+pub fn find_whisper_model(name: &str) -> Option<&'static WhisperModelSpec> {
+    WHISPER_MODELS.iter().find(|model| model.name == name)
+}
+
+/// Checks a configured model name against the registry
+///
+/// Returns a warning for unknown names rather than an error: custom models
+/// dropped into the models folder still work, they just can't be checked.
+/// Empty names (no local model selected) are accepted.
+pub fn validate_whisper_model(name: &str) -> Option<String> {
+    let name = name.trim();
+    if name.is_empty() || find_whisper_model(name).is_some() {
+        return None;
+    }
+    let close = WHISPER_MODELS
+        .iter()
+        .find(|model| model.name.eq_ignore_ascii_case(name));
+    Some(match close {
+        Some(model) => format!(
+            "Unknown Whisper model '{}'; did you mean '{}'?",
+            name, model.name
+        ),
+        None => format!(
+            "Unknown Whisper model '{}'; known models: {}",
+            name,
+            WHISPER_MODELS
+                .iter()
+                .map(|model| model.name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    })
+}
+
+/// Free memory and disk on this machine, in MB
+#[derive(Debug, Clone, PartialEq)]
+pub struct HostResources {
+    pub available_memory_mb: u64,
+    /// None when the disk holding the models folder can't be determined
+    pub available_disk_mb: Option<u64>,
+}
+
+impl HostResources {
+    /// Reads current values; disk space is for the disk holding `path`
+    pub fn probe(path: &Path) -> Self {
+        let mut system = System::new();
+        system.refresh_memory();
+
+        let disks = Disks::new_with_refreshed_list();
+        let available_disk_mb = disks
+            .list()
+            .iter()
+            .filter(|disk| path.starts_with(disk.mount_point()))
+            .max_by_key(|disk| disk.mount_point().as_os_str().len())
+            .map(|disk| disk.available_space() / 1024 / 1024);
+
+        Self {
+            available_memory_mb: system.available_memory() / 1024 / 1024,
+            available_disk_mb,
+        }
+    }
+
+    fn fits(&self, model: &WhisperModelSpec, needs_download: bool) -> bool {
+        self.available_memory_mb >= model.min_ram_mb
+            && self.available_disk_mb.map_or(true, |disk| {
+                disk >= disk_needed(model, needs_download)
+            })
+    }
+}
+
+fn disk_needed(model: &WhisperModelSpec, needs_download: bool) -> u64 {
+    let download = if needs_download { model.download_size_mb } else { 0 };
+    download + RECORDING_DISK_HEADROOM_MB
+}
+
+/// Largest model that fits, for suggesting a smaller one
+fn suggest_model(
+    model: &WhisperModelSpec,
+    resources: &HostResources,
+    needs_download: bool,
+) -> Option<&'static WhisperModelSpec> {
+    WHISPER_MODELS
+        .iter()
+        .filter(|candidate| candidate.min_ram_mb < model.min_ram_mb)
+        .filter(|candidate| resources.fits(candidate, needs_download))
+        .max_by_key(|candidate| candidate.min_ram_mb)
+}
+
+/// Checks the machine has enough memory to run `model_name` and enough disk
+/// for the recording (plus the download when `needs_download`)
+///
+/// Unknown models pass, since their requirements aren't known.
+pub fn preflight_check(
+    model_name: &str,
+    needs_download: bool,
+    resources: &HostResources,
+) -> Result<(), String> {
+    let Some(model) = find_whisper_model(model_name) else {
+        return Ok(());
+    };
+
+    let problem = if resources.available_memory_mb < model.min_ram_mb {
+        format!(
+            "Whisper model '{}' needs about {} MB of free memory, but only {} MB is available",
+            model.name, model.min_ram_mb, resources.available_memory_mb
+        )
+    } else {
+        match resources.available_disk_mb {
+            Some(disk) if disk < disk_needed(model, needs_download) => {
+                if needs_download {
+                    format!(
+                        "Whisper model '{}' is a {} MB download and recording needs about {} MB more, but only {} MB of disk space is free",
+                        model.name, model.download_size_mb, RECORDING_DISK_HEADROOM_MB, disk
+                    )
+                } else {
+                    format!(
+                        "Recording needs about {} MB of free disk space, but only {} MB is free",
+                        RECORDING_DISK_HEADROOM_MB, disk
+                    )
+                }
+            }
+            _ => return Ok(()),
+        }
+    };
+
+    Err(match suggest_model(model, resources, needs_download) {
+        Some(smaller) => format!("{}. Try the smaller '{}' model.", problem, smaller.name),
+        None => format!("{}. Free up memory or disk space and try again.", problem),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resources(memory: u64, disk: Option<u64>) -> HostResources {
+        HostResources {
+            available_memory_mb: memory,
+            available_disk_mb: disk,
+        }
+    }
+
+    #[test]
+    fn test_validate_whisper_model() {
+        assert_eq!(validate_whisper_model("large-v3"), None);
+        assert_eq!(validate_whisper_model(""), None);
+        assert_eq!(
+            validate_whisper_model("Large-V3").as_deref(),
+            Some("Unknown Whisper model 'Large-V3'; did you mean 'large-v3'?")
+        );
+        let warning = validate_whisper_model("huge-v9").unwrap();
+        assert!(warning.starts_with("Unknown Whisper model 'huge-v9'; known models: tiny, base"));
+        assert!(warning.contains("large-v3-q5_0"));
+    }
+
+    #[test]
+    fn test_preflight_fails_fast_with_smaller_suggestion() {
+        // Enough for everything
+        assert!(preflight_check("large-v3", true, &resources(16_000, Some(50_000))).is_ok());
+
+        // 2 GB free memory: large-v3 can't load, the biggest model that fits is suggested
+        let err = preflight_check("large-v3", false, &resources(2_000, Some(50_000))).unwrap_err();
+        assert_eq!(
+            err,
+            "Whisper model 'large-v3' needs about 4000 MB of free memory, but only 2000 MB is available. Try the smaller 'large-v3-q5_0' model."
+        );
+
+        // 2 GB free disk: the 2.8 GB download can't fit; suggestion must fit the disk too
+        let err = preflight_check("large-v3", true, &resources(16_000, Some(2_000))).unwrap_err();
+        assert!(err.starts_with("Whisper model 'large-v3' is a 2870 MB download"), "{}", err);
+        assert!(err.ends_with("Try the smaller 'medium' model."), "{}", err);
+
+        // Already downloaded: only the recording headroom counts
+        assert!(preflight_check("large-v3", false, &resources(16_000, Some(2_000))).is_ok());
+
+        // Nothing fits
+        let err = preflight_check("tiny", false, &resources(100, Some(100))).unwrap_err();
+        assert!(err.ends_with("Free up memory or disk space and try again."), "{}", err);
+
+        // Unknown disk or unknown model: nothing to check against
+        assert!(preflight_check("large-v3", true, &resources(16_000, None)).is_ok());
+        assert!(preflight_check("custom-model", true, &resources(0, Some(0))).is_ok());
+    }
+}
@@ -5,6 +5,7 @@
 use super::engine::TranscriptionEngine;
 use super::provider::TranscriptionError;
 use crate::audio::AudioChunk;
+use crate::logging::log_preview;
 use log::{error, info, warn};
 use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
@@ -225,7 +226,7 @@ pub fn start_transcription_task<R: Runtime>(
                                             worker_id,
                                             update.sequence_id,
                                             update.text.len(),
-                                            update.text.chars().take(50).collect::<String>()
+                                            log_preview(&update.text)
                                         );
                                         
                                         if let Err(e) = app_clone.emit("transcript-update", &update)
@@ -3,7 +3,7 @@ use serde::Serialize;
 use std::path::PathBuf;
 use tauri::{AppHandle, Emitter, Manager};
 
-use super::manager::DatabaseManager;
+use super::manager::{DatabaseInfo, DatabaseManager, IntegrityReport};
 use crate::state::AppState;
 
 #[derive(Serialize)]
@@ -140,7 +140,7 @@ pub async fn import_and_initialize_database(
         })?;
 
     // Update app state with the new manager
-    app.manage(AppState { db_manager });
+    app.manage(AppState::new(db_manager));
 
     info!("Legacy database imported and initialized successfully");
 
@@ -164,7 +164,7 @@ pub async fn initialize_fresh_database(app: AppHandle) -> Result<(), String> {
         })?;
 
     // Update app state with the new manager
-    app.manage(AppState { db_manager });
+    app.manage(AppState::new(db_manager));
 
     info!("Fresh database initialized successfully");
 
@@ -175,6 +175,40 @@ pub async fn initialize_fresh_database(app: AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+/// Check referential integrity (foreign_key_check plus orphaned child rows)
+#[tauri::command]
+pub async fn api_check_db_integrity(
+    state: tauri::State<'_, AppState>,
+) -> Result<IntegrityReport, String> {
+    info!("Running database integrity check");
+
+    let report = state.db_manager.check_integrity().await.map_err(|e| {
+        error!("Database integrity check failed to run: {}", e);
+        format!("Failed to check database integrity: {}", e)
+    })?;
+
+    if report.ok {
+        info!("Database integrity check passed");
+    } else {
+        error!(
+            "Database integrity issues found: {} foreign key violations, orphaned rows: {:?}",
+            report.foreign_key_violations.len(),
+            report.orphaned_rows
+        );
+    }
+
+    Ok(report)
+}
+
+/// Report the effective SQLite pragmas and pool state for debugging
+#[tauri::command]
+pub async fn api_get_db_info(state: tauri::State<'_, AppState>) -> Result<DatabaseInfo, String> {
+    state.db_manager.database_info().await.map_err(|e| {
+        error!("Failed to read database info: {}", e);
+        format!("Failed to read database info: {}", e)
+    })
+}
+
 /// Get the database directory path
 #[tauri::command]
 pub async fn get_database_directory(app: AppHandle) -> Result<String, String> {
@@ -1,10 +1,57 @@
+use serde::Serialize;
+use sqlx::sqlite::{
+    SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteSynchronous,
+};
 use sqlx::{migrate::MigrateDatabase, Result, Sqlite, SqlitePool, Transaction};
 use std::fs;
 use std::path::Path;
+use std::time::Duration;
 use tauri::Manager;
+use tokio::sync::{Mutex, MutexGuard};
+
+/// How long a connection waits on a locked database before returning SQLITE_BUSY
+pub const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Upper bound on pooled connections. WAL lets readers run alongside the
+/// single writer, so a handful is enough for status polling plus saves.
+pub const MAX_CONNECTIONS: u32 = 8;
+
+/// Effective connection settings, reported for debugging
+#[derive(Debug, Clone, Serialize)]
+pub struct DatabaseInfo {
+    pub journal_mode: String,
+    pub synchronous: String,
+    pub busy_timeout_ms: i64,
+    pub foreign_keys: bool,
+    pub max_connections: u32,
+    pub open_connections: u32,
+    pub idle_connections: usize,
+}
+
+/// A row reported by `PRAGMA foreign_key_check`
+#[derive(Debug, Clone, Serialize)]
+pub struct ForeignKeyViolation {
+    pub table: String,
+    pub rowid: Option<i64>,
+    pub parent: String,
+}
+
+/// Result of a database integrity check
+#[derive(Debug, Clone, Serialize)]
+pub struct IntegrityReport {
+    pub foreign_keys_enabled: bool,
+    pub foreign_key_violations: Vec<ForeignKeyViolation>,
+    /// Child table name -> number of rows whose meeting no longer exists
+    pub orphaned_rows: Vec<(String, i64)>,
+    pub ok: bool,
+}
 
 pub struct DatabaseManager {
     pool: SqlitePool,
+    /// Serialises hot write paths (segment saves, transactions) on this
+    /// database so concurrent writers queue in the application instead of
+    /// racing for the SQLite lock
+    write_lock: Mutex<()>,
 }
 
 impl DatabaseManager {
@@ -29,11 +76,16 @@ impl DatabaseManager {
             }
         }
 
-        let pool = SqlitePool::connect(tauri_db_path).await?;
+        let pool = Self::pool_options()
+            .connect_with(Self::connect_options(tauri_db_path))
+            .await?;
 
         sqlx::migrate!("./migrations").run(&pool).await?;
 
-        Ok(DatabaseManager { pool })
+        Ok(DatabaseManager {
+            pool,
+            write_lock: Mutex::new(()),
+        })
     }
 
     // NOTE: So for the first time users they needs to start the application
@@ -106,15 +158,135 @@ impl DatabaseManager {
         Self::new_from_app_handle(app_handle).await
     }
 
+    /// Connection options applied to every pooled connection.
+    /// Foreign keys are enabled per connection so ON DELETE CASCADE is honoured.
+    /// WAL lets readers proceed during a write, and the busy timeout makes
+    /// contending writers wait instead of failing with "database is locked".
+    pub fn connect_options(db_path: &str) -> SqliteConnectOptions {
+        SqliteConnectOptions::new()
+            .filename(db_path)
+            .journal_mode(SqliteJournalMode::Wal)
+            .synchronous(SqliteSynchronous::Normal)
+            .busy_timeout(BUSY_TIMEOUT)
+            .foreign_keys(true)
+    }
+
+    /// Pool sizing shared by all database initialisation paths
+    pub fn pool_options() -> SqlitePoolOptions {
+        SqlitePoolOptions::new()
+            .max_connections(MAX_CONNECTIONS)
+            .acquire_timeout(Duration::from_secs(30))
+    }
+
+    /// Reads the pragmas actually in effect on a pooled connection
+    pub async fn database_info(&self) -> Result<DatabaseInfo> {
+        let mut conn = self.pool.acquire().await?;
+
+        let journal_mode: String = sqlx::query_scalar("PRAGMA journal_mode")
+            .fetch_one(&mut *conn)
+            .await?;
+        let synchronous: i64 = sqlx::query_scalar("PRAGMA synchronous")
+            .fetch_one(&mut *conn)
+            .await?;
+        let busy_timeout_ms: i64 = sqlx::query_scalar("PRAGMA busy_timeout")
+            .fetch_one(&mut *conn)
+            .await?;
+        let foreign_keys: i64 = sqlx::query_scalar("PRAGMA foreign_keys")
+            .fetch_one(&mut *conn)
+            .await?;
+
+        let synchronous = match synchronous {
+            0 => "OFF",
+            1 => "NORMAL",
+            2 => "FULL",
+            3 => "EXTRA",
+            _ => "UNKNOWN",
+        }
+        .to_string();
+
+        Ok(DatabaseInfo {
+            journal_mode: journal_mode.to_uppercase(),
+            synchronous,
+            busy_timeout_ms,
+            foreign_keys: foreign_keys == 1,
+            max_connections: self.pool.options().get_max_connections(),
+            open_connections: self.pool.size(),
+            idle_connections: self.pool.num_idle(),
+        })
+    }
+
+    /// Runs `PRAGMA foreign_key_check` plus explicit orphan queries for every
+    /// table that references meetings
+    pub async fn check_integrity(&self) -> Result<IntegrityReport> {
+        let foreign_keys_enabled: i64 = sqlx::query_scalar("PRAGMA foreign_keys")
+            .fetch_one(&self.pool)
+            .await?;
+
+        let violations: Vec<(String, Option<i64>, String, i64)> =
+            sqlx::query_as("PRAGMA foreign_key_check")
+                .fetch_all(&self.pool)
+                .await?;
+        let foreign_key_violations: Vec<ForeignKeyViolation> = violations
+            .into_iter()
+            .map(|(table, rowid, parent, _)| ForeignKeyViolation {
+                table,
+                rowid,
+                parent,
+            })
+            .collect();
+
+        // Read from the schema so child tables added later are checked too
+        let meeting_references: Vec<(String, String)> = sqlx::query_as(
+            r#"SELECT m.name, f."from"
+               FROM sqlite_master m
+               JOIN pragma_foreign_key_list(m.name) f
+               WHERE m.type = 'table' AND f."table" = 'meetings'
+               ORDER BY m.name"#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut orphaned_rows = Vec::new();
+        for (table, column) in meeting_references {
+            let query = format!(
+                r#"SELECT COUNT(*) FROM "{}" WHERE "{}" NOT IN (SELECT id FROM meetings)"#,
+                table, column
+            );
+            let count: i64 = sqlx::query_scalar(&query).fetch_one(&self.pool).await?;
+            if count > 0 {
+                orphaned_rows.push((table, count));
+            }
+        }
+
+        let ok = foreign_keys_enabled == 1
+            && foreign_key_violations.is_empty()
+            && orphaned_rows.is_empty();
+
+        Ok(IntegrityReport {
+            foreign_keys_enabled: foreign_keys_enabled == 1,
+            foreign_key_violations,
+            orphaned_rows,
+            ok,
+        })
+    }
+
     pub fn pool(&self) -> &SqlitePool {
         &self.pool
     }
 
+    /// Acquire this database's single-writer lock.
+    /// Hold the guard for the lifetime of the write; repository functions
+    /// never take it themselves, so they can run while it is held.
+    pub async fn write_lock(&self) -> MutexGuard<'_, ()> {
+        self.write_lock.lock().await
+    }
+
     pub async fn with_transaction<T, F, Fut>(&self, f: F) -> Result<T>
     where
         F: FnOnce(&mut Transaction<'_, Sqlite>) -> Fut,
         Fut: std::future::Future<Output = Result<T>>,
     {
+        let _write_guard = self.write_lock().await;
         let mut tx = self.pool.begin().await?;
         let result = f(&mut tx).await;
 
@@ -130,3 +302,214 @@ impl DatabaseManager {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn temp_manager(dir: &tempfile::TempDir) -> DatabaseManager {
+        let db_path = dir.path().join("meeting_minutes.sqlite");
+        let legacy_path = dir.path().join("meeting_minutes.db");
+        DatabaseManager::new(
+            &db_path.to_string_lossy(),
+            &legacy_path.to_string_lossy(),
+        )
+        .await
+        .expect("database manager")
+    }
+
+    #[tokio::test]
+    async fn test_foreign_keys_enabled_on_new_connections() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = temp_manager(&dir).await;
+
+        // Hold several connections at once so the pool has to open new ones
+        let mut connections = Vec::new();
+        for _ in 0..3 {
+            connections.push(manager.pool().acquire().await.unwrap());
+        }
+        for conn in connections.iter_mut() {
+            let enabled: i64 = sqlx::query_scalar("PRAGMA foreign_keys")
+                .fetch_one(&mut **conn)
+                .await
+                .unwrap();
+            assert_eq!(enabled, 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_integrity_check_reports_clean_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = temp_manager(&dir).await;
+
+        let report = manager.check_integrity().await.unwrap();
+        assert!(report.ok);
+        assert!(report.foreign_keys_enabled);
+        assert!(report.foreign_key_violations.is_empty());
+        assert!(report.orphaned_rows.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_integrity_check_reports_orphans() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = temp_manager(&dir).await;
+
+        let mut conn = manager.pool().acquire().await.unwrap();
+        sqlx::query("PRAGMA foreign_keys = OFF")
+            .execute(&mut *conn)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO transcripts (id, meeting_id, transcript, timestamp) VALUES ('t', 'missing', 'x', '0')",
+        )
+        .execute(&mut *conn)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO meeting_participants (meeting_id, position, name) VALUES ('missing', 0, 'Ann')",
+        )
+        .execute(&mut *conn)
+        .await
+        .unwrap();
+        sqlx::query("PRAGMA foreign_keys = ON")
+            .execute(&mut *conn)
+            .await
+            .unwrap();
+        drop(conn);
+
+        let report = manager.check_integrity().await.unwrap();
+        assert!(!report.ok);
+        assert_eq!(
+            report.orphaned_rows,
+            vec![
+                ("meeting_participants".to_string(), 1),
+                ("transcripts".to_string(), 1)
+            ]
+        );
+        assert_eq!(report.foreign_key_violations.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_connection_pragmas_applied() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = temp_manager(&dir).await;
+
+        let info = manager.database_info().await.unwrap();
+        assert_eq!(info.journal_mode, "WAL");
+        assert_eq!(info.synchronous, "NORMAL");
+        assert_eq!(info.busy_timeout_ms, BUSY_TIMEOUT.as_millis() as i64);
+        assert!(info.foreign_keys);
+        assert_eq!(info.max_connections, MAX_CONNECTIONS);
+    }
+
+    #[tokio::test]
+    async fn test_reads_and_writes_during_long_write_transaction() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = std::sync::Arc::new(temp_manager(&dir).await);
+
+        // Long-running writer holding the write lock and an open transaction
+        let writer = {
+            let manager = manager.clone();
+            tokio::spawn(async move {
+                let _guard = manager.write_lock().await;
+                let mut tx = manager.pool().begin().await.unwrap();
+                for i in 0..50 {
+                    sqlx::query(
+                        "INSERT INTO meetings (id, title, created_at, updated_at) VALUES (?, ?, datetime('now'), datetime('now'))",
+                    )
+                    .bind(format!("long-{}", i))
+                    .bind("Long write")
+                    .execute(&mut *tx)
+                    .await
+                    .unwrap();
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+                tx.commit().await.unwrap();
+            })
+        };
+
+        // Give the writer time to open its transaction
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let mut readers = Vec::new();
+        for _ in 0..20 {
+            let manager = manager.clone();
+            readers.push(tokio::spawn(async move {
+                for _ in 0..10 {
+                    sqlx::query_scalar::<_, i64>(
+                        "SELECT COUNT(*) FROM summary_processes WHERE status = 'processing'",
+                    )
+                    .fetch_one(manager.pool())
+                    .await?;
+                    sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM meetings")
+                        .fetch_one(manager.pool())
+                        .await?;
+                }
+                Ok::<_, sqlx::Error>(())
+            }));
+        }
+
+        // A second writer queues behind the first instead of failing
+        let second_writer = {
+            let manager = manager.clone();
+            tokio::spawn(async move {
+                let _guard = manager.write_lock().await;
+                let mut tx = manager.pool().begin().await?;
+                sqlx::query(
+                    "INSERT INTO meetings (id, title, created_at, updated_at) VALUES ('queued', 'Queued', datetime('now'), datetime('now'))",
+                )
+                .execute(&mut *tx)
+                .await?;
+                tx.commit().await
+            })
+        };
+
+        for reader in readers {
+            reader.await.unwrap().expect("read should not hit a lock error");
+        }
+        writer.await.unwrap();
+        second_writer
+            .await
+            .unwrap()
+            .expect("queued write should not hit a lock error");
+
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM meetings")
+            .fetch_one(manager.pool())
+            .await
+            .unwrap();
+        assert_eq!(count, 51);
+    }
+
+    #[tokio::test]
+    async fn test_repository_writes_inside_a_transaction() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = temp_manager(&dir).await;
+
+        // Repository functions don't take the lock, so this can't deadlock
+        let write = manager.with_transaction(|_| async {
+            crate::database::repositories::transcript::TranscriptsRepository::create_meeting(
+                manager.pool(),
+                "Standup",
+                0,
+                None,
+            )
+            .await
+        });
+        let meeting_id = tokio::time::timeout(Duration::from_secs(5), write)
+            .await
+            .expect("transaction should not wait on its own lock")
+            .unwrap();
+        assert!(meeting_id.starts_with("meeting-"));
+    }
+
+    #[tokio::test]
+    async fn test_databases_have_separate_write_locks() {
+        let (dir_a, dir_b) = (tempfile::tempdir().unwrap(), tempfile::tempdir().unwrap());
+        let (a, b) = (temp_manager(&dir_a).await, temp_manager(&dir_b).await);
+
+        let _held = a.write_lock().await;
+        assert!(tokio::time::timeout(Duration::from_secs(1), b.write_lock())
+            .await
+            .is_ok());
+    }
+}
@@ -2,4 +2,107 @@ pub mod commands;
 pub mod manager;
 pub mod models;
 pub mod repositories;
+pub mod segment_order;
+pub mod segment_repeats;
 pub mod setup;
+pub mod transcripts_json;
+
+#[cfg(test)]
+pub(crate) mod test_utils {
+    use crate::api::TranscriptSegment;
+    use chrono::Utc;
+    use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+    use sqlx::SqlitePool;
+    use std::str::FromStr;
+
+    /// Creates a migrated in-memory database (single connection so every
+    /// query sees the same database)
+    pub async fn test_pool() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(
+                SqliteConnectOptions::from_str("sqlite::memory:")
+                    .expect("in-memory options")
+                    .foreign_keys(true),
+            )
+            .await
+            .expect("in-memory database");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("migrations");
+        pool
+    }
+
+    /// Seeds `meetings` meetings with `segments_per_meeting` 5-second transcript
+    /// segments each; every even meeting gets a completed summary
+    pub async fn seed_meetings(pool: &SqlitePool, meetings: usize, segments_per_meeting: usize) {
+        let mut tx = pool.begin().await.unwrap();
+        let now = Utc::now();
+        for i in 0..meetings {
+            let meeting_id = format!("meeting-{}", i);
+            let created = now - chrono::Duration::seconds(i as i64);
+            sqlx::query("INSERT INTO meetings (id, title, created_at, updated_at) VALUES (?, ?, ?, ?)")
+                .bind(&meeting_id)
+                .bind(format!("Meeting {}", i))
+                .bind(created)
+                .bind(created)
+                .execute(&mut *tx)
+                .await
+                .unwrap();
+            for j in 0..segments_per_meeting {
+                sqlx::query(
+                    "INSERT INTO transcripts (id, meeting_id, transcript, timestamp, audio_start_time, audio_end_time, duration) VALUES (?, ?, ?, ?, ?, ?, ?)",
+                )
+                .bind(format!("transcript-{}-{}", i, j))
+                .bind(&meeting_id)
+                .bind("segment text")
+                .bind("00:00")
+                .bind(j as f64 * 5.0)
+                .bind(j as f64 * 5.0 + 5.0)
+                .bind(5.0)
+                .execute(&mut *tx)
+                .await
+                .unwrap();
+            }
+            if i % 2 == 0 {
+                let result = serde_json::json!({
+                    "markdown": format!("## Summary\n\nMeeting {} covered the release plan.\n\n## Key Decisions\n\n* Ship it", i)
+                });
+                sqlx::query(
+                    "INSERT INTO summary_processes (meeting_id, status, created_at, updated_at, result) VALUES (?, 'completed', ?, ?, ?)",
+                )
+                .bind(&meeting_id)
+                .bind(created)
+                .bind(created)
+                .bind(result.to_string())
+                .execute(&mut *tx)
+                .await
+                .unwrap();
+            }
+        }
+        tx.commit().await.unwrap();
+    }
+
+    /// A transcript segment from `start` to `end` seconds into the recording
+    pub fn segment(id: &str, text: &str, start: f64, end: f64) -> TranscriptSegment {
+        TranscriptSegment {
+            id: id.to_string(),
+            text: text.to_string(),
+            timestamp: "00:00".to_string(),
+            audio_start_time: Some(start),
+            audio_end_time: Some(end),
+            duration: Some(end - start),
+        }
+    }
+
+    /// A segment without recording times, like those saved before they existed
+    pub fn untimed_segment(id: &str, text: &str) -> TranscriptSegment {
+        TranscriptSegment {
+            audio_start_time: None,
+            audio_end_time: None,
+            duration: None,
+            ..segment(id, text, 0.0, 0.0)
+        }
+    }
+}
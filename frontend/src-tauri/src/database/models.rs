@@ -11,6 +11,48 @@ pub struct MeetingModel {
     pub folder_path: Option<String>,
 }
 
+/// Participant name -> pseudonym ("Engineer A") used for anonymized summaries
+pub type ParticipantMap = std::collections::BTreeMap<String, String>;
+
+/// Background notes attached to a meeting and fed to its prompts as
+/// reference material
+#[derive(Debug, Clone, PartialEq, FromRow, Serialize, Deserialize)]
+pub struct MeetingContext {
+    #[sqlx(rename = "background_context")]
+    pub text: String,
+    /// File the text was read from; None when it was entered directly
+    #[sqlx(rename = "background_context_source")]
+    pub source: Option<String>,
+    /// Rough token estimate taken when the text was stored
+    #[sqlx(rename = "background_context_tokens")]
+    pub token_count: i64,
+}
+
+/// Per-meeting summary generation defaults (template and model)
+#[derive(Debug, Clone, Default, PartialEq, FromRow, Serialize, Deserialize)]
+pub struct MeetingPreferences {
+    #[sqlx(rename = "preferred_template_id")]
+    pub template_id: Option<String>,
+    #[sqlx(rename = "preferred_model_provider")]
+    pub model_provider: Option<String>,
+    #[sqlx(rename = "preferred_model_name")]
+    pub model_name: Option<String>,
+}
+
+/// Row produced by the meetings overview query (meeting plus aggregated
+/// transcript and summary data)
+#[derive(Debug, Clone, FromRow)]
+pub struct MeetingOverviewRow {
+    pub id: String,
+    pub title: String,
+    pub created_at: DateTimeUtc,
+    pub updated_at: DateTimeUtc,
+    pub duration: Option<f64>,
+    pub segment_count: i64,
+    pub summary_status: Option<String>,
+    pub summary_markdown: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(transparent)]
 pub struct DateTimeUtc(pub DateTime<Utc>);
@@ -50,18 +92,261 @@ pub struct SummaryProcess {
     pub chunk_count: i64,
     pub processing_time: f64,
     pub metadata: Option<String>, // JSON
+    /// Kind of error a failed run hit (see `SummaryErrorKind`)
+    pub error_kind: Option<String>,
+    /// Validation warnings left in the completed summary
+    pub warning_count: Option<i64>,
+}
+
+/// Totals of the summary runs in a metrics period; the averages are over
+/// completed runs and None without any
+#[derive(Debug, Clone, PartialEq, FromRow, Serialize, Deserialize)]
+pub struct SummaryRunTotals {
+    pub runs: i64,
+    pub completed: i64,
+    pub failed: i64,
+    /// Share of runs that failed (0.0 - 1.0)
+    pub failure_rate: f64,
+    pub avg_chunks: Option<f64>,
+    pub avg_processing_time: Option<f64>,
+    pub avg_warning_count: Option<f64>,
+}
+
+/// Failed summary runs with one kind of error
+#[derive(Debug, Clone, PartialEq, FromRow, Serialize, Deserialize)]
+pub struct SummaryFailureKind {
+    pub kind: String,
+    pub count: i64,
+    /// Share of all runs in the period (0.0 - 1.0)
+    pub rate: f64,
+}
+
+/// Completed summary runs of one provider/model
+#[derive(Debug, Clone, PartialEq, FromRow, Serialize, Deserialize)]
+pub struct SummaryModelMetrics {
+    pub provider: String,
+    pub model: String,
+    pub runs: i64,
+    pub avg_chunks: f64,
+    pub avg_processing_time: f64,
+    /// Processing time per chunk, comparable across meetings of any length
+    pub avg_seconds_per_chunk: f64,
+    pub avg_warning_count: Option<f64>,
+    /// `avg_seconds_per_chunk` over the fastest model's; None if that is 0
+    pub relative_time: Option<f64>,
+}
+
+/// Summary of a meeting generated with one template of a multi-template run
+///
+/// `status` is `completed` or `failed`.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct SummaryVersion {
+    pub id: String,
+    pub meeting_id: String,
+    pub template_id: String,
+    pub status: String,
+    pub result: Option<String>, // JSON
+    pub error: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Background Jira task analysis of a meeting
+///
+/// `status` is `PENDING`, `processing`, `completed` or `failed`; `result`
+/// holds the proposals JSON returned by the analysis.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct JiraAnalysisProcess {
+    pub meeting_id: String,
+    /// Changes with every new run, so a superseded run can't store its result
+    pub run_id: String,
+    pub status: String,
+    pub result: Option<String>, // JSON
+    pub error: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Entry of the people directory used to resolve action item owners
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Person {
+    pub id: String,
+    pub display_name: String,
+    pub aliases: Vec<String>,
+    pub email: Option<String>,
+    pub jira_account_id: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Attendee of a meeting found in its transcript
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MeetingParticipant {
+    pub name: String,
+    pub role: Option<String>,
+    /// Every way the transcript wrote the name, the first one included
+    pub mentions: Vec<String>,
+}
+
+/// Editable fields of a `Person`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PersonInput {
+    pub display_name: String,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    pub email: Option<String>,
+    pub jira_account_id: Option<String>,
+}
+
+/// What the summary of a named pipeline looks like
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PipelineOutputFormat {
+    /// The template's full markdown report
+    Full,
+    /// Every section as a few short bullets
+    Skim,
+    /// A JSON report in the structured schema, kept next to its markdown
+    Structured,
+}
+
+/// Named preset of summary settings, e.g. "Quick" or "Detailed"; settings
+/// left None fall back to the global settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NamedPipeline {
+    pub id: String,
+    pub name: String,
+    pub template_id: Option<String>,
+    pub model_provider: Option<String>,
+    pub model_name: Option<String>,
+    pub compact_transcript: Option<bool>,
+    pub provenance: Option<bool>,
+    pub citations: Option<bool>,
+    pub anonymize: Option<bool>,
+    /// Whether chunk summaries get the extra combine call before the report
+    pub refine: Option<bool>,
+    pub output_format: Option<PipelineOutputFormat>,
+    /// Sampling temperature; stored with the preset but not sent to the
+    /// provider until the request builder takes generation params
+    pub temperature: Option<f64>,
+    /// Cap on generated tokens; stored like `temperature`
+    pub max_tokens: Option<u32>,
+    /// Webhooks the summary is sent to; empty sends it to every enabled one
+    pub webhook_ids: Vec<String>,
+    /// Seeded by migration; can be edited but not deleted
+    pub built_in: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Editable fields of a `NamedPipeline`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NamedPipelineInput {
+    pub name: String,
+    pub template_id: Option<String>,
+    pub model_provider: Option<String>,
+    pub model_name: Option<String>,
+    pub compact_transcript: Option<bool>,
+    pub provenance: Option<bool>,
+    pub citations: Option<bool>,
+    pub anonymize: Option<bool>,
+    pub refine: Option<bool>,
+    pub output_format: Option<PipelineOutputFormat>,
+    pub temperature: Option<f64>,
+    pub max_tokens: Option<u32>,
+    #[serde(default)]
+    pub webhook_ids: Vec<String>,
+}
+
+/// Clarifying question generated for a meeting
+#[derive(Debug, Clone, PartialEq, FromRow, Serialize, Deserialize)]
+pub struct StoredQuestion {
+    pub id: String,
+    pub meeting_id: String,
+    pub text: String,
+    pub category: String,
+    /// The transcript chunk that triggered the question
+    pub context: String,
+    pub answered: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// "shown", "suppressed", "released" or "duplicate"; once sent to the
+    /// meeting chat "sending", then "sent", "failed" or "unknown"
+    pub status: String,
+    /// Id of the latest delivery to the meeting chat
+    pub delivery_id: Option<String>,
+    /// Chat platform the extension confirmed the delivery on
+    pub delivery_platform: Option<String>,
+    pub delivery_requested_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Rolling summary draft of a meeting that is still being recorded
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct LiveSummary {
+    pub session_id: String,
+    pub meeting_name: Option<String>,
+    pub meeting_id: Option<String>,
+    pub draft: String,
+    pub passes: i64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// A meeting whose summary matches a full-text search
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct SummarySearchResult {
+    pub id: String,
+    pub title: String,
+    /// Excerpt of the summary with each match wrapped in `<mark>` tags
+    pub snippet: String,
+}
+
+/// How far saving a meeting's transcript has got
+///
+/// `state` is one of `in_progress`, `completed` or `failed`.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct TranscriptionProgress {
+    pub meeting_id: String,
+    pub state: String,
+    pub segments_done: i64,
+    pub segments_total: i64,
+    pub error: Option<String>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// Transcript text sent for summarization and how it was chunked
+///
+/// One row per meeting; see `TranscriptChunksRepository` for what writes it.
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct TranscriptChunk {
     pub meeting_id: String,
+    /// Kept in sync with the meeting title
     pub meeting_name: Option<String>,
     pub transcript_text: String,
     pub model: String,
     pub model_name: String,
+    /// In tokens once a summary run recorded its chunking
     pub chunk_size: Option<i64>,
     pub overlap: Option<i64>,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    /// JSON array of `ChunkBoundary`; None until a summary run chunked the text
+    pub chunk_ranges: Option<String>,
+}
+
+impl TranscriptChunk {
+    pub fn boundaries(&self) -> Vec<ChunkBoundary> {
+        self.chunk_ranges
+            .as_deref()
+            .and_then(|json| serde_json::from_str(json).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Character range of `transcript_text` covered by one chunk
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChunkBoundary {
+    /// 1-based, in transcript order
+    pub chunk_index: usize,
+    pub char_start: usize,
+    pub char_end: usize,
 }
 
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
@@ -95,6 +380,13 @@ pub struct Setting {
     pub ollama_endpoint: Option<String>,
 }
 
+/// Summary provider and model tried when the one before it is unavailable
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SummaryFallback {
+    pub provider: String,
+    pub model: String,
+}
+
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
 pub struct TranscriptSetting {
     pub id: String,
@@ -116,3 +408,35 @@ pub struct TranscriptSetting {
     #[serde(rename = "openaiApiKey")]
     pub openai_api_key: Option<String>,
 }
+
+/// A meeting as the retention policy sees it
+#[derive(Debug, Clone, FromRow)]
+pub struct RetentionCandidate {
+    pub id: String,
+    pub title: String,
+    pub created_at: DateTimeUtc,
+    pub folder_path: Option<String>,
+    pub audio_deleted: bool,
+    pub transcript_deleted: bool,
+    pub has_segments: bool,
+    pub has_summary: bool,
+}
+
+/// One attempt to deliver a webhook
+///
+/// `payload` is the event data without the signed envelope; `status` is
+/// None when no response arrived.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    pub id: String,
+    pub webhook_id: String,
+    pub event: String,
+    pub meeting_id: Option<String>,
+    pub payload: String, // JSON
+    pub status: Option<i64>,
+    pub latency_ms: i64,
+    pub error: Option<String>,
+    /// Delivery this one resent, if it is a redelivery
+    pub redelivery_of: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
@@ -0,0 +1,602 @@
+//! In-memory repository fakes for tests
+//!
+//! Mirrors the observable behaviour of the SQLx repositories (including
+//! cascading deletes and the meeting foreign key on summary processes) so
+//! services can be tested without a database. Both implementations are
+//! checked by the contract tests in `traits.rs`.
+
+use crate::api::{TranscriptSearchResult, TranscriptSegment};
+use crate::database::models::{
+    ChunkBoundary, DateTimeUtc, MeetingContext, MeetingModel, MeetingParticipant,
+    MeetingPreferences, ParticipantMap, Setting, SummaryFallback, SummaryProcess, SummaryVersion, TranscriptChunk,
+};
+use crate::database::repositories::traits::{
+    MeetingsRepo, SettingsRepo, SummaryProcessesRepo, TranscriptsRepo,
+};
+use crate::database::repositories::transcript::TranscriptsRepository;
+use crate::database::segment_order::order_segments;
+use crate::database::segment_repeats::drop_repeats;
+use crate::summary::metrics::{warning_count, SummaryErrorKind};
+use async_trait::async_trait;
+use chrono::Utc;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+#[derive(Default)]
+struct Store {
+    meetings: Vec<MeetingModel>,
+    preferences: HashMap<String, MeetingPreferences>,
+    participant_maps: HashMap<String, ParticipantMap>,
+    participants: HashMap<String, Vec<MeetingParticipant>>,
+    contexts: HashMap<String, MeetingContext>,
+    /// (meeting_id, segment)
+    transcripts: Vec<(String, TranscriptSegment)>,
+    transcript_chunks: HashMap<String, TranscriptChunk>,
+    setting: Option<Setting>,
+    summary_fallbacks: Vec<SummaryFallback>,
+    processes: HashMap<String, SummaryProcess>,
+    /// Oldest first
+    summary_versions: Vec<SummaryVersion>,
+}
+
+impl Store {
+    fn meeting_exists(&self, meeting_id: &str) -> bool {
+        self.meetings.iter().any(|m| m.id == meeting_id)
+    }
+}
+
+#[derive(Default)]
+pub struct InMemoryRepositories {
+    store: Mutex<Store>,
+}
+
+impl InMemoryRepositories {
+    fn store(&self) -> std::sync::MutexGuard<'_, Store> {
+        self.store.lock().unwrap()
+    }
+}
+
+fn empty_meeting_id_error() -> sqlx::Error {
+    sqlx::Error::Protocol("meeting_id cannot be empty".to_string())
+}
+
+fn api_key_slot<'a>(
+    setting: &'a mut Setting,
+    provider: &str,
+) -> Result<&'a mut Option<String>, sqlx::Error> {
+    match provider {
+        "openai" => Ok(&mut setting.openai_api_key),
+        "claude" => Ok(&mut setting.anthropic_api_key),
+        "ollama" => Ok(&mut setting.ollama_api_key),
+        "groq" => Ok(&mut setting.groq_api_key),
+        "openrouter" => Ok(&mut setting.open_router_api_key),
+        "gemini" => Ok(&mut setting.gemini_api_key),
+        _ => Err(sqlx::Error::Protocol(format!("Invalid provider: {}", provider))),
+    }
+}
+
+/// Defaults used by `SettingsRepository::save_api_key` when no settings row exists
+fn default_setting() -> Setting {
+    Setting {
+        id: "1".to_string(),
+        provider: "openai".to_string(),
+        model: "gpt-4o-2024-11-20".to_string(),
+        whisper_model: "large-v3".to_string(),
+        groq_api_key: None,
+        openai_api_key: None,
+        anthropic_api_key: None,
+        ollama_api_key: None,
+        open_router_api_key: None,
+        gemini_api_key: None,
+        ollama_endpoint: None,
+    }
+}
+
+#[async_trait]
+impl MeetingsRepo for InMemoryRepositories {
+    async fn get_meetings(&self) -> Result<Vec<MeetingModel>, sqlx::Error> {
+        let mut meetings = self.store().meetings.clone();
+        meetings.sort_by(|a, b| b.created_at.0.cmp(&a.created_at.0));
+        Ok(meetings)
+    }
+
+    async fn update_meeting_title(
+        &self,
+        meeting_id: &str,
+        new_title: &str,
+    ) -> Result<bool, sqlx::Error> {
+        if meeting_id.trim().is_empty() {
+            return Err(empty_meeting_id_error());
+        }
+        let mut store = self.store();
+        match store.meetings.iter_mut().find(|m| m.id == meeting_id) {
+            Some(meeting) => {
+                meeting.title = new_title.to_string();
+                meeting.updated_at = DateTimeUtc(Utc::now());
+            }
+            None => return Ok(false),
+        }
+        if let Some(chunk) = store.transcript_chunks.get_mut(meeting_id) {
+            chunk.meeting_name = Some(new_title.to_string());
+        }
+        Ok(true)
+    }
+
+    async fn delete_meeting(&self, meeting_id: &str) -> Result<bool, sqlx::Error> {
+        if meeting_id.trim().is_empty() {
+            return Err(empty_meeting_id_error());
+        }
+        let mut store = self.store();
+        if !store.meeting_exists(meeting_id) {
+            return Ok(false);
+        }
+        store.meetings.retain(|m| m.id != meeting_id);
+        store.preferences.remove(meeting_id);
+        store.participant_maps.remove(meeting_id);
+        store.participants.remove(meeting_id);
+        store.contexts.remove(meeting_id);
+        store.transcripts.retain(|(id, _)| id != meeting_id);
+        store.transcript_chunks.remove(meeting_id);
+        store.processes.remove(meeting_id);
+        store.summary_versions.retain(|v| v.meeting_id != meeting_id);
+        Ok(true)
+    }
+
+    async fn get_meeting_preferences(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Option<MeetingPreferences>, sqlx::Error> {
+        let store = self.store();
+        if !store.meeting_exists(meeting_id) {
+            return Ok(None);
+        }
+        Ok(Some(store.preferences.get(meeting_id).cloned().unwrap_or_default()))
+    }
+
+    async fn set_meeting_preferences(
+        &self,
+        meeting_id: &str,
+        preferences: &MeetingPreferences,
+    ) -> Result<bool, sqlx::Error> {
+        let mut store = self.store();
+        if !store.meeting_exists(meeting_id) {
+            return Ok(false);
+        }
+        store
+            .preferences
+            .insert(meeting_id.to_string(), preferences.clone());
+        Ok(true)
+    }
+
+    async fn get_participant_map(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Option<ParticipantMap>, sqlx::Error> {
+        let store = self.store();
+        if !store.meeting_exists(meeting_id) {
+            return Ok(None);
+        }
+        Ok(Some(store.participant_maps.get(meeting_id).cloned().unwrap_or_default()))
+    }
+
+    async fn set_participant_map(
+        &self,
+        meeting_id: &str,
+        map: &ParticipantMap,
+    ) -> Result<bool, sqlx::Error> {
+        let mut store = self.store();
+        if !store.meeting_exists(meeting_id) {
+            return Ok(false);
+        }
+        store
+            .participant_maps
+            .insert(meeting_id.to_string(), map.clone());
+        Ok(true)
+    }
+
+    async fn get_participants(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Option<Vec<MeetingParticipant>>, sqlx::Error> {
+        let store = self.store();
+        if !store.meeting_exists(meeting_id) {
+            return Ok(None);
+        }
+        Ok(Some(store.participants.get(meeting_id).cloned().unwrap_or_default()))
+    }
+
+    async fn set_participants(
+        &self,
+        meeting_id: &str,
+        participants: &[MeetingParticipant],
+    ) -> Result<bool, sqlx::Error> {
+        let mut store = self.store();
+        if !store.meeting_exists(meeting_id) {
+            return Ok(false);
+        }
+        store
+            .participants
+            .insert(meeting_id.to_string(), participants.to_vec());
+        Ok(true)
+    }
+
+    async fn get_meeting_context(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Option<MeetingContext>, sqlx::Error> {
+        Ok(self.store().contexts.get(meeting_id).cloned())
+    }
+
+    async fn set_meeting_context(
+        &self,
+        meeting_id: &str,
+        context: Option<&MeetingContext>,
+    ) -> Result<bool, sqlx::Error> {
+        let mut store = self.store();
+        if !store.meeting_exists(meeting_id) {
+            return Ok(false);
+        }
+        match context {
+            Some(context) => store.contexts.insert(meeting_id.to_string(), context.clone()),
+            None => store.contexts.remove(meeting_id),
+        };
+        Ok(true)
+    }
+}
+
+#[async_trait]
+impl TranscriptsRepo for InMemoryRepositories {
+    async fn save_transcript(
+        &self,
+        meeting_title: &str,
+        transcripts: &[TranscriptSegment],
+        folder_path: Option<String>,
+    ) -> Result<String, sqlx::Error> {
+        let meeting_id = format!("meeting-{}", Uuid::new_v4());
+        let now = DateTimeUtc(Utc::now());
+
+        let mut store = self.store();
+        store.meetings.push(MeetingModel {
+            id: meeting_id.clone(),
+            title: meeting_title.to_string(),
+            created_at: now.clone(),
+            updated_at: now,
+            folder_path,
+        });
+        // Same filtering as the SQL repository
+        let (transcripts, _) = drop_repeats(transcripts);
+        for segment in transcripts {
+            store.transcripts.push((meeting_id.clone(), segment));
+        }
+
+        Ok(meeting_id)
+    }
+
+    async fn search_transcripts(
+        &self,
+        query: &str,
+    ) -> Result<Vec<TranscriptSearchResult>, sqlx::Error> {
+        if query.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        let query_lower = query.to_lowercase();
+
+        let store = self.store();
+        let results = store
+            .transcripts
+            .iter()
+            .filter(|(_, segment)| segment.text.to_lowercase().contains(&query_lower))
+            .filter_map(|(meeting_id, segment)| {
+                let meeting = store.meetings.iter().find(|m| &m.id == meeting_id)?;
+                Some(TranscriptSearchResult {
+                    id: meeting.id.clone(),
+                    title: meeting.title.clone(),
+                    match_context: TranscriptsRepository::get_match_context(&segment.text, query),
+                    timestamp: segment.timestamp.clone(),
+                })
+            })
+            .collect();
+
+        Ok(results)
+    }
+
+    async fn get_transcript_segments(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Vec<TranscriptSegment>, sqlx::Error> {
+        let mut segments: Vec<TranscriptSegment> = self
+            .store()
+            .transcripts
+            .iter()
+            .filter(|(id, _)| id == meeting_id)
+            .map(|(_, segment)| segment.clone())
+            .collect();
+        // Same order as the SQL repository
+        order_segments(&mut segments);
+        Ok(segments)
+    }
+
+    async fn save_transcript_data(
+        &self,
+        meeting_id: &str,
+        text: &str,
+        model: &str,
+        model_name: &str,
+        chunk_size: i32,
+        overlap: i32,
+    ) -> Result<(), sqlx::Error> {
+        let mut store = self.store();
+        let Some(meeting) = store.meetings.iter().find(|m| m.id == meeting_id) else {
+            return Err(sqlx::Error::Protocol(
+                "FOREIGN KEY constraint failed".to_string(),
+            ));
+        };
+        let chunk = TranscriptChunk {
+            meeting_id: meeting_id.to_string(),
+            meeting_name: Some(meeting.title.clone()),
+            transcript_text: text.to_string(),
+            model: model.to_string(),
+            model_name: model_name.to_string(),
+            chunk_size: Some(chunk_size as i64),
+            overlap: Some(overlap as i64),
+            created_at: Utc::now(),
+            chunk_ranges: None,
+        };
+        store.transcript_chunks.insert(meeting_id.to_string(), chunk);
+        Ok(())
+    }
+
+    async fn get_transcript_data(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Option<TranscriptChunk>, sqlx::Error> {
+        Ok(self.store().transcript_chunks.get(meeting_id).cloned())
+    }
+
+    async fn save_chunk_layout(
+        &self,
+        meeting_id: &str,
+        text: &str,
+        chunk_size: usize,
+        overlap: usize,
+        boundaries: &[ChunkBoundary],
+    ) -> Result<bool, sqlx::Error> {
+        let json = serde_json::to_string(boundaries)
+            .map_err(|e| sqlx::Error::Protocol(format!("Invalid chunk boundaries: {}", e)))?;
+        let mut store = self.store();
+        let Some(chunk) = store.transcript_chunks.get_mut(meeting_id) else {
+            return Ok(false);
+        };
+        chunk.transcript_text = text.to_string();
+        chunk.chunk_size = Some(chunk_size as i64);
+        chunk.overlap = Some(overlap as i64);
+        chunk.chunk_ranges = Some(json);
+        Ok(true)
+    }
+}
+
+#[async_trait]
+impl SettingsRepo for InMemoryRepositories {
+    async fn get_model_config(&self) -> Result<Option<Setting>, sqlx::Error> {
+        Ok(self.store().setting.clone())
+    }
+
+    async fn save_model_config(
+        &self,
+        provider: &str,
+        model: &str,
+        whisper_model: &str,
+        ollama_endpoint: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        let mut store = self.store();
+        let setting = store.setting.get_or_insert_with(default_setting);
+        setting.provider = provider.to_string();
+        setting.model = model.to_string();
+        setting.whisper_model = whisper_model.to_string();
+        setting.ollama_endpoint = ollama_endpoint.map(str::to_string);
+        Ok(())
+    }
+
+    async fn get_api_key(&self, provider: &str) -> Result<Option<String>, sqlx::Error> {
+        // Validate the provider even when no settings row exists
+        api_key_slot(&mut default_setting(), provider)?;
+        match self.store().setting.as_mut() {
+            Some(setting) => Ok(api_key_slot(setting, provider)?.clone()),
+            None => Ok(None),
+        }
+    }
+
+    async fn save_api_key(&self, provider: &str, api_key: &str) -> Result<(), sqlx::Error> {
+        let mut store = self.store();
+        let mut setting = store.setting.clone().unwrap_or_else(default_setting);
+        *api_key_slot(&mut setting, provider)? = Some(api_key.to_string());
+        store.setting = Some(setting);
+        Ok(())
+    }
+
+    async fn get_summary_fallbacks(&self) -> Result<Vec<SummaryFallback>, sqlx::Error> {
+        Ok(self.store().summary_fallbacks.clone())
+    }
+
+    async fn save_summary_fallbacks(
+        &self,
+        fallbacks: &[SummaryFallback],
+    ) -> Result<(), sqlx::Error> {
+        let mut store = self.store();
+        // Like the SQL upsert, this creates the settings row if missing
+        store.setting.get_or_insert_with(default_setting);
+        store.summary_fallbacks = fallbacks.to_vec();
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SummaryProcessesRepo for InMemoryRepositories {
+    async fn get_summary_data(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Option<SummaryProcess>, sqlx::Error> {
+        Ok(self.store().processes.get(meeting_id).cloned())
+    }
+
+    async fn create_or_reset_process(&self, meeting_id: &str) -> Result<(), sqlx::Error> {
+        let mut store = self.store();
+        if !store.meeting_exists(meeting_id) {
+            return Err(sqlx::Error::Protocol(
+                "FOREIGN KEY constraint failed".to_string(),
+            ));
+        }
+
+        let now = Utc::now();
+        let process = store
+            .processes
+            .entry(meeting_id.to_string())
+            .or_insert_with(|| SummaryProcess {
+                meeting_id: meeting_id.to_string(),
+                status: "PENDING".to_string(),
+                created_at: now,
+                updated_at: now,
+                error: None,
+                result: None,
+                start_time: None,
+                end_time: None,
+                chunk_count: 0,
+                processing_time: 0.0,
+                metadata: None,
+                error_kind: None,
+                warning_count: None,
+            });
+        process.status = "PENDING".to_string();
+        process.updated_at = now;
+        process.start_time = Some(now);
+        process.result = None;
+        process.error = None;
+        process.error_kind = None;
+        process.warning_count = None;
+        Ok(())
+    }
+
+    async fn update_process_processing(&self, meeting_id: &str) -> Result<(), sqlx::Error> {
+        if let Some(process) = self.store().processes.get_mut(meeting_id) {
+            process.status = "processing".to_string();
+            process.updated_at = Utc::now();
+        }
+        Ok(())
+    }
+
+    async fn update_process_completed(
+        &self,
+        meeting_id: &str,
+        result: Value,
+        chunk_count: i64,
+        processing_time: f64,
+    ) -> Result<(), sqlx::Error> {
+        let result_str = serde_json::to_string(&result)
+            .map_err(|e| sqlx::Error::Protocol(format!("Failed to serialize result: {}", e)))?;
+        if let Some(process) = self.store().processes.get_mut(meeting_id) {
+            let now = Utc::now();
+            process.warning_count = warning_count(&result);
+            process.error_kind = None;
+            process.status = "completed".to_string();
+            process.result = Some(result_str);
+            process.updated_at = now;
+            process.end_time = Some(now);
+            process.chunk_count = chunk_count;
+            process.processing_time = processing_time;
+            process.error = None;
+        }
+        Ok(())
+    }
+
+    async fn update_process_failed(
+        &self,
+        meeting_id: &str,
+        error: &str,
+    ) -> Result<(), sqlx::Error> {
+        if let Some(process) = self.store().processes.get_mut(meeting_id) {
+            let now = Utc::now();
+            process.status = "failed".to_string();
+            process.error = Some(error.to_string());
+            process.error_kind = Some(SummaryErrorKind::classify(error).as_str().to_string());
+            process.updated_at = now;
+            process.end_time = Some(now);
+        }
+        Ok(())
+    }
+
+    async fn update_meeting_summary(
+        &self,
+        meeting_id: &str,
+        summary: &Value,
+    ) -> Result<bool, sqlx::Error> {
+        let mut store = self.store();
+        if !store.meeting_exists(meeting_id) {
+            return Ok(false);
+        }
+        let result_str = match serde_json::to_string(summary) {
+            Ok(s) => s,
+            Err(_) => return Ok(false),
+        };
+
+        let now = Utc::now();
+        if let Some(process) = store.processes.get_mut(meeting_id) {
+            process.result = Some(result_str);
+            process.updated_at = now;
+        }
+        if let Some(meeting) = store.meetings.iter_mut().find(|m| m.id == meeting_id) {
+            meeting.updated_at = DateTimeUtc(now);
+        }
+        Ok(true)
+    }
+
+    async fn delete_live_summaries(&self, _meeting_id: &str) -> Result<u64, sqlx::Error> {
+        // Live drafts are only written while recording, which the fake never does
+        Ok(0)
+    }
+
+    async fn save_summary_version(
+        &self,
+        meeting_id: &str,
+        template_id: &str,
+        result: Option<&Value>,
+        error: Option<&str>,
+    ) -> Result<String, sqlx::Error> {
+        let result_str = result
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| sqlx::Error::Protocol(format!("Failed to serialize result: {}", e)))?;
+        let mut store = self.store();
+        if !store.meeting_exists(meeting_id) {
+            return Err(sqlx::Error::Protocol(
+                "FOREIGN KEY constraint failed".to_string(),
+            ));
+        }
+        let id = Uuid::new_v4().to_string();
+        store.summary_versions.push(SummaryVersion {
+            id: id.clone(),
+            meeting_id: meeting_id.to_string(),
+            template_id: template_id.to_string(),
+            status: if result_str.is_some() { "completed" } else { "failed" }.to_string(),
+            result: result_str,
+            error: error.map(str::to_string),
+            created_at: Utc::now(),
+        });
+        Ok(id)
+    }
+
+    async fn list_summary_versions(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Vec<SummaryVersion>, sqlx::Error> {
+        Ok(self
+            .store()
+            .summary_versions
+            .iter()
+            .rev()
+            .filter(|v| v.meeting_id == meeting_id)
+            .cloned()
+            .collect())
+    }
+}
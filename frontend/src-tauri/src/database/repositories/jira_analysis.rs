@@ -0,0 +1,125 @@
+use crate::database::models::JiraAnalysisProcess;
+use chrono::Utc;
+use serde_json::Value;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+pub struct JiraAnalysisRepository;
+
+impl JiraAnalysisRepository {
+    /// Starts a PENDING run for the meeting, superseding any earlier run;
+    /// returns the new run id
+    pub async fn start(pool: &SqlitePool, meeting_id: &str) -> Result<String, sqlx::Error> {
+        let run_id = Uuid::new_v4().to_string();
+        let now = Utc::now();
+        sqlx::query(
+            r#"
+            INSERT INTO jira_analysis_processes (meeting_id, run_id, status, result, error, created_at, updated_at)
+            VALUES ($1, $2, 'PENDING', NULL, NULL, $3, $3)
+            ON CONFLICT(meeting_id) DO UPDATE SET
+                run_id = excluded.run_id,
+                status = 'PENDING',
+                result = NULL,
+                error = NULL,
+                created_at = excluded.created_at,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(meeting_id)
+        .bind(&run_id)
+        .bind(now)
+        .execute(pool)
+        .await?;
+        Ok(run_id)
+    }
+
+    pub async fn get(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Option<JiraAnalysisProcess>, sqlx::Error> {
+        sqlx::query_as::<_, JiraAnalysisProcess>(
+            "SELECT * FROM jira_analysis_processes WHERE meeting_id = ?",
+        )
+        .bind(meeting_id)
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Moves a PENDING run to processing; false if the run was superseded
+    pub async fn mark_processing(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        run_id: &str,
+    ) -> Result<bool, sqlx::Error> {
+        Self::finish_step(pool, meeting_id, run_id, "PENDING", "processing", None, None).await
+    }
+
+    /// Stores the proposals of a processing run; false if it was superseded
+    pub async fn complete(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        run_id: &str,
+        result: &Value,
+    ) -> Result<bool, sqlx::Error> {
+        let result = serde_json::to_string(result)
+            .map_err(|e| sqlx::Error::Protocol(format!("Failed to serialize result: {}", e)))?;
+        Self::finish_step(
+            pool,
+            meeting_id,
+            run_id,
+            "processing",
+            "completed",
+            Some(&result),
+            None,
+        )
+        .await
+    }
+
+    /// Records why a processing run failed; false if it was superseded
+    pub async fn fail(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        run_id: &str,
+        error: &str,
+    ) -> Result<bool, sqlx::Error> {
+        Self::finish_step(
+            pool,
+            meeting_id,
+            run_id,
+            "processing",
+            "failed",
+            None,
+            Some(error),
+        )
+        .await
+    }
+
+    /// Moves the run from `from` to `to` if it is still the meeting's latest
+    async fn finish_step(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        run_id: &str,
+        from: &str,
+        to: &str,
+        result: Option<&str>,
+        error: Option<&str>,
+    ) -> Result<bool, sqlx::Error> {
+        let updated = sqlx::query(
+            r#"
+            UPDATE jira_analysis_processes
+            SET status = ?, result = ?, error = ?, updated_at = ?
+            WHERE meeting_id = ? AND run_id = ? AND status = ?
+            "#,
+        )
+        .bind(to)
+        .bind(result)
+        .bind(error)
+        .bind(Utc::now())
+        .bind(meeting_id)
+        .bind(run_id)
+        .bind(from)
+        .execute(pool)
+        .await?;
+        Ok(updated.rows_affected() > 0)
+    }
+}
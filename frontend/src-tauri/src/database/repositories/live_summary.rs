@@ -0,0 +1,177 @@
+use crate::database::models::LiveSummary;
+use chrono::Utc;
+use sqlx::SqlitePool;
+
+pub struct LiveSummariesRepository;
+
+impl LiveSummariesRepository {
+    /// Creates or replaces the draft of a recording session
+    pub async fn save_draft(
+        pool: &SqlitePool,
+        session_id: &str,
+        meeting_name: Option<&str>,
+        draft: &str,
+        passes: i64,
+    ) -> Result<(), sqlx::Error> {
+        let now = Utc::now();
+        sqlx::query(
+            r#"
+            INSERT INTO live_summaries (session_id, meeting_name, draft, passes, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $5)
+            ON CONFLICT(session_id) DO UPDATE SET
+                meeting_name = excluded.meeting_name,
+                draft = excluded.draft,
+                passes = excluded.passes,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(session_id)
+        .bind(meeting_name)
+        .bind(draft)
+        .bind(passes)
+        .bind(now)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Rows reserved by `attach_to_meeting` before the first pass finished
+    /// have no draft yet and are skipped
+    pub async fn get_for_session(
+        pool: &SqlitePool,
+        session_id: &str,
+    ) -> Result<Option<LiveSummary>, sqlx::Error> {
+        sqlx::query_as::<_, LiveSummary>(
+            "SELECT * FROM live_summaries WHERE session_id = ? AND passes > 0",
+        )
+        .bind(session_id)
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn get_for_meeting(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Option<LiveSummary>, sqlx::Error> {
+        sqlx::query_as::<_, LiveSummary>(
+            "SELECT * FROM live_summaries WHERE meeting_id = ? AND passes > 0 ORDER BY updated_at DESC LIMIT 1",
+        )
+        .bind(meeting_id)
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Links a session's draft to the meeting its recording was saved as
+    ///
+    /// If the session has no draft yet, an empty row is reserved so a pass
+    /// that finishes later still lands on the meeting.
+    pub async fn attach_to_meeting(
+        pool: &SqlitePool,
+        session_id: &str,
+        meeting_id: &str,
+    ) -> Result<(), sqlx::Error> {
+        let now = Utc::now();
+        sqlx::query(
+            r#"
+            INSERT INTO live_summaries (session_id, meeting_id, draft, passes, created_at, updated_at)
+            VALUES ($1, $2, '', 0, $3, $3)
+            ON CONFLICT(session_id) DO UPDATE SET meeting_id = excluded.meeting_id
+            "#,
+        )
+        .bind(session_id)
+        .bind(meeting_id)
+        .bind(now)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Removes the drafts of a meeting once its final summary exists
+    pub async fn delete_for_meeting(pool: &SqlitePool, meeting_id: &str) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM live_summaries WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::{seed_meetings, test_pool};
+
+    #[tokio::test]
+    async fn test_draft_lifecycle() {
+        let pool = test_pool().await;
+        seed_meetings(&pool, 1, 1).await;
+
+        LiveSummariesRepository::save_draft(&pool, "session-1", Some("Standup"), "first", 1)
+            .await
+            .unwrap();
+        LiveSummariesRepository::save_draft(&pool, "session-1", Some("Standup"), "second", 2)
+            .await
+            .unwrap();
+
+        let draft = LiveSummariesRepository::get_for_session(&pool, "session-1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(draft.draft, "second");
+        assert_eq!(draft.passes, 2);
+
+        LiveSummariesRepository::attach_to_meeting(&pool, "session-1", "meeting-0")
+            .await
+            .unwrap();
+        assert_eq!(
+            LiveSummariesRepository::get_for_meeting(&pool, "meeting-0")
+                .await
+                .unwrap()
+                .unwrap()
+                .draft,
+            "second"
+        );
+
+        assert_eq!(
+            LiveSummariesRepository::delete_for_meeting(&pool, "meeting-0").await.unwrap(),
+            1
+        );
+        assert!(LiveSummariesRepository::get_for_session(&pool, "session-1")
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_draft_stored_after_attaching_lands_on_the_meeting() {
+        let pool = test_pool().await;
+        seed_meetings(&pool, 2, 1).await;
+        LiveSummariesRepository::save_draft(&pool, "other", None, "other draft", 3)
+            .await
+            .unwrap();
+
+        // The recording was saved before its last pass finished
+        LiveSummariesRepository::attach_to_meeting(&pool, "session-1", "meeting-0")
+            .await
+            .unwrap();
+        assert!(LiveSummariesRepository::get_for_meeting(&pool, "meeting-0")
+            .await
+            .unwrap()
+            .is_none());
+
+        LiveSummariesRepository::save_draft(&pool, "session-1", Some("Standup"), "final", 1)
+            .await
+            .unwrap();
+        let draft = LiveSummariesRepository::get_for_meeting(&pool, "meeting-0")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(draft.session_id, "session-1");
+        assert_eq!(draft.draft, "final");
+        // Another session's draft is never picked up
+        assert!(LiveSummariesRepository::get_for_meeting(&pool, "meeting-1")
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
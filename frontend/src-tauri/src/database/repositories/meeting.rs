@@ -1,14 +1,37 @@
-use crate::api::{MeetingDetails, MeetingTranscript};
-use crate::database::models::{MeetingModel, Transcript};
-use chrono::Utc;
+use crate::api::{MeetingDetails, MeetingOverview, MeetingTranscript, TranscriptSegment};
+use crate::database::transcripts_json::TranscriptsJsonCache;
+use crate::database::models::{
+    MeetingContext, MeetingModel, MeetingOverviewRow, MeetingParticipant, MeetingPreferences,
+    ParticipantMap,
+    Transcript,
+};
+use chrono::{DateTime, Utc};
 use sqlx::{Connection, Error as SqlxError, SqliteConnection, SqlitePool};
 use tracing::{error, info, warn};
-use std::path::PathBuf;
-use std::fs;
-use serde_json::Value;
 
 pub struct MeetingsRepository;
 
+/// Maximum characters kept in a meeting overview summary snippet
+const SUMMARY_SNIPPET_CHARS: usize = 160;
+
+/// Single query backing the meetings overview. Transcript aggregates are
+/// correlated subqueries served by idx_transcripts_meeting_id_end_time, and
+/// only the head of the summary markdown is read to keep rows small.
+const MEETINGS_OVERVIEW_QUERY: &str = r#"
+    SELECT
+        m.id,
+        m.title,
+        m.created_at,
+        m.updated_at,
+        (SELECT MAX(t.audio_end_time) FROM transcripts t WHERE t.meeting_id = m.id) AS duration,
+        (SELECT COUNT(*) FROM transcripts t WHERE t.meeting_id = m.id) AS segment_count,
+        sp.status AS summary_status,
+        substr(json_extract(sp.result, '$.markdown'), 1, 4000) AS summary_markdown
+    FROM meetings m
+    LEFT JOIN summary_processes sp ON sp.meeting_id = m.id
+    ORDER BY m.created_at DESC
+"#;
+
 impl MeetingsRepository {
     pub async fn get_meetings(pool: &SqlitePool) -> Result<Vec<MeetingModel>, sqlx::Error> {
         let meetings =
@@ -18,6 +41,48 @@ impl MeetingsRepository {
         Ok(meetings)
     }
 
+    /// Meetings created at or after `from` and before `to`, newest first; an
+    /// open bound is None
+    pub async fn get_meetings_created_between(
+        pool: &SqlitePool,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<MeetingModel>, sqlx::Error> {
+        sqlx::query_as::<_, MeetingModel>(
+            "SELECT * FROM meetings WHERE (? IS NULL OR created_at >= ?) AND (? IS NULL OR created_at < ?) ORDER BY created_at DESC",
+        )
+        .bind(from)
+        .bind(from)
+        .bind(to)
+        .bind(to)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Lists meetings with duration, segment count, latest summary status and
+    /// a summary snippet, all fetched in one query (no per-row lookups).
+    pub async fn get_meetings_overview(
+        pool: &SqlitePool,
+    ) -> Result<Vec<MeetingOverview>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, MeetingOverviewRow>(MEETINGS_OVERVIEW_QUERY)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| MeetingOverview {
+                summary_snippet: row.summary_markdown.as_deref().and_then(summary_snippet),
+                id: row.id,
+                title: row.title,
+                created_at: row.created_at.0.to_rfc3339(),
+                updated_at: row.updated_at.0.to_rfc3339(),
+                duration: row.duration,
+                segment_count: row.segment_count,
+                summary_status: row.summary_status.map(|s| s.to_lowercase()),
+            })
+            .collect())
+    }
+
     pub async fn delete_meeting(pool: &SqlitePool, meeting_id: &str) -> Result<bool, SqlxError> {
         if meeting_id.trim().is_empty() {
             return Err(SqlxError::Protocol(
@@ -50,9 +115,74 @@ impl MeetingsRepository {
         }
     }
 
+    /// (meeting id, folder path) for every meeting with a recording folder
+    pub async fn get_folder_paths(pool: &SqlitePool) -> Result<Vec<(String, String)>, SqlxError> {
+        sqlx::query_as(
+            "SELECT id, folder_path FROM meetings WHERE folder_path IS NOT NULL AND folder_path != '' ORDER BY created_at, rowid",
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// The meeting's recording folder; None if it has none or doesn't exist
+    pub async fn get_folder_path(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Option<String>, SqlxError> {
+        let row: Option<(Option<String>,)> =
+            sqlx::query_as("SELECT folder_path FROM meetings WHERE id = ?")
+                .bind(meeting_id)
+                .fetch_optional(pool)
+                .await?;
+        Ok(row
+            .and_then(|(folder_path,)| folder_path)
+            .filter(|folder_path| !folder_path.is_empty()))
+    }
+
+    /// Points one meeting at a moved folder; false if the meeting doesn't exist
+    pub async fn set_folder_path(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        folder_path: &str,
+    ) -> Result<bool, SqlxError> {
+        let result = sqlx::query("UPDATE meetings SET folder_path = ? WHERE id = ?")
+            .bind(folder_path)
+            .bind(meeting_id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Points meetings at renamed folders, given as (old path, new path);
+    /// either every meeting is updated or none is
+    pub async fn update_folder_paths(
+        pool: &SqlitePool,
+        renames: &[(String, String)],
+    ) -> Result<u64, SqlxError> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+
+        let mut updated = 0;
+        for (from, to) in renames {
+            updated += sqlx::query("UPDATE meetings SET folder_path = ? WHERE folder_path = ?")
+                .bind(to)
+                .bind(from)
+                .execute(&mut *transaction)
+                .await?
+                .rows_affected();
+        }
+        transaction.commit().await?;
+
+        info!("Updated folder path of {} meeting(s)", updated);
+        Ok(updated)
+    }
+
+    /// The meeting and its transcript; meetings with no segments stored fall
+    /// back to the `transcripts.json` in their recording folder
     pub async fn get_meeting(
         pool: &SqlitePool,
         meeting_id: &str,
+        transcripts_json: &TranscriptsJsonCache,
     ) -> Result<Option<MeetingDetails>, SqlxError> {
         if meeting_id.trim().is_empty() {
             return Err(SqlxError::Protocol(
@@ -102,64 +232,21 @@ impl MeetingsRepository {
             // If there are no transcripts stored in the database yet but we have a
             // recording folder with transcripts.json, try to load segments directly
             // from that file so the frontend can still display the transcript.
+            // Once segments are stored the file isn't looked at again.
             if meeting_transcripts.is_empty() {
                 if let Some(folder_path) = &meeting.folder_path {
-                    let path = PathBuf::from(folder_path).join("transcripts.json");
-                    if path.exists() {
-                        match fs::read_to_string(&path) {
-                            Ok(contents) => {
-                                match serde_json::from_str::<Value>(&contents) {
-                                    Ok(json) => {
-                                        if let Some(segments) = json.get("segments").and_then(|v| v.as_array()) {
-                                            for seg in segments {
-                                                // Map recording_saver::TranscriptSegment JSON into MeetingTranscript
-                                                if let Ok(s) = serde_json::from_value::<crate::audio::recording_saver::TranscriptSegment>(seg.clone()) {
-                                                    meeting_transcripts.push(MeetingTranscript {
-                                                        id: s.id.clone(),
-                                                        text: s.text.clone(),
-                                                        // Use the human-friendly display time as timestamp for now
-                                                        timestamp: s.display_time.clone(),
-                                                        audio_start_time: Some(s.audio_start_time),
-                                                        audio_end_time: Some(s.audio_end_time),
-                                                        duration: Some(s.duration),
-                                                    });
-                                                }
-                                            }
-
-                                            if !meeting_transcripts.is_empty() {
-                                                info!(
-                                                    "Loaded {} transcript segments from transcripts.json for meeting {}",
-                                                    meeting_transcripts.len(),
-                                                    meeting_id
-                                                );
-                                            }
-                                        } else {
-                                            warn!(
-                                                "transcripts.json for meeting {} does not contain a 'segments' array",
-                                                meeting_id
-                                            );
-                                        }
-                                    }
-                                    Err(e) => {
-                                        warn!(
-                                            "Failed to parse transcripts.json for meeting {} at {}: {}",
-                                            meeting_id,
-                                            path.display(),
-                                            e
-                                        );
-                                    }
-                                }
-                            }
-                            Err(e) => {
-                                warn!(
-                                    "Failed to read transcripts.json for meeting {} at {}: {}",
-                                    meeting_id,
-                                    path.display(),
-                                    e
-                                );
-                            }
-                        }
-                    }
+                    meeting_transcripts = transcripts_json
+                        .segments(meeting_id, folder_path)
+                        .into_iter()
+                        .map(|s| MeetingTranscript {
+                            id: s.id,
+                            text: s.text,
+                            timestamp: s.timestamp,
+                            audio_start_time: s.audio_start_time,
+                            audio_end_time: s.audio_end_time,
+                            duration: s.duration,
+                        })
+                        .collect();
                 }
             }
 
@@ -168,6 +255,7 @@ impl MeetingsRepository {
                 title: meeting.title,
                 created_at: meeting.created_at.0.to_rfc3339(),
                 updated_at: meeting.updated_at.0.to_rfc3339(),
+                local_date: None,
                 transcripts: meeting_transcripts,
             }))
         } else {
@@ -176,6 +264,10 @@ impl MeetingsRepository {
         }
     }
 
+    /// Renames a meeting, including the copy of its name in `transcript_chunks`
+    ///
+    /// Every rename goes through here: the summary pipeline, the title editor
+    /// and the API.
     pub async fn update_meeting_title(
         pool: &SqlitePool,
         meeting_id: &str,
@@ -190,7 +282,7 @@ impl MeetingsRepository {
         let mut conn = pool.acquire().await?;
         let mut transaction = conn.begin().await?;
 
-        let now = Utc::now().naive_utc();
+        let now = Utc::now();
 
         let rows_affected =
             sqlx::query("UPDATE meetings SET title = ?, updated_at = ? WHERE id = ?")
@@ -203,42 +295,247 @@ impl MeetingsRepository {
             transaction.rollback().await?;
             return Ok(false);
         }
+
+        // transcript_chunks keeps a copy of the name
+        sqlx::query("UPDATE transcript_chunks SET meeting_name = ? WHERE meeting_id = ?")
+            .bind(new_title)
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+
         transaction.commit().await?;
         Ok(true)
     }
 
-    pub async fn update_meeting_name(
+    /// Returns the stored template/model defaults, or None if the meeting doesn't exist
+    pub async fn get_meeting_preferences(
         pool: &SqlitePool,
         meeting_id: &str,
-        new_title: &str,
+    ) -> Result<Option<MeetingPreferences>, SqlxError> {
+        sqlx::query_as::<_, MeetingPreferences>(
+            "SELECT preferred_template_id, preferred_model_provider, preferred_model_name FROM meetings WHERE id = ?",
+        )
+        .bind(meeting_id)
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Stores the template/model defaults for a meeting (None clears a value)
+    pub async fn set_meeting_preferences(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        preferences: &MeetingPreferences,
     ) -> Result<bool, SqlxError> {
-        let mut transaction = pool.begin().await?;
-        let now = Utc::now();
+        let result = sqlx::query(
+            "UPDATE meetings SET preferred_template_id = ?, preferred_model_provider = ?, preferred_model_name = ? WHERE id = ?",
+        )
+        .bind(&preferences.template_id)
+        .bind(&preferences.model_provider)
+        .bind(&preferences.model_name)
+        .bind(meeting_id)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
 
-        // Update meetings table
-        let meeting_update =
-            sqlx::query("UPDATE meetings SET title = ?, updated_at = ? WHERE id = ?")
-                .bind(new_title)
-                .bind(now)
+    /// Distinct template ids stored as meeting defaults
+    pub async fn preferred_template_ids(pool: &SqlitePool) -> Result<Vec<String>, SqlxError> {
+        sqlx::query_scalar(
+            "SELECT DISTINCT preferred_template_id FROM meetings WHERE preferred_template_id IS NOT NULL AND trim(preferred_template_id) != '' ORDER BY preferred_template_id",
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Points every meeting defaulting to template `from` at `to`; returns
+    /// how many meetings changed
+    pub async fn replace_preferred_template(
+        pool: &SqlitePool,
+        from: &str,
+        to: &str,
+    ) -> Result<u64, SqlxError> {
+        let result = sqlx::query(
+            "UPDATE meetings SET preferred_template_id = ? WHERE preferred_template_id = ?",
+        )
+        .bind(to)
+        .bind(from)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Stored name -> pseudonym map; empty if none was stored, None if the
+    /// meeting doesn't exist
+    pub async fn get_participant_map(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Option<ParticipantMap>, SqlxError> {
+        let stored: Option<Option<String>> =
+            sqlx::query_scalar("SELECT participant_map FROM meetings WHERE id = ?")
                 .bind(meeting_id)
-                .execute(&mut *transaction)
+                .fetch_optional(pool)
                 .await?;
+        Ok(stored.map(|json| {
+            json.and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default()
+        }))
+    }
 
-        if meeting_update.rows_affected() == 0 {
-            transaction.rollback().await?;
-            return Ok(false); // Meeting not found
-        }
+    pub async fn set_participant_map(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        map: &ParticipantMap,
+    ) -> Result<bool, SqlxError> {
+        let json = serde_json::to_string(map)
+            .map_err(|e| SqlxError::Protocol(format!("Invalid participant map: {}", e)))?;
+        let result = sqlx::query("UPDATE meetings SET participant_map = ? WHERE id = ?")
+            .bind(json)
+            .bind(meeting_id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
 
-        // Update transcript_chunks table
-        sqlx::query("UPDATE transcript_chunks SET meeting_name = ? WHERE meeting_id = ?")
-            .bind(new_title)
+    /// Attendees in the order they were first mentioned; None if the meeting
+    /// doesn't exist
+    pub async fn get_participants(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Option<Vec<MeetingParticipant>>, SqlxError> {
+        let exists: Option<String> = sqlx::query_scalar("SELECT id FROM meetings WHERE id = ?")
             .bind(meeting_id)
-            .execute(&mut *transaction)
+            .fetch_optional(pool)
             .await?;
+        if exists.is_none() {
+            return Ok(None);
+        }
+        let rows: Vec<(String, Option<String>, String)> = sqlx::query_as(
+            "SELECT name, role, mentions FROM meeting_participants WHERE meeting_id = ? ORDER BY position",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await?;
+        Ok(Some(
+            rows.into_iter()
+                .map(|(name, role, mentions)| MeetingParticipant {
+                    name,
+                    role,
+                    mentions: serde_json::from_str(&mentions).unwrap_or_default(),
+                })
+                .collect(),
+        ))
+    }
 
-        transaction.commit().await?;
+    /// Replaces the meeting's attendees; false if the meeting doesn't exist
+    pub async fn set_participants(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        participants: &[MeetingParticipant],
+    ) -> Result<bool, SqlxError> {
+        let mut tx = pool.begin().await?;
+        let exists: Option<String> = sqlx::query_scalar("SELECT id FROM meetings WHERE id = ?")
+            .bind(meeting_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+        if exists.is_none() {
+            return Ok(false);
+        }
+        sqlx::query("DELETE FROM meeting_participants WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .execute(&mut *tx)
+            .await?;
+        for (position, participant) in participants.iter().enumerate() {
+            let mentions = serde_json::to_string(&participant.mentions)
+                .map_err(|e| SqlxError::Protocol(format!("Invalid participant mentions: {}", e)))?;
+            sqlx::query(
+                "INSERT INTO meeting_participants (meeting_id, position, name, role, mentions) VALUES (?, ?, ?, ?, ?)",
+            )
+            .bind(meeting_id)
+            .bind(position as i64)
+            .bind(&participant.name)
+            .bind(&participant.role)
+            .bind(mentions)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
         Ok(true)
     }
+
+    /// Background notes stored for the meeting; None if there are none or
+    /// the meeting doesn't exist
+    pub async fn get_meeting_context(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Option<MeetingContext>, SqlxError> {
+        sqlx::query_as::<_, MeetingContext>(
+            "SELECT background_context, background_context_source, background_context_tokens FROM meetings WHERE id = ? AND background_context IS NOT NULL",
+        )
+        .bind(meeting_id)
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Stores the background notes for a meeting; None clears them
+    pub async fn set_meeting_context(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        context: Option<&MeetingContext>,
+    ) -> Result<bool, SqlxError> {
+        let result = sqlx::query(
+            "UPDATE meetings SET background_context = ?, background_context_source = ?, background_context_tokens = ? WHERE id = ?",
+        )
+        .bind(context.map(|c| c.text.as_str()))
+        .bind(context.and_then(|c| c.source.as_deref()))
+        .bind(context.map(|c| c.token_count))
+        .bind(meeting_id)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+/// Builds a short plain-text snippet from the "Summary" section of a summary
+/// markdown, falling back to the first paragraph when no such section exists.
+fn summary_snippet(markdown: &str) -> Option<String> {
+    let mut in_summary = false;
+    let mut summary_lines: Vec<&str> = Vec::new();
+    let mut first_text: Option<&str> = None;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('#') {
+            if in_summary {
+                break;
+            }
+            in_summary = trimmed
+                .trim_start_matches('#')
+                .trim()
+                .eq_ignore_ascii_case("summary");
+            continue;
+        }
+        if trimmed.is_empty() {
+            continue;
+        }
+        if in_summary {
+            summary_lines.push(trimmed);
+        } else if first_text.is_none() {
+            first_text = Some(trimmed);
+        }
+    }
+
+    let text = if summary_lines.is_empty() {
+        first_text?.to_string()
+    } else {
+        summary_lines.join(" ")
+    };
+
+    if text.chars().count() > SUMMARY_SNIPPET_CHARS {
+        let truncated: String = text.chars().take(SUMMARY_SNIPPET_CHARS).collect();
+        Some(format!("{}...", truncated.trim_end()))
+    } else {
+        Some(text)
+    }
 }
 
 async fn delete_meeting_with_transaction(
@@ -256,30 +553,253 @@ async fn delete_meeting_with_transaction(
         return Ok(false);
     }
 
-    // Delete from related tables in proper order
-    // 1. Delete from transcript_chunks
-    sqlx::query("DELETE FROM transcript_chunks WHERE meeting_id = ?")
+    // transcripts, transcript_chunks and summary_processes reference meetings
+    // with ON DELETE CASCADE (foreign keys are enabled on every connection)
+    let result = sqlx::query("DELETE FROM meetings WHERE id = ?")
         .bind(meeting_id)
         .execute(&mut *transaction)
         .await?;
 
-    // 2. Delete from summary_processes
-    sqlx::query("DELETE FROM summary_processes WHERE meeting_id = ?")
-        .bind(meeting_id)
-        .execute(&mut *transaction)
-        .await?;
+    Ok(result.rows_affected() > 0)
+}
 
-    // 3. Delete from transcripts
-    sqlx::query("DELETE FROM transcripts WHERE meeting_id = ?")
-        .bind(meeting_id)
-        .execute(&mut *transaction)
-        .await?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::{seed_meetings, test_pool};
 
-    // 4. Finally, delete the meeting
-    let result = sqlx::query("DELETE FROM meetings WHERE id = ?")
-        .bind(meeting_id)
-        .execute(&mut *transaction)
-        .await?;
+    async fn assert_overview_uses_indexes(pool: &SqlitePool) {
+        let plan: Vec<(i64, i64, i64, String)> =
+            sqlx::query_as(&format!("EXPLAIN QUERY PLAN {}", MEETINGS_OVERVIEW_QUERY))
+                .fetch_all(pool)
+                .await
+                .unwrap();
+        let details: Vec<&str> = plan.iter().map(|(_, _, _, d)| d.as_str()).collect();
 
-    Ok(result.rows_affected() > 0)
+        assert!(
+            details.iter().all(|d| !d.starts_with("SCAN t")),
+            "transcripts must not be scanned: {:?}",
+            details
+        );
+        assert!(
+            details
+                .iter()
+                .any(|d| d.contains("idx_transcripts_meeting_id_end_time")),
+            "expected transcript index in plan: {:?}",
+            details
+        );
+        assert!(
+            details.iter().all(|d| !d.contains("USE TEMP B-TREE FOR ORDER BY")),
+            "ordering should come from idx_meetings_created_at: {:?}",
+            details
+        );
+    }
+
+    #[tokio::test]
+    async fn test_overview_query_uses_indexes() {
+        let pool = test_pool().await;
+        assert_overview_uses_indexes(&pool).await;
+    }
+
+    #[tokio::test]
+    async fn test_overview_aggregates() {
+        let pool = test_pool().await;
+        seed_meetings(&pool, 3, 4).await;
+
+        let overview = MeetingsRepository::get_meetings_overview(&pool).await.unwrap();
+        assert_eq!(overview.len(), 3);
+
+        let newest = &overview[0];
+        assert_eq!(newest.id, "meeting-0");
+        assert_eq!(newest.segment_count, 4);
+        assert_eq!(newest.duration, Some(20.0));
+        assert_eq!(newest.summary_status.as_deref(), Some("completed"));
+        assert_eq!(
+            newest.summary_snippet.as_deref(),
+            Some("Meeting 0 covered the release plan.")
+        );
+
+        let without_summary = &overview[1];
+        assert_eq!(without_summary.summary_status, None);
+        assert_eq!(without_summary.summary_snippet, None);
+    }
+
+    #[tokio::test]
+    async fn test_overview_plan_with_seeded_data() {
+        let pool = test_pool().await;
+        seed_meetings(&pool, 2000, 10).await;
+
+        let overview = MeetingsRepository::get_meetings_overview(&pool).await.unwrap();
+        assert_eq!(overview.len(), 2000);
+
+        // Wall-clock limits flake on slow machines; check that the planner
+        // keeps using the indexes once it has statistics for a large library
+        sqlx::query("ANALYZE").execute(&pool).await.unwrap();
+        assert_overview_uses_indexes(&pool).await;
+    }
+
+    #[tokio::test]
+    async fn test_delete_meeting_cascades_to_child_tables() {
+        let pool = test_pool().await;
+        seed_meetings(&pool, 2, 3).await;
+        sqlx::query(
+            "INSERT INTO transcript_chunks (meeting_id, transcript_text, model, model_name, created_at) VALUES ('meeting-0', 'text', 'ollama', 'llama3', ?)",
+        )
+        .bind(Utc::now())
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        assert!(MeetingsRepository::delete_meeting(&pool, "meeting-0").await.unwrap());
+
+        for table in ["transcripts", "transcript_chunks", "summary_processes"] {
+            let count: i64 = sqlx::query_scalar(&format!(
+                "SELECT COUNT(*) FROM {} WHERE meeting_id = 'meeting-0'",
+                table
+            ))
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+            assert_eq!(count, 0, "{} rows were not cascaded", table);
+        }
+
+        let remaining: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM transcripts WHERE meeting_id = 'meeting-1'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(remaining, 3);
+    }
+
+    #[tokio::test]
+    async fn test_orphan_insert_is_rejected() {
+        let pool = test_pool().await;
+        let result = sqlx::query(
+            "INSERT INTO transcripts (id, meeting_id, transcript, timestamp) VALUES ('t', 'missing', 'x', '0')",
+        )
+        .execute(&pool)
+        .await;
+        assert!(result.is_err());
+    }
+
+    const NORMALIZE_TIMESTAMPS: &str =
+        include_str!("../../../migrations/20251221000000_normalize_meeting_timestamps.sql");
+
+    async fn insert_meeting(pool: &SqlitePool, id: &str, created_at: &str) {
+        sqlx::query("INSERT INTO meetings (id, title, created_at, updated_at) VALUES (?, ?, ?, ?)")
+            .bind(id)
+            .bind(id)
+            .bind(created_at)
+            .bind(created_at)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+
+    async fn stored_timestamps(pool: &SqlitePool, id: &str) -> (String, String) {
+        sqlx::query_as("SELECT created_at, updated_at FROM meetings WHERE id = ?")
+            .bind(id)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_migration_normalizes_naive_timestamps_to_utc_rfc3339() {
+        let pool = test_pool().await;
+        insert_meeting(&pool, "naive", "2024-03-10 04:30:00").await;
+        insert_meeting(&pool, "naive-fraction", "2024-03-10 04:30:00.250").await;
+        insert_meeting(&pool, "offset", "2024-03-10T06:30:00+02:00").await;
+        insert_meeting(&pool, "zulu", "2024-03-10T04:30:00Z").await;
+        insert_meeting(&pool, "canonical", "2024-03-10T04:30:00.123456789+00:00").await;
+        insert_meeting(&pool, "unparseable", "yesterday").await;
+
+        sqlx::raw_sql(NORMALIZE_TIMESTAMPS)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        for (id, expected) in [
+            ("naive", "2024-03-10T04:30:00.000+00:00"),
+            ("naive-fraction", "2024-03-10T04:30:00.250+00:00"),
+            ("offset", "2024-03-10T04:30:00.000+00:00"),
+            ("zulu", "2024-03-10T04:30:00.000+00:00"),
+            ("canonical", "2024-03-10T04:30:00.123456789+00:00"),
+            ("unparseable", "yesterday"),
+        ] {
+            assert_eq!(
+                stored_timestamps(&pool, id).await,
+                (expected.to_string(), expected.to_string()),
+                "{}",
+                id
+            );
+        }
+
+        // Renames store RFC3339 UTC too, so both columns stay comparable
+        assert!(
+            MeetingsRepository::update_meeting_title(&pool, "naive", "Renamed")
+                .await
+                .unwrap()
+        );
+        let (_, updated_at) = stored_timestamps(&pool, "naive").await;
+        assert!(updated_at.contains('T') && updated_at.ends_with("+00:00"));
+    }
+
+    #[tokio::test]
+    async fn test_meetings_filtered_by_day_in_user_timezone() {
+        use crate::timezone::{parse_date, UserTimezone};
+
+        let pool = test_pool().await;
+        // 23:30 and 00:30 New York time around the start of 2024-03-10
+        insert_meeting(&pool, "evening-before", "2024-03-10T04:30:00.000+00:00").await;
+        insert_meeting(&pool, "just-after-midnight", "2024-03-10T05:30:00+00:00").await;
+        // 23:30 New York time on the 23-hour DST day
+        insert_meeting(&pool, "late-that-day", "2024-03-11T03:30:00+00:00").await;
+        insert_meeting(&pool, "next-day", "2024-03-11T04:00:00+00:00").await;
+
+        let new_york = UserTimezone::Named(chrono_tz::America::New_York);
+        let day = parse_date("2024-03-10").unwrap();
+        let (from, to) = new_york.day_range(Some(day), Some(day));
+        let ids: Vec<String> = MeetingsRepository::get_meetings_created_between(&pool, from, to)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|m| m.id)
+            .collect();
+        assert_eq!(ids, vec!["late-that-day", "just-after-midnight"]);
+
+        let (from, _) = new_york.day_range(Some(day), None);
+        let all_since = MeetingsRepository::get_meetings_created_between(&pool, from, None)
+            .await
+            .unwrap();
+        assert_eq!(all_since.len(), 3);
+        assert_eq!(
+            MeetingsRepository::get_meetings_created_between(&pool, None, None)
+                .await
+                .unwrap()
+                .len(),
+            4
+        );
+    }
+
+    #[test]
+    fn test_summary_snippet() {
+        let markdown = "## Summary\n\nFirst line.\nSecond line.\n\n## Action Items\n\n| a |";
+        assert_eq!(
+            summary_snippet(markdown).as_deref(),
+            Some("First line. Second line.")
+        );
+
+        assert_eq!(
+            summary_snippet("Plain text only").as_deref(),
+            Some("Plain text only")
+        );
+
+        let long = format!("## Summary\n\n{}", "word ".repeat(100));
+        let snippet = summary_snippet(&long).unwrap();
+        assert!(snippet.ends_with("..."));
+        assert!(snippet.chars().count() <= SUMMARY_SNIPPET_CHARS + 3);
+
+        assert_eq!(summary_snippet(""), None);
+    }
 }
@@ -1,5 +1,20 @@
+#[cfg(test)]
+pub mod in_memory;
+pub mod jira_analysis;
+pub mod live_summary;
 pub mod meeting;
+pub mod people;
+pub mod pipeline;
+pub mod question;
+pub mod retention;
 pub mod setting;
 pub mod summary;
+pub mod traits;
 pub mod transcript;
 pub mod transcript_chunk;
+pub mod transcription_status;
+pub mod webhook_delivery;
+
+pub use traits::{
+    MeetingsRepo, Repositories, SettingsRepo, SummaryProcessesRepo, TranscriptsRepo,
+};
@@ -0,0 +1,195 @@
+use crate::database::models::{Person, PersonInput};
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, SqlitePool};
+use uuid::Uuid;
+
+pub struct PeopleRepository;
+
+/// `people` row as stored; aliases are a JSON array
+#[derive(FromRow)]
+struct PersonRow {
+    id: String,
+    display_name: String,
+    aliases: String,
+    email: Option<String>,
+    jira_account_id: Option<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<PersonRow> for Person {
+    fn from(row: PersonRow) -> Self {
+        Person {
+            id: row.id,
+            display_name: row.display_name,
+            aliases: serde_json::from_str(&row.aliases).unwrap_or_default(),
+            email: row.email,
+            jira_account_id: row.jira_account_id,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+/// Trims the input and drops empty aliases/optional fields
+fn normalize(input: &PersonInput) -> Result<PersonInput, sqlx::Error> {
+    let display_name = input.display_name.trim();
+    if display_name.is_empty() {
+        return Err(sqlx::Error::Protocol("display_name cannot be empty".to_string()));
+    }
+    let non_empty = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+    };
+    let mut aliases: Vec<String> = Vec::new();
+    for alias in input.aliases.iter().map(|a| a.trim()).filter(|a| !a.is_empty()) {
+        if !aliases.iter().any(|a| a.eq_ignore_ascii_case(alias)) {
+            aliases.push(alias.to_string());
+        }
+    }
+    Ok(PersonInput {
+        display_name: display_name.to_string(),
+        aliases,
+        email: non_empty(&input.email),
+        jira_account_id: non_empty(&input.jira_account_id),
+    })
+}
+
+fn aliases_json(aliases: &[String]) -> Result<String, sqlx::Error> {
+    serde_json::to_string(aliases)
+        .map_err(|e| sqlx::Error::Protocol(format!("Failed to serialize aliases: {}", e)))
+}
+
+impl PeopleRepository {
+    pub async fn list(pool: &SqlitePool) -> Result<Vec<Person>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, PersonRow>(
+            "SELECT * FROM people ORDER BY display_name COLLATE NOCASE",
+        )
+        .fetch_all(pool)
+        .await?;
+        Ok(rows.into_iter().map(Person::from).collect())
+    }
+
+    pub async fn get(pool: &SqlitePool, id: &str) -> Result<Option<Person>, sqlx::Error> {
+        let row = sqlx::query_as::<_, PersonRow>("SELECT * FROM people WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await?;
+        Ok(row.map(Person::from))
+    }
+
+    pub async fn create(pool: &SqlitePool, input: &PersonInput) -> Result<Person, sqlx::Error> {
+        let input = normalize(input)?;
+        let id = format!("person-{}", Uuid::new_v4());
+        let now = Utc::now();
+        sqlx::query(
+            "INSERT INTO people (id, display_name, aliases, email, jira_account_id, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&id)
+        .bind(&input.display_name)
+        .bind(aliases_json(&input.aliases)?)
+        .bind(&input.email)
+        .bind(&input.jira_account_id)
+        .bind(now)
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+        Ok(Person {
+            id,
+            display_name: input.display_name,
+            aliases: input.aliases,
+            email: input.email,
+            jira_account_id: input.jira_account_id,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Replaces every editable field; returns None if the person doesn't exist
+    pub async fn update(
+        pool: &SqlitePool,
+        id: &str,
+        input: &PersonInput,
+    ) -> Result<Option<Person>, sqlx::Error> {
+        let input = normalize(input)?;
+        let result = sqlx::query(
+            "UPDATE people SET display_name = ?, aliases = ?, email = ?, jira_account_id = ?, updated_at = ? WHERE id = ?",
+        )
+        .bind(&input.display_name)
+        .bind(aliases_json(&input.aliases)?)
+        .bind(&input.email)
+        .bind(&input.jira_account_id)
+        .bind(Utc::now())
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+        Self::get(pool, id).await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM people WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::test_pool;
+
+    fn input(name: &str, aliases: &[&str]) -> PersonInput {
+        PersonInput {
+            display_name: name.to_string(),
+            aliases: aliases.iter().map(|a| a.to_string()).collect(),
+            email: Some(" alex@example.com ".to_string()),
+            jira_account_id: Some(String::new()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_people_crud() {
+        let pool = test_pool().await;
+
+        let created = PeopleRepository::create(&pool, &input(" Alex Kim ", &["AK", "ak", " "]))
+            .await
+            .unwrap();
+        assert_eq!(created.display_name, "Alex Kim");
+        assert_eq!(created.aliases, vec!["AK"]);
+        assert_eq!(created.email.as_deref(), Some("alex@example.com"));
+        assert_eq!(created.jira_account_id, None);
+        assert_eq!(PeopleRepository::list(&pool).await.unwrap(), vec![created.clone()]);
+
+        let mut changed = input("Alex Kim", &["Lex"]);
+        changed.jira_account_id = Some("5b10a2844c20165700ede21g".to_string());
+        let updated = PeopleRepository::update(&pool, &created.id, &changed)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.aliases, vec!["Lex"]);
+        assert_eq!(
+            updated.jira_account_id.as_deref(),
+            Some("5b10a2844c20165700ede21g")
+        );
+        assert!(PeopleRepository::update(&pool, "missing", &changed)
+            .await
+            .unwrap()
+            .is_none());
+
+        assert!(PeopleRepository::create(&pool, &input("  ", &[])).await.is_err());
+
+        assert!(PeopleRepository::delete(&pool, &created.id).await.unwrap());
+        assert!(!PeopleRepository::delete(&pool, &created.id).await.unwrap());
+        assert!(PeopleRepository::list(&pool).await.unwrap().is_empty());
+    }
+}
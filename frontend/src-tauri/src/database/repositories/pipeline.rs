@@ -0,0 +1,385 @@
+use crate::database::models::{NamedPipeline, NamedPipelineInput, PipelineOutputFormat};
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, SqliteConnection, SqlitePool};
+use uuid::Uuid;
+
+pub struct PipelinesRepository;
+
+/// Highest temperature any supported provider accepts
+const MAX_TEMPERATURE: f64 = 2.0;
+
+/// `pipelines` row as stored; webhook_ids is a JSON array
+#[derive(FromRow)]
+struct PipelineRow {
+    id: String,
+    name: String,
+    template_id: Option<String>,
+    model_provider: Option<String>,
+    model_name: Option<String>,
+    compact_transcript: Option<bool>,
+    provenance: Option<bool>,
+    citations: Option<bool>,
+    anonymize: Option<bool>,
+    refine: Option<bool>,
+    output_format: Option<String>,
+    temperature: Option<f64>,
+    max_tokens: Option<i64>,
+    webhook_ids: String,
+    built_in: bool,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<PipelineRow> for NamedPipeline {
+    fn from(row: PipelineRow) -> Self {
+        NamedPipeline {
+            id: row.id,
+            name: row.name,
+            template_id: row.template_id,
+            model_provider: row.model_provider,
+            model_name: row.model_name,
+            compact_transcript: row.compact_transcript,
+            provenance: row.provenance,
+            citations: row.citations,
+            anonymize: row.anonymize,
+            refine: row.refine,
+            output_format: row
+                .output_format
+                .and_then(|format| serde_json::from_value(serde_json::Value::String(format)).ok()),
+            temperature: row.temperature,
+            max_tokens: row.max_tokens.and_then(|tokens| u32::try_from(tokens).ok()),
+            webhook_ids: serde_json::from_str(&row.webhook_ids).unwrap_or_default(),
+            built_in: row.built_in,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+/// Trims the input and drops empty optional fields and webhook ids
+fn normalize(input: &NamedPipelineInput) -> Result<NamedPipelineInput, sqlx::Error> {
+    let name = input.name.trim();
+    if name.is_empty() {
+        return Err(sqlx::Error::Protocol(
+            "Pipeline name cannot be empty".to_string(),
+        ));
+    }
+    if let Some(temperature) = input.temperature {
+        if !(0.0..=MAX_TEMPERATURE).contains(&temperature) {
+            return Err(sqlx::Error::Protocol(format!(
+                "Pipeline temperature must be between 0 and {}",
+                MAX_TEMPERATURE
+            )));
+        }
+    }
+    if input.max_tokens == Some(0) {
+        return Err(sqlx::Error::Protocol(
+            "Pipeline max tokens must be greater than 0".to_string(),
+        ));
+    }
+    let non_empty = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+    };
+    let mut webhook_ids: Vec<String> = Vec::new();
+    for id in input
+        .webhook_ids
+        .iter()
+        .map(|id| id.trim())
+        .filter(|id| !id.is_empty())
+    {
+        if !webhook_ids.iter().any(|existing| existing == id) {
+            webhook_ids.push(id.to_string());
+        }
+    }
+    Ok(NamedPipelineInput {
+        name: name.to_string(),
+        template_id: non_empty(&input.template_id),
+        model_provider: non_empty(&input.model_provider),
+        model_name: non_empty(&input.model_name),
+        webhook_ids,
+        ..input.clone()
+    })
+}
+
+fn webhook_ids_json(ids: &[String]) -> Result<String, sqlx::Error> {
+    serde_json::to_string(ids)
+        .map_err(|e| sqlx::Error::Protocol(format!("Failed to serialize webhook ids: {}", e)))
+}
+
+fn output_format_text(format: Option<PipelineOutputFormat>) -> Option<String> {
+    format
+        .and_then(|format| serde_json::to_value(format).ok())
+        .and_then(|value| value.as_str().map(str::to_string))
+}
+
+impl PipelinesRepository {
+    /// Built-in pipelines first, then by name
+    pub async fn list(pool: &SqlitePool) -> Result<Vec<NamedPipeline>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, PipelineRow>(
+            "SELECT * FROM pipelines ORDER BY built_in DESC, name COLLATE NOCASE",
+        )
+        .fetch_all(pool)
+        .await?;
+        Ok(rows.into_iter().map(NamedPipeline::from).collect())
+    }
+
+    pub async fn get(pool: &SqlitePool, id: &str) -> Result<Option<NamedPipeline>, sqlx::Error> {
+        let row = sqlx::query_as::<_, PipelineRow>("SELECT * FROM pipelines WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await?;
+        Ok(row.map(NamedPipeline::from))
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        input: &NamedPipelineInput,
+    ) -> Result<NamedPipeline, sqlx::Error> {
+        let input = normalize(input)?;
+        let id = format!("pipeline-{}", Uuid::new_v4());
+        let now = Utc::now();
+        sqlx::query(
+            "INSERT INTO pipelines (id, name, template_id, model_provider, model_name, compact_transcript, provenance, citations, anonymize, refine, output_format, temperature, max_tokens, webhook_ids, built_in, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 0, ?, ?)",
+        )
+        .bind(&id)
+        .bind(&input.name)
+        .bind(&input.template_id)
+        .bind(&input.model_provider)
+        .bind(&input.model_name)
+        .bind(input.compact_transcript)
+        .bind(input.provenance)
+        .bind(input.citations)
+        .bind(input.anonymize)
+        .bind(input.refine)
+        .bind(output_format_text(input.output_format))
+        .bind(input.temperature)
+        .bind(input.max_tokens)
+        .bind(webhook_ids_json(&input.webhook_ids)?)
+        .bind(now)
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+        Ok(NamedPipeline {
+            id,
+            name: input.name,
+            template_id: input.template_id,
+            model_provider: input.model_provider,
+            model_name: input.model_name,
+            compact_transcript: input.compact_transcript,
+            provenance: input.provenance,
+            citations: input.citations,
+            anonymize: input.anonymize,
+            refine: input.refine,
+            output_format: input.output_format,
+            temperature: input.temperature,
+            max_tokens: input.max_tokens,
+            webhook_ids: input.webhook_ids,
+            built_in: false,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Replaces every editable field; returns None if the pipeline doesn't exist
+    pub async fn update(
+        pool: &SqlitePool,
+        id: &str,
+        input: &NamedPipelineInput,
+    ) -> Result<Option<NamedPipeline>, sqlx::Error> {
+        let input = normalize(input)?;
+        let result = sqlx::query(
+            "UPDATE pipelines SET name = ?, template_id = ?, model_provider = ?, model_name = ?, compact_transcript = ?, provenance = ?, citations = ?, anonymize = ?, refine = ?, output_format = ?, temperature = ?, max_tokens = ?, webhook_ids = ?, updated_at = ? WHERE id = ?",
+        )
+        .bind(&input.name)
+        .bind(&input.template_id)
+        .bind(&input.model_provider)
+        .bind(&input.model_name)
+        .bind(input.compact_transcript)
+        .bind(input.provenance)
+        .bind(input.citations)
+        .bind(input.anonymize)
+        .bind(input.refine)
+        .bind(output_format_text(input.output_format))
+        .bind(input.temperature)
+        .bind(input.max_tokens)
+        .bind(webhook_ids_json(&input.webhook_ids)?)
+        .bind(Utc::now())
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+        Self::get(pool, id).await
+    }
+
+    /// Checks an input the way `create` and `update` would
+    pub fn validate(input: &NamedPipelineInput) -> Result<(), sqlx::Error> {
+        normalize(input).map(|_| ())
+    }
+
+    /// Creates or replaces the pipeline `id` inside `transaction`, e.g. when
+    /// a settings bundle is imported; an existing pipeline keeps its
+    /// built-in flag and creation time
+    pub async fn upsert(
+        transaction: &mut SqliteConnection,
+        id: &str,
+        input: &NamedPipelineInput,
+    ) -> Result<(), sqlx::Error> {
+        let input = normalize(input)?;
+        let now = Utc::now();
+        sqlx::query(
+            r#"
+            INSERT INTO pipelines (id, name, template_id, model_provider, model_name, compact_transcript, provenance, citations, anonymize, refine, output_format, temperature, max_tokens, webhook_ids, built_in, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 0, ?, ?)
+            ON CONFLICT(id) DO UPDATE SET
+                name = excluded.name,
+                template_id = excluded.template_id,
+                model_provider = excluded.model_provider,
+                model_name = excluded.model_name,
+                compact_transcript = excluded.compact_transcript,
+                provenance = excluded.provenance,
+                citations = excluded.citations,
+                anonymize = excluded.anonymize,
+                refine = excluded.refine,
+                output_format = excluded.output_format,
+                temperature = excluded.temperature,
+                max_tokens = excluded.max_tokens,
+                webhook_ids = excluded.webhook_ids,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(id)
+        .bind(&input.name)
+        .bind(&input.template_id)
+        .bind(&input.model_provider)
+        .bind(&input.model_name)
+        .bind(input.compact_transcript)
+        .bind(input.provenance)
+        .bind(input.citations)
+        .bind(input.anonymize)
+        .bind(input.refine)
+        .bind(output_format_text(input.output_format))
+        .bind(input.temperature)
+        .bind(input.max_tokens)
+        .bind(webhook_ids_json(&input.webhook_ids)?)
+        .bind(now)
+        .bind(now)
+        .execute(&mut *transaction)
+        .await?;
+        Ok(())
+    }
+
+    /// Deletes a pipeline the user created; built-in ones are an error
+    pub async fn delete(pool: &SqlitePool, id: &str) -> Result<bool, sqlx::Error> {
+        if Self::get(pool, id)
+            .await?
+            .is_some_and(|pipeline| pipeline.built_in)
+        {
+            return Err(sqlx::Error::Protocol(format!(
+                "Built-in pipeline {} can't be deleted",
+                id
+            )));
+        }
+        let result = sqlx::query("DELETE FROM pipelines WHERE id = ?")
+            .bind(id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::test_pool;
+
+    #[tokio::test]
+    async fn test_pipelines_crud_keeps_built_ins() {
+        let pool = test_pool().await;
+
+        let built_in = PipelinesRepository::list(&pool).await.unwrap();
+        let ids: Vec<&str> = built_in.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, vec!["detailed", "quick"]);
+        let quick = &built_in[1];
+        assert!(quick.built_in);
+        assert_eq!(quick.output_format, Some(PipelineOutputFormat::Skim));
+        assert_eq!(quick.refine, Some(false));
+        assert_eq!(quick.model_provider, None);
+
+        let input = NamedPipelineInput {
+            name: " Board minutes ".to_string(),
+            template_id: Some("standard_meeting".to_string()),
+            model_provider: Some(" ".to_string()),
+            output_format: Some(PipelineOutputFormat::Structured),
+            webhook_ids: vec!["hook-1".to_string(), "hook-1".to_string(), String::new()],
+            ..Default::default()
+        };
+        let created = PipelinesRepository::create(&pool, &input).await.unwrap();
+        assert_eq!(created.name, "Board minutes");
+        assert_eq!(created.model_provider, None);
+        assert_eq!(created.webhook_ids, vec!["hook-1"]);
+        assert_eq!(
+            PipelinesRepository::get(&pool, &created.id).await.unwrap(),
+            Some(created.clone())
+        );
+
+        let changed = NamedPipelineInput {
+            refine: Some(true),
+            temperature: Some(0.2),
+            max_tokens: Some(2048),
+            ..input.clone()
+        };
+        let updated = PipelinesRepository::update(&pool, &created.id, &changed)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.refine, Some(true));
+        assert_eq!(
+            (updated.temperature, updated.max_tokens),
+            (Some(0.2), Some(2048))
+        );
+        for invalid in [
+            NamedPipelineInput {
+                temperature: Some(2.5),
+                ..input.clone()
+            },
+            NamedPipelineInput {
+                max_tokens: Some(0),
+                ..input.clone()
+            },
+        ] {
+            assert!(PipelinesRepository::update(&pool, &created.id, &invalid)
+                .await
+                .is_err());
+        }
+        assert!(PipelinesRepository::update(&pool, "missing", &changed)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(PipelinesRepository::create(
+            &pool,
+            &NamedPipelineInput {
+                name: "  ".to_string(),
+                ..Default::default()
+            }
+        )
+        .await
+        .is_err());
+
+        assert!(PipelinesRepository::delete(&pool, "quick").await.is_err());
+        assert!(PipelinesRepository::delete(&pool, &created.id)
+            .await
+            .unwrap());
+        assert!(!PipelinesRepository::delete(&pool, &created.id)
+            .await
+            .unwrap());
+        assert_eq!(PipelinesRepository::list(&pool).await.unwrap(), built_in);
+    }
+}
@@ -0,0 +1,396 @@
+use crate::database::models::StoredQuestion;
+use crate::summary::question_generator::Question;
+use crate::summary::question_suppression::QuestionStatus;
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+pub struct QuestionsRepository;
+
+impl QuestionsRepository {
+    /// Stores questions generated for a meeting, returning them as saved
+    pub async fn save_questions(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        questions: &[Question],
+        status: QuestionStatus,
+    ) -> Result<Vec<StoredQuestion>, sqlx::Error> {
+        let now = Utc::now();
+        let stored: Vec<StoredQuestion> = questions
+            .iter()
+            .map(|question| StoredQuestion {
+                id: Uuid::new_v4().to_string(),
+                meeting_id: meeting_id.to_string(),
+                text: question.text.clone(),
+                category: question.category.clone(),
+                context: question.context.clone(),
+                answered: false,
+                created_at: now,
+                status: status.as_str().to_string(),
+                delivery_id: None,
+                delivery_platform: None,
+                delivery_requested_at: None,
+            })
+            .collect();
+
+        let mut transaction = pool.begin().await?;
+        for question in &stored {
+            sqlx::query(
+                "INSERT INTO clarifying_questions (id, meeting_id, text, category, context, answered, created_at, status)
+                 VALUES (?, ?, ?, ?, ?, 0, ?, ?)",
+            )
+            .bind(&question.id)
+            .bind(&question.meeting_id)
+            .bind(&question.text)
+            .bind(&question.category)
+            .bind(&question.context)
+            .bind(question.created_at)
+            .bind(&question.status)
+            .execute(&mut *transaction)
+            .await?;
+        }
+        transaction.commit().await?;
+        Ok(stored)
+    }
+
+    /// Questions of a meeting, oldest first
+    pub async fn list_for_meeting(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Vec<StoredQuestion>, sqlx::Error> {
+        sqlx::query_as::<_, StoredQuestion>(
+            "SELECT * FROM clarifying_questions WHERE meeting_id = ? ORDER BY created_at, rowid",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Sets whether a question got answered; returns false if it doesn't exist
+    pub async fn set_answered(
+        pool: &SqlitePool,
+        question_id: &str,
+        answered: bool,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("UPDATE clarifying_questions SET answered = ? WHERE id = ?")
+            .bind(answered)
+            .bind(question_id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Sets the status of questions, e.g. once held back ones are released;
+    /// returns how many were updated
+    pub async fn set_status(
+        pool: &SqlitePool,
+        question_ids: &[String],
+        status: QuestionStatus,
+    ) -> Result<u64, sqlx::Error> {
+        let mut transaction = pool.begin().await?;
+        let mut updated = 0;
+        for question_id in question_ids {
+            let result = sqlx::query("UPDATE clarifying_questions SET status = ? WHERE id = ?")
+                .bind(status.as_str())
+                .bind(question_id)
+                .execute(&mut *transaction)
+                .await?;
+            updated += result.rows_affected();
+        }
+        transaction.commit().await?;
+        Ok(updated)
+    }
+
+    /// Gives each question a new delivery id and marks it as being sent to
+    /// the meeting chat; returns the questions found, in the order given
+    pub async fn start_delivery(
+        pool: &SqlitePool,
+        question_ids: &[String],
+        requested_at: DateTime<Utc>,
+    ) -> Result<Vec<StoredQuestion>, sqlx::Error> {
+        let mut transaction = pool.begin().await?;
+        let mut started = Vec::new();
+        for question_id in question_ids {
+            sqlx::query(
+                "UPDATE clarifying_questions
+                 SET status = ?, delivery_id = ?, delivery_platform = NULL, delivery_requested_at = ?
+                 WHERE id = ?",
+            )
+            .bind(QuestionStatus::Sending.as_str())
+            .bind(Uuid::new_v4().to_string())
+            .bind(requested_at)
+            .bind(question_id)
+            .execute(&mut *transaction)
+            .await?;
+            let question = sqlx::query_as::<_, StoredQuestion>(
+                "SELECT * FROM clarifying_questions WHERE id = ?",
+            )
+            .bind(question_id)
+            .fetch_optional(&mut *transaction)
+            .await?;
+            started.extend(question);
+        }
+        transaction.commit().await?;
+        Ok(started)
+    }
+
+    /// Records the extension's confirmation of a delivery as "sent" or
+    /// "failed"; returns false for an unknown delivery id
+    ///
+    /// A confirmation arriving after the delivery timed out still replaces
+    /// "unknown", as it is what actually happened.
+    pub async fn confirm_delivery(
+        pool: &SqlitePool,
+        delivery_id: &str,
+        platform: Option<&str>,
+        success: bool,
+    ) -> Result<bool, sqlx::Error> {
+        let status = if success {
+            QuestionStatus::Sent
+        } else {
+            QuestionStatus::Failed
+        };
+        let result = sqlx::query(
+            "UPDATE clarifying_questions
+             SET status = ?, delivery_platform = COALESCE(?, delivery_platform)
+             WHERE delivery_id = ?",
+        )
+        .bind(status.as_str())
+        .bind(platform)
+        .bind(delivery_id)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Marks deliveries requested before `older_than` and still not
+    /// confirmed as "unknown"; returns how many
+    pub async fn expire_deliveries(
+        pool: &SqlitePool,
+        older_than: DateTime<Utc>,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE clarifying_questions SET status = ?
+             WHERE status = ? AND julianday(delivery_requested_at) < julianday(?)",
+        )
+        .bind(QuestionStatus::Unknown.as_str())
+        .bind(QuestionStatus::Sending.as_str())
+        .bind(older_than)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::{seed_meetings, test_pool};
+
+    fn question(text: &str, category: &str) -> Question {
+        Question {
+            text: text.to_string(),
+            category: category.to_string(),
+            context: "Sam: we ship the Stripe fix".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_save_list_and_mark_answered() {
+        let pool = test_pool().await;
+        seed_meetings(&pool, 2, 1).await;
+
+        let saved = QuestionsRepository::save_questions(
+            &pool,
+            "meeting-0",
+            &[
+                question("Who owns the Stripe fix?", "owner"),
+                question("When is it due?", "deadline"),
+            ],
+            QuestionStatus::Shown,
+        )
+        .await
+        .unwrap();
+        QuestionsRepository::save_questions(
+            &pool,
+            "meeting-1",
+            &[question("Why?", "general")],
+            QuestionStatus::Suppressed,
+        )
+        .await
+        .unwrap();
+
+        let listed = QuestionsRepository::list_for_meeting(&pool, "meeting-0")
+            .await
+            .unwrap();
+        let ids = |questions: &[StoredQuestion]| {
+            questions.iter().map(|q| q.id.clone()).collect::<Vec<_>>()
+        };
+        assert_eq!(ids(&listed), ids(&saved));
+        assert_eq!(listed[0].text, "Who owns the Stripe fix?");
+        assert_eq!(listed[1].category, "deadline");
+        assert!(listed.iter().all(|question| !question.answered));
+
+        assert!(QuestionsRepository::set_answered(&pool, &saved[1].id, true)
+            .await
+            .unwrap());
+        assert!(!QuestionsRepository::set_answered(&pool, "missing", true)
+            .await
+            .unwrap());
+        let listed = QuestionsRepository::list_for_meeting(&pool, "meeting-0")
+            .await
+            .unwrap();
+        assert!(!listed[0].answered);
+        assert!(listed[1].answered);
+
+        // Unknown meetings are rejected by the foreign key
+        assert!(QuestionsRepository::save_questions(
+            &pool,
+            "missing",
+            &[question("Why?", "general")],
+            QuestionStatus::Shown,
+        )
+        .await
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_set_status() {
+        let pool = test_pool().await;
+        seed_meetings(&pool, 1, 1).await;
+        let held = QuestionsRepository::save_questions(
+            &pool,
+            "meeting-0",
+            &[question("Who owns it?", "owner"), question("Who owns it", "owner")],
+            QuestionStatus::Suppressed,
+        )
+        .await
+        .unwrap();
+        assert_eq!(held[0].status, "suppressed");
+
+        let updated = QuestionsRepository::set_status(
+            &pool,
+            &[held[0].id.clone(), "missing".to_string()],
+            QuestionStatus::Released,
+        )
+        .await
+        .unwrap();
+        assert_eq!(updated, 1);
+        QuestionsRepository::set_status(&pool, &[held[1].id.clone()], QuestionStatus::Duplicate)
+            .await
+            .unwrap();
+
+        let statuses: Vec<String> = QuestionsRepository::list_for_meeting(&pool, "meeting-0")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|question| question.status)
+            .collect();
+        assert_eq!(statuses, vec!["released", "duplicate"]);
+    }
+
+    #[tokio::test]
+    async fn test_delivery_confirmations_failures_and_timeouts() {
+        let pool = test_pool().await;
+        seed_meetings(&pool, 1, 1).await;
+        let saved = QuestionsRepository::save_questions(
+            &pool,
+            "meeting-0",
+            &[
+                question("Who owns the Stripe fix?", "owner"),
+                question("When is it due?", "deadline"),
+                question("Is QA involved?", "general"),
+            ],
+            QuestionStatus::Shown,
+        )
+        .await
+        .unwrap();
+        let ids: Vec<String> = saved.iter().map(|q| q.id.clone()).collect();
+        let requested_at = Utc::now() - chrono::Duration::seconds(120);
+
+        let started = QuestionsRepository::start_delivery(
+            &pool,
+            &[
+                ids[0].clone(),
+                "missing".to_string(),
+                ids[1].clone(),
+                ids[2].clone(),
+            ],
+            requested_at,
+        )
+        .await
+        .unwrap();
+        assert_eq!(started.len(), 3);
+        assert!(started.iter().all(|q| q.status == "sending"));
+        let delivery_ids: Vec<String> = started
+            .iter()
+            .map(|q| q.delivery_id.clone().unwrap())
+            .collect();
+        assert_ne!(delivery_ids[0], delivery_ids[1]);
+
+        assert!(
+            QuestionsRepository::confirm_delivery(&pool, &delivery_ids[0], Some("meet"), true)
+                .await
+                .unwrap()
+        );
+        assert!(QuestionsRepository::confirm_delivery(
+            &pool,
+            &delivery_ids[1],
+            Some("zoom"),
+            false
+        )
+        .await
+        .unwrap());
+        assert!(
+            !QuestionsRepository::confirm_delivery(&pool, "missing", Some("meet"), true)
+                .await
+                .unwrap()
+        );
+
+        // Only the unconfirmed delivery times out, and only once it is old enough
+        let cutoff = Utc::now() - chrono::Duration::seconds(60);
+        assert_eq!(
+            QuestionsRepository::expire_deliveries(&pool, requested_at)
+                .await
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            QuestionsRepository::expire_deliveries(&pool, cutoff)
+                .await
+                .unwrap(),
+            1
+        );
+
+        let listed = QuestionsRepository::list_for_meeting(&pool, "meeting-0")
+            .await
+            .unwrap();
+        let outcome: Vec<(&str, Option<&str>)> = listed
+            .iter()
+            .map(|q| (q.status.as_str(), q.delivery_platform.as_deref()))
+            .collect();
+        assert_eq!(
+            outcome,
+            vec![
+                ("sent", Some("meet")),
+                ("failed", Some("zoom")),
+                ("unknown", None)
+            ]
+        );
+
+        // A late confirmation still records what happened
+        assert!(QuestionsRepository::confirm_delivery(
+            &pool,
+            &delivery_ids[2],
+            Some("teams"),
+            true
+        )
+        .await
+        .unwrap());
+        let listed = QuestionsRepository::list_for_meeting(&pool, "meeting-0")
+            .await
+            .unwrap();
+        assert_eq!(listed[2].status, "sent");
+        assert_eq!(listed[2].delivery_platform.as_deref(), Some("teams"));
+    }
+}
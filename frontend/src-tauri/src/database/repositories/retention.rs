@@ -0,0 +1,91 @@
+use crate::database::models::RetentionCandidate;
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+pub struct RetentionRepository;
+
+impl RetentionRepository {
+    /// Every meeting with what is left of it, oldest first
+    pub async fn candidates(pool: &SqlitePool) -> Result<Vec<RetentionCandidate>, sqlx::Error> {
+        sqlx::query_as::<_, RetentionCandidate>(
+            "SELECT
+                m.id,
+                m.title,
+                m.created_at,
+                m.folder_path,
+                m.audio_deleted_at IS NOT NULL AS audio_deleted,
+                m.transcript_deleted_at IS NOT NULL AS transcript_deleted,
+                EXISTS (SELECT 1 FROM transcripts t WHERE t.meeting_id = m.id) AS has_segments,
+                EXISTS (SELECT 1 FROM summary_processes sp WHERE sp.meeting_id = m.id) AS has_summary
+             FROM meetings m
+             ORDER BY m.created_at, m.rowid",
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Records that the meeting's recording is gone, which disables playback
+    pub async fn mark_audio_deleted(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE meetings SET audio_deleted_at = ? WHERE id = ?")
+            .bind(at)
+            .bind(meeting_id)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
+    /// When the retention policy deleted the meeting's recording, if it did
+    pub async fn audio_deleted_at(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Option<DateTime<Utc>>, sqlx::Error> {
+        let deleted: Option<Option<DateTime<Utc>>> =
+            sqlx::query_scalar("SELECT audio_deleted_at FROM meetings WHERE id = ?")
+                .bind(meeting_id)
+                .fetch_optional(pool)
+                .await?;
+        Ok(deleted.flatten())
+    }
+
+    /// Deletes the meeting's transcript segments and the text stored for
+    /// summarization
+    pub async fn delete_transcript(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        let mut transaction = pool.begin().await?;
+        sqlx::query("DELETE FROM transcripts WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+        sqlx::query("DELETE FROM transcript_chunks WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+        sqlx::query("UPDATE meetings SET transcript_deleted_at = ? WHERE id = ?")
+            .bind(at)
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+        transaction.commit().await
+    }
+
+    /// Deletes the meeting's summary and its earlier versions
+    pub async fn delete_summary(pool: &SqlitePool, meeting_id: &str) -> Result<(), sqlx::Error> {
+        let mut transaction = pool.begin().await?;
+        sqlx::query("DELETE FROM summary_versions WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+        sqlx::query("DELETE FROM summary_processes WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+        transaction.commit().await
+    }
+}
@@ -1,4 +1,4 @@
-use crate::database::models::{Setting, TranscriptSetting};
+use crate::database::models::{Setting, SummaryFallback, TranscriptSetting};
 use sqlx::SqlitePool;
 
 #[derive(serde::Deserialize, Debug)]
@@ -121,8 +121,45 @@ impl SettingsRepository {
             "SELECT {} FROM settings WHERE id = '1' LIMIT 1",
             api_key_column
         );
-        let api_key = sqlx::query_scalar(&query).fetch_optional(pool).await?;
-        Ok(api_key)
+        // The column is nullable, so a row without a key for this provider is None
+        let api_key: Option<Option<String>> =
+            sqlx::query_scalar(&query).fetch_optional(pool).await?;
+        Ok(api_key.flatten())
+    }
+
+    /// Fallback summary providers in the order they are tried; empty if none
+    pub async fn get_summary_fallbacks(
+        pool: &SqlitePool,
+    ) -> std::result::Result<Vec<SummaryFallback>, sqlx::Error> {
+        let stored: Option<Option<String>> =
+            sqlx::query_scalar("SELECT summaryFallbacks FROM settings WHERE id = '1' LIMIT 1")
+                .fetch_optional(pool)
+                .await?;
+        Ok(stored
+            .flatten()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default())
+    }
+
+    pub async fn save_summary_fallbacks(
+        pool: &SqlitePool,
+        fallbacks: &[SummaryFallback],
+    ) -> std::result::Result<(), sqlx::Error> {
+        let json = serde_json::to_string(fallbacks)
+            .map_err(|e| sqlx::Error::Protocol(format!("Invalid fallbacks: {}", e)))?;
+        sqlx::query(
+            r#"
+            INSERT INTO settings (id, provider, model, whisperModel, summaryFallbacks)
+            VALUES ('1', 'openai', 'gpt-4o-2024-11-20', 'large-v3', $1)
+            ON CONFLICT(id) DO UPDATE SET
+                summaryFallbacks = excluded.summaryFallbacks
+            "#,
+        )
+        .bind(json)
+        .execute(pool)
+        .await?;
+
+        Ok(())
     }
 
     pub async fn get_transcript_config(
@@ -215,8 +252,10 @@ impl SettingsRepository {
             "SELECT {} FROM transcript_settings WHERE id = '1' LIMIT 1",
             api_key_column
         );
-        let api_key = sqlx::query_scalar(&query).fetch_optional(pool).await?;
-        Ok(api_key)
+        // The column is nullable, so a row without a key for this provider is None
+        let api_key: Option<Option<String>> =
+            sqlx::query_scalar(&query).fetch_optional(pool).await?;
+        Ok(api_key.flatten())
     }
 
     pub async fn delete_api_key(
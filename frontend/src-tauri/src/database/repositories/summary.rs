@@ -1,11 +1,37 @@
-use crate::database::models::SummaryProcess;
-use chrono::Utc;
+use crate::database::models::{
+    SummaryFailureKind, SummaryModelMetrics, SummaryProcess, SummaryRunTotals,
+    SummarySearchResult, SummaryVersion,
+};
+use crate::summary::metrics::{warning_count, SummaryErrorKind};
+use chrono::{DateTime, Utc};
 use serde_json::Value;
 use sqlx::SqlitePool;
 use tracing::{error, info as log_info};
+use uuid::Uuid;
 
 pub struct SummaryProcessesRepository;
 
+/// Most meetings returned by `search_summaries`
+const MAX_SEARCH_RESULTS: i64 = 50;
+
+/// Tokens of the summary shown around the matches in a search snippet
+const SNIPPET_TOKENS: i64 = 24;
+
+/// Turns user input into an FTS5 query matching summaries that contain
+/// every word
+///
+/// Each word is quoted so operators in the input ("AND", "NEAR", quotes)
+/// are searched for rather than parsed; words without letters or digits are
+/// dropped since they can't match anything.
+fn fts_query(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .filter(|word| word.chars().any(char::is_alphanumeric))
+        .map(|word| format!("\"{}\"", word.replace('"', "\"\"")))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
 impl SummaryProcessesRepository {
     /// Retrieves the current summary process state for a given meeting ID.
     pub async fn get_summary_data(
@@ -70,6 +96,36 @@ impl SummaryProcessesRepository {
         Ok(true)
     }
 
+    /// Full-text search over the markdown of stored summaries
+    ///
+    /// Returns the best matching meetings first, with a highlighted snippet.
+    /// The index is maintained by triggers on `summary_processes`, so generated
+    /// and edited summaries are both searchable.
+    pub async fn search_summaries(
+        pool: &SqlitePool,
+        query: &str,
+    ) -> Result<Vec<SummarySearchResult>, sqlx::Error> {
+        let Some(fts_query) = fts_query(query) else {
+            return Ok(Vec::new());
+        };
+        sqlx::query_as::<_, SummarySearchResult>(
+            r#"
+            SELECT m.id, m.title,
+                   snippet(summary_search, 1, '<mark>', '</mark>', '…', ?) AS snippet
+            FROM summary_search
+            JOIN meetings m ON m.id = summary_search.meeting_id
+            WHERE summary_search MATCH ?
+            ORDER BY bm25(summary_search)
+            LIMIT ?
+            "#,
+        )
+        .bind(SNIPPET_TOKENS)
+        .bind(fts_query)
+        .bind(MAX_SEARCH_RESULTS)
+        .fetch_all(pool)
+        .await
+    }
+
     pub async fn get_summary_data_for_meeting(
         pool: &SqlitePool,
         meeting_id: &str,
@@ -131,7 +187,9 @@ impl SummaryProcessesRepository {
                 updated_at = excluded.updated_at,
                 start_time = excluded.start_time,
                 result = NULL,
-                error = NULL
+                error = NULL,
+                error_kind = NULL,
+                warning_count = NULL
             "#
         )
         .bind(meeting_id)
@@ -157,7 +215,8 @@ impl SummaryProcessesRepository {
         sqlx::query(
             r#"
             UPDATE summary_processes
-            SET status = 'completed', result = ?, updated_at = ?, end_time = ?, chunk_count = ?, processing_time = ?, error = NULL
+            SET status = 'completed', result = ?, updated_at = ?, end_time = ?, chunk_count = ?, processing_time = ?, error = NULL,
+                error_kind = NULL, warning_count = ?
             WHERE meeting_id = ?
             "#
         )
@@ -166,6 +225,7 @@ impl SummaryProcessesRepository {
         .bind(now)
         .bind(chunk_count)
         .bind(processing_time)
+        .bind(warning_count(&result))
         .bind(meeting_id)
         .execute(pool)
         .await?;
@@ -200,11 +260,12 @@ impl SummaryProcessesRepository {
         sqlx::query(
             r#"
             UPDATE summary_processes
-            SET status = 'failed', error = ?, updated_at = ?, end_time = ?
+            SET status = 'failed', error = ?, error_kind = ?, updated_at = ?, end_time = ?
             WHERE meeting_id = ?
             "#,
         )
         .bind(error)
+        .bind(SummaryErrorKind::classify(error).as_str())
         .bind(now)
         .bind(now)
         .bind(meeting_id)
@@ -212,4 +273,330 @@ impl SummaryProcessesRepository {
         .await?;
         Ok(())
     }
+
+    /// Stores one template's result of a multi-template run, or its error
+    /// when `result` is None; returns the version id
+    pub async fn save_summary_version(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        template_id: &str,
+        result: Option<&Value>,
+        error: Option<&str>,
+    ) -> Result<String, sqlx::Error> {
+        let id = Uuid::new_v4().to_string();
+        let result_str = result
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| sqlx::Error::Protocol(format!("Failed to serialize result: {}", e)))?;
+        let status = if result_str.is_some() { "completed" } else { "failed" };
+
+        sqlx::query(
+            "INSERT INTO summary_versions (id, meeting_id, template_id, status, result, error, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&id)
+        .bind(meeting_id)
+        .bind(template_id)
+        .bind(status)
+        .bind(result_str)
+        .bind(error)
+        .bind(Utc::now())
+        .execute(pool)
+        .await?;
+        Ok(id)
+    }
+
+    /// Counts and averages of the runs that finished since `since` (all of
+    /// them when None)
+    pub async fn run_totals(
+        pool: &SqlitePool,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<SummaryRunTotals, sqlx::Error> {
+        sqlx::query_as::<_, SummaryRunTotals>(
+            r#"
+            SELECT COUNT(*) AS runs,
+                   COALESCE(SUM(status = 'completed'), 0) AS completed,
+                   COALESCE(SUM(status = 'failed'), 0) AS failed,
+                   COALESCE(1.0 * SUM(status = 'failed') / COUNT(*), 0.0) AS failure_rate,
+                   AVG(CASE WHEN status = 'completed' THEN chunk_count END) AS avg_chunks,
+                   AVG(CASE WHEN status = 'completed' THEN processing_time END) AS avg_processing_time,
+                   AVG(CASE WHEN status = 'completed' THEN warning_count END) AS avg_warning_count
+            FROM summary_processes
+            WHERE status IN ('completed', 'failed') AND (? IS NULL OR end_time >= ?)
+            "#,
+        )
+        .bind(since)
+        .bind(since)
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Failed runs since `since` by error kind, most common first; `runs` is
+    /// the number of runs the rates are a share of
+    pub async fn failure_kinds(
+        pool: &SqlitePool,
+        since: Option<DateTime<Utc>>,
+        runs: i64,
+    ) -> Result<Vec<SummaryFailureKind>, sqlx::Error> {
+        sqlx::query_as::<_, SummaryFailureKind>(
+            r#"
+            SELECT COALESCE(error_kind, 'other') AS kind,
+                   COUNT(*) AS count,
+                   1.0 * COUNT(*) / ? AS rate
+            FROM summary_processes
+            WHERE status = 'failed' AND (? IS NULL OR end_time >= ?)
+            GROUP BY kind
+            ORDER BY count DESC, kind
+            "#,
+        )
+        .bind(runs)
+        .bind(since)
+        .bind(since)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Completed runs since `since` per provider/model, slowest per chunk
+    /// first
+    ///
+    /// The provider and model are read from the stored result, so failed
+    /// runs and summaries saved before it was recorded are left out.
+    pub async fn model_metrics(
+        pool: &SqlitePool,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<SummaryModelMetrics>, sqlx::Error> {
+        sqlx::query_as::<_, SummaryModelMetrics>(
+            r#"
+            WITH models AS (
+                SELECT json_extract(result, '$.generated_by.provider') AS provider,
+                       json_extract(result, '$.generated_by.model') AS model,
+                       COUNT(*) AS runs,
+                       AVG(chunk_count) AS avg_chunks,
+                       AVG(processing_time) AS avg_processing_time,
+                       AVG(processing_time / MAX(chunk_count, 1)) AS avg_seconds_per_chunk,
+                       AVG(warning_count) AS avg_warning_count
+                FROM summary_processes
+                WHERE status = 'completed' AND json_valid(result)
+                  AND (? IS NULL OR end_time >= ?)
+                GROUP BY provider, model
+                HAVING provider IS NOT NULL AND model IS NOT NULL
+            )
+            SELECT *,
+                   avg_seconds_per_chunk / NULLIF(MIN(avg_seconds_per_chunk) OVER (), 0) AS relative_time
+            FROM models
+            ORDER BY avg_seconds_per_chunk DESC, provider, model
+            "#,
+        )
+        .bind(since)
+        .bind(since)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Deletes process rows last updated before `older_than` whose status
+    /// isn't one of `keep_statuses`; returns how many were deleted
+    ///
+    /// A meeting has a single row, its latest run. One that still holds a
+    /// summary is always kept, so this only clears runs that failed, were
+    /// cancelled or never finished.
+    pub async fn prune_old(
+        pool: &SqlitePool,
+        older_than: DateTime<Utc>,
+        keep_statuses: &[&str],
+    ) -> Result<u64, sqlx::Error> {
+        let status_filter = if keep_statuses.is_empty() {
+            String::new()
+        } else {
+            format!(
+                " AND status NOT IN ({})",
+                vec!["?"; keep_statuses.len()].join(", ")
+            )
+        };
+        let sql = format!(
+            "DELETE FROM summary_processes
+             WHERE datetime(updated_at) < datetime(?) AND result IS NULL{}",
+            status_filter
+        );
+        let mut query = sqlx::query(&sql).bind(older_than);
+        for status in keep_statuses {
+            query = query.bind(*status);
+        }
+        Ok(query.execute(pool).await?.rows_affected())
+    }
+
+    /// Summary versions of a meeting, newest first
+    pub async fn list_summary_versions(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Vec<SummaryVersion>, sqlx::Error> {
+        sqlx::query_as::<_, SummaryVersion>(
+            "SELECT * FROM summary_versions WHERE meeting_id = ? ORDER BY created_at DESC, rowid DESC",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::repositories::meeting::MeetingsRepository;
+    use crate::database::repositories::transcript::TranscriptsRepository;
+    use crate::database::test_utils::test_pool;
+
+    async fn meeting_with_summary(pool: &SqlitePool, title: &str, markdown: &str) -> String {
+        let meeting_id = TranscriptsRepository::save_transcript(pool, title, &[], None)
+            .await
+            .unwrap();
+        SummaryProcessesRepository::create_or_reset_process(pool, &meeting_id)
+            .await
+            .unwrap();
+        SummaryProcessesRepository::update_process_completed(
+            pool,
+            &meeting_id,
+            serde_json::json!({ "markdown": markdown }),
+            1,
+            0.0,
+        )
+        .await
+        .unwrap();
+        meeting_id
+    }
+
+    #[tokio::test]
+    async fn test_prune_old_removes_only_old_failed_runs() {
+        let pool = test_pool().await;
+        let now = Utc::now();
+        let old = now - chrono::Duration::days(60);
+        let cutoff = now - chrono::Duration::days(30);
+        let completed = meeting_with_summary(&pool, "Old summary", "## Summary\n\nDone.").await;
+        let mut meetings = Vec::new();
+        for title in ["Old failure", "Old cancel", "Recent failure"] {
+            let meeting_id = TranscriptsRepository::save_transcript(&pool, title, &[], None)
+                .await
+                .unwrap();
+            SummaryProcessesRepository::create_or_reset_process(&pool, &meeting_id)
+                .await
+                .unwrap();
+            SummaryProcessesRepository::update_process_failed(&pool, &meeting_id, "LLM timeout")
+                .await
+                .unwrap();
+            meetings.push(meeting_id);
+        }
+        sqlx::query("UPDATE summary_processes SET updated_at = ? WHERE meeting_id IN (?, ?, ?)")
+            .bind(old)
+            .bind(&completed)
+            .bind(&meetings[0])
+            .bind(&meetings[1])
+            .execute(&pool)
+            .await
+            .unwrap();
+        // Written before timestamps were stored as RFC3339
+        sqlx::query(
+            "UPDATE summary_processes SET updated_at = '2020-01-01 10:00:00' WHERE meeting_id = ?",
+        )
+        .bind(&meetings[1])
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let pruned = SummaryProcessesRepository::prune_old(&pool, cutoff, &["completed"])
+            .await
+            .unwrap();
+        assert_eq!(pruned, 2);
+        let kept: Vec<String> =
+            sqlx::query_scalar("SELECT meeting_id FROM summary_processes ORDER BY meeting_id")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        let mut expected = vec![completed.clone(), meetings[2].clone()];
+        expected.sort();
+        assert_eq!(kept, expected);
+
+        // A row holding a summary survives even without keep statuses
+        assert_eq!(
+            SummaryProcessesRepository::prune_old(&pool, cutoff, &[])
+                .await
+                .unwrap(),
+            0
+        );
+        assert!(
+            SummaryProcessesRepository::get_summary_data(&pool, &completed)
+                .await
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_summaries_finds_decision() {
+        let pool = test_pool().await;
+        let standup = meeting_with_summary(
+            &pool,
+            "Standup",
+            "## Summary\n\nSam is still on the Stripe webhook retries.",
+        )
+        .await;
+        let planning = meeting_with_summary(
+            &pool,
+            "Q3 Planning",
+            "## Summary\n\nRoadmap review.\n\n## Key Decisions\n\n* We decided to drop Safari 15 support in the next release",
+        )
+        .await;
+
+        let results = SummaryProcessesRepository::search_summaries(&pool, "decided drop Safari")
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, planning);
+        assert_eq!(results[0].title, "Q3 Planning");
+        assert!(
+            results[0].snippet.contains("<mark>decided</mark> to"),
+            "{}",
+            results[0].snippet
+        );
+
+        // Stemming matches other forms; operators in the input are plain words
+        let results = SummaryProcessesRepository::search_summaries(&pool, "retry")
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, standup);
+        assert!(SummaryProcessesRepository::search_summaries(&pool, "\"Safari AND -")
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(SummaryProcessesRepository::search_summaries(&pool, "  ")
+            .await
+            .unwrap()
+            .is_empty());
+
+        // Edits replace the indexed text
+        SummaryProcessesRepository::update_meeting_summary(
+            &pool,
+            &planning,
+            &serde_json::json!({ "markdown": "## Summary\n\nSafari support stays for now." }),
+        )
+        .await
+        .unwrap();
+        assert!(SummaryProcessesRepository::search_summaries(&pool, "decided")
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            SummaryProcessesRepository::search_summaries(&pool, "safari")
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        // Deleting the meeting drops its summary from the index
+        assert!(MeetingsRepository::delete_meeting(&pool, &planning).await.unwrap());
+        assert!(SummaryProcessesRepository::search_summaries(&pool, "safari")
+            .await
+            .unwrap()
+            .is_empty());
+    }
 }
@@ -0,0 +1,927 @@
+//! Trait abstractions over the repositories
+//!
+//! The SQLx repositories are structs of associated functions bound to a
+//! `SqlitePool`. These traits let services depend on the behaviour instead,
+//! so they can run against `SqlxRepositories` in the app and against the
+//! in-memory fakes in tests.
+
+use crate::api::{TranscriptSearchResult, TranscriptSegment};
+use crate::database::models::{
+    ChunkBoundary, MeetingContext, MeetingModel, MeetingParticipant, MeetingPreferences,
+    ParticipantMap, Setting, SummaryFallback, SummaryProcess, SummaryVersion, TranscriptChunk,
+};
+use crate::database::repositories::{
+    live_summary::LiveSummariesRepository, meeting::MeetingsRepository,
+    setting::SettingsRepository, summary::SummaryProcessesRepository,
+    transcript::TranscriptsRepository, transcript_chunk::TranscriptChunksRepository,
+};
+use async_trait::async_trait;
+use serde_json::Value;
+use sqlx::SqlitePool;
+use std::sync::Arc;
+
+#[async_trait]
+pub trait MeetingsRepo: Send + Sync {
+    async fn get_meetings(&self) -> Result<Vec<MeetingModel>, sqlx::Error>;
+
+    async fn update_meeting_title(
+        &self,
+        meeting_id: &str,
+        new_title: &str,
+    ) -> Result<bool, sqlx::Error>;
+
+    /// Deletes a meeting and everything that belongs to it
+    async fn delete_meeting(&self, meeting_id: &str) -> Result<bool, sqlx::Error>;
+
+    /// Stored template/model defaults; None if the meeting doesn't exist
+    async fn get_meeting_preferences(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Option<MeetingPreferences>, sqlx::Error>;
+
+    async fn set_meeting_preferences(
+        &self,
+        meeting_id: &str,
+        preferences: &MeetingPreferences,
+    ) -> Result<bool, sqlx::Error>;
+
+    /// Name -> pseudonym map for anonymized summaries; None if the meeting
+    /// doesn't exist
+    async fn get_participant_map(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Option<ParticipantMap>, sqlx::Error>;
+
+    async fn set_participant_map(
+        &self,
+        meeting_id: &str,
+        map: &ParticipantMap,
+    ) -> Result<bool, sqlx::Error>;
+
+    /// Attendees found in the transcript; None if the meeting doesn't exist
+    async fn get_participants(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Option<Vec<MeetingParticipant>>, sqlx::Error>;
+
+    /// Replaces the attendees; false if the meeting doesn't exist
+    async fn set_participants(
+        &self,
+        meeting_id: &str,
+        participants: &[MeetingParticipant],
+    ) -> Result<bool, sqlx::Error>;
+
+    /// Background notes for the meeting's prompts; None if there are none or
+    /// the meeting doesn't exist
+    async fn get_meeting_context(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Option<MeetingContext>, sqlx::Error>;
+
+    /// None clears the notes; false if the meeting doesn't exist
+    async fn set_meeting_context(
+        &self,
+        meeting_id: &str,
+        context: Option<&MeetingContext>,
+    ) -> Result<bool, sqlx::Error>;
+}
+
+#[async_trait]
+pub trait TranscriptsRepo: Send + Sync {
+    /// Creates a meeting with its transcript segments and returns the meeting id
+    async fn save_transcript(
+        &self,
+        meeting_title: &str,
+        transcripts: &[TranscriptSegment],
+        folder_path: Option<String>,
+    ) -> Result<String, sqlx::Error>;
+
+    async fn search_transcripts(
+        &self,
+        query: &str,
+    ) -> Result<Vec<TranscriptSearchResult>, sqlx::Error>;
+
+    /// Segments of a meeting in recording order
+    async fn get_transcript_segments(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Vec<TranscriptSegment>, sqlx::Error>;
+
+    /// Stores the text sent for summarization, replacing any earlier run's
+    async fn save_transcript_data(
+        &self,
+        meeting_id: &str,
+        text: &str,
+        model: &str,
+        model_name: &str,
+        chunk_size: i32,
+        overlap: i32,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn get_transcript_data(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Option<TranscriptChunk>, sqlx::Error>;
+
+    /// Records the text a summary run chunked and where it split it; false
+    /// if no transcript data was saved for the meeting
+    async fn save_chunk_layout(
+        &self,
+        meeting_id: &str,
+        text: &str,
+        chunk_size: usize,
+        overlap: usize,
+        boundaries: &[ChunkBoundary],
+    ) -> Result<bool, sqlx::Error>;
+}
+
+#[async_trait]
+pub trait SettingsRepo: Send + Sync {
+    async fn get_model_config(&self) -> Result<Option<Setting>, sqlx::Error>;
+
+    async fn save_model_config(
+        &self,
+        provider: &str,
+        model: &str,
+        whisper_model: &str,
+        ollama_endpoint: Option<&str>,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn get_api_key(&self, provider: &str) -> Result<Option<String>, sqlx::Error>;
+
+    async fn save_api_key(&self, provider: &str, api_key: &str) -> Result<(), sqlx::Error>;
+
+    async fn get_summary_fallbacks(&self) -> Result<Vec<SummaryFallback>, sqlx::Error>;
+
+    async fn save_summary_fallbacks(
+        &self,
+        fallbacks: &[SummaryFallback],
+    ) -> Result<(), sqlx::Error>;
+}
+
+#[async_trait]
+pub trait SummaryProcessesRepo: Send + Sync {
+    async fn get_summary_data(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Option<SummaryProcess>, sqlx::Error>;
+
+    /// Creates a PENDING process, cancelling any process still running
+    async fn create_or_reset_process(&self, meeting_id: &str) -> Result<(), sqlx::Error>;
+
+    async fn update_process_processing(&self, meeting_id: &str) -> Result<(), sqlx::Error>;
+
+    async fn update_process_completed(
+        &self,
+        meeting_id: &str,
+        result: Value,
+        chunk_count: i64,
+        processing_time: f64,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn update_process_failed(&self, meeting_id: &str, error: &str)
+        -> Result<(), sqlx::Error>;
+
+    /// Replaces the stored summary result; returns false if the meeting doesn't exist
+    async fn update_meeting_summary(
+        &self,
+        meeting_id: &str,
+        summary: &Value,
+    ) -> Result<bool, sqlx::Error>;
+
+    /// Drops the live drafts of a meeting once its final summary is stored
+    async fn delete_live_summaries(&self, meeting_id: &str) -> Result<u64, sqlx::Error>;
+
+    /// Stores one template's result of a multi-template run, or its error
+    /// when `result` is None; returns the version id
+    async fn save_summary_version(
+        &self,
+        meeting_id: &str,
+        template_id: &str,
+        result: Option<&Value>,
+        error: Option<&str>,
+    ) -> Result<String, sqlx::Error>;
+
+    /// Summary versions of a meeting, newest first
+    async fn list_summary_versions(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Vec<SummaryVersion>, sqlx::Error>;
+}
+
+/// The set of repositories shared through `AppState`
+#[derive(Clone)]
+pub struct Repositories {
+    pub meetings: Arc<dyn MeetingsRepo>,
+    pub transcripts: Arc<dyn TranscriptsRepo>,
+    pub settings: Arc<dyn SettingsRepo>,
+    pub summaries: Arc<dyn SummaryProcessesRepo>,
+}
+
+impl Repositories {
+    /// Repositories backed by the SQLite pool
+    pub fn sqlx(pool: SqlitePool) -> Self {
+        let repo = Arc::new(SqlxRepositories { pool });
+        Self {
+            meetings: repo.clone(),
+            transcripts: repo.clone(),
+            settings: repo.clone(),
+            summaries: repo,
+        }
+    }
+
+    /// Repositories backed by a shared in-memory store
+    #[cfg(test)]
+    pub fn in_memory() -> Self {
+        let repo = Arc::new(super::in_memory::InMemoryRepositories::default());
+        Self {
+            meetings: repo.clone(),
+            transcripts: repo.clone(),
+            settings: repo.clone(),
+            summaries: repo,
+        }
+    }
+}
+
+/// Trait implementations delegating to the existing SQLx repositories
+pub struct SqlxRepositories {
+    pool: SqlitePool,
+}
+
+#[async_trait]
+impl MeetingsRepo for SqlxRepositories {
+    async fn get_meetings(&self) -> Result<Vec<MeetingModel>, sqlx::Error> {
+        MeetingsRepository::get_meetings(&self.pool).await
+    }
+
+    async fn update_meeting_title(
+        &self,
+        meeting_id: &str,
+        new_title: &str,
+    ) -> Result<bool, sqlx::Error> {
+        MeetingsRepository::update_meeting_title(&self.pool, meeting_id, new_title).await
+    }
+
+    async fn delete_meeting(&self, meeting_id: &str) -> Result<bool, sqlx::Error> {
+        MeetingsRepository::delete_meeting(&self.pool, meeting_id).await
+    }
+
+    async fn get_meeting_preferences(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Option<MeetingPreferences>, sqlx::Error> {
+        MeetingsRepository::get_meeting_preferences(&self.pool, meeting_id).await
+    }
+
+    async fn set_meeting_preferences(
+        &self,
+        meeting_id: &str,
+        preferences: &MeetingPreferences,
+    ) -> Result<bool, sqlx::Error> {
+        MeetingsRepository::set_meeting_preferences(&self.pool, meeting_id, preferences).await
+    }
+
+    async fn get_participant_map(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Option<ParticipantMap>, sqlx::Error> {
+        MeetingsRepository::get_participant_map(&self.pool, meeting_id).await
+    }
+
+    async fn set_participant_map(
+        &self,
+        meeting_id: &str,
+        map: &ParticipantMap,
+    ) -> Result<bool, sqlx::Error> {
+        MeetingsRepository::set_participant_map(&self.pool, meeting_id, map).await
+    }
+
+    async fn get_participants(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Option<Vec<MeetingParticipant>>, sqlx::Error> {
+        MeetingsRepository::get_participants(&self.pool, meeting_id).await
+    }
+
+    async fn set_participants(
+        &self,
+        meeting_id: &str,
+        participants: &[MeetingParticipant],
+    ) -> Result<bool, sqlx::Error> {
+        MeetingsRepository::set_participants(&self.pool, meeting_id, participants).await
+    }
+
+    async fn get_meeting_context(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Option<MeetingContext>, sqlx::Error> {
+        MeetingsRepository::get_meeting_context(&self.pool, meeting_id).await
+    }
+
+    async fn set_meeting_context(
+        &self,
+        meeting_id: &str,
+        context: Option<&MeetingContext>,
+    ) -> Result<bool, sqlx::Error> {
+        MeetingsRepository::set_meeting_context(&self.pool, meeting_id, context).await
+    }
+}
+
+#[async_trait]
+impl TranscriptsRepo for SqlxRepositories {
+    async fn save_transcript(
+        &self,
+        meeting_title: &str,
+        transcripts: &[TranscriptSegment],
+        folder_path: Option<String>,
+    ) -> Result<String, sqlx::Error> {
+        TranscriptsRepository::save_transcript(&self.pool, meeting_title, transcripts, folder_path)
+            .await
+    }
+
+    async fn search_transcripts(
+        &self,
+        query: &str,
+    ) -> Result<Vec<TranscriptSearchResult>, sqlx::Error> {
+        TranscriptsRepository::search_transcripts(&self.pool, query).await
+    }
+
+    async fn get_transcript_segments(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Vec<TranscriptSegment>, sqlx::Error> {
+        TranscriptsRepository::get_segments(&self.pool, meeting_id).await
+    }
+
+    async fn save_transcript_data(
+        &self,
+        meeting_id: &str,
+        text: &str,
+        model: &str,
+        model_name: &str,
+        chunk_size: i32,
+        overlap: i32,
+    ) -> Result<(), sqlx::Error> {
+        TranscriptChunksRepository::save_transcript_data(
+            &self.pool, meeting_id, text, model, model_name, chunk_size, overlap,
+        )
+        .await
+    }
+
+    async fn get_transcript_data(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Option<TranscriptChunk>, sqlx::Error> {
+        TranscriptChunksRepository::get(&self.pool, meeting_id).await
+    }
+
+    async fn save_chunk_layout(
+        &self,
+        meeting_id: &str,
+        text: &str,
+        chunk_size: usize,
+        overlap: usize,
+        boundaries: &[ChunkBoundary],
+    ) -> Result<bool, sqlx::Error> {
+        TranscriptChunksRepository::save_chunk_layout(
+            &self.pool, meeting_id, text, chunk_size, overlap, boundaries,
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl SettingsRepo for SqlxRepositories {
+    async fn get_model_config(&self) -> Result<Option<Setting>, sqlx::Error> {
+        SettingsRepository::get_model_config(&self.pool).await
+    }
+
+    async fn save_model_config(
+        &self,
+        provider: &str,
+        model: &str,
+        whisper_model: &str,
+        ollama_endpoint: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        SettingsRepository::save_model_config(
+            &self.pool,
+            provider,
+            model,
+            whisper_model,
+            ollama_endpoint,
+        )
+        .await
+    }
+
+    async fn get_api_key(&self, provider: &str) -> Result<Option<String>, sqlx::Error> {
+        SettingsRepository::get_api_key(&self.pool, provider).await
+    }
+
+    async fn save_api_key(&self, provider: &str, api_key: &str) -> Result<(), sqlx::Error> {
+        SettingsRepository::save_api_key(&self.pool, provider, api_key).await
+    }
+
+    async fn get_summary_fallbacks(&self) -> Result<Vec<SummaryFallback>, sqlx::Error> {
+        SettingsRepository::get_summary_fallbacks(&self.pool).await
+    }
+
+    async fn save_summary_fallbacks(
+        &self,
+        fallbacks: &[SummaryFallback],
+    ) -> Result<(), sqlx::Error> {
+        SettingsRepository::save_summary_fallbacks(&self.pool, fallbacks).await
+    }
+}
+
+#[async_trait]
+impl SummaryProcessesRepo for SqlxRepositories {
+    async fn get_summary_data(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Option<SummaryProcess>, sqlx::Error> {
+        SummaryProcessesRepository::get_summary_data(&self.pool, meeting_id).await
+    }
+
+    async fn create_or_reset_process(&self, meeting_id: &str) -> Result<(), sqlx::Error> {
+        SummaryProcessesRepository::create_or_reset_process(&self.pool, meeting_id).await
+    }
+
+    async fn update_process_processing(&self, meeting_id: &str) -> Result<(), sqlx::Error> {
+        SummaryProcessesRepository::update_process_processing(&self.pool, meeting_id).await
+    }
+
+    async fn update_process_completed(
+        &self,
+        meeting_id: &str,
+        result: Value,
+        chunk_count: i64,
+        processing_time: f64,
+    ) -> Result<(), sqlx::Error> {
+        SummaryProcessesRepository::update_process_completed(
+            &self.pool,
+            meeting_id,
+            result,
+            chunk_count,
+            processing_time,
+        )
+        .await
+    }
+
+    async fn update_process_failed(
+        &self,
+        meeting_id: &str,
+        error: &str,
+    ) -> Result<(), sqlx::Error> {
+        SummaryProcessesRepository::update_process_failed(&self.pool, meeting_id, error).await
+    }
+
+    async fn update_meeting_summary(
+        &self,
+        meeting_id: &str,
+        summary: &Value,
+    ) -> Result<bool, sqlx::Error> {
+        SummaryProcessesRepository::update_meeting_summary(&self.pool, meeting_id, summary).await
+    }
+
+    async fn delete_live_summaries(&self, meeting_id: &str) -> Result<u64, sqlx::Error> {
+        LiveSummariesRepository::delete_for_meeting(&self.pool, meeting_id).await
+    }
+
+    async fn save_summary_version(
+        &self,
+        meeting_id: &str,
+        template_id: &str,
+        result: Option<&Value>,
+        error: Option<&str>,
+    ) -> Result<String, sqlx::Error> {
+        SummaryProcessesRepository::save_summary_version(
+            &self.pool,
+            meeting_id,
+            template_id,
+            result,
+            error,
+        )
+        .await
+    }
+
+    async fn list_summary_versions(
+        &self,
+        meeting_id: &str,
+    ) -> Result<Vec<SummaryVersion>, sqlx::Error> {
+        SummaryProcessesRepository::list_summary_versions(&self.pool, meeting_id).await
+    }
+}
+
+/// Contract tests run against both the SQLx and in-memory implementations
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::{segment, test_pool};
+
+    async fn contract_meetings_and_transcripts(repos: &Repositories) {
+        let meeting_id = repos
+            .transcripts
+            .save_transcript(
+                "Weekly Sync",
+                &[
+                    segment("s1", "We agreed to ship the Stripe fix", 0.0, 5.0),
+                    segment("s2", "Sam owns the webhook retry", 5.0, 10.0),
+                ],
+                None,
+            )
+            .await
+            .unwrap();
+
+        let segments = repos
+            .transcripts
+            .get_transcript_segments(&meeting_id)
+            .await
+            .unwrap();
+        assert_eq!(
+            segments.iter().map(|s| s.text.as_str()).collect::<Vec<_>>(),
+            vec!["We agreed to ship the Stripe fix", "Sam owns the webhook retry"]
+        );
+        assert_eq!(segments[1].audio_start_time, Some(5.0));
+        assert!(repos
+            .transcripts
+            .get_transcript_segments("missing")
+            .await
+            .unwrap()
+            .is_empty());
+
+        let meetings = repos.meetings.get_meetings().await.unwrap();
+        assert_eq!(meetings.len(), 1);
+        assert_eq!(meetings[0].id, meeting_id);
+        assert_eq!(meetings[0].title, "Weekly Sync");
+
+        let results = repos.transcripts.search_transcripts("WEBHOOK").await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, meeting_id);
+        assert!(results[0].match_context.contains("webhook"));
+        assert!(repos.transcripts.search_transcripts("  ").await.unwrap().is_empty());
+
+        let text = "We agreed to ship the Stripe fix\nSam owns the webhook retry";
+        repos
+            .transcripts
+            .save_transcript_data(&meeting_id, text, "ollama", "llama3", 40000, 1000)
+            .await
+            .unwrap();
+        assert!(repos
+            .transcripts
+            .save_transcript_data("missing", text, "ollama", "llama3", 40000, 1000)
+            .await
+            .is_err());
+        let data = repos.transcripts.get_transcript_data(&meeting_id).await.unwrap().unwrap();
+        assert_eq!(data.meeting_name.as_deref(), Some("Weekly Sync"));
+        assert_eq!(data.transcript_text, text);
+        assert!(data.boundaries().is_empty());
+
+        let boundaries = vec![
+            ChunkBoundary {
+                chunk_index: 1,
+                char_start: 0,
+                char_end: 40,
+            },
+            ChunkBoundary {
+                chunk_index: 2,
+                char_start: 30,
+                char_end: 59,
+            },
+        ];
+        assert!(repos
+            .transcripts
+            .save_chunk_layout(&meeting_id, "compacted", 10, 2, &boundaries)
+            .await
+            .unwrap());
+        assert!(!repos
+            .transcripts
+            .save_chunk_layout("missing", "compacted", 10, 2, &boundaries)
+            .await
+            .unwrap());
+        let data = repos.transcripts.get_transcript_data(&meeting_id).await.unwrap().unwrap();
+        assert_eq!(data.transcript_text, "compacted");
+        assert_eq!((data.chunk_size, data.overlap), (Some(10), Some(2)));
+        assert_eq!(data.boundaries(), boundaries);
+
+        assert!(repos
+            .meetings
+            .update_meeting_title(&meeting_id, "Renamed")
+            .await
+            .unwrap());
+        assert!(!repos
+            .meetings
+            .update_meeting_title("missing", "Renamed")
+            .await
+            .unwrap());
+        assert_eq!(repos.meetings.get_meetings().await.unwrap()[0].title, "Renamed");
+        // The copy of the name in the transcript data follows renames
+        assert_eq!(
+            repos
+                .transcripts
+                .get_transcript_data(&meeting_id)
+                .await
+                .unwrap()
+                .unwrap()
+                .meeting_name
+                .as_deref(),
+            Some("Renamed")
+        );
+
+        assert_eq!(
+            repos.meetings.get_meeting_preferences(&meeting_id).await.unwrap(),
+            Some(MeetingPreferences::default())
+        );
+        let preferences = MeetingPreferences {
+            template_id: Some("standard_meeting".to_string()),
+            model_provider: Some("ollama".to_string()),
+            model_name: Some("llama3.2:latest".to_string()),
+        };
+        assert!(repos
+            .meetings
+            .set_meeting_preferences(&meeting_id, &preferences)
+            .await
+            .unwrap());
+        assert!(!repos
+            .meetings
+            .set_meeting_preferences("missing", &preferences)
+            .await
+            .unwrap());
+        assert_eq!(
+            repos.meetings.get_meeting_preferences(&meeting_id).await.unwrap(),
+            Some(preferences)
+        );
+        assert_eq!(repos.meetings.get_meeting_preferences("missing").await.unwrap(), None);
+
+        assert_eq!(
+            repos.meetings.get_participant_map(&meeting_id).await.unwrap(),
+            Some(ParticipantMap::new())
+        );
+        let map = ParticipantMap::from([("Sam Lee".to_string(), "Engineer A".to_string())]);
+        assert!(repos
+            .meetings
+            .set_participant_map(&meeting_id, &map)
+            .await
+            .unwrap());
+        assert!(!repos.meetings.set_participant_map("missing", &map).await.unwrap());
+        assert_eq!(
+            repos.meetings.get_participant_map(&meeting_id).await.unwrap(),
+            Some(map)
+        );
+        assert_eq!(repos.meetings.get_participant_map("missing").await.unwrap(), None);
+
+        assert_eq!(
+            repos.meetings.get_participants(&meeting_id).await.unwrap(),
+            Some(Vec::new())
+        );
+        let participants = vec![
+            MeetingParticipant {
+                name: "Sam Lee".to_string(),
+                role: Some("Engineer".to_string()),
+                mentions: vec!["Sam Lee".to_string(), "@sam lee".to_string()],
+            },
+            MeetingParticipant {
+                name: "Ana".to_string(),
+                role: None,
+                mentions: vec!["Ana".to_string()],
+            },
+        ];
+        assert!(repos
+            .meetings
+            .set_participants(&meeting_id, &participants)
+            .await
+            .unwrap());
+        assert!(repos
+            .meetings
+            .set_participants(&meeting_id, &participants[1..])
+            .await
+            .unwrap());
+        assert!(!repos
+            .meetings
+            .set_participants("missing", &participants)
+            .await
+            .unwrap());
+        assert_eq!(
+            repos.meetings.get_participants(&meeting_id).await.unwrap(),
+            Some(participants[1..].to_vec())
+        );
+        assert_eq!(repos.meetings.get_participants("missing").await.unwrap(), None);
+
+        assert_eq!(repos.meetings.get_meeting_context(&meeting_id).await.unwrap(), None);
+        let context = MeetingContext {
+            text: "Project Atlas: migrate billing to Stripe".to_string(),
+            source: Some("/notes/atlas.md".to_string()),
+            token_count: 10,
+        };
+        assert!(repos
+            .meetings
+            .set_meeting_context(&meeting_id, Some(&context))
+            .await
+            .unwrap());
+        assert!(!repos
+            .meetings
+            .set_meeting_context("missing", Some(&context))
+            .await
+            .unwrap());
+        assert_eq!(
+            repos.meetings.get_meeting_context(&meeting_id).await.unwrap(),
+            Some(context)
+        );
+        assert!(repos.meetings.set_meeting_context(&meeting_id, None).await.unwrap());
+        assert_eq!(repos.meetings.get_meeting_context(&meeting_id).await.unwrap(), None);
+
+        repos.summaries.create_or_reset_process(&meeting_id).await.unwrap();
+        assert!(repos.meetings.delete_meeting(&meeting_id).await.unwrap());
+        assert!(!repos.meetings.delete_meeting(&meeting_id).await.unwrap());
+        assert!(repos.meetings.delete_meeting("  ").await.is_err());
+        assert!(repos.meetings.get_meetings().await.unwrap().is_empty());
+        // Deleting the meeting removes its summary process and transcripts
+        assert!(repos.summaries.get_summary_data(&meeting_id).await.unwrap().is_none());
+        assert!(repos.transcripts.search_transcripts("webhook").await.unwrap().is_empty());
+        assert!(repos.transcripts.get_transcript_data(&meeting_id).await.unwrap().is_none());
+    }
+
+    async fn contract_settings(repos: &Repositories) {
+        assert!(repos.settings.get_model_config().await.unwrap().is_none());
+        assert_eq!(repos.settings.get_api_key("openai").await.unwrap(), None);
+        assert!(repos.settings.get_api_key("unknown").await.is_err());
+
+        repos.settings.save_api_key("claude", "sk-ant").await.unwrap();
+        assert_eq!(
+            repos.settings.get_api_key("claude").await.unwrap(),
+            Some("sk-ant".to_string())
+        );
+        assert_eq!(repos.settings.get_api_key("groq").await.unwrap(), None);
+        assert!(repos.settings.save_api_key("unknown", "x").await.is_err());
+
+        repos
+            .settings
+            .save_model_config("ollama", "llama3.2:latest", "large-v3", Some("http://host:11434"))
+            .await
+            .unwrap();
+        let config = repos.settings.get_model_config().await.unwrap().unwrap();
+        assert_eq!(config.provider, "ollama");
+        assert_eq!(config.model, "llama3.2:latest");
+        assert_eq!(config.ollama_endpoint.as_deref(), Some("http://host:11434"));
+        // Saving the model config keeps previously stored keys
+        assert_eq!(config.anthropic_api_key.as_deref(), Some("sk-ant"));
+
+        assert!(repos.settings.get_summary_fallbacks().await.unwrap().is_empty());
+        let fallbacks = vec![
+            SummaryFallback {
+                provider: "groq".to_string(),
+                model: "llama-3.3-70b-versatile".to_string(),
+            },
+            SummaryFallback {
+                provider: "openai".to_string(),
+                model: "gpt-4o-mini".to_string(),
+            },
+        ];
+        repos.settings.save_summary_fallbacks(&fallbacks).await.unwrap();
+        assert_eq!(repos.settings.get_summary_fallbacks().await.unwrap(), fallbacks);
+        // Fallbacks are kept separately from the primary provider
+        let config = repos.settings.get_model_config().await.unwrap().unwrap();
+        assert_eq!(config.provider, "ollama");
+    }
+
+    async fn contract_summary_processes(repos: &Repositories) {
+        let meeting_id = repos
+            .transcripts
+            .save_transcript("Standup", &[segment("s1", "Status update", 0.0, 5.0)], None)
+            .await
+            .unwrap();
+
+        assert!(repos.summaries.get_summary_data(&meeting_id).await.unwrap().is_none());
+        assert!(repos.summaries.create_or_reset_process("missing").await.is_err());
+
+        repos.summaries.create_or_reset_process(&meeting_id).await.unwrap();
+        let process = repos.summaries.get_summary_data(&meeting_id).await.unwrap().unwrap();
+        assert_eq!(process.status, "PENDING");
+        assert!(process.result.is_none());
+
+        repos.summaries.update_process_processing(&meeting_id).await.unwrap();
+        assert_eq!(
+            repos.summaries.get_summary_data(&meeting_id).await.unwrap().unwrap().status,
+            "processing"
+        );
+
+        // Resetting a running process cancels it and starts a new PENDING one
+        repos.summaries.create_or_reset_process(&meeting_id).await.unwrap();
+        let process = repos.summaries.get_summary_data(&meeting_id).await.unwrap().unwrap();
+        assert_eq!(process.status, "PENDING");
+        assert!(process.error.is_none());
+
+        repos
+            .summaries
+            .update_process_completed(
+                &meeting_id,
+                serde_json::json!({
+                    "markdown": "## Summary",
+                    "validation_warnings": ["Found placeholder 'TBD'"],
+                }),
+                2,
+                1.5,
+            )
+            .await
+            .unwrap();
+        let process = repos.summaries.get_summary_data(&meeting_id).await.unwrap().unwrap();
+        assert_eq!(process.status, "completed");
+        assert_eq!(process.chunk_count, 2);
+        assert_eq!(process.processing_time, 1.5);
+        assert_eq!(process.warning_count, Some(1));
+        let result: Value = serde_json::from_str(process.result.as_deref().unwrap()).unwrap();
+        assert_eq!(result["markdown"], "## Summary");
+
+        let edited = serde_json::json!({ "markdown": "## Summary\n\nEdited" });
+        assert!(repos
+            .summaries
+            .update_meeting_summary(&meeting_id, &edited)
+            .await
+            .unwrap());
+        assert!(!repos
+            .summaries
+            .update_meeting_summary("missing", &edited)
+            .await
+            .unwrap());
+        let process = repos.summaries.get_summary_data(&meeting_id).await.unwrap().unwrap();
+        let result: Value = serde_json::from_str(process.result.as_deref().unwrap()).unwrap();
+        assert_eq!(result, edited);
+        assert_eq!(
+            repos.summaries.delete_live_summaries(&meeting_id).await.unwrap(),
+            0
+        );
+
+        repos
+            .summaries
+            .update_process_failed(&meeting_id, "boom")
+            .await
+            .unwrap();
+        let process = repos.summaries.get_summary_data(&meeting_id).await.unwrap().unwrap();
+        assert_eq!(process.status, "failed");
+        assert_eq!(process.error.as_deref(), Some("boom"));
+        assert_eq!(process.error_kind.as_deref(), Some("other"));
+
+        let standup = serde_json::json!({ "markdown": "## Standup" });
+        repos
+            .summaries
+            .save_summary_version(&meeting_id, "daily_standup", Some(&standup), None)
+            .await
+            .unwrap();
+        repos
+            .summaries
+            .save_summary_version(&meeting_id, "retrospective", None, Some("HTTP 400"))
+            .await
+            .unwrap();
+        assert!(repos
+            .summaries
+            .save_summary_version("missing", "daily_standup", Some(&standup), None)
+            .await
+            .is_err());
+        let versions = repos.summaries.list_summary_versions(&meeting_id).await.unwrap();
+        let listed: Vec<(&str, &str)> = versions
+            .iter()
+            .map(|v| (v.template_id.as_str(), v.status.as_str()))
+            .collect();
+        assert_eq!(listed, vec![("retrospective", "failed"), ("daily_standup", "completed")]);
+        assert_eq!(versions[0].error.as_deref(), Some("HTTP 400"));
+        let result: Value = serde_json::from_str(versions[1].result.as_deref().unwrap()).unwrap();
+        assert_eq!(result, standup);
+
+        // Versions go with the meeting
+        assert!(repos.meetings.delete_meeting(&meeting_id).await.unwrap());
+        assert!(repos.summaries.list_summary_versions(&meeting_id).await.unwrap().is_empty());
+    }
+
+    async fn sqlx_repos() -> Repositories {
+        Repositories::sqlx(test_pool().await)
+    }
+
+    #[tokio::test]
+    async fn test_sqlx_meetings_and_transcripts_contract() {
+        contract_meetings_and_transcripts(&sqlx_repos().await).await;
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_meetings_and_transcripts_contract() {
+        contract_meetings_and_transcripts(&Repositories::in_memory()).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlx_settings_contract() {
+        contract_settings(&sqlx_repos().await).await;
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_settings_contract() {
+        contract_settings(&Repositories::in_memory()).await;
+    }
+
+    #[tokio::test]
+    async fn test_sqlx_summary_processes_contract() {
+        contract_summary_processes(&sqlx_repos().await).await;
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_summary_processes_contract() {
+        contract_summary_processes(&Repositories::in_memory()).await;
+    }
+}
@@ -1,20 +1,51 @@
 use crate::api::{TranscriptSearchResult, TranscriptSegment};
+use crate::database::models::Transcript;
+use crate::database::repositories::transcription_status::TranscriptionStatusRepository;
+use crate::database::segment_order::order_segments;
+use crate::database::segment_repeats::drop_repeats;
 use chrono::Utc;
 use sqlx::{Connection, Error as SqlxError, SqlitePool};
-use tracing::{error, info};
+use tracing::{error, info, warn};
 use uuid::Uuid;
 
+/// Segments committed per transaction when saving a transcript
+pub const SEGMENT_BATCH_SIZE: usize = 100;
+
+/// Outcome of `TranscriptsRepository::save_segments`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SavedSegments {
+    /// Segments saved in total, including earlier attempts
+    pub saved: usize,
+    /// Repeated segments left out
+    pub duplicates_skipped: usize,
+}
+
 pub struct TranscriptsRepository;
 
 impl TranscriptsRepository {
     /// Saves a new meeting and its associated transcript segments.
-    /// This function uses a transaction to ensure that either both the meeting
-    /// and all its transcripts are saved, or none of them are.
+    /// The meeting is created first and the segments are committed in batches
+    /// of `SEGMENT_BATCH_SIZE`, with progress recorded in `transcription_status`;
+    /// if a batch fails, `save_segments` can resume from the last committed one.
     pub async fn save_transcript(
         pool: &SqlitePool,
         meeting_title: &str,
         transcripts: &[TranscriptSegment],
         folder_path: Option<String>,
+    ) -> Result<String, SqlxError> {
+        let meeting_id =
+            Self::create_meeting(pool, meeting_title, transcripts.len(), folder_path).await?;
+        Self::save_segments(pool, &meeting_id, transcripts, SEGMENT_BATCH_SIZE).await?;
+        Ok(meeting_id)
+    }
+
+    /// Creates the meeting a transcript will be saved into, with its
+    /// transcription status in progress
+    pub async fn create_meeting(
+        pool: &SqlitePool,
+        meeting_title: &str,
+        segments_total: usize,
+        folder_path: Option<String>,
     ) -> Result<String, SqlxError> {
         let meeting_id = format!("meeting-{}", Uuid::new_v4());
 
@@ -23,7 +54,6 @@ impl TranscriptsRepository {
 
         let now = Utc::now();
 
-        // 1. Create the new meeting
         let result = sqlx::query(
             "INSERT INTO meetings (id, title, created_at, updated_at, folder_path) VALUES (?, ?, ?, ?, ?)",
         )
@@ -41,17 +71,100 @@ impl TranscriptsRepository {
             return Err(e);
         }
 
+        TranscriptionStatusRepository::start(&mut *transaction, &meeting_id, segments_total)
+            .await?;
+        transaction.commit().await?;
+
         info!("Successfully created meeting with id: {}", meeting_id);
+        Ok(meeting_id)
+    }
 
-        // 2. Save each transcript segment with audio timing fields
-        for segment in transcripts {
+    /// Saves transcript segments with audio timing fields in batches, each
+    /// committed together with the progress it makes.
+    ///
+    /// Segments repeating the one before them (see `segment_repeats`) are
+    /// left out. The whole list is filtered before resuming, so the segment
+    /// before the first unsaved one is the last one saved for the meeting.
+    ///
+    /// Segments already recorded as saved for the meeting are skipped, so
+    /// calling this again with the same list after a failure is safe.
+    pub async fn save_segments(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        transcripts: &[TranscriptSegment],
+        batch_size: usize,
+    ) -> Result<SavedSegments, SqlxError> {
+        let (transcripts, duplicates_skipped) = drop_repeats(transcripts);
+        if duplicates_skipped > 0 {
+            warn!(
+                "⚠️ Leaving out {} repeated segments of meeting {}",
+                duplicates_skipped, meeting_id
+            );
+        }
+        let transcripts = transcripts.as_slice();
+
+        let already_saved = TranscriptionStatusRepository::get(pool, meeting_id)
+            .await?
+            .map(|progress| progress.segments_done.max(0) as usize)
+            .unwrap_or(0)
+            .min(transcripts.len());
+        TranscriptionStatusRepository::start(pool, meeting_id, transcripts.len()).await?;
+
+        let mut saved = already_saved;
+        for batch in transcripts[already_saved..].chunks(batch_size.max(1)) {
+            if let Err(e) = Self::save_batch(pool, meeting_id, batch, saved).await {
+                error!(
+                    "Failed to save transcript segments for meeting {} after {}/{}: {}",
+                    meeting_id,
+                    saved,
+                    transcripts.len(),
+                    e
+                );
+                if let Err(status_err) =
+                    TranscriptionStatusRepository::mark_failed(pool, meeting_id, &e.to_string())
+                        .await
+                {
+                    error!(
+                        "Failed to record transcription failure for meeting {}: {}",
+                        meeting_id, status_err
+                    );
+                }
+                return Err(e);
+            }
+            saved += batch.len();
+        }
+
+        TranscriptionStatusRepository::mark_completed(pool, meeting_id).await?;
+        info!(
+            "Successfully saved {} transcript segments for meeting {} ({} resumed)",
+            transcripts.len(),
+            meeting_id,
+            already_saved
+        );
+        Ok(SavedSegments {
+            saved,
+            duplicates_skipped,
+        })
+    }
+
+    /// Inserts one batch and advances the recorded progress in the same transaction
+    async fn save_batch(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        batch: &[TranscriptSegment],
+        saved_before: usize,
+    ) -> Result<(), SqlxError> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+
+        for segment in batch {
             let transcript_id = format!("transcript-{}", Uuid::new_v4());
             let result = sqlx::query(
                 "INSERT INTO transcripts (id, meeting_id, transcript, timestamp, audio_start_time, audio_end_time, duration)
                  VALUES (?, ?, ?, ?, ?, ?, ?)"
             )
             .bind(&transcript_id)
-            .bind(&meeting_id)
+            .bind(meeting_id)
             .bind(&segment.text)
             .bind(&segment.timestamp)
             .bind(segment.audio_start_time)
@@ -61,25 +174,52 @@ impl TranscriptsRepository {
             .await;
 
             if let Err(e) = result {
-                error!(
-                    "Failed to save transcript segment for meeting {}: {}",
-                    meeting_id, e
-                );
                 transaction.rollback().await?;
                 return Err(e);
             }
         }
 
-        info!(
-            "Successfully saved {} transcript segments for meeting {}",
-            transcripts.len(),
-            meeting_id
-        );
+        TranscriptionStatusRepository::record_progress(
+            &mut *transaction,
+            meeting_id,
+            saved_before + batch.len(),
+        )
+        .await?;
+        transaction.commit().await
+    }
 
-        // Commit the transaction
-        transaction.commit().await?;
+    /// Segments of a meeting, ordered by their position in the recording
+    pub async fn get_segments(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Vec<TranscriptSegment>, SqlxError> {
+        let transcripts = sqlx::query_as::<_, Transcript>(
+            "SELECT * FROM transcripts WHERE meeting_id = ? ORDER BY rowid",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await?;
 
-        Ok(meeting_id)
+        let mut segments: Vec<TranscriptSegment> = transcripts
+            .into_iter()
+            .map(|t| TranscriptSegment {
+                id: t.id,
+                text: t.transcript,
+                timestamp: t.timestamp,
+                audio_start_time: t.audio_start_time,
+                audio_end_time: t.audio_end_time,
+                duration: t.duration,
+            })
+            .collect();
+        // Transcripts saved before segments were ordered on save
+        let order = order_segments(&mut segments);
+        if order.anomaly {
+            warn!(
+                "⚠️ Transcript of meeting {} was stored out of order ({} segments moved, up to {:.1}s back)",
+                meeting_id, order.moved, order.max_backstep_secs
+            );
+        }
+        Ok(segments)
     }
 
     /// Searches for a query string within the transcripts.
@@ -121,7 +261,7 @@ impl TranscriptsRepository {
     }
 
     /// Helper function to extract a snippet of text around the first match of a query.
-    fn get_match_context(transcript: &str, query: &str) -> String {
+    pub(crate) fn get_match_context(transcript: &str, query: &str) -> String {
         let transcript_lower = transcript.to_lowercase();
         let query_lower = query.to_lowercase();
 
@@ -1,12 +1,35 @@
 // src/database/repo/transcript_chunks.rs
+//
+// One row per meeting holding the transcript text sent for summarization.
+// Rows are written by `api_process_transcript` when a summary is requested
+// (`save_transcript_data`), completed by the summary pipeline with the chunk
+// boundaries it used (`save_chunk_layout`), renamed with the meeting
+// (`MeetingsRepository::update_meeting_title`) and can be rebuilt from the
+// transcript segments (`rebuild_from_transcripts`).
 
+use crate::database::models::{ChunkBoundary, TranscriptChunk};
+use crate::database::repositories::transcript::TranscriptsRepository;
+use crate::export::transcript::{render_transcript, TranscriptTextOptions};
 use chrono::Utc;
 use log::info as log_info;
+use serde::Serialize;
 use sqlx::SqlitePool;
 pub struct TranscriptChunksRepository;
 
+/// Result of `TranscriptChunksRepository::rebuild_from_transcripts`
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct TranscriptChunksRebuild {
+    /// Rows rewritten from their meeting's transcript segments
+    pub rebuilt: u64,
+    /// Rows dropped because their meeting has no transcript segments
+    pub removed: u64,
+}
+
 impl TranscriptChunksRepository {
     /// Saves the full transcript text and processing parameters.
+    ///
+    /// Boundaries from an earlier run are cleared since they belong to the
+    /// previous text.
     pub async fn save_transcript_data(
         pool: &SqlitePool,
         meeting_id: &str,
@@ -23,18 +46,21 @@ impl TranscriptChunksRepository {
         let now = Utc::now();
         sqlx::query(
             r#"
-            INSERT INTO transcript_chunks (meeting_id, transcript_text, model, model_name, chunk_size, overlap, created_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO transcript_chunks (meeting_id, meeting_name, transcript_text, model, model_name, chunk_size, overlap, created_at)
+            VALUES (?, (SELECT title FROM meetings WHERE id = ?), ?, ?, ?, ?, ?, ?)
             ON CONFLICT(meeting_id) DO UPDATE SET
+                meeting_name = excluded.meeting_name,
                 transcript_text = excluded.transcript_text,
                 model = excluded.model,
                 model_name = excluded.model_name,
                 chunk_size = excluded.chunk_size,
                 overlap = excluded.overlap,
-                created_at = excluded.created_at
+                created_at = excluded.created_at,
+                chunk_ranges = NULL
             "#
         )
         .bind(meeting_id)
+        .bind(meeting_id)
         .bind(text)
         .bind(model)
         .bind(model_name)
@@ -46,4 +72,234 @@ impl TranscriptChunksRepository {
 
         Ok(())
     }
+
+    pub async fn get(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Option<TranscriptChunk>, sqlx::Error> {
+        sqlx::query_as::<_, TranscriptChunk>("SELECT * FROM transcript_chunks WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Records how a summary run chunked the transcript
+    ///
+    /// `text` is what was actually chunked (after compaction or
+    /// anonymization) and replaces the stored text so the boundaries index
+    /// into it. Returns false if no transcript data was saved for the meeting.
+    pub async fn save_chunk_layout(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        text: &str,
+        chunk_size: usize,
+        overlap: usize,
+        boundaries: &[ChunkBoundary],
+    ) -> Result<bool, sqlx::Error> {
+        let json = serde_json::to_string(boundaries)
+            .map_err(|e| sqlx::Error::Protocol(format!("Invalid chunk boundaries: {}", e)))?;
+        let result = sqlx::query(
+            "UPDATE transcript_chunks SET transcript_text = ?, chunk_size = ?, overlap = ?, chunk_ranges = ? WHERE meeting_id = ?",
+        )
+        .bind(text)
+        .bind(chunk_size as i64)
+        .bind(overlap as i64)
+        .bind(json)
+        .bind(meeting_id)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Rewrites the table from the transcripts table
+    ///
+    /// Every meeting with segments gets a row holding its segments rendered
+    /// as for the summary prompt and its current title. The model
+    /// of an existing row is kept, otherwise the meeting's preferred model is
+    /// used. Boundaries are kept only where the text didn't change. Rows of
+    /// meetings without segments are dropped.
+    pub async fn rebuild_from_transcripts(
+        pool: &SqlitePool,
+    ) -> Result<TranscriptChunksRebuild, sqlx::Error> {
+        let meetings: Vec<(String, String, Option<String>, Option<String>)> = sqlx::query_as(
+            "SELECT id, title, preferred_model_provider, preferred_model_name FROM meetings m
+             WHERE EXISTS (SELECT 1 FROM transcripts t WHERE t.meeting_id = m.id)",
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut rows = Vec::with_capacity(meetings.len());
+        for (meeting_id, title, provider, model_name) in meetings {
+            let segments = TranscriptsRepository::get_segments(pool, &meeting_id).await?;
+            let text = render_transcript(&segments, &TranscriptTextOptions::for_prompt());
+            rows.push((meeting_id, title, text, provider, model_name));
+        }
+
+        let now = Utc::now();
+        let mut transaction = pool.begin().await?;
+        let removed = sqlx::query(
+            "DELETE FROM transcript_chunks WHERE meeting_id NOT IN (SELECT meeting_id FROM transcripts)",
+        )
+        .execute(&mut *transaction)
+        .await?
+        .rows_affected();
+
+        for (meeting_id, title, text, provider, model_name) in &rows {
+            sqlx::query(
+                r#"
+                INSERT INTO transcript_chunks (meeting_id, meeting_name, transcript_text, model, model_name, created_at)
+                VALUES (?, ?, ?, COALESCE(?, ''), COALESCE(?, ''), ?)
+                ON CONFLICT(meeting_id) DO UPDATE SET
+                    meeting_name = excluded.meeting_name,
+                    transcript_text = excluded.transcript_text,
+                    chunk_ranges = CASE
+                        WHEN transcript_chunks.transcript_text = excluded.transcript_text
+                        THEN transcript_chunks.chunk_ranges
+                    END
+                "#,
+            )
+            .bind(meeting_id)
+            .bind(title)
+            .bind(text)
+            .bind(provider)
+            .bind(model_name)
+            .bind(now)
+            .execute(&mut *transaction)
+            .await?;
+        }
+        transaction.commit().await?;
+
+        Ok(TranscriptChunksRebuild {
+            rebuilt: rows.len() as u64,
+            removed,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::repositories::meeting::MeetingsRepository;
+    use crate::database::test_utils::{segment, test_pool};
+
+    fn boundary(chunk_index: usize, char_start: usize, char_end: usize) -> ChunkBoundary {
+        ChunkBoundary {
+            chunk_index,
+            char_start,
+            char_end,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rebuild_from_transcripts() {
+        let pool = test_pool().await;
+        let segments = [
+            segment("s2", "Sam owns the webhook retry", 5.0, 10.0),
+            segment("s1", "We agreed to ship the Stripe fix", 0.0, 5.0),
+        ];
+        let kept = TranscriptsRepository::save_transcript(&pool, "Weekly Sync", &segments, None)
+            .await
+            .unwrap();
+        let stale = TranscriptsRepository::save_transcript(&pool, "Standup", &segments, None)
+            .await
+            .unwrap();
+        let missing = TranscriptsRepository::save_transcript(&pool, "Retro", &segments, None)
+            .await
+            .unwrap();
+        let text = "[00:00:00–00:00:05] We agreed to ship the Stripe fix\n[00:00:05–00:00:10] Sam owns the webhook retry";
+
+        // Up to date, with boundaries from a summary run
+        TranscriptChunksRepository::save_transcript_data(
+            &pool, &kept, text, "ollama", "llama3", 0, 0,
+        )
+        .await
+        .unwrap();
+        let boundaries = vec![boundary(1, 0, 40), boundary(2, 30, 59)];
+        assert!(TranscriptChunksRepository::save_chunk_layout(
+            &pool,
+            &kept,
+            text,
+            10,
+            2,
+            &boundaries
+        )
+        .await
+        .unwrap());
+        // Stale text and name
+        TranscriptChunksRepository::save_transcript_data(
+            &pool, &stale, "old", "openai", "gpt-4o", 0, 0,
+        )
+        .await
+        .unwrap();
+        TranscriptChunksRepository::save_chunk_layout(&pool, &stale, "old", 10, 2, &boundaries)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE transcript_chunks SET meeting_name = 'Old name' WHERE meeting_id = ?")
+            .bind(&stale)
+            .execute(&pool)
+            .await
+            .unwrap();
+        // Orphaned: the meeting has no segments
+        let orphan = TranscriptsRepository::save_transcript(&pool, "Empty", &[], None)
+            .await
+            .unwrap();
+        TranscriptChunksRepository::save_transcript_data(
+            &pool, &orphan, "gone", "openai", "gpt-4o", 0, 0,
+        )
+        .await
+        .unwrap();
+
+        let report = TranscriptChunksRepository::rebuild_from_transcripts(&pool)
+            .await
+            .unwrap();
+        assert_eq!(
+            report,
+            TranscriptChunksRebuild {
+                rebuilt: 3,
+                removed: 1
+            }
+        );
+
+        let row = TranscriptChunksRepository::get(&pool, &kept)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(row.transcript_text, text);
+        assert_eq!(row.boundaries(), boundaries);
+        assert_eq!((row.chunk_size, row.overlap), (Some(10), Some(2)));
+
+        let row = TranscriptChunksRepository::get(&pool, &stale)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(row.meeting_name.as_deref(), Some("Standup"));
+        assert_eq!(row.transcript_text, text);
+        assert_eq!(row.model, "openai");
+        assert!(row.boundaries().is_empty());
+
+        let row = TranscriptChunksRepository::get(&pool, &missing)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(row.meeting_name.as_deref(), Some("Retro"));
+        assert_eq!(row.transcript_text, text);
+        assert_eq!(row.model, "");
+
+        assert!(TranscriptChunksRepository::get(&pool, &orphan)
+            .await
+            .unwrap()
+            .is_none());
+
+        // Renames keep the copied name current
+        assert!(
+            MeetingsRepository::update_meeting_title(&pool, &kept, "Renamed")
+                .await
+                .unwrap()
+        );
+        let row = TranscriptChunksRepository::get(&pool, &kept)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(row.meeting_name.as_deref(), Some("Renamed"));
+    }
 }
@@ -0,0 +1,206 @@
+use crate::database::models::TranscriptionProgress;
+use chrono::Utc;
+use sqlx::{SqliteExecutor, SqlitePool};
+
+pub const STATE_IN_PROGRESS: &str = "in_progress";
+pub const STATE_COMPLETED: &str = "completed";
+pub const STATE_FAILED: &str = "failed";
+
+pub struct TranscriptionStatusRepository;
+
+impl TranscriptionStatusRepository {
+    /// Marks a save as in progress, keeping the segments already recorded so a
+    /// retried save resumes where the last one stopped
+    pub async fn start<'e, E: SqliteExecutor<'e>>(
+        executor: E,
+        meeting_id: &str,
+        segments_total: usize,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO transcription_status (meeting_id, state, segments_done, segments_total, error, updated_at)
+            VALUES ($1, $2, 0, $3, NULL, $4)
+            ON CONFLICT(meeting_id) DO UPDATE SET
+                state = excluded.state,
+                segments_total = excluded.segments_total,
+                error = NULL,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(meeting_id)
+        .bind(STATE_IN_PROGRESS)
+        .bind(segments_total as i64)
+        .bind(Utc::now())
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
+    /// Records how many segments are saved; called in the transaction that
+    /// saves them so the count never runs ahead of the data
+    pub async fn record_progress<'e, E: SqliteExecutor<'e>>(
+        executor: E,
+        meeting_id: &str,
+        segments_done: usize,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE transcription_status SET segments_done = ?, updated_at = ? WHERE meeting_id = ?",
+        )
+        .bind(segments_done as i64)
+        .bind(Utc::now())
+        .bind(meeting_id)
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn mark_completed(pool: &SqlitePool, meeting_id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE transcription_status SET state = ?, error = NULL, updated_at = ? WHERE meeting_id = ?",
+        )
+        .bind(STATE_COMPLETED)
+        .bind(Utc::now())
+        .bind(meeting_id)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn mark_failed(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        error: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE transcription_status SET state = ?, error = ?, updated_at = ? WHERE meeting_id = ?",
+        )
+        .bind(STATE_FAILED)
+        .bind(error)
+        .bind(Utc::now())
+        .bind(meeting_id)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Option<TranscriptionProgress>, sqlx::Error> {
+        sqlx::query_as::<_, TranscriptionProgress>(
+            "SELECT * FROM transcription_status WHERE meeting_id = ?",
+        )
+        .bind(meeting_id)
+        .fetch_optional(pool)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::TranscriptSegment;
+    use crate::database::repositories::transcript::TranscriptsRepository;
+    use crate::database::test_utils::{segment, test_pool};
+
+    fn segments(count: usize) -> Vec<TranscriptSegment> {
+        (0..count)
+            .map(|i| {
+                let id = format!("seg-{}", i);
+                segment(&id, &format!("segment {}", i), i as f64, i as f64 + 1.0)
+            })
+            .collect()
+    }
+
+    async fn saved_segments(pool: &SqlitePool, meeting_id: &str) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM transcripts WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_saving_batches_advances_progress_and_resumes() {
+        let pool = test_pool().await;
+        let segments = segments(5);
+        let meeting_id =
+            TranscriptsRepository::create_meeting(&pool, "Standup", segments.len(), None)
+                .await
+                .unwrap();
+
+        let progress = TranscriptionStatusRepository::get(&pool, &meeting_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(progress.state, STATE_IN_PROGRESS);
+        assert_eq!((progress.segments_done, progress.segments_total), (0, 5));
+
+        // The second batch fails; the first stays committed and counted
+        sqlx::query(
+            "CREATE TRIGGER fail_segment BEFORE INSERT ON transcripts WHEN NEW.transcript = 'segment 3'
+             BEGIN SELECT RAISE(ABORT, 'disk I/O error'); END",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        assert!(
+            TranscriptsRepository::save_segments(&pool, &meeting_id, &segments, 2)
+                .await
+                .is_err()
+        );
+
+        let progress = TranscriptionStatusRepository::get(&pool, &meeting_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(progress.state, STATE_FAILED);
+        assert_eq!(progress.segments_done, 2);
+        assert!(progress.error.unwrap().contains("disk I/O error"));
+        assert_eq!(saved_segments(&pool, &meeting_id).await, 2);
+
+        // Retrying skips what already landed
+        sqlx::query("DROP TRIGGER fail_segment")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let done = TranscriptsRepository::save_segments(&pool, &meeting_id, &segments, 2)
+            .await
+            .unwrap();
+        assert_eq!(done.saved, 5);
+        assert_eq!(done.duplicates_skipped, 0);
+
+        let progress = TranscriptionStatusRepository::get(&pool, &meeting_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(progress.state, STATE_COMPLETED);
+        assert_eq!((progress.segments_done, progress.segments_total), (5, 5));
+        assert_eq!(progress.error, None);
+        assert_eq!(saved_segments(&pool, &meeting_id).await, 5);
+    }
+
+    #[tokio::test]
+    async fn test_repeated_segments_are_not_saved_twice() {
+        let pool = test_pool().await;
+        let mut segments = segments(3);
+        segments.insert(2, segments[1].clone());
+        let meeting_id =
+            TranscriptsRepository::create_meeting(&pool, "Standup", segments.len(), None)
+                .await
+                .unwrap();
+
+        let saved = TranscriptsRepository::save_segments(&pool, &meeting_id, &segments, 2)
+            .await
+            .unwrap();
+        assert_eq!(saved.saved, 3);
+        assert_eq!(saved.duplicates_skipped, 1);
+        assert_eq!(saved_segments(&pool, &meeting_id).await, 3);
+
+        let progress = TranscriptionStatusRepository::get(&pool, &meeting_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!((progress.segments_done, progress.segments_total), (3, 3));
+    }
+}
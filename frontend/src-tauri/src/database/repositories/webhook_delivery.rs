@@ -0,0 +1,55 @@
+use crate::database::models::WebhookDelivery;
+use sqlx::SqlitePool;
+
+pub struct WebhookDeliveriesRepository;
+
+impl WebhookDeliveriesRepository {
+    pub async fn insert(pool: &SqlitePool, delivery: &WebhookDelivery) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO webhook_deliveries
+                (id, webhook_id, event, meeting_id, payload, status, latency_ms, error, redelivery_of, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&delivery.id)
+        .bind(&delivery.webhook_id)
+        .bind(&delivery.event)
+        .bind(&delivery.meeting_id)
+        .bind(&delivery.payload)
+        .bind(delivery.status)
+        .bind(delivery.latency_ms)
+        .bind(&delivery.error)
+        .bind(&delivery.redelivery_of)
+        .bind(delivery.created_at)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get(
+        pool: &SqlitePool,
+        delivery_id: &str,
+    ) -> Result<Option<WebhookDelivery>, sqlx::Error> {
+        sqlx::query_as::<_, WebhookDelivery>("SELECT * FROM webhook_deliveries WHERE id = ?")
+            .bind(delivery_id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// The webhook's latest `limit` deliveries, newest first
+    pub async fn list(
+        pool: &SqlitePool,
+        webhook_id: &str,
+        limit: i64,
+    ) -> Result<Vec<WebhookDelivery>, sqlx::Error> {
+        sqlx::query_as::<_, WebhookDelivery>(
+            "SELECT * FROM webhook_deliveries
+             WHERE webhook_id = ?
+             ORDER BY created_at DESC, rowid DESC
+             LIMIT ?",
+        )
+        .bind(webhook_id)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+    }
+}
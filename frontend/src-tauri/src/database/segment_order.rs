@@ -0,0 +1,166 @@
+//! Puts transcript segments back in recording order
+//!
+//! Live capture can hand segments over out of order when transcription of
+//! the microphone and system audio races, which scrambles the transcript
+//! text summaries are made from. Segments are sorted by `audio_start_time`,
+//! or by their wall-clock `timestamp` when no segment has one; segments
+//! without either keep their place.
+
+use crate::api::TranscriptSegment;
+use chrono::{DateTime, NaiveTime, Timelike};
+use serde::Serialize;
+
+/// Jumps back in time larger than this are more than the usual overlap of
+/// microphone and system audio segments
+pub const ANOMALY_BACKSTEP_SECS: f64 = 10.0;
+
+/// How far the segments were from recording order
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SegmentOrder {
+    /// Segments that had to move
+    pub moved: usize,
+    /// Largest jump back in time between consecutive segments as given
+    pub max_backstep_secs: f64,
+    /// Some jump back exceeded `ANOMALY_BACKSTEP_SECS`
+    pub anomaly: bool,
+}
+
+/// Seconds since midnight of "14:30:05" or "14:30", or the Unix time of an
+/// RFC 3339 timestamp
+fn wall_clock_secs(timestamp: &str) -> Option<f64> {
+    let timestamp = timestamp.trim();
+    if let Ok(time) = DateTime::parse_from_rfc3339(timestamp) {
+        return Some(time.timestamp_millis() as f64 / 1000.0);
+    }
+    NaiveTime::parse_from_str(timestamp, "%H:%M:%S%.f")
+        .or_else(|_| NaiveTime::parse_from_str(timestamp, "%H:%M"))
+        .ok()
+        .map(|time| time.num_seconds_from_midnight() as f64 + time.nanosecond() as f64 / 1e9)
+}
+
+/// Sorts the segments into recording order in place
+///
+/// The sort is stable, so segments starting at the same time keep their
+/// order, and segments without a time stay where they were while the
+/// timed ones are sorted around them.
+pub fn order_segments(segments: &mut [TranscriptSegment]) -> SegmentOrder {
+    // Recording times and wall-clock times can't be compared with each other
+    let use_audio_time = segments.iter().any(|s| s.audio_start_time.is_some());
+    let keys: Vec<Option<f64>> = segments
+        .iter()
+        .map(|segment| {
+            if use_audio_time {
+                segment.audio_start_time
+            } else {
+                wall_clock_secs(&segment.timestamp)
+            }
+        })
+        .collect();
+
+    let slots: Vec<usize> = (0..segments.len()).filter(|&i| keys[i].is_some()).collect();
+    let key = |i: usize| keys[i].unwrap_or_default();
+    let mut sorted = slots.clone();
+    sorted.sort_by(|&a, &b| key(a).total_cmp(&key(b)));
+
+    let max_backstep_secs = slots
+        .windows(2)
+        .map(|pair| key(pair[0]) - key(pair[1]))
+        .fold(0.0, f64::max);
+    let moved = slots
+        .iter()
+        .zip(&sorted)
+        .filter(|(slot, i)| slot != i)
+        .count();
+    if moved > 0 {
+        let reordered: Vec<TranscriptSegment> =
+            sorted.iter().map(|&i| segments[i].clone()).collect();
+        for (&slot, segment) in slots.iter().zip(reordered) {
+            segments[slot] = segment;
+        }
+    }
+
+    SegmentOrder {
+        moved,
+        max_backstep_secs,
+        anomaly: max_backstep_secs > ANOMALY_BACKSTEP_SECS,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::{segment, untimed_segment};
+
+    /// An untimed segment with wall-clock `timestamp`
+    fn wall_clock_segment(id: &str, timestamp: &str) -> TranscriptSegment {
+        TranscriptSegment {
+            timestamp: timestamp.to_string(),
+            ..untimed_segment(id, "")
+        }
+    }
+
+    fn ids(segments: &[TranscriptSegment]) -> Vec<&str> {
+        segments.iter().map(|segment| segment.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_shuffled_segments_are_put_back_in_recording_order() {
+        let mut segments = vec![
+            segment("s4", "", 15.0, 19.0),
+            segment("s1", "", 0.0, 4.0),
+            segment("s3", "", 10.0, 14.0),
+            segment("s2", "", 5.0, 9.0),
+            segment("s5", "", 20.0, 24.0),
+        ];
+
+        let order = order_segments(&mut segments);
+
+        assert_eq!(ids(&segments), ["s1", "s2", "s3", "s4", "s5"]);
+        assert_eq!(order.moved, 3);
+        assert_eq!(order.max_backstep_secs, 15.0);
+        assert!(order.anomaly);
+    }
+
+    #[test]
+    fn test_untimed_segments_keep_their_place() {
+        let mut segments = vec![
+            segment("a", "", 5.0, 9.0),
+            untimed_segment("note", ""),
+            segment("b", "", 0.0, 4.0),
+            segment("c", "", 5.0, 9.0),
+        ];
+
+        let order = order_segments(&mut segments);
+
+        // Equal start times keep their order
+        assert_eq!(ids(&segments), ["b", "note", "a", "c"]);
+        assert_eq!(order.moved, 2);
+        assert!(!order.anomaly);
+
+        let mut untimed = vec![
+            wall_clock_segment("x", ""),
+            wall_clock_segment("y", "not a time"),
+        ];
+        assert_eq!(order_segments(&mut untimed), SegmentOrder::default());
+        assert_eq!(ids(&untimed), ["x", "y"]);
+    }
+
+    #[test]
+    fn test_wall_clock_timestamps_are_used_without_recording_times() {
+        let mut segments = vec![
+            wall_clock_segment("b", "14:30:07"),
+            wall_clock_segment("a", "14:30:05"),
+            wall_clock_segment("c", "14:31"),
+        ];
+
+        let order = order_segments(&mut segments);
+
+        assert_eq!(ids(&segments), ["a", "b", "c"]);
+        assert_eq!(order.max_backstep_secs, 2.0);
+        assert!(!order.anomaly, "a small overlap isn't an anomaly");
+        assert_eq!(
+            wall_clock_secs("2024-05-01T10:00:01.500Z"),
+            Some(1714557601.5)
+        );
+    }
+}
@@ -0,0 +1,92 @@
+//! Drops repeated transcript segments before they are saved
+//!
+//! When a transcription request is retried after a network blip, the
+//! pipeline can emit the same segment twice, and the saved transcript then
+//! reads every such sentence twice. A segment is a repeat when it has the
+//! same text as the segment saved just before it and starts at the same
+//! point of the recording, within `START_TIME_EPSILON_SECS`. The same
+//! phrase said again later in the meeting starts elsewhere and is kept.
+
+use crate::api::TranscriptSegment;
+
+/// Start times closer than this belong to the same segment
+pub const START_TIME_EPSILON_SECS: f64 = 0.1;
+
+/// Whether `segment` repeats `previous`
+pub fn is_repeat(previous: &TranscriptSegment, segment: &TranscriptSegment) -> bool {
+    if previous.text.trim() != segment.text.trim() {
+        return false;
+    }
+    match (previous.audio_start_time, segment.audio_start_time) {
+        (Some(a), Some(b)) => (a - b).abs() <= START_TIME_EPSILON_SECS,
+        // Untimed segments can only be told apart by their wall-clock time
+        (None, None) => previous.timestamp == segment.timestamp,
+        _ => false,
+    }
+}
+
+/// The segments without repeats of the segment before them, and how many
+/// were dropped
+///
+/// Segments should be in recording order, so that a retried segment lands
+/// next to the one it repeats.
+pub fn drop_repeats(segments: &[TranscriptSegment]) -> (Vec<TranscriptSegment>, usize) {
+    let mut kept: Vec<TranscriptSegment> = Vec::with_capacity(segments.len());
+    for segment in segments {
+        if kept
+            .last()
+            .is_some_and(|previous| is_repeat(previous, segment))
+        {
+            continue;
+        }
+        kept.push(segment.clone());
+    }
+    let dropped = segments.len() - kept.len();
+    (kept, dropped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::{segment, untimed_segment};
+
+    fn ids(segments: &[TranscriptSegment]) -> Vec<&str> {
+        segments.iter().map(|segment| segment.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_retried_segments_are_dropped() {
+        let segments = vec![
+            segment("a", "Let's start with the release.", 0.0, 3.0),
+            segment("a-retry", "Let's start with the release.", 0.0, 3.0),
+            segment("b", "The webhook fix is in review.", 3.0, 6.0),
+            // Re-timed by the retry, but within the epsilon
+            segment("b-retry", "The webhook fix is in review. ", 3.04, 6.0),
+            untimed_segment("c", "Untimed note"),
+            untimed_segment("c-retry", "Untimed note"),
+        ];
+
+        let (kept, dropped) = drop_repeats(&segments);
+
+        assert_eq!(ids(&kept), ["a", "b", "c"]);
+        assert_eq!(dropped, 3);
+    }
+
+    #[test]
+    fn test_repeated_phrases_at_other_times_are_kept() {
+        let segments = vec![
+            segment("yes-1", "Yes.", 10.0, 13.0),
+            segment("yes-2", "Yes.", 11.5, 14.5),
+            segment("other", "Agreed.", 13.0, 16.0),
+            segment("yes-3", "Yes.", 13.0, 16.0),
+            segment("late", "Yes.", 13.0 + START_TIME_EPSILON_SECS * 2.0, 16.0),
+        ];
+
+        let (kept, dropped) = drop_repeats(&segments);
+
+        assert_eq!(ids(&kept), ["yes-1", "yes-2", "other", "yes-3", "late"]);
+        assert_eq!(dropped, 0);
+        // A timed and an untimed segment are never the same one
+        assert!(!is_repeat(&segments[0], &untimed_segment("x", "Yes.")));
+    }
+}
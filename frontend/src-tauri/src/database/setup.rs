@@ -30,7 +30,7 @@ pub async fn initialize_database_on_startup(app: &AppHandle) -> Result<(), Strin
             .await
             .map_err(|e| format!("Failed to initialize database manager: {}", e))?;
 
-        app.manage(AppState { db_manager });
+        app.manage(AppState::new(db_manager));
         info!("Database initialized successfully");
     }
 
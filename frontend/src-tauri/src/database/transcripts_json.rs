@@ -0,0 +1,389 @@
+//! Transcript segments read from a recording folder's `transcripts.json`,
+//! for meetings whose transcript isn't stored in the database
+//!
+//! The meeting view polls `get_meeting`, and the file of a long meeting is
+//! several MB, so a parsed file is kept per meeting until its size or
+//! modification time changes. Segments are deserialized one at a time
+//! straight from the file rather than from the whole file read into a
+//! string and a `Value` tree, and files above `MAX_TRANSCRIPTS_JSON_BYTES`
+//! are skipped.
+
+use crate::api::TranscriptSegment;
+use crate::audio::recording_saver::TranscriptSegment as SavedSegment;
+use serde::de::{Deserializer, SeqAccess, Visitor};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Instant, SystemTime};
+use tracing::{info, warn};
+
+const TRANSCRIPTS_FILE: &str = "transcripts.json";
+
+/// Largest file parsed; a few hours of segments are well below it
+pub const MAX_TRANSCRIPTS_JSON_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Meetings whose parsed file is kept; the least recently read is dropped
+const MAX_CACHED_MEETINGS: usize = 8;
+
+/// Identifies one version of a file
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FileStamp {
+    pub len: u64,
+    pub modified: Option<SystemTime>,
+}
+
+/// The file access the cache needs, so tests can count reads
+pub trait TranscriptFiles: Send + Sync {
+    fn is_dir(&self, path: &Path) -> bool;
+    /// None if the file doesn't exist
+    fn stamp(&self, path: &Path) -> io::Result<Option<FileStamp>>;
+    fn open(&self, path: &Path) -> io::Result<Box<dyn Read>>;
+}
+
+pub struct StdTranscriptFiles;
+
+impl TranscriptFiles for StdTranscriptFiles {
+    fn is_dir(&self, path: &Path) -> bool {
+        path.is_dir()
+    }
+
+    fn stamp(&self, path: &Path) -> io::Result<Option<FileStamp>> {
+        match std::fs::metadata(path) {
+            Ok(metadata) => Ok(Some(FileStamp {
+                len: metadata.len(),
+                modified: metadata.modified().ok(),
+            })),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn open(&self, path: &Path) -> io::Result<Box<dyn Read>> {
+        Ok(Box::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+struct CachedSegments {
+    path: PathBuf,
+    stamp: FileStamp,
+    segments: Arc<Vec<TranscriptSegment>>,
+    last_read: Instant,
+}
+
+pub struct TranscriptsJsonCache {
+    files: Box<dyn TranscriptFiles>,
+    capacity: usize,
+    entries: Mutex<HashMap<String, CachedSegments>>,
+}
+
+impl Default for TranscriptsJsonCache {
+    fn default() -> Self {
+        Self::with_files(Box::new(StdTranscriptFiles))
+    }
+}
+
+impl TranscriptsJsonCache {
+    pub fn with_files(files: Box<dyn TranscriptFiles>) -> Self {
+        Self {
+            files,
+            capacity: MAX_CACHED_MEETINGS,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Reads the same way but keeps nothing, for a pass over many meetings
+    /// such as an export
+    pub fn uncached() -> Self {
+        Self {
+            capacity: 0,
+            ..Self::default()
+        }
+    }
+
+    /// Segments of the `transcripts.json` in the meeting's recording folder
+    ///
+    /// Empty if the folder or file is missing, too large or can't be read.
+    pub fn segments(&self, meeting_id: &str, folder_path: &str) -> Vec<TranscriptSegment> {
+        let folder = Path::new(folder_path);
+        if !self.files.is_dir(folder) {
+            warn!(
+                "Recording folder missing for meeting {}: {}",
+                meeting_id, folder_path
+            );
+            return Vec::new();
+        }
+        let path = folder.join(TRANSCRIPTS_FILE);
+        let stamp = match self.files.stamp(&path) {
+            Ok(Some(stamp)) => stamp,
+            Ok(None) => return Vec::new(),
+            Err(e) => {
+                warn!(
+                    "Failed to read transcripts.json for meeting {} at {}: {}",
+                    meeting_id,
+                    path.display(),
+                    e
+                );
+                return Vec::new();
+            }
+        };
+        if stamp.len > MAX_TRANSCRIPTS_JSON_BYTES {
+            warn!(
+                "⚠️ transcripts.json for meeting {} is {} MB, above the {} MB limit; not loading it",
+                meeting_id,
+                stamp.len / (1024 * 1024),
+                MAX_TRANSCRIPTS_JSON_BYTES / (1024 * 1024)
+            );
+            return Vec::new();
+        }
+
+        let mut entries = self.entries.lock().unwrap();
+        if let Some(cached) = entries.get_mut(meeting_id) {
+            if cached.path == path && cached.stamp == stamp {
+                cached.last_read = Instant::now();
+                return cached.segments.as_ref().clone();
+            }
+        }
+        // Parsed under the lock so concurrent polls of one meeting parse once
+        let segments = match self.parse(&path) {
+            Ok(segments) => segments,
+            Err(e) => {
+                warn!(
+                    "Failed to parse transcripts.json for meeting {} at {}: {}",
+                    meeting_id,
+                    path.display(),
+                    e
+                );
+                return Vec::new();
+            }
+        };
+        if !segments.is_empty() {
+            info!(
+                "Loaded {} transcript segments from transcripts.json for meeting {}",
+                segments.len(),
+                meeting_id
+            );
+        }
+
+        if self.capacity == 0 {
+            return segments;
+        }
+        if entries.len() >= self.capacity && !entries.contains_key(meeting_id) {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, cached)| cached.last_read)
+                .map(|(id, _)| id.clone());
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+        let segments = Arc::new(segments);
+        entries.insert(
+            meeting_id.to_string(),
+            CachedSegments {
+                path,
+                stamp,
+                segments: segments.clone(),
+                last_read: Instant::now(),
+            },
+        );
+        segments.as_ref().clone()
+    }
+
+    fn parse(&self, path: &Path) -> Result<Vec<TranscriptSegment>, String> {
+        let reader = self.files.open(path).map_err(|e| e.to_string())?;
+        // The reader is capped too, in case the file grew after its stamp was read
+        parse_segments(reader.take(MAX_TRANSCRIPTS_JSON_BYTES))
+    }
+}
+
+/// The `segments` array of a transcripts file, read element by element;
+/// segments that don't match the saved format are skipped
+pub fn parse_segments<R: Read>(reader: R) -> Result<Vec<TranscriptSegment>, String> {
+    #[derive(Deserialize)]
+    struct TranscriptsFile {
+        #[serde(deserialize_with = "deserialize_segments")]
+        segments: Vec<TranscriptSegment>,
+    }
+
+    serde_json::from_reader::<_, TranscriptsFile>(reader)
+        .map(|file| file.segments)
+        .map_err(|e| e.to_string())
+}
+
+fn deserialize_segments<'de, D>(deserializer: D) -> Result<Vec<TranscriptSegment>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct SegmentsVisitor;
+
+    impl<'de> Visitor<'de> for SegmentsVisitor {
+        type Value = Vec<TranscriptSegment>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("an array of transcript segments")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut segments = Vec::new();
+            while let Some(value) = seq.next_element::<Value>()? {
+                if let Ok(saved) = serde_json::from_value::<SavedSegment>(value) {
+                    segments.push(TranscriptSegment {
+                        id: saved.id,
+                        text: saved.text,
+                        // Use the human-friendly display time as timestamp for now
+                        timestamp: saved.display_time,
+                        audio_start_time: Some(saved.audio_start_time),
+                        audio_end_time: Some(saved.audio_end_time),
+                        duration: Some(saved.duration),
+                    });
+                }
+            }
+            Ok(segments)
+        }
+    }
+
+    deserializer.deserialize_seq(SegmentsVisitor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+    /// One folder holding one transcripts file, counting how often it's opened
+    #[derive(Default)]
+    struct MockFiles {
+        contents: Mutex<String>,
+        modified: AtomicU64,
+        len_override: Mutex<Option<u64>>,
+        opens: Arc<AtomicUsize>,
+    }
+
+    impl MockFiles {
+        fn write(&self, contents: String) {
+            *self.contents.lock().unwrap() = contents;
+            self.modified.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    impl TranscriptFiles for Arc<MockFiles> {
+        fn is_dir(&self, path: &Path) -> bool {
+            path == Path::new("/meetings/standup")
+        }
+
+        fn stamp(&self, path: &Path) -> io::Result<Option<FileStamp>> {
+            if path != Path::new("/meetings/standup").join(TRANSCRIPTS_FILE) {
+                return Ok(None);
+            }
+            let len = self.contents.lock().unwrap().len() as u64;
+            Ok(Some(FileStamp {
+                len: self.len_override.lock().unwrap().unwrap_or(len),
+                modified: Some(
+                    SystemTime::UNIX_EPOCH
+                        + std::time::Duration::from_secs(self.modified.load(Ordering::SeqCst)),
+                ),
+            }))
+        }
+
+        fn open(&self, _path: &Path) -> io::Result<Box<dyn Read>> {
+            self.opens.fetch_add(1, Ordering::SeqCst);
+            Ok(Box::new(io::Cursor::new(
+                self.contents.lock().unwrap().clone().into_bytes(),
+            )))
+        }
+    }
+
+    fn transcripts_file(texts: &[&str]) -> String {
+        let segments: Vec<Value> = texts
+            .iter()
+            .enumerate()
+            .map(|(i, text)| {
+                serde_json::json!({
+                    "id": format!("segment-{}", i),
+                    "text": text,
+                    "audio_start_time": i as f64 * 5.0,
+                    "audio_end_time": i as f64 * 5.0 + 5.0,
+                    "duration": 5.0,
+                    "display_time": "[00:00]",
+                    "confidence": 0.9,
+                    "sequence_id": i,
+                })
+            })
+            .collect();
+        serde_json::json!({ "version": "1.0", "segments": segments }).to_string()
+    }
+
+    fn texts(segments: &[TranscriptSegment]) -> Vec<&str> {
+        segments.iter().map(|s| s.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_repeated_reads_parse_the_file_once_until_it_changes() {
+        let files = Arc::new(MockFiles::default());
+        files.write(transcripts_file(&["Hello", "World"]));
+        let cache = TranscriptsJsonCache::with_files(Box::new(files.clone()));
+
+        for _ in 0..5 {
+            let segments = cache.segments("meeting-1", "/meetings/standup");
+            assert_eq!(texts(&segments), vec!["Hello", "World"]);
+        }
+        assert_eq!(files.opens.load(Ordering::SeqCst), 1);
+
+        // A rewritten file is parsed again
+        files.write(transcripts_file(&["Hello", "World", "Again"]));
+        assert_eq!(cache.segments("meeting-1", "/meetings/standup").len(), 3);
+        assert_eq!(cache.segments("meeting-1", "/meetings/standup").len(), 3);
+        assert_eq!(files.opens.load(Ordering::SeqCst), 2);
+
+        // Missing folders and files aren't opened at all
+        assert!(cache.segments("meeting-2", "/meetings/missing").is_empty());
+        assert_eq!(files.opens.load(Ordering::SeqCst), 2);
+
+        // Without a capacity every read parses
+        let uncached = TranscriptsJsonCache {
+            capacity: 0,
+            ..TranscriptsJsonCache::with_files(Box::new(files.clone()))
+        };
+        assert_eq!(uncached.segments("meeting-1", "/meetings/standup").len(), 3);
+        assert_eq!(uncached.segments("meeting-1", "/meetings/standup").len(), 3);
+        assert_eq!(files.opens.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn test_oversized_file_is_skipped_without_reading_it() {
+        let files = Arc::new(MockFiles::default());
+        files.write(transcripts_file(&["Hello"]));
+        *files.len_override.lock().unwrap() = Some(MAX_TRANSCRIPTS_JSON_BYTES + 1);
+        let cache = TranscriptsJsonCache::with_files(Box::new(files.clone()));
+
+        assert!(cache.segments("meeting-1", "/meetings/standup").is_empty());
+        assert_eq!(files.opens.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_parse_skips_malformed_segments_and_requires_the_array() {
+        let kept: Value = serde_json::from_str(&transcripts_file(&["Kept"])).unwrap();
+        let file = serde_json::json!({
+            "segments": [
+                { "id": "bad", "text": "missing timing" },
+                kept["segments"][0],
+            ],
+            "speakers": { "ignored": [1, 2, 3] },
+        })
+        .to_string();
+        assert_eq!(
+            texts(&parse_segments(file.as_bytes()).unwrap()),
+            vec!["Kept"]
+        );
+
+        assert!(parse_segments(r#"{"version":"1.0"}"#.as_bytes())
+            .unwrap_err()
+            .contains("segments"));
+        assert!(parse_segments(r#"{"segments":[{"#.as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,284 @@
+use super::renderer::{encode_export, render_meeting, ExportFormat, MeetingExport};
+use crate::database::repositories::{
+    meeting::MeetingsRepository, summary::SummaryProcessesRepository,
+    transcript::TranscriptsRepository,
+};
+use crate::database::transcripts_json::TranscriptsJsonCache;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use tracing::{info, warn};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// Name of the index entry written at the root of every export archive
+pub const INDEX_FILE_NAME: &str = "index.json";
+
+/// Which meetings to export: an explicit id list or a transcript search query
+/// (an empty query selects every meeting)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum MeetingSelection {
+    Ids(Vec<String>),
+    Query(String),
+}
+
+/// Index entry describing one exported meeting
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IndexEntry {
+    pub id: String,
+    pub title: String,
+    pub created_at: String,
+    pub file: String,
+    pub has_summary: bool,
+}
+
+/// Result of a bulk export
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportReport {
+    pub path: String,
+    pub exported: usize,
+    /// Meeting ids that were selected but could not be exported
+    pub skipped: Vec<String>,
+}
+
+/// Resolves a selection into an ordered, de-duplicated list of meeting ids
+pub async fn resolve_meeting_ids(
+    pool: &SqlitePool,
+    selection: &MeetingSelection,
+) -> Result<Vec<String>, sqlx::Error> {
+    let ids: Vec<String> = match selection {
+        MeetingSelection::Ids(ids) => ids.clone(),
+        MeetingSelection::Query(query) if query.trim().is_empty() => {
+            MeetingsRepository::get_meetings(pool)
+                .await?
+                .into_iter()
+                .map(|m| m.id)
+                .collect()
+        }
+        MeetingSelection::Query(query) => TranscriptsRepository::search_transcripts(pool, query)
+            .await?
+            .into_iter()
+            .map(|r| r.id)
+            .collect(),
+    };
+
+    let mut seen = std::collections::HashSet::new();
+    Ok(ids.into_iter().filter(|id| seen.insert(id.clone())).collect())
+}
+
+/// Builds a filesystem-safe archive entry name for a meeting
+fn entry_file_name(position: usize, title: &str, format: ExportFormat) -> String {
+    let safe_title: String = title
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .take(60)
+        .collect();
+    let safe_title = safe_title.trim_matches('_');
+    let safe_title = if safe_title.is_empty() { "meeting" } else { safe_title };
+    format!("{:04}-{}.{}", position + 1, safe_title, format.extension())
+}
+
+/// Reads the summary markdown stored for a meeting, if any
+pub(crate) async fn load_summary_markdown(pool: &SqlitePool, meeting_id: &str) -> Option<String> {
+    let process = SummaryProcessesRepository::get_summary_data(pool, meeting_id)
+        .await
+        .ok()??;
+    let result: serde_json::Value = serde_json::from_str(process.result.as_deref()?).ok()?;
+    result
+        .get("markdown")
+        .and_then(|m| m.as_str())
+        .map(|m| m.to_string())
+}
+
+/// Exports the given meetings into a single zip archive at `dest_path`
+///
+/// Meetings are loaded, rendered and written one at a time so only a single
+/// meeting is held in memory. An index file listing every exported meeting is
+/// written last. With `bom`, each meeting file starts with a UTF-8 byte order
+/// mark; the index never does.
+pub async fn export_meetings_to_zip(
+    pool: &SqlitePool,
+    meeting_ids: &[String],
+    format: ExportFormat,
+    dest_path: &Path,
+    bom: bool,
+) -> Result<ExportReport, String> {
+    if let Some(parent) = dest_path.parent() {
+        if !parent.as_os_str().is_empty() && !parent.exists() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create export directory: {}", e))?;
+        }
+    }
+
+    let file = File::create(dest_path)
+        .map_err(|e| format!("Failed to create export file {}: {}", dest_path.display(), e))?;
+    let mut zip = ZipWriter::new(BufWriter::new(file));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let mut index: Vec<IndexEntry> = Vec::new();
+    let mut skipped = Vec::new();
+
+    let transcripts_json = TranscriptsJsonCache::uncached();
+    for meeting_id in meeting_ids {
+        let meeting =
+            match MeetingsRepository::get_meeting(pool, meeting_id, &transcripts_json).await {
+                Ok(Some(meeting)) => meeting,
+                Ok(None) | Err(sqlx::Error::RowNotFound) => {
+                    warn!("Skipping export of missing meeting {}", meeting_id);
+                    skipped.push(meeting_id.clone());
+                    continue;
+                }
+                Err(e) => return Err(format!("Failed to load meeting {}: {}", meeting_id, e)),
+            };
+
+        let summary_markdown = load_summary_markdown(pool, meeting_id).await;
+        let rendered = render_meeting(
+            &MeetingExport {
+                meeting: &meeting,
+                summary_markdown: summary_markdown.as_deref(),
+            },
+            format,
+        )?;
+
+        let file_name = entry_file_name(index.len(), &meeting.title, format);
+        zip.start_file(file_name.as_str(), options)
+            .map_err(|e| format!("Failed to add {} to archive: {}", file_name, e))?;
+        zip.write_all(&encode_export(&rendered, bom))
+            .map_err(|e| format!("Failed to write {} to archive: {}", file_name, e))?;
+
+        index.push(IndexEntry {
+            id: meeting.id,
+            title: meeting.title,
+            created_at: meeting.created_at,
+            file: file_name,
+            has_summary: summary_markdown.is_some(),
+        });
+    }
+
+    let index_json = serde_json::to_string_pretty(&index)
+        .map_err(|e| format!("Failed to serialize export index: {}", e))?;
+    zip.start_file(INDEX_FILE_NAME, options)
+        .map_err(|e| format!("Failed to add index to archive: {}", e))?;
+    zip.write_all(index_json.as_bytes())
+        .map_err(|e| format!("Failed to write index to archive: {}", e))?;
+
+    let mut writer = zip
+        .finish()
+        .map_err(|e| format!("Failed to finalize archive: {}", e))?;
+    writer
+        .flush()
+        .map_err(|e| format!("Failed to flush archive: {}", e))?;
+
+    info!(
+        "Exported {} meetings to {} ({} skipped)",
+        index.len(),
+        dest_path.display(),
+        skipped.len()
+    );
+
+    Ok(ExportReport {
+        path: dest_path.to_string_lossy().to_string(),
+        exported: index.len(),
+        skipped,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::{seed_meetings, test_pool};
+
+    #[tokio::test]
+    async fn test_zip_contains_one_entry_per_meeting_plus_index() {
+        let pool = test_pool().await;
+        seed_meetings(&pool, 3, 2).await;
+
+        let ids = resolve_meeting_ids(&pool, &MeetingSelection::Query(String::new()))
+            .await
+            .unwrap();
+        assert_eq!(ids.len(), 3);
+
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("export.zip");
+        let report = export_meetings_to_zip(&pool, &ids, ExportFormat::Markdown, &dest, false)
+            .await
+            .unwrap();
+        assert_eq!(report.exported, 3);
+        assert!(report.skipped.is_empty());
+
+        let mut archive = zip::ZipArchive::new(File::open(&dest).unwrap()).unwrap();
+        assert_eq!(archive.len(), 4);
+        let names: Vec<String> = archive.file_names().map(|n| n.to_string()).collect();
+        assert!(names.contains(&INDEX_FILE_NAME.to_string()));
+        assert_eq!(names.iter().filter(|n| n.ends_with(".md")).count(), 3);
+
+        let index: Vec<IndexEntry> =
+            serde_json::from_reader(archive.by_name(INDEX_FILE_NAME).unwrap()).unwrap();
+        assert_eq!(index.len(), 3);
+        assert!(index.iter().all(|entry| names.contains(&entry.file)));
+    }
+
+    #[tokio::test]
+    async fn test_missing_meetings_are_skipped() {
+        let pool = test_pool().await;
+        seed_meetings(&pool, 1, 1).await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("export.zip");
+        let ids = vec!["meeting-0".to_string(), "meeting-missing".to_string()];
+        let report = export_meetings_to_zip(&pool, &ids, ExportFormat::Json, &dest, false)
+            .await
+            .unwrap();
+
+        assert_eq!(report.exported, 1);
+        assert_eq!(report.skipped, vec!["meeting-missing".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_bom_only_when_requested() {
+        let pool = test_pool().await;
+        seed_meetings(&pool, 1, 1).await;
+        let ids = vec!["meeting-0".to_string()];
+        let dir = tempfile::tempdir().unwrap();
+
+        let entry_bytes = |dest: &Path, name: &str| {
+            let mut archive = zip::ZipArchive::new(File::open(dest).unwrap()).unwrap();
+            let mut bytes = Vec::new();
+            std::io::Read::read_to_end(&mut archive.by_name(name).unwrap(), &mut bytes).unwrap();
+            bytes
+        };
+        let entry = entry_file_name(0, "Meeting 0", ExportFormat::Markdown);
+
+        let plain = dir.path().join("plain.zip");
+        export_meetings_to_zip(&pool, &ids, ExportFormat::Markdown, &plain, false)
+            .await
+            .unwrap();
+        let bytes = entry_bytes(&plain, &entry);
+        assert!(bytes.starts_with(b"# "), "{:?}", &bytes[..3]);
+
+        let with_bom = dir.path().join("bom.zip");
+        export_meetings_to_zip(&pool, &ids, ExportFormat::Markdown, &with_bom, true)
+            .await
+            .unwrap();
+        let bytes = entry_bytes(&with_bom, &entry);
+        assert_eq!(&bytes[..3], &[0xEF, 0xBB, 0xBF]);
+        assert_eq!(&bytes[3..], entry_bytes(&plain, &entry).as_slice());
+        assert!(entry_bytes(&with_bom, INDEX_FILE_NAME).starts_with(b"["));
+
+        // Never doubled, and dropped when not asked for
+        assert_eq!(encode_export("\u{FEFF}Café", true), b"\xEF\xBB\xBFCaf\xC3\xA9");
+        assert_eq!(encode_export("\u{FEFF}Café", false), "Café".as_bytes());
+    }
+
+    #[test]
+    fn test_entry_file_name_is_sanitized() {
+        assert_eq!(
+            entry_file_name(0, "Weekly sync: Q3/Q4", ExportFormat::Text),
+            "0001-Weekly_sync__Q3_Q4.txt"
+        );
+        assert_eq!(entry_file_name(9, "///", ExportFormat::Json), "0010-meeting.json");
+    }
+}
@@ -0,0 +1,224 @@
+use super::archive::{
+    export_meetings_to_zip, load_summary_markdown, resolve_meeting_ids, ExportReport,
+    MeetingSelection,
+};
+use super::config::{
+    decrypt_notion_token, encrypt_notion_token, load_note_export_config, save_note_export_config,
+    NoteExportSettings,
+};
+use super::notion::{create_page, markdown_to_blocks};
+use super::obsidian::{note_file_name, render_note};
+use super::renderer::{encode_export, ExportFormat};
+use crate::api::MeetingDetails;
+use crate::database::repositories::meeting::MeetingsRepository;
+use crate::database::transcripts_json::TranscriptsJsonCache;
+use crate::http::client_factory;
+use crate::state::AppState;
+use log::{error as log_error, info as log_info};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use tauri::{AppHandle, Runtime};
+
+/// A summary rendered as a note, and where it was written if anywhere
+#[derive(Debug, Serialize)]
+pub struct NoteExport {
+    pub markdown: String,
+    pub path: Option<String>,
+}
+
+/// Exports all meetings matching a query (or an explicit id list) into one zip
+///
+/// Each meeting is rendered in `format` ("markdown", "json" or "text") and the
+/// archive also contains an `index.json` listing every exported meeting.
+/// `bom` starts each meeting file with a UTF-8 byte order mark for Excel and
+/// older Windows editors.
+#[tauri::command]
+pub async fn api_export_meetings<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    query_or_ids: MeetingSelection,
+    format: String,
+    dest_path: String,
+    bom: Option<bool>,
+) -> Result<ExportReport, String> {
+    log_info!(
+        "api_export_meetings called: selection={:?}, format={}, dest={}, bom={:?}",
+        query_or_ids,
+        format,
+        dest_path,
+        bom
+    );
+    let format = ExportFormat::from_str(&format)?;
+    let pool = state.db_manager.pool();
+
+    let meeting_ids = resolve_meeting_ids(pool, &query_or_ids)
+        .await
+        .map_err(|e| format!("Failed to resolve meetings to export: {}", e))?;
+    if meeting_ids.is_empty() {
+        return Err("No meetings match the export selection".to_string());
+    }
+
+    export_meetings_to_zip(
+        pool,
+        &meeting_ids,
+        format,
+        std::path::Path::new(&dest_path),
+        bom.unwrap_or(false),
+    )
+    .await
+    .map_err(|e| {
+        log_error!("Meeting export failed: {}", e);
+        e
+    })
+}
+
+async fn load_meeting_with_summary(
+    pool: &SqlitePool,
+    transcripts_json: &TranscriptsJsonCache,
+    meeting_id: &str,
+) -> Result<(MeetingDetails, String), String> {
+    let meeting = match MeetingsRepository::get_meeting(pool, meeting_id, transcripts_json).await {
+        Ok(Some(meeting)) => meeting,
+        Ok(None) | Err(sqlx::Error::RowNotFound) => return Err("Meeting not found".to_string()),
+        Err(e) => return Err(format!("Failed to load meeting {}: {}", meeting_id, e)),
+    };
+    let summary = load_summary_markdown(pool, meeting_id)
+        .await
+        .ok_or_else(|| "No summary found for this meeting".to_string())?;
+    Ok((meeting, summary))
+}
+
+/// Renders a meeting's summary as an Obsidian-style note (YAML frontmatter,
+/// action items as tasks)
+///
+/// The note is written to `dest_dir`, or to the configured vault directory;
+/// with neither it is only returned. `bom` starts the written file with a
+/// UTF-8 byte order mark.
+#[tauri::command]
+pub async fn api_export_summary_note<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    dest_dir: Option<String>,
+    bom: Option<bool>,
+) -> Result<NoteExport, String> {
+    log_info!(
+        "api_export_summary_note called for meeting_id: {}, dest_dir: {:?}",
+        meeting_id,
+        dest_dir
+    );
+    let config = load_note_export_config(&app);
+    let (meeting, summary) = load_meeting_with_summary(
+        state.db_manager.pool(),
+        &state.transcripts_json,
+        &meeting_id,
+    )
+    .await?;
+    let markdown = render_note(&meeting, &summary, &config.tags);
+
+    let dir = dest_dir
+        .or(config.vault_dir)
+        .filter(|dir| !dir.trim().is_empty());
+    let path = match dir {
+        Some(dir) => {
+            std::fs::create_dir_all(&dir)
+                .map_err(|e| format!("Failed to create export directory {}: {}", dir, e))?;
+            let path =
+                std::path::Path::new(&dir).join(note_file_name(&config.filename_pattern, &meeting));
+            let bytes = encode_export(&markdown, bom.unwrap_or(false));
+            std::fs::write(&path, bytes).map_err(|e| {
+                log_error!("Failed to write note to {}: {}", path.display(), e);
+                format!("Failed to write note: {}", e)
+            })?;
+            log_info!("📝 Summary note written to {}", path.display());
+            Some(path.to_string_lossy().to_string())
+        }
+        None => None,
+    };
+
+    Ok(NoteExport { markdown, path })
+}
+
+/// Creates a Notion page with the meeting's summary under the configured
+/// parent page; returns the page URL
+#[tauri::command]
+pub async fn api_export_summary_notion<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<String, String> {
+    log_info!(
+        "api_export_summary_notion called for meeting_id: {}",
+        meeting_id
+    );
+    let config = load_note_export_config(&app);
+    let parent_page_id = config
+        .notion_parent_page_id
+        .filter(|id| !id.trim().is_empty())
+        .ok_or_else(|| "No Notion parent page configured".to_string())?;
+    let token = match &config.notion_token {
+        Some(secrets) => decrypt_notion_token(&app, secrets)?,
+        None => return Err("No Notion integration token configured".to_string()),
+    };
+
+    let (meeting, summary) = load_meeting_with_summary(
+        state.db_manager.pool(),
+        &state.transcripts_json,
+        &meeting_id,
+    )
+    .await?;
+    let blocks = markdown_to_blocks(&summary);
+    let url = create_page(
+        &client_factory::client(),
+        &token,
+        &parent_page_id,
+        &meeting.title,
+        &blocks,
+    )
+    .await
+    .map_err(|e| {
+        log_error!("Notion export failed for {}: {}", meeting_id, e);
+        e
+    })?;
+    log_info!("✅ Summary for {} exported to Notion: {}", meeting_id, url);
+    Ok(url)
+}
+
+#[tauri::command]
+pub async fn api_get_note_export_config<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<NoteExportSettings, String> {
+    Ok(NoteExportSettings::from(&load_note_export_config(&app)))
+}
+
+/// Saves the note export settings
+///
+/// `notion_token` replaces the stored token; an empty string removes it and
+/// None keeps it.
+#[tauri::command]
+pub async fn api_save_note_export_config<R: Runtime>(
+    app: AppHandle<R>,
+    settings: NoteExportSettings,
+    notion_token: Option<String>,
+) -> Result<NoteExportSettings, String> {
+    log_info!("api_save_note_export_config called");
+    let mut config = load_note_export_config(&app);
+    config.vault_dir = settings.vault_dir.filter(|dir| !dir.trim().is_empty());
+    config.filename_pattern = settings.filename_pattern;
+    config.tags = settings
+        .tags
+        .into_iter()
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    config.notion_parent_page_id = settings
+        .notion_parent_page_id
+        .filter(|id| !id.trim().is_empty());
+    match notion_token.as_deref().map(str::trim) {
+        Some("") => config.notion_token = None,
+        Some(token) => config.notion_token = Some(encrypt_notion_token(&app, token)?),
+        None => {}
+    }
+    save_note_export_config(&app, &config)?;
+    Ok(NoteExportSettings::from(&config))
+}
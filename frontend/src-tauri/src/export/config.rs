@@ -0,0 +1,144 @@
+use super::obsidian::DEFAULT_FILENAME_PATTERN;
+use crate::settings_bundle::crypto::{decrypt_local, encrypt_local, EncryptedSecrets};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use log::warn;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_store::StoreExt;
+
+const CONFIG_STORE: &str = "note-export.json";
+const CONFIG_KEY: &str = "config";
+
+/// Per-install key the Notion token is encrypted with, in the app data dir
+const SECRET_KEY_FILE: &str = "export-secret.key";
+
+/// Where and how summaries are exported as notes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NoteExportConfig {
+    /// Notes are written here when no directory is given for an export
+    pub vault_dir: Option<String>,
+    /// `{date}`, `{title}` and `{id}` are replaced; `.md` is appended
+    pub filename_pattern: String,
+    pub tags: Vec<String>,
+    /// Page the Notion export creates its pages under
+    pub notion_parent_page_id: Option<String>,
+    /// Notion integration token, encrypted; never returned to the frontend
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notion_token: Option<EncryptedSecrets>,
+}
+
+impl Default for NoteExportConfig {
+    fn default() -> Self {
+        Self {
+            vault_dir: None,
+            filename_pattern: DEFAULT_FILENAME_PATTERN.to_string(),
+            tags: vec!["meeting".to_string()],
+            notion_parent_page_id: None,
+            notion_token: None,
+        }
+    }
+}
+
+/// The config as shown to the frontend, with the token reduced to a flag
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NoteExportSettings {
+    pub vault_dir: Option<String>,
+    pub filename_pattern: String,
+    pub tags: Vec<String>,
+    pub notion_parent_page_id: Option<String>,
+    #[serde(default)]
+    pub has_notion_token: bool,
+}
+
+impl From<&NoteExportConfig> for NoteExportSettings {
+    fn from(config: &NoteExportConfig) -> Self {
+        Self {
+            vault_dir: config.vault_dir.clone(),
+            filename_pattern: config.filename_pattern.clone(),
+            tags: config.tags.clone(),
+            notion_parent_page_id: config.notion_parent_page_id.clone(),
+            has_notion_token: config.notion_token.is_some(),
+        }
+    }
+}
+
+pub fn load_note_export_config<R: Runtime>(app: &AppHandle<R>) -> NoteExportConfig {
+    let stored = app
+        .store(CONFIG_STORE)
+        .ok()
+        .and_then(|store| store.get(CONFIG_KEY));
+    match stored.map(serde_json::from_value::<NoteExportConfig>) {
+        Some(Ok(config)) => config,
+        Some(Err(e)) => {
+            warn!(
+                "⚠️ Invalid note export config in store, using defaults: {}",
+                e
+            );
+            NoteExportConfig::default()
+        }
+        None => NoteExportConfig::default(),
+    }
+}
+
+pub fn save_note_export_config<R: Runtime>(
+    app: &AppHandle<R>,
+    config: &NoteExportConfig,
+) -> Result<(), String> {
+    let store = app
+        .store(CONFIG_STORE)
+        .map_err(|e| format!("Failed to open note export store: {}", e))?;
+    let value = serde_json::to_value(config)
+        .map_err(|e| format!("Failed to serialize note export config: {}", e))?;
+    store.set(CONFIG_KEY, value);
+    store
+        .save()
+        .map_err(|e| format!("Failed to save note export config: {}", e))
+}
+
+/// Random key created on first use and kept next to the app database
+///
+/// This keeps tokens out of the plaintext settings stores; anyone who can
+/// read the app data directory can still recover them.
+pub(crate) fn local_secret_key<R: Runtime>(app: &AppHandle<R>) -> Result<String, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    let path = dir.join(SECRET_KEY_FILE);
+    if let Ok(key) = std::fs::read_to_string(&path) {
+        if !key.trim().is_empty() {
+            return Ok(key.trim().to_string());
+        }
+    }
+
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create app data dir: {}", e))?;
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    let key = BASE64.encode(bytes);
+    std::fs::write(&path, &key).map_err(|e| format!("Failed to store secret key: {}", e))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600));
+    }
+    Ok(key)
+}
+
+pub fn encrypt_notion_token<R: Runtime>(
+    app: &AppHandle<R>,
+    token: &str,
+) -> Result<EncryptedSecrets, String> {
+    encrypt_local(token.as_bytes(), &local_secret_key(app)?)
+}
+
+pub fn decrypt_notion_token<R: Runtime>(
+    app: &AppHandle<R>,
+    secrets: &EncryptedSecrets,
+) -> Result<String, String> {
+    let bytes = decrypt_local(secrets, &local_secret_key(app)?)
+        .map_err(|_| "Stored Notion token can't be decrypted; enter it again".to_string())?;
+    String::from_utf8(bytes).map_err(|_| "Stored Notion token is corrupted".to_string())
+}
@@ -0,0 +1,20 @@
+/// Export module - renders meetings to files for use outside the app
+///
+/// This module contains:
+/// - Renderers that turn a single meeting (transcript + summary) into Markdown, JSON or plain text
+/// - Bulk export that packages many meetings into a zip archive with an index
+/// - Summary notes for Obsidian (YAML frontmatter, action items as tasks) and
+///   Notion pages built from the same summary
+/// - The transcript rendered as one document, also used for summary prompts
+/// - Tauri commands for frontend integration
+
+pub mod archive;
+pub mod commands;
+pub mod config;
+pub mod notion;
+pub mod obsidian;
+pub mod renderer;
+pub mod transcript;
+
+pub use archive::{export_meetings_to_zip, ExportReport};
+pub use renderer::{render_meeting, ExportFormat, MeetingExport};
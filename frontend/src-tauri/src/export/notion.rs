@@ -0,0 +1,312 @@
+use crate::export::obsidian::action_items_to_tasks;
+use crate::summary::table::{is_separator_row, is_table_line, split_cells, unescape_cell};
+use reqwest::Client;
+use serde_json::{json, Value};
+
+const NOTION_API_URL: &str = "https://api.notion.com/v1";
+/// API version the block payloads below are written against
+const NOTION_VERSION: &str = "2022-06-28";
+
+/// Notion rejects text objects longer than this
+const MAX_TEXT_CHARS: usize = 2000;
+/// Most children Notion accepts in one request
+const MAX_BLOCKS_PER_REQUEST: usize = 100;
+
+fn text_object(content: &str, bold: bool) -> Value {
+    let mut object = json!({
+        "type": "text",
+        "text": { "content": content },
+    });
+    if bold {
+        object["annotations"] = json!({ "bold": true });
+    }
+    object
+}
+
+/// Rich text for a line of markdown: `**bold**` runs are annotated and long
+/// runs are split to stay under the text length limit
+pub fn rich_text(markdown: &str) -> Vec<Value> {
+    let mut objects = Vec::new();
+    for (i, run) in markdown.split("**").enumerate() {
+        let bold = i % 2 == 1;
+        let chars: Vec<char> = run.chars().collect();
+        for piece in chars.chunks(MAX_TEXT_CHARS) {
+            objects.push(text_object(&piece.iter().collect::<String>(), bold));
+        }
+    }
+    objects
+}
+
+fn text_block(kind: &str, markdown: &str) -> Value {
+    json!({
+        "object": "block",
+        "type": kind,
+        kind: { "rich_text": rich_text(markdown) },
+    })
+}
+
+fn to_do_block(markdown: &str, checked: bool) -> Value {
+    json!({
+        "object": "block",
+        "type": "to_do",
+        "to_do": { "rich_text": rich_text(markdown), "checked": checked },
+    })
+}
+
+/// Table block with the first row as column header
+fn table_block(lines: &[&str]) -> Value {
+    let rows: Vec<Vec<String>> = lines
+        .iter()
+        .filter(|line| !is_separator_row(line))
+        .map(|line| {
+            split_cells(line)
+                .iter()
+                .map(|cell| unescape_cell(cell))
+                .collect()
+        })
+        .collect();
+    let width = rows.iter().map(Vec::len).max().unwrap_or(1).max(1);
+    let children: Vec<Value> = rows
+        .iter()
+        .map(|cells| {
+            // Every row must have exactly `table_width` cells
+            let cells: Vec<Vec<Value>> = (0..width)
+                .map(|i| rich_text(cells.get(i).map_or("", String::as_str)))
+                .collect();
+            json!({
+                "object": "block",
+                "type": "table_row",
+                "table_row": { "cells": cells },
+            })
+        })
+        .collect();
+    json!({
+        "object": "block",
+        "type": "table",
+        "table": {
+            "table_width": width,
+            "has_column_header": true,
+            "has_row_header": false,
+            "children": children,
+        },
+    })
+}
+
+fn numbered_item(line: &str) -> Option<&str> {
+    let digits = line.find(|c: char| !c.is_ascii_digit())?;
+    if digits == 0 {
+        return None;
+    }
+    line[digits..].strip_prefix(". ")
+}
+
+/// Converts a summary into Notion blocks
+///
+/// The Action Items table becomes to-do blocks, as in the Obsidian export;
+/// other tables become table blocks. Consecutive plain lines form one
+/// paragraph.
+pub fn markdown_to_blocks(markdown: &str) -> Vec<Value> {
+    let markdown = action_items_to_tasks(markdown);
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut blocks = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut i = 0;
+
+    let flush = |paragraph: &mut Vec<&str>, blocks: &mut Vec<Value>| {
+        if !paragraph.is_empty() {
+            blocks.push(text_block("paragraph", &paragraph.join("\n")));
+            paragraph.clear();
+        }
+    };
+
+    while i < lines.len() {
+        let line = lines[i].trim();
+        if is_table_line(line) {
+            flush(&mut paragraph, &mut blocks);
+            let start = i;
+            while i < lines.len() && is_table_line(lines[i]) {
+                i += 1;
+            }
+            blocks.push(table_block(&lines[start..i]));
+            continue;
+        }
+        i += 1;
+
+        let block = if line.is_empty() {
+            None
+        } else if let Some(text) = line.strip_prefix("### ") {
+            Some(text_block("heading_3", text))
+        } else if let Some(text) = line.strip_prefix("## ") {
+            Some(text_block("heading_2", text))
+        } else if let Some(text) = line.strip_prefix("# ") {
+            Some(text_block("heading_1", text))
+        } else if line.starts_with("####") {
+            // Notion has three heading levels
+            Some(text_block("heading_3", line.trim_start_matches('#').trim()))
+        } else if let Some(text) = line.strip_prefix("- [ ] ") {
+            Some(to_do_block(text, false))
+        } else if let Some(text) = line
+            .strip_prefix("- [x] ")
+            .or_else(|| line.strip_prefix("- [X] "))
+        {
+            Some(to_do_block(text, true))
+        } else if let Some(text) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+            Some(text_block("bulleted_list_item", text))
+        } else if let Some(text) = numbered_item(line) {
+            Some(text_block("numbered_list_item", text))
+        } else if let Some(text) = line.strip_prefix("> ") {
+            Some(text_block("quote", text))
+        } else if line == "---" || line == "***" {
+            Some(json!({ "object": "block", "type": "divider", "divider": {} }))
+        } else {
+            paragraph.push(line);
+            continue;
+        };
+
+        flush(&mut paragraph, &mut blocks);
+        if let Some(block) = block {
+            blocks.push(block);
+        }
+    }
+    flush(&mut paragraph, &mut blocks);
+    blocks
+}
+
+/// Body of the create-page request: a child page of `parent_page_id` titled
+/// `title`, with up to the first 100 blocks
+pub fn page_request(parent_page_id: &str, title: &str, blocks: &[Value]) -> Value {
+    let first = &blocks[..blocks.len().min(MAX_BLOCKS_PER_REQUEST)];
+    json!({
+        "parent": { "page_id": parent_page_id },
+        "properties": {
+            "title": { "title": [text_object(title, false)] },
+        },
+        "children": first,
+    })
+}
+
+async fn send(request: reqwest::RequestBuilder, token: &str) -> Result<Value, String> {
+    let response = request
+        .bearer_auth(token)
+        .header("Notion-Version", NOTION_VERSION)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach Notion: {}", e))?;
+    let status = response.status();
+    let body: Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Invalid response from Notion: {}", e))?;
+    if !status.is_success() {
+        let message = body
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown error");
+        return Err(format!(
+            "Notion API error (HTTP {}): {}",
+            status.as_u16(),
+            message
+        ));
+    }
+    Ok(body)
+}
+
+/// Creates the page and appends any blocks past the first request's limit;
+/// returns the page URL
+pub async fn create_page(
+    client: &Client,
+    token: &str,
+    parent_page_id: &str,
+    title: &str,
+    blocks: &[Value],
+) -> Result<String, String> {
+    let page = send(
+        client
+            .post(format!("{}/pages", NOTION_API_URL))
+            .json(&page_request(parent_page_id, title, blocks)),
+        token,
+    )
+    .await?;
+    let page_id = page
+        .get("id")
+        .and_then(Value::as_str)
+        .ok_or_else(|| "Notion did not return a page id".to_string())?;
+
+    for batch in blocks
+        .get(MAX_BLOCKS_PER_REQUEST..)
+        .unwrap_or_default()
+        .chunks(MAX_BLOCKS_PER_REQUEST)
+    {
+        send(
+            client
+                .patch(format!("{}/blocks/{}/children", NOTION_API_URL, page_id))
+                .json(&json!({ "children": batch })),
+            token,
+        )
+        .await?;
+    }
+
+    Ok(page
+        .get("url")
+        .and_then(Value::as_str)
+        .unwrap_or(page_id)
+        .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blocks_match_notion_schema() {
+        let markdown = "## Summary\n\nLaunch moved to **Friday**.\nQA signs off Thursday.\n\n- Budget approved\n1. Ship\n\n## Action Items\n\n| Owner | Task | Due |\n|---|---|---|\n| Sam | Fix webhook | 2024-06-14 |\n\n## Risks\n\n| Risk | Impact |\n|---|---|\n| Vendor delay | High |\n";
+
+        let blocks = markdown_to_blocks(markdown);
+
+        // Shapes as documented for the 2022-06-28 API (block objects with a
+        // type key and a same-named payload)
+        let expected: Value = serde_json::from_str(
+            r#"[
+              {"object":"block","type":"heading_2","heading_2":{"rich_text":[{"type":"text","text":{"content":"Summary"}}]}},
+              {"object":"block","type":"paragraph","paragraph":{"rich_text":[
+                {"type":"text","text":{"content":"Launch moved to "}},
+                {"type":"text","text":{"content":"Friday"},"annotations":{"bold":true}},
+                {"type":"text","text":{"content":".\nQA signs off Thursday."}}
+              ]}},
+              {"object":"block","type":"bulleted_list_item","bulleted_list_item":{"rich_text":[{"type":"text","text":{"content":"Budget approved"}}]}},
+              {"object":"block","type":"numbered_list_item","numbered_list_item":{"rich_text":[{"type":"text","text":{"content":"Ship"}}]}},
+              {"object":"block","type":"heading_2","heading_2":{"rich_text":[{"type":"text","text":{"content":"Action Items"}}]}},
+              {"object":"block","type":"to_do","to_do":{"rich_text":[{"type":"text","text":{"content":"Fix webhook (@Sam, due 2024-06-14)"}}],"checked":false}},
+              {"object":"block","type":"heading_2","heading_2":{"rich_text":[{"type":"text","text":{"content":"Risks"}}]}},
+              {"object":"block","type":"table","table":{"table_width":2,"has_column_header":true,"has_row_header":false,"children":[
+                {"object":"block","type":"table_row","table_row":{"cells":[[{"type":"text","text":{"content":"Risk"}}],[{"type":"text","text":{"content":"Impact"}}]]}},
+                {"object":"block","type":"table_row","table_row":{"cells":[[{"type":"text","text":{"content":"Vendor delay"}}],[{"type":"text","text":{"content":"High"}}]]}}
+              ]}}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(Value::Array(blocks), expected);
+    }
+
+    #[test]
+    fn test_page_request_respects_api_limits() {
+        let long = "x".repeat(MAX_TEXT_CHARS + 10);
+        let pieces = rich_text(&long);
+        assert_eq!(pieces.len(), 2);
+        assert_eq!(pieces[1]["text"]["content"].as_str().unwrap().len(), 10);
+
+        let blocks: Vec<Value> = (0..150)
+            .map(|i| text_block("paragraph", &i.to_string()))
+            .collect();
+        let request = page_request("parent-id", "Weekly sync", &blocks);
+        assert_eq!(request["parent"]["page_id"], "parent-id");
+        assert_eq!(
+            request["properties"]["title"]["title"][0]["text"]["content"],
+            "Weekly sync"
+        );
+        assert_eq!(
+            request["children"].as_array().unwrap().len(),
+            MAX_BLOCKS_PER_REQUEST
+        );
+    }
+}
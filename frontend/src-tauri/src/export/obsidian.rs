@@ -0,0 +1,289 @@
+use crate::api::MeetingDetails;
+use crate::audio::meeting_folders::{sanitize_folder_name_for, FolderRules};
+use crate::summary::action_items::{parse_action_items, ActionItem};
+use crate::summary::table::is_table_line;
+use chrono::{DateTime, Local};
+
+/// Used when no filename pattern is configured
+pub const DEFAULT_FILENAME_PATTERN: &str = "{date} {title}";
+
+/// Owner and due values that mean "nobody said"
+const PLACEHOLDER_VALUES: &[&str] = &["", "-", "n/a", "na", "none", "tbd", "not specified"];
+
+/// Metadata written at the top of an exported note
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoteFrontmatter {
+    pub title: String,
+    /// YYYY-MM-DD, or the raw creation time if it couldn't be parsed
+    pub date: String,
+    pub participants: Vec<String>,
+    pub tags: Vec<String>,
+    pub action_items: usize,
+    pub source_meeting_id: String,
+}
+
+/// Double-quoted YAML scalar, safe for any title or name
+fn yaml_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04X}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn yaml_list(key: &str, values: &[String]) -> String {
+    if values.is_empty() {
+        return format!("{}: []\n", key);
+    }
+    let mut out = format!("{}:\n", key);
+    for value in values {
+        out.push_str(&format!("  - {}\n", yaml_string(value)));
+    }
+    out
+}
+
+fn is_iso_date(value: &str) -> bool {
+    chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok()
+}
+
+impl NoteFrontmatter {
+    /// YAML block including the `---` fences
+    ///
+    /// A parseable date is written bare so Obsidian treats it as a date
+    /// property; everything else is quoted.
+    pub fn to_yaml(&self) -> String {
+        let date = if is_iso_date(&self.date) {
+            self.date.clone()
+        } else {
+            yaml_string(&self.date)
+        };
+        let mut out = String::from("---\n");
+        out.push_str(&format!("title: {}\n", yaml_string(&self.title)));
+        out.push_str(&format!("date: {}\n", date));
+        out.push_str(&yaml_list("participants", &self.participants));
+        out.push_str(&yaml_list("tags", &self.tags));
+        out.push_str(&format!("action_items: {}\n", self.action_items));
+        out.push_str(&format!(
+            "source_meeting_id: {}\n",
+            yaml_string(&self.source_meeting_id)
+        ));
+        out.push_str("---\n");
+        out
+    }
+}
+
+fn is_placeholder(value: &str) -> bool {
+    PLACEHOLDER_VALUES.contains(&value.trim().to_lowercase().as_str())
+}
+
+/// Obsidian Tasks line for one action item:
+/// `- [ ] Task (@owner, due 2024-06-14)`
+pub fn task_line(item: &ActionItem) -> String {
+    let owner = item
+        .resolved_person()
+        .map(|person| person.display_name.as_str())
+        .unwrap_or(item.owner.as_str());
+    let mut details = Vec::new();
+    if !is_placeholder(owner) {
+        details.push(format!("@{}", owner.trim()));
+    }
+    if !is_placeholder(&item.due) {
+        details.push(format!("due {}", item.due.trim()));
+    }
+    let task = item.task.trim();
+    if details.is_empty() {
+        format!("- [ ] {}", task)
+    } else {
+        format!("- [ ] {} ({})", task, details.join(", "))
+    }
+}
+
+/// Replaces the Action Items table with one checkbox task per row
+///
+/// Everything else, including other tables, is left as is. Summaries without
+/// an Action Items table are returned unchanged.
+pub fn action_items_to_tasks(markdown: &str) -> String {
+    let items = parse_action_items(markdown);
+    if items.is_empty() {
+        return markdown.to_string();
+    }
+
+    let lines: Vec<&str> = markdown.lines().collect();
+    let Some(heading) = lines
+        .iter()
+        .position(|line| line.starts_with("##") && line.contains("Action Items"))
+    else {
+        return markdown.to_string();
+    };
+    let section_end = (heading + 1..lines.len())
+        .find(|&i| lines[i].starts_with("##"))
+        .unwrap_or(lines.len());
+    let Some(table_start) = (heading + 1..section_end).find(|&i| is_table_line(lines[i])) else {
+        return markdown.to_string();
+    };
+    let table_end = (table_start..section_end)
+        .find(|&i| !is_table_line(lines[i]))
+        .unwrap_or(section_end);
+
+    let mut output: Vec<String> = lines[..table_start].iter().map(|l| l.to_string()).collect();
+    output.extend(items.iter().map(task_line));
+    output.extend(lines[table_end..].iter().map(|l| l.to_string()));
+
+    let mut result = output.join("\n");
+    if markdown.ends_with('\n') {
+        result.push('\n');
+    }
+    result
+}
+
+/// Local calendar date of a meeting's creation time
+pub fn meeting_date(created_at: &str) -> String {
+    DateTime::parse_from_rfc3339(created_at)
+        .map(|time| time.with_timezone(&Local).format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|_| created_at.to_string())
+}
+
+/// Action item owners, in order of first appearance, as participants
+fn participants(items: &[ActionItem]) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for item in items {
+        let name = item
+            .resolved_person()
+            .map(|person| person.display_name.clone())
+            .unwrap_or_else(|| item.owner.trim().to_string());
+        if !is_placeholder(&name) && !names.iter().any(|n| n.eq_ignore_ascii_case(&name)) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// Renders a meeting summary as a note: frontmatter, title, then the summary
+/// with action items as tasks
+pub fn render_note(meeting: &MeetingDetails, summary_markdown: &str, tags: &[String]) -> String {
+    let items = parse_action_items(summary_markdown);
+    let frontmatter = NoteFrontmatter {
+        title: meeting.title.clone(),
+        date: meeting_date(&meeting.created_at),
+        participants: participants(&items),
+        tags: tags.to_vec(),
+        action_items: items.len(),
+        source_meeting_id: meeting.id.clone(),
+    };
+    format!(
+        "{}\n# {}\n\n{}\n",
+        frontmatter.to_yaml(),
+        meeting.title,
+        action_items_to_tasks(summary_markdown).trim()
+    )
+}
+
+/// File name for a note from a pattern with `{date}`, `{title}` and `{id}`
+/// placeholders
+///
+/// Windows naming rules are used everywhere: vaults are synced between
+/// machines and Obsidian rejects the same characters in note names.
+pub fn note_file_name(pattern: &str, meeting: &MeetingDetails) -> String {
+    let pattern = if pattern.trim().is_empty() {
+        DEFAULT_FILENAME_PATTERN
+    } else {
+        pattern
+    };
+    let name = pattern
+        .replace("{date}", &meeting_date(&meeting.created_at))
+        .replace("{title}", &meeting.title)
+        .replace("{id}", &meeting.id);
+    format!(
+        "{}.md",
+        sanitize_folder_name_for(&name, FolderRules::Windows)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meeting(title: &str) -> MeetingDetails {
+        MeetingDetails {
+            id: "meeting-42".to_string(),
+            title: title.to_string(),
+            created_at: "2024-06-10T12:00:00+00:00".to_string(),
+            updated_at: "2024-06-10T12:00:00+00:00".to_string(),
+            local_date: None,
+            transcripts: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_frontmatter_serialization() {
+        let frontmatter = NoteFrontmatter {
+            title: "Q3 \"launch\" sync: C:\\plans".to_string(),
+            date: "2024-06-10".to_string(),
+            participants: vec!["Sam Lee".to_string(), "Ana".to_string()],
+            tags: Vec::new(),
+            action_items: 2,
+            source_meeting_id: "meeting-42".to_string(),
+        };
+        assert_eq!(
+            frontmatter.to_yaml(),
+            "---\ntitle: \"Q3 \\\"launch\\\" sync: C:\\\\plans\"\ndate: 2024-06-10\nparticipants:\n  - \"Sam Lee\"\n  - \"Ana\"\ntags: []\naction_items: 2\nsource_meeting_id: \"meeting-42\"\n---\n"
+        );
+
+        let unparsed = NoteFrontmatter {
+            date: "yesterday".to_string(),
+            title: "Line\nbreak".to_string(),
+            ..frontmatter
+        };
+        let yaml = unparsed.to_yaml();
+        assert!(yaml.contains("date: \"yesterday\"\n"), "{}", yaml);
+        assert!(yaml.contains("title: \"Line\\nbreak\"\n"), "{}", yaml);
+    }
+
+    #[test]
+    fn test_action_items_table_becomes_tasks() {
+        let summary = "## Summary\n\nLaunch moved.\n\n## Action Items\n\n| **Owner** | **Task** | **Due** |\n|---|---|---|\n| Sam | Fix PROJ-404 webhook | 2024-06-14 |\n| Not specified | Update docs | TBD |\n| Ana | Review | |\n\n## Decisions\n\n| Decision | Owner |\n|---|---|\n| Ship Friday | Sam |\n";
+
+        let converted = action_items_to_tasks(summary);
+        assert_eq!(
+            converted,
+            "## Summary\n\nLaunch moved.\n\n## Action Items\n\n- [ ] Fix PROJ-404 webhook (@Sam, due 2024-06-14)\n- [ ] Update docs\n- [ ] Review (@Ana)\n\n## Decisions\n\n| Decision | Owner |\n|---|---|\n| Ship Friday | Sam |\n"
+        );
+        // No Action Items table: unchanged
+        assert_eq!(
+            action_items_to_tasks("## Summary\n\nNothing.\n"),
+            "## Summary\n\nNothing.\n"
+        );
+
+        let note = render_note(&meeting("Weekly sync"), summary, &["meeting".to_string()]);
+        assert!(
+            note.starts_with("---\ntitle: \"Weekly sync\"\n"),
+            "{}",
+            note
+        );
+        assert!(
+            note.contains("participants:\n  - \"Sam\"\n  - \"Ana\"\n"),
+            "{}",
+            note
+        );
+        assert!(note.contains("action_items: 3\n"), "{}", note);
+        assert!(
+            note.contains("---\n\n# Weekly sync\n\n## Summary"),
+            "{}",
+            note
+        );
+        assert_eq!(
+            note_file_name("{date} {title}", &meeting("Roadmap: Q3/Q4")),
+            "2024-06-10 Roadmap_ Q3_Q4.md"
+        );
+    }
+}
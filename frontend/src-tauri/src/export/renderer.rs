@@ -0,0 +1,106 @@
+use crate::api::MeetingDetails;
+use serde::Serialize;
+
+/// Output format for exported meetings
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Markdown,
+    Json,
+    Text,
+}
+
+impl ExportFormat {
+    /// Parse format from string (case-insensitive)
+    pub fn from_str(s: &str) -> Result<Self, String> {
+        match s.to_lowercase().as_str() {
+            "markdown" | "md" => Ok(Self::Markdown),
+            "json" => Ok(Self::Json),
+            "text" | "txt" => Ok(Self::Text),
+            _ => Err(format!("Unsupported export format: {}", s)),
+        }
+    }
+
+    /// File extension used for exported files
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Json => "json",
+            Self::Text => "txt",
+        }
+    }
+}
+
+/// Byte order mark some Windows tools (Excel, older editors) need to read a
+/// file as UTF-8 rather than the system code page
+pub const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Bytes of an exported file: UTF-8, with a BOM only when `bom` is set
+///
+/// A BOM already at the start of `text` is dropped first so it never ends up
+/// doubled or present when not asked for.
+pub fn encode_export(text: &str, bom: bool) -> Vec<u8> {
+    let text = text.strip_prefix('\u{FEFF}').unwrap_or(text);
+    let mut bytes = Vec::with_capacity(text.len() + UTF8_BOM.len());
+    if bom {
+        bytes.extend_from_slice(UTF8_BOM);
+    }
+    bytes.extend_from_slice(text.as_bytes());
+    bytes
+}
+
+/// Everything needed to render one meeting
+pub struct MeetingExport<'a> {
+    pub meeting: &'a MeetingDetails,
+    /// Summary markdown, if a summary has been generated
+    pub summary_markdown: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct JsonExport<'a> {
+    id: &'a str,
+    title: &'a str,
+    created_at: &'a str,
+    updated_at: &'a str,
+    summary: Option<&'a str>,
+    transcripts: &'a [crate::api::MeetingTranscript],
+}
+
+/// Renders a single meeting in the requested format
+pub fn render_meeting(export: &MeetingExport<'_>, format: ExportFormat) -> Result<String, String> {
+    let meeting = export.meeting;
+    match format {
+        ExportFormat::Markdown => {
+            let mut out = format!("# {}\n\n", meeting.title);
+            out.push_str(&format!("*Created: {}*\n\n", meeting.created_at));
+            if let Some(summary) = export.summary_markdown {
+                out.push_str(summary.trim());
+                out.push_str("\n\n");
+            }
+            out.push_str("## Transcript\n\n");
+            for segment in &meeting.transcripts {
+                out.push_str(&format!("**[{}]** {}\n\n", segment.timestamp, segment.text.trim()));
+            }
+            Ok(out)
+        }
+        ExportFormat::Json => serde_json::to_string_pretty(&JsonExport {
+            id: &meeting.id,
+            title: &meeting.title,
+            created_at: &meeting.created_at,
+            updated_at: &meeting.updated_at,
+            summary: export.summary_markdown,
+            transcripts: &meeting.transcripts,
+        })
+        .map_err(|e| format!("Failed to serialize meeting {}: {}", meeting.id, e)),
+        ExportFormat::Text => {
+            let mut out = format!("{}\n{}\n\n", meeting.title, meeting.created_at);
+            if let Some(summary) = export.summary_markdown {
+                out.push_str(summary.trim());
+                out.push_str("\n\n");
+            }
+            for segment in &meeting.transcripts {
+                out.push_str(&format!("[{}] {}\n", segment.timestamp, segment.text.trim()));
+            }
+            Ok(out)
+        }
+    }
+}
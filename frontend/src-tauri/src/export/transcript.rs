@@ -0,0 +1,257 @@
+//! Renders a meeting's transcript segments into a single document
+//!
+//! Used by `api_get_meeting_transcript_text` and when building the summary
+//! prompt, so the model sees the same text users can inspect.
+
+use crate::api::TranscriptSegment;
+use crate::utils::format_timestamp;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// "Sam Lee: ..." at the start of a segment; up to four capitalized words
+static SPEAKER_LABEL_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^([\p{Lu}\d][\w'.-]*(?: [\p{Lu}\d][\w'.-]*){0,3}):\s+").unwrap()
+});
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptTextFormat {
+    /// One line per segment (or merged run)
+    #[default]
+    Plain,
+    /// One paragraph per segment, timestamps and speakers in bold
+    Markdown,
+}
+
+/// How `render_transcript` lays out the segments
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TranscriptTextOptions {
+    /// Prefix each line with its recording time as HH:MM:SS
+    pub include_timestamps: bool,
+    /// Give the prefix as the line's start and end time, HH:MM:SS–HH:MM:SS
+    pub timestamp_ranges: bool,
+    /// Keep the "Name:" label segments start with; without it only the
+    /// spoken text is kept
+    pub include_speakers: bool,
+    /// Consecutive segments of the same speaker starting at most this many
+    /// seconds after the previous one ended are joined; None keeps every
+    /// segment separate
+    pub merge_gap_seconds: Option<f64>,
+    pub format: TranscriptTextFormat,
+}
+
+impl Default for TranscriptTextOptions {
+    fn default() -> Self {
+        Self {
+            include_timestamps: true,
+            timestamp_ranges: false,
+            include_speakers: true,
+            merge_gap_seconds: None,
+            format: TranscriptTextFormat::Plain,
+        }
+    }
+}
+
+impl TranscriptTextOptions {
+    /// Layout of the text summaries are generated from
+    ///
+    /// Segments stay unmerged so provenance can find each one's text, and
+    /// carry their time range so action items can cite it.
+    pub fn for_prompt() -> Self {
+        Self {
+            timestamp_ranges: true,
+            ..Self::default()
+        }
+    }
+}
+
+/// Splits a leading speaker label off a segment's text
+fn split_speaker(text: &str) -> (Option<&str>, &str) {
+    match SPEAKER_LABEL_RE.captures(text) {
+        Some(caps) => {
+            let label = caps.get(1).map(|m| m.as_str());
+            (label, &text[caps.get(0).map_or(0, |m| m.end())..])
+        }
+        None => (None, text),
+    }
+}
+
+/// Consecutive segments rendered as one line
+struct Run<'a> {
+    start: Option<f64>,
+    end: Option<f64>,
+    speaker: Option<&'a str>,
+    texts: Vec<&'a str>,
+}
+
+impl Run<'_> {
+    /// Whether a segment starting at `start` continues this run
+    fn continues(&self, speaker: Option<&str>, start: Option<f64>, max_gap: f64) -> bool {
+        if self.speaker != speaker {
+            return false;
+        }
+        match (self.end, start) {
+            (Some(end), Some(start)) => start - end <= max_gap,
+            _ => false,
+        }
+    }
+}
+
+/// Renders `segments`, in the order given, as one document
+pub fn render_transcript(
+    segments: &[TranscriptSegment],
+    options: &TranscriptTextOptions,
+) -> String {
+    let mut runs: Vec<Run> = Vec::new();
+    for segment in segments {
+        let (speaker, text) = split_speaker(segment.text.trim());
+        if text.is_empty() {
+            continue;
+        }
+        let end = segment.audio_end_time.or(segment.audio_start_time);
+        if let (Some(max_gap), Some(last)) = (options.merge_gap_seconds, runs.last_mut()) {
+            if last.continues(speaker, segment.audio_start_time, max_gap) {
+                last.texts.push(text);
+                last.end = end;
+                continue;
+            }
+        }
+        runs.push(Run {
+            start: segment.audio_start_time,
+            end,
+            speaker,
+            texts: vec![text],
+        });
+    }
+
+    let markdown = options.format == TranscriptTextFormat::Markdown;
+    let lines: Vec<String> = runs
+        .iter()
+        .map(|run| {
+            let mut line = String::new();
+            if let Some(start) = run.start.filter(|_| options.include_timestamps) {
+                let stamp = match run.end.filter(|_| options.timestamp_ranges) {
+                    Some(end) => format!("[{}–{}]", format_timestamp(start), format_timestamp(end)),
+                    None => format!("[{}]", format_timestamp(start)),
+                };
+                if markdown {
+                    line.push_str(&format!("**{}** ", stamp));
+                } else {
+                    line.push_str(&format!("{} ", stamp));
+                }
+            }
+            if let Some(speaker) = run.speaker.filter(|_| options.include_speakers) {
+                if markdown {
+                    line.push_str(&format!("**{}:** ", speaker));
+                } else {
+                    line.push_str(&format!("{}: ", speaker));
+                }
+            }
+            line.push_str(&run.texts.join(" "));
+            line
+        })
+        .collect();
+    lines.join(if markdown { "\n\n" } else { "\n" })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::{segment, untimed_segment};
+
+    fn meeting() -> Vec<TranscriptSegment> {
+        vec![
+            segment("s1", "Sam Lee: We ship the Stripe fix", 0.0, 4.0),
+            segment("s2", "Sam Lee: by Friday.", 5.5, 7.0),
+            segment("s3", "Ana: Who reviews it?", 8.0, 10.0),
+            segment("s4", "Ana: Maybe me.", 25.0, 26.0),
+            segment("s5", "  ", 27.0, 28.0),
+            untimed_segment("s6", "Note taken without timing"),
+            segment("s7", "Ana: Done", 3725.0, 3726.0),
+        ]
+    }
+
+    #[test]
+    fn test_render_options() {
+        let segments = meeting();
+
+        assert_eq!(
+            render_transcript(&segments, &TranscriptTextOptions::default()),
+            "[00:00:00] Sam Lee: We ship the Stripe fix\n\
+             [00:00:05] Sam Lee: by Friday.\n\
+             [00:00:08] Ana: Who reviews it?\n\
+             [00:00:25] Ana: Maybe me.\n\
+             Note taken without timing\n\
+             [01:02:05] Ana: Done"
+        );
+
+        let bare = TranscriptTextOptions {
+            include_timestamps: false,
+            include_speakers: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            render_transcript(&segments[..3], &bare),
+            "We ship the Stripe fix\nby Friday.\nWho reviews it?"
+        );
+
+        let markdown = TranscriptTextOptions {
+            format: TranscriptTextFormat::Markdown,
+            ..Default::default()
+        };
+        assert_eq!(
+            render_transcript(&segments[2..4], &markdown),
+            "**[00:00:08]** **Ana:** Who reviews it?\n\n**[00:00:25]** **Ana:** Maybe me."
+        );
+
+        // The summary prompt cites each line's time range
+        assert_eq!(
+            render_transcript(&segments[5..], &TranscriptTextOptions::for_prompt()),
+            "Note taken without timing\n[01:02:05–01:02:06] Ana: Done"
+        );
+
+        // Lowercase words before a colon are text, not a speaker
+        assert_eq!(
+            render_transcript(&[segment("s1", "so: we agreed", 0.0, 2.0)], &bare),
+            "so: we agreed"
+        );
+    }
+
+    #[test]
+    fn test_merge_gap() {
+        let segments = meeting();
+        let merged = |gap: f64| TranscriptTextOptions {
+            merge_gap_seconds: Some(gap),
+            ..Default::default()
+        };
+
+        // 1.5s pause within Sam's turn is merged; the speaker change is not
+        assert_eq!(
+            render_transcript(&segments[..4], &merged(2.0)),
+            "[00:00:00] Sam Lee: We ship the Stripe fix by Friday.\n\
+             [00:00:08] Ana: Who reviews it?\n\
+             [00:00:25] Ana: Maybe me."
+        );
+        // A longer threshold bridges Ana's 15s pause too
+        assert_eq!(
+            render_transcript(&segments[2..4], &merged(15.0)),
+            "[00:00:08] Ana: Who reviews it? Maybe me."
+        );
+        // Below the pause nothing is merged
+        assert_eq!(
+            render_transcript(&segments[..2], &merged(1.0)),
+            render_transcript(&segments[..2], &TranscriptTextOptions::default())
+        );
+        // Segments without timing are never merged
+        let untimed = [
+            untimed_segment("n1", "First note"),
+            untimed_segment("n2", "Second note"),
+        ];
+        assert_eq!(
+            render_transcript(&untimed, &merged(10_000.0)),
+            "First note\nSecond note"
+        );
+    }
+}
@@ -0,0 +1,645 @@
+use crate::export::config::local_secret_key;
+use crate::settings_bundle::crypto::{decrypt_local, encrypt_local, EncryptedSecrets};
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use reqwest::{Client, ClientBuilder, Proxy, Url};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use std::net::IpAddr;
+use std::sync::RwLock;
+use std::time::Duration;
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+
+const CONFIG_STORE: &str = "proxy.json";
+const CONFIG_KEY: &str = "config";
+
+/// Proxy all outbound requests go through, except the excluded hosts
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProxyConfig {
+    /// e.g. `http://proxy.corp.example:3128`; None connects directly
+    pub proxy_url: Option<String>,
+    pub username: Option<String>,
+    /// Proxy password, encrypted; never returned to the frontend
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<EncryptedSecrets>,
+    /// Hosts reached directly, NO_PROXY style: `corp.example` and
+    /// `.corp.example` also cover subdomains, `*` covers everything.
+    /// Loopback addresses are always reached directly.
+    pub no_proxy: Vec<String>,
+}
+
+/// The config as shown to the frontend, with the password reduced to a flag
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProxySettings {
+    pub proxy_url: Option<String>,
+    pub username: Option<String>,
+    pub no_proxy: Vec<String>,
+    #[serde(default)]
+    pub has_password: bool,
+}
+
+impl From<&ProxyConfig> for ProxySettings {
+    fn from(config: &ProxyConfig) -> Self {
+        Self {
+            proxy_url: config.proxy_url.clone(),
+            username: config.username.clone(),
+            no_proxy: config.no_proxy.clone(),
+            has_password: config.password.is_some(),
+        }
+    }
+}
+
+/// The proxy in use, with the password decrypted
+#[derive(Clone)]
+pub struct ActiveProxy {
+    pub url: Url,
+    pub credentials: Option<(String, String)>,
+    pub no_proxy: Vec<String>,
+}
+
+impl fmt::Debug for ActiveProxy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ActiveProxy")
+            .field("url", &self.url.as_str())
+            .field("username", &self.credentials.as_ref().map(|(user, _)| user))
+            .field("no_proxy", &self.no_proxy)
+            .finish()
+    }
+}
+
+impl ActiveProxy {
+    /// Checks the URL and decrypts the password of `config`; None when no
+    /// proxy is configured
+    pub fn from_config(config: &ProxyConfig, key: &str) -> Result<Option<Self>, String> {
+        let Some(proxy_url) = config
+            .proxy_url
+            .as_deref()
+            .map(str::trim)
+            .filter(|u| !u.is_empty())
+        else {
+            return Ok(None);
+        };
+        let url = Url::parse(proxy_url)
+            .map_err(|e| format!("Invalid proxy URL '{}': {}", proxy_url, e))?;
+        if url.scheme() != "http" && url.scheme() != "https" {
+            return Err(format!("Proxy URL '{}' must use http or https", proxy_url));
+        }
+        let password = match &config.password {
+            Some(secrets) => {
+                let bytes = decrypt_local(secrets, key).map_err(|_| {
+                    "Stored proxy password can't be decrypted; enter it again".to_string()
+                })?;
+                String::from_utf8(bytes)
+                    .map_err(|_| "Stored proxy password is corrupted".to_string())?
+            }
+            None => String::new(),
+        };
+        let credentials = config
+            .username
+            .as_deref()
+            .filter(|user| !user.is_empty())
+            .map(|user| (user.to_string(), password));
+        Ok(Some(Self {
+            url,
+            credentials,
+            no_proxy: config.no_proxy.clone(),
+        }))
+    }
+}
+
+static ACTIVE_PROXY: Lazy<RwLock<Option<ActiveProxy>>> = Lazy::new(|| RwLock::new(None));
+
+/// Hosts of the Ollama endpoints in use, which never go through the proxy
+static DIRECT_HOSTS: Lazy<RwLock<HashSet<String>>> = Lazy::new(|| RwLock::new(HashSet::new()));
+
+/// Whether `host` is a loopback address, which never goes through the proxy
+fn is_loopback(host: &str) -> bool {
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    if host.eq_ignore_ascii_case("localhost") || host.to_ascii_lowercase().ends_with(".localhost") {
+        return true;
+    }
+    host.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+/// Whether `host` matches a NO_PROXY style exclusion list
+///
+/// Entries may carry a port, which is ignored; `corp.example` and
+/// `.corp.example` match the domain and its subdomains.
+pub fn is_excluded(host: &str, no_proxy: &[String]) -> bool {
+    if is_loopback(host) {
+        return true;
+    }
+    let host = host
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_ascii_lowercase();
+    no_proxy.iter().any(|entry| {
+        let entry = entry.trim().to_ascii_lowercase();
+        if entry == "*" {
+            return true;
+        }
+        // "[::1]:8080", "corp.example:443"
+        let entry = match entry.strip_prefix('[') {
+            Some(rest) => rest.split(']').next().unwrap_or_default().to_string(),
+            None if entry.matches(':').count() == 1 => {
+                entry.split(':').next().unwrap_or_default().to_string()
+            }
+            None => entry,
+        };
+        let domain = entry.trim_start_matches("*.").trim_start_matches('.');
+        !domain.is_empty() && (host == domain || host.ends_with(&format!(".{}", domain)))
+    })
+}
+
+/// Where a request to `url` goes: the proxy URL, or None to connect directly
+fn route(url: &Url, proxy: &ActiveProxy, direct_hosts: &HashSet<String>) -> Option<Url> {
+    let host = url.host_str().unwrap_or_default().to_ascii_lowercase();
+    if direct_hosts.contains(&host) || is_excluded(&host, &proxy.no_proxy) {
+        return None;
+    }
+    Some(proxy.url.clone())
+}
+
+/// Makes requests to the host of `endpoint` bypass the proxy
+///
+/// Called with every Ollama endpoint, so local models are never reached
+/// through a corporate proxy even when they run on another machine.
+pub fn bypass_proxy_for(endpoint: &str) {
+    let Some(host) = Url::parse(endpoint)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_ascii_lowercase))
+    else {
+        return;
+    };
+    if let Ok(mut hosts) = DIRECT_HOSTS.write() {
+        hosts.insert(host);
+    }
+}
+
+fn active_proxy() -> Option<ActiveProxy> {
+    ACTIVE_PROXY.read().ok().and_then(|proxy| proxy.clone())
+}
+
+fn set_active_proxy(proxy: Option<ActiveProxy>) {
+    if let Ok(mut active) = ACTIVE_PROXY.write() {
+        *active = proxy;
+    }
+}
+
+/// The configured proxy as a reqwest `Proxy`, deciding per request
+fn reqwest_proxy(proxy: ActiveProxy) -> Proxy {
+    let credentials = proxy.credentials.clone();
+    let custom = Proxy::custom(move |url| {
+        let direct_hosts = DIRECT_HOSTS.read().ok();
+        let none = HashSet::new();
+        route(url, &proxy, direct_hosts.as_deref().unwrap_or(&none))
+    });
+    match credentials {
+        Some((user, password)) => custom.basic_auth(&user, &password),
+        None => custom,
+    }
+}
+
+/// Client builder for outbound requests, going through the configured proxy
+///
+/// Without a configured proxy the system proxy settings apply as before.
+pub fn builder() -> ClientBuilder {
+    match active_proxy() {
+        Some(proxy) => Client::builder().proxy(reqwest_proxy(proxy)),
+        None => Client::builder(),
+    }
+}
+
+/// Blocking counterpart of `builder`
+pub fn blocking_builder() -> reqwest::blocking::ClientBuilder {
+    match active_proxy() {
+        Some(proxy) => reqwest::blocking::Client::builder().proxy(reqwest_proxy(proxy)),
+        None => reqwest::blocking::Client::builder(),
+    }
+}
+
+/// Client for outbound requests without a timeout
+pub fn client() -> Client {
+    builder().build().unwrap_or_else(|e| {
+        warn!(
+            "⚠️ Failed to build HTTP client, connecting without a proxy: {}",
+            e
+        );
+        Client::builder().no_proxy().build().unwrap_or_default()
+    })
+}
+
+/// Blocking client for outbound requests without a timeout
+pub fn blocking_client() -> reqwest::blocking::Client {
+    blocking_builder().build().unwrap_or_else(|e| {
+        warn!(
+            "⚠️ Failed to build HTTP client, connecting without a proxy: {}",
+            e
+        );
+        reqwest::blocking::Client::new()
+    })
+}
+
+/// Client that never uses a proxy, for Ollama servers
+pub fn direct_client() -> Client {
+    Client::builder().no_proxy().build().unwrap_or_default()
+}
+
+/// Builders tried in order by `http_client`; each one keeps the timeout
+fn client_builders(timeout: Duration) -> [ClientBuilder; 2] {
+    [
+        builder().timeout(timeout),
+        // Reading the system proxy settings is what usually fails
+        Client::builder().no_proxy().timeout(timeout),
+    ]
+}
+
+/// HTTP client whose requests give up after `timeout`
+///
+/// A client that can't be built is logged and replaced by a simpler one with
+/// the same timeout, never by `Client::new()`, which would wait forever on a
+/// provider that stops answering. Fails only if every builder fails.
+pub fn http_client(timeout: Duration) -> Result<Client, String> {
+    let mut errors = Vec::new();
+    for builder in client_builders(timeout) {
+        match builder.build() {
+            Ok(client) => return Ok(client),
+            Err(e) => {
+                warn!(
+                    "⚠️ Failed to build HTTP client, trying a simpler one: {}",
+                    e
+                );
+                errors.push(e.to_string());
+            }
+        }
+    }
+    Err(format!(
+        "Failed to build an HTTP client: {}",
+        errors.join("; ")
+    ))
+}
+
+/// Why a request through the proxy failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProxyFailure {
+    /// The proxy or the target didn't answer in time
+    Timeout,
+    /// No connection to the proxy or the target
+    Connect,
+    /// The proxy rejected the credentials (HTTP 407)
+    ProxyAuthentication,
+    /// The target answered with an error status
+    Status,
+    Other,
+}
+
+/// Outcome of a test request through the configured proxy
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProxyTestResult {
+    pub url: String,
+    /// A proxy was configured and the URL isn't excluded from it
+    pub via_proxy: bool,
+    pub ok: bool,
+    pub latency_ms: u64,
+    pub status: Option<u16>,
+    pub failure: Option<ProxyFailure>,
+    pub error: Option<String>,
+}
+
+fn classify_error(error: &reqwest::Error) -> ProxyFailure {
+    if error.is_timeout() {
+        ProxyFailure::Timeout
+    } else if error.is_connect() {
+        ProxyFailure::Connect
+    } else {
+        ProxyFailure::Other
+    }
+}
+
+/// Fetches `url` with `client` and reports the latency or why it failed
+pub async fn check_connection(client: &Client, url: &str, via_proxy: bool) -> ProxyTestResult {
+    let started = std::time::Instant::now();
+    let response = client.get(url).send().await;
+    let latency_ms = started.elapsed().as_millis() as u64;
+    let (status, failure, error) = match response {
+        Ok(response) if response.status().is_success() => {
+            (Some(response.status().as_u16()), None, None)
+        }
+        Ok(response) => {
+            let status = response.status();
+            let failure = if status == reqwest::StatusCode::PROXY_AUTHENTICATION_REQUIRED {
+                ProxyFailure::ProxyAuthentication
+            } else {
+                ProxyFailure::Status
+            };
+            (
+                Some(status.as_u16()),
+                Some(failure),
+                Some(format!("HTTP {}", status)),
+            )
+        }
+        Err(e) => (None, Some(classify_error(&e)), Some(e.to_string())),
+    };
+    ProxyTestResult {
+        url: url.to_string(),
+        via_proxy,
+        ok: failure.is_none(),
+        latency_ms,
+        status,
+        failure,
+        error,
+    }
+}
+
+/// Whether a request to `url` would go through the configured proxy
+pub fn uses_proxy(url: &str) -> bool {
+    let (Some(proxy), Ok(url)) = (active_proxy(), Url::parse(url)) else {
+        return false;
+    };
+    let direct_hosts = DIRECT_HOSTS.read().ok();
+    let none = HashSet::new();
+    route(&url, &proxy, direct_hosts.as_deref().unwrap_or(&none)).is_some()
+}
+
+pub fn load_proxy_config<R: Runtime>(app: &AppHandle<R>) -> ProxyConfig {
+    let stored = app
+        .store(CONFIG_STORE)
+        .ok()
+        .and_then(|store| store.get(CONFIG_KEY));
+    match stored.map(serde_json::from_value::<ProxyConfig>) {
+        Some(Ok(config)) => config,
+        Some(Err(e)) => {
+            warn!(
+                "⚠️ Invalid proxy config in store, connecting directly: {}",
+                e
+            );
+            ProxyConfig::default()
+        }
+        None => ProxyConfig::default(),
+    }
+}
+
+pub fn save_proxy_config<R: Runtime>(
+    app: &AppHandle<R>,
+    config: &ProxyConfig,
+) -> Result<(), String> {
+    let store = app
+        .store(CONFIG_STORE)
+        .map_err(|e| format!("Failed to open proxy store: {}", e))?;
+    let value = serde_json::to_value(config)
+        .map_err(|e| format!("Failed to serialize proxy config: {}", e))?;
+    store.set(CONFIG_KEY, value);
+    store
+        .save()
+        .map_err(|e| format!("Failed to save proxy config: {}", e))
+}
+
+pub fn encrypt_proxy_password<R: Runtime>(
+    app: &AppHandle<R>,
+    password: &str,
+) -> Result<EncryptedSecrets, String> {
+    encrypt_local(password.as_bytes(), &local_secret_key(app)?)
+}
+
+/// Checks `config` and makes clients built from now on use it
+pub fn apply_proxy_config<R: Runtime>(
+    app: &AppHandle<R>,
+    config: &ProxyConfig,
+) -> Result<(), String> {
+    let key = match config.password {
+        Some(_) => local_secret_key(app)?,
+        None => String::new(),
+    };
+    let proxy = ActiveProxy::from_config(config, &key)?;
+    match &proxy {
+        Some(proxy) => info!("🌐 Outbound requests use proxy: {:?}", proxy),
+        None => info!("🌐 Outbound requests connect directly"),
+    }
+    set_active_proxy(proxy);
+    Ok(())
+}
+
+/// Loads the saved proxy so clients built from now on use it
+pub fn init_proxy<R: Runtime>(app: &AppHandle<R>) {
+    let config = load_proxy_config(app);
+    if config.proxy_url.is_none() {
+        return;
+    }
+    if let Err(e) = apply_proxy_config(app, &config) {
+        warn!("⚠️ Proxy not applied, connecting directly: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proxy(no_proxy: &[&str]) -> ActiveProxy {
+        ActiveProxy {
+            url: Url::parse("http://proxy.corp.example:3128").unwrap(),
+            credentials: Some(("alex".to_string(), "hunter2".to_string())),
+            no_proxy: no_proxy.iter().map(|entry| entry.to_string()).collect(),
+        }
+    }
+
+    fn url(url: &str) -> Url {
+        Url::parse(url).unwrap()
+    }
+
+    #[test]
+    fn test_exclusions() {
+        let no_proxy: Vec<String> = [
+            "corp.example",
+            ".internal.test",
+            "10.0.0.5:8080",
+            "[fd00::1]",
+        ]
+        .iter()
+        .map(|entry| entry.to_string())
+        .collect();
+
+        for host in [
+            "localhost",
+            "app.localhost",
+            "127.0.0.1",
+            "127.8.0.1",
+            "::1",
+            "[::1]",
+        ] {
+            assert!(is_excluded(host, &[]), "{}", host);
+        }
+        for host in [
+            "corp.example",
+            "git.corp.example",
+            "internal.test",
+            "a.internal.test",
+            "10.0.0.5",
+            "fd00::1",
+        ] {
+            assert!(is_excluded(host, &no_proxy), "{}", host);
+        }
+        for host in [
+            "api.openai.com",
+            "notcorp.example",
+            "corp.example.com",
+            "10.0.0.50",
+        ] {
+            assert!(!is_excluded(host, &no_proxy), "{}", host);
+        }
+        assert!(is_excluded("api.openai.com", &["*".to_string()]));
+    }
+
+    #[test]
+    fn test_routes_skip_the_proxy_for_ollama_and_local_hosts() {
+        let proxy = proxy(&["corp.example"]);
+        let proxied = Some(url("http://proxy.corp.example:3128"));
+        let direct_hosts: HashSet<String> = ["gpu-box".to_string()].into_iter().collect();
+
+        assert_eq!(
+            route(
+                &url("https://api.openai.com/v1/chat/completions"),
+                &proxy,
+                &direct_hosts
+            ),
+            proxied
+        );
+        assert_eq!(
+            route(
+                &url("https://wiki.corp.example/page"),
+                &proxy,
+                &direct_hosts
+            ),
+            None
+        );
+        // The Python backend and local Ollama
+        assert_eq!(
+            route(&url("http://localhost:5167/docs"), &proxy, &direct_hosts),
+            None
+        );
+        assert_eq!(
+            route(
+                &url("http://127.0.0.1:11434/api/tags"),
+                &proxy,
+                &direct_hosts
+            ),
+            None
+        );
+        // A remote Ollama endpoint once it is registered
+        assert_eq!(
+            route(
+                &url("http://gpu-box:11434/v1/chat/completions"),
+                &proxy,
+                &direct_hosts
+            ),
+            None
+        );
+        assert_eq!(
+            route(
+                &url("http://gpu-box:11434/api/tags"),
+                &proxy,
+                &HashSet::new()
+            ),
+            proxied
+        );
+
+        bypass_proxy_for("http://GPU-Box.lan:11434");
+        assert!(DIRECT_HOSTS.read().unwrap().contains("gpu-box.lan"));
+    }
+
+    #[test]
+    fn test_proxy_password_stays_encrypted() {
+        let key = "test-local-key";
+        let config = ProxyConfig {
+            proxy_url: Some("http://proxy.corp.example:3128".to_string()),
+            username: Some("alex".to_string()),
+            password: Some(encrypt_local(b"hunter2", key).unwrap()),
+            no_proxy: Vec::new(),
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(!json.contains("hunter2"));
+        assert!(!serde_json::to_string(&ProxySettings::from(&config))
+            .unwrap()
+            .contains("hunter2"));
+
+        let active = ActiveProxy::from_config(&config, key).unwrap().unwrap();
+        assert_eq!(
+            active.credentials,
+            Some(("alex".to_string(), "hunter2".to_string()))
+        );
+        assert!(!format!("{:?}", active).contains("hunter2"));
+        assert!(ActiveProxy::from_config(&config, "wrong-key").is_err());
+
+        let invalid = ProxyConfig {
+            proxy_url: Some("proxy.corp.example".to_string()),
+            ..ProxyConfig::default()
+        };
+        assert!(ActiveProxy::from_config(&invalid, key).is_err());
+        assert!(ActiveProxy::from_config(&ProxyConfig::default(), key)
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_proxy_rejecting_credentials_is_reported() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 4096];
+            let n = socket.read(&mut buf).await.unwrap();
+            socket
+                .write_all(b"HTTP/1.1 407 Proxy Authentication Required\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&buf[..n]).to_string()
+        });
+
+        let active = ActiveProxy {
+            url: Url::parse(&proxy_url).unwrap(),
+            credentials: Some(("alex".to_string(), "wrong".to_string())),
+            no_proxy: Vec::new(),
+        };
+        let client = Client::builder()
+            .proxy(reqwest_proxy(active))
+            .timeout(Duration::from_secs(5))
+            .build()
+            .unwrap();
+        let result = check_connection(&client, "http://example.invalid/generate_204", true).await;
+
+        assert!(!result.ok);
+        assert_eq!(result.status, Some(407));
+        assert_eq!(result.failure, Some(ProxyFailure::ProxyAuthentication));
+        let request = server.await.unwrap();
+        assert!(
+            request.starts_with("GET http://example.invalid/generate_204"),
+            "{}",
+            request
+        );
+        assert!(request
+            .to_ascii_lowercase()
+            .contains("proxy-authorization: basic"));
+    }
+
+    #[tokio::test]
+    async fn test_http_clients_time_out() {
+        // Connections queue up in the backlog but are never answered
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let timeout = Duration::from_millis(200);
+
+        let fallbacks = client_builders(timeout).map(|builder| builder.build().unwrap());
+        for client in std::iter::once(http_client(timeout).unwrap()).chain(fallbacks) {
+            let started = std::time::Instant::now();
+            let error = client.get(&url).send().await.unwrap_err();
+            assert!(error.is_timeout(), "{}", error);
+            assert!(started.elapsed() < Duration::from_secs(5));
+        }
+        drop(listener);
+    }
+}
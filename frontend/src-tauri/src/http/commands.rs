@@ -0,0 +1,83 @@
+use super::client_factory::{
+    apply_proxy_config, builder, check_connection, encrypt_proxy_password, load_proxy_config,
+    save_proxy_config, uses_proxy, ProxySettings, ProxyTestResult,
+};
+use log::{info as log_info, warn as log_warn};
+use std::time::Duration;
+use tauri::{AppHandle, Runtime};
+
+/// Answers with an empty 204, so only reachability is measured
+const PROXY_TEST_URL: &str = "https://www.gstatic.com/generate_204";
+const PROXY_TEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+#[tauri::command]
+pub async fn api_get_proxy_settings<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<ProxySettings, String> {
+    Ok(ProxySettings::from(&load_proxy_config(&app)))
+}
+
+/// Saves and applies the proxy settings
+///
+/// `password` replaces the stored password; an empty string removes it and
+/// None keeps it. Clients built after this use the new settings.
+#[tauri::command]
+pub async fn api_save_proxy_settings<R: Runtime>(
+    app: AppHandle<R>,
+    settings: ProxySettings,
+    password: Option<String>,
+) -> Result<ProxySettings, String> {
+    log_info!("api_save_proxy_settings called");
+    let mut config = load_proxy_config(&app);
+    config.proxy_url = settings
+        .proxy_url
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty());
+    config.username = settings
+        .username
+        .map(|user| user.trim().to_string())
+        .filter(|user| !user.is_empty());
+    config.no_proxy = settings
+        .no_proxy
+        .into_iter()
+        .map(|entry| entry.trim().to_string())
+        .filter(|entry| !entry.is_empty())
+        .collect();
+    match password.as_deref() {
+        Some("") => config.password = None,
+        Some(password) => config.password = Some(encrypt_proxy_password(&app, password)?),
+        None => {}
+    }
+    // Checked before saving so a broken proxy URL never reaches the store
+    apply_proxy_config(&app, &config)?;
+    save_proxy_config(&app, &config)?;
+    Ok(ProxySettings::from(&config))
+}
+
+/// Fetches a known URL through the configured proxy and reports the latency
+/// or the kind of failure
+#[tauri::command]
+pub async fn api_test_proxy<R: Runtime>(_app: AppHandle<R>) -> Result<ProxyTestResult, String> {
+    log_info!("api_test_proxy called");
+    let client = builder()
+        .timeout(PROXY_TEST_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let result = check_connection(&client, PROXY_TEST_URL, uses_proxy(PROXY_TEST_URL)).await;
+    if result.ok {
+        log_info!(
+            "✅ Proxy test reached {} in {}ms (via proxy: {})",
+            result.url,
+            result.latency_ms,
+            result.via_proxy
+        );
+    } else {
+        log_warn!(
+            "⚠️ Proxy test to {} failed ({:?}): {}",
+            result.url,
+            result.failure,
+            result.error.as_deref().unwrap_or_default()
+        );
+    }
+    Ok(result)
+}
@@ -0,0 +1,13 @@
+/// HTTP module - construction of the clients used for outbound requests
+///
+/// This module contains:
+/// - The client factory every outbound client is built with, applying the
+///   configured proxy and its exclusions (loopback and Ollama hosts always
+///   connect directly)
+/// - Tauri commands for the proxy settings and a test request through them
+
+pub mod client_factory;
+pub mod commands;
+
+#[cfg(test)]
+pub(crate) mod test_server;
@@ -0,0 +1,109 @@
+// http/test_server.rs
+//
+// Minimal HTTP server for tests of outbound requests (providers, webhooks,
+// the backend API). Every connection gets one response and is closed.
+
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Raw requests a test server received, in arrival order
+pub type Requests = Arc<Mutex<Vec<String>>>;
+
+/// Reads one request; the headers and body may arrive in separate reads
+pub async fn read_request(socket: &mut TcpStream) -> String {
+    let mut received = Vec::new();
+    let mut buf = vec![0u8; 16384];
+    loop {
+        let n = socket.read(&mut buf).await.unwrap_or(0);
+        received.extend_from_slice(&buf[..n]);
+        let text = String::from_utf8_lossy(&received).to_string();
+        let complete = text.split_once("\r\n\r\n").is_some_and(|(head, body)| {
+            let length = head
+                .lines()
+                .find_map(|line| {
+                    let (name, value) = line.split_once(':')?;
+                    name.eq_ignore_ascii_case("content-length")
+                        .then(|| value.trim().parse::<usize>().ok())?
+                })
+                .unwrap_or(0);
+            body.len() >= length
+        });
+        if n == 0 || complete {
+            return text;
+        }
+    }
+}
+
+/// A complete JSON response with `status` and `body`
+pub fn json_response(status: u16, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )
+}
+
+/// Answers every request with the full response `respond` builds from it and
+/// records the requests; returns the server's base URL
+pub async fn serve<F>(respond: F) -> (String, Requests)
+where
+    F: Fn(&str) -> String + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Requests::default();
+    let recorded = requests.clone();
+    let respond = Arc::new(respond);
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let recorded = recorded.clone();
+            let respond = respond.clone();
+            tokio::spawn(async move {
+                let request = read_request(&mut socket).await;
+                let response = respond(&request);
+                recorded.lock().unwrap().push(request);
+                let _ = socket.write_all(response.as_bytes()).await;
+            });
+        }
+    });
+    (url, requests)
+}
+
+/// Answers every request with `status` and `body`
+pub async fn mock_server(status: u16, body: impl Into<String>) -> (String, Requests) {
+    let body = body.into();
+    serve(move |_| json_response(status, &body)).await
+}
+
+/// Accepts connections but never answers
+pub async fn silent_server() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        let mut held = Vec::new();
+        while let Ok((socket, _)) = listener.accept().await {
+            held.push(socket);
+        }
+    });
+    url
+}
+
+/// A URL nothing is listening on
+pub async fn closed_url() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+    url
+}
+
+/// First line of each recorded request, e.g. `GET /api/tags HTTP/1.1`
+pub fn request_lines(requests: &Requests) -> Vec<String> {
+    requests
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|request| request.lines().next().unwrap_or_default().to_string())
+        .collect()
+}
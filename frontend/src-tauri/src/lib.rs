@@ -40,14 +40,23 @@ pub mod api;
 pub mod audio;
 pub mod console_utils;
 pub mod database;
+pub mod export;
+pub mod http;
+pub mod logging;
 pub mod notifications;
 pub mod ollama;
 pub mod openrouter;
 pub mod parakeet_engine;
+pub mod people;
+pub mod retention;
+pub mod settings_bundle;
+pub mod setup;
 pub mod state;
 pub mod summary;
+pub mod timezone;
 pub mod tray;
 pub mod utils;
+pub mod webhooks;
 pub mod whisper_engine;
 
 use audio::{list_audio_devices, AudioDevice};
@@ -494,6 +503,15 @@ pub fn run() {
                 log::warn!("Failed to resolve resource directory for templates");
             }
 
+            logging::apply_logging_config(_app.handle());
+            http::client_factory::init_proxy(_app.handle());
+            summary::question_suppression::init_question_suppression(_app.handle());
+            summary::provider_overrides::init_provider_overrides(_app.handle());
+            retention::policy::start_retention_task(_app.handle());
+            retention::summary_processes::start_summary_process_cleanup(_app.handle());
+            summary::templates::remote::init_remote_templates(_app.handle());
+            setup::self_check::start_startup_self_check(_app.handle());
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -579,6 +597,7 @@ pub fn run() {
             audio::recording_commands::toggle_microphone_mute,
             audio::recording_commands::is_microphone_muted,
             audio::recording_commands::get_recording_state,
+            audio::recording_commands::api_get_recording_status,
             audio::recording_commands::get_meeting_folder_path,
             // Reload sync commands (retrieve transcript history and meeting name)
             audio::recording_commands::get_transcript_history,
@@ -597,7 +616,9 @@ pub fn run() {
             ollama::delete_ollama_model,
             ollama::get_ollama_model_context,
             api::api_get_meetings,
+            api::api_get_meetings_overview,
             api::api_search_transcripts,
+            api::api_search_summaries,
             api::api_get_profile,
             api::api_save_profile,
             api::api_update_profile,
@@ -608,20 +629,55 @@ pub fn run() {
             // api::api_save_auto_generate_setting,
             api::api_get_transcript_config,
             api::api_save_transcript_config,
+            api::api_get_whisper_model_info,
             api::api_get_transcript_api_key,
             api::api_delete_meeting,
             api::api_get_meeting,
+            api::api_get_meeting_transcript_text,
             api::api_save_meeting_title,
+            api::api_get_meeting_preferences,
+            api::api_set_meeting_preferences,
             api::api_save_transcript,
+            api::api_get_transcription_status,
             api::open_meeting_folder,
+            api::api_open_meeting_artifact,
+            api::api_benchmark_transcription,
+            api::api_check_recording_audio,
+            api::api_repair_meeting_folders,
+            api::api_get_meeting_folder_state,
+            api::api_relocate_meeting_folder,
+            api::api_rebuild_transcript_chunks,
+            export::commands::api_export_meetings,
+            export::commands::api_export_summary_note,
+            export::commands::api_export_summary_notion,
+            export::commands::api_get_note_export_config,
+            export::commands::api_save_note_export_config,
+            settings_bundle::commands::api_export_settings,
+            settings_bundle::commands::api_import_settings,
+            setup::commands::api_setup_status,
+            setup::commands::api_setup_detect_ollama,
+            setup::commands::api_setup_validate_provider,
+            setup::commands::api_setup_check_audio,
+            setup::commands::api_setup_complete,
+            setup::commands::api_get_startup_notices,
+            setup::commands::api_dismiss_startup_notice,
+            people::commands::api_list_people,
+            people::commands::api_create_person,
+            people::commands::api_update_person,
+            people::commands::api_delete_person,
+            people::commands::api_resolve_owners,
+            people::commands::api_get_meeting_participants,
             api::test_backend_connection,
             api::debug_backend_connection,
             api::open_external_url,
             // Jira commands
+            api::api_jira_health,
             api::api_save_jira_config,
             api::api_get_jira_config,
             api::api_create_jira_task,
+            api::api_bulk_create_jira_tasks,
             api::api_analyze_jira_tasks,
+            api::api_get_jira_analysis_status,
             api::api_get_jira_projects,
             api::api_get_jira_issue_types,
             api::api_get_jira_project_context,
@@ -635,17 +691,80 @@ pub fn run() {
             api::api_get_extension_status,
             api::api_send_to_chat,
             api::api_send_questions_to_chat,
+            api::api_confirm_question_delivered,
+            api::api_get_question_delivery_config,
+            api::api_set_question_delivery_config,
+            api::api_get_response_limits,
+            api::api_set_response_limits,
             api::api_generate_clarifying_questions,
             api::api_ping_extensions,
             // Summary commands
             summary::api_process_transcript,
             summary::api_get_summary,
             summary::api_save_meeting_summary,
+            summary::commands::api_list_pipelines,
+            summary::commands::api_create_pipeline,
+            summary::commands::api_update_pipeline,
+            summary::commands::api_delete_pipeline,
+            summary::commands::api_get_live_summary,
+            summary::commands::api_get_live_summary_config,
+            summary::commands::api_set_live_summary_config,
+            summary::commands::api_get_question_suppression,
+            summary::commands::api_set_question_suppression,
+            summary::commands::api_get_provider_overrides,
+            summary::commands::api_set_provider_override,
+            summary::commands::api_test_provider_override,
+            summary::commands::api_test_llm_provider,
+            http::commands::api_get_proxy_settings,
+            http::commands::api_save_proxy_settings,
+            http::commands::api_test_proxy,
+            logging::api_get_logging_config,
+            logging::api_set_logging_config,
+            logging::api_get_log_tail,
+            timezone::api_get_timezone_config,
+            timezone::api_set_timezone_config,
+            retention::commands::api_get_retention_config,
+            retention::commands::api_set_retention_config,
+            retention::commands::api_preview_retention,
+            retention::commands::api_run_retention,
+            retention::commands::api_get_summary_process_cleanup_config,
+            retention::commands::api_set_summary_process_cleanup_config,
+            retention::commands::api_run_summary_process_cleanup,
+            webhooks::commands::api_get_webhooks,
+            webhooks::commands::api_save_webhook,
+            webhooks::commands::api_delete_webhook,
+            webhooks::commands::api_list_webhook_deliveries,
+            webhooks::commands::api_redeliver_webhook,
+            summary::commands::api_get_content_filter,
+            summary::commands::api_save_content_filter,
+            summary::commands::api_get_summary_pipeline_config,
+            summary::commands::api_save_summary_pipeline_config,
+            summary::commands::api_get_question_prompt_config,
+            summary::commands::api_save_question_prompt_config,
+            summary::commands::api_get_summary_metrics,
+            summary::commands::api_preview_chunking,
+            summary::commands::api_compare_summaries,
+            summary::commands::api_extract_action_items,
+            summary::commands::api_get_ticket_coverage,
+            summary::commands::api_verify_quotes,
+            summary::commands::api_get_meeting_summaries,
+            summary::commands::api_preview_summary_prompt,
+            summary::commands::api_normalize_summary,
+            summary::commands::api_get_participant_map,
+            summary::commands::api_set_participant_map,
+            summary::commands::api_get_meeting_context,
+            summary::commands::api_set_meeting_context,
+            summary::commands::api_deanonymize_summary,
             summary::commands::generate_clarifying_questions,
+            summary::commands::api_get_meeting_questions,
+            summary::commands::api_mark_question_answered,
             // Template commands
             summary::api_list_templates,
             summary::api_get_template_details,
             summary::api_validate_template,
+            summary::template_commands::api_get_remote_template_config,
+            summary::template_commands::api_set_remote_template_config,
+            summary::template_commands::api_refresh_remote_templates,
             openrouter::get_openrouter_models,
             audio::recording_preferences::get_recording_preferences,
             audio::recording_preferences::set_recording_preferences,
@@ -676,6 +795,7 @@ pub fn run() {
             notifications::commands::get_notification_stats,
             // System audio capture commands
             audio::system_audio_commands::start_system_audio_capture_command,
+            audio::system_audio_commands::stop_system_audio_capture_command,
             audio::system_audio_commands::list_system_audio_devices_command,
             audio::system_audio_commands::check_system_audio_permissions_command,
             audio::system_audio_commands::start_system_audio_monitoring,
@@ -686,6 +806,7 @@ pub fn run() {
             // Screen Recording permission commands
             audio::permissions::check_screen_recording_permission_command,
             audio::permissions::request_screen_recording_permission_command,
+            audio::permissions::api_request_audio_permission,
             // audio::permissions::trigger_system_audio_permission_command,
             // Database import commands
             database::commands::check_first_launch,
@@ -694,6 +815,8 @@ pub fn run() {
             database::commands::check_homebrew_database,
             database::commands::import_and_initialize_database,
             database::commands::initialize_fresh_database,
+            database::commands::api_check_db_integrity,
+            database::commands::api_get_db_info,
             // Database and Models path commands
             database::commands::get_database_directory,
             database::commands::open_database_folder,
@@ -702,6 +825,16 @@ pub fn run() {
             #[cfg(target_os = "macos")]
             utils::open_system_settings,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                // Release captures started outside the main recording flow;
+                // AppState is missing until the database is initialized
+                if let Some(state) = app.try_state::<state::AppState>() {
+                    log::info!("🛑 Stopping registered recordings before exit");
+                    tauri::async_runtime::block_on(state.recordings.stop_all());
+                }
+            }
+        });
 }
@@ -0,0 +1,400 @@
+//! Log output for the app and its support bundle
+//!
+//! `log::` and `tracing::` events both end up here. The level comes from
+//! `RUST_LOG`, or else the saved logging config; `LOG_FORMAT=json` (or the
+//! config) switches to one JSON object per line, carrying the fields of the
+//! enclosing spans such as the `meeting_id` of a summary run. The most
+//! recent lines are kept in memory for `api_get_log_tail`, and the config
+//! also sets how much of a transcript, prompt or response `log_preview` shows.
+
+use once_cell::sync::{Lazy, OnceCell};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+use tracing::Subscriber;
+use tracing_subscriber::filter::filter_fn;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, reload, EnvFilter, Layer, Registry};
+
+const CONFIG_STORE: &str = "logging.json";
+const CONFIG_KEY: &str = "config";
+
+const DEFAULT_LEVEL: &str = "info";
+/// Lines kept for `api_get_log_tail`
+const TAIL_CAPACITY: usize = 2000;
+const DEFAULT_TAIL_LINES: usize = 200;
+/// Characters of transcripts, prompts and responses shown in previews
+pub const DEFAULT_PREVIEW_CHARS: usize = 200;
+const MAX_PREVIEW_CHARS: usize = 20_000;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl LogFormat {
+    fn from_env() -> Option<Self> {
+        match std::env::var("LOG_FORMAT").ok()?.to_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "text" => Some(Self::Text),
+            _ => None,
+        }
+    }
+}
+
+/// Saved log level and format; `RUST_LOG` and `LOG_FORMAT` win when set
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoggingConfig {
+    /// An `EnvFilter` directive such as `info` or `info,app_lib::summary=debug`
+    pub level: String,
+    pub format: LogFormat,
+    /// Characters of a transcript, prompt or response shown in log previews;
+    /// 0 leaves their content out
+    pub preview_chars: usize,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            level: DEFAULT_LEVEL.to_string(),
+            format: LogFormat::default(),
+            preview_chars: DEFAULT_PREVIEW_CHARS,
+        }
+    }
+}
+
+impl LoggingConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.preview_chars > MAX_PREVIEW_CHARS {
+            return Err(format!(
+                "Log previews must be at most {} characters",
+                MAX_PREVIEW_CHARS
+            ));
+        }
+        EnvFilter::try_new(&self.level)
+            .map(|_| ())
+            .map_err(|e| format!("Invalid log level '{}': {}", self.level, e))
+    }
+}
+
+static PREVIEW_CHARS: AtomicUsize = AtomicUsize::new(DEFAULT_PREVIEW_CHARS);
+
+/// The first `max_chars` characters of `text`, with "..." appended when
+/// anything was cut; never splits a character
+pub fn preview(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((end, _)) => format!("{}...", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+/// `preview` at the length set in the logging config
+pub fn log_preview(text: &str) -> String {
+    preview(text, PREVIEW_CHARS.load(Ordering::Relaxed))
+}
+
+static JSON_OUTPUT: AtomicBool = AtomicBool::new(false);
+static FILTER_HANDLE: OnceCell<reload::Handle<EnvFilter, Registry>> = OnceCell::new();
+static TAIL: Lazy<Mutex<VecDeque<String>>> =
+    Lazy::new(|| Mutex::new(VecDeque::with_capacity(TAIL_CAPACITY)));
+
+fn push_tail(line: &str) {
+    if let Ok(mut tail) = TAIL.lock() {
+        if tail.len() == TAIL_CAPACITY {
+            tail.pop_front();
+        }
+        tail.push_back(line.to_string());
+    }
+}
+
+/// The last `lines` log lines, oldest first
+pub fn log_tail(lines: usize) -> Vec<String> {
+    TAIL.lock()
+        .map(|tail| {
+            tail.iter()
+                .skip(tail.len().saturating_sub(lines))
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Writes each formatted event to stdout and to the in-memory tail
+#[derive(Clone, Copy)]
+struct TailWriter;
+
+impl Write for TailWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // The formatter writes one whole event per call
+        for line in String::from_utf8_lossy(buf).lines() {
+            push_tail(line);
+        }
+        io::stdout().write_all(buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        io::stdout().flush()
+    }
+}
+
+impl<'a> MakeWriter<'a> for TailWriter {
+    type Writer = TailWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        *self
+    }
+}
+
+/// Subscriber with a reloadable level filter, writing text or JSON lines
+/// (whichever `JSON_OUTPUT` selects) to `writer`
+fn build_subscriber<W>(
+    level: &str,
+    writer: W,
+) -> (
+    impl Subscriber + Send + Sync,
+    reload::Handle<EnvFilter, Registry>,
+)
+where
+    W: for<'a> MakeWriter<'a> + Clone + Send + Sync + 'static,
+{
+    let filter = EnvFilter::try_new(level).unwrap_or_else(|e| {
+        eprintln!(
+            "Invalid log level '{}', using {}: {}",
+            level, DEFAULT_LEVEL, e
+        );
+        EnvFilter::new(DEFAULT_LEVEL)
+    });
+    let (filter, handle) = reload::Layer::new(filter);
+    let text = fmt::layer()
+        .with_ansi(false)
+        .with_writer(writer.clone())
+        .with_filter(filter_fn(|_| !JSON_OUTPUT.load(Ordering::Relaxed)));
+    let json = fmt::layer()
+        .json()
+        .with_current_span(true)
+        .with_span_list(true)
+        .with_writer(writer)
+        .with_filter(filter_fn(|_| JSON_OUTPUT.load(Ordering::Relaxed)));
+    let subscriber = Registry::default().with(filter).with(text).with(json);
+    (subscriber, handle)
+}
+
+/// Installs the global logger; called once from `main` before anything logs
+///
+/// Uses `RUST_LOG` and `LOG_FORMAT` if set; the saved config is applied
+/// later by `apply_logging_config`, once the app is running.
+pub fn init_logging() {
+    let level = std::env::var("RUST_LOG").unwrap_or_else(|_| DEFAULT_LEVEL.to_string());
+    JSON_OUTPUT.store(
+        LogFormat::from_env() == Some(LogFormat::Json),
+        Ordering::Relaxed,
+    );
+    let (subscriber, handle) = build_subscriber(&level, TailWriter);
+    // Also routes `log::` records into the subscriber
+    if let Err(e) = subscriber.try_init() {
+        eprintln!("Failed to initialize logging: {}", e);
+        return;
+    }
+    // `log::` records are otherwise cut off at the level set here, so a
+    // level raised later would only apply to `tracing::` events
+    log::set_max_level(log::LevelFilter::Trace);
+    let _ = FILTER_HANDLE.set(handle);
+}
+
+fn set_level(level: &str) -> Result<(), String> {
+    let filter =
+        EnvFilter::try_new(level).map_err(|e| format!("Invalid log level '{}': {}", level, e))?;
+    match FILTER_HANDLE.get() {
+        Some(handle) => handle
+            .reload(filter)
+            .map_err(|e| format!("Failed to change the log level: {}", e)),
+        None => Err("Logging is not initialized".to_string()),
+    }
+}
+
+pub fn load_logging_config<R: Runtime>(app: &AppHandle<R>) -> LoggingConfig {
+    let stored = app
+        .store(CONFIG_STORE)
+        .ok()
+        .and_then(|store| store.get(CONFIG_KEY));
+    match stored.map(serde_json::from_value::<LoggingConfig>) {
+        Some(Ok(config)) => config,
+        Some(Err(e)) => {
+            log::warn!("⚠️ Invalid logging config in store, using defaults: {}", e);
+            LoggingConfig::default()
+        }
+        None => LoggingConfig::default(),
+    }
+}
+
+pub fn save_logging_config<R: Runtime>(
+    app: &AppHandle<R>,
+    config: &LoggingConfig,
+) -> Result<(), String> {
+    let store = app
+        .store(CONFIG_STORE)
+        .map_err(|e| format!("Failed to open logging store: {}", e))?;
+    let value = serde_json::to_value(config)
+        .map_err(|e| format!("Failed to serialize logging config: {}", e))?;
+    store.set(CONFIG_KEY, value);
+    store
+        .save()
+        .map_err(|e| format!("Failed to save logging config: {}", e))
+}
+
+/// Applies the saved level and format, except where `RUST_LOG` or
+/// `LOG_FORMAT` is set
+pub fn apply_logging_config<R: Runtime>(app: &AppHandle<R>) {
+    let config = load_logging_config(app);
+    PREVIEW_CHARS.store(config.preview_chars, Ordering::Relaxed);
+    if LogFormat::from_env().is_none() {
+        JSON_OUTPUT.store(config.format == LogFormat::Json, Ordering::Relaxed);
+    }
+    if std::env::var("RUST_LOG").is_err() && config.level != DEFAULT_LEVEL {
+        if let Err(e) = set_level(&config.level) {
+            log::warn!("⚠️ Saved log level not applied: {}", e);
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn api_get_logging_config<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<LoggingConfig, String> {
+    Ok(load_logging_config(&app))
+}
+
+/// Saves the log level and format and applies them right away
+#[tauri::command]
+pub async fn api_set_logging_config<R: Runtime>(
+    app: AppHandle<R>,
+    config: LoggingConfig,
+) -> Result<LoggingConfig, String> {
+    log::info!("api_set_logging_config called: {:?}", config);
+    config.validate()?;
+    set_level(&config.level)?;
+    JSON_OUTPUT.store(config.format == LogFormat::Json, Ordering::Relaxed);
+    PREVIEW_CHARS.store(config.preview_chars, Ordering::Relaxed);
+    save_logging_config(&app, &config)?;
+    Ok(config)
+}
+
+/// The most recent log lines, oldest first, for attaching to support requests
+#[tauri::command]
+pub async fn api_get_log_tail(lines: Option<usize>) -> Result<Vec<String>, String> {
+    Ok(log_tail(
+        lines.unwrap_or(DEFAULT_TAIL_LINES).min(TAIL_CAPACITY),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Capture {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for Capture {
+        type Writer = Capture;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    impl Capture {
+        fn text(&self) -> String {
+            String::from_utf8_lossy(&self.0.lock().unwrap()).to_string()
+        }
+    }
+
+    #[test]
+    fn test_level_filter_suppresses_debug_lines() {
+        let capture = Capture::default();
+        let (subscriber, handle) = build_subscriber("info", capture.clone());
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::debug!("chunk 3 prompt built");
+            tracing::info!("summary saved");
+            handle.reload(EnvFilter::new("debug")).unwrap();
+            tracing::debug!("chunk 4 prompt built");
+        });
+
+        let output = capture.text();
+        assert!(!output.contains("chunk 3 prompt built"), "{}", output);
+        assert!(output.contains("summary saved"), "{}", output);
+        assert!(output.contains("chunk 4 prompt built"), "{}", output);
+    }
+
+    #[test]
+    fn test_invalid_levels_are_rejected() {
+        let valid = LoggingConfig {
+            level: "info,app_lib::summary=debug".to_string(),
+            format: LogFormat::Json,
+            ..LoggingConfig::default()
+        };
+        assert_eq!(valid.validate(), Ok(()));
+        let invalid = LoggingConfig {
+            level: "app_lib=loud".to_string(),
+            ..LoggingConfig::default()
+        };
+        assert!(invalid.validate().is_err());
+        let too_long = LoggingConfig {
+            preview_chars: MAX_PREVIEW_CHARS + 1,
+            ..LoggingConfig::default()
+        };
+        assert!(too_long.validate().is_err());
+    }
+
+    #[test]
+    fn test_preview_respects_char_boundaries() {
+        assert_eq!(preview("Sam: ship it", 4), "Sam:...");
+        assert_eq!(preview("Sam: ship it", 12), "Sam: ship it");
+        assert_eq!(preview("", 4), "");
+        // Multi-byte characters are kept whole
+        assert_eq!(preview("Zoë fixes the naïve café bug", 3), "Zoë...");
+        assert_eq!(preview("日本語のテキスト", 3), "日本語...");
+        assert_eq!(preview("🎙️ mic", 1), "🎙...");
+        assert_eq!(preview("content", 0), "...");
+
+        let long = "a".repeat(DEFAULT_PREVIEW_CHARS * 2);
+        assert_eq!(
+            log_preview(&long).chars().count(),
+            DEFAULT_PREVIEW_CHARS + 3
+        );
+    }
+
+    #[test]
+    fn test_tail_keeps_the_latest_lines() {
+        for i in 0..TAIL_CAPACITY + 5 {
+            push_tail(&format!("tail test line {}", i));
+        }
+        let tail = log_tail(2);
+        assert_eq!(tail.len(), 2);
+        assert_eq!(tail[1], format!("tail test line {}", TAIL_CAPACITY + 4));
+        assert!(log_tail(TAIL_CAPACITY * 2).len() <= TAIL_CAPACITY);
+    }
+}
@@ -3,7 +3,7 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 use serde::{Deserialize, Serialize};
-use reqwest::Client;
+use crate::http::client_factory::direct_client;
 use regex::Regex;
 use once_cell::sync::Lazy;
 
@@ -16,6 +16,43 @@ pub struct ModelMetadata {
     pub family: String,
 }
 
+impl ModelMetadata {
+    /// Parameter count in billions, from Ollama's parameter size ("8.0B", "494.03M")
+    pub fn parameter_billions(&self) -> Option<f64> {
+        parse_parameter_count(&self.parameter_count)
+    }
+}
+
+/// Number followed by a K/M/B/T unit
+static PARAMETER_COUNT_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^\s*(\d+(?:\.\d+)?)\s*([kmbt])\s*$").expect("Invalid regex pattern")
+});
+
+/// A size tag inside a model name: "8b" in "llama3.1:8b-instruct-q4", not
+/// the "1" of a version number or the "4" of a quantization tag
+static NAME_PARAMETER_COUNT_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)(?:^|[^a-z0-9.])(\d+(?:\.\d+)?[mbt])(?:$|[^a-z0-9])")
+        .expect("Invalid regex pattern")
+});
+
+/// Parses a parameter count like "8.0B", "494M" or "1.5t" into billions
+pub fn parse_parameter_count(value: &str) -> Option<f64> {
+    let caps = PARAMETER_COUNT_RE.captures(value)?;
+    let number: f64 = caps[1].parse().ok()?;
+    match caps[2].to_ascii_lowercase().as_str() {
+        "k" => Some(number / 1e6),
+        "m" => Some(number / 1e3),
+        "b" => Some(number),
+        _ => Some(number * 1e3),
+    }
+}
+
+/// Parameter count in billions guessed from a size tag in the model name
+pub fn parameter_billions_from_name(model_name: &str) -> Option<f64> {
+    let caps = NAME_PARAMETER_COUNT_RE.captures(model_name)?;
+    parse_parameter_count(&caps[1])
+}
+
 /// Response structure from Ollama /api/show endpoint
 #[derive(Debug, Deserialize)]
 struct OllamaShowResponse {
@@ -141,7 +178,7 @@ async fn fetch_model_info(
     model_name: &str,
     endpoint: Option<&str>,
 ) -> Result<ModelMetadata, String> {
-    let client = Client::new();
+    let client = direct_client();
     let base_url = endpoint.unwrap_or("http://localhost:11434");
     let url = format!("{}/api/show", base_url);
 
@@ -315,6 +352,25 @@ fn get_fallback_metadata(model_name: &str) -> ModelMetadata {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_parameter_counts() {
+        assert_eq!(parse_parameter_count("8.0B"), Some(8.0));
+        assert_eq!(parse_parameter_count("494M"), Some(0.494));
+        assert_eq!(parse_parameter_count(" 1.5t "), Some(1500.0));
+        assert_eq!(parse_parameter_count("unknown"), None);
+        assert_eq!(parse_parameter_count(""), None);
+
+        assert_eq!(parameter_billions_from_name("llama3.1:8b-instruct-q4"), Some(8.0));
+        assert_eq!(parameter_billions_from_name("llama3.2:1b"), Some(1.0));
+        assert_eq!(parameter_billions_from_name("phi-3.1b-custom"), Some(3.1));
+        assert_eq!(parameter_billions_from_name("qwen2.5:0.5b"), Some(0.5));
+        assert_eq!(parameter_billions_from_name("gemma3:270m"), Some(0.27));
+        // Versions, quantization and mixture tags aren't sizes
+        assert_eq!(parameter_billions_from_name("llama3.1:latest"), None);
+        assert_eq!(parameter_billions_from_name("mistral:7x-q4_K_M"), None);
+        assert_eq!(parameter_billions_from_name("gpt-4o"), None);
+    }
+
     #[test]
     fn test_parse_num_ctx_standard() {
         let modelfile = "FROM /path/to/model\nPARAMETER num_ctx 8192\nPARAMETER temperature 0.7";
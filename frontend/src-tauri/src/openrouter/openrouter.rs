@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 use tauri::command;
-use reqwest::blocking::Client;
+use crate::http::client_factory::blocking_client;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct OpenRouterModel {
@@ -40,7 +40,7 @@ struct OpenRouterResponse {
 
 #[command]
 pub fn get_openrouter_models() -> Result<Vec<OpenRouterModel>, String> {
-    let client = Client::new();
+    let client = blocking_client();
     let response = client
         .get("https://openrouter.ai/api/v1/models")
         .send()
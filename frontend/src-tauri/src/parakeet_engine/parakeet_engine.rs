@@ -1,3 +1,4 @@
+use crate::http::client_factory;
 use crate::parakeet_engine::model::ParakeetModel;
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
@@ -479,7 +480,7 @@ impl ParakeetEngine {
         }
 
         // Download each file
-        let client = reqwest::Client::new();
+        let client = client_factory::client();
         let total_files = files_to_download.len();
 
         // Calculate total download size for weighted progress
@@ -0,0 +1,103 @@
+use crate::database::models::{Person, PersonInput};
+use crate::database::repositories::{meeting::MeetingsRepository, people::PeopleRepository};
+use crate::people::participants::{match_attendees, MeetingAttendee};
+use crate::people::resolution::load_resolved_action_items;
+use crate::state::AppState;
+use crate::summary::action_items::ActionItem;
+use log::{error as log_error, info as log_info};
+use tauri::{AppHandle, Runtime};
+
+#[tauri::command]
+pub async fn api_list_people<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<Person>, String> {
+    PeopleRepository::list(state.db_manager.pool())
+        .await
+        .map_err(|e| format!("Failed to list people: {}", e))
+}
+
+#[tauri::command]
+pub async fn api_create_person<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    person: PersonInput,
+) -> Result<Person, String> {
+    log_info!("api_create_person called for '{}'", person.display_name);
+    PeopleRepository::create(state.db_manager.pool(), &person)
+        .await
+        .map_err(|e| {
+            log_error!("Error creating person: {}", e);
+            format!("Failed to create person: {}", e)
+        })
+}
+
+#[tauri::command]
+pub async fn api_update_person<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    id: String,
+    person: PersonInput,
+) -> Result<Person, String> {
+    log_info!("api_update_person called for id: {}", id);
+    match PeopleRepository::update(state.db_manager.pool(), &id, &person).await {
+        Ok(Some(person)) => Ok(person),
+        Ok(None) => Err(format!("Person not found: {}", id)),
+        Err(e) => {
+            log_error!("Error updating person {}: {}", id, e);
+            Err(format!("Failed to update person: {}", e))
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn api_delete_person<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    id: String,
+) -> Result<bool, String> {
+    log_info!("api_delete_person called for id: {}", id);
+    PeopleRepository::delete(state.db_manager.pool(), &id)
+        .await
+        .map_err(|e| format!("Failed to delete person: {}", e))
+}
+
+/// Parses the meeting's Action Items and matches their owners against the
+/// current directory; call again after editing the directory
+#[tauri::command]
+pub async fn api_resolve_owners<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Vec<ActionItem>, String> {
+    log_info!("api_resolve_owners called for meeting_id: {}", meeting_id);
+    let items = load_resolved_action_items(state.db_manager.pool(), &meeting_id).await?;
+    let resolved = items.iter().filter(|i| i.resolved_person().is_some()).count();
+    log_info!(
+        "👥 Resolved {}/{} action item owners for {}",
+        resolved,
+        items.len(),
+        meeting_id
+    );
+    Ok(items)
+}
+
+/// Attendees found in the meeting's transcript by the last summary run, each
+/// matched against the current directory
+#[tauri::command]
+pub async fn api_get_meeting_participants<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Vec<MeetingAttendee>, String> {
+    log_info!("api_get_meeting_participants called for meeting_id: {}", meeting_id);
+    let pool = state.db_manager.pool();
+    let participants = MeetingsRepository::get_participants(pool, &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load participants: {}", e))?
+        .ok_or_else(|| format!("Meeting not found: {}", meeting_id))?;
+    let people = PeopleRepository::list(pool)
+        .await
+        .map_err(|e| format!("Failed to load people: {}", e))?;
+    Ok(match_attendees(participants, &people))
+}
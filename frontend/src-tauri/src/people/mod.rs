@@ -0,0 +1,14 @@
+/// People module - directory of meeting participants
+///
+/// This module contains:
+/// - Resolution of action item owners ("Alex", "AK") to directory entries,
+///   flagging owners that fit several people
+/// - The attendee list of each meeting, merged from the names the model
+///   found in its transcript
+/// - Tauri commands for editing the directory and re-running resolution
+
+pub mod commands;
+pub mod participants;
+pub mod resolution;
+
+pub use resolution::{load_resolved_action_items, resolve_action_items, resolve_owner};
@@ -0,0 +1,188 @@
+use crate::database::models::{MeetingParticipant, Person};
+use crate::people::resolution::{normalize, resolve_owner};
+use crate::summary::action_items::OwnerMatch;
+use crate::summary::anonymize::DetectedParticipant;
+use serde::Serialize;
+
+/// Role the participant detection uses when it can't tell
+const UNKNOWN_ROLE: &str = "participant";
+
+/// An attendee with its match in the people directory
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MeetingAttendee {
+    #[serde(flatten)]
+    pub participant: MeetingParticipant,
+    pub directory_match: OwnerMatch,
+}
+
+/// The name as written, without markdown emphasis, a leading @ or
+/// surrounding punctuation
+fn display_name(name: &str) -> String {
+    name.replace('*', "")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_matches(|c: char| c.is_ascii_punctuation())
+        .to_string()
+}
+
+/// Merges detected participants that are the same person written
+/// differently ("Sam Lee", "sam lee", "**Sam Lee**", "@Sam Lee:")
+///
+/// Only exact matches after normalization are merged, the same level owner
+/// resolution treats as certain; "Sam" and "Sam Lee" stay apart. The first
+/// spelling with a capital letter names the attendee and the first known
+/// role is kept.
+pub fn merge_participants(detected: &[DetectedParticipant]) -> Vec<MeetingParticipant> {
+    let mut merged: Vec<(String, MeetingParticipant)> = Vec::new();
+    for participant in detected {
+        let key = normalize(&participant.name);
+        let name = display_name(&participant.name);
+        if key.is_empty() || name.is_empty() {
+            continue;
+        }
+        let role = participant
+            .role
+            .as_deref()
+            .map(str::trim)
+            .filter(|role| !role.is_empty() && !role.eq_ignore_ascii_case(UNKNOWN_ROLE))
+            .map(str::to_string);
+        let mention = participant.name.trim().to_string();
+
+        match merged.iter_mut().find(|(existing, _)| *existing == key) {
+            Some((_, attendee)) => {
+                if !attendee.name.chars().any(char::is_uppercase)
+                    && name.chars().any(char::is_uppercase)
+                {
+                    attendee.name = name;
+                }
+                if attendee.role.is_none() {
+                    attendee.role = role;
+                }
+                if !attendee.mentions.contains(&mention) {
+                    attendee.mentions.push(mention);
+                }
+            }
+            None => merged.push((
+                key,
+                MeetingParticipant {
+                    name,
+                    role,
+                    mentions: vec![mention],
+                },
+            )),
+        }
+    }
+    merged.into_iter().map(|(_, attendee)| attendee).collect()
+}
+
+/// Matches each attendee against the people directory
+pub fn match_attendees(
+    participants: Vec<MeetingParticipant>,
+    people: &[Person],
+) -> Vec<MeetingAttendee> {
+    participants
+        .into_iter()
+        .map(|participant| MeetingAttendee {
+            directory_match: resolve_owner(&participant.name, people),
+            participant,
+        })
+        .collect()
+}
+
+/// Prompt note listing the attendees, so Action Item owners are written
+/// with the names the directory can resolve; None without attendees
+pub fn attendees_prompt_note(participants: &[MeetingParticipant]) -> Option<String> {
+    if participants.is_empty() {
+        return None;
+    }
+    let attendees: Vec<String> = participants
+        .iter()
+        .map(|participant| match &participant.role {
+            Some(role) => format!("- {} ({})", participant.name, role),
+            None => format!("- {}", participant.name),
+        })
+        .collect();
+    Some(format!(
+        "Known attendees of this meeting:\n{}\nWrite Action Item owners using these names exactly as listed. Only use another name when the owner is clearly not one of them.",
+        attendees.join("\n")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn detected(name: &str, role: Option<&str>) -> DetectedParticipant {
+        DetectedParticipant {
+            name: name.to_string(),
+            role: role.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_same_person_written_differently_is_merged() {
+        let merged = merge_participants(&[
+            detected("sam lee", Some("Participant")),
+            detected("Ana", Some("Product Manager")),
+            detected("**Sam Lee**", Some("Engineer")),
+            detected("@Sam  Lee:", None),
+            detected("Sam", Some("Designer")),
+            detected("  ", Some("Engineer")),
+        ]);
+
+        assert_eq!(
+            merged,
+            vec![
+                MeetingParticipant {
+                    name: "Sam Lee".to_string(),
+                    role: Some("Engineer".to_string()),
+                    mentions: vec![
+                        "sam lee".to_string(),
+                        "**Sam Lee**".to_string(),
+                        "@Sam  Lee:".to_string(),
+                    ],
+                },
+                MeetingParticipant {
+                    name: "Ana".to_string(),
+                    role: Some("Product Manager".to_string()),
+                    mentions: vec!["Ana".to_string()],
+                },
+                // Only a first name: not certain enough to merge with Sam Lee
+                MeetingParticipant {
+                    name: "Sam".to_string(),
+                    role: Some("Designer".to_string()),
+                    mentions: vec!["Sam".to_string()],
+                },
+            ]
+        );
+
+        let note = attendees_prompt_note(&merged).unwrap();
+        assert!(note.contains("- Sam Lee (Engineer)\n- Ana (Product Manager)\n- Sam (Designer)"));
+        assert_eq!(attendees_prompt_note(&[]), None);
+    }
+
+    #[test]
+    fn test_attendees_matched_against_directory() {
+        let person = Person {
+            id: "1".to_string(),
+            display_name: "Samuel Lee".to_string(),
+            aliases: vec!["Sam Lee".to_string()],
+            email: None,
+            jira_account_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let attendees = match_attendees(
+            merge_participants(&[detected("Sam Lee", None), detected("Priya", None)]),
+            &[person],
+        );
+
+        assert!(matches!(
+            &attendees[0].directory_match,
+            OwnerMatch::Resolved { person } if person.person_id == "1"
+        ));
+        assert_eq!(attendees[1].directory_match, OwnerMatch::Unmatched);
+    }
+}
@@ -0,0 +1,198 @@
+use crate::database::models::Person;
+use crate::database::repositories::{people::PeopleRepository, summary::SummaryProcessesRepository};
+use crate::summary::action_items::{parse_action_items, ActionItem, OwnerMatch, ResolvedPerson};
+use sqlx::SqlitePool;
+
+/// Lowercases and strips markdown emphasis, a leading @ and surrounding punctuation
+pub(crate) fn normalize(text: &str) -> String {
+    text.replace('*', "")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_matches(|c: char| c.is_ascii_punctuation())
+        .to_lowercase()
+}
+
+fn initials(name: &str) -> String {
+    name.split_whitespace()
+        .filter_map(|word| word.chars().next())
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// Display name, aliases, email and the email's local part
+fn matches_exactly(owner: &str, person: &Person) -> bool {
+    let email = person.email.as_deref().map(normalize);
+    normalize(&person.display_name) == owner
+        || person.aliases.iter().any(|alias| normalize(alias) == owner)
+        || email.as_deref() == Some(owner)
+        || email
+            .as_deref()
+            .and_then(|email| email.split('@').next())
+            .map(|local| !local.is_empty() && local == owner)
+            .unwrap_or(false)
+}
+
+/// First name alone ("alex") or initials ("ak", "a.k.")
+fn matches_loosely(owner: &str, person: &Person) -> bool {
+    let display_name = normalize(&person.display_name);
+    let first_name = display_name.split_whitespace().next().unwrap_or("");
+    if !owner.contains(' ') && owner == first_name {
+        return true;
+    }
+
+    let compact: String = owner.chars().filter(|c| !matches!(c, '.' | ' ')).collect();
+    let word_count = display_name.split_whitespace().count();
+    word_count >= 2
+        && compact.chars().all(|c| c.is_alphabetic())
+        && compact == initials(&display_name)
+}
+
+fn to_resolved(person: &Person) -> ResolvedPerson {
+    ResolvedPerson {
+        person_id: person.id.clone(),
+        display_name: person.display_name.clone(),
+        email: person.email.clone(),
+        jira_account_id: person.jira_account_id.clone(),
+    }
+}
+
+/// Matches owner text against the directory
+///
+/// Exact matches (name, alias, email) win over loose ones (first name,
+/// initials); within the winning tier more than one person is ambiguous.
+pub fn resolve_owner(owner: &str, people: &[Person]) -> OwnerMatch {
+    let owner = normalize(owner);
+    if owner.is_empty() {
+        return OwnerMatch::Unmatched;
+    }
+
+    let exact: Vec<&Person> = people.iter().filter(|p| matches_exactly(&owner, p)).collect();
+    let candidates = if exact.is_empty() {
+        people
+            .iter()
+            .filter(|p| matches_loosely(&owner, p))
+            .collect()
+    } else {
+        exact
+    };
+
+    match candidates.as_slice() {
+        [] => OwnerMatch::Unmatched,
+        [person] => OwnerMatch::Resolved {
+            person: to_resolved(person),
+        },
+        _ => OwnerMatch::Ambiguous {
+            candidates: candidates.iter().map(|p| to_resolved(p)).collect(),
+        },
+    }
+}
+
+/// Annotates every item with its owner match, replacing any earlier result
+pub fn resolve_action_items(items: &mut [ActionItem], people: &[Person]) {
+    for item in items {
+        item.owner_match = Some(resolve_owner(&item.owner, people));
+    }
+}
+
+/// Parses the Action Items of a meeting's summary and resolves their owners
+/// against the current directory
+pub async fn load_resolved_action_items(
+    pool: &SqlitePool,
+    meeting_id: &str,
+) -> Result<Vec<ActionItem>, String> {
+    let process = SummaryProcessesRepository::get_summary_data(pool, meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load summary: {}", e))?
+        .ok_or_else(|| format!("No summary found for meeting {}", meeting_id))?;
+    let result: serde_json::Value = process
+        .result
+        .as_deref()
+        .map(serde_json::from_str)
+        .transpose()
+        .map_err(|e| format!("Failed to parse summary: {}", e))?
+        .ok_or_else(|| format!("Summary for meeting {} is not ready", meeting_id))?;
+    let markdown = result
+        .get("markdown")
+        .and_then(|m| m.as_str())
+        .unwrap_or_default();
+
+    let people = PeopleRepository::list(pool)
+        .await
+        .map_err(|e| format!("Failed to load people: {}", e))?;
+    let mut items = parse_action_items(markdown);
+    resolve_action_items(&mut items, &people);
+    Ok(items)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn person(id: &str, name: &str, aliases: &[&str], email: Option<&str>) -> Person {
+        Person {
+            id: id.to_string(),
+            display_name: name.to_string(),
+            aliases: aliases.iter().map(|a| a.to_string()).collect(),
+            email: email.map(str::to_string),
+            jira_account_id: Some(format!("jira-{}", id)),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn directory() -> Vec<Person> {
+        vec![
+            person("1", "Alex Kim", &["AK"], Some("alex.kim@example.com")),
+            person("2", "Alex Romero", &[], Some("aromero@example.com")),
+            person("3", "Priya Natarajan", &["Pri"], None),
+        ]
+    }
+
+    fn resolved_id(result: OwnerMatch) -> Option<String> {
+        match result {
+            OwnerMatch::Resolved { person } => Some(person.person_id),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_first_name_shared_by_two_people_is_ambiguous() {
+        match resolve_owner("Alex", &directory()) {
+            OwnerMatch::Ambiguous { candidates } => {
+                let ids: Vec<_> = candidates.iter().map(|c| c.person_id.as_str()).collect();
+                assert_eq!(ids, vec!["1", "2"]);
+            }
+            other => panic!("expected ambiguous, got {:?}", other),
+        }
+        // The full name settles it
+        assert_eq!(resolved_id(resolve_owner("alex romero", &directory())).as_deref(), Some("2"));
+    }
+
+    #[test]
+    fn test_alias_email_initials_and_first_name() {
+        let people = directory();
+        assert_eq!(resolved_id(resolve_owner("**AK**", &people)).as_deref(), Some("1"));
+        assert_eq!(resolved_id(resolve_owner("@pri", &people)).as_deref(), Some("3"));
+        assert_eq!(resolved_id(resolve_owner("aromero", &people)).as_deref(), Some("2"));
+        assert_eq!(resolved_id(resolve_owner("A.R.", &people)).as_deref(), Some("2"));
+        assert_eq!(resolved_id(resolve_owner("Priya", &people)).as_deref(), Some("3"));
+    }
+
+    #[test]
+    fn test_unmatched_owner_passes_through() {
+        let mut items = vec![ActionItem {
+            owner: "Two developers".to_string(),
+            task: "Review PR".to_string(),
+            ..Default::default()
+        }];
+
+        resolve_action_items(&mut items, &directory());
+
+        assert_eq!(items[0].owner, "Two developers");
+        assert_eq!(items[0].owner_match, Some(OwnerMatch::Unmatched));
+        assert!(items[0].resolved_person().is_none());
+        assert_eq!(resolve_owner("  ", &directory()), OwnerMatch::Unmatched);
+    }
+}
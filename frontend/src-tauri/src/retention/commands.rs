@@ -0,0 +1,97 @@
+use super::policy::{
+    load_retention_config, plan_retention, run_retention, save_last_run, save_retention_config,
+    RetentionConfig, RetentionPlan, RetentionReport,
+};
+use super::summary_processes::{
+    cleanup_summary_processes, load_cleanup_config, save_cleanup_config,
+    SummaryProcessCleanupConfig,
+};
+use crate::state::AppState;
+use chrono::Utc;
+use log::info as log_info;
+use tauri::{AppHandle, Runtime};
+
+#[tauri::command]
+pub async fn api_get_retention_config<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<RetentionConfig, String> {
+    Ok(load_retention_config(&app))
+}
+
+/// Saves the retention settings; the scheduled task uses them from its next run
+#[tauri::command]
+pub async fn api_set_retention_config<R: Runtime>(
+    app: AppHandle<R>,
+    config: RetentionConfig,
+) -> Result<RetentionConfig, String> {
+    log_info!("api_set_retention_config called: {:?}", config);
+    config.validate()?;
+    save_retention_config(&app, &config)?;
+    Ok(config)
+}
+
+/// What the next run would delete or compress and how much space it frees;
+/// nothing is changed
+#[tauri::command]
+pub async fn api_preview_retention<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+) -> Result<RetentionPlan, String> {
+    let config = load_retention_config(&app);
+    plan_retention(state.db_manager.pool(), &config, Utc::now()).await
+}
+
+/// Runs the policy now, whether or not the schedule is enabled; the next
+/// scheduled run counts from this one
+#[tauri::command]
+pub async fn api_run_retention<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+) -> Result<RetentionReport, String> {
+    log_info!("api_run_retention called");
+    if crate::audio::recording_commands::is_recording().await {
+        return Err("Stop the recording before running the retention policy".to_string());
+    }
+    let config = load_retention_config(&app);
+    config.validate()?;
+    let now = Utc::now();
+    let report = run_retention(state.db_manager.pool(), &config, now).await?;
+    if let Err(e) = save_last_run(&app, now) {
+        log::warn!("⚠️ {}", e);
+    }
+    Ok(report)
+}
+
+#[tauri::command]
+pub async fn api_get_summary_process_cleanup_config<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<SummaryProcessCleanupConfig, String> {
+    Ok(load_cleanup_config(&app))
+}
+
+/// Saves how long failed summary runs are kept; applies from the next run
+#[tauri::command]
+pub async fn api_set_summary_process_cleanup_config<R: Runtime>(
+    app: AppHandle<R>,
+    config: SummaryProcessCleanupConfig,
+) -> Result<SummaryProcessCleanupConfig, String> {
+    log_info!(
+        "api_set_summary_process_cleanup_config called: {:?}",
+        config
+    );
+    config.validate()?;
+    save_cleanup_config(&app, &config)?;
+    Ok(config)
+}
+
+/// Removes old summary runs without a summary now; returns how many
+#[tauri::command]
+pub async fn api_run_summary_process_cleanup<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+) -> Result<u64, String> {
+    log_info!("api_run_summary_process_cleanup called");
+    let config = load_cleanup_config(&app);
+    config.validate()?;
+    cleanup_summary_processes(state.db_manager.pool(), &config, Utc::now()).await
+}
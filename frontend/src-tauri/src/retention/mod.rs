@@ -0,0 +1,12 @@
+/// Retention module - how long recordings, transcripts and summaries are kept
+///
+/// This module contains:
+/// - The retention config and the purge it plans for a library: deleting old
+///   recordings (marking the meeting so playback is refused), compressing or
+///   deleting transcripts, and deleting summaries only when configured
+/// - The scheduled task running the policy in the background
+/// - A cleanup, on by default, of old summary runs that never produced a summary
+/// - Tauri commands for the settings, a preview of the next run and a manual run
+pub mod commands;
+pub mod policy;
+pub mod summary_processes;
@@ -0,0 +1,644 @@
+use crate::audio::meeting_artifacts::{recording_files, transcript_file, CHECKPOINTS_DIR};
+use crate::database::models::RetentionCandidate;
+use crate::database::repositories::retention::RetentionRepository;
+use crate::state::AppState;
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::io::{Cursor, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_store::StoreExt;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+const CONFIG_STORE: &str = "retention.json";
+const CONFIG_KEY: &str = "config";
+const LAST_RUN_KEY: &str = "last_run_at";
+
+/// Longest wait between scheduled runs
+pub(crate) const MAX_INTERVAL_HOURS: u64 = 7 * 24;
+
+/// How often the scheduled task wakes up to read the config again and to
+/// retry a run that was skipped or failed
+const RECHECK_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Share of a transcript file's size left after zipping; JSON transcripts
+/// deflate well, so the preview estimates instead of compressing each one
+const TRANSCRIPT_ZIP_RATIO: f64 = 0.25;
+
+/// How long each part of a meeting is kept; None keeps it forever
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionConfig {
+    /// Runs on a schedule; the preview and manual runs work either way
+    pub enabled: bool,
+    pub audio_days: Option<u32>,
+    /// Transcript segments and the transcript file
+    pub transcript_days: Option<u32>,
+    /// Zips the transcript file once the recording is deleted, when the
+    /// transcript is also in the database
+    pub compress_transcripts: bool,
+    /// Summaries are only ever deleted when this is set
+    pub summary_days: Option<u32>,
+    pub interval_hours: u64,
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            audio_days: Some(90),
+            transcript_days: None,
+            compress_transcripts: true,
+            summary_days: None,
+            interval_hours: 24,
+        }
+    }
+}
+
+impl RetentionConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        for (days, what) in [
+            (self.audio_days, "Recordings"),
+            (self.transcript_days, "Transcripts"),
+            (self.summary_days, "Summaries"),
+        ] {
+            if days == Some(0) {
+                return Err(format!("{} must be kept for at least one day", what));
+            }
+        }
+        if !(1..=MAX_INTERVAL_HOURS).contains(&self.interval_hours) {
+            return Err(format!(
+                "Retention interval must be between 1 and {} hours",
+                MAX_INTERVAL_HOURS
+            ));
+        }
+        Ok(())
+    }
+}
+
+pub fn load_retention_config<R: Runtime>(app: &AppHandle<R>) -> RetentionConfig {
+    let stored = app
+        .store(CONFIG_STORE)
+        .ok()
+        .and_then(|store| store.get(CONFIG_KEY));
+    match stored.map(serde_json::from_value::<RetentionConfig>) {
+        Some(Ok(config)) => config,
+        Some(Err(e)) => {
+            warn!(
+                "⚠️ Invalid retention config in store, using defaults: {}",
+                e
+            );
+            RetentionConfig::default()
+        }
+        None => RetentionConfig::default(),
+    }
+}
+
+pub fn save_retention_config<R: Runtime>(
+    app: &AppHandle<R>,
+    config: &RetentionConfig,
+) -> Result<(), String> {
+    let store = app
+        .store(CONFIG_STORE)
+        .map_err(|e| format!("Failed to open retention store: {}", e))?;
+    let value = serde_json::to_value(config)
+        .map_err(|e| format!("Failed to serialize retention config: {}", e))?;
+    store.set(CONFIG_KEY, value);
+    store
+        .save()
+        .map_err(|e| format!("Failed to save retention config: {}", e))
+}
+
+/// When the policy last ran, scheduled or manual; None if it never did
+pub fn load_last_run<R: Runtime>(app: &AppHandle<R>) -> Option<DateTime<Utc>> {
+    app.store(CONFIG_STORE)
+        .ok()
+        .and_then(|store| store.get(LAST_RUN_KEY))
+        .and_then(|value| serde_json::from_value(value).ok())
+}
+
+pub fn save_last_run<R: Runtime>(app: &AppHandle<R>, at: DateTime<Utc>) -> Result<(), String> {
+    let store = app
+        .store(CONFIG_STORE)
+        .map_err(|e| format!("Failed to open retention store: {}", e))?;
+    store.set(LAST_RUN_KEY, serde_json::json!(at));
+    store
+        .save()
+        .map_err(|e| format!("Failed to save retention run time: {}", e))
+}
+
+/// How long until the next scheduled run; zero when it is due now
+///
+/// A policy that never ran, or whose last run is more than `interval_hours`
+/// ago (the app was closed), is due right away.
+fn time_until_due(
+    last_run: Option<DateTime<Utc>>,
+    interval_hours: u64,
+    now: DateTime<Utc>,
+) -> Duration {
+    let Some(last_run) = last_run else {
+        return Duration::ZERO;
+    };
+    let next_run = last_run + chrono::Duration::hours(interval_hours.max(1) as i64);
+    (next_run - now).to_std().unwrap_or(Duration::ZERO)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PurgeAction {
+    DeleteAudio,
+    CompressTranscript,
+    DeleteTranscript,
+    DeleteSummary,
+}
+
+/// One thing the next run would do to one meeting
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlannedPurge {
+    pub meeting_id: String,
+    pub title: String,
+    pub age_days: i64,
+    pub action: PurgeAction,
+    /// Files deleted, or the file compressed
+    pub files: Vec<PathBuf>,
+    /// Estimated for compressions until the run has done them
+    pub bytes_freed: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RetentionPlan {
+    pub purges: Vec<PlannedPurge>,
+    pub bytes_freed: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FailedPurge {
+    pub purge: PlannedPurge,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RetentionReport {
+    pub done: Vec<PlannedPurge>,
+    pub failed: Vec<FailedPurge>,
+    pub bytes_freed: u64,
+}
+
+fn file_size(path: &Path) -> u64 {
+    std::fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+}
+
+fn compressed_path(transcript: &Path) -> PathBuf {
+    let mut name = transcript.as_os_str().to_owned();
+    name.push(".zip");
+    PathBuf::from(name)
+}
+
+fn zip_file(name: String, contents: &[u8]) -> zip::result::ZipResult<Vec<u8>> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    zip.start_file(name, options)?;
+    zip.write_all(contents)?;
+    Ok(zip.finish()?.into_inner())
+}
+
+/// The transcript file zipped in memory
+fn compress_transcript(transcript: &Path) -> Result<Vec<u8>, String> {
+    let contents = std::fs::read(transcript)
+        .map_err(|e| format!("Failed to read {}: {}", transcript.display(), e))?;
+    let name = transcript
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    zip_file(name, &contents)
+        .map_err(|e| format!("Failed to compress {}: {}", transcript.display(), e))
+}
+
+fn is_due(keep_days: Option<u32>, age_days: i64) -> bool {
+    keep_days.is_some_and(|days| age_days >= days as i64)
+}
+
+/// What is due for one meeting, in the order it is done
+fn plan_meeting(
+    config: &RetentionConfig,
+    meeting: &RetentionCandidate,
+    now: DateTime<Utc>,
+) -> Vec<PlannedPurge> {
+    let age_days = (now - meeting.created_at.0).num_days();
+    let folder = meeting.folder_path.as_deref().map(Path::new);
+    let purge = |action, files: Vec<PathBuf>, bytes_freed| PlannedPurge {
+        meeting_id: meeting.id.clone(),
+        title: meeting.title.clone(),
+        age_days,
+        action,
+        files,
+        bytes_freed,
+    };
+    let mut purges = Vec::new();
+
+    let audio_due = is_due(config.audio_days, age_days);
+    if audio_due && !meeting.audio_deleted {
+        let files = folder.map(recording_files).unwrap_or_default();
+        if !files.is_empty() {
+            let bytes = files.iter().map(|file| file_size(file)).sum();
+            purges.push(purge(PurgeAction::DeleteAudio, files, bytes));
+        }
+    }
+
+    let transcript = folder.map(transcript_file);
+    if is_due(config.transcript_days, age_days) {
+        if !meeting.transcript_deleted {
+            let files: Vec<PathBuf> = transcript
+                .iter()
+                .flat_map(|file| [file.clone(), compressed_path(file)])
+                .filter(|file| file.is_file())
+                .collect();
+            let bytes = files.iter().map(|file| file_size(file)).sum();
+            purges.push(purge(PurgeAction::DeleteTranscript, files, bytes));
+        }
+    } else if config.compress_transcripts && audio_due && meeting.has_segments {
+        // Without segments in the database the meeting is read from the file
+        if let Some(file) = transcript.filter(|file| file.is_file()) {
+            let size = file_size(&file);
+            let bytes = size - (size as f64 * TRANSCRIPT_ZIP_RATIO) as u64;
+            purges.push(purge(PurgeAction::CompressTranscript, vec![file], bytes));
+        }
+    }
+
+    if is_due(config.summary_days, age_days) && meeting.has_summary {
+        purges.push(purge(PurgeAction::DeleteSummary, Vec::new(), 0));
+    }
+    purges
+}
+
+/// What a run at `now` would delete or compress, and the space it frees
+pub async fn plan_retention(
+    pool: &SqlitePool,
+    config: &RetentionConfig,
+    now: DateTime<Utc>,
+) -> Result<RetentionPlan, String> {
+    let meetings = RetentionRepository::candidates(pool)
+        .await
+        .map_err(|e| format!("Failed to load meetings: {}", e))?;
+    let purges: Vec<PlannedPurge> = meetings
+        .iter()
+        .flat_map(|meeting| plan_meeting(config, meeting, now))
+        .collect();
+    let bytes_freed = purges.iter().map(|purge| purge.bytes_freed).sum();
+    Ok(RetentionPlan {
+        purges,
+        bytes_freed,
+    })
+}
+
+fn remove_files(files: &[PathBuf]) -> Result<(), String> {
+    for file in files {
+        match std::fs::remove_file(file) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(format!("Failed to delete {}: {}", file.display(), e)),
+        }
+    }
+    Ok(())
+}
+
+/// Carries out one purge; returns the bytes it actually freed
+async fn apply_purge(
+    pool: &SqlitePool,
+    purge: &PlannedPurge,
+    now: DateTime<Utc>,
+) -> Result<u64, String> {
+    let db_error = |e: sqlx::Error| format!("Database error: {}", e);
+    match purge.action {
+        PurgeAction::DeleteAudio => {
+            remove_files(&purge.files)?;
+            // Only removed once empty, so nothing else is lost with it
+            if let Some(checkpoints) = purge
+                .files
+                .iter()
+                .filter_map(|file| file.parent())
+                .find(|dir| dir.ends_with(CHECKPOINTS_DIR))
+            {
+                let _ = std::fs::remove_dir(checkpoints);
+            }
+            RetentionRepository::mark_audio_deleted(pool, &purge.meeting_id, now)
+                .await
+                .map_err(db_error)?;
+        }
+        PurgeAction::CompressTranscript => {
+            let file = &purge.files[0];
+            let size = file_size(file);
+            let zipped = compress_transcript(file)?;
+            let zipped_size = zipped.len() as u64;
+            std::fs::write(compressed_path(file), zipped).map_err(|e| {
+                format!("Failed to write {}: {}", compressed_path(file).display(), e)
+            })?;
+            remove_files(&purge.files)?;
+            return Ok(size.saturating_sub(zipped_size));
+        }
+        PurgeAction::DeleteTranscript => {
+            remove_files(&purge.files)?;
+            RetentionRepository::delete_transcript(pool, &purge.meeting_id, now)
+                .await
+                .map_err(db_error)?;
+        }
+        PurgeAction::DeleteSummary => {
+            RetentionRepository::delete_summary(pool, &purge.meeting_id)
+                .await
+                .map_err(db_error)?;
+        }
+    }
+    Ok(purge.bytes_freed)
+}
+
+/// Carries out the plan for `now`; a failed purge is reported and the rest
+/// still run
+pub async fn run_retention(
+    pool: &SqlitePool,
+    config: &RetentionConfig,
+    now: DateTime<Utc>,
+) -> Result<RetentionReport, String> {
+    let plan = plan_retention(pool, config, now).await?;
+    let mut report = RetentionReport::default();
+    for mut purge in plan.purges {
+        match apply_purge(pool, &purge, now).await {
+            Ok(bytes_freed) => {
+                purge.bytes_freed = bytes_freed;
+                report.bytes_freed += bytes_freed;
+                report.done.push(purge);
+            }
+            Err(error) => {
+                warn!(
+                    "⚠️ Retention {:?} failed for meeting {}: {}",
+                    purge.action, purge.meeting_id, error
+                );
+                report.failed.push(FailedPurge { purge, error });
+            }
+        }
+    }
+    info!(
+        "🧹 Retention run: {} purges done, {} failed, {} bytes freed",
+        report.done.len(),
+        report.failed.len(),
+        report.bytes_freed
+    );
+    Ok(report)
+}
+
+/// Playback and other uses of the recording fail with a clear message once
+/// the retention policy deleted it
+pub async fn ensure_recording_kept(pool: &SqlitePool, meeting_id: &str) -> Result<(), String> {
+    let deleted_at = RetentionRepository::audio_deleted_at(pool, meeting_id)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+    match deleted_at {
+        Some(at) => Err(format!(
+            "The recording was deleted by the retention policy on {}",
+            at.format("%Y-%m-%d")
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Runs the policy every `interval_hours` while it is enabled
+///
+/// The time of the last run is kept in the retention store, so a run that
+/// became due while the app was closed happens right after startup. The
+/// config is read again at least hourly, so changes apply without a
+/// restart. Runs are skipped while recording and retried an hour later.
+pub fn start_retention_task<R: Runtime>(app: &AppHandle<R>) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let config = load_retention_config(&app);
+            let wait = time_until_due(load_last_run(&app), config.interval_hours, Utc::now());
+            if !wait.is_zero() {
+                tokio::time::sleep(wait.min(RECHECK_INTERVAL)).await;
+                continue;
+            }
+
+            if !config.enabled {
+                tokio::time::sleep(RECHECK_INTERVAL).await;
+                continue;
+            }
+            // The store may have been edited by hand since it was saved
+            if let Err(e) = config.validate() {
+                warn!("⚠️ Retention run skipped, invalid config: {}", e);
+                tokio::time::sleep(RECHECK_INTERVAL).await;
+                continue;
+            }
+            if crate::audio::recording_commands::is_recording().await {
+                info!("ℹ️ Retention run skipped while recording");
+                tokio::time::sleep(RECHECK_INTERVAL).await;
+                continue;
+            }
+            let Some(state) = app.try_state::<AppState>() else {
+                tokio::time::sleep(RECHECK_INTERVAL).await;
+                continue;
+            };
+            let now = Utc::now();
+            match run_retention(state.db_manager.pool(), &config, now).await {
+                Ok(_) => {
+                    if let Err(e) = save_last_run(&app, now) {
+                        warn!("⚠️ {}", e);
+                        tokio::time::sleep(RECHECK_INTERVAL).await;
+                    }
+                }
+                Err(e) => {
+                    warn!("⚠️ Retention run failed: {}", e);
+                    tokio::time::sleep(RECHECK_INTERVAL).await;
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::{seed_meetings, test_pool};
+
+    const DAY: i64 = 24 * 3600;
+
+    /// meeting-0 is 200 days old, meeting-1 100 days, meeting-2 10 days; each
+    /// has a recording, checkpoint parts and a transcript file
+    async fn library(pool: &SqlitePool, root: &Path, now: DateTime<Utc>) {
+        seed_meetings(pool, 3, 2).await;
+        for (i, age_days) in [200, 100, 10].into_iter().enumerate() {
+            let folder = root.join(format!("meeting-{}", i));
+            std::fs::create_dir_all(folder.join(".checkpoints")).unwrap();
+            std::fs::write(folder.join("audio.mp4"), vec![0u8; 1000]).unwrap();
+            std::fs::write(
+                folder.join(".checkpoints").join("audio_chunk_000.mp4"),
+                vec![0u8; 500],
+            )
+            .unwrap();
+            std::fs::write(
+                folder.join("transcripts.json"),
+                "{\"segments\": \"segment text\"}\n".repeat(200),
+            )
+            .unwrap();
+            let created = now - chrono::Duration::seconds(age_days * DAY);
+            sqlx::query("UPDATE meetings SET created_at = ?, folder_path = ? WHERE id = ?")
+                .bind(created)
+                .bind(folder.to_string_lossy().to_string())
+                .bind(format!("meeting-{}", i))
+                .execute(pool)
+                .await
+                .unwrap();
+        }
+    }
+
+    fn actions(purges: &[PlannedPurge]) -> Vec<(&str, PurgeAction)> {
+        purges
+            .iter()
+            .map(|purge| (purge.meeting_id.as_str(), purge.action))
+            .collect()
+    }
+
+    async fn summary_count(pool: &SqlitePool) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM summary_processes")
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_old_recordings_are_purged_and_summaries_kept() {
+        let pool = test_pool().await;
+        let dir = tempfile::tempdir().unwrap();
+        let now = Utc::now();
+        library(&pool, dir.path(), now).await;
+        let config = RetentionConfig::default();
+
+        let plan = plan_retention(&pool, &config, now).await.unwrap();
+        assert_eq!(
+            actions(&plan.purges),
+            [
+                ("meeting-0", PurgeAction::DeleteAudio),
+                ("meeting-0", PurgeAction::CompressTranscript),
+                ("meeting-1", PurgeAction::DeleteAudio),
+                ("meeting-1", PurgeAction::CompressTranscript),
+            ]
+        );
+        assert_eq!(plan.purges[0].bytes_freed, 1500);
+        assert!(plan.bytes_freed > 3000, "{}", plan.bytes_freed);
+
+        // Nothing was touched by the preview
+        assert!(dir.path().join("meeting-0/audio.mp4").is_file());
+
+        let report = run_retention(&pool, &config, now).await.unwrap();
+        assert!(report.failed.is_empty(), "{:?}", report.failed);
+        assert_eq!(actions(&report.done), actions(&plan.purges));
+        // Compressions report what zipping actually saved, not the estimate
+        let zipped = file_size(&dir.path().join("meeting-0/transcripts.json.zip"));
+        assert_eq!(report.done[1].bytes_freed, 200 * 29 - zipped);
+        let done_bytes: u64 = report.done.iter().map(|purge| purge.bytes_freed).sum();
+        assert_eq!(report.bytes_freed, done_bytes);
+
+        let old = dir.path().join("meeting-0");
+        assert!(!old.join("audio.mp4").exists());
+        assert!(!old.join(".checkpoints").exists());
+        assert!(!old.join("transcripts.json").exists());
+        assert!(old.join("transcripts.json.zip").is_file());
+        let recent = dir.path().join("meeting-2");
+        assert!(recent.join("audio.mp4").is_file());
+        assert!(recent.join("transcripts.json").is_file());
+
+        assert!(ensure_recording_kept(&pool, "meeting-0")
+            .await
+            .unwrap_err()
+            .starts_with("The recording was deleted by the retention policy"));
+        assert_eq!(ensure_recording_kept(&pool, "meeting-2").await, Ok(()));
+        assert_eq!(summary_count(&pool).await, 2);
+
+        // A second run has nothing left to do
+        let plan = plan_retention(&pool, &config, now).await.unwrap();
+        assert!(plan.purges.is_empty(), "{:?}", plan.purges);
+    }
+
+    #[tokio::test]
+    async fn test_transcripts_and_summaries_only_go_when_configured() {
+        let pool = test_pool().await;
+        let dir = tempfile::tempdir().unwrap();
+        let now = Utc::now();
+        library(&pool, dir.path(), now).await;
+        let config = RetentionConfig {
+            audio_days: None,
+            transcript_days: Some(150),
+            summary_days: Some(180),
+            ..RetentionConfig::default()
+        };
+
+        let report = run_retention(&pool, &config, now).await.unwrap();
+        assert_eq!(
+            actions(&report.done),
+            [
+                ("meeting-0", PurgeAction::DeleteTranscript),
+                ("meeting-0", PurgeAction::DeleteSummary),
+            ]
+        );
+        assert!(dir.path().join("meeting-0/audio.mp4").is_file());
+        assert!(!dir.path().join("meeting-0/transcripts.json").exists());
+
+        let segments: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM transcripts WHERE meeting_id = 'meeting-0'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(segments, 0);
+        // meeting-2 keeps its summary, meeting-1 never had one
+        assert_eq!(summary_count(&pool).await, 1);
+
+        // Later, meeting-1 crosses the transcript threshold
+        let later = now + chrono::Duration::seconds(60 * DAY);
+        let plan = plan_retention(&pool, &config, later).await.unwrap();
+        assert_eq!(
+            actions(&plan.purges),
+            [("meeting-1", PurgeAction::DeleteTranscript)]
+        );
+    }
+
+    #[test]
+    fn test_overdue_runs_happen_at_startup() {
+        let now = Utc::now();
+        let hours_ago = |hours| Some(now - chrono::Duration::hours(hours));
+
+        assert_eq!(time_until_due(None, 24, now), Duration::ZERO);
+        // The app was closed past the scheduled time
+        assert_eq!(time_until_due(hours_ago(30), 24, now), Duration::ZERO);
+        assert_eq!(
+            time_until_due(hours_ago(20), 24, now),
+            Duration::from_secs(4 * 3600)
+        );
+    }
+
+    #[test]
+    fn test_invalid_configs_are_rejected() {
+        assert_eq!(RetentionConfig::default().validate(), Ok(()));
+        for invalid in [
+            RetentionConfig {
+                audio_days: Some(0),
+                ..RetentionConfig::default()
+            },
+            RetentionConfig {
+                summary_days: Some(0),
+                ..RetentionConfig::default()
+            },
+            RetentionConfig {
+                interval_hours: 0,
+                ..RetentionConfig::default()
+            },
+            RetentionConfig {
+                interval_hours: MAX_INTERVAL_HOURS + 1,
+                ..RetentionConfig::default()
+            },
+        ] {
+            assert!(invalid.validate().is_err(), "{:?}", invalid);
+        }
+    }
+}
@@ -0,0 +1,204 @@
+//! Cleanup of old summary runs that never produced a summary
+//!
+//! Failed and cancelled runs stay in `summary_processes` until the meeting
+//! is summarized again, and crashes leave runs that never finish. Unlike the
+//! file purge this is on by default: it never touches a summary, only the
+//! record of runs without one.
+
+use super::policy::MAX_INTERVAL_HOURS;
+use crate::database::repositories::summary::SummaryProcessesRepository;
+use crate::state::AppState;
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_store::StoreExt;
+
+const CONFIG_STORE: &str = "summary-process-cleanup.json";
+const CONFIG_KEY: &str = "config";
+
+/// Statuses never cleaned up; everything else is a failed (or cancelled)
+/// run or one left behind unfinished
+const KEEP_STATUSES: [&str; 1] = ["completed"];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SummaryProcessCleanupConfig {
+    /// Runs at startup and then every `interval_hours`
+    pub enabled: bool,
+    /// Runs last updated longer ago than this are removed
+    pub keep_days: u32,
+    pub interval_hours: u64,
+}
+
+impl Default for SummaryProcessCleanupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            keep_days: 30,
+            interval_hours: 24,
+        }
+    }
+}
+
+impl SummaryProcessCleanupConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.keep_days == 0 {
+            return Err("Failed summary runs must be kept for at least one day".to_string());
+        }
+        if !(1..=MAX_INTERVAL_HOURS).contains(&self.interval_hours) {
+            return Err(format!(
+                "Cleanup interval must be between 1 and {} hours",
+                MAX_INTERVAL_HOURS
+            ));
+        }
+        Ok(())
+    }
+}
+
+pub fn load_cleanup_config<R: Runtime>(app: &AppHandle<R>) -> SummaryProcessCleanupConfig {
+    let stored = app
+        .store(CONFIG_STORE)
+        .ok()
+        .and_then(|store| store.get(CONFIG_KEY));
+    match stored.map(serde_json::from_value::<SummaryProcessCleanupConfig>) {
+        Some(Ok(config)) => config,
+        Some(Err(e)) => {
+            warn!(
+                "⚠️ Invalid summary process cleanup config in store, using defaults: {}",
+                e
+            );
+            SummaryProcessCleanupConfig::default()
+        }
+        None => SummaryProcessCleanupConfig::default(),
+    }
+}
+
+pub fn save_cleanup_config<R: Runtime>(
+    app: &AppHandle<R>,
+    config: &SummaryProcessCleanupConfig,
+) -> Result<(), String> {
+    let store = app
+        .store(CONFIG_STORE)
+        .map_err(|e| format!("Failed to open summary process cleanup store: {}", e))?;
+    let value = serde_json::to_value(config)
+        .map_err(|e| format!("Failed to serialize summary process cleanup config: {}", e))?;
+    store.set(CONFIG_KEY, value);
+    store
+        .save()
+        .map_err(|e| format!("Failed to save summary process cleanup config: {}", e))
+}
+
+/// Removes the runs older than `keep_days` at `now`; returns how many
+pub async fn cleanup_summary_processes(
+    pool: &SqlitePool,
+    config: &SummaryProcessCleanupConfig,
+    now: DateTime<Utc>,
+) -> Result<u64, String> {
+    let older_than = now - chrono::Duration::days(config.keep_days as i64);
+    let removed = SummaryProcessesRepository::prune_old(pool, older_than, &KEEP_STATUSES)
+        .await
+        .map_err(|e| format!("Failed to clean up summary runs: {}", e))?;
+    if removed > 0 {
+        info!(
+            "🧹 Removed {} summary run(s) without a summary older than {} days",
+            removed, config.keep_days
+        );
+    }
+    Ok(removed)
+}
+
+/// Cleans up once at startup, then every `interval_hours` while enabled
+///
+/// The config is read again before each run, so changes apply from the next
+/// one.
+pub fn start_summary_process_cleanup<R: Runtime>(app: &AppHandle<R>) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let config = load_cleanup_config(&app);
+            if config.enabled {
+                if let Some(state) = app.try_state::<AppState>() {
+                    if let Err(e) =
+                        cleanup_summary_processes(state.db_manager.pool(), &config, Utc::now())
+                            .await
+                    {
+                        warn!("⚠️ {}", e);
+                    }
+                }
+            }
+            tokio::time::sleep(Duration::from_secs(config.interval_hours.max(1) * 3600)).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::{seed_meetings, test_pool};
+
+    #[tokio::test]
+    async fn test_cleanup_keeps_summaries_and_recent_runs() {
+        let pool = test_pool().await;
+        // meeting-0 and meeting-2 have a completed summary
+        seed_meetings(&pool, 4, 0).await;
+        let now = Utc::now();
+        for (meeting_id, status, age_days) in
+            [("meeting-1", "failed", 45), ("meeting-3", "processing", 90)]
+        {
+            sqlx::query(
+                "INSERT INTO summary_processes (meeting_id, status, created_at, updated_at) VALUES (?, ?, ?, ?)",
+            )
+            .bind(meeting_id)
+            .bind(status)
+            .bind(now - chrono::Duration::days(age_days))
+            .bind(now - chrono::Duration::days(age_days))
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        sqlx::query("UPDATE summary_processes SET updated_at = ? WHERE status = 'completed'")
+            .bind(now - chrono::Duration::days(365))
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let config = SummaryProcessCleanupConfig {
+            keep_days: 60,
+            ..Default::default()
+        };
+        // The failed run is within 60 days, the unfinished one isn't
+        assert_eq!(cleanup_summary_processes(&pool, &config, now).await, Ok(1));
+        let config = SummaryProcessCleanupConfig {
+            keep_days: 30,
+            ..Default::default()
+        };
+        assert_eq!(cleanup_summary_processes(&pool, &config, now).await, Ok(1));
+
+        let kept: Vec<String> =
+            sqlx::query_scalar("SELECT meeting_id FROM summary_processes ORDER BY meeting_id")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(kept, vec!["meeting-0", "meeting-2"]);
+    }
+
+    #[test]
+    fn test_invalid_configs_are_rejected() {
+        assert_eq!(SummaryProcessCleanupConfig::default().validate(), Ok(()));
+        for config in [
+            SummaryProcessCleanupConfig {
+                keep_days: 0,
+                ..Default::default()
+            },
+            SummaryProcessCleanupConfig {
+                interval_hours: MAX_INTERVAL_HOURS + 1,
+                ..Default::default()
+            },
+        ] {
+            assert!(config.validate().is_err(), "{:?}", config);
+        }
+    }
+}
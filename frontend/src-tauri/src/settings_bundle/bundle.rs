@@ -0,0 +1,727 @@
+use super::crypto::{self, EncryptedSecrets};
+use crate::database::models::NamedPipelineInput;
+use crate::database::repositories::pipeline::PipelinesRepository;
+use crate::database::repositories::setting::SettingsRepository;
+use crate::summary::templates::validate_and_parse_template;
+use chrono::Utc;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{SqliteConnection, SqlitePool};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Bumped whenever the bundle layout changes incompatibly
+pub const SETTINGS_BUNDLE_VERSION: u32 = 1;
+
+/// Summary provider -> `settings` column holding its API key
+const SUMMARY_KEY_COLUMNS: &[(&str, &str)] = &[
+    ("openai", "openaiApiKey"),
+    ("claude", "anthropicApiKey"),
+    ("ollama", "ollamaApiKey"),
+    ("groq", "groqApiKey"),
+    ("openrouter", "openRouterApiKey"),
+    ("gemini", "geminiApiKey"),
+];
+
+/// Transcription provider -> `transcript_settings` column holding its API key
+const TRANSCRIPT_KEY_COLUMNS: &[(&str, &str)] = &[
+    ("localWhisper", "whisperApiKey"),
+    ("deepgram", "deepgramApiKey"),
+    ("elevenLabs", "elevenLabsApiKey"),
+    ("groq", "groqApiKey"),
+    ("openai", "openaiApiKey"),
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelConfigEntry {
+    pub provider: String,
+    pub model: String,
+    pub whisper_model: String,
+    pub ollama_endpoint: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptConfigEntry {
+    pub provider: String,
+    pub model: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateEntry {
+    pub id: String,
+    pub content: Value,
+}
+
+/// A named pipeline (template, model and output presets) by id
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PipelineEntry {
+    pub id: String,
+    #[serde(flatten)]
+    pub settings: NamedPipelineInput,
+}
+
+/// Portable snapshot of the app configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettingsBundle {
+    pub schema_version: u32,
+    pub exported_at: String,
+    pub model_config: Option<ModelConfigEntry>,
+    pub transcript_config: Option<TranscriptConfigEntry>,
+    /// Custom summary templates (bundled/built-in templates are not exported)
+    #[serde(default)]
+    pub templates: Vec<TemplateEntry>,
+    pub recording_preferences: Option<Value>,
+    /// Named pipelines, including edits to the built-in ones
+    #[serde(default)]
+    pub pipelines: Vec<PipelineEntry>,
+    /// Prompt used to generate clarifying questions
+    #[serde(default)]
+    pub question_prompt: Option<Value>,
+    /// API keys, only ever present in encrypted form
+    pub secrets: Option<EncryptedSecrets>,
+}
+
+/// Plaintext form of `SettingsBundle::secrets`
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SecretsPayload {
+    /// Summary provider -> API key
+    api_keys: BTreeMap<String, String>,
+    /// Transcription provider -> API key
+    transcript_api_keys: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportReport {
+    pub model_config: bool,
+    pub transcript_config: bool,
+    pub templates: usize,
+    pub api_keys: usize,
+    pub recording_preferences: bool,
+    pub pipelines: usize,
+    pub question_prompt: bool,
+}
+
+/// Builds a bundle from the database and the custom templates directory;
+/// settings kept in stores are passed in by the caller
+///
+/// API keys are included only when `secrets_passphrase` is given, and are
+/// encrypted with it.
+pub async fn collect_bundle(
+    pool: &SqlitePool,
+    templates_dir: Option<&Path>,
+    recording_preferences: Option<Value>,
+    question_prompt: Option<Value>,
+    secrets_passphrase: Option<&str>,
+) -> Result<SettingsBundle, String> {
+    let model_config = SettingsRepository::get_model_config(pool)
+        .await
+        .map_err(|e| format!("Failed to read model config: {}", e))?
+        .map(|s| ModelConfigEntry {
+            provider: s.provider,
+            model: s.model,
+            whisper_model: s.whisper_model,
+            ollama_endpoint: s.ollama_endpoint,
+        });
+    let transcript_config = SettingsRepository::get_transcript_config(pool)
+        .await
+        .map_err(|e| format!("Failed to read transcript config: {}", e))?
+        .map(|s| TranscriptConfigEntry {
+            provider: s.provider,
+            model: s.model,
+        });
+
+    let templates = match templates_dir {
+        Some(dir) => read_templates(dir)?,
+        None => Vec::new(),
+    };
+
+    let pipelines = PipelinesRepository::list(pool)
+        .await
+        .map_err(|e| format!("Failed to read pipelines: {}", e))?
+        .into_iter()
+        .map(|pipeline| PipelineEntry {
+            id: pipeline.id,
+            settings: NamedPipelineInput {
+                name: pipeline.name,
+                template_id: pipeline.template_id,
+                model_provider: pipeline.model_provider,
+                model_name: pipeline.model_name,
+                compact_transcript: pipeline.compact_transcript,
+                provenance: pipeline.provenance,
+                citations: pipeline.citations,
+                anonymize: pipeline.anonymize,
+                refine: pipeline.refine,
+                output_format: pipeline.output_format,
+                temperature: pipeline.temperature,
+                max_tokens: pipeline.max_tokens,
+                webhook_ids: pipeline.webhook_ids,
+            },
+        })
+        .collect();
+
+    let secrets = match secrets_passphrase {
+        Some(passphrase) => {
+            let mut payload = SecretsPayload::default();
+            for (provider, _) in SUMMARY_KEY_COLUMNS {
+                if let Some(key) = SettingsRepository::get_api_key(pool, provider)
+                    .await
+                    .map_err(|e| format!("Failed to read API key for {}: {}", provider, e))?
+                    .filter(|k| !k.is_empty())
+                {
+                    payload.api_keys.insert(provider.to_string(), key);
+                }
+            }
+            for (provider, _) in TRANSCRIPT_KEY_COLUMNS {
+                if let Some(key) = SettingsRepository::get_transcript_api_key(pool, provider)
+                    .await
+                    .map_err(|e| format!("Failed to read API key for {}: {}", provider, e))?
+                    .filter(|k| !k.is_empty())
+                {
+                    payload.transcript_api_keys.insert(provider.to_string(), key);
+                }
+            }
+            let plaintext = serde_json::to_vec(&payload)
+                .map_err(|e| format!("Failed to serialize secrets: {}", e))?;
+            Some(crypto::encrypt(&plaintext, passphrase)?)
+        }
+        None => None,
+    };
+
+    Ok(SettingsBundle {
+        schema_version: SETTINGS_BUNDLE_VERSION,
+        exported_at: Utc::now().to_rfc3339(),
+        model_config,
+        transcript_config,
+        templates,
+        recording_preferences,
+        pipelines,
+        question_prompt,
+        secrets,
+    })
+}
+
+/// Reads every `*.json` custom template in `dir`
+fn read_templates(dir: &Path) -> Result<Vec<TemplateEntry>, String> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read templates directory {:?}: {}", dir, e))?;
+    let mut templates = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let Some(id) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read template {:?}: {}", path, e))?;
+        match serde_json::from_str::<Value>(&content) {
+            Ok(content) => templates.push(TemplateEntry {
+                id: id.to_string(),
+                content,
+            }),
+            Err(e) => warn!("Skipping unreadable template {:?}: {}", path, e),
+        }
+    }
+    templates.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(templates)
+}
+
+fn is_valid_template_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// A bundle that passed `validate_bundle`, with its secrets decrypted
+pub struct ValidatedBundle<'a> {
+    bundle: &'a SettingsBundle,
+    summary_keys: Vec<(&'static str, String)>,
+    transcript_keys: Vec<(&'static str, String)>,
+}
+
+/// Applies a bundle to the database and templates directory
+///
+/// See `validate_bundle` and `apply_validated_bundle`; callers that need to
+/// act between the two (e.g. check the recordings folder) call them directly.
+pub async fn apply_bundle(
+    pool: &SqlitePool,
+    bundle: &SettingsBundle,
+    passphrase: Option<&str>,
+    templates_dir: Option<&Path>,
+) -> Result<ImportReport, String> {
+    let validated = validate_bundle(bundle, passphrase, templates_dir).await?;
+    apply_validated_bundle(pool, validated, templates_dir).await
+}
+
+/// Checks the schema version, passphrase, templates and pipelines without
+/// writing anything
+///
+/// The key derivation is slow on purpose, so decryption runs on a blocking
+/// thread.
+pub async fn validate_bundle<'a>(
+    bundle: &'a SettingsBundle,
+    passphrase: Option<&str>,
+    templates_dir: Option<&Path>,
+) -> Result<ValidatedBundle<'a>, String> {
+    if bundle.schema_version != SETTINGS_BUNDLE_VERSION {
+        return Err(format!(
+            "Unsupported settings bundle version {} (expected {})",
+            bundle.schema_version, SETTINGS_BUNDLE_VERSION
+        ));
+    }
+
+    let secrets = match &bundle.secrets {
+        Some(encrypted) => {
+            let passphrase = passphrase
+                .filter(|p| !p.is_empty())
+                .ok_or_else(|| "This bundle contains API keys; a passphrase is required".to_string())?;
+            let (encrypted, passphrase) = (encrypted.clone(), passphrase.to_string());
+            let plaintext =
+                tokio::task::spawn_blocking(move || crypto::decrypt(&encrypted, &passphrase))
+                    .await
+                    .map_err(|e| format!("Failed to decrypt secrets: {}", e))??;
+            serde_json::from_slice::<SecretsPayload>(&plaintext)
+                .map_err(|e| format!("Invalid secrets payload: {}", e))?
+        }
+        None => SecretsPayload::default(),
+    };
+
+    let summary_keys = map_key_columns(&secrets.api_keys, SUMMARY_KEY_COLUMNS)?;
+    let transcript_keys = map_key_columns(&secrets.transcript_api_keys, TRANSCRIPT_KEY_COLUMNS)?;
+
+    for template in &bundle.templates {
+        if !is_valid_template_id(&template.id) {
+            return Err(format!("Invalid template id '{}'", template.id));
+        }
+        validate_and_parse_template(&template.content.to_string())
+            .map_err(|e| format!("Invalid template '{}': {}", template.id, e))?;
+    }
+    if !bundle.templates.is_empty() && templates_dir.is_none() {
+        return Err("No templates directory available to import templates into".to_string());
+    }
+
+    for pipeline in &bundle.pipelines {
+        if pipeline.id.trim().is_empty() {
+            return Err("Pipeline without an id in bundle".to_string());
+        }
+        PipelinesRepository::validate(&pipeline.settings)
+            .map_err(|e| format!("Invalid pipeline '{}': {}", pipeline.id, e))?;
+    }
+
+    Ok(ValidatedBundle {
+        bundle,
+        summary_keys,
+        transcript_keys,
+    })
+}
+
+/// Writes a validated bundle
+///
+/// Database changes run in one transaction. Templates are staged next to
+/// their destination and moved into place just before the commit; if that
+/// or the commit fails, the templates they replaced are put back.
+pub async fn apply_validated_bundle(
+    pool: &SqlitePool,
+    validated: ValidatedBundle<'_>,
+    templates_dir: Option<&Path>,
+) -> Result<ImportReport, String> {
+    let ValidatedBundle {
+        bundle,
+        summary_keys,
+        transcript_keys,
+    } = validated;
+
+    // Stage templates so a failed transaction leaves the directory untouched
+    let mut staged: Vec<(PathBuf, PathBuf)> = Vec::new();
+    if let Some(dir) = templates_dir.filter(|_| !bundle.templates.is_empty()) {
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create templates directory: {}", e))?;
+        for template in &bundle.templates {
+            let target = dir.join(format!("{}.json", template.id));
+            let staging = dir.join(format!(".{}.json.import", template.id));
+            let content = serde_json::to_string_pretty(&template.content)
+                .map_err(|e| format!("Failed to serialize template '{}': {}", template.id, e))?;
+            if let Err(e) = fs::write(&staging, content) {
+                discard_staged(&staged);
+                let _ = fs::remove_file(&staging);
+                return Err(format!("Failed to stage template '{}': {}", template.id, e));
+            }
+            staged.push((staging, target));
+        }
+    }
+
+    let mut transaction = match pool.begin().await {
+        Ok(transaction) => transaction,
+        Err(e) => {
+            discard_staged(&staged);
+            return Err(format!("Failed to apply settings: {}", e));
+        }
+    };
+    let written = apply_database(&mut transaction, bundle, &summary_keys, &transcript_keys).await;
+    if let Err(e) = written {
+        discard_staged(&staged);
+        return Err(format!("Failed to apply settings: {}", e));
+    }
+
+    let installed = match install_staged(&staged) {
+        Ok(installed) => installed,
+        Err(e) => {
+            discard_staged(&staged);
+            return Err(e);
+        }
+    };
+    if let Err(e) = transaction.commit().await {
+        restore_installed(&installed);
+        return Err(format!("Failed to apply settings: {}", e));
+    }
+    for backup in installed.iter().filter_map(|(_, backup)| backup.as_ref()) {
+        let _ = fs::remove_file(backup);
+    }
+
+    let report = ImportReport {
+        model_config: bundle.model_config.is_some(),
+        transcript_config: bundle.transcript_config.is_some(),
+        templates: staged.len(),
+        api_keys: summary_keys.len() + transcript_keys.len(),
+        recording_preferences: bundle.recording_preferences.is_some(),
+        pipelines: bundle.pipelines.len(),
+        question_prompt: bundle.question_prompt.is_some(),
+    };
+    info!("Imported settings bundle: {:?}", report);
+    Ok(report)
+}
+
+fn discard_staged(staged: &[(PathBuf, PathBuf)]) {
+    for (staging, _) in staged {
+        let _ = fs::remove_file(staging);
+    }
+}
+
+/// Moves staged templates into place, keeping each template it replaces as
+/// a backup; on failure the ones already moved are rolled back
+///
+/// Returns the installed targets with their backups.
+fn install_staged(
+    staged: &[(PathBuf, PathBuf)],
+) -> Result<Vec<(PathBuf, Option<PathBuf>)>, String> {
+    let mut installed: Vec<(PathBuf, Option<PathBuf>)> = Vec::new();
+    for (staging, target) in staged {
+        let backup = staging.with_extension("backup");
+        let backup = if target.exists() {
+            if let Err(e) = fs::rename(target, &backup) {
+                restore_installed(&installed);
+                return Err(format!("Failed to replace template {:?}: {}", target, e));
+            }
+            Some(backup)
+        } else {
+            None
+        };
+        if let Err(e) = fs::rename(staging, target) {
+            if let Some(backup) = &backup {
+                let _ = fs::rename(backup, target);
+            }
+            restore_installed(&installed);
+            return Err(format!("Failed to install template {:?}: {}", target, e));
+        }
+        installed.push((target.clone(), backup));
+    }
+    Ok(installed)
+}
+
+/// Puts back the templates `install_staged` replaced
+fn restore_installed(installed: &[(PathBuf, Option<PathBuf>)]) {
+    for (target, backup) in installed {
+        match backup {
+            Some(backup) => {
+                let _ = fs::rename(backup, target);
+            }
+            None => {
+                let _ = fs::remove_file(target);
+            }
+        }
+    }
+}
+
+/// Resolves provider names to their key columns, rejecting unknown providers
+fn map_key_columns(
+    keys: &BTreeMap<String, String>,
+    columns: &[(&'static str, &'static str)],
+) -> Result<Vec<(&'static str, String)>, String> {
+    keys.iter()
+        .map(|(provider, key)| {
+            columns
+                .iter()
+                .find(|(p, _)| p == provider)
+                .map(|(_, column)| (*column, key.clone()))
+                .ok_or_else(|| format!("Unknown provider '{}' in secrets", provider))
+        })
+        .collect()
+}
+
+/// Writes the database part of the bundle; the caller commits
+async fn apply_database(
+    transaction: &mut SqliteConnection,
+    bundle: &SettingsBundle,
+    summary_keys: &[(&str, String)],
+    transcript_keys: &[(&str, String)],
+) -> Result<(), sqlx::Error> {
+    if let Some(config) = &bundle.model_config {
+        sqlx::query(
+            r#"
+            INSERT INTO settings (id, provider, model, whisperModel, ollamaEndpoint)
+            VALUES ('1', $1, $2, $3, $4)
+            ON CONFLICT(id) DO UPDATE SET
+                provider = excluded.provider,
+                model = excluded.model,
+                whisperModel = excluded.whisperModel,
+                ollamaEndpoint = excluded.ollamaEndpoint
+            "#,
+        )
+        .bind(&config.provider)
+        .bind(&config.model)
+        .bind(&config.whisper_model)
+        .bind(&config.ollama_endpoint)
+        .execute(&mut *transaction)
+        .await?;
+    }
+
+    for (column, key) in summary_keys {
+        let query = format!(
+            r#"
+            INSERT INTO settings (id, provider, model, whisperModel, "{}")
+            VALUES ('1', 'openai', 'gpt-4o-2024-11-20', 'large-v3', $1)
+            ON CONFLICT(id) DO UPDATE SET
+                "{}" = $1
+            "#,
+            column, column
+        );
+        sqlx::query(&query).bind(key).execute(&mut *transaction).await?;
+    }
+
+    if let Some(config) = &bundle.transcript_config {
+        sqlx::query(
+            r#"
+            INSERT INTO transcript_settings (id, provider, model)
+            VALUES ('1', $1, $2)
+            ON CONFLICT(id) DO UPDATE SET
+                provider = excluded.provider,
+                model = excluded.model
+            "#,
+        )
+        .bind(&config.provider)
+        .bind(&config.model)
+        .execute(&mut *transaction)
+        .await?;
+    }
+
+    for (column, key) in transcript_keys {
+        let query = format!(
+            r#"
+            INSERT INTO transcript_settings (id, provider, model, "{}")
+            VALUES ('1', 'localWhisper', 'large-v3', $1)
+            ON CONFLICT(id) DO UPDATE SET
+                "{}" = $1
+            "#,
+            column, column
+        );
+        sqlx::query(&query).bind(key).execute(&mut *transaction).await?;
+    }
+
+    for pipeline in &bundle.pipelines {
+        PipelinesRepository::upsert(transaction, &pipeline.id, &pipeline.settings).await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::test_pool;
+
+    const TEMPLATE_JSON: &str = include_str!("../../templates/standard_meeting.json");
+
+    async fn seeded_pool() -> SqlitePool {
+        let pool = test_pool().await;
+        SettingsRepository::save_model_config(
+            &pool,
+            "claude",
+            "claude-sonnet",
+            "large-v3",
+            Some("http://ollama:11434"),
+        )
+        .await
+        .unwrap();
+        SettingsRepository::save_api_key(&pool, "claude", "sk-ant-secret").await.unwrap();
+        SettingsRepository::save_api_key(&pool, "openrouter", "sk-or-secret").await.unwrap();
+        SettingsRepository::save_transcript_config(&pool, "deepgram", "nova-2").await.unwrap();
+        SettingsRepository::save_transcript_api_key(&pool, "deepgram", "dg-secret")
+            .await
+            .unwrap();
+        PipelinesRepository::create(
+            &pool,
+            &NamedPipelineInput {
+                name: "Board minutes".to_string(),
+                template_id: Some("team_sync".to_string()),
+                temperature: Some(0.2),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        pool
+    }
+
+    fn write_template(dir: &Path, id: &str) {
+        fs::write(dir.join(format!("{}.json", id)), TEMPLATE_JSON).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_round_trip_full_bundle() {
+        let source = seeded_pool().await;
+        let source_templates = tempfile::tempdir().unwrap();
+        write_template(source_templates.path(), "team_sync");
+        let prefs = serde_json::json!({ "save_folder": "/tmp/rec", "auto_save": false, "file_format": "wav" });
+        let question_prompt = serde_json::json!({ "examples": ["Who owns the budget?"] });
+
+        let bundle = collect_bundle(
+            &source,
+            Some(source_templates.path()),
+            Some(prefs.clone()),
+            Some(question_prompt.clone()),
+            Some("passphrase"),
+        )
+        .await
+        .unwrap();
+
+        // Secrets never appear in plaintext in the serialized bundle
+        let serialized = serde_json::to_string(&bundle).unwrap();
+        for secret in ["sk-ant-secret", "sk-or-secret", "dg-secret"] {
+            assert!(!serialized.contains(secret));
+        }
+
+        let bundle: SettingsBundle = serde_json::from_str(&serialized).unwrap();
+        let target = test_pool().await;
+        let target_templates = tempfile::tempdir().unwrap();
+        fs::write(target_templates.path().join("team_sync.json"), "{}").unwrap();
+        let report = apply_bundle(&target, &bundle, Some("passphrase"), Some(target_templates.path()))
+            .await
+            .unwrap();
+
+        assert!(report.model_config);
+        assert!(report.transcript_config);
+        assert_eq!(report.templates, 1);
+        assert_eq!(report.api_keys, 3);
+        assert_eq!(bundle.recording_preferences, Some(prefs));
+        assert_eq!(bundle.question_prompt, Some(question_prompt));
+        assert!(report.question_prompt);
+        // Both built-in pipelines and the custom one
+        assert_eq!(report.pipelines, 3);
+        let summarize = |pipelines: Vec<crate::database::models::NamedPipeline>| {
+            pipelines
+                .into_iter()
+                .map(|p| (p.id, p.name, p.template_id, p.temperature, p.built_in))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            summarize(PipelinesRepository::list(&target).await.unwrap()),
+            summarize(PipelinesRepository::list(&source).await.unwrap())
+        );
+
+        let config = SettingsRepository::get_model_config(&target).await.unwrap().unwrap();
+        assert_eq!(config.provider, "claude");
+        assert_eq!(config.model, "claude-sonnet");
+        assert_eq!(config.ollama_endpoint.as_deref(), Some("http://ollama:11434"));
+        assert_eq!(
+            SettingsRepository::get_api_key(&target, "claude").await.unwrap().as_deref(),
+            Some("sk-ant-secret")
+        );
+        assert_eq!(
+            SettingsRepository::get_api_key(&target, "openrouter").await.unwrap().as_deref(),
+            Some("sk-or-secret")
+        );
+        let transcript = SettingsRepository::get_transcript_config(&target).await.unwrap().unwrap();
+        assert_eq!(transcript.provider, "deepgram");
+        assert_eq!(transcript.model, "nova-2");
+        assert_eq!(
+            SettingsRepository::get_transcript_api_key(&target, "deepgram")
+                .await
+                .unwrap()
+                .as_deref(),
+            Some("dg-secret")
+        );
+        // The existing template was replaced and its backup removed
+        let installed = fs::read_to_string(target_templates.path().join("team_sync.json")).unwrap();
+        assert_ne!(installed, "{}");
+        assert_eq!(fs::read_dir(target_templates.path()).unwrap().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_export_without_secrets_omits_keys() {
+        let source = seeded_pool().await;
+        let bundle = collect_bundle(&source, None, None, None, None).await.unwrap();
+        assert!(bundle.secrets.is_none());
+        assert!(bundle.model_config.is_some());
+
+        let target = test_pool().await;
+        let report = apply_bundle(&target, &bundle, None, None).await.unwrap();
+        assert_eq!(report.api_keys, 0);
+        assert_eq!(SettingsRepository::get_api_key(&target, "claude").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_wrong_passphrase_applies_nothing() {
+        let source = seeded_pool().await;
+        let source_templates = tempfile::tempdir().unwrap();
+        write_template(source_templates.path(), "team_sync");
+        let bundle = collect_bundle(&source, Some(source_templates.path()), None, None, Some("right"))
+            .await
+            .unwrap();
+
+        let target = test_pool().await;
+        SettingsRepository::save_model_config(&target, "openai", "gpt-4o", "base", None)
+            .await
+            .unwrap();
+        let target_templates = tempfile::tempdir().unwrap();
+
+        let result = apply_bundle(&target, &bundle, Some("wrong"), Some(target_templates.path())).await;
+        assert!(result.is_err());
+        let result = apply_bundle(&target, &bundle, None, Some(target_templates.path())).await;
+        assert!(result.is_err());
+
+        let config = SettingsRepository::get_model_config(&target).await.unwrap().unwrap();
+        assert_eq!(config.provider, "openai");
+        assert_eq!(config.model, "gpt-4o");
+        assert_eq!(SettingsRepository::get_api_key(&target, "claude").await.unwrap(), None);
+        assert!(SettingsRepository::get_transcript_config(&target).await.unwrap().is_none());
+        assert_eq!(PipelinesRepository::list(&target).await.unwrap().len(), 2);
+        assert_eq!(fs::read_dir(target_templates.path()).unwrap().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_unknown_schema_version_and_bad_templates() {
+        let source = seeded_pool().await;
+        let mut bundle = collect_bundle(&source, None, None, None, None).await.unwrap();
+        let target = test_pool().await;
+
+        bundle.schema_version = SETTINGS_BUNDLE_VERSION + 1;
+        assert!(apply_bundle(&target, &bundle, None, None).await.is_err());
+
+        bundle.schema_version = SETTINGS_BUNDLE_VERSION;
+        bundle.templates.push(TemplateEntry {
+            id: "../escape".to_string(),
+            content: serde_json::from_str(TEMPLATE_JSON).unwrap(),
+        });
+        let templates = tempfile::tempdir().unwrap();
+        assert!(apply_bundle(&target, &bundle, None, Some(templates.path())).await.is_err());
+        assert!(SettingsRepository::get_model_config(&target).await.unwrap().is_none());
+    }
+}
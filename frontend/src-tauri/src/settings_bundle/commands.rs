@@ -0,0 +1,170 @@
+use super::bundle::{
+    apply_validated_bundle, collect_bundle, validate_bundle, ImportReport, SettingsBundle,
+};
+use crate::audio::recording_preferences::{
+    ensure_recordings_directory, load_recording_preferences, save_recording_preferences,
+    RecordingPreferences,
+};
+use crate::state::AppState;
+use crate::summary::question_prompt::{
+    load_question_prompt_config, save_question_prompt_config, QuestionPromptConfig,
+};
+use crate::summary::templates::get_custom_templates_dir;
+use log::{error as log_error, info as log_info};
+use tauri::{AppHandle, Runtime};
+
+/// Exports model/transcription config, custom templates, named pipelines,
+/// the question prompt and recording preferences to a JSON bundle at `path`
+///
+/// API keys are only included when `include_secrets` is set, and are then
+/// encrypted with `passphrase` (required in that case).
+#[tauri::command]
+pub async fn api_export_settings<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    path: String,
+    include_secrets: bool,
+    passphrase: Option<String>,
+) -> Result<(), String> {
+    log_info!(
+        "api_export_settings called: path={}, include_secrets={}",
+        path,
+        include_secrets
+    );
+
+    let secrets_passphrase = if include_secrets {
+        match passphrase.as_deref() {
+            Some(p) if !p.is_empty() => Some(p),
+            _ => return Err("A passphrase is required to export API keys".to_string()),
+        }
+    } else {
+        None
+    };
+
+    let recording_preferences = load_recording_preferences(&app)
+        .await
+        .ok()
+        .and_then(|prefs| serde_json::to_value(prefs).ok());
+    let question_prompt = serde_json::to_value(load_question_prompt_config(&app)).ok();
+    let templates_dir = get_custom_templates_dir();
+
+    let bundle = collect_bundle(
+        state.db_manager.pool(),
+        templates_dir.as_deref(),
+        recording_preferences,
+        question_prompt,
+        secrets_passphrase,
+    )
+    .await?;
+
+    let json = serde_json::to_string_pretty(&bundle)
+        .map_err(|e| format!("Failed to serialize settings bundle: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| {
+        log_error!("Failed to write settings bundle to {}: {}", path, e);
+        format!("Failed to write settings bundle: {}", e)
+    })?;
+
+    log_info!("✅ Settings exported to {}", path);
+    Ok(())
+}
+
+/// Imports a settings bundle written by `api_export_settings`
+///
+/// The bundle is fully validated (schema version, passphrase, templates,
+/// pipelines, question prompt, recording preferences) before anything is
+/// applied. If a step fails, the settings already written are restored.
+#[tauri::command]
+pub async fn api_import_settings<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    path: String,
+    passphrase: Option<String>,
+) -> Result<ImportReport, String> {
+    log_info!("api_import_settings called: path={}", path);
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read settings bundle: {}", e))?;
+    let bundle: SettingsBundle = serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid settings bundle: {}", e))?;
+
+    let recording_preferences = match &bundle.recording_preferences {
+        Some(value) => Some(
+            serde_json::from_value::<RecordingPreferences>(value.clone())
+                .map_err(|e| format!("Invalid recording preferences in bundle: {}", e))?,
+        ),
+        None => None,
+    };
+    let question_prompt = match &bundle.question_prompt {
+        Some(value) => {
+            let config = serde_json::from_value::<QuestionPromptConfig>(value.clone())
+                .map_err(|e| format!("Invalid question prompt in bundle: {}", e))?;
+            config.validate()?;
+            Some(config)
+        }
+        None => None,
+    };
+
+    let templates_dir = get_custom_templates_dir();
+    let validated = validate_bundle(&bundle, passphrase.as_deref(), templates_dir.as_deref())
+        .await
+        .map_err(|e| {
+            log_error!("Settings import failed: {}", e);
+            e
+        })?;
+
+    // The save folder must be usable here before anything else is applied,
+    // but a bundle that fails validation must not create it
+    if let Some(prefs) = &recording_preferences {
+        ensure_recordings_directory(&prefs.save_folder)
+            .map_err(|e| format!("Recording folder from bundle is not usable: {}", e))?;
+    }
+
+    // Store-backed settings are written before the database commits; if
+    // anything after them fails, their previous values are put back
+    let previous_preferences = match &recording_preferences {
+        Some(_) => Some(
+            load_recording_preferences(&app)
+                .await
+                .map_err(|e| format!("Failed to read current recording preferences: {}", e))?,
+        ),
+        None => None,
+    };
+    let previous_question_prompt = question_prompt
+        .as_ref()
+        .map(|_| load_question_prompt_config(&app));
+
+    let _write = state.db_manager.write_lock().await;
+    let result = async {
+        if let Some(prefs) = &recording_preferences {
+            save_recording_preferences(&app, prefs)
+                .await
+                .map_err(|e| format!("Failed to save recording preferences: {}", e))?;
+        }
+        if let Some(config) = &question_prompt {
+            save_question_prompt_config(&app, config)?;
+        }
+        apply_validated_bundle(state.db_manager.pool(), validated, templates_dir.as_deref()).await
+    }
+    .await;
+
+    let report = match result {
+        Ok(report) => report,
+        Err(e) => {
+            log_error!("Settings import failed, restoring previous settings: {}", e);
+            if let Some(prefs) = &previous_preferences {
+                if let Err(restore) = save_recording_preferences(&app, prefs).await {
+                    log_error!("Failed to restore recording preferences: {}", restore);
+                }
+            }
+            if let Some(config) = &previous_question_prompt {
+                if let Err(restore) = save_question_prompt_config(&app, config) {
+                    log_error!("Failed to restore question prompt: {}", restore);
+                }
+            }
+            return Err(e);
+        }
+    };
+
+    log_info!("✅ Settings imported from {}", path);
+    Ok(report)
+}
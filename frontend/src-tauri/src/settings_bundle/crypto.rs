@@ -0,0 +1,220 @@
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Key derivation rounds for new bundles (the count is stored in the bundle)
+#[cfg(not(test))]
+const PBKDF2_ITERATIONS: u32 = 600_000;
+#[cfg(test)]
+const PBKDF2_ITERATIONS: u32 = 1_000;
+
+/// Iteration counts accepted from a bundle; the count is read from the file,
+/// so an absurd value must not stall the import
+const MIN_PBKDF2_ITERATIONS: u32 = PBKDF2_ITERATIONS / 10;
+const MAX_PBKDF2_ITERATIONS: u32 = PBKDF2_ITERATIONS * 10;
+
+const KDF_NAME: &str = "pbkdf2-sha256";
+/// Secrets kept on this machine are encrypted with the random per-install
+/// key, which needs no stretching
+const LOCAL_KDF_NAME: &str = "local-sha256";
+const CIPHER_NAME: &str = "aes-256-gcm";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// Secrets encrypted with a key derived from a user passphrase or the local
+/// secret key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncryptedSecrets {
+    pub kdf: String,
+    pub iterations: u32,
+    pub cipher: String,
+    /// Base64-encoded salt, nonce and ciphertext
+    pub salt: String,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], iterations: u32) -> [u8; 32] {
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, iterations, &mut key);
+    key
+}
+
+fn derive_local_key(local_key: &str, salt: &[u8]) -> [u8; 32] {
+    Sha256::new()
+        .chain_update(salt)
+        .chain_update(local_key.as_bytes())
+        .finalize()
+        .into()
+}
+
+impl EncryptedSecrets {
+    /// Whether decrypting runs the slow passphrase key derivation
+    pub fn uses_passphrase_kdf(&self) -> bool {
+        self.kdf == KDF_NAME
+    }
+}
+
+fn seal(
+    plaintext: &[u8],
+    kdf: &str,
+    iterations: u32,
+    derive: impl FnOnce(&[u8]) -> [u8; 32],
+) -> Result<EncryptedSecrets, String> {
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut salt);
+    rand::thread_rng().fill_bytes(&mut nonce);
+
+    let key = derive(&salt);
+    let cipher = Aes256Gcm::new_from_slice(&key)
+        .map_err(|e| format!("Failed to initialise cipher: {}", e))?;
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), plaintext)
+        .map_err(|_| "Failed to encrypt secrets".to_string())?;
+
+    Ok(EncryptedSecrets {
+        kdf: kdf.to_string(),
+        iterations,
+        cipher: CIPHER_NAME.to_string(),
+        salt: BASE64.encode(salt),
+        nonce: BASE64.encode(nonce),
+        ciphertext: BASE64.encode(ciphertext),
+    })
+}
+
+fn open(
+    secrets: &EncryptedSecrets,
+    derive: impl FnOnce(&[u8]) -> [u8; 32],
+) -> Result<Vec<u8>, String> {
+    let decode = |field: &str, value: &str| {
+        BASE64
+            .decode(value)
+            .map_err(|e| format!("Invalid secrets {}: {}", field, e))
+    };
+    let salt = decode("salt", &secrets.salt)?;
+    let nonce = decode("nonce", &secrets.nonce)?;
+    let ciphertext = decode("ciphertext", &secrets.ciphertext)?;
+    if nonce.len() != NONCE_LEN {
+        return Err("Invalid secrets nonce length".to_string());
+    }
+
+    let key = derive(&salt);
+    let cipher = Aes256Gcm::new_from_slice(&key)
+        .map_err(|e| format!("Failed to initialise cipher: {}", e))?;
+    cipher
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_ref())
+        .map_err(|_| "Failed to decrypt secrets: wrong passphrase or corrupted bundle".to_string())
+}
+
+pub fn encrypt(plaintext: &[u8], passphrase: &str) -> Result<EncryptedSecrets, String> {
+    if passphrase.is_empty() {
+        return Err("A passphrase is required to export secrets".to_string());
+    }
+    seal(plaintext, KDF_NAME, PBKDF2_ITERATIONS, |salt| {
+        derive_key(passphrase, salt, PBKDF2_ITERATIONS)
+    })
+}
+
+/// Decrypts secrets; a wrong passphrase fails authentication instead of
+/// producing garbage
+pub fn decrypt(secrets: &EncryptedSecrets, passphrase: &str) -> Result<Vec<u8>, String> {
+    if secrets.kdf != KDF_NAME || secrets.cipher != CIPHER_NAME {
+        return Err(format!(
+            "Unsupported secrets encryption: {} / {}",
+            secrets.kdf, secrets.cipher
+        ));
+    }
+
+    if !(MIN_PBKDF2_ITERATIONS..=MAX_PBKDF2_ITERATIONS).contains(&secrets.iterations) {
+        return Err(format!(
+            "Unsupported key derivation iterations: {}",
+            secrets.iterations
+        ));
+    }
+
+    open(secrets, |salt| {
+        derive_key(passphrase, salt, secrets.iterations)
+    })
+}
+
+/// Encrypts a secret kept on this machine with the per-install random key
+///
+/// That key is not a passphrase, so it is hashed once instead of run
+/// through PBKDF2, and decrypting is cheap enough for every request.
+pub fn encrypt_local(plaintext: &[u8], local_key: &str) -> Result<EncryptedSecrets, String> {
+    if local_key.is_empty() {
+        return Err("The local secret key is empty".to_string());
+    }
+    seal(plaintext, LOCAL_KDF_NAME, 1, |salt| {
+        derive_local_key(local_key, salt)
+    })
+}
+
+/// Decrypts a secret from `encrypt_local`
+///
+/// Secrets stored before it existed used the passphrase format with the
+/// local key; they still decrypt, slowly, until they are entered again.
+pub fn decrypt_local(secrets: &EncryptedSecrets, local_key: &str) -> Result<Vec<u8>, String> {
+    if secrets.uses_passphrase_kdf() {
+        return decrypt(secrets, local_key);
+    }
+    if secrets.kdf != LOCAL_KDF_NAME || secrets.cipher != CIPHER_NAME {
+        return Err(format!(
+            "Unsupported secrets encryption: {} / {}",
+            secrets.kdf, secrets.cipher
+        ));
+    }
+    open(secrets, |salt| derive_local_key(local_key, salt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_decrypt_round_trip() {
+        let secrets = encrypt(b"sk-secret", "correct horse").unwrap();
+        assert!(!secrets.ciphertext.contains("sk-secret"));
+        assert_eq!(decrypt(&secrets, "correct horse").unwrap(), b"sk-secret");
+    }
+
+    #[test]
+    fn test_wrong_passphrase_fails() {
+        let secrets = encrypt(b"sk-secret", "correct horse").unwrap();
+        assert!(decrypt(&secrets, "battery staple").is_err());
+    }
+
+    #[test]
+    fn test_out_of_range_iterations_rejected() {
+        let mut secrets = encrypt(b"sk-secret", "correct horse").unwrap();
+        for iterations in [1, u32::MAX] {
+            secrets.iterations = iterations;
+            let err = decrypt(&secrets, "correct horse").unwrap_err();
+            assert!(err.contains("iterations"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_local_round_trip_and_legacy_format() {
+        let secrets = encrypt_local(b"whsec", "local-key").unwrap();
+        assert!(!secrets.uses_passphrase_kdf());
+        assert_eq!(decrypt_local(&secrets, "local-key").unwrap(), b"whsec");
+        assert!(decrypt_local(&secrets, "other-key").is_err());
+        // Not accepted where a passphrase is expected
+        assert!(decrypt(&secrets, "local-key").is_err());
+
+        let legacy = encrypt(b"whsec", "local-key").unwrap();
+        assert!(legacy.uses_passphrase_kdf());
+        assert_eq!(decrypt_local(&legacy, "local-key").unwrap(), b"whsec");
+    }
+
+    #[test]
+    fn test_empty_passphrase_rejected() {
+        assert!(encrypt(b"sk-secret", "").is_err());
+    }
+}
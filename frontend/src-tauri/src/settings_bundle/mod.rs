@@ -0,0 +1,16 @@
+/// Settings bundle module - moves an app setup between machines
+///
+/// This module contains:
+/// - The versioned JSON bundle format (model/transcription config, custom
+///   templates, named pipelines, the question prompt, recording preferences
+///   and optionally API keys)
+/// - Passphrase-based encryption for API keys; keys are never written in plaintext
+/// - Tauri commands for exporting and importing bundles
+
+pub mod bundle;
+pub mod commands;
+pub(crate) mod crypto;
+
+pub use bundle::{
+    apply_bundle, collect_bundle, ImportReport, SettingsBundle, SETTINGS_BUNDLE_VERSION,
+};
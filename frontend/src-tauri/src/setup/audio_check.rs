@@ -0,0 +1,312 @@
+use crate::audio::devices::{list_audio_devices, DeviceType};
+use crate::audio::permissions::check_screen_recording_permission;
+use anyhow::anyhow;
+use async_trait::async_trait;
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use log::{error, info};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Length of the test recording
+pub const CAPTURE_DURATION: Duration = Duration::from_secs(1);
+
+/// Upper bound for device enumeration and for the test recording (on top of
+/// `CAPTURE_DURATION`); a stuck driver must not hang the wizard
+pub const AUDIO_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Below this RMS the test recording is reported as silent
+const SILENCE_RMS: f32 = 1e-4;
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AudioDeviceList {
+    pub inputs: Vec<String>,
+    pub outputs: Vec<String>,
+    pub default_input: Option<String>,
+}
+
+/// Level of the test recording
+#[derive(Debug, Clone, Serialize)]
+pub struct CaptureLevel {
+    pub samples: usize,
+    pub rms: f32,
+    pub peak: f32,
+    /// No signal: the mic is muted, unplugged, or permission was silently denied
+    pub silent: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AudioCheckResult {
+    pub permission_granted: bool,
+    pub devices: AudioDeviceList,
+    pub devices_error: Option<String>,
+    pub capture: Option<CaptureLevel>,
+    pub capture_error: Option<String>,
+    pub elapsed_ms: u64,
+}
+
+/// The audio operations the setup check needs, so the check can run without hardware in tests
+#[async_trait]
+pub trait AudioProbe: Send + Sync {
+    fn permission_granted(&self) -> bool;
+
+    async fn devices(&self) -> Result<AudioDeviceList, String>;
+
+    /// Records mono samples from the default input; blocks for `duration`
+    fn capture(&self, duration: Duration) -> Result<Vec<f32>, String>;
+}
+
+/// `AudioProbe` backed by the system audio host
+pub struct CpalAudioProbe;
+
+#[async_trait]
+impl AudioProbe for CpalAudioProbe {
+    fn permission_granted(&self) -> bool {
+        check_screen_recording_permission()
+    }
+
+    async fn devices(&self) -> Result<AudioDeviceList, String> {
+        let devices = list_audio_devices()
+            .await
+            .map_err(|e| format!("Failed to list audio devices: {}", e))?;
+        let default_input = cpal::default_host()
+            .default_input_device()
+            .and_then(|device| device.name().ok());
+
+        let mut list = AudioDeviceList {
+            default_input,
+            ..Default::default()
+        };
+        for device in devices {
+            match device.device_type {
+                DeviceType::Input => list.inputs.push(device.name),
+                DeviceType::Output => list.outputs.push(device.name),
+            }
+        }
+        Ok(list)
+    }
+
+    fn capture(&self, duration: Duration) -> Result<Vec<f32>, String> {
+        capture_default_input(duration).map_err(|e| e.to_string())
+    }
+}
+
+fn capture_default_input(duration: Duration) -> anyhow::Result<Vec<f32>> {
+    let host = cpal::default_host();
+    let device = host
+        .default_input_device()
+        .ok_or_else(|| anyhow!("No default input device found"))?;
+    let config = device.default_input_config()?;
+    let channels = config.channels().max(1) as usize;
+    let samples: Arc<Mutex<Vec<f32>>> = Arc::new(Mutex::new(Vec::new()));
+    let err_fn = |err| error!("Error in setup audio check stream: {}", err);
+
+    // Only the first channel is kept; that is enough to measure the level
+    let stream = match config.sample_format() {
+        cpal::SampleFormat::F32 => {
+            let samples = samples.clone();
+            device.build_input_stream(
+                &config.into(),
+                move |data: &[f32], _: &cpal::InputCallbackInfo| {
+                    let mut samples = samples.lock().unwrap();
+                    samples.extend(data.iter().step_by(channels).copied());
+                },
+                err_fn,
+                None,
+            )?
+        }
+        cpal::SampleFormat::I16 => {
+            let samples = samples.clone();
+            device.build_input_stream(
+                &config.into(),
+                move |data: &[i16], _: &cpal::InputCallbackInfo| {
+                    let mut samples = samples.lock().unwrap();
+                    samples.extend(
+                        data.iter()
+                            .step_by(channels)
+                            .map(|&s| s as f32 / i16::MAX as f32),
+                    );
+                },
+                err_fn,
+                None,
+            )?
+        }
+        cpal::SampleFormat::I32 => {
+            let samples = samples.clone();
+            device.build_input_stream(
+                &config.into(),
+                move |data: &[i32], _: &cpal::InputCallbackInfo| {
+                    let mut samples = samples.lock().unwrap();
+                    samples.extend(
+                        data.iter()
+                            .step_by(channels)
+                            .map(|&s| s as f32 / i32::MAX as f32),
+                    );
+                },
+                err_fn,
+                None,
+            )?
+        }
+        format => return Err(anyhow!("Unsupported sample format: {:?}", format)),
+    };
+
+    stream.play()?;
+    std::thread::sleep(duration);
+    drop(stream);
+
+    let samples = std::mem::take(&mut *samples.lock().unwrap());
+    Ok(samples)
+}
+
+/// RMS and peak of the recording
+pub fn measure_level(samples: &[f32]) -> CaptureLevel {
+    let peak = samples.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+    let rms = if samples.is_empty() {
+        0.0
+    } else {
+        (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+    };
+    CaptureLevel {
+        samples: samples.len(),
+        rms,
+        peak,
+        silent: rms < SILENCE_RMS,
+    }
+}
+
+/// Checks permissions, lists devices and measures a short test recording
+///
+/// Device enumeration and the recording are timed out independently, and a
+/// failure in one step is reported in its own field without skipping the rest.
+pub async fn check_audio(
+    probe: Arc<dyn AudioProbe>,
+    capture_duration: Duration,
+    timeout: Duration,
+) -> AudioCheckResult {
+    let start = Instant::now();
+    let permission_granted = probe.permission_granted();
+
+    let (devices, devices_error) = match tokio::time::timeout(timeout, probe.devices()).await {
+        Ok(Ok(devices)) => (devices, None),
+        Ok(Err(e)) => (AudioDeviceList::default(), Some(e)),
+        Err(_) => (
+            AudioDeviceList::default(),
+            Some(format!("Listing audio devices timed out after {}ms", timeout.as_millis())),
+        ),
+    };
+
+    // Audio streams are not Send, so the recording runs on a blocking thread
+    let capture_probe = probe.clone();
+    let capture = tokio::task::spawn_blocking(move || capture_probe.capture(capture_duration));
+    let (capture, capture_error) =
+        match tokio::time::timeout(capture_duration + timeout, capture).await {
+            Ok(Ok(Ok(samples))) => (Some(measure_level(&samples)), None),
+            Ok(Ok(Err(e))) => (None, Some(e)),
+            Ok(Err(e)) => (None, Some(format!("Test recording failed: {}", e))),
+            Err(_) => (
+                None,
+                Some(format!(
+                    "Test recording did not finish within {}ms",
+                    (capture_duration + timeout).as_millis()
+                )),
+            ),
+        };
+
+    let result = AudioCheckResult {
+        permission_granted,
+        devices,
+        devices_error,
+        capture,
+        capture_error,
+        elapsed_ms: start.elapsed().as_millis() as u64,
+    };
+    info!(
+        "🎙️ Setup audio check: permission={}, inputs={}, capture={:?}, errors={:?}/{:?}",
+        result.permission_granted,
+        result.devices.inputs.len(),
+        result.capture.as_ref().map(|c| c.rms),
+        result.devices_error,
+        result.capture_error
+    );
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FakeProbe {
+        devices: Result<AudioDeviceList, String>,
+        capture: Result<Vec<f32>, String>,
+        capture_delay: Duration,
+    }
+
+    #[async_trait]
+    impl AudioProbe for FakeProbe {
+        fn permission_granted(&self) -> bool {
+            true
+        }
+
+        async fn devices(&self) -> Result<AudioDeviceList, String> {
+            self.devices.clone()
+        }
+
+        fn capture(&self, _duration: Duration) -> Result<Vec<f32>, String> {
+            std::thread::sleep(self.capture_delay);
+            self.capture.clone()
+        }
+    }
+
+    fn device_list() -> AudioDeviceList {
+        AudioDeviceList {
+            inputs: vec!["Built-in Microphone".to_string()],
+            outputs: vec!["Speakers".to_string()],
+            default_input: Some("Built-in Microphone".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_measure_level() {
+        let level = measure_level(&[0.5, -0.5, 0.5, -0.5]);
+        assert!((level.rms - 0.5).abs() < 1e-6);
+        assert!((level.peak - 0.5).abs() < 1e-6);
+        assert!(!level.silent);
+
+        assert!(measure_level(&[0.0; 16]).silent);
+        assert!(measure_level(&[]).silent);
+    }
+
+    #[tokio::test]
+    async fn test_check_audio_reports_devices_and_level() {
+        let probe = Arc::new(FakeProbe {
+            devices: Ok(device_list()),
+            capture: Ok(vec![0.1, -0.1, 0.1, -0.1]),
+            capture_delay: Duration::ZERO,
+        });
+
+        let result = check_audio(probe, Duration::from_millis(10), Duration::from_secs(1)).await;
+
+        assert!(result.permission_granted);
+        assert_eq!(result.devices.inputs, vec!["Built-in Microphone"]);
+        assert!(result.devices_error.is_none());
+        let capture = result.capture.unwrap();
+        assert_eq!(capture.samples, 4);
+        assert!(!capture.silent);
+    }
+
+    #[tokio::test]
+    async fn test_check_audio_isolates_failures_and_times_out_capture() {
+        let probe = Arc::new(FakeProbe {
+            devices: Err("Failed to list audio devices: no host".to_string()),
+            capture: Ok(vec![0.1]),
+            capture_delay: Duration::from_millis(500),
+        });
+
+        let result = check_audio(probe, Duration::from_millis(10), Duration::from_millis(50)).await;
+
+        assert!(result.devices.inputs.is_empty());
+        assert!(result.devices_error.unwrap().contains("no host"));
+        assert!(result.capture.is_none());
+        assert!(result.capture_error.unwrap().contains("did not finish"));
+    }
+}
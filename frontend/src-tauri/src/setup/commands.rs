@@ -0,0 +1,185 @@
+use super::audio_check::{
+    check_audio, AudioCheckResult, CpalAudioProbe, AUDIO_PROBE_TIMEOUT, CAPTURE_DURATION,
+};
+use super::completion::{
+    complete_setup, get_setup_status, validate_setup, SetupModelConfig, SetupStatus,
+    SetupTranscriptConfig,
+};
+use super::probes::{
+    detect_ollama, validate_provider, OllamaProbeResult, ProviderValidation,
+    DEFAULT_OLLAMA_ENDPOINTS, OLLAMA_PROBE_TIMEOUT, PROVIDER_PROBE_TIMEOUT,
+};
+use super::self_check::{load_startup_notices, save_startup_notices, StartupNotice};
+use crate::audio::recording_preferences::{
+    ensure_recordings_directory, load_recording_preferences, save_recording_preferences,
+    RecordingPreferences,
+};
+use crate::database::repositories::setting::SettingsRepository;
+use crate::http::client_factory::{self, direct_client};
+use crate::state::AppState;
+use log::{error as log_error, info as log_info, warn as log_warn};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tauri::{AppHandle, Runtime};
+
+/// Everything the wizard collects, applied by `api_setup_complete`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetupConfig {
+    pub model_config: SetupModelConfig,
+    pub transcript_config: SetupTranscriptConfig,
+    pub recording_preferences: Option<RecordingPreferences>,
+}
+
+#[tauri::command]
+pub async fn api_setup_status(state: tauri::State<'_, AppState>) -> Result<SetupStatus, String> {
+    get_setup_status(state.db_manager.pool()).await
+}
+
+/// Probes the default Ollama endpoints (plus the configured one and any
+/// `endpoints` given) and lists the models each serves
+#[tauri::command]
+pub async fn api_setup_detect_ollama(
+    state: tauri::State<'_, AppState>,
+    endpoints: Option<Vec<String>>,
+) -> Result<Vec<OllamaProbeResult>, String> {
+    let mut candidates: Vec<String> =
+        DEFAULT_OLLAMA_ENDPOINTS.iter().map(|e| e.to_string()).collect();
+    if let Ok(Some(config)) = SettingsRepository::get_model_config(state.db_manager.pool()).await {
+        candidates.extend(config.ollama_endpoint);
+    }
+    candidates.extend(endpoints.unwrap_or_default());
+    let mut seen = HashSet::new();
+    candidates.retain(|e| {
+        let e = e.trim().trim_end_matches('/');
+        !e.is_empty() && seen.insert(e.to_string())
+    });
+
+    log_info!("api_setup_detect_ollama probing {:?}", candidates);
+    let results = detect_ollama(&direct_client(), &candidates, OLLAMA_PROBE_TIMEOUT).await;
+    log_info!(
+        "Ollama reachable at {} of {} endpoints",
+        results.iter().filter(|r| r.reachable).count(),
+        results.len()
+    );
+    Ok(results)
+}
+
+/// Checks an API key with one cheap authenticated request; `endpoint` is
+/// only used for Ollama
+#[tauri::command]
+pub async fn api_setup_validate_provider(
+    provider: String,
+    key: String,
+    endpoint: Option<String>,
+) -> Result<ProviderValidation, String> {
+    log_info!("api_setup_validate_provider called for provider: {}", provider);
+    let base_url = endpoint.as_deref().filter(|_| provider == "ollama");
+    // Ollama servers are always reached directly, other providers through the proxy
+    let client = if provider == "ollama" {
+        direct_client()
+    } else {
+        client_factory::client()
+    };
+    let result = validate_provider(
+        &client,
+        &provider,
+        &key,
+        base_url,
+        PROVIDER_PROBE_TIMEOUT,
+    )
+    .await;
+    if !result.valid {
+        log_warn!("Provider validation failed for {}: {:?}", provider, result.error);
+    }
+    Ok(result)
+}
+
+/// Checks audio permission, lists devices and records one second from the
+/// default input to measure its level
+#[tauri::command]
+pub async fn api_setup_check_audio() -> Result<AudioCheckResult, String> {
+    log_info!("api_setup_check_audio called");
+    Ok(check_audio(Arc::new(CpalAudioProbe), CAPTURE_DURATION, AUDIO_PROBE_TIMEOUT).await)
+}
+
+/// Applies the wizard's choices and marks setup as done
+///
+/// The database changes commit together or not at all. Recording preferences
+/// live in the app store, so they are saved first and restored if the
+/// database step fails.
+#[tauri::command]
+pub async fn api_setup_complete<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    config: SetupConfig,
+) -> Result<SetupStatus, String> {
+    log_info!(
+        "api_setup_complete called: provider={}, model={}, transcription={}",
+        config.model_config.provider,
+        config.model_config.model,
+        config.transcript_config.provider
+    );
+    validate_setup(&config.model_config, &config.transcript_config)?;
+
+    let previous_preferences = match &config.recording_preferences {
+        Some(prefs) => {
+            ensure_recordings_directory(&prefs.save_folder)
+                .map_err(|e| format!("Recording folder is not usable: {}", e))?;
+            let previous = load_recording_preferences(&app)
+                .await
+                .map_err(|e| format!("Failed to load recording preferences: {}", e))?;
+            save_recording_preferences(&app, prefs)
+                .await
+                .map_err(|e| format!("Failed to save recording preferences: {}", e))?;
+            Some(previous)
+        }
+        None => None,
+    };
+
+    let _write_guard = state.db_manager.write_lock().await;
+    match complete_setup(
+        state.db_manager.pool(),
+        &config.model_config,
+        &config.transcript_config,
+    )
+    .await
+    {
+        Ok(status) => Ok(status),
+        Err(e) => {
+            log_error!("Setup completion failed: {}", e);
+            if let Some(previous) = previous_preferences {
+                if let Err(restore_err) = save_recording_preferences(&app, &previous).await {
+                    log_error!("Failed to restore recording preferences: {}", restore_err);
+                }
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Findings of the startup self-check not dismissed yet
+#[tauri::command]
+pub async fn api_get_startup_notices<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<Vec<StartupNotice>, String> {
+    Ok(load_startup_notices(&app))
+}
+
+/// Removes a startup notice; returns the ones left
+#[tauri::command]
+pub async fn api_dismiss_startup_notice<R: Runtime>(
+    app: AppHandle<R>,
+    id: String,
+) -> Result<Vec<StartupNotice>, String> {
+    let mut notices = load_startup_notices(&app);
+    let before = notices.len();
+    notices.retain(|notice| notice.id != id);
+    if notices.len() == before {
+        return Err(format!("No startup notice with id {}", id));
+    }
+    save_startup_notices(&app, &notices)?;
+    log_info!("Dismissed startup notice {}", id);
+    Ok(notices)
+}
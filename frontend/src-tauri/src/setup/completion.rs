@@ -0,0 +1,309 @@
+use crate::summary::LLMProvider;
+use chrono::Utc;
+use log::info;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+/// Summary model chosen in the wizard
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetupModelConfig {
+    pub provider: String,
+    pub model: String,
+    pub whisper_model: String,
+    pub api_key: Option<String>,
+    pub ollama_endpoint: Option<String>,
+}
+
+/// Transcription provider chosen in the wizard
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetupTranscriptConfig {
+    pub provider: String,
+    pub model: String,
+    pub api_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetupStatus {
+    pub completed: bool,
+    pub completed_at: Option<String>,
+}
+
+fn summary_key_column(provider: &str) -> Option<&'static str> {
+    match provider {
+        "openai" => Some("openaiApiKey"),
+        "claude" => Some("anthropicApiKey"),
+        "ollama" => Some("ollamaApiKey"),
+        "groq" => Some("groqApiKey"),
+        "openrouter" => Some("openRouterApiKey"),
+        "gemini" => Some("geminiApiKey"),
+        _ => None,
+    }
+}
+
+/// None for a known provider that doesn't take a key (parakeet)
+fn transcript_key_column(provider: &str) -> Result<Option<&'static str>, String> {
+    match provider {
+        "parakeet" => Ok(None),
+        "localWhisper" => Ok(Some("whisperApiKey")),
+        "deepgram" => Ok(Some("deepgramApiKey")),
+        "elevenLabs" => Ok(Some("elevenLabsApiKey")),
+        "groq" => Ok(Some("groqApiKey")),
+        "openai" => Ok(Some("openaiApiKey")),
+        _ => Err(format!("Unsupported transcription provider: {}", provider)),
+    }
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+}
+
+/// Rejects a config that would leave the app unusable, before anything is written
+pub fn validate_setup(
+    model: &SetupModelConfig,
+    transcript: &SetupTranscriptConfig,
+) -> Result<(), String> {
+    let provider = LLMProvider::from_str(&model.provider)?;
+    if model.model.trim().is_empty() {
+        return Err("A summary model is required".to_string());
+    }
+    if model.whisper_model.trim().is_empty() {
+        return Err("A whisper model is required".to_string());
+    }
+    if provider != LLMProvider::Ollama && non_empty(&model.api_key).is_none() {
+        return Err(format!("An API key is required for {}", model.provider));
+    }
+    if let Some(endpoint) = non_empty(&model.ollama_endpoint) {
+        if !endpoint.starts_with("http://") && !endpoint.starts_with("https://") {
+            return Err("Ollama endpoint must start with http:// or https://".to_string());
+        }
+    }
+
+    transcript_key_column(&transcript.provider)?;
+    if transcript.model.trim().is_empty() {
+        return Err("A transcription model is required".to_string());
+    }
+    Ok(())
+}
+
+/// Writes the model config, transcript config and API keys and marks setup
+/// complete, all in one transaction
+pub async fn complete_setup(
+    pool: &SqlitePool,
+    model: &SetupModelConfig,
+    transcript: &SetupTranscriptConfig,
+) -> Result<SetupStatus, String> {
+    validate_setup(model, transcript)?;
+    let completed_at = Utc::now().to_rfc3339();
+
+    let mut transaction = pool
+        .begin()
+        .await
+        .map_err(|e| format!("Failed to start setup transaction: {}", e))?;
+
+    // Any error below drops the transaction, which rolls everything back
+    let write = async {
+        sqlx::query(
+            r#"
+            INSERT INTO settings (id, provider, model, whisperModel, ollamaEndpoint, setupCompletedAt)
+            VALUES ('1', $1, $2, $3, $4, $5)
+            ON CONFLICT(id) DO UPDATE SET
+                provider = excluded.provider,
+                model = excluded.model,
+                whisperModel = excluded.whisperModel,
+                ollamaEndpoint = excluded.ollamaEndpoint,
+                setupCompletedAt = excluded.setupCompletedAt
+            "#,
+        )
+        .bind(&model.provider)
+        .bind(&model.model)
+        .bind(&model.whisper_model)
+        .bind(non_empty(&model.ollama_endpoint))
+        .bind(&completed_at)
+        .execute(&mut *transaction)
+        .await?;
+
+        if let (Some(key), Some(column)) =
+            (non_empty(&model.api_key), summary_key_column(&model.provider))
+        {
+            let query = format!(r#"UPDATE settings SET "{}" = $1 WHERE id = '1'"#, column);
+            sqlx::query(&query).bind(key).execute(&mut *transaction).await?;
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO transcript_settings (id, provider, model)
+            VALUES ('1', $1, $2)
+            ON CONFLICT(id) DO UPDATE SET
+                provider = excluded.provider,
+                model = excluded.model
+            "#,
+        )
+        .bind(&transcript.provider)
+        .bind(&transcript.model)
+        .execute(&mut *transaction)
+        .await?;
+
+        if let (Some(key), Ok(Some(column))) = (
+            non_empty(&transcript.api_key),
+            transcript_key_column(&transcript.provider),
+        ) {
+            let query = format!(
+                r#"UPDATE transcript_settings SET "{}" = $1 WHERE id = '1'"#,
+                column
+            );
+            sqlx::query(&query).bind(key).execute(&mut *transaction).await?;
+        }
+
+        Ok::<(), sqlx::Error>(())
+    }
+    .await;
+
+    if let Err(e) = write {
+        return Err(format!("Failed to save setup: {}", e));
+    }
+    transaction
+        .commit()
+        .await
+        .map_err(|e| format!("Failed to save setup: {}", e))?;
+
+    info!(
+        "✅ Setup completed: provider={}, model={}, transcription={}",
+        model.provider, model.model, transcript.provider
+    );
+    Ok(SetupStatus {
+        completed: true,
+        completed_at: Some(completed_at),
+    })
+}
+
+pub async fn get_setup_status(pool: &SqlitePool) -> Result<SetupStatus, String> {
+    let completed_at: Option<Option<String>> =
+        sqlx::query_scalar("SELECT setupCompletedAt FROM settings WHERE id = '1' LIMIT 1")
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| format!("Failed to read setup status: {}", e))?;
+    let completed_at = completed_at.flatten();
+    Ok(SetupStatus {
+        completed: completed_at.is_some(),
+        completed_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::repositories::setting::SettingsRepository;
+    use crate::database::test_utils::test_pool;
+
+    fn model_config() -> SetupModelConfig {
+        SetupModelConfig {
+            provider: "claude".to_string(),
+            model: "claude-3-5-sonnet-latest".to_string(),
+            whisper_model: "large-v3".to_string(),
+            api_key: Some("sk-ant-test".to_string()),
+            ollama_endpoint: None,
+        }
+    }
+
+    fn transcript_config() -> SetupTranscriptConfig {
+        SetupTranscriptConfig {
+            provider: "deepgram".to_string(),
+            model: "nova-2".to_string(),
+            api_key: Some("dg-test".to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_complete_setup_writes_everything() {
+        let pool = test_pool().await;
+        assert!(!get_setup_status(&pool).await.unwrap().completed);
+
+        let status = complete_setup(&pool, &model_config(), &transcript_config())
+            .await
+            .unwrap();
+        assert!(status.completed);
+
+        let config = SettingsRepository::get_model_config(&pool).await.unwrap().unwrap();
+        assert_eq!(config.provider, "claude");
+        assert_eq!(config.model, "claude-3-5-sonnet-latest");
+        assert_eq!(
+            SettingsRepository::get_api_key(&pool, "claude").await.unwrap().as_deref(),
+            Some("sk-ant-test")
+        );
+        let transcript = SettingsRepository::get_transcript_config(&pool).await.unwrap().unwrap();
+        assert_eq!(transcript.provider, "deepgram");
+        assert_eq!(
+            SettingsRepository::get_transcript_api_key(&pool, "deepgram")
+                .await
+                .unwrap()
+                .as_deref(),
+            Some("dg-test")
+        );
+        let stored = get_setup_status(&pool).await.unwrap();
+        assert!(stored.completed);
+        assert_eq!(stored.completed_at, status.completed_at);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_config_writes_nothing() {
+        let pool = test_pool().await;
+
+        let mut missing_key = model_config();
+        missing_key.api_key = Some("  ".to_string());
+        assert!(complete_setup(&pool, &missing_key, &transcript_config()).await.is_err());
+
+        let mut unknown_transcript = transcript_config();
+        unknown_transcript.provider = "carrier-pigeon".to_string();
+        assert!(complete_setup(&pool, &model_config(), &unknown_transcript).await.is_err());
+
+        assert!(SettingsRepository::get_model_config(&pool).await.unwrap().is_none());
+        assert!(!get_setup_status(&pool).await.unwrap().completed);
+    }
+
+    #[tokio::test]
+    async fn test_failure_mid_transaction_rolls_back() {
+        let pool = test_pool().await;
+        SettingsRepository::save_model_config(&pool, "openai", "gpt-4o", "base", None)
+            .await
+            .unwrap();
+        // Make the transcript step fail after the settings row was written
+        sqlx::query("DROP TABLE transcript_settings")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert!(complete_setup(&pool, &model_config(), &transcript_config()).await.is_err());
+
+        let config = SettingsRepository::get_model_config(&pool).await.unwrap().unwrap();
+        assert_eq!(config.provider, "openai");
+        assert_eq!(config.model, "gpt-4o");
+        assert_eq!(SettingsRepository::get_api_key(&pool, "claude").await.unwrap(), None);
+        assert!(!get_setup_status(&pool).await.unwrap().completed);
+    }
+
+    #[tokio::test]
+    async fn test_ollama_needs_no_key() {
+        let pool = test_pool().await;
+        let model = SetupModelConfig {
+            provider: "ollama".to_string(),
+            model: "llama3.2:latest".to_string(),
+            whisper_model: "large-v3".to_string(),
+            api_key: None,
+            ollama_endpoint: Some("http://localhost:11434".to_string()),
+        };
+        let transcript = SetupTranscriptConfig {
+            provider: "parakeet".to_string(),
+            model: "parakeet-tdt-0.6b-v3-int8".to_string(),
+            api_key: None,
+        };
+
+        complete_setup(&pool, &model, &transcript).await.unwrap();
+
+        let config = SettingsRepository::get_model_config(&pool).await.unwrap().unwrap();
+        assert_eq!(config.ollama_endpoint.as_deref(), Some("http://localhost:11434"));
+        assert!(get_setup_status(&pool).await.unwrap().completed);
+    }
+}
@@ -0,0 +1,22 @@
+/// Setup module - backend for the first-run setup wizard
+///
+/// This module contains:
+/// - Probes for a local Ollama server and for provider API keys, and a
+///   test completion against a provider and model
+/// - An audio check (permission, devices and a short test recording)
+/// - The atomic completion step that saves the chosen configuration and
+///   marks setup as done
+/// - A startup self-check repairing settings that point at missing
+///   templates, folders or malformed endpoints, reported as notices
+/// - Tauri commands exposing the above to the wizard
+
+pub mod audio_check;
+pub mod commands;
+pub mod completion;
+pub mod probes;
+pub mod self_check;
+
+pub use audio_check::{AudioCheckResult, AudioProbe, CaptureLevel};
+pub use completion::{SetupModelConfig, SetupStatus, SetupTranscriptConfig};
+pub use probes::{OllamaProbeResult, ProviderTestResult, ProviderValidation};
+pub use self_check::{StartupNotice, StartupNoticeKind};
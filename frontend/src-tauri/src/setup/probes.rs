@@ -0,0 +1,683 @@
+use crate::summary::llm_client::{completion_request, parse_completion};
+use crate::summary::provider_overrides::ProviderOverride;
+use crate::summary::LLMProvider;
+use futures_util::future::join_all;
+use reqwest::{header, Client, RequestBuilder};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Endpoints probed when looking for a local Ollama server
+pub const DEFAULT_OLLAMA_ENDPOINTS: &[&str] = &["http://localhost:11434", "http://127.0.0.1:11434"];
+
+/// Ollama is local, so anything slower than this is treated as not running
+pub const OLLAMA_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Upper bound for a provider key check (one cheap authenticated request)
+pub const PROVIDER_PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Upper bound for a test completion; local models may have to load first
+pub const PROVIDER_TEST_TIMEOUT: Duration = Duration::from_secs(20);
+
+const TEST_SYSTEM_PROMPT: &str = "You are checking a connection. Answer in one word.";
+const TEST_USER_PROMPT: &str = "Reply with OK.";
+
+/// Outcome of probing one Ollama endpoint
+#[derive(Debug, Clone, Serialize)]
+pub struct OllamaProbeResult {
+    pub endpoint: String,
+    pub reachable: bool,
+    pub models: Vec<String>,
+    pub elapsed_ms: u64,
+    pub error: Option<String>,
+}
+
+/// Outcome of checking an API key against its provider
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderValidation {
+    pub provider: String,
+    pub valid: bool,
+    /// HTTP status of the check request, if one was answered
+    pub status: Option<u16>,
+    pub elapsed_ms: u64,
+    pub error: Option<String>,
+}
+
+/// Why a test completion failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProviderTestErrorKind {
+    InvalidKey,
+    ModelNotFound,
+    Network,
+    RateLimited,
+    Other,
+}
+
+/// Outcome of a test completion against one provider and model
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderTestResult {
+    pub provider: String,
+    pub model: String,
+    pub ok: bool,
+    /// Round trip of the completion on success, time until the failure otherwise
+    pub latency_ms: u64,
+    pub error_kind: Option<ProviderTestErrorKind>,
+    pub error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TagsResponse {
+    models: Vec<TagsModel>,
+}
+
+#[derive(Deserialize)]
+struct TagsModel {
+    name: String,
+}
+
+fn elapsed_ms(start: Instant) -> u64 {
+    start.elapsed().as_millis() as u64
+}
+
+/// Lists the models served by the Ollama instance at `endpoint`
+pub async fn probe_ollama(client: &Client, endpoint: &str, timeout: Duration) -> OllamaProbeResult {
+    let start = Instant::now();
+    let endpoint = endpoint.trim_end_matches('/').to_string();
+    let url = format!("{}/api/tags", endpoint);
+
+    let outcome = async {
+        let response = client
+            .get(&url)
+            .timeout(timeout)
+            .send()
+            .await
+            .map_err(|e| {
+                if e.is_timeout() {
+                    format!("Timed out after {}ms", timeout.as_millis())
+                } else {
+                    format!("Not reachable: {}", e)
+                }
+            })?;
+        if !response.status().is_success() {
+            return Err(format!("Ollama returned status {}", response.status()));
+        }
+        let tags: TagsResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Unexpected response from Ollama: {}", e))?;
+        Ok(tags.models.into_iter().map(|m| m.name).collect::<Vec<_>>())
+    }
+    .await;
+
+    match outcome {
+        Ok(models) => OllamaProbeResult {
+            endpoint,
+            reachable: true,
+            models,
+            elapsed_ms: elapsed_ms(start),
+            error: None,
+        },
+        Err(error) => OllamaProbeResult {
+            endpoint,
+            reachable: false,
+            models: Vec::new(),
+            elapsed_ms: elapsed_ms(start),
+            error: Some(error),
+        },
+    }
+}
+
+/// Probes every endpoint concurrently; results keep the order of `endpoints`
+pub async fn detect_ollama(
+    client: &Client,
+    endpoints: &[String],
+    timeout: Duration,
+) -> Vec<OllamaProbeResult> {
+    join_all(
+        endpoints
+            .iter()
+            .map(|endpoint| probe_ollama(client, endpoint, timeout)),
+    )
+    .await
+}
+
+/// Base URL of each provider's public API
+pub fn default_base_url(provider: &LLMProvider) -> &'static str {
+    match provider {
+        LLMProvider::OpenAI => "https://api.openai.com/v1",
+        LLMProvider::Claude => "https://api.anthropic.com/v1",
+        LLMProvider::Groq => "https://api.groq.com/openai/v1",
+        LLMProvider::OpenRouter => "https://openrouter.ai/api/v1",
+        LLMProvider::Gemini => "https://generativelanguage.googleapis.com/v1beta",
+        LLMProvider::Ollama => "http://localhost:11434",
+    }
+}
+
+/// Builds the cheapest request that still requires a valid key: listing
+/// models (or, for OpenRouter whose model list is public, reading the key info)
+fn validation_request(
+    client: &Client,
+    provider: &LLMProvider,
+    api_key: &str,
+    base_url: &str,
+) -> RequestBuilder {
+    let base_url = base_url.trim_end_matches('/');
+    match provider {
+        LLMProvider::OpenAI | LLMProvider::Groq => client
+            .get(format!("{}/models", base_url))
+            .bearer_auth(api_key),
+        LLMProvider::OpenRouter => client
+            .get(format!("{}/key", base_url))
+            .bearer_auth(api_key),
+        LLMProvider::Claude => client
+            .get(format!("{}/models", base_url))
+            .header("x-api-key", api_key)
+            .header("anthropic-version", "2023-06-01"),
+        // Header rather than query parameter so the key never ends up in URLs or logs
+        LLMProvider::Gemini => client
+            .get(format!("{}/models", base_url))
+            .header("x-goog-api-key", api_key),
+        LLMProvider::Ollama => {
+            let request = client.get(format!("{}/api/tags", base_url));
+            if api_key.is_empty() {
+                request
+            } else {
+                request.header(header::AUTHORIZATION, format!("Bearer {}", api_key))
+            }
+        }
+    }
+}
+
+/// Checks `api_key` with one authenticated request to the provider
+///
+/// `base_url` overrides the provider's public API (the Ollama endpoint, or a
+/// mock server in tests).
+pub async fn validate_provider(
+    client: &Client,
+    provider: &str,
+    api_key: &str,
+    base_url: Option<&str>,
+    timeout: Duration,
+) -> ProviderValidation {
+    let start = Instant::now();
+    let invalid = |status: Option<u16>, error: String| ProviderValidation {
+        provider: provider.to_string(),
+        valid: false,
+        status,
+        elapsed_ms: elapsed_ms(start),
+        error: Some(error),
+    };
+
+    let llm_provider = match LLMProvider::from_str(provider) {
+        Ok(p) => p,
+        Err(e) => return invalid(None, e),
+    };
+    let api_key = api_key.trim();
+    if api_key.is_empty() && llm_provider != LLMProvider::Ollama {
+        return invalid(None, format!("An API key is required for {}", provider));
+    }
+
+    let base_url = base_url
+        .filter(|url| !url.trim().is_empty())
+        .unwrap_or_else(|| default_base_url(&llm_provider));
+    let response = validation_request(client, &llm_provider, api_key, base_url)
+        .timeout(timeout)
+        .send()
+        .await;
+
+    match response {
+        Ok(response) if response.status().is_success() => ProviderValidation {
+            provider: provider.to_string(),
+            valid: true,
+            status: Some(response.status().as_u16()),
+            elapsed_ms: elapsed_ms(start),
+            error: None,
+        },
+        Ok(response) => {
+            let status = response.status();
+            let error = match status.as_u16() {
+                401 | 403 => format!("{} rejected the API key", provider),
+                429 => format!("{} is rate limiting requests; try again shortly", provider),
+                _ => format!("{} returned unexpected status {}", provider, status),
+            };
+            invalid(Some(status.as_u16()), error)
+        }
+        Err(e) if e.is_timeout() => invalid(
+            None,
+            format!("{} did not respond within {}ms", provider, timeout.as_millis()),
+        ),
+        Err(e) => invalid(None, format!("Could not reach {}: {}", provider, e)),
+    }
+}
+
+/// What a failed test completion's status and error body mean
+fn classify_test_failure(status: u16, body: &str) -> ProviderTestErrorKind {
+    let body = body.to_lowercase();
+    match status {
+        401 | 403 => ProviderTestErrorKind::InvalidKey,
+        404 => ProviderTestErrorKind::ModelNotFound,
+        429 => ProviderTestErrorKind::RateLimited,
+        // Gemini answers bad keys, and OpenRouter unknown models, with a 400
+        400 if body.contains("api_key_invalid") || body.contains("api key not valid") => {
+            ProviderTestErrorKind::InvalidKey
+        }
+        400 if body.contains("not a valid model")
+            || body.contains("model_not_found")
+            || body.contains("model not found") =>
+        {
+            ProviderTestErrorKind::ModelNotFound
+        }
+        _ => ProviderTestErrorKind::Other,
+    }
+}
+
+/// Whether Ollama serves `model`, which may leave off the `:latest` tag
+fn ollama_has_model(models: &[String], model: &str) -> bool {
+    models
+        .iter()
+        .any(|name| name == model || name.strip_suffix(":latest") == Some(model))
+}
+
+/// Sends a minimal "reply with OK" completion to `model` and times it
+///
+/// For Ollama the server is asked for its models first, so a server that
+/// isn't running and a model that isn't pulled are told apart. `overrides`
+/// is the provider's base URL override (a mock server in tests).
+pub async fn test_llm_provider(
+    client: &Client,
+    provider: &str,
+    model: &str,
+    api_key: &str,
+    ollama_endpoint: Option<&str>,
+    overrides: &ProviderOverride,
+    timeout: Duration,
+) -> ProviderTestResult {
+    let start = Instant::now();
+    let model = model.trim();
+    let failed = |kind: ProviderTestErrorKind, error: String| ProviderTestResult {
+        provider: provider.to_string(),
+        model: model.to_string(),
+        ok: false,
+        latency_ms: elapsed_ms(start),
+        error_kind: Some(kind),
+        error: Some(error),
+    };
+
+    let llm_provider = match LLMProvider::from_str(provider) {
+        Ok(p) => p,
+        Err(e) => return failed(ProviderTestErrorKind::Other, e),
+    };
+    if model.is_empty() {
+        return failed(
+            ProviderTestErrorKind::ModelNotFound,
+            "Choose a model to test".to_string(),
+        );
+    }
+    let api_key = api_key.trim();
+    if api_key.is_empty() && llm_provider != LLMProvider::Ollama {
+        return failed(
+            ProviderTestErrorKind::InvalidKey,
+            format!("An API key is required for {}", provider),
+        );
+    }
+
+    if llm_provider == LLMProvider::Ollama {
+        let endpoint = ollama_endpoint
+            .filter(|url| !url.trim().is_empty())
+            .unwrap_or(DEFAULT_OLLAMA_ENDPOINTS[0]);
+        let probe = probe_ollama(client, endpoint, timeout).await;
+        if !probe.reachable {
+            return failed(
+                ProviderTestErrorKind::Network,
+                format!(
+                    "Ollama at {}: {}",
+                    probe.endpoint,
+                    probe.error.unwrap_or_default()
+                ),
+            );
+        }
+        if !ollama_has_model(&probe.models, model) {
+            return failed(
+                ProviderTestErrorKind::ModelNotFound,
+                format!(
+                    "Ollama at {} has no model '{}'; pull it with `ollama pull {}`",
+                    probe.endpoint, model, model
+                ),
+            );
+        }
+    }
+
+    let request = match completion_request(
+        client,
+        &llm_provider,
+        model,
+        api_key,
+        TEST_SYSTEM_PROMPT,
+        TEST_USER_PROMPT,
+        ollama_endpoint,
+        overrides,
+    ) {
+        Ok(request) => request,
+        Err(e) => return failed(ProviderTestErrorKind::Other, e),
+    };
+    let request_start = Instant::now();
+    match request.timeout(timeout).send().await {
+        Ok(response) if response.status().is_success() => {
+            let latency_ms = elapsed_ms(request_start);
+            let parsed = match response.text().await {
+                Ok(body) => parse_completion(&llm_provider, &body),
+                Err(e) => Err(e.to_string()),
+            };
+            match parsed {
+                Ok(_) => ProviderTestResult {
+                    provider: provider.to_string(),
+                    model: model.to_string(),
+                    ok: true,
+                    latency_ms,
+                    error_kind: None,
+                    error: None,
+                },
+                Err(e) => failed(
+                    ProviderTestErrorKind::Other,
+                    format!("{} sent an unexpected response: {}", provider, e),
+                ),
+            }
+        }
+        Ok(response) => {
+            let status = response.status().as_u16();
+            let body = response.text().await.unwrap_or_default();
+            let kind = classify_test_failure(status, &body);
+            let error = match kind {
+                ProviderTestErrorKind::InvalidKey => format!("{} rejected the API key", provider),
+                ProviderTestErrorKind::ModelNotFound => {
+                    format!("{} has no model '{}'", provider, model)
+                }
+                ProviderTestErrorKind::RateLimited => {
+                    format!("{} is rate limiting requests; try again shortly", provider)
+                }
+                _ => format!("{} returned unexpected status {}", provider, status),
+            };
+            failed(kind, error)
+        }
+        Err(e) if e.is_timeout() => failed(
+            ProviderTestErrorKind::Network,
+            format!("{} did not respond within {}ms", provider, timeout.as_millis()),
+        ),
+        // Without the URL, Gemini's carries the key
+        Err(e) => failed(
+            ProviderTestErrorKind::Network,
+            format!("Could not reach {}: {}", provider, e.without_url()),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::test_server::{
+        closed_url, json_response, mock_server, request_lines, serve, silent_server, Requests,
+    };
+
+    /// Like `mock_server`, answering by the first route whose path the
+    /// request line contains
+    async fn routed_server(routes: Vec<(&'static str, u16, &'static str)>) -> (String, Requests) {
+        serve(move |request| {
+            let request_line = request.lines().next().unwrap_or_default();
+            let (status, body) = routes
+                .iter()
+                .find(|(path, _, _)| request_line.contains(path))
+                .map(|(_, status, body)| (*status, *body))
+                .unwrap_or((404, "{}"));
+            json_response(status, body)
+        })
+        .await
+    }
+
+    fn base_url_override(url: &str) -> ProviderOverride {
+        ProviderOverride {
+            base_url: Some(url.to_string()),
+            ..ProviderOverride::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_detect_ollama_lists_models_and_reports_unreachable() {
+        let (url, _) =
+            mock_server(200, r#"{"models":[{"name":"llama3.2:latest"},{"name":"qwen2.5:7b"}]}"#)
+                .await;
+        let slow = silent_server().await;
+        let client = Client::new();
+
+        let results = detect_ollama(
+            &client,
+            &[url.clone(), slow.clone()],
+            Duration::from_millis(300),
+        )
+        .await;
+
+        assert_eq!(results.len(), 2);
+        assert!(results[0].reachable);
+        assert_eq!(results[0].endpoint, url);
+        assert_eq!(results[0].models, vec!["llama3.2:latest", "qwen2.5:7b"]);
+        assert!(!results[1].reachable);
+        assert!(results[1].error.as_deref().unwrap().contains("Timed out"));
+        assert!(results[1].models.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_validate_provider_sends_key_and_accepts_success() {
+        let (url, requests) = mock_server(200, r#"{"data":[]}"#).await;
+        let client = Client::new();
+
+        let result =
+            validate_provider(&client, "claude", "sk-ant-test", Some(&url), PROVIDER_PROBE_TIMEOUT)
+                .await;
+
+        assert!(result.valid, "{:?}", result.error);
+        assert_eq!(result.status, Some(200));
+        let request = requests.lock().unwrap()[0].to_lowercase();
+        assert!(request.starts_with("get /models"));
+        assert!(request.contains("x-api-key: sk-ant-test"));
+        assert!(request.contains("anthropic-version"));
+    }
+
+    #[tokio::test]
+    async fn test_validate_provider_reports_rejected_key() {
+        let (url, requests) = mock_server(401, r#"{"error":"invalid key"}"#).await;
+        let client = Client::new();
+
+        let result =
+            validate_provider(&client, "openai", "sk-bad", Some(&url), PROVIDER_PROBE_TIMEOUT).await;
+
+        assert!(!result.valid);
+        assert_eq!(result.status, Some(401));
+        assert_eq!(result.error.as_deref(), Some("openai rejected the API key"));
+        assert!(requests.lock().unwrap()[0]
+            .to_lowercase()
+            .contains("authorization: bearer sk-bad"));
+    }
+
+    #[tokio::test]
+    async fn test_validate_provider_without_request() {
+        let client = Client::new();
+
+        let missing_key = validate_provider(&client, "groq", "  ", None, PROVIDER_PROBE_TIMEOUT).await;
+        assert!(!missing_key.valid);
+        assert_eq!(missing_key.status, None);
+
+        let unknown =
+            validate_provider(&client, "not-a-provider", "key", None, PROVIDER_PROBE_TIMEOUT).await;
+        assert!(!unknown.valid);
+        assert!(unknown.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_validate_provider_times_out() {
+        let slow = silent_server().await;
+        let client = Client::new();
+
+        let result =
+            validate_provider(&client, "openai", "sk-test", Some(&slow), Duration::from_millis(200))
+                .await;
+
+        assert!(!result.valid);
+        assert!(result.error.as_deref().unwrap().contains("did not respond"));
+    }
+
+    #[tokio::test]
+    async fn test_llm_provider_test_maps_errors() {
+        let cases = [
+            (
+                "openai",
+                401,
+                r#"{"error":{"code":"invalid_api_key"}}"#,
+                ProviderTestErrorKind::InvalidKey,
+            ),
+            (
+                "claude",
+                404,
+                r#"{"type":"error","error":{"type":"not_found_error"}}"#,
+                ProviderTestErrorKind::ModelNotFound,
+            ),
+            (
+                "groq",
+                429,
+                r#"{"error":{"message":"Rate limit reached"}}"#,
+                ProviderTestErrorKind::RateLimited,
+            ),
+            (
+                "gemini",
+                400,
+                r#"{"error":{"status":"INVALID_ARGUMENT","details":[{"reason":"API_KEY_INVALID"}]}}"#,
+                ProviderTestErrorKind::InvalidKey,
+            ),
+            (
+                "openrouter",
+                400,
+                r#"{"error":{"message":"acme/nope is not a valid model ID"}}"#,
+                ProviderTestErrorKind::ModelNotFound,
+            ),
+            (
+                "openai",
+                500,
+                r#"{"error":"boom"}"#,
+                ProviderTestErrorKind::Other,
+            ),
+        ];
+        let client = Client::new();
+        for (provider, status, body, expected) in cases {
+            let (url, requests) = mock_server(status, body).await;
+            let result = test_llm_provider(
+                &client,
+                provider,
+                "some-model",
+                "key-123",
+                None,
+                &base_url_override(&url),
+                PROVIDER_PROBE_TIMEOUT,
+            )
+            .await;
+
+            assert!(!result.ok, "{} {}", provider, status);
+            assert_eq!(result.error_kind, Some(expected), "{} {}", provider, status);
+            assert_eq!(requests.lock().unwrap().len(), 1);
+        }
+
+        // No key: nothing is sent
+        let missing = test_llm_provider(
+            &client,
+            "openai",
+            "gpt-4o-mini",
+            " ",
+            None,
+            &ProviderOverride::default(),
+            PROVIDER_PROBE_TIMEOUT,
+        )
+        .await;
+        assert_eq!(missing.error_kind, Some(ProviderTestErrorKind::InvalidKey));
+
+        let slow = silent_server().await;
+        let timed_out = test_llm_provider(
+            &client,
+            "claude",
+            "claude-3-5-haiku-latest",
+            "key-123",
+            None,
+            &base_url_override(&slow),
+            Duration::from_millis(200),
+        )
+        .await;
+        assert_eq!(timed_out.error_kind, Some(ProviderTestErrorKind::Network));
+    }
+
+    #[tokio::test]
+    async fn test_llm_provider_test_succeeds_with_completion() {
+        let (url, requests) =
+            mock_server(200, r#"{"content":[{"type":"text","text":"OK"}],"stop_reason":"end_turn"}"#)
+                .await;
+        let client = Client::new();
+
+        let result = test_llm_provider(
+            &client,
+            "claude",
+            "claude-3-5-haiku-latest",
+            "sk-ant-test",
+            None,
+            &base_url_override(&url),
+            PROVIDER_PROBE_TIMEOUT,
+        )
+        .await;
+
+        assert!(result.ok, "{:?}", result.error);
+        assert_eq!(result.error_kind, None);
+        let request = requests.lock().unwrap()[0].to_lowercase();
+        assert!(request.starts_with("post /messages"));
+        assert!(request.contains("x-api-key: sk-ant-test"));
+        assert!(request.contains("reply with ok."));
+    }
+
+    #[tokio::test]
+    async fn test_ollama_test_checks_server_and_model() {
+        let (url, requests) = routed_server(vec![
+            ("/api/tags", 200, r#"{"models":[{"name":"llama3.2:latest"}]}"#),
+            (
+                "/v1/chat/completions",
+                200,
+                r#"{"choices":[{"message":{"content":"OK"},"finish_reason":"stop"}]}"#,
+            ),
+        ])
+        .await;
+        let client = Client::new();
+        let test = |model: &'static str, endpoint: String| {
+            let client = client.clone();
+            async move {
+                test_llm_provider(
+                    &client,
+                    "ollama",
+                    model,
+                    "",
+                    Some(&endpoint),
+                    &ProviderOverride::default(),
+                    PROVIDER_PROBE_TIMEOUT,
+                )
+                .await
+            }
+        };
+
+        let present = test("llama3.2", url.clone()).await;
+        assert!(present.ok, "{:?}", present.error);
+        assert_eq!(
+            request_lines(&requests),
+            ["GET /api/tags HTTP/1.1", "POST /v1/chat/completions HTTP/1.1"]
+        );
+
+        // A model that isn't pulled is reported without a completion
+        requests.lock().unwrap().clear();
+        let missing = test("qwen2.5:7b", url).await;
+        assert_eq!(missing.error_kind, Some(ProviderTestErrorKind::ModelNotFound));
+        assert_eq!(request_lines(&requests), ["GET /api/tags HTTP/1.1"]);
+
+        let unreachable = test("llama3.2", closed_url().await).await;
+        assert_eq!(unreachable.error_kind, Some(ProviderTestErrorKind::Network));
+    }
+}
@@ -0,0 +1,449 @@
+//! Startup self-check of settings that point at things which may be gone
+//!
+//! After upgrades, meetings can default to a deleted template, the
+//! recordings folder may have been moved, and the model settings can name a
+//! provider or an Ollama endpoint that no longer parses. Each check either
+//! repairs the setting or leaves it alone, and reports what it found as a
+//! startup notice. Whenever a value is replaced the notice keeps the old one,
+//! so nothing the user configured is lost without a trace. Notices are kept
+//! in the app store until dismissed.
+
+use super::probes::DEFAULT_OLLAMA_ENDPOINTS;
+use crate::audio::recording_preferences::load_recording_preferences;
+use crate::database::repositories::meeting::MeetingsRepository;
+use crate::database::repositories::setting::SettingsRepository;
+use crate::state::AppState;
+use crate::summary::llm_client::LLMProvider;
+use crate::summary::templates;
+use chrono::Utc;
+use log::{info, warn};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::path::Path;
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_store::StoreExt;
+
+const NOTICES_STORE: &str = "startup-notices.json";
+const NOTICES_KEY: &str = "notices";
+
+/// Template meetings are pointed at when theirs no longer resolves
+pub const FALLBACK_TEMPLATE_ID: &str = "standard_meeting";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupNoticeKind {
+    MissingTemplate,
+    RecordingsFolder,
+    UnknownProvider,
+    InvalidOllamaEndpoint,
+}
+
+/// One finding of the self-check
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StartupNotice {
+    pub id: String,
+    pub kind: StartupNoticeKind,
+    pub message: String,
+    /// The value found in the settings
+    pub previous_value: Option<String>,
+    /// What it was replaced with; None when the setting was left as it was
+    pub replacement: Option<String>,
+    pub created_at: String,
+}
+
+impl StartupNotice {
+    fn new(
+        kind: StartupNoticeKind,
+        message: String,
+        previous_value: Option<String>,
+        replacement: Option<String>,
+    ) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            kind,
+            message,
+            previous_value,
+            replacement,
+            created_at: Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Whether `other` reports the same problem, so a check failing on every
+    /// start adds one notice rather than one per start
+    fn same_finding(&self, other: &StartupNotice) -> bool {
+        self.kind == other.kind
+            && self.previous_value == other.previous_value
+            && self.replacement == other.replacement
+    }
+}
+
+/// Resets meeting defaults naming a template `resolves` rejects to
+/// `FALLBACK_TEMPLATE_ID`
+pub async fn check_templates(
+    pool: &SqlitePool,
+    resolves: impl Fn(&str) -> bool,
+) -> Result<Vec<StartupNotice>, String> {
+    let template_ids = MeetingsRepository::preferred_template_ids(pool)
+        .await
+        .map_err(|e| format!("Failed to load meeting templates: {}", e))?;
+    let mut notices = Vec::new();
+    for template_id in template_ids {
+        if template_id == FALLBACK_TEMPLATE_ID || resolves(&template_id) {
+            continue;
+        }
+        let changed = MeetingsRepository::replace_preferred_template(
+            pool,
+            &template_id,
+            FALLBACK_TEMPLATE_ID,
+        )
+        .await
+        .map_err(|e| format!("Failed to reset template '{}': {}", template_id, e))?;
+        warn!(
+            "⚠️ Template '{}' not found; {} meeting(s) now default to '{}'",
+            template_id, changed, FALLBACK_TEMPLATE_ID
+        );
+        notices.push(StartupNotice::new(
+            StartupNoticeKind::MissingTemplate,
+            format!(
+                "Template '{}' no longer exists; {} meeting(s) using it now default to '{}'",
+                template_id, changed, FALLBACK_TEMPLATE_ID
+            ),
+            Some(template_id),
+            Some(FALLBACK_TEMPLATE_ID.to_string()),
+        ));
+    }
+    Ok(notices)
+}
+
+/// Creates the recordings folder if it is missing
+///
+/// A folder that can't be created stays configured, as the user may only
+/// have to reconnect a drive.
+pub fn check_recordings_folder(folder: &Path) -> Option<StartupNotice> {
+    if folder.is_dir() {
+        return None;
+    }
+    let previous = Some(folder.to_string_lossy().to_string());
+    let message = if folder.exists() {
+        format!(
+            "Recordings folder {} is not a folder; choose another one in the recording settings",
+            folder.display()
+        )
+    } else {
+        match std::fs::create_dir_all(folder) {
+            Ok(()) => {
+                info!("📁 Created missing recordings folder {:?}", folder);
+                format!(
+                    "Recordings folder {} was missing and has been created",
+                    folder.display()
+                )
+            }
+            Err(e) => format!(
+                "Recordings folder {} is missing and could not be created: {}",
+                folder.display(),
+                e
+            ),
+        }
+    };
+    Some(StartupNotice::new(
+        StartupNoticeKind::RecordingsFolder,
+        message,
+        previous,
+        None,
+    ))
+}
+
+/// http(s) URL with a host, the only kind the Ollama client can reach
+pub fn is_valid_ollama_endpoint(endpoint: &str) -> bool {
+    Url::parse(endpoint.trim()).is_ok_and(|url| {
+        matches!(url.scheme(), "http" | "https") && url.host_str().is_some_and(|h| !h.is_empty())
+    })
+}
+
+/// Checks the summary provider parses and clears a malformed Ollama endpoint
+/// so the default one is used
+///
+/// An unknown provider is only reported: there is no safe guess which one the
+/// user meant, and the model stored with it would not fit another.
+pub async fn check_model_config(pool: &SqlitePool) -> Result<Vec<StartupNotice>, String> {
+    let Some(config) = SettingsRepository::get_model_config(pool)
+        .await
+        .map_err(|e| format!("Failed to load model config: {}", e))?
+    else {
+        return Ok(Vec::new());
+    };
+    let mut notices = Vec::new();
+
+    if let Err(e) = LLMProvider::from_str(&config.provider) {
+        warn!("⚠️ {}", e);
+        notices.push(StartupNotice::new(
+            StartupNoticeKind::UnknownProvider,
+            format!(
+                "Summary provider '{}' is not supported; select a provider in the model settings",
+                config.provider
+            ),
+            Some(config.provider.clone()),
+            None,
+        ));
+    }
+
+    let endpoint = config
+        .ollama_endpoint
+        .as_deref()
+        .map(str::trim)
+        .filter(|endpoint| !endpoint.is_empty());
+    if let Some(endpoint) = endpoint.filter(|endpoint| !is_valid_ollama_endpoint(endpoint)) {
+        SettingsRepository::save_model_config(
+            pool,
+            &config.provider,
+            &config.model,
+            &config.whisper_model,
+            None,
+        )
+        .await
+        .map_err(|e| format!("Failed to reset Ollama endpoint: {}", e))?;
+        warn!(
+            "⚠️ Malformed Ollama endpoint '{}' replaced by the default",
+            endpoint
+        );
+        notices.push(StartupNotice::new(
+            StartupNoticeKind::InvalidOllamaEndpoint,
+            format!(
+                "Ollama endpoint '{}' is not a valid http(s) URL; using {} instead",
+                endpoint, DEFAULT_OLLAMA_ENDPOINTS[0]
+            ),
+            Some(endpoint.to_string()),
+            Some(DEFAULT_OLLAMA_ENDPOINTS[0].to_string()),
+        ));
+    }
+    Ok(notices)
+}
+
+pub fn load_startup_notices<R: Runtime>(app: &AppHandle<R>) -> Vec<StartupNotice> {
+    let stored = app
+        .store(NOTICES_STORE)
+        .ok()
+        .and_then(|store| store.get(NOTICES_KEY));
+    match stored.map(serde_json::from_value::<Vec<StartupNotice>>) {
+        Some(Ok(notices)) => notices,
+        Some(Err(e)) => {
+            warn!("⚠️ Invalid startup notices in store, ignoring them: {}", e);
+            Vec::new()
+        }
+        None => Vec::new(),
+    }
+}
+
+pub fn save_startup_notices<R: Runtime>(
+    app: &AppHandle<R>,
+    notices: &[StartupNotice],
+) -> Result<(), String> {
+    let store = app
+        .store(NOTICES_STORE)
+        .map_err(|e| format!("Failed to open startup notices store: {}", e))?;
+    let value = serde_json::to_value(notices)
+        .map_err(|e| format!("Failed to serialize startup notices: {}", e))?;
+    store.set(NOTICES_KEY, value);
+    store
+        .save()
+        .map_err(|e| format!("Failed to save startup notices: {}", e))
+}
+
+/// Appends the findings not already among the pending notices
+pub fn merge_notices(pending: &mut Vec<StartupNotice>, found: Vec<StartupNotice>) -> usize {
+    let before = pending.len();
+    for notice in found {
+        if !pending.iter().any(|p| p.same_finding(&notice)) {
+            pending.push(notice);
+        }
+    }
+    pending.len() - before
+}
+
+/// Runs every check once, in the background, and stores the findings
+///
+/// Meant to run after the templates (including cached remote ones) are set
+/// up, so only templates that are really gone are replaced.
+pub fn start_startup_self_check<R: Runtime>(app: &AppHandle<R>) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let Some(state) = app.try_state::<AppState>() else {
+            return;
+        };
+        let pool = state.db_manager.pool();
+        let mut found = Vec::new();
+
+        match check_templates(pool, |id| templates::get_template(id).is_ok()).await {
+            Ok(notices) => found.extend(notices),
+            Err(e) => warn!("⚠️ {}", e),
+        }
+        match load_recording_preferences(&app).await {
+            Ok(prefs) => found.extend(check_recordings_folder(&prefs.save_folder)),
+            Err(e) => warn!("⚠️ Failed to load recording preferences: {}", e),
+        }
+        match check_model_config(pool).await {
+            Ok(notices) => found.extend(notices),
+            Err(e) => warn!("⚠️ {}", e),
+        }
+
+        if found.is_empty() {
+            info!("✅ Startup self-check found nothing to fix");
+            return;
+        }
+        let mut pending = load_startup_notices(&app);
+        let added = merge_notices(&mut pending, found);
+        if added > 0 {
+            info!("🩺 Startup self-check added {} notice(s)", added);
+            if let Err(e) = save_startup_notices(&app, &pending) {
+                warn!("⚠️ {}", e);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::{seed_meetings, test_pool};
+
+    async fn save_model_config(pool: &SqlitePool, provider: &str, endpoint: Option<&str>) {
+        SettingsRepository::save_model_config(pool, provider, "llama3", "large-v3", endpoint)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_missing_template_is_reset_and_recorded() {
+        let pool = test_pool().await;
+        seed_meetings(&pool, 3, 0).await;
+        for (meeting_id, template_id) in [
+            ("meeting-0", "deleted_retro"),
+            ("meeting-1", "deleted_retro"),
+            ("meeting-2", "daily_standup"),
+        ] {
+            sqlx::query("UPDATE meetings SET preferred_template_id = ? WHERE id = ?")
+                .bind(template_id)
+                .bind(meeting_id)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let notices = check_templates(&pool, |id| id == "daily_standup")
+            .await
+            .unwrap();
+        assert_eq!(notices.len(), 1);
+        assert_eq!(notices[0].kind, StartupNoticeKind::MissingTemplate);
+        assert_eq!(notices[0].previous_value.as_deref(), Some("deleted_retro"));
+        assert_eq!(
+            notices[0].replacement.as_deref(),
+            Some(FALLBACK_TEMPLATE_ID)
+        );
+        assert!(notices[0].message.contains("2 meeting(s)"));
+
+        let ids = MeetingsRepository::preferred_template_ids(&pool)
+            .await
+            .unwrap();
+        assert_eq!(ids, vec!["daily_standup", FALLBACK_TEMPLATE_ID]);
+        // Nothing left to fix on the next start
+        assert!(check_templates(&pool, |id| id == "daily_standup")
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_missing_recordings_folder_is_created() {
+        let dir = std::env::temp_dir().join(format!("self-check-{}", uuid::Uuid::new_v4()));
+        let folder = dir.join("recordings");
+
+        let notice = check_recordings_folder(&folder).unwrap();
+        assert_eq!(notice.kind, StartupNoticeKind::RecordingsFolder);
+        assert!(notice.message.contains("has been created"));
+        assert_eq!(
+            notice.previous_value,
+            Some(folder.to_string_lossy().to_string())
+        );
+        assert!(folder.is_dir());
+        assert_eq!(check_recordings_folder(&folder), None);
+
+        // A file in the way is reported, not removed
+        let file = dir.join("not-a-folder");
+        std::fs::write(&file, "keep me").unwrap();
+        let notice = check_recordings_folder(&file).unwrap();
+        assert!(notice.message.contains("is not a folder"));
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "keep me");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_model_config_checks() {
+        let pool = test_pool().await;
+        assert!(check_model_config(&pool).await.unwrap().is_empty());
+
+        save_model_config(&pool, "ollama", Some("http://gpu-box:11434")).await;
+        assert!(check_model_config(&pool).await.unwrap().is_empty());
+
+        save_model_config(&pool, "mistral-cloud", Some("localhost 11434")).await;
+        let notices = check_model_config(&pool).await.unwrap();
+        let kinds: Vec<StartupNoticeKind> = notices.iter().map(|n| n.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                StartupNoticeKind::UnknownProvider,
+                StartupNoticeKind::InvalidOllamaEndpoint
+            ]
+        );
+        assert_eq!(notices[0].previous_value.as_deref(), Some("mistral-cloud"));
+        assert_eq!(notices[0].replacement, None);
+        assert_eq!(
+            notices[1].previous_value.as_deref(),
+            Some("localhost 11434")
+        );
+        assert_eq!(
+            notices[1].replacement.as_deref(),
+            Some(DEFAULT_OLLAMA_ENDPOINTS[0])
+        );
+
+        // The provider and model are kept, only the endpoint is cleared
+        let config = SettingsRepository::get_model_config(&pool)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(config.provider, "mistral-cloud");
+        assert_eq!(config.model, "llama3");
+        assert_eq!(config.ollama_endpoint, None);
+    }
+
+    #[test]
+    fn test_endpoint_validation() {
+        for endpoint in ["http://localhost:11434", " https://ollama.example.com/ "] {
+            assert!(is_valid_ollama_endpoint(endpoint), "{}", endpoint);
+        }
+        for endpoint in ["localhost:11434", "ftp://host", "http://", "not a url"] {
+            assert!(!is_valid_ollama_endpoint(endpoint), "{}", endpoint);
+        }
+    }
+
+    #[test]
+    fn test_repeated_findings_are_not_duplicated() {
+        let notice = |previous: &str| {
+            StartupNotice::new(
+                StartupNoticeKind::UnknownProvider,
+                "unsupported".to_string(),
+                Some(previous.to_string()),
+                None,
+            )
+        };
+        let mut pending = vec![notice("mistral-cloud")];
+        assert_eq!(
+            merge_notices(
+                &mut pending,
+                vec![notice("mistral-cloud"), notice("cohere")]
+            ),
+            1
+        );
+        assert_eq!(pending.len(), 2);
+    }
+}
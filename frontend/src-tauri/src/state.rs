@@ -1,5 +1,25 @@
+use crate::audio::recording_registry::RecordingRegistry;
 use crate::database::manager::DatabaseManager;
+use crate::database::repositories::Repositories;
+use crate::database::transcripts_json::TranscriptsJsonCache;
+use std::sync::Arc;
 
 pub struct AppState {
     pub db_manager: DatabaseManager,
+    pub repos: Repositories,
+    pub recordings: Arc<RecordingRegistry>,
+    /// Parsed `transcripts.json` fallbacks of meetings with no stored segments
+    pub transcripts_json: TranscriptsJsonCache,
+}
+
+impl AppState {
+    pub fn new(db_manager: DatabaseManager) -> Self {
+        let repos = Repositories::sqlx(db_manager.pool().clone());
+        Self {
+            db_manager,
+            repos,
+            recordings: Arc::new(RecordingRegistry::new()),
+            transcripts_json: TranscriptsJsonCache::default(),
+        }
+    }
 }
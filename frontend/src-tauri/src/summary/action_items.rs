@@ -0,0 +1,423 @@
+use crate::api::TranscriptSegment;
+use crate::summary::processor::{
+    clean_llm_markdown_output, extract_section_content, heading_title, ChunkMarkers,
+};
+use crate::summary::table::{
+    is_separator_row, is_table_line, normalize_tables, split_cells, unescape_cell,
+};
+use crate::summary::templates::TableSchema;
+use crate::utils::format_timestamp;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// HH:MM:SS, H:MM:SS or MM:SS
+static CLOCK_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b(?:(\d{1,2}):)?(\d{1,2}):(\d{2})\b").unwrap());
+
+/// System prompt of the action-items-only extraction
+pub const ACTION_ITEMS_SYSTEM_PROMPT: &str = "You extract action items from meeting transcripts. Reply with a single markdown table and nothing else: no headings, no commentary. Only list tasks someone committed to or was asked to do, with the exact owner names, deadlines and task IDs (e.g., PROJ-404) used in the meeting.";
+
+/// One row of the Action Items table
+/// (Owner | Task | Due | Reference Transcript Segment | Segment Time stamp)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ActionItem {
+    pub owner: String,
+    pub task: String,
+    pub due: String,
+    pub reference: String,
+    pub timestamp: String,
+    /// Recording time range of `timestamp` in seconds, so playback can jump
+    /// to where the item was discussed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_seconds: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_seconds: Option<f64>,
+    /// Directory match for `owner`; None until resolution has run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner_match: Option<OwnerMatch>,
+}
+
+/// Person an owner resolved to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResolvedPerson {
+    pub person_id: String,
+    pub display_name: String,
+    pub email: Option<String>,
+    pub jira_account_id: Option<String>,
+}
+
+/// Outcome of matching an owner against the people directory
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum OwnerMatch {
+    Resolved { person: ResolvedPerson },
+    /// Several people fit equally well; the user has to pick
+    Ambiguous { candidates: Vec<ResolvedPerson> },
+    /// Nobody matched; the owner text is kept as written
+    Unmatched,
+}
+
+impl ActionItem {
+    pub fn resolved_person(&self) -> Option<&ResolvedPerson> {
+        match &self.owner_match {
+            Some(OwnerMatch::Resolved { person }) => Some(person),
+            _ => None,
+        }
+    }
+}
+
+/// Cells with markdown bold and pipe escapes removed
+fn split_row(line: &str) -> Vec<String> {
+    split_cells(line)
+        .into_iter()
+        .map(|cell| unescape_cell(cell.trim_matches('*').trim()))
+        .collect()
+}
+
+/// Column index of each field, from the header row
+struct Columns {
+    owner: usize,
+    task: usize,
+    due: Option<usize>,
+    reference: Option<usize>,
+    timestamp: Option<usize>,
+}
+
+impl Columns {
+    fn from_header(cells: &[String]) -> Option<Self> {
+        let find = |pred: &dyn Fn(&str) -> bool| {
+            cells.iter().position(|c| pred(&c.to_lowercase()))
+        };
+        Some(Self {
+            owner: find(&|c| c.contains("owner"))?,
+            task: find(&|c| c.contains("task"))?,
+            due: find(&|c| c.contains("due")),
+            reference: find(&|c| c.contains("reference")),
+            timestamp: find(&|c| c.contains("time")),
+        })
+    }
+}
+
+/// Parses the Action Items table of a summary
+///
+/// Columns are located by header name, so reordered tables still parse.
+/// Rows without a task are skipped.
+pub fn parse_action_items(markdown: &str) -> Vec<ActionItem> {
+    let section = match extract_section_content(markdown, "Action Items", true) {
+        Some(section) => section,
+        None => return Vec::new(),
+    };
+
+    let mut columns: Option<Columns> = None;
+    let mut items = Vec::new();
+    for line in section.lines() {
+        if !is_table_line(line) || is_separator_row(line) {
+            continue;
+        }
+        let cells = split_row(line);
+        let cols = match &columns {
+            Some(cols) => cols,
+            None => {
+                columns = Columns::from_header(&cells);
+                continue;
+            }
+        };
+
+        let cell = |index: Option<usize>| {
+            index
+                .and_then(|i| cells.get(i))
+                .cloned()
+                .unwrap_or_default()
+        };
+        let task = cell(Some(cols.task));
+        if task.is_empty() {
+            continue;
+        }
+        let timestamp = cell(cols.timestamp);
+        let range = parse_timestamp_range(&timestamp);
+        items.push(ActionItem {
+            owner: cell(Some(cols.owner)),
+            task,
+            due: cell(cols.due),
+            reference: cell(cols.reference),
+            start_seconds: range.map(|(start, _)| start),
+            end_seconds: range.map(|(_, end)| end),
+            timestamp,
+            owner_match: None,
+        });
+    }
+    items
+}
+
+/// Action items pulled from a transcript without a full summary
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExtractedActionItems {
+    pub items: Vec<ActionItem>,
+    /// The items as a canonical Action Items table; empty if there are none
+    pub table: String,
+}
+
+/// User prompt asking only for the canonical Action Items table
+pub fn action_items_prompt(transcript: &str, markers: &ChunkMarkers) -> String {
+    let schema = TableSchema::action_items();
+    format!(
+        "List every action item from the meeting transcript below as a markdown table with exactly these columns: {names}\n\n- One row per task; use \"Not specified\" when the transcript doesn't name an owner or due date\n- Reference Transcript Segment quotes the words the task came from\n- If there are no action items, reply with only the header and separator rows\n\n{header}\n{separator}\n\n<{tag}>\n{transcript}\n</{tag}>",
+        names = schema.names(),
+        header = schema.header(),
+        separator = schema.separator(),
+        tag = markers.transcript_tag,
+        transcript = transcript,
+    )
+}
+
+/// Turns the model's reply into the canonical table and typed items
+///
+/// The first table in the reply is used. Its columns are matched to the
+/// canonical layout by name or synonym, so reordered or renamed columns
+/// still land in the right place; rows without a task are dropped.
+pub fn parse_extracted_action_items(response: &str) -> ExtractedActionItems {
+    let schema = TableSchema::action_items();
+    let (normalized, _) = normalize_tables(&clean_llm_markdown_output(response));
+
+    let mut targets: Option<Vec<Option<usize>>> = None;
+    let mut rows = Vec::new();
+    for line in normalized.lines() {
+        if targets.is_some() && !is_table_line(line) {
+            break;
+        }
+        if !is_table_line(line) || is_separator_row(line) {
+            continue;
+        }
+        let cells = split_row(line);
+        if targets.is_none() {
+            // Canonical column of each header cell
+            targets = Some(cells.iter().map(|cell| schema.column_for(cell)).collect());
+            continue;
+        }
+        let mut row = vec![String::new(); schema.columns.len()];
+        for (cell, target) in cells.into_iter().zip(targets.iter().flatten()) {
+            if let Some(column) = target {
+                row[*column] = cell;
+            }
+        }
+        if !row[schema.primary_index()].is_empty() {
+            rows.push(schema.row(&row));
+        }
+    }
+
+    if rows.is_empty() {
+        return ExtractedActionItems {
+            items: Vec::new(),
+            table: String::new(),
+        };
+    }
+    let table = format!("{}\n{}\n{}", schema.header(), schema.separator(), rows.join("\n"));
+    ExtractedActionItems {
+        items: parse_action_items(&format!("## Action Items\n\n{}", table)),
+        table,
+    }
+}
+
+/// Start and end in seconds of a timestamp cell such as "00:01:05",
+/// "[00:01:05–00:01:12]" or "1:05 - 1:12"; a single time starts and ends
+/// at once. None if the cell has no time.
+pub fn parse_timestamp_range(cell: &str) -> Option<(f64, f64)> {
+    let mut times = CLOCK_RE.captures_iter(cell).map(|caps| {
+        let part = |i: usize| caps.get(i).map_or(0.0, |m| m.as_str().parse::<f64>().unwrap_or(0.0));
+        part(1) * 3600.0 + part(2) * 60.0 + part(3)
+    });
+    let start = times.next()?;
+    Some((start, times.next().unwrap_or(start)))
+}
+
+/// A range as written in the Segment Time stamp column
+pub fn format_timestamp_range(start: f64, end: f64) -> String {
+    format!("{}–{}", format_timestamp(start), format_timestamp(end))
+}
+
+/// The segment range containing `time`, or else the one closest to it
+fn nearest_segment(segments: &[(f64, f64)], time: f64) -> Option<(f64, f64)> {
+    let distance = |&(start, end): &(f64, f64)| {
+        if time < start {
+            start - time
+        } else if time > end {
+            time - end
+        } else {
+            0.0
+        }
+    };
+    segments
+        .iter()
+        .copied()
+        .min_by(|a, b| distance(a).total_cmp(&distance(b)))
+}
+
+/// Rewrites the Segment Time stamp column of the Action Items table as
+/// `HH:MM:SS–HH:MM:SS` ranges that exist in the recording
+///
+/// A single time becomes the range of the segment it falls in. Ranges that
+/// run backwards or past the end of the recording are replaced with the
+/// segment nearest to their start. Cells without a time ("Not specified")
+/// are left, as is everything when no segment has timing.
+pub fn normalize_action_item_timestamps(markdown: &str, segments: &[TranscriptSegment]) -> String {
+    let mut timed: Vec<(f64, f64)> = segments
+        .iter()
+        .filter_map(|segment| {
+            let start = segment.audio_start_time?;
+            Some((start, segment.audio_end_time.unwrap_or(start).max(start)))
+        })
+        .collect();
+    if timed.is_empty() {
+        return markdown.to_string();
+    }
+    timed.sort_by(|a, b| a.0.total_cmp(&b.0));
+    // Stamps are whole seconds, so the last one may round past the end
+    let duration = timed.iter().map(|&(_, end)| end).fold(0.0, f64::max).ceil();
+
+    let fix = |cell: &str| -> Option<String> {
+        let (start, end) = parse_timestamp_range(cell)?;
+        let (start, end) = if start < end && end <= duration {
+            (start, end)
+        } else {
+            nearest_segment(&timed, start.min(duration)).unwrap_or((start, end))
+        };
+        Some(format_timestamp_range(start, end))
+    };
+
+    let mut section_level: Option<usize> = None;
+    let mut column: Option<Option<usize>> = None;
+    let lines: Vec<String> = markdown
+        .lines()
+        .map(|line| {
+            if let Some((level, title)) = heading_title(line) {
+                if section_level.map_or(true, |section| level <= section) {
+                    section_level = (level >= 2 && title.eq_ignore_ascii_case("Action Items"))
+                        .then_some(level);
+                }
+                column = None;
+                return line.to_string();
+            }
+            if section_level.is_none() || !is_table_line(line) {
+                column = None;
+                return line.to_string();
+            }
+            if is_separator_row(line) {
+                return line.to_string();
+            }
+            let mut cells = split_cells(line);
+            let index = match column {
+                Some(index) => index,
+                None => {
+                    column = Some(cells.iter().position(|c| c.to_lowercase().contains("time")));
+                    return line.to_string();
+                }
+            };
+            let fixed = index.and_then(|i| cells.get(i)).and_then(|cell| fix(cell));
+            match (index, fixed) {
+                (Some(i), Some(fixed)) if cells[i] != fixed => {
+                    cells[i] = fixed;
+                    format!("| {} |", cells.join(" | "))
+                }
+                _ => line.to_string(),
+            }
+        })
+        .collect();
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::segment;
+
+    #[test]
+    fn test_parse_action_items_table() {
+        let markdown = "# Standup\n\n## Action Items\n\n| **Owner** | **Task** | **Due** | **Reference Transcript Segment** | **Segment Time stamp** |\n|---|---|---|---|---|\n| **Alex** | Fix PROJ-404 login bug | Friday | \"I'll take the login bug\" | 00:01:10 |\n| Two developers | Review PR | | | |\n| Sam | | Monday | | |\n\n## Decisions\n\n| Owner | Task |\n|---|---|\n| X | ignored |\n";
+
+        let items = parse_action_items(markdown);
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].owner, "Alex");
+        assert_eq!(items[0].task, "Fix PROJ-404 login bug");
+        assert_eq!(items[0].due, "Friday");
+        assert_eq!(items[0].timestamp, "00:01:10");
+        assert_eq!(items[1].owner, "Two developers");
+        assert_eq!(items[1].due, "");
+        assert!(items.iter().all(|item| item.owner_match.is_none()));
+    }
+
+    #[test]
+    fn test_normalize_action_item_timestamps() {
+        let segments = [
+            segment("s1", "text", 0.0, 4.5),
+            segment("s2", "text", 65.0, 72.0),
+            segment("s3", "text", 300.0, 310.2),
+        ];
+        let markdown = "## Action Items\n\n| Owner | Task | Segment Time stamp |\n|---|---|---|\n\
+             | Alex | Fix PROJ-404 | [00:01:05 - 00:01:12] |\n\
+             | Sam | Docs | 1:07 |\n\
+             | Ana | Deploy | 02:00:00–02:00:30 |\n\
+             | Priya | Review | 00:05:10-00:05:00 |\n\
+             | Lee | Call | Not specified |\n\n\
+             ## Notes\n\n| Time | Note |\n|---|---|\n| 9:99 | left alone |";
+
+        let normalized = normalize_action_item_timestamps(markdown, &segments);
+        let items = parse_action_items(&normalized);
+        let stamps: Vec<&str> = items.iter().map(|item| item.timestamp.as_str()).collect();
+        assert_eq!(
+            stamps,
+            vec![
+                // Copied ranges are only reformatted
+                "00:01:05–00:01:12",
+                // A single time gets the range of its segment
+                "00:01:05–00:01:12",
+                // Past the end of the recording: the last segment
+                "00:05:00–00:05:10",
+                // Backwards: the segment around its start
+                "00:05:00–00:05:10",
+                "Not specified",
+            ]
+        );
+        assert_eq!((items[0].start_seconds, items[0].end_seconds), (Some(65.0), Some(72.0)));
+        assert_eq!(items[4].start_seconds, None);
+        // Only the Action Items table is touched
+        assert!(normalized.ends_with("| 9:99 | left alone |"));
+
+        // Without timed segments there is nothing to check against
+        assert_eq!(normalize_action_item_timestamps(markdown, &[]), markdown);
+    }
+
+    #[test]
+    fn test_parse_without_section_or_header() {
+        assert!(parse_action_items("# Notes\n\nNothing to do").is_empty());
+        assert!(parse_action_items("## Action Items\n\n- call Alex\n").is_empty());
+    }
+
+    #[test]
+    fn test_extract_action_items_from_reply() {
+        let transcript = "[00:00:05] Alex: I'll fix the PROJ-404 login bug by Friday.\n[00:00:12] Priya: I can update the release notes.\n[00:00:20] Sam: Sounds good, thanks both.";
+        let prompt = action_items_prompt(transcript, &ChunkMarkers::default());
+        assert!(prompt.contains(&TableSchema::action_items().header()));
+        assert!(prompt.contains(&format!("<transcript_chunks>\n{}\n</transcript_chunks>", transcript)));
+
+        // Renamed and reordered columns, fences and a trailing note
+        let reply = "```markdown\n| Task | Assignee | Deadline | Quote |\n|---|---|---|---|\n| Fix PROJ-404 login bug | **Alex** | Friday | I'll fix the PROJ-404 login bug |\n| Update release notes | Priya | | I can update the release notes |\n| | Sam | | |\n\nLet me know if you need more.\n```";
+        let extracted = parse_extracted_action_items(reply);
+
+        assert_eq!(extracted.items.len(), 2);
+        assert_eq!(extracted.items[0].owner, "Alex");
+        assert_eq!(extracted.items[0].task, "Fix PROJ-404 login bug");
+        assert_eq!(extracted.items[0].due, "Friday");
+        assert_eq!(extracted.items[0].reference, "I'll fix the PROJ-404 login bug");
+        assert_eq!(extracted.items[1].owner, "Priya");
+        assert_eq!(extracted.items[1].due, "Not specified");
+        assert!(extracted.table.starts_with(&TableSchema::action_items().header()));
+        assert_eq!(extracted.table.lines().count(), 4);
+
+        // Header only: no action items
+        let empty = parse_extracted_action_items("| Owner | Task | Due |\n|---|---|---|");
+        assert!(empty.items.is_empty());
+        assert_eq!(empty.table, "");
+    }
+}
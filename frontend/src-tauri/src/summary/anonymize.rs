@@ -0,0 +1,316 @@
+use crate::database::models::ParticipantMap;
+use crate::summary::llm_client::generate_summary;
+use crate::summary::processor::{chunk_spans, with_fallback, RetryPolicy, SummaryTarget};
+use regex::{Captures, Regex, RegexBuilder};
+use reqwest::Client;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use tracing::info;
+
+/// Role used when the model can't tell what a participant does
+const DEFAULT_ROLE: &str = "Participant";
+
+/// Longest role kept from the model's answer, in characters
+const MAX_ROLE_CHARS: usize = 40;
+
+/// Overlap between detection chunks so a name split at a boundary is still seen
+const DETECT_OVERLAP_TOKENS: usize = 50;
+
+const DETECT_SYSTEM_PROMPT: &str = "You find the people taking part in a meeting transcript. Reply with a JSON array only, no prose.";
+
+/// A participant found in the transcript by the model
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct DetectedParticipant {
+    pub name: String,
+    #[serde(default)]
+    pub role: Option<String>,
+}
+
+/// Builds a case-insensitive, whole-word pattern matching any of `names`
+///
+/// Longer names come first so "Sam Lee" is replaced as a whole rather than
+/// as "Sam" followed by "Lee".
+fn names_pattern<'a>(names: impl Iterator<Item = &'a str>) -> Option<Regex> {
+    let mut names: Vec<&str> = names.filter(|name| !name.trim().is_empty()).collect();
+    if names.is_empty() {
+        return None;
+    }
+    names.sort_by_key(|name| std::cmp::Reverse(name.chars().count()));
+    let alternatives: Vec<String> = names.iter().map(|name| regex::escape(name.trim())).collect();
+    RegexBuilder::new(&format!(r"\b(?:{})\b", alternatives.join("|")))
+        .case_insensitive(true)
+        .build()
+        .ok()
+}
+
+/// Names to look for mapped to their pseudonym, keyed by lowercase name
+///
+/// A multi-word name also matches its first word ("Sam" for "Sam Lee"),
+/// unless another participant shares that first word or it is mapped itself.
+fn lookup_table(name_map: &ParticipantMap) -> HashMap<String, String> {
+    let mut lookup: HashMap<String, String> = name_map
+        .iter()
+        .map(|(name, pseudonym)| (name.trim().to_lowercase(), pseudonym.clone()))
+        .collect();
+
+    let mut first_words: HashMap<String, Vec<&String>> = HashMap::new();
+    for (name, pseudonym) in name_map {
+        let mut words = name.split_whitespace();
+        if let (Some(first), Some(_)) = (words.next(), words.next()) {
+            first_words.entry(first.to_lowercase()).or_default().push(pseudonym);
+        }
+    }
+    for (first, pseudonyms) in first_words {
+        let unique: HashSet<&String> = pseudonyms.iter().copied().collect();
+        if unique.len() == 1 && !lookup.contains_key(&first) {
+            lookup.insert(first, pseudonyms[0].clone());
+        }
+    }
+    lookup
+}
+
+/// Replaces every mention of a mapped participant with their pseudonym
+///
+/// Matching is whole-word and case-insensitive, so "sam's" becomes
+/// "Engineer A's" but "Samantha" is left alone.
+pub fn anonymize_participants(text: &str, name_map: &ParticipantMap) -> String {
+    let lookup = lookup_table(name_map);
+    let Some(pattern) = names_pattern(lookup.keys().map(String::as_str)) else {
+        return text.to_string();
+    };
+    pattern
+        .replace_all(text, |caps: &Captures| {
+            lookup
+                .get(&caps[0].to_lowercase())
+                .cloned()
+                .unwrap_or_else(|| caps[0].to_string())
+        })
+        .into_owned()
+}
+
+/// Puts participant names back in place of their pseudonyms
+///
+/// When several names share a pseudonym the first one (alphabetically) is used.
+pub fn deanonymize_participants(text: &str, name_map: &ParticipantMap) -> String {
+    let mut names: HashMap<&str, &str> = HashMap::new();
+    for (name, pseudonym) in name_map {
+        names.entry(pseudonym.as_str()).or_insert(name.as_str());
+    }
+    if names.is_empty() {
+        return text.to_string();
+    }
+    // Pseudonyms are matched exactly; "engineer a" in prose is not one
+    let mut pseudonyms: Vec<&str> = names.keys().copied().collect();
+    pseudonyms.sort_by_key(|pseudonym| std::cmp::Reverse(pseudonym.len()));
+    let alternatives: Vec<String> = pseudonyms.iter().map(|p| regex::escape(p)).collect();
+    let Ok(pattern) = Regex::new(&format!(r"\b(?:{})\b", alternatives.join("|"))) else {
+        return text.to_string();
+    };
+    pattern
+        .replace_all(text, |caps: &Captures| names[&caps[0]].to_string())
+        .into_owned()
+}
+
+/// "A", "B", ... "Z", "AA", "AB", ...
+fn pseudonym_letter(mut index: usize) -> String {
+    let mut letters = Vec::new();
+    loop {
+        letters.push((b'A' + (index % 26) as u8) as char);
+        if index < 26 {
+            break;
+        }
+        index = index / 26 - 1;
+    }
+    letters.iter().rev().collect()
+}
+
+/// Title-cases a role from the model and keeps it short and plain
+fn clean_role(role: Option<&str>) -> String {
+    let role: String = role
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == ' ' || *c == '-')
+        .take(MAX_ROLE_CHARS)
+        .collect();
+    let words: Vec<String> = role
+        .split_whitespace()
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect();
+    if words.is_empty() {
+        DEFAULT_ROLE.to_string()
+    } else {
+        words.join(" ")
+    }
+}
+
+/// Adds pseudonyms for newly detected participants to an existing map
+///
+/// Names already in the map keep their pseudonym so re-runs stay consistent.
+/// New names get the next free letter for their role ("Engineer A",
+/// "Engineer B"), in the order they were detected.
+pub fn assign_pseudonyms(
+    existing: &ParticipantMap,
+    detected: &[DetectedParticipant],
+) -> ParticipantMap {
+    let mut map = existing.clone();
+    let mut taken: HashSet<String> = map.values().cloned().collect();
+    let mut known: HashSet<String> = map.keys().map(|name| name.to_lowercase()).collect();
+    let mut next_letter: BTreeMap<String, usize> = BTreeMap::new();
+
+    for participant in detected {
+        let name = participant.name.trim();
+        if name.is_empty() || !known.insert(name.to_lowercase()) {
+            continue;
+        }
+        let role = clean_role(participant.role.as_deref());
+        let index = next_letter.entry(role.clone()).or_insert(0);
+        let pseudonym = loop {
+            let candidate = format!("{} {}", role, pseudonym_letter(*index));
+            *index += 1;
+            if taken.insert(candidate.clone()) {
+                break candidate;
+            }
+        };
+        map.insert(name.to_string(), pseudonym);
+    }
+    map
+}
+
+/// Reads the participants array out of a model reply, tolerating code
+/// fences and prose around it
+fn parse_detected_participants(response: &str) -> Option<Vec<DetectedParticipant>> {
+    let start = response.find('[')?;
+    serde_json::Deserializer::from_str(&response[start..])
+        .into_iter::<Vec<DetectedParticipant>>()
+        .next()?
+        .ok()
+}
+
+fn detect_user_prompt(chunk: &str) -> String {
+    format!(
+        "List every person mentioned by name in the transcript below. For each, give the name exactly as written and a generic job role inferred from the conversation (e.g. \"Engineer\", \"Designer\", \"Product Manager\"); use \"Participant\" when the role is unclear. Reply as [{{\"name\": \"...\", \"role\": \"...\"}}]. Reply with [] if nobody is named.\n\n<transcript>\n{}\n</transcript>",
+        chunk
+    )
+}
+
+/// Asks the model for the participants named in `text`
+///
+/// Long transcripts are scanned in chunks sized for the target; each name is
+/// kept once, with the role from its first mention.
+pub async fn detect_participants(
+    client: &Client,
+    targets: &[SummaryTarget],
+    text: &str,
+) -> Result<Vec<DetectedParticipant>, String> {
+    let mut detected: Vec<DetectedParticipant> = Vec::new();
+    let mut seen = HashSet::new();
+
+    for (_, chunk) in chunk_spans(text, targets[0].token_threshold, DETECT_OVERLAP_TOKENS) {
+        let user_prompt = detect_user_prompt(chunk);
+        let (response, _) = with_fallback(targets, RetryPolicy::NONE, |target| {
+            generate_summary(
+                client,
+                &target.provider,
+                &target.model_name,
+                &target.api_key,
+                DETECT_SYSTEM_PROMPT,
+                &user_prompt,
+                target.ollama_endpoint.as_deref(),
+            )
+        })
+        .await?;
+        let participants = parse_detected_participants(&response)
+            .ok_or_else(|| "Participant detection returned no JSON array".to_string())?;
+        for participant in participants {
+            if seen.insert(participant.name.trim().to_lowercase()) {
+                detected.push(participant);
+            }
+        }
+    }
+
+    info!("🕵️ Detected {} participant(s)", detected.len());
+    Ok(detected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detected(name: &str, role: &str) -> DetectedParticipant {
+        DetectedParticipant {
+            name: name.to_string(),
+            role: Some(role.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_mentions_map_consistently() {
+        let map = assign_pseudonyms(
+            &ParticipantMap::new(),
+            &[
+                detected("Sam Lee", "engineer"),
+                detected("Ana", "Product manager"),
+                detected("Priya", "Engineer"),
+                detected("sam lee", "Designer"),
+                detected("Jo", ""),
+            ],
+        );
+        assert_eq!(
+            map,
+            ParticipantMap::from([
+                ("Sam Lee".to_string(), "Engineer A".to_string()),
+                ("Ana".to_string(), "Product Manager A".to_string()),
+                ("Priya".to_string(), "Engineer B".to_string()),
+                ("Jo".to_string(), "Participant A".to_string()),
+            ])
+        );
+
+        let text = "Sam Lee: Ana, can you review? ana: Sure, after Sam's fix. SAM LEE and Priya pair on it. Samantha from sales joins later.";
+        let anonymized = anonymize_participants(text, &map);
+        assert_eq!(
+            anonymized,
+            "Engineer A: Product Manager A, can you review? Product Manager A: Sure, after Engineer A's fix. Engineer A and Engineer B pair on it. Samantha from sales joins later."
+        );
+        // Deterministic across runs and chunks
+        assert_eq!(anonymize_participants(text, &map), anonymized);
+        assert_eq!(
+            deanonymize_participants(&anonymized, &map),
+            "Sam Lee: Ana, can you review? Ana: Sure, after Sam Lee's fix. Sam Lee and Priya pair on it. Samantha from sales joins later."
+        );
+    }
+
+    #[test]
+    fn test_rerun_keeps_existing_pseudonyms() {
+        let existing = ParticipantMap::from([
+            ("Sam Lee".to_string(), "Engineer A".to_string()),
+            ("Sam Ortiz".to_string(), "Engineer B".to_string()),
+        ]);
+        let map = assign_pseudonyms(
+            &existing,
+            &[detected("Sam Lee", "Manager"), detected("Lee", "Engineer")],
+        );
+        assert_eq!(map["Sam Lee"], "Engineer A");
+        assert_eq!(map["Lee"], "Engineer C");
+
+        // "Sam" alone is ambiguous between two people, so it is left as is
+        assert_eq!(
+            anonymize_participants("Sam asked Sam Ortiz", &existing),
+            "Sam asked Engineer B"
+        );
+        assert_eq!(
+            parse_detected_participants("```json\n[{\"name\": \"Ana\"}]\n```"),
+            Some(vec![DetectedParticipant {
+                name: "Ana".to_string(),
+                role: None,
+            }])
+        );
+        assert_eq!(pseudonym_letter(0), "A");
+        assert_eq!(pseudonym_letter(27), "AB");
+    }
+}
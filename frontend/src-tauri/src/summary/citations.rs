@@ -0,0 +1,141 @@
+use crate::api::TranscriptSegment;
+use crate::summary::action_items::parse_timestamp_range;
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+
+/// Asks the final prompt to back up key claims with transcript times
+pub const CITATION_INSTRUCTION: &str = "Back up each key claim (decisions, commitments, numbers and dates) with the recording time it comes from, written right after the claim as [t=mm:ss], or [t=hh:mm:ss] past the first hour, e.g. \"Launch moved to Friday [t=12:05]\". Copy the time from the timestamp in front of the transcript line; never estimate or invent one, and never put citations in headings.";
+
+/// "[t=12:05]", "[t=1:02:03]", "[t = 12:05]"
+static CITATION_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"[ \t]*\[t\s*=\s*((?:\d{1,2}:)?\d{1,2}:\d{2})\]").unwrap());
+
+/// Seconds a cited time may fall outside a segment, since transcript
+/// timestamps are rounded to whole seconds
+const CITATION_TOLERANCE_SECS: f64 = 1.0;
+
+/// A `[t=mm:ss]` citation found in the summary
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Citation {
+    /// The time as the model wrote it
+    pub cited: String,
+    /// Seconds into the recording
+    pub time: f64,
+    /// The summary line the citation was on, without citations
+    pub claim: String,
+    /// The segment the time falls in; None when fabricated
+    pub audio_start_time: Option<f64>,
+    pub audio_end_time: Option<f64>,
+    /// No segment was spoken at the cited time, so the citation was removed
+    /// from the summary
+    pub fabricated: bool,
+}
+
+/// Every citation in summary order, stored as `citations` in the result JSON
+pub type CitationMap = Vec<Citation>;
+
+/// The segment spoken at `time`, allowing for rounding
+fn segment_at(segments: &[(f64, f64)], time: f64) -> Option<(f64, f64)> {
+    segments.iter().copied().find(|&(start, end)| {
+        time >= start - CITATION_TOLERANCE_SECS && time <= end + CITATION_TOLERANCE_SECS
+    })
+}
+
+/// Checks each `[t=mm:ss]` citation against the segments' recording times
+///
+/// Citations of a time no segment covers are removed from the markdown and
+/// kept in the map flagged as fabricated; valid ones stay in place and map
+/// to their segment. Citations in headings are dropped without being
+/// recorded. Without timed segments nothing can be checked, so the markdown
+/// is returned unchanged with an empty map.
+pub fn verify_citations(markdown: &str, segments: &[TranscriptSegment]) -> (String, CitationMap) {
+    let timed: Vec<(f64, f64)> = segments
+        .iter()
+        .filter_map(|segment| {
+            let start = segment.audio_start_time?;
+            Some((start, segment.audio_end_time.unwrap_or(start).max(start)))
+        })
+        .collect();
+    if timed.is_empty() {
+        return (markdown.to_string(), CitationMap::new());
+    }
+
+    let mut citations = CitationMap::new();
+    let lines: Vec<String> = markdown
+        .lines()
+        .map(|line| {
+            if line.trim_start().starts_with('#') {
+                return CITATION_RE.replace_all(line, "").into_owned();
+            }
+            let claim = CITATION_RE.replace_all(line, "").trim().to_string();
+            CITATION_RE
+                .replace_all(line, |caps: &Captures| {
+                    let cited = caps[1].to_string();
+                    let Some((time, _)) = parse_timestamp_range(&cited) else {
+                        return String::new();
+                    };
+                    let segment = segment_at(&timed, time);
+                    citations.push(Citation {
+                        cited: cited.clone(),
+                        time,
+                        claim: claim.clone(),
+                        audio_start_time: segment.map(|(start, _)| start),
+                        audio_end_time: segment.map(|(_, end)| end),
+                        fabricated: segment.is_none(),
+                    });
+                    match segment {
+                        Some(_) => format!(" [t={}]", cited),
+                        None => String::new(),
+                    }
+                })
+                .into_owned()
+        })
+        .collect();
+
+    let mut output = lines.join("\n");
+    if markdown.ends_with('\n') {
+        output.push('\n');
+    }
+    (output, citations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::segment;
+
+    #[test]
+    fn test_fabricated_citation_is_flagged_and_valid_one_kept() {
+        let segments = vec![
+            segment("s1", "Sam: I'll fix the webhook.", 60.0, 65.0),
+            segment("s2", "Ana: Launch moves to Friday.", 3725.0, 3731.0),
+        ];
+        let markdown = "## Decisions [t=01:02]\n\n- Sam fixes the webhook [t=01:03].\n- Budget doubled [t=45:00].\n- Launch moved to Friday [t=1:02:06]\n";
+
+        let (output, citations) = verify_citations(markdown, &segments);
+
+        assert_eq!(
+            output,
+            "## Decisions\n\n- Sam fixes the webhook [t=01:03].\n- Budget doubled.\n- Launch moved to Friday [t=1:02:06]\n"
+        );
+        assert_eq!(citations.len(), 3);
+        assert_eq!(citations[0].time, 63.0);
+        assert_eq!(citations[0].claim, "- Sam fixes the webhook.");
+        assert_eq!(citations[0].audio_start_time, Some(60.0));
+        assert!(!citations[0].fabricated);
+        assert_eq!(citations[1].cited, "45:00");
+        assert!(citations[1].fabricated);
+        assert_eq!(citations[1].audio_start_time, None);
+        assert_eq!(citations[2].time, 3726.0);
+        assert!(!citations[2].fabricated);
+    }
+
+    #[test]
+    fn test_citations_are_left_alone_without_segment_times() {
+        let markdown = "- Budget doubled [t=45:00]";
+        let (output, citations) = verify_citations(markdown, &[]);
+        assert_eq!(output, markdown);
+        assert!(citations.is_empty());
+    }
+}
@@ -1,12 +1,46 @@
 use crate::database::repositories::{
-    meeting::MeetingsRepository, summary::SummaryProcessesRepository,
+    meeting::MeetingsRepository, people::PeopleRepository, pipeline::PipelinesRepository,
+    question::QuestionsRepository, summary::SummaryProcessesRepository,
     transcript_chunk::TranscriptChunksRepository,
 };
+use crate::database::models::{
+    LiveSummary, MeetingContext, NamedPipeline, NamedPipelineInput, ParticipantMap, StoredQuestion,
+};
+use crate::http::client_factory::http_client;
+use crate::logging::log_preview;
+use crate::people::resolve_action_items;
+use crate::setup::probes::{test_llm_provider, ProviderTestResult, PROVIDER_TEST_TIMEOUT};
 use crate::state::AppState;
-use crate::summary::service::SummaryService;
+use crate::summary::action_items::ExtractedActionItems;
+use crate::summary::anonymize::deanonymize_participants;
+use crate::summary::content_filter::{self, ContentFilterConfig};
+use crate::summary::context::read_context_input;
+use crate::summary::live::{self, LiveSummaryConfig};
+use crate::summary::llm_client::LLMProvider;
+use crate::summary::metrics::{self, MetricsPeriod, SummaryMetrics};
+use crate::summary::named_pipelines::RequestedSummarySettings;
+use crate::summary::pipeline_config::{self, SummaryPipelineConfig};
+use crate::summary::processor::{
+    normalize_edited_summary, rough_token_count, ChunkingPreview, NormalizedSummary,
+    SummaryPromptPreview,
+};
+use crate::summary::progress::current_progress;
+use crate::summary::provider_overrides::{self, ProviderOverrideSettings};
+use crate::summary::quality::SummaryQuality;
+use crate::summary::quote_check::QuoteCheck;
+use crate::summary::templates;
+use crate::summary::tickets::TicketCoverage;
+use crate::summary::service::{ModelChoice, SummaryComparison, SummaryService};
 use crate::summary::question_generator;
+use crate::summary::question_prompt::{self, QuestionPromptConfig};
+use crate::summary::question_suppression::{
+    self, FocusWindow, PendingQuestion, QuestionStatus, SuppressionMode, SuppressionState,
+};
+use crate::webhooks::config::load_webhooks_config;
 use log::{error as log_error, info as log_info, warn as log_warn};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
 use tauri::{AppHandle, Runtime};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -19,6 +53,23 @@ pub struct SummaryResponse {
     pub end: Option<String>,
     pub data: Option<serde_json::Value>,
     pub error: Option<String>,
+    /// Estimated percentage while a streamed summary is being written
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress: Option<u8>,
+    /// Quality score stored with a completed summary
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quality: Option<SummaryQuality>,
+}
+
+/// Latest summary of a meeting generated with one template
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TemplateSummary {
+    pub template_id: String,
+    /// `completed` or `failed`
+    pub status: String,
+    pub data: Option<serde_json::Value>,
+    pub error: Option<String>,
+    pub created_at: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -104,20 +155,28 @@ pub async fn api_get_summary<R: Runtime>(
             };
 
             // Fetch meeting title from database
-            let meeting_name = match MeetingsRepository::get_meeting(pool, &meeting_id).await {
-                Ok(Some(meeting_details)) => {
-                    log_info!("Fetched meeting title: {}", &meeting_details.title);
-                    Some(meeting_details.title)
-                }
-                Ok(None) => {
-                    log_warn!("Meeting not found for meeting_id: {}", meeting_id);
-                    None
-                }
-                Err(e) => {
-                    log_error!("Failed to fetch meeting title: {}", e);
-                    None
-                }
-            };
+            let meeting_name =
+                match MeetingsRepository::get_meeting(pool, &meeting_id, &state.transcripts_json)
+                    .await
+                {
+                    Ok(Some(meeting_details)) => {
+                        log_info!("Fetched meeting title: {}", &meeting_details.title);
+                        Some(meeting_details.title)
+                    }
+                    Ok(None) => {
+                        log_warn!("Meeting not found for meeting_id: {}", meeting_id);
+                        None
+                    }
+                    Err(e) => {
+                        log_error!("Failed to fetch meeting title: {}", e);
+                        None
+                    }
+                };
+
+            let quality = data
+                .as_ref()
+                .and_then(|data| data.get("quality"))
+                .and_then(|quality| serde_json::from_value(quality.clone()).ok());
 
             let response = SummaryResponse {
                 status: status.clone(),
@@ -127,6 +186,8 @@ pub async fn api_get_summary<R: Runtime>(
                 end: process.end_time.map(|t| t.to_rfc3339()),
                 data,
                 error,
+                progress: current_progress(&meeting_id),
+                quality,
             };
 
             log_info!(
@@ -142,10 +203,13 @@ pub async fn api_get_summary<R: Runtime>(
             log_info!("No summary process found for meeting_id: {}", meeting_id);
 
             // Still fetch meeting title for idle state
-            let meeting_name = match MeetingsRepository::get_meeting(pool, &meeting_id).await {
-                Ok(Some(meeting_details)) => Some(meeting_details.title),
-                _ => None,
-            };
+            let meeting_name =
+                match MeetingsRepository::get_meeting(pool, &meeting_id, &state.transcripts_json)
+                    .await
+                {
+                    Ok(Some(meeting_details)) => Some(meeting_details.title),
+                    _ => None,
+                };
 
             Ok(SummaryResponse {
                 status: "idle".to_string(),
@@ -155,6 +219,8 @@ pub async fn api_get_summary<R: Runtime>(
                 end: None,
                 data: None,
                 error: None,
+                progress: None,
+                quality: None,
             })
         }
         Err(e) => {
@@ -166,33 +232,146 @@ pub async fn api_get_summary<R: Runtime>(
 
 /// Processes transcript and generates summary (Native SQLx implementation)
 ///
-/// Spawns a background task and returns immediately with process_id
+/// Spawns a background task and returns immediately with process_id. With a
+/// `pipeline_id`, the named pipeline's settings fill in the ones not given.
 #[tauri::command]
 pub async fn api_process_transcript<R: Runtime>(
     app: AppHandle<R>,
     state: tauri::State<'_, AppState>,
     text: String,
-    model: String,
-    model_name: String,
+    model: Option<String>,
+    model_name: Option<String>,
     meeting_id: Option<String>,
     _chunk_size: Option<i32>,
     _overlap: Option<i32>,
     custom_prompt: Option<String>,
     template_id: Option<String>,
+    template_ids: Option<Vec<String>>,
     _auth_token: Option<String>,
+    compact_transcript: Option<bool>,
+    provenance: Option<bool>,
+    citations: Option<bool>,
+    stream: Option<bool>,
+    anonymize: Option<bool>,
+    skim: Option<bool>,
+    pipeline_id: Option<String>,
 ) -> Result<ProcessTranscriptResponse, String> {
     use uuid::Uuid;
 
     let m_id = meeting_id.unwrap_or_else(|| format!("meeting-{}", Uuid::new_v4()));
     log_info!(
-        "api_process_transcript (native) called for meeting_id: {}, model: {}",
+        "api_process_transcript (native) called for meeting_id: {}, model: {:?}",
         &m_id,
         &model
     );
 
     let pool = state.db_manager.pool().clone();
+    let repos = state.repos.clone();
     let final_prompt = custom_prompt.unwrap_or_else(|| "".to_string());
-    let final_template_id = template_id.unwrap_or_else(|| "daily_standup".to_string());
+    let pipeline_config = pipeline_config::load_summary_pipeline_config(&app);
+
+    // Stored segments are rendered the way api_get_meeting_transcript_text
+    // shows them, less duplicate segments; the text sent by the frontend is
+    // used when there are none
+    let text = match repos.transcripts.get_transcript_segments(&m_id).await {
+        Ok(segments) if !segments.is_empty() => {
+            SummaryService::prompt_transcript(&m_id, segments, &pipeline_config)
+        }
+        Ok(_) => text,
+        Err(e) => {
+            log_warn!(
+                "⚠️ Failed to load transcript segments for {}, using the text sent: {}",
+                m_id,
+                e
+            );
+            text
+        }
+    };
+    // Several templates share one chunk pass; the first one is the primary
+    let requested_templates = template_ids.unwrap_or_default();
+    let template_id = template_id.or_else(|| requested_templates.first().cloned());
+
+    // Explicit values win over the named pipeline's
+    let named_pipeline = match pipeline_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+    {
+        Some(id) => Some(
+            PipelinesRepository::get(&pool, id)
+                .await
+                .map_err(|e| format!("Failed to load pipeline: {}", e))?
+                .ok_or_else(|| format!("Pipeline not found: {}", id))?,
+        ),
+        None => None,
+    };
+    let requested = RequestedSummarySettings {
+        template_id,
+        model_provider: model,
+        model_name,
+        compact_transcript,
+        provenance,
+        citations,
+        anonymize,
+        skim,
+    };
+    let requested = match &named_pipeline {
+        Some(named) => {
+            log_info!("Using pipeline '{}' for meeting_id: {}", named.name, &m_id);
+            requested.with_pipeline(named)
+        }
+        None => requested,
+    };
+    let compact = requested
+        .compact_transcript
+        .unwrap_or(pipeline_config.compact_transcript);
+    let provenance = requested.provenance.unwrap_or(false);
+    let citations = requested.citations.unwrap_or(false);
+    let stream = stream.unwrap_or(false);
+    let anonymize = requested.anonymize.unwrap_or(false);
+    let skim = requested.skim.unwrap_or(false);
+
+    // Without a selected model the routing policy, if on, picks one by size
+    let routing = pipeline_config.provider_routing.route_unselected(
+        requested.model_provider.as_deref(),
+        requested.model_name.as_deref(),
+        &text,
+    );
+    let (model_provider, model_name) = match &routing {
+        Some(decision) => {
+            log_info!(
+                "🔀 Routing {} token transcript ({:?}, threshold {}) to {}/{} for meeting_id: {}",
+                decision.transcript_tokens,
+                decision.size,
+                decision.threshold_tokens,
+                decision.provider,
+                decision.model,
+                &m_id
+            );
+            (
+                Some(decision.provider.clone()),
+                Some(decision.model.clone()),
+            )
+        }
+        None => (requested.model_provider, requested.model_name),
+    };
+
+    // Missing values fall back to the meeting's stored preferences
+    let resolved = SummaryService::resolve_settings(
+        &repos,
+        &m_id,
+        model_provider,
+        model_name,
+        requested.template_id,
+    )
+    .await?;
+    log_info!(
+        "Using provider: {}, model: {}, template: {} for meeting_id: {}",
+        &resolved.model_provider,
+        &resolved.model_name,
+        &resolved.template_id,
+        &m_id
+    );
 
     // Create or reset the process entry in the database
     SummaryProcessesRepository::create_or_reset_process(&pool, &m_id)
@@ -201,7 +380,8 @@ pub async fn api_process_transcript<R: Runtime>(
 
     log_info!("✓ Summary process initialized for meeting_id: {}", &m_id);
 
-    // Save transcript chunks data (matching Python backend behavior)
+    // Save transcript chunks data (matching Python backend behavior); the
+    // summary run adds the chunk boundaries it used
     let chunk_size = _chunk_size.unwrap_or(40000);
     let overlap = _overlap.unwrap_or(1000);
 
@@ -209,8 +389,8 @@ pub async fn api_process_transcript<R: Runtime>(
         &pool,
         &m_id,
         &text,
-        &model,
-        &model_name,
+        &resolved.model_provider,
+        &resolved.model_name,
         chunk_size,
         overlap,
     )
@@ -222,25 +402,30 @@ pub async fn api_process_transcript<R: Runtime>(
     // Spawn background task for actual processing
     let meeting_id_clone = m_id.clone();
     let text_len = text.len();
-    let text_preview = if text.len() > 200 {
-        format!("{}...", &text[..200])
-    } else {
-        text.clone()
-    };
-    log_info!("📝 Transcript received in api_process_transcript: length={} chars, preview: {}", text_len, text_preview);
+    log_info!("📝 Transcript received in api_process_transcript: length={} chars, preview: {}", text_len, log_preview(&text));
     
     tauri::async_runtime::spawn(async move {
         log_info!("🔄 Background task starting for meeting_id: {}", meeting_id_clone);
         log_info!("📝 Transcript in background task: length={} chars", text.len());
         SummaryService::process_transcript_background(
             app,
-            pool,
+            repos,
             meeting_id_clone.clone(),
             text,
-            model,
-            model_name,
+            Some(resolved.model_provider),
+            Some(resolved.model_name),
             final_prompt,
-            final_template_id,
+            std::iter::once(resolved.template_id)
+                .chain(requested_templates)
+                .collect(),
+            compact,
+            provenance,
+            citations,
+            stream,
+            anonymize,
+            skim,
+            named_pipeline,
+            routing,
         )
         .await;
         log_info!("✅ Background task completed for meeting_id: {}", meeting_id_clone);
@@ -254,32 +439,801 @@ pub async fn api_process_transcript<R: Runtime>(
     })
 }
 
+#[tauri::command]
+pub async fn api_list_pipelines<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<NamedPipeline>, String> {
+    PipelinesRepository::list(state.db_manager.pool())
+        .await
+        .map_err(|e| format!("Failed to list pipelines: {}", e))
+}
+
+/// Webhook targets must be saved webhooks
+fn check_pipeline_webhooks<R: Runtime>(
+    app: &AppHandle<R>,
+    pipeline: &NamedPipelineInput,
+) -> Result<(), String> {
+    let config = load_webhooks_config(app);
+    match pipeline
+        .webhook_ids
+        .iter()
+        .map(|id| id.trim())
+        .find(|id| !id.is_empty() && config.webhook(id).is_none())
+    {
+        Some(id) => Err(format!("Webhook not found: {}", id)),
+        None => Ok(()),
+    }
+}
+
+#[tauri::command]
+pub async fn api_create_pipeline<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    pipeline: NamedPipelineInput,
+) -> Result<NamedPipeline, String> {
+    log_info!("api_create_pipeline called for '{}'", pipeline.name);
+    check_pipeline_webhooks(&app, &pipeline)?;
+    let _write_guard = state.db_manager.write_lock().await;
+    PipelinesRepository::create(state.db_manager.pool(), &pipeline)
+        .await
+        .map_err(|e| {
+            log_error!("Error creating pipeline: {}", e);
+            format!("Failed to create pipeline: {}", e)
+        })
+}
+
+#[tauri::command]
+pub async fn api_update_pipeline<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    id: String,
+    pipeline: NamedPipelineInput,
+) -> Result<NamedPipeline, String> {
+    log_info!("api_update_pipeline called for id: {}", id);
+    check_pipeline_webhooks(&app, &pipeline)?;
+    // Held across the write and the read back of the updated row
+    let _write_guard = state.db_manager.write_lock().await;
+    match PipelinesRepository::update(state.db_manager.pool(), &id, &pipeline).await {
+        Ok(Some(pipeline)) => Ok(pipeline),
+        Ok(None) => Err(format!("Pipeline not found: {}", id)),
+        Err(e) => {
+            log_error!("Error updating pipeline {}: {}", id, e);
+            Err(format!("Failed to update pipeline: {}", e))
+        }
+    }
+}
+
+/// Deletes a pipeline; the built-in ones can't be deleted
+#[tauri::command]
+pub async fn api_delete_pipeline<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    id: String,
+) -> Result<bool, String> {
+    log_info!("api_delete_pipeline called for id: {}", id);
+    // Held so the built-in check and the delete see the same row
+    let _write_guard = state.db_manager.write_lock().await;
+    PipelinesRepository::delete(state.db_manager.pool(), &id)
+        .await
+        .map_err(|e| format!("Failed to delete pipeline: {}", e))
+}
+
+/// Gets the live draft of a saved meeting, or of the recording in progress when no id is given
+#[tauri::command]
+pub async fn api_get_live_summary<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: Option<String>,
+) -> Result<Option<LiveSummary>, String> {
+    log_info!("api_get_live_summary called for meeting_id: {:?}", meeting_id);
+    live::get_live_summary(state.db_manager.pool(), meeting_id.as_deref())
+        .await
+        .map_err(|e| format!("Failed to get live summary: {}", e))
+}
+
+#[tauri::command]
+pub async fn api_get_live_summary_config<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<LiveSummaryConfig, String> {
+    Ok(live::load_live_summary_config(&app))
+}
+
+/// Saves the live summary config; takes effect from the next recording
+#[tauri::command]
+pub async fn api_set_live_summary_config<R: Runtime>(
+    app: AppHandle<R>,
+    config: LiveSummaryConfig,
+) -> Result<(), String> {
+    log_info!("api_set_live_summary_config called: {:?}", config);
+    live::save_live_summary_config(&app, &config)
+}
+
+#[tauri::command]
+pub async fn api_get_content_filter<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<ContentFilterConfig, String> {
+    Ok(content_filter::load_content_filter(&app))
+}
+
+/// Saves the summary language filter; applies to summaries generated afterwards
+#[tauri::command]
+pub async fn api_save_content_filter<R: Runtime>(
+    app: AppHandle<R>,
+    config: ContentFilterConfig,
+) -> Result<(), String> {
+    log_info!(
+        "api_save_content_filter called: enabled={}, {} custom term(s)",
+        config.sanitize_summary_language,
+        config.terms.len()
+    );
+    content_filter::save_content_filter(&app, &config)
+}
+
+#[tauri::command]
+pub async fn api_get_summary_pipeline_config<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<SummaryPipelineConfig, String> {
+    Ok(pipeline_config::load_summary_pipeline_config(&app))
+}
+
+/// Saves how long transcripts are chunked and merged and how much of the
+/// context window background context may take; applies to summaries generated
+/// afterwards
+#[tauri::command]
+pub async fn api_save_summary_pipeline_config<R: Runtime>(
+    app: AppHandle<R>,
+    config: SummaryPipelineConfig,
+) -> Result<(), String> {
+    log_info!("api_save_summary_pipeline_config called: {:?}", config);
+    config.validate()?;
+    pipeline_config::save_summary_pipeline_config(&app, &config)
+}
+
+#[tauri::command]
+pub async fn api_get_question_prompt_config<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<QuestionPromptConfig, String> {
+    Ok(question_prompt::load_question_prompt_config(&app))
+}
+
+/// Saves the preamble, focus areas, example questions and output
+/// instructions of the clarifying question prompt
+#[tauri::command]
+pub async fn api_save_question_prompt_config<R: Runtime>(
+    app: AppHandle<R>,
+    config: QuestionPromptConfig,
+) -> Result<(), String> {
+    log_info!(
+        "api_save_question_prompt_config called: {} focus areas, {} examples",
+        config.focus_areas.len(),
+        config.examples.len()
+    );
+    config.validate()?;
+    question_prompt::save_question_prompt_config(&app, &config)
+}
+
+/// Averages, failure kinds and per-model timing of the summaries generated
+/// within `period`, for diagnostics
+#[tauri::command]
+pub async fn api_get_summary_metrics<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    period: MetricsPeriod,
+) -> Result<SummaryMetrics, String> {
+    log_info!("api_get_summary_metrics called for period: {:?}", period);
+    metrics::summary_metrics(state.db_manager.pool(), period, chrono::Utc::now())
+        .await
+        .map_err(|e| format!("Failed to compute summary metrics: {}", e))
+}
+
+/// Chunk count and sizes summarizing the meeting would use with the current
+/// settings; no model is called
+#[tauri::command]
+pub async fn api_preview_chunking<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<ChunkingPreview, String> {
+    log_info!("api_preview_chunking called for meeting_id: {}", meeting_id);
+    let pipeline = pipeline_config::load_summary_pipeline_config(&app);
+    SummaryService::preview_chunking(&state.repos, &meeting_id, &pipeline).await
+}
+
+/// Summaries of the meeting by each of `configs`, with timings, for a side
+/// by side evaluation; the stored summary is not changed
+#[tauri::command]
+pub async fn api_compare_summaries<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    configs: Vec<ModelChoice>,
+    template_id: Option<String>,
+) -> Result<Vec<SummaryComparison>, String> {
+    log_info!(
+        "api_compare_summaries called for meeting_id: {} with {} model(s)",
+        meeting_id,
+        configs.len()
+    );
+    let pipeline = pipeline_config::load_summary_pipeline_config(&app);
+    SummaryService::compare_summaries(&state.repos, &meeting_id, configs, template_id, &pipeline)
+        .await
+}
+
+/// The system and user prompt summarizing the meeting would send, with token
+/// estimates per part; no model is called
+///
+/// `template_id` and `custom_prompt` are used as `api_process_transcript`
+/// would; with `anonymize` the meeting's stored participant map is applied.
+#[tauri::command]
+pub async fn api_preview_summary_prompt<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    template_id: Option<String>,
+    custom_prompt: Option<String>,
+    anonymize: Option<bool>,
+) -> Result<SummaryPromptPreview, String> {
+    log_info!("api_preview_summary_prompt called for meeting_id: {}", meeting_id);
+    let pipeline = pipeline_config::load_summary_pipeline_config(&app);
+    SummaryService::preview_summary_prompt(
+        &state.repos,
+        &meeting_id,
+        template_id,
+        custom_prompt,
+        anonymize.unwrap_or(false),
+        &pipeline,
+    )
+    .await
+}
+
+/// Extracts only the meeting's action items, without generating a summary
+///
+/// Faster and cheaper than a full summary. Owners are resolved against the
+/// people directory so the items can go straight to Jira.
+#[tauri::command]
+pub async fn api_extract_action_items<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    model_provider: Option<String>,
+    model: Option<String>,
+) -> Result<ExtractedActionItems, String> {
+    log_info!("api_extract_action_items called for meeting_id: {}", meeting_id);
+    let pipeline = pipeline_config::load_summary_pipeline_config(&app);
+    let mut extracted = SummaryService::extract_action_items(
+        &state.repos,
+        &meeting_id,
+        model_provider,
+        model,
+        &pipeline,
+    )
+    .await?;
+    let people = PeopleRepository::list(state.db_manager.pool())
+        .await
+        .map_err(|e| format!("Failed to load people: {}", e))?;
+    resolve_action_items(&mut extracted.items, &people);
+    Ok(extracted)
+}
+
+/// Ticket IDs mentioned in the meeting's transcript and which of them its
+/// summary covers
+#[tauri::command]
+pub async fn api_get_ticket_coverage<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<TicketCoverage, String> {
+    log_info!("api_get_ticket_coverage called for meeting_id: {}", meeting_id);
+    let pipeline = pipeline_config::load_summary_pipeline_config(&app);
+    SummaryService::ticket_coverage(&state.repos, &meeting_id, &pipeline).await
+}
+
+/// Looks up the quotes and number or date claims of the meeting's summary in
+/// its transcript, with a verdict per claim
+///
+/// With `attach`, the result is stored with the summary and a validation
+/// warning added when too many claims weren't found.
+#[tauri::command]
+pub async fn api_verify_quotes<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    attach: Option<bool>,
+) -> Result<QuoteCheck, String> {
+    log_info!("api_verify_quotes called for meeting_id: {}", meeting_id);
+    let pipeline = pipeline_config::load_summary_pipeline_config(&app);
+    let attach = attach.unwrap_or(false);
+    SummaryService::verify_quotes(&state.repos, &meeting_id, &pipeline, attach).await
+}
+
+/// Latest summary of each template a meeting was summarized with in a
+/// multi-template run, newest first
+#[tauri::command]
+pub async fn api_get_meeting_summaries(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Vec<TemplateSummary>, String> {
+    log_info!("api_get_meeting_summaries called for meeting_id: {}", meeting_id);
+    let versions = state
+        .repos
+        .summaries
+        .list_summary_versions(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load summaries: {}", e))?;
+
+    let mut seen = std::collections::HashSet::new();
+    Ok(versions
+        .into_iter()
+        .filter(|version| seen.insert(version.template_id.clone()))
+        .map(|version| {
+            let data = version.result.as_deref().and_then(|result| {
+                serde_json::from_str(result)
+                    .map_err(|e| log_error!("Failed to parse summary version JSON: {}", e))
+                    .ok()
+            });
+            TemplateSummary {
+                template_id: version.template_id,
+                status: version.status,
+                data,
+                error: version.error,
+                created_at: version.created_at.to_rfc3339(),
+            }
+        })
+        .collect())
+}
+
+/// Cleans up the formatting of a hand-edited summary and lists the fixes applied
+///
+/// With a template id, its table sections are also rewritten to the
+/// template's columns.
+#[tauri::command]
+pub async fn api_normalize_summary(
+    markdown: String,
+    template_id: Option<String>,
+) -> Result<NormalizedSummary, String> {
+    log_info!(
+        "api_normalize_summary called: {} chars, template: {:?}",
+        markdown.len(),
+        template_id
+    );
+    let template = match template_id.as_deref().filter(|id| !id.trim().is_empty()) {
+        Some(id) => Some(
+            templates::get_template(id)
+                .map_err(|e| format!("Failed to load template '{}': {}", id, e))?,
+        ),
+        None => None,
+    };
+    Ok(normalize_edited_summary(&markdown, template.as_ref()))
+}
+
+/// Name -> pseudonym map used when the meeting is summarized anonymously
+#[tauri::command]
+pub async fn api_get_participant_map(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<ParticipantMap, String> {
+    log_info!("api_get_participant_map called for meeting_id: {}", meeting_id);
+    state
+        .repos
+        .meetings
+        .get_participant_map(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load participant map: {}", e))?
+        .ok_or_else(|| "Meeting not found".to_string())
+}
+
+/// Replaces the meeting's name -> pseudonym map, e.g. after correcting a
+/// detected name; the next anonymized summary uses it as is
+#[tauri::command]
+pub async fn api_set_participant_map(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    map: ParticipantMap,
+) -> Result<(), String> {
+    log_info!(
+        "api_set_participant_map called for meeting_id: {} ({} names)",
+        meeting_id,
+        map.len()
+    );
+    if map
+        .iter()
+        .any(|(name, pseudonym)| name.trim().is_empty() || pseudonym.trim().is_empty())
+    {
+        return Err("Participant names and pseudonyms cannot be empty".to_string());
+    }
+    let updated = state
+        .repos
+        .meetings
+        .set_participant_map(&meeting_id, &map)
+        .await
+        .map_err(|e| format!("Failed to save participant map: {}", e))?;
+    if !updated {
+        return Err("Meeting not found".to_string());
+    }
+    Ok(())
+}
+
+/// Background notes stored for the meeting, if any
+#[tauri::command]
+pub async fn api_get_meeting_context(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Option<MeetingContext>, String> {
+    log_info!("api_get_meeting_context called for meeting_id: {}", meeting_id);
+    state
+        .repos
+        .meetings
+        .get_meeting_context(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load background context: {}", e))
+}
+
+/// Attaches background notes (agenda, project brief, glossary) to a meeting
+///
+/// `text_or_path` is either the notes or the path of a plain text file to
+/// read them from. Empty text clears the notes. The summary, question and
+/// Jira prompts use them as reference material only.
+#[tauri::command]
+pub async fn api_set_meeting_context(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    text_or_path: String,
+) -> Result<Option<MeetingContext>, String> {
+    log_info!("api_set_meeting_context called for meeting_id: {}", meeting_id);
+    let input = read_context_input(&text_or_path)?;
+    let context = (!input.text.is_empty()).then(|| MeetingContext {
+        token_count: rough_token_count(&input.text) as i64,
+        text: input.text,
+        source: input.source,
+    });
+    let updated = state
+        .repos
+        .meetings
+        .set_meeting_context(&meeting_id, context.as_ref())
+        .await
+        .map_err(|e| format!("Failed to save background context: {}", e))?;
+    if !updated {
+        return Err("Meeting not found".to_string());
+    }
+    match &context {
+        Some(context) => log_info!(
+            "📎 Stored background context for {}: {} tokens{}",
+            meeting_id,
+            context.token_count,
+            context
+                .source
+                .as_deref()
+                .map(|source| format!(" from {}", source))
+                .unwrap_or_default()
+        ),
+        None => log_info!("📎 Cleared background context for {}", meeting_id),
+    }
+    Ok(context)
+}
+
+/// The meeting's summary with pseudonyms replaced by participant names, for
+/// internal viewing; the stored summary stays anonymized
+#[tauri::command]
+pub async fn api_deanonymize_summary(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<String, String> {
+    log_info!("api_deanonymize_summary called for meeting_id: {}", meeting_id);
+    let process = state
+        .repos
+        .summaries
+        .get_summary_data(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load summary: {}", e))?
+        .ok_or_else(|| "No summary found for this meeting".to_string())?;
+    let markdown = process
+        .result
+        .as_deref()
+        .and_then(|result| serde_json::from_str::<serde_json::Value>(result).ok())
+        .and_then(|result| result.get("markdown")?.as_str().map(str::to_string))
+        .ok_or_else(|| "No summary found for this meeting".to_string())?;
+
+    let map = state
+        .repos
+        .meetings
+        .get_participant_map(&meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load participant map: {}", e))?
+        .unwrap_or_default();
+    Ok(deanonymize_participants(&markdown, &map))
+}
+
 /// Generate clarifying questions from transcript chunk
+///
+/// With a `meeting_id`, the meeting's background context is added to the
+/// prompt and the questions are stored with the meeting.
 #[tauri::command]
 pub async fn generate_clarifying_questions<R: Runtime>(
-    _app: AppHandle<R>,
+    app: AppHandle<R>,
     state: tauri::State<'_, AppState>,
     transcript_chunk: String,
     recent_context: String,
+    meeting_id: Option<String>,
 ) -> Result<Vec<question_generator::Question>, String> {
     log_info!("🚀 [Question Command] generate_clarifying_questions called");
     log_info!("🚀 [Question Command] transcript_chunk length: {} chars", transcript_chunk.len());
     log_info!("🚀 [Question Command] recent_context length: {} chars", recent_context.len());
-    let pool = state.db_manager.pool();
-    
-    let result = question_generator::generate_questions(pool, &transcript_chunk, &recent_context).await;
+    let settings = state.repos.settings.clone();
+    let meeting_id = meeting_id.filter(|id| !id.trim().is_empty());
+    let background_context = match meeting_id.as_deref() {
+        Some(meeting_id) => state
+            .repos
+            .meetings
+            .get_meeting_context(meeting_id)
+            .await
+            .map_err(|e| format!("Failed to load background context: {}", e))?
+            .map(|context| context.text)
+            .unwrap_or_default(),
+        None => String::new(),
+    };
+
+    let window_tokens = pipeline_config::load_summary_pipeline_config(&app).question_window_tokens;
+    let debug_log = question_generator::question_debug_log(&app, meeting_id.as_deref());
+    let prompt_config = question_prompt::load_question_prompt_config(&app);
+
+    let result = question_generator::generate_questions(
+        settings.as_ref(),
+        &prompt_config,
+        &transcript_chunk,
+        &recent_context,
+        &background_context,
+        window_tokens,
+        debug_log.as_ref(),
+    )
+    .await;
     
-    match &result {
-        Ok(questions) => {
-            log_info!("✅ [Question Command] Generated {} questions", questions.len());
-            for (idx, q) in questions.iter().enumerate() {
-                log_info!("   Question {}: '{}'", idx + 1, q.text);
-            }
-        }
+    let questions = match result {
+        Ok(questions) => questions,
         Err(e) => {
             log::error!("❌ [Question Command] Error generating questions: {}", e);
+            return Err(e);
         }
+    };
+    log_info!("✅ [Question Command] Generated {} questions", questions.len());
+    for (idx, q) in questions.iter().enumerate() {
+        log_info!("   Question {}: '{}'", idx + 1, q.text);
+    }
+
+    let suppressed = !questions.is_empty() && question_suppression::is_suppressed();
+    let status = if suppressed {
+        QuestionStatus::Suppressed
+    } else {
+        QuestionStatus::Shown
+    };
+    // The questions are still useful if they can't be stored
+    let mut stored_ids = Vec::new();
+    if let Some(meeting_id) = &meeting_id {
+        match QuestionsRepository::save_questions(
+            state.db_manager.pool(),
+            meeting_id,
+            &questions,
+            status,
+        )
+        .await
+        {
+            Ok(stored) => stored_ids = stored.into_iter().map(|q| q.id).collect(),
+            Err(e) => log_warn!(
+                "⚠️ [Question Command] Failed to store questions for {}: {}",
+                meeting_id, e
+            ),
+        }
+    }
+
+    if suppressed {
+        let mut stored_ids = stored_ids.into_iter();
+        let pending = questions
+            .into_iter()
+            .map(|question| PendingQuestion {
+                id: stored_ids.next(),
+                question,
+            })
+            .collect();
+        question_suppression::hold_questions(&app, pending).await;
+        return Ok(Vec::new());
+    }
+    Ok(questions)
+}
+
+/// Whether question popups are held back right now, and why
+#[tauri::command]
+pub async fn api_get_question_suppression<R: Runtime>(
+    _app: AppHandle<R>,
+) -> Result<SuppressionState, String> {
+    Ok(question_suppression::current_state())
+}
+
+/// Sets when question popups are held back; `focus_hours` keeps the saved
+/// windows when left out
+#[tauri::command]
+pub async fn api_set_question_suppression<R: Runtime>(
+    app: AppHandle<R>,
+    mode: SuppressionMode,
+    focus_hours: Option<Vec<FocusWindow>>,
+) -> Result<SuppressionState, String> {
+    log_info!(
+        "api_set_question_suppression called: {:?}, focus hours: {:?}",
+        mode, focus_hours
+    );
+    question_suppression::set_question_suppression(&app, mode, focus_hours).await
+}
+
+/// Base URL and extra header overrides saved per provider, without the
+/// values of secret headers
+#[tauri::command]
+pub async fn api_get_provider_overrides<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<Vec<ProviderOverrideSettings>, String> {
+    Ok(provider_overrides::list_provider_overrides(&app))
+}
+
+/// Saves a provider's base URL, extra headers and the models that get the
+/// system prompt merged into the user message
+///
+/// `secret_headers` are stored encrypted and replace the saved ones;
+/// `merged_system_prompt_models` also replaces the saved list. None keeps
+/// either.
+#[tauri::command]
+pub async fn api_set_provider_override<R: Runtime>(
+    app: AppHandle<R>,
+    provider: String,
+    base_url: Option<String>,
+    headers: BTreeMap<String, String>,
+    secret_headers: Option<BTreeMap<String, String>>,
+    merged_system_prompt_models: Option<BTreeSet<String>>,
+) -> Result<ProviderOverrideSettings, String> {
+    log_info!("api_set_provider_override called for provider: {}", provider);
+    provider_overrides::set_provider_override(
+        &app,
+        &provider,
+        base_url,
+        headers,
+        secret_headers,
+        merged_system_prompt_models,
+    )
+}
+
+/// Makes a short test call to `model` through the provider's saved override
+/// and returns the reply
+#[tauri::command]
+pub async fn api_test_provider_override<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    provider: String,
+    model: String,
+) -> Result<String, String> {
+    log_info!(
+        "api_test_provider_override called for provider: {}, model: {}",
+        provider, model
+    );
+    let llm_provider = LLMProvider::from_str(&provider)?;
+    let api_key = state
+        .repos
+        .settings
+        .get_api_key(&provider)
+        .await
+        .map_err(|e| format!("Failed to retrieve api key for {}: {}", provider, e))?
+        .unwrap_or_default();
+    let ollama_endpoint = match llm_provider {
+        LLMProvider::Ollama => state
+            .repos
+            .settings
+            .get_model_config()
+            .await
+            .ok()
+            .flatten()
+            .and_then(|config| config.ollama_endpoint),
+        _ => None,
+    };
+    let client = http_client(Duration::from_secs(60))?;
+    provider_overrides::test_override(
+        &client,
+        &llm_provider,
+        &model,
+        &api_key,
+        ollama_endpoint.as_deref(),
+    )
+    .await
+    .map_err(|e| {
+        log_error!("Provider override test failed: {}", e);
+        e
+    })
+}
+
+/// Sends a short test completion to `model` with `api_key`, or the saved
+/// key when none is given, and reports the latency or what went wrong
+///
+/// The key passed in is only used for the test and never saved.
+#[tauri::command]
+pub async fn api_test_llm_provider<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    provider: String,
+    model: String,
+    api_key: Option<String>,
+) -> Result<ProviderTestResult, String> {
+    log_info!(
+        "api_test_llm_provider called for provider: {}, model: {}",
+        provider, model
+    );
+    let llm_provider = LLMProvider::from_str(&provider)?;
+    let api_key = match api_key.filter(|key| !key.trim().is_empty()) {
+        Some(key) => key,
+        None => state
+            .repos
+            .settings
+            .get_api_key(&provider)
+            .await
+            .map_err(|e| format!("Failed to retrieve api key for {}: {}", provider, e))?
+            .unwrap_or_default(),
+    };
+    let ollama_endpoint = match llm_provider {
+        LLMProvider::Ollama => state
+            .repos
+            .settings
+            .get_model_config()
+            .await
+            .ok()
+            .flatten()
+            .and_then(|config| config.ollama_endpoint),
+        _ => None,
+    };
+    let client = http_client(PROVIDER_TEST_TIMEOUT)?;
+    let result = test_llm_provider(
+        &client,
+        &provider,
+        &model,
+        &api_key,
+        ollama_endpoint.as_deref(),
+        &provider_overrides::override_for(&llm_provider),
+        PROVIDER_TEST_TIMEOUT,
+    )
+    .await;
+    if result.ok {
+        log_info!("✅ {} answered the test in {}ms", provider, result.latency_ms);
+    } else {
+        log_warn!("⚠️ Provider test failed for {}: {:?}", provider, result.error);
+    }
+    Ok(result)
+}
+
+/// Clarifying questions raised for a meeting, oldest first
+#[tauri::command]
+pub async fn api_get_meeting_questions<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Vec<StoredQuestion>, String> {
+    log_info!("api_get_meeting_questions called for meeting_id: {}", meeting_id);
+    QuestionsRepository::list_for_meeting(state.db_manager.pool(), &meeting_id)
+        .await
+        .map_err(|e| format!("Failed to get questions: {}", e))
+}
+
+/// Marks a clarifying question as answered, or unanswered again
+#[tauri::command]
+pub async fn api_mark_question_answered<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    question_id: String,
+    answered: Option<bool>,
+) -> Result<(), String> {
+    log_info!("api_mark_question_answered called for question_id: {}", question_id);
+    let updated = QuestionsRepository::set_answered(
+        state.db_manager.pool(),
+        &question_id,
+        answered.unwrap_or(true),
+    )
+    .await
+    .map_err(|e| format!("Failed to update question: {}", e))?;
+    if updated {
+        Ok(())
+    } else {
+        Err(format!("Question {} not found", question_id))
     }
-    
-    result
 }
@@ -0,0 +1,321 @@
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+
+const CONFIG_STORE: &str = "content-filter.json";
+const CONFIG_KEY: &str = "config";
+
+/// Common profanity masked when `include_default_profanity` is on
+const DEFAULT_PROFANITY: &[&str] = &[
+    "fuck", "fucking", "fucked", "fucker", "motherfucker", "shit", "shitty", "bullshit",
+    "damn", "goddamn", "crap", "crappy", "bitch", "bastard", "asshole", "ass", "dick",
+    "piss", "pissed", "cunt", "wtf",
+];
+
+/// Code blocks, inline code and task IDs (PROJ-123) are never rewritten
+static PROTECTED_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)```.*?```|`[^`\n]+`|\b[A-Z][A-Z0-9]+-\d+\b").unwrap()
+});
+
+/// A word or phrase to filter; without a replacement it is masked with asterisks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FilterTerm {
+    pub term: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replacement: Option<String>,
+}
+
+/// Language filter applied to finished summaries
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContentFilterConfig {
+    pub sanitize_summary_language: bool,
+    pub include_default_profanity: bool,
+    /// User-editable list, checked in addition to the default list
+    pub terms: Vec<FilterTerm>,
+}
+
+impl Default for ContentFilterConfig {
+    fn default() -> Self {
+        Self {
+            sanitize_summary_language: false,
+            include_default_profanity: true,
+            terms: Vec::new(),
+        }
+    }
+}
+
+impl ContentFilterConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        for term in &self.terms {
+            if term.term.trim().is_empty() {
+                return Err("Filter terms cannot be empty".to_string());
+            }
+            if let Some(replacement) = &term.replacement {
+                // Would break the markdown tables the summary is made of
+                if replacement.contains(|c| matches!(c, '|' | '\n' | '\r')) {
+                    return Err(format!(
+                        "Replacement for '{}' cannot contain '|' or line breaks",
+                        term.term
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn load_content_filter<R: Runtime>(app: &AppHandle<R>) -> ContentFilterConfig {
+    let stored = app
+        .store(CONFIG_STORE)
+        .ok()
+        .and_then(|store| store.get(CONFIG_KEY));
+    match stored.map(serde_json::from_value::<ContentFilterConfig>) {
+        Some(Ok(config)) => config,
+        Some(Err(e)) => {
+            warn!("⚠️ Invalid content filter in store, using defaults: {}", e);
+            ContentFilterConfig::default()
+        }
+        None => ContentFilterConfig::default(),
+    }
+}
+
+pub fn save_content_filter<R: Runtime>(
+    app: &AppHandle<R>,
+    config: &ContentFilterConfig,
+) -> Result<(), String> {
+    config.validate()?;
+    let store = app
+        .store(CONFIG_STORE)
+        .map_err(|e| format!("Failed to open content filter store: {}", e))?;
+    let value = serde_json::to_value(config)
+        .map_err(|e| format!("Failed to serialize content filter: {}", e))?;
+    store.set(CONFIG_KEY, value);
+    store
+        .save()
+        .map_err(|e| format!("Failed to save content filter: {}", e))
+}
+
+/// Lowercased with whitespace collapsed, so "Damn  It" and "damn it" are one key
+fn term_key(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Word-boundary pattern for a term; `\b` is Unicode-aware, so "con" does not
+/// match inside "conçu" and "cunt" does not match inside "Scunthorpe"
+fn term_pattern(term: &str) -> String {
+    let body = term
+        .split_whitespace()
+        .map(regex::escape)
+        .collect::<Vec<_>>()
+        .join(r"\s+");
+    let starts_word = term.chars().next().map_or(false, char::is_alphanumeric);
+    let ends_word = term.chars().last().map_or(false, char::is_alphanumeric);
+    format!(
+        "{}{}{}",
+        if starts_word { r"\b" } else { "" },
+        body,
+        if ends_word { r"\b" } else { "" }
+    )
+}
+
+/// Escaped so the mask isn't read as emphasis markers
+fn mask(matched: &str) -> String {
+    matched
+        .chars()
+        .map(|c| if c.is_whitespace() { c.to_string() } else { r"\*".to_string() })
+        .collect()
+}
+
+/// Capitalizes the replacement when the matched text was capitalized
+fn match_case(matched: &str, replacement: &str) -> String {
+    let capitalized = matched.chars().next().map_or(false, char::is_uppercase);
+    let mut chars = replacement.chars();
+    match chars.next() {
+        Some(first) if capitalized => first.to_uppercase().chain(chars).collect(),
+        _ => replacement.to_string(),
+    }
+}
+
+/// Compiled filter: one alternation, longest terms first so phrases win
+struct CompiledFilter {
+    pattern: Regex,
+    replacements: HashMap<String, Option<String>>,
+}
+
+impl CompiledFilter {
+    fn new(config: &ContentFilterConfig) -> Option<Self> {
+        let mut replacements: HashMap<String, Option<String>> = HashMap::new();
+        if config.include_default_profanity {
+            for word in DEFAULT_PROFANITY {
+                replacements.insert(word.to_string(), None);
+            }
+        }
+        // User terms override the defaults
+        for term in &config.terms {
+            let key = term_key(&term.term);
+            if key.is_empty() {
+                continue;
+            }
+            let replacement = term
+                .replacement
+                .as_deref()
+                .map(str::trim)
+                .filter(|r| !r.is_empty())
+                .map(str::to_string);
+            replacements.insert(key, replacement);
+        }
+        if replacements.is_empty() {
+            return None;
+        }
+
+        let mut terms: Vec<&String> = replacements.keys().collect();
+        terms.sort_by(|a, b| b.chars().count().cmp(&a.chars().count()).then(a.cmp(b)));
+        let alternation = terms
+            .iter()
+            .map(|term| term_pattern(term))
+            .collect::<Vec<_>>()
+            .join("|");
+        let pattern = match Regex::new(&format!("(?i)(?:{})", alternation)) {
+            Ok(pattern) => pattern,
+            Err(e) => {
+                warn!("⚠️ Failed to compile content filter, summary left unfiltered: {}", e);
+                return None;
+            }
+        };
+        Some(Self {
+            pattern,
+            replacements,
+        })
+    }
+
+    fn apply(&self, text: &str) -> (String, usize) {
+        let mut count = 0;
+        let replaced = self.pattern.replace_all(text, |caps: &Captures| {
+            count += 1;
+            let matched = &caps[0];
+            match self.replacements.get(&term_key(matched)) {
+                Some(Some(replacement)) => match_case(matched, replacement),
+                _ => mask(matched),
+            }
+        });
+        (replaced.into_owned(), count)
+    }
+}
+
+/// Replaces filtered words in a summary, leaving code spans and task IDs as written
+///
+/// Returns the markdown unchanged when `sanitize_summary_language` is off.
+pub fn sanitize_summary(markdown: &str, config: &ContentFilterConfig) -> String {
+    if !config.sanitize_summary_language {
+        return markdown.to_string();
+    }
+    let filter = match CompiledFilter::new(config) {
+        Some(filter) => filter,
+        None => return markdown.to_string(),
+    };
+
+    let mut output = String::with_capacity(markdown.len());
+    let mut replaced = 0;
+    let mut last = 0;
+    for protected in PROTECTED_RE.find_iter(markdown) {
+        let (text, count) = filter.apply(&markdown[last..protected.start()]);
+        output.push_str(&text);
+        output.push_str(protected.as_str());
+        replaced += count;
+        last = protected.end();
+    }
+    let (text, count) = filter.apply(&markdown[last..]);
+    output.push_str(&text);
+    replaced += count;
+
+    if replaced > 0 {
+        info!("🧼 Content filter replaced {} term(s) in summary", replaced);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enabled(terms: &[(&str, Option<&str>)]) -> ContentFilterConfig {
+        ContentFilterConfig {
+            sanitize_summary_language: true,
+            include_default_profanity: true,
+            terms: terms
+                .iter()
+                .map(|(term, replacement)| FilterTerm {
+                    term: term.to_string(),
+                    replacement: replacement.map(str::to_string),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_word_boundaries_avoid_false_positives() {
+        let config = enabled(&[("con", Some("scam"))]);
+        let input = "Scunthorpe assessment: a classic Dickens passage. Le plan est conçu. The shit hit the fan, SHIT. Total con.";
+
+        let output = sanitize_summary(input, &config);
+
+        assert_eq!(
+            output,
+            r"Scunthorpe assessment: a classic Dickens passage. Le plan est conçu. The \*\*\*\* hit the fan, \*\*\*\*. Total scam."
+        );
+    }
+
+    #[test]
+    fn test_code_spans_and_task_ids_are_protected() {
+        let config = enabled(&[("damn", Some("darn")), ("blocker", Some("issue"))]);
+        let input = "## Action Items\n\n| Owner | Task |\n|---|---|\n| Sam | Fix DAMN-42 damn blocker in `damn_cache()` |\n\n```\nlet damn = 1;\n```\n";
+
+        let output = sanitize_summary(input, &config);
+
+        assert_eq!(
+            output,
+            "## Action Items\n\n| Owner | Task |\n|---|---|\n| Sam | Fix DAMN-42 darn issue in `damn_cache()` |\n\n```\nlet damn = 1;\n```\n"
+        );
+    }
+
+    #[test]
+    fn test_custom_phrases_and_capitalization() {
+        let mut config = enabled(&[
+            ("dumpster  fire", Some("difficult situation")),
+            ("Acme Corp", None),
+        ]);
+        config.include_default_profanity = false;
+
+        let output = sanitize_summary(
+            "Dumpster\nfire of a release. Acme corp asked about the ass-end of the queue.",
+            &config,
+        );
+
+        assert_eq!(
+            output,
+            r"Difficult situation of a release. \*\*\*\* \*\*\*\* asked about the ass-end of the queue."
+        );
+    }
+
+    #[test]
+    fn test_disabled_filter_leaves_summary_untouched() {
+        let mut config = enabled(&[]);
+        config.sanitize_summary_language = false;
+        assert_eq!(sanitize_summary("What the fuck", &config), "What the fuck");
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(enabled(&[("ok", Some("fine"))]).validate().is_ok());
+        assert!(enabled(&[("  ", None)]).validate().is_err());
+        assert!(enabled(&[("bad", Some("a | b"))]).validate().is_err());
+    }
+}
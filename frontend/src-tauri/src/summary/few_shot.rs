@@ -0,0 +1,250 @@
+//! Few-shot example exchange for the final report
+//!
+//! The system prompt describes each section's format, but small models
+//! follow a table layout far more reliably once they have seen it filled
+//! in. In few-shot mode the final report request starts with a short sample
+//! transcript and a report of it in the template's exact layout, sent as an
+//! earlier user and assistant turn. That costs a few hundred tokens per
+//! report, so it is off by default and left out whenever it would push the
+//! prompt past the model's context.
+
+use crate::summary::llm_client::FewShotExample;
+use crate::summary::processor::{prompt_budget, rough_token_count, ChunkMarkers, SummaryTarget};
+use crate::summary::table::{is_separator_row, is_table_line, split_cells};
+use crate::summary::templates::{TableColumn, TableSchema, Template, TemplateSection};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+/// Which models get the example exchange
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FewShotMode {
+    #[default]
+    Off,
+    /// Only models that get the simplified small-model prompt
+    SmallModels,
+    Always,
+}
+
+impl FewShotMode {
+    pub fn applies(self, small_model: bool) -> bool {
+        match self {
+            FewShotMode::Off => false,
+            FewShotMode::SmallModels => small_model,
+            FewShotMode::Always => true,
+        }
+    }
+}
+
+/// The sample meeting the example report summarizes
+const SAMPLE_TRANSCRIPT: &str =
+    "[00:00] Alex: The Stripe webhook is still failing in production, that's PROJ-404.\n\
+[00:12] Sam: Two of us can take it and have a fix before noon today.\n\
+[00:25] Alex: Good, then we hold the release until the fix is verified.";
+
+const SAMPLE_TITLE: &str = "Stripe Webhook Fix";
+const SAMPLE_PARAGRAPH: &str = "Alex reported that the Stripe webhook (PROJ-404) is still failing in production. Two developers will fix it before noon today, and the release waits until the fix is verified.";
+const SAMPLE_ITEM: &str = "The release waits until the Stripe webhook fix (PROJ-404) is verified";
+
+/// Sample value for a table column, taken from the sample transcript
+fn sample_cell(column: &TableColumn) -> &'static str {
+    let name = column.name.to_lowercase();
+    if name.contains("reference") {
+        "Two of us can take it and have a fix before noon today"
+    } else if column.is_reference() {
+        "00:12"
+    } else if name.contains("owner") {
+        "Two developers"
+    } else if name.contains("due") || name.contains("eta") || name.contains("date") {
+        "Before noon today"
+    } else if name.contains("status") {
+        "In Progress"
+    } else {
+        "Fix Stripe webhook (PROJ-404)"
+    }
+}
+
+/// A row from the section's own example hint ("Good example: | ... |"),
+/// if it has one with the table's column count
+fn template_example_row(section: &TemplateSection, schema: &TableSchema) -> Option<String> {
+    let hint = section.example_item_format.as_deref()?;
+    hint.lines()
+        .filter(|line| !line.to_lowercase().contains("bad example"))
+        .map(|line| line.trim().trim_start_matches("Good example:").trim())
+        .filter(|line| is_table_line(line) && !is_separator_row(line))
+        .map(split_cells)
+        .find(|cells| cells.len() == schema.columns.len() && !schema.is_header(cells))
+        .map(|cells| schema.row(&cells))
+}
+
+/// The table layout of a section: its schema, or the header of a table
+/// `item_format`/`example_item_format` hint
+fn section_table(template: &Template, section: &TemplateSection) -> Option<TableSchema> {
+    if let Some(schema) = template.table_schema(section) {
+        return Some(schema);
+    }
+    let hint = section
+        .item_format
+        .as_deref()
+        .or(section.example_item_format.as_deref())?;
+    let header = hint.lines().next().filter(|line| is_table_line(line))?;
+    Some(TableSchema::from_header(&split_cells(header)))
+}
+
+fn section_example(template: &Template, section: &TemplateSection) -> String {
+    if let Some(schema) = section_table(template, section) {
+        let row = template_example_row(section, &schema).unwrap_or_else(|| {
+            let cells: Vec<&str> = schema.columns.iter().map(sample_cell).collect();
+            schema.row(&cells)
+        });
+        return format!("{}\n{}\n{}", schema.header(), schema.separator(), row);
+    }
+    match section.format.as_str() {
+        "list" => format!("- {}", SAMPLE_ITEM),
+        _ => SAMPLE_PARAGRAPH.to_string(),
+    }
+}
+
+/// A report of the sample transcript with every section of `template` in
+/// its format
+pub fn example_report(template: &Template) -> String {
+    let mut report = format!("# {}\n", SAMPLE_TITLE);
+    for section in &template.sections {
+        report.push_str(&format!(
+            "\n## {}\n\n{}\n",
+            section.title,
+            section_example(template, section)
+        ));
+    }
+    report
+}
+
+/// The sample transcript in the final prompt's transcript tags and its report
+pub fn example_exchange(template: &Template, markers: &ChunkMarkers) -> FewShotExample {
+    FewShotExample {
+        user: format!(
+            "<{tag}>\n{transcript}\n</{tag}>",
+            tag = markers.transcript_tag,
+            transcript = SAMPLE_TRANSCRIPT
+        ),
+        assistant: example_report(template),
+    }
+}
+
+/// The example exchange for the final report prompt, if `target` asks for
+/// one and it fits in the model's context next to the prompt
+pub fn examples_for(
+    target: &SummaryTarget,
+    template: &Template,
+    markers: &ChunkMarkers,
+    system_prompt: &str,
+    user_prompt: &str,
+) -> Vec<FewShotExample> {
+    if !target.few_shot {
+        return Vec::new();
+    }
+    let example = example_exchange(template, markers);
+    let example_tokens = rough_token_count(&example.user) + rough_token_count(&example.assistant);
+    if let Some(context_tokens) = target.context_tokens {
+        let prompt_tokens = rough_token_count(system_prompt) + rough_token_count(user_prompt);
+        if prompt_tokens + example_tokens > prompt_budget(context_tokens) {
+            info!(
+                "📎 Leaving out the {}-token example for {}: the prompt needs the room",
+                example_tokens, target.model_name
+            );
+            return Vec::new();
+        }
+    }
+    info!(
+        "📎 Adding a {}-token example exchange for {}",
+        example_tokens, target.model_name
+    );
+    vec![example]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::summary::llm_client::LLMProvider;
+    use crate::summary::processor::ModelSize;
+    use crate::summary::templates::SectionMode;
+
+    fn section(title: &str, format: &str, example_item_format: Option<&str>) -> TemplateSection {
+        TemplateSection {
+            title: title.to_string(),
+            instruction: format!("Cover the {}", title),
+            format: format.to_string(),
+            item_format: None,
+            example_item_format: example_item_format.map(str::to_string),
+            table_columns: None,
+        }
+    }
+
+    fn template() -> Template {
+        Template {
+            name: "Sync".to_string(),
+            description: "Sync meeting".to_string(),
+            sections: vec![
+                section("Summary", "paragraph", None),
+                section("Decisions", "list", None),
+                section(
+                    "Action Items",
+                    "list",
+                    Some("Good example: | Two developers | Fix Stripe webhook (PROJ-404) | Before noon today |\nBad example: | No blocker | Stripe debugging continues | None |"),
+                ),
+            ],
+            preserve_heading_decorations: false,
+            omit_reference_columns: true,
+            system_prompt_override: None,
+            section_mode: SectionMode::default(),
+        }
+    }
+
+    #[test]
+    fn test_few_shot_mode_adds_the_example_exchange() {
+        let target = SummaryTarget {
+            provider: LLMProvider::Ollama,
+            model_name: "llama3.2:1b".to_string(),
+            api_key: String::new(),
+            ollama_endpoint: None,
+            token_threshold: 4000,
+            context_tokens: Some(4096),
+            model_size: ModelSize {
+                small: true,
+                ..ModelSize::default()
+            },
+            few_shot: FewShotMode::SmallModels.applies(true),
+        };
+        let markers = ChunkMarkers::default();
+
+        let examples = examples_for(&target, &template(), &markers, "sys", "user");
+        assert_eq!(examples.len(), 1);
+        let example = &examples[0];
+        assert!(example
+            .user
+            .starts_with(&format!("<{}>\n[00:00] Alex:", markers.transcript_tag)));
+        assert!(example
+            .assistant
+            .starts_with("# Stripe Webhook Fix\n\n## Summary\n\n"));
+        assert!(example
+            .assistant
+            .contains("## Decisions\n\n- The release waits"));
+        // The template's own good example row, in its 3-column layout
+        assert!(
+            example.assistant.ends_with(
+                "## Action Items\n\n| **Owner** | Task | Due |\n| --- | --- | --- |\n| Two developers | Fix Stripe webhook (PROJ-404) | Before noon today |\n"
+            ),
+            "{}",
+            example.assistant
+        );
+
+        // Off, or a prompt that needs the whole context, sends no example
+        let off = SummaryTarget {
+            few_shot: FewShotMode::SmallModels.applies(false),
+            ..target.clone()
+        };
+        assert!(examples_for(&off, &template(), &markers, "sys", "user").is_empty());
+        let crowded = "word ".repeat(3000);
+        assert!(examples_for(&target, &template(), &markers, "sys", &crowded).is_empty());
+    }
+}
@@ -0,0 +1,652 @@
+use crate::audio::recording_commands::TranscriptUpdate;
+use crate::database::models::LiveSummary;
+use crate::database::repositories::live_summary::LiveSummariesRepository;
+use crate::database::repositories::SettingsRepo;
+use crate::http::client_factory;
+use crate::state::AppState;
+use crate::summary::llm_client::{generate_summary, LLMProvider};
+use crate::summary::pipeline_config::load_summary_pipeline_config;
+use crate::summary::processor::{
+    chunk_user_prompt, combine_user_prompt, rough_token_count, ChunkMarkers,
+    CHUNK_SYSTEM_PROMPT, COMBINE_SYSTEM_PROMPT,
+};
+use crate::summary::queue::summary_slots;
+use async_trait::async_trait;
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, EventId, Listener, Manager, Runtime};
+use tauri_plugin_store::StoreExt;
+use tokio::sync::Semaphore;
+use tokio::task::JoinHandle;
+
+const CONFIG_STORE: &str = "live-summary.json";
+const CONFIG_KEY: &str = "config";
+
+/// Settings for the draft summary built while recording
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LiveSummaryConfig {
+    pub enabled: bool,
+    /// Minutes between update passes
+    pub interval_minutes: u64,
+    /// A pass is skipped until at least this much new transcript arrived
+    pub min_new_tokens: usize,
+}
+
+impl Default for LiveSummaryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_minutes: 5,
+            min_new_tokens: 200,
+        }
+    }
+}
+
+impl LiveSummaryConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.interval_minutes == 0 {
+            return Err("Live summary interval must be at least one minute".to_string());
+        }
+        Ok(())
+    }
+}
+
+pub fn load_live_summary_config<R: Runtime>(app: &AppHandle<R>) -> LiveSummaryConfig {
+    let stored = app
+        .store(CONFIG_STORE)
+        .ok()
+        .and_then(|store| store.get(CONFIG_KEY));
+    match stored.map(serde_json::from_value::<LiveSummaryConfig>) {
+        Some(Ok(config)) => config,
+        Some(Err(e)) => {
+            warn!("⚠️ Invalid live summary config in store, using defaults: {}", e);
+            LiveSummaryConfig::default()
+        }
+        None => LiveSummaryConfig::default(),
+    }
+}
+
+pub fn save_live_summary_config<R: Runtime>(
+    app: &AppHandle<R>,
+    config: &LiveSummaryConfig,
+) -> Result<(), String> {
+    config.validate()?;
+    let store = app
+        .store(CONFIG_STORE)
+        .map_err(|e| format!("Failed to open live summary store: {}", e))?;
+    let value = serde_json::to_value(config)
+        .map_err(|e| format!("Failed to serialize live summary config: {}", e))?;
+    store.set(CONFIG_KEY, value);
+    store
+        .save()
+        .map_err(|e| format!("Failed to save live summary config: {}", e))
+}
+
+/// The two LLM steps of a live pass
+#[async_trait]
+pub trait Summarizer: Send + Sync {
+    /// Summarizes transcript that arrived since the last pass
+    async fn summarize_chunk(&self, text: &str) -> Result<String, String>;
+
+    /// Folds a new partial summary into the running draft
+    async fn merge(&self, draft: &str, update: &str) -> Result<String, String>;
+}
+
+/// `Summarizer` using the configured model and the same prompts as the final summary
+pub struct LlmSummarizer {
+    client: Client,
+    provider: LLMProvider,
+    model_name: String,
+    api_key: String,
+    ollama_endpoint: Option<String>,
+    markers: ChunkMarkers,
+}
+
+impl LlmSummarizer {
+    pub async fn from_settings(
+        settings: &dyn SettingsRepo,
+        markers: ChunkMarkers,
+    ) -> Result<Self, String> {
+        let config = settings
+            .get_model_config()
+            .await
+            .map_err(|e| format!("Failed to get model config: {}", e))?
+            .ok_or_else(|| "Model config not found".to_string())?;
+        let provider = LLMProvider::from_str(&config.provider)?;
+        let api_key = if provider == LLMProvider::Ollama {
+            String::new()
+        } else {
+            settings
+                .get_api_key(&config.provider)
+                .await
+                .map_err(|e| format!("Failed to get API key: {}", e))?
+                .filter(|key| !key.is_empty())
+                .ok_or_else(|| format!("API key not found for {}", config.provider))?
+        };
+
+        Ok(Self {
+            client: client_factory::client(),
+            provider,
+            model_name: config.model,
+            api_key,
+            ollama_endpoint: config.ollama_endpoint,
+            markers,
+        })
+    }
+
+    async fn call(&self, system_prompt: &str, user_prompt: &str) -> Result<String, String> {
+        generate_summary(
+            &self.client,
+            &self.provider,
+            &self.model_name,
+            &self.api_key,
+            system_prompt,
+            user_prompt,
+            self.ollama_endpoint.as_deref(),
+        )
+        .await
+        .map_err(|e| e.to_string())
+    }
+}
+
+#[async_trait]
+impl Summarizer for LlmSummarizer {
+    async fn summarize_chunk(&self, text: &str) -> Result<String, String> {
+        self.call(CHUNK_SYSTEM_PROMPT, &chunk_user_prompt(text, &self.markers))
+            .await
+    }
+
+    async fn merge(&self, draft: &str, update: &str) -> Result<String, String> {
+        let summaries = [draft.to_string(), update.to_string()];
+        self.call(COMBINE_SYSTEM_PROMPT, &combine_user_prompt(&summaries, &self.markers))
+            .await
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PassOutcome {
+    Updated,
+    /// Not enough new transcript since the last pass
+    TooFewTokens,
+    /// Every summary slot is taken; the new text waits for the next pass
+    QueueBusy,
+}
+
+/// Running draft of one recording session
+#[derive(Debug, Default)]
+pub struct LiveDraft {
+    /// Finalized transcript text not yet folded into the draft
+    pending: Mutex<Vec<String>>,
+    draft: Mutex<Option<String>>,
+    passes: Mutex<i64>,
+}
+
+impl LiveDraft {
+    pub fn push(&self, text: &str) {
+        let text = text.trim();
+        if !text.is_empty() {
+            self.pending.lock().unwrap().push(text.to_string());
+        }
+    }
+
+    pub fn pending_tokens(&self) -> usize {
+        self.pending
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|text| rough_token_count(text))
+            .sum()
+    }
+
+    pub fn draft(&self) -> Option<String> {
+        self.draft.lock().unwrap().clone()
+    }
+
+    pub fn passes(&self) -> i64 {
+        *self.passes.lock().unwrap()
+    }
+
+    /// Summarizes the pending text and merges it into the draft
+    ///
+    /// Live passes never wait for a summary slot: a full summary always takes
+    /// priority. Pending text is kept when the pass is skipped or fails.
+    pub async fn run_pass(
+        &self,
+        summarizer: &dyn Summarizer,
+        slots: &Semaphore,
+        min_new_tokens: usize,
+    ) -> Result<PassOutcome, String> {
+        let pending_tokens = self.pending_tokens();
+        if pending_tokens == 0 || pending_tokens < min_new_tokens {
+            return Ok(PassOutcome::TooFewTokens);
+        }
+        let _slot = match slots.try_acquire() {
+            Ok(permit) => permit,
+            Err(_) => return Ok(PassOutcome::QueueBusy),
+        };
+        self.fold_pending(summarizer).await
+    }
+
+    /// Folds all pending text into the draft, however little, waiting for a
+    /// summary slot if needed; the last pass when the recording stops
+    pub async fn flush(
+        &self,
+        summarizer: &dyn Summarizer,
+        slots: &Semaphore,
+    ) -> Result<PassOutcome, String> {
+        if self.pending_tokens() == 0 {
+            return Ok(PassOutcome::TooFewTokens);
+        }
+        let _slot = slots.acquire().await.map_err(|e| e.to_string())?;
+        self.fold_pending(summarizer).await
+    }
+
+    async fn fold_pending(&self, summarizer: &dyn Summarizer) -> Result<PassOutcome, String> {
+        let taken = std::mem::take(&mut *self.pending.lock().unwrap());
+        let result = async {
+            let update = summarizer.summarize_chunk(&taken.join("\n")).await?;
+            match self.draft() {
+                Some(draft) => summarizer.merge(&draft, &update).await,
+                None => Ok(update),
+            }
+        }
+        .await;
+
+        match result {
+            Ok(draft) => {
+                *self.draft.lock().unwrap() = Some(draft);
+                *self.passes.lock().unwrap() += 1;
+                Ok(PassOutcome::Updated)
+            }
+            Err(e) => {
+                // Put the text back ahead of anything that arrived meanwhile
+                let mut pending = self.pending.lock().unwrap();
+                let newer = std::mem::replace(&mut *pending, taken);
+                pending.extend(newer);
+                Err(e)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveSummaryUpdated {
+    pub session_id: String,
+    pub meeting_name: Option<String>,
+    pub draft: String,
+    pub passes: i64,
+    pub updated_at: String,
+}
+
+struct LiveSession {
+    session_id: String,
+    meeting_name: Option<String>,
+    draft: Arc<LiveDraft>,
+    listener: EventId,
+    task: JoinHandle<()>,
+}
+
+static LIVE_SESSION: Lazy<Mutex<Option<LiveSession>>> = Lazy::new(|| Mutex::new(None));
+
+/// Starts building a draft for the recording that just started
+pub fn start_live_summary<R: Runtime>(app: &AppHandle<R>, meeting_name: Option<String>) {
+    let config = load_live_summary_config(app);
+    if !config.enabled {
+        info!("ℹ️ Live summary disabled, not starting");
+        return;
+    }
+    stop_live_summary(app);
+
+    let session_id = uuid::Uuid::new_v4().to_string();
+    let draft = Arc::new(LiveDraft::default());
+
+    let listener_draft = draft.clone();
+    let listener = app.listen("transcript-update", move |event: tauri::Event| {
+        if let Ok(update) = serde_json::from_str::<TranscriptUpdate>(event.payload()) {
+            if !update.is_partial {
+                listener_draft.push(&update.text);
+            }
+        }
+    });
+
+    let task = tokio::spawn(run_live_summary(
+        app.clone(),
+        session_id.clone(),
+        meeting_name.clone(),
+        draft.clone(),
+        config,
+    ));
+
+    info!("📝 Live summary started for session {}", session_id);
+    *LIVE_SESSION.lock().unwrap() = Some(LiveSession {
+        session_id,
+        meeting_name,
+        draft,
+        listener,
+        task,
+    });
+}
+
+/// Stops the running session and returns its id, which the transcript save
+/// passes back to attach the draft to the meeting
+///
+/// Call this once the last transcript of the recording was emitted: text
+/// that arrived since the last pass is folded into the draft in the
+/// background. The stored draft stays until the final summary replaces it.
+pub fn stop_live_summary<R: Runtime>(app: &AppHandle<R>) -> Option<String> {
+    let session = LIVE_SESSION.lock().unwrap().take()?;
+    app.unlisten(session.listener);
+    session.task.abort();
+    info!("🛑 Live summary stopped for session {}", session.session_id);
+
+    tokio::spawn(flush_live_summary(
+        app.clone(),
+        session.session_id.clone(),
+        session.meeting_name,
+        session.draft,
+    ));
+    Some(session.session_id)
+}
+
+/// Session id of the recording currently being summarized
+pub fn active_session_id() -> Option<String> {
+    LIVE_SESSION
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|session| session.session_id.clone())
+}
+
+async fn run_live_summary<R: Runtime>(
+    app: AppHandle<R>,
+    session_id: String,
+    meeting_name: Option<String>,
+    draft: Arc<LiveDraft>,
+    config: LiveSummaryConfig,
+) {
+    let mut interval = tokio::time::interval(Duration::from_secs(config.interval_minutes * 60));
+    // The first tick fires immediately, before anything was said
+    interval.tick().await;
+
+    loop {
+        interval.tick().await;
+
+        let state = app.state::<AppState>();
+        let markers = load_summary_pipeline_config(&app).chunk_markers;
+        let summarizer =
+            match LlmSummarizer::from_settings(state.repos.settings.as_ref(), markers).await {
+                Ok(summarizer) => summarizer,
+                Err(e) => {
+                    warn!("⚠️ Live summary pass skipped: {}", e);
+                    continue;
+                }
+            };
+
+        match draft
+            .run_pass(&summarizer, &summary_slots(), config.min_new_tokens)
+            .await
+        {
+            Ok(PassOutcome::Updated) => {
+                publish_draft(&app, &session_id, meeting_name.as_deref(), &draft).await;
+            }
+            Ok(PassOutcome::TooFewTokens) => {
+                info!(
+                    "⏭️ Live summary pass skipped: {} new tokens (< {})",
+                    draft.pending_tokens(),
+                    config.min_new_tokens
+                );
+            }
+            Ok(PassOutcome::QueueBusy) => {
+                info!("⏭️ Live summary pass skipped: summary queue is busy");
+            }
+            Err(e) => warn!("⚠️ Live summary pass failed: {}", e),
+        }
+    }
+}
+
+/// Folds the text of a stopped session's last minutes into its draft
+async fn flush_live_summary<R: Runtime>(
+    app: AppHandle<R>,
+    session_id: String,
+    meeting_name: Option<String>,
+    draft: Arc<LiveDraft>,
+) {
+    if draft.pending_tokens() == 0 {
+        return;
+    }
+    let state = app.state::<AppState>();
+    let markers = load_summary_pipeline_config(&app).chunk_markers;
+    let summarizer =
+        match LlmSummarizer::from_settings(state.repos.settings.as_ref(), markers).await {
+            Ok(summarizer) => summarizer,
+            Err(e) => {
+                warn!("⚠️ Final live summary pass skipped: {}", e);
+                return;
+            }
+        };
+    match draft.flush(&summarizer, &summary_slots()).await {
+        Ok(PassOutcome::Updated) => {
+            publish_draft(&app, &session_id, meeting_name.as_deref(), &draft).await;
+        }
+        Ok(_) => {}
+        Err(e) => warn!("⚠️ Final live summary pass failed: {}", e),
+    }
+}
+
+/// Stores the draft and tells the frontend about it
+async fn publish_draft<R: Runtime>(
+    app: &AppHandle<R>,
+    session_id: &str,
+    meeting_name: Option<&str>,
+    draft: &LiveDraft,
+) {
+    let text = draft.draft().unwrap_or_default();
+    let passes = draft.passes();
+    let state = app.state::<AppState>();
+    if let Err(e) = LiveSummariesRepository::save_draft(
+        state.db_manager.pool(),
+        session_id,
+        meeting_name,
+        &text,
+        passes,
+    )
+    .await
+    {
+        warn!("⚠️ Failed to store live summary draft: {}", e);
+    }
+    let _ = app.emit(
+        "live-summary-updated",
+        LiveSummaryUpdated {
+            session_id: session_id.to_string(),
+            meeting_name: meeting_name.map(str::to_string),
+            draft: text,
+            passes,
+            updated_at: chrono::Utc::now().to_rfc3339(),
+        },
+    );
+    info!("📝 Live summary updated (pass {})", passes);
+}
+
+/// Hands the draft of a recording session to the meeting it was saved as
+///
+/// The final pass of the session may still be running; its draft lands on
+/// the meeting when it is stored.
+pub async fn attach_draft(pool: &SqlitePool, session_id: &str, meeting_id: &str) {
+    if let Err(e) = LiveSummariesRepository::attach_to_meeting(pool, session_id, meeting_id).await {
+        warn!("⚠️ Failed to attach live summary draft to {}: {}", meeting_id, e);
+    }
+}
+
+/// The draft of a saved meeting, or of the recording in progress
+pub async fn get_live_summary(
+    pool: &SqlitePool,
+    meeting_id: Option<&str>,
+) -> Result<Option<LiveSummary>, sqlx::Error> {
+    match meeting_id {
+        Some(meeting_id) => LiveSummariesRepository::get_for_meeting(pool, meeting_id).await,
+        None => match active_session_id() {
+            Some(session_id) => LiveSummariesRepository::get_for_session(pool, &session_id).await,
+            None => Ok(None),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Records the merge chain as text: S(chunk) and M(draft,update)
+    struct MockSummarizer;
+
+    #[async_trait]
+    impl Summarizer for MockSummarizer {
+        async fn summarize_chunk(&self, text: &str) -> Result<String, String> {
+            Ok(format!("S({})", text))
+        }
+
+        async fn merge(&self, draft: &str, update: &str) -> Result<String, String> {
+            Ok(format!("M({},{})", draft, update))
+        }
+    }
+
+    struct FailingSummarizer;
+
+    #[async_trait]
+    impl Summarizer for FailingSummarizer {
+        async fn summarize_chunk(&self, _text: &str) -> Result<String, String> {
+            Err("provider down".to_string())
+        }
+
+        async fn merge(&self, _draft: &str, _update: &str) -> Result<String, String> {
+            Err("provider down".to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_three_passes_merge_into_draft() {
+        let draft = LiveDraft::default();
+        let slots = Semaphore::new(1);
+
+        draft.push("alpha");
+        assert_eq!(
+            draft.run_pass(&MockSummarizer, &slots, 1).await.unwrap(),
+            PassOutcome::Updated
+        );
+        draft.push("beta");
+        draft.push("gamma");
+        draft.run_pass(&MockSummarizer, &slots, 1).await.unwrap();
+        draft.push("delta");
+        draft.run_pass(&MockSummarizer, &slots, 1).await.unwrap();
+
+        assert_eq!(
+            draft.draft().as_deref(),
+            Some("M(M(S(alpha),S(beta\ngamma)),S(delta))")
+        );
+        assert_eq!(draft.passes(), 3);
+        assert_eq!(draft.pending_tokens(), 0);
+        assert_eq!(slots.available_permits(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_pass_skipped_below_min_tokens() {
+        let draft = LiveDraft::default();
+        let slots = Semaphore::new(1);
+
+        assert_eq!(
+            draft.run_pass(&MockSummarizer, &slots, 0).await.unwrap(),
+            PassOutcome::TooFewTokens
+        );
+        draft.push("short");
+        assert_eq!(
+            draft.run_pass(&MockSummarizer, &slots, 50).await.unwrap(),
+            PassOutcome::TooFewTokens
+        );
+        assert_eq!(draft.draft(), None);
+
+        draft.push(&"enough words now ".repeat(20));
+        assert_eq!(
+            draft.run_pass(&MockSummarizer, &slots, 50).await.unwrap(),
+            PassOutcome::Updated
+        );
+        // The skipped text is part of the first summary
+        assert!(draft.draft().unwrap().starts_with("S(short\n"));
+    }
+
+    #[tokio::test]
+    async fn test_pass_skipped_when_queue_busy_keeps_text() {
+        let draft = LiveDraft::default();
+        let slots = Semaphore::new(1);
+        draft.push("alpha");
+
+        let held = slots.try_acquire().unwrap();
+        assert_eq!(
+            draft.run_pass(&MockSummarizer, &slots, 1).await.unwrap(),
+            PassOutcome::QueueBusy
+        );
+        drop(held);
+
+        draft.run_pass(&MockSummarizer, &slots, 1).await.unwrap();
+        assert_eq!(draft.draft().as_deref(), Some("S(alpha)"));
+    }
+
+    #[tokio::test]
+    async fn test_failed_pass_keeps_pending_text_in_order() {
+        let draft = LiveDraft::default();
+        let slots = Semaphore::new(1);
+        draft.push("alpha");
+
+        assert!(draft.run_pass(&FailingSummarizer, &slots, 1).await.is_err());
+        draft.push("beta");
+        draft.run_pass(&MockSummarizer, &slots, 1).await.unwrap();
+
+        assert_eq!(draft.draft().as_deref(), Some("S(alpha\nbeta)"));
+        assert_eq!(draft.passes(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_flush_folds_the_remaining_text_at_stop() {
+        let draft = LiveDraft::default();
+        let slots = Arc::new(Semaphore::new(1));
+        draft.push("alpha");
+        draft.run_pass(&MockSummarizer, &slots, 1).await.unwrap();
+        draft.push("last words");
+
+        // Too little for a regular pass, and the queue is busy
+        let held = slots.clone().try_acquire_owned().unwrap();
+        assert_eq!(
+            draft.run_pass(&MockSummarizer, &slots, 50).await.unwrap(),
+            PassOutcome::TooFewTokens
+        );
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            drop(held);
+        });
+
+        assert_eq!(
+            draft.flush(&MockSummarizer, &slots).await.unwrap(),
+            PassOutcome::Updated
+        );
+        assert_eq!(draft.draft().as_deref(), Some("M(S(alpha),S(last words))"));
+        assert_eq!(
+            draft.flush(&MockSummarizer, &slots).await.unwrap(),
+            PassOutcome::TooFewTokens
+        );
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(LiveSummaryConfig::default().validate().is_ok());
+        let config = LiveSummaryConfig {
+            interval_minutes: 0,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+}
@@ -1,8 +1,16 @@
+use crate::http::client_factory::bypass_proxy_for;
+use crate::logging::log_preview;
+use crate::summary::provider_overrides::{override_for, ProviderOverride};
 use reqwest::{header, Client};
 use serde::{Deserialize, Serialize};
-use tracing::info;
+use std::fmt;
+use tracing::{info, warn};
+
+pub(crate) const SEND_FAILED_PREFIX: &str = "Failed to send request to LLM";
+pub(crate) const REQUEST_FAILED_PREFIX: &str = "LLM API request failed (HTTP ";
+
+const GEMINI_BASE_URL: &str = "https://generativelanguage.googleapis.com/v1beta";
 
-// Generic structure for OpenAI-compatible API chat messages
 #[derive(Debug, Serialize)]
 pub struct ChatMessage {
     pub role: String,
@@ -14,6 +22,8 @@ pub struct ChatMessage {
 pub struct ChatRequest {
     pub model: String,
     pub messages: Vec<ChatMessage>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub stream: bool,
 }
 
 // Generic structure for OpenAI-compatible API chat responses
@@ -25,6 +35,8 @@ pub struct ChatResponse {
 #[derive(Deserialize, Debug)]
 pub struct Choice {
     pub message: MessageContent,
+    #[serde(default)]
+    pub finish_reason: Option<String>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -40,11 +52,16 @@ pub struct GeminiResponse {
 
 #[derive(Deserialize, Debug)]
 pub struct GeminiCandidate {
+    // Missing when the candidate was cut off before producing any text
+    #[serde(default)]
     pub content: GeminiContent,
+    #[serde(default, rename = "finishReason")]
+    pub finish_reason: Option<String>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Default)]
 pub struct GeminiContent {
+    #[serde(default)]
     pub parts: Vec<GeminiPart>,
 }
 
@@ -58,14 +75,19 @@ pub struct GeminiPart {
 pub struct ClaudeRequest {
     pub model: String,
     pub max_tokens: u32,
+    #[serde(skip_serializing_if = "String::is_empty")]
     pub system: String,
     pub messages: Vec<ChatMessage>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub stream: bool,
 }
 
 // Claude-specific response structure
 #[derive(Deserialize, Debug)]
 pub struct ClaudeChatResponse {
     pub content: Vec<ClaudeChatContent>,
+    #[serde(default)]
+    pub stop_reason: Option<String>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -73,6 +95,56 @@ pub struct ClaudeChatContent {
     pub text: String,
 }
 
+/// An earlier exchange sent ahead of the user prompt, showing the model
+/// the output expected of it
+#[derive(Debug, Clone, PartialEq)]
+pub struct FewShotExample {
+    pub user: String,
+    pub assistant: String,
+}
+
+/// Example exchanges followed by the user prompt, as chat turns
+fn chat_turns(examples: &[FewShotExample], user_prompt: &str) -> Vec<ChatMessage> {
+    let mut turns = Vec::with_capacity(examples.len() * 2 + 1);
+    for example in examples {
+        turns.push(ChatMessage {
+            role: "user".to_string(),
+            content: example.user.clone(),
+        });
+        turns.push(ChatMessage {
+            role: "assistant".to_string(),
+            content: example.assistant.clone(),
+        });
+    }
+    turns.push(ChatMessage {
+        role: "user".to_string(),
+        content: user_prompt.to_string(),
+    });
+    turns
+}
+
+/// Text of a finished LLM call and the reason the provider gave for stopping
+#[derive(Debug, Clone, PartialEq)]
+pub struct LlmCompletion {
+    pub text: String,
+    /// `finish_reason` (OpenAI-compatible), `stop_reason` (Claude) or
+    /// `finishReason` (Gemini) as reported; None if the provider sent none
+    pub finish_reason: Option<String>,
+}
+
+impl LlmCompletion {
+    /// Whether the output was cut off by the token limit
+    pub fn truncated(&self) -> bool {
+        self.finish_reason.as_deref().is_some_and(is_truncation_reason)
+    }
+}
+
+/// "length" (OpenAI-compatible), "max_tokens" (Claude) and "MAX_TOKENS"
+/// (Gemini) all mean the model hit its output limit
+pub fn is_truncation_reason(reason: &str) -> bool {
+    reason.eq_ignore_ascii_case("length") || reason.eq_ignore_ascii_case("max_tokens")
+}
+
 /// LLM Provider enumeration for multi-provider support
 #[derive(Debug, Clone, PartialEq)]
 pub enum LLMProvider {
@@ -97,59 +169,145 @@ impl LLMProvider {
             _ => Err(format!("Unsupported LLM provider: {}", s)),
         }
     }
+
+    /// Name as stored in settings, the inverse of `from_str`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::OpenAI => "openai",
+            Self::Claude => "claude",
+            Self::Groq => "groq",
+            Self::Ollama => "ollama",
+            Self::OpenRouter => "openrouter",
+            Self::Gemini => "gemini",
+        }
+    }
 }
 
-/// Generates a summary using the specified LLM provider
+/// Why a call to an LLM provider failed
 ///
-/// # Arguments
-/// * `client` - Reqwest HTTP client (reused for performance)
-/// * `provider` - The LLM provider to use
-/// * `model_name` - The specific model to use (e.g., "gpt-4", "claude-3-opus")
-/// * `api_key` - API key for the provider (not needed for Ollama)
-/// * `system_prompt` - System instructions for the LLM
-/// * `user_prompt` - User query/content to process
-/// * `ollama_endpoint` - Optional custom Ollama endpoint (defaults to localhost:11434)
+/// Messages keep the `SEND_FAILED_PREFIX` and `REQUEST_FAILED_PREFIX`
+/// formats, which stored errors are still classified by.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LlmError {
+    /// Network failure, rate limiting (429) or a server error (5xx)
+    Unavailable(String),
+    /// No response within the client timeout
+    TimedOut(String),
+    /// Anything else: bad key, bad request, unparseable response
+    Failed(String),
+}
+
+impl LlmError {
+    /// The provider itself is unavailable, so another one may succeed
+    pub fn is_provider_unavailable(&self) -> bool {
+        !matches!(self, Self::Failed(_))
+    }
+
+    /// Worth sending again to the same provider; timeouts aren't, since the
+    /// client timeout is already long
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::Unavailable(_))
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            Self::Unavailable(message) | Self::TimedOut(message) | Self::Failed(message) => message,
+        }
+    }
+
+    /// The same kind of error with `prefix` in front of its message
+    pub fn context(self, prefix: &str) -> Self {
+        match self {
+            Self::Unavailable(message) => Self::Unavailable(format!("{}{}", prefix, message)),
+            Self::TimedOut(message) => Self::TimedOut(format!("{}{}", prefix, message)),
+            Self::Failed(message) => Self::Failed(format!("{}{}", prefix, message)),
+        }
+    }
+}
+
+impl fmt::Display for LlmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl From<String> for LlmError {
+    fn from(message: String) -> Self {
+        Self::Failed(message)
+    }
+}
+
+impl From<&str> for LlmError {
+    fn from(message: &str) -> Self {
+        Self::Failed(message.to_string())
+    }
+}
+
+/// URL, headers and body of a chat request for `provider`
 ///
-/// # Returns
-/// The generated summary text or an error message
-pub async fn generate_summary(
-    client: &Client,
+/// With `stream` the provider is asked for server-sent events instead of a
+/// single JSON response. `overrides` replaces the base URL and adds its
+/// headers last, so they win over the provider's own, and may ask for the
+/// system prompt to be sent inside the user message. `examples` go before
+/// the user prompt as earlier user and assistant turns.
+#[allow(clippy::too_many_arguments)]
+fn build_request(
     provider: &LLMProvider,
     model_name: &str,
     api_key: &str,
     system_prompt: &str,
     user_prompt: &str,
+    examples: &[FewShotExample],
     ollama_endpoint: Option<&str>,
-) -> Result<String, String> {
+    stream: bool,
+    overrides: &ProviderOverride,
+) -> Result<(String, header::HeaderMap, serde_json::Value), String> {
+    let merged_prompt;
+    let (system_prompt, user_prompt) = if overrides.merges_system_prompt(model_name) {
+        merged_prompt = merge_system_prompt(system_prompt, user_prompt);
+        ("", merged_prompt.as_str())
+    } else {
+        (system_prompt, user_prompt)
+    };
+
+    let mut messages = Vec::with_capacity(examples.len() * 2 + 2);
+    if !system_prompt.is_empty() {
+        messages.push(ChatMessage {
+            role: "system".to_string(),
+            content: system_prompt.to_string(),
+        });
+    }
+    messages.extend(chat_turns(examples, user_prompt));
     let openai_style_body = serde_json::json!(ChatRequest {
         model: model_name.to_string(),
-        messages: vec![
-            ChatMessage {
-                role: "system".to_string(),
-                content: system_prompt.to_string(),
-            },
-            ChatMessage {
-                role: "user".to_string(),
-                content: user_prompt.to_string(),
-            }
-        ],
+        messages,
+        stream,
     });
 
     let (api_url, mut headers, request_body, uses_bearer_auth) = match provider {
         LLMProvider::OpenAI => (
-            "https://api.openai.com/v1/chat/completions".to_string(),
+            format!(
+                "{}/chat/completions",
+                overrides.base_url_or("https://api.openai.com/v1".to_string())
+            ),
             header::HeaderMap::new(),
             openai_style_body.clone(),
             true,
         ),
         LLMProvider::Groq => (
-            "https://api.groq.com/openai/v1/chat/completions".to_string(),
+            format!(
+                "{}/chat/completions",
+                overrides.base_url_or("https://api.groq.com/openai/v1".to_string())
+            ),
             header::HeaderMap::new(),
             openai_style_body.clone(),
             true,
         ),
         LLMProvider::OpenRouter => (
-            "https://openrouter.ai/api/v1/chat/completions".to_string(),
+            format!(
+                "{}/chat/completions",
+                overrides.base_url_or("https://openrouter.ai/api/v1".to_string())
+            ),
             header::HeaderMap::new(),
             openai_style_body.clone(),
             true,
@@ -158,8 +316,9 @@ pub async fn generate_summary(
             let host = ollama_endpoint
                 .map(|s| s.to_string())
                 .unwrap_or_else(|| "http://localhost:11434".to_string());
+            bypass_proxy_for(&host);
             (
-                format!("{}/v1/chat/completions", host),
+                format!("{}/chat/completions", overrides.base_url_or(format!("{}/v1", host))),
                 header::HeaderMap::new(),
                 openai_style_body.clone(),
                 true,
@@ -180,39 +339,65 @@ pub async fn generate_summary(
                     .map_err(|_| "Invalid anthropic version".to_string())?,
             );
             (
-                "https://api.anthropic.com/v1/messages".to_string(),
+                format!(
+                    "{}/messages",
+                    overrides.base_url_or("https://api.anthropic.com/v1".to_string())
+                ),
                 header_map,
                 serde_json::json!(ClaudeRequest {
                     system: system_prompt.to_string(),
                     model: model_name.to_string(),
                     max_tokens: 2048,
-                    messages: vec![ChatMessage {
-                        role: "user".to_string(),
-                        content: user_prompt.to_string(),
-                    }]
+                    messages: chat_turns(examples, user_prompt),
+                    stream,
                 }),
                 false,
             )
         }
         LLMProvider::Gemini => (
-            format!(
-                "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
-                model_name, api_key
-            ),
+            if stream {
+                format!(
+                    "{}/models/{}:streamGenerateContent?alt=sse&key={}",
+                    overrides.base_url_or(GEMINI_BASE_URL.to_string()),
+                    model_name,
+                    api_key
+                )
+            } else {
+                format!(
+                    "{}/models/{}:generateContent?key={}",
+                    overrides.base_url_or(GEMINI_BASE_URL.to_string()),
+                    model_name,
+                    api_key
+                )
+            },
             header::HeaderMap::new(),
-            serde_json::json!({
-                "system_instruction": {
-                    "parts": [{
-                        "text": system_prompt
-                    }]
-                },
-                "contents": [{
-                    "role": "user",
-                    "parts": [{
-                        "text": user_prompt
-                    }]
-                }]
-            }),
+            {
+                // Gemini calls the assistant turns "model"
+                let contents: Vec<serde_json::Value> = chat_turns(examples, user_prompt)
+                    .into_iter()
+                    .map(|turn| {
+                        let role = match turn.role.as_str() {
+                            "assistant" => "model",
+                            _ => "user",
+                        };
+                        serde_json::json!({
+                            "role": role,
+                            "parts": [{
+                                "text": turn.content
+                            }]
+                        })
+                    })
+                    .collect();
+                let mut body = serde_json::json!({ "contents": contents });
+                if !system_prompt.is_empty() {
+                    body["system_instruction"] = serde_json::json!({
+                        "parts": [{
+                            "text": system_prompt
+                        }]
+                    });
+                }
+                body
+            },
             false,
         ),
     };
@@ -231,39 +416,213 @@ pub async fn generate_summary(
             .parse()
             .map_err(|_| "Invalid content type".to_string())?,
     );
+    for (name, value) in &overrides.headers {
+        let name = header::HeaderName::from_bytes(name.as_bytes())
+            .map_err(|_| format!("Invalid override header name '{}'", name))?;
+        let mut value = header::HeaderValue::from_str(value)
+            .map_err(|_| format!("Invalid value for override header '{}'", name))?;
+        value.set_sensitive(true);
+        headers.insert(name, value);
+    }
 
-    info!("🐞 LLM Request to {}: model={}, url={}", provider_name(provider), model_name, api_url);
-    let request_start = std::time::Instant::now();
-    let api_url_clone = api_url.clone(); // Clone for error message
+    Ok((api_url, headers, request_body))
+}
 
-    // Send request with timeout logging
-    let response = client
-        .post(&api_url)
+/// The user message for models that get no system turn: the instructions
+/// first, then the content they apply to
+fn merge_system_prompt(system_prompt: &str, user_prompt: &str) -> String {
+    if system_prompt.trim().is_empty() {
+        return user_prompt.to_string();
+    }
+    format!("{}\n\n{}", system_prompt.trim_end(), user_prompt)
+}
+
+/// A single chat request for `provider`, built like the summary requests
+/// but sent by the caller, without retries
+pub(crate) fn completion_request(
+    client: &Client,
+    provider: &LLMProvider,
+    model_name: &str,
+    api_key: &str,
+    system_prompt: &str,
+    user_prompt: &str,
+    ollama_endpoint: Option<&str>,
+    overrides: &ProviderOverride,
+) -> Result<reqwest::RequestBuilder, String> {
+    let (api_url, headers, request_body) = build_request(
+        provider,
+        model_name,
+        api_key,
+        system_prompt,
+        user_prompt,
+        &[],
+        ollama_endpoint,
+        false,
+        overrides,
+    )?;
+    Ok(client.post(api_url).headers(headers).json(&request_body))
+}
+
+/// Sends a request, failing on a non-success status
+async fn send_request(
+    client: &Client,
+    api_url: &str,
+    headers: header::HeaderMap,
+    request_body: &serde_json::Value,
+) -> Result<reqwest::Response, LlmError> {
+    let request_start = std::time::Instant::now();
+    match client
+        .post(api_url)
         .headers(headers)
-        .json(&request_body)
+        .json(request_body)
         .send()
         .await
-        .map_err(|e| {
+    {
+        Ok(response) if response.status().is_success() => {
+            info!(
+                "🐞 LLM Request sent, waiting for response (elapsed: {}s)...",
+                request_start.elapsed().as_secs()
+            );
+            Ok(response)
+        }
+        Ok(response) => {
+            let status = response.status();
+            let error_body = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            let message = format!(
+                "{}{}): {}",
+                REQUEST_FAILED_PREFIX,
+                status.as_u16(),
+                error_body
+            );
+            if status.as_u16() == 429 || status.is_server_error() {
+                Err(LlmError::Unavailable(message))
+            } else {
+                Err(LlmError::Failed(message))
+            }
+        }
+        Err(e) => {
             let elapsed = request_start.elapsed().as_secs();
-            format!("Failed to send request to LLM after {}s: {} (URL: {})", elapsed, e, api_url_clone)
-        })?;
-    
-    let request_elapsed = request_start.elapsed().as_secs();
-    info!("🐞 LLM Request sent, waiting for response (elapsed: {}s)...", request_elapsed);
-
-    if !response.status().is_success() {
-        let error_body = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Unknown error".to_string());
-        return Err(format!("LLM API request failed: {}", error_body));
+            let message = format!(
+                "{} after {}s: {} (URL: {})",
+                SEND_FAILED_PREFIX, elapsed, e, api_url
+            );
+            if e.is_timeout() {
+                Err(LlmError::TimedOut(message))
+            } else {
+                Err(LlmError::Unavailable(message))
+            }
+        }
     }
+}
 
+/// Generates a summary using the specified LLM provider
+///
+/// # Arguments
+/// * `client` - Reqwest HTTP client (reused for performance)
+/// * `provider` - The LLM provider to use
+/// * `model_name` - The specific model to use (e.g., "gpt-4", "claude-3-opus")
+/// * `api_key` - API key for the provider (not needed for Ollama)
+/// * `system_prompt` - System instructions for the LLM
+/// * `user_prompt` - User query/content to process
+/// * `ollama_endpoint` - Optional custom Ollama endpoint (defaults to localhost:11434)
+///
+/// # Returns
+/// The generated summary text or an error message
+pub async fn generate_summary(
+    client: &Client,
+    provider: &LLMProvider,
+    model_name: &str,
+    api_key: &str,
+    system_prompt: &str,
+    user_prompt: &str,
+    ollama_endpoint: Option<&str>,
+) -> Result<String, LlmError> {
+    generate_completion(
+        client,
+        provider,
+        model_name,
+        api_key,
+        system_prompt,
+        user_prompt,
+        ollama_endpoint,
+    )
+    .await
+    .map(|completion| completion.text)
+}
+
+/// Like `generate_summary`, also returning why the provider stopped
+pub async fn generate_completion(
+    client: &Client,
+    provider: &LLMProvider,
+    model_name: &str,
+    api_key: &str,
+    system_prompt: &str,
+    user_prompt: &str,
+    ollama_endpoint: Option<&str>,
+) -> Result<LlmCompletion, LlmError> {
+    generate_completion_with_examples(
+        client,
+        provider,
+        model_name,
+        api_key,
+        system_prompt,
+        user_prompt,
+        &[],
+        ollama_endpoint,
+    )
+    .await
+}
+
+/// Like `generate_completion`, with `examples` sent ahead of the user prompt
+#[allow(clippy::too_many_arguments)]
+pub async fn generate_completion_with_examples(
+    client: &Client,
+    provider: &LLMProvider,
+    model_name: &str,
+    api_key: &str,
+    system_prompt: &str,
+    user_prompt: &str,
+    examples: &[FewShotExample],
+    ollama_endpoint: Option<&str>,
+) -> Result<LlmCompletion, LlmError> {
+    let (api_url, headers, request_body) = build_request(
+        provider,
+        model_name,
+        api_key,
+        system_prompt,
+        user_prompt,
+        examples,
+        ollama_endpoint,
+        false,
+        &override_for(provider),
+    )?;
+
+    info!("🐞 LLM Request to {}: model={}, url={}", provider_name(provider), model_name, api_url);
+    let response = send_request(client, &api_url, headers, &request_body).await?;
+    let response_text = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read LLM response: {}", e))?;
+
+    let completion = parse_completion(provider, &response_text)?;
+    if completion.truncated() {
+        warn!(
+            "⚠️ {} stopped at its output limit (finish reason: {}); the response may be incomplete",
+            provider_name(provider),
+            completion.finish_reason.as_deref().unwrap_or_default()
+        );
+    }
+    Ok(completion)
+}
+
+/// Reads the text and finish reason out of a non-streamed response body
+pub(crate) fn parse_completion(provider: &LLMProvider, response_text: &str) -> Result<LlmCompletion, String> {
     // Parse response based on provider
     if provider == &LLMProvider::Claude {
-        let chat_response = response
-            .json::<ClaudeChatResponse>()
-            .await
+        let chat_response: ClaudeChatResponse = serde_json::from_str(response_text)
             .map_err(|e| format!("Failed to parse LLM response: {}", e))?;
 
         info!("🐞 LLM Response received from Claude");
@@ -274,18 +633,16 @@ pub async fn generate_summary(
             .ok_or("No content in LLM response")?
             .text
             .trim();
-        Ok(content.to_string())
+        Ok(LlmCompletion {
+            text: content.to_string(),
+            finish_reason: chat_response.stop_reason,
+        })
     } else if provider == &LLMProvider::Gemini {
-        let response_text = response
-            .text()
-            .await
-            .map_err(|e| format!("Failed to read Gemini response text: {}", e))?;
-        
         info!("🐞 Gemini raw response length: {} chars", response_text.len());
-        info!("🐞 Gemini raw response preview: {}", &response_text.chars().take(500).collect::<String>());
+        info!("🐞 Gemini raw response preview: {}", log_preview(response_text));
         
-        let gemini_response: GeminiResponse = serde_json::from_str(&response_text)
-            .map_err(|e| format!("Failed to parse Gemini response JSON: {}. Response preview: {}", e, &response_text.chars().take(200).collect::<String>()))?;
+        let gemini_response: GeminiResponse = serde_json::from_str(response_text)
+            .map_err(|e| format!("Failed to parse Gemini response JSON: {}. Response preview: {}", e, log_preview(response_text)))?;
 
         info!("🐞 LLM Response received from Gemini, candidates count: {}", gemini_response.candidates.len());
 
@@ -309,26 +666,172 @@ pub async fn generate_summary(
 
         let full_content = all_text_parts.join("");
         info!("🐞 Gemini final content length: {} chars", full_content.len());
-        info!("🐞 Gemini final content preview: {}", &full_content.chars().take(500).collect::<String>());
+        info!("🐞 Gemini final content preview: {}", log_preview(&full_content));
 
-        Ok(full_content.trim().to_string())
+        // A truncated candidate anywhere makes the joined text incomplete
+        let finish_reason = gemini_response
+            .candidates
+            .iter()
+            .filter_map(|candidate| candidate.finish_reason.clone())
+            .find(|reason| is_truncation_reason(reason))
+            .or_else(|| {
+                gemini_response
+                    .candidates
+                    .iter()
+                    .find_map(|candidate| candidate.finish_reason.clone())
+            });
+        Ok(LlmCompletion {
+            text: full_content.trim().to_string(),
+            finish_reason,
+        })
     } else {
-        let chat_response = response
-            .json::<ChatResponse>()
-            .await
+        let chat_response: ChatResponse = serde_json::from_str(response_text)
             .map_err(|e| format!("Failed to parse LLM response: {}", e))?;
 
         info!("🐞 LLM Response received from {}", provider_name(provider));
 
-        let content = chat_response
+        let choice = chat_response
             .choices
-            .get(0)
-            .ok_or("No content in LLM response")?
-            .message
-            .content
-            .trim();
-        Ok(content.to_string())
+            .into_iter()
+            .next()
+            .ok_or("No content in LLM response")?;
+        Ok(LlmCompletion {
+            text: choice.message.content.trim().to_string(),
+            finish_reason: choice.finish_reason,
+        })
+    }
+}
+
+/// Text delta carried by one server-sent event line, if any
+///
+/// Handles the OpenAI-compatible format (OpenAI, Groq, OpenRouter, Ollama),
+/// Claude's `content_block_delta` events and Gemini's streamed candidates.
+/// Error events are returned as errors.
+fn parse_stream_line(provider: &LLMProvider, line: &str) -> Result<Option<String>, String> {
+    let Some(data) = line.strip_prefix("data:").map(str::trim) else {
+        return Ok(None);
+    };
+    if data.is_empty() || data == "[DONE]" {
+        return Ok(None);
+    }
+    let event: serde_json::Value = serde_json::from_str(data)
+        .map_err(|e| format!("Failed to parse LLM stream event: {}", e))?;
+    if let Some(error) = event.get("error").filter(|e| !e.is_null()) {
+        return Err(format!("LLM stream returned an error: {}", error));
+    }
+
+    let text = match provider {
+        LLMProvider::Claude => (event["type"] == "content_block_delta")
+            .then(|| event["delta"]["text"].as_str().map(str::to_string))
+            .flatten(),
+        LLMProvider::Gemini => {
+            let parts: String = event["candidates"][0]["content"]["parts"]
+                .as_array()
+                .map(|parts| parts.iter().filter_map(|p| p["text"].as_str()).collect())
+                .unwrap_or_default();
+            Some(parts)
+        }
+        _ => event["choices"][0]["delta"]["content"]
+            .as_str()
+            .map(str::to_string),
+    };
+    Ok(text.filter(|t| !t.is_empty()))
+}
+
+/// Finish reason carried by one server-sent event line, if any
+///
+/// OpenAI-compatible streams send it on the last choice delta, Claude in its
+/// `message_delta` event and Gemini on the final candidate.
+fn parse_stream_finish_reason(provider: &LLMProvider, line: &str) -> Option<String> {
+    let data = line.strip_prefix("data:").map(str::trim)?;
+    let event: serde_json::Value = serde_json::from_str(data).ok()?;
+    let reason = match provider {
+        LLMProvider::Claude => (event["type"] == "message_delta")
+            .then(|| event["delta"]["stop_reason"].as_str())
+            .flatten(),
+        LLMProvider::Gemini => event["candidates"][0]["finishReason"].as_str(),
+        _ => event["choices"][0]["finish_reason"].as_str(),
+    };
+    reason.map(str::to_string)
+}
+
+/// Like `generate_completion`, but streams the response and passes each
+/// text delta to `on_delta` as it arrives
+#[allow(clippy::too_many_arguments)]
+pub async fn generate_summary_streaming<F: FnMut(&str) + Send>(
+    client: &Client,
+    provider: &LLMProvider,
+    model_name: &str,
+    api_key: &str,
+    system_prompt: &str,
+    user_prompt: &str,
+    examples: &[FewShotExample],
+    ollama_endpoint: Option<&str>,
+    mut on_delta: F,
+) -> Result<LlmCompletion, LlmError> {
+    let (api_url, headers, request_body) = build_request(
+        provider,
+        model_name,
+        api_key,
+        system_prompt,
+        user_prompt,
+        examples,
+        ollama_endpoint,
+        true,
+        &override_for(provider),
+    )?;
+
+    info!(
+        "🐞 Streaming LLM Request to {}: model={}",
+        provider_name(provider),
+        model_name
+    );
+    let mut response = send_request(client, &api_url, headers, &request_body).await?;
+
+    let mut content = String::new();
+    let mut finish_reason = None;
+    // Events can be split across chunks, including inside a UTF-8 character
+    let mut pending: Vec<u8> = Vec::new();
+    loop {
+        let chunk = response
+            .chunk()
+            .await
+            .map_err(|e| format!("Failed to read LLM stream: {}", e))?;
+        let done = chunk.is_none();
+        if let Some(chunk) = chunk {
+            pending.extend_from_slice(&chunk);
+        } else if !pending.is_empty() {
+            pending.push(b'\n');
+        }
+        while let Some(newline) = pending.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = pending.drain(..=newline).collect();
+            let line = String::from_utf8_lossy(&line);
+            if let Some(delta) = parse_stream_line(provider, line.trim())? {
+                on_delta(&delta);
+                content.push_str(&delta);
+            }
+            if let Some(reason) = parse_stream_finish_reason(provider, line.trim()) {
+                finish_reason = Some(reason);
+            }
+        }
+        if done {
+            break;
+        }
+    }
+
+    if content.trim().is_empty() {
+        return Err("No content in streamed LLM response".into());
     }
+    info!(
+        "🐞 Streamed LLM Response received from {}: {} chars, finish reason: {:?}",
+        provider_name(provider),
+        content.len(),
+        finish_reason
+    );
+    Ok(LlmCompletion {
+        text: content.trim().to_string(),
+        finish_reason,
+    })
 }
 
 /// Helper function to get provider name for logging
@@ -342,3 +845,267 @@ fn provider_name(provider: &LLMProvider) -> &str {
         LLMProvider::Gemini => "Gemini",
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_lines_yield_text_deltas() {
+        let openai = LLMProvider::OpenAI;
+        assert_eq!(
+            parse_stream_line(&openai, r###"data: {"choices":[{"delta":{"content":"## Sum"}}]}"###),
+            Ok(Some("## Sum".to_string()))
+        );
+        // Role-only first delta, keep-alive comments and the end marker carry no text
+        assert_eq!(
+            parse_stream_line(&openai, r#"data: {"choices":[{"delta":{"role":"assistant"}}]}"#),
+            Ok(None)
+        );
+        assert_eq!(parse_stream_line(&openai, ": keep-alive"), Ok(None));
+        assert_eq!(parse_stream_line(&openai, "data: [DONE]"), Ok(None));
+
+        let claude = LLMProvider::Claude;
+        assert_eq!(
+            parse_stream_line(
+                &claude,
+                r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"mary"}}"#
+            ),
+            Ok(Some("mary".to_string()))
+        );
+        assert_eq!(
+            parse_stream_line(&claude, r#"data: {"type":"message_stop"}"#),
+            Ok(None)
+        );
+        assert!(parse_stream_line(
+            &claude,
+            r#"data: {"type":"error","error":{"type":"overloaded_error"}}"#
+        )
+        .is_err());
+
+        assert_eq!(
+            parse_stream_line(
+                &LLMProvider::Gemini,
+                r#"data: {"candidates":[{"content":{"parts":[{"text":"Launch "},{"text":"moved"}]}}]}"#
+            ),
+            Ok(Some("Launch moved".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_finish_reasons_are_parsed_per_provider() {
+        let openai = parse_completion(
+            &LLMProvider::OpenAI,
+            r###"{"id":"chatcmpl-1","object":"chat.completion","choices":[{"index":0,"message":{"role":"assistant","content":" ## Summary\nShip it "},"finish_reason":"length"}],"usage":{"completion_tokens":4096}}"###,
+        )
+        .unwrap();
+        assert_eq!(openai.text, "## Summary\nShip it");
+        assert_eq!(openai.finish_reason.as_deref(), Some("length"));
+        assert!(openai.truncated());
+
+        let claude = parse_completion(
+            &LLMProvider::Claude,
+            r#"{"id":"msg_1","type":"message","role":"assistant","content":[{"type":"text","text":"Done."}],"stop_reason":"end_turn","stop_sequence":null}"#,
+        )
+        .unwrap();
+        assert_eq!(claude.finish_reason.as_deref(), Some("end_turn"));
+        assert!(!claude.truncated());
+        let claude = parse_completion(
+            &LLMProvider::Claude,
+            r#"{"content":[{"type":"text","text":"Cut"}],"stop_reason":"max_tokens"}"#,
+        )
+        .unwrap();
+        assert!(claude.truncated());
+
+        let gemini = parse_completion(
+            &LLMProvider::Gemini,
+            r#"{"candidates":[{"content":{"parts":[{"text":"Launch "},{"text":"moved"}],"role":"model"},"finishReason":"MAX_TOKENS","index":0}],"usageMetadata":{"totalTokenCount":8192}}"#,
+        )
+        .unwrap();
+        assert_eq!(gemini.text, "Launch moved");
+        assert!(gemini.truncated());
+        let gemini = parse_completion(
+            &LLMProvider::Gemini,
+            r#"{"candidates":[{"content":{"parts":[{"text":"All good"}]},"finishReason":"STOP"}]}"#,
+        )
+        .unwrap();
+        assert!(!gemini.truncated());
+
+        // Older or minimal payloads without a reason are not treated as truncated
+        let ollama = parse_completion(
+            &LLMProvider::Ollama,
+            r#"{"choices":[{"message":{"content":"Hi"}}]}"#,
+        )
+        .unwrap();
+        assert_eq!(ollama.finish_reason, None);
+        assert!(!ollama.truncated());
+
+        // Streams report the reason on their final events
+        assert_eq!(
+            parse_stream_finish_reason(
+                &LLMProvider::OpenAI,
+                r#"data: {"choices":[{"delta":{},"finish_reason":"length"}]}"#
+            ),
+            Some("length".to_string())
+        );
+        assert_eq!(
+            parse_stream_finish_reason(
+                &LLMProvider::OpenAI,
+                r#"data: {"choices":[{"delta":{"content":"x"},"finish_reason":null}]}"#
+            ),
+            None
+        );
+        assert_eq!(
+            parse_stream_finish_reason(
+                &LLMProvider::Claude,
+                r#"data: {"type":"message_delta","delta":{"stop_reason":"max_tokens","stop_sequence":null},"usage":{"output_tokens":4096}}"#
+            ),
+            Some("max_tokens".to_string())
+        );
+        assert_eq!(
+            parse_stream_finish_reason(
+                &LLMProvider::Gemini,
+                r#"data: {"candidates":[{"content":{"parts":[{"text":"."}]},"finishReason":"STOP"}]}"#
+            ),
+            Some("STOP".to_string())
+        );
+        assert_eq!(parse_stream_finish_reason(&LLMProvider::OpenAI, "data: [DONE]"), None);
+    }
+
+    #[test]
+    fn test_overrides_win_over_provider_defaults() {
+        let overrides = ProviderOverride {
+            base_url: Some("https://gateway.corp.example/llm/".to_string()),
+            headers: [
+                ("x-api-key", "gateway-key"),
+                ("Authorization", "Bearer gateway-token"),
+                ("X-Trace-Id", "trace-1"),
+            ]
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect(),
+            ..ProviderOverride::default()
+        };
+
+        let (url, headers, _) = build_request(
+            &LLMProvider::Claude, "claude-3", "user-key", "sys", "user", &[], None, false, &overrides,
+        )
+        .unwrap();
+        assert_eq!(url, "https://gateway.corp.example/llm/messages");
+        assert_eq!(headers["x-api-key"], "gateway-key");
+        assert_eq!(headers["anthropic-version"], "2023-06-01");
+        assert_eq!(headers["x-trace-id"], "trace-1");
+
+        let (url, headers, _) = build_request(
+            &LLMProvider::OpenAI, "gpt-4", "user-key", "sys", "user", &[], None, true, &overrides,
+        )
+        .unwrap();
+        assert_eq!(url, "https://gateway.corp.example/llm/chat/completions");
+        assert_eq!(headers.get_all(header::AUTHORIZATION).iter().count(), 1);
+        assert_eq!(headers[header::AUTHORIZATION], "Bearer gateway-token");
+        let logged = format!("{:?}", headers);
+        assert!(!logged.contains("gateway-token") && !logged.contains("trace-1"), "{}", logged);
+
+        let none = ProviderOverride::default();
+        let (url, headers, _) = build_request(
+            &LLMProvider::OpenAI, "gpt-4", "user-key", "sys", "user", &[], None, false, &none,
+        )
+        .unwrap();
+        assert_eq!(url, "https://api.openai.com/v1/chat/completions");
+        assert_eq!(headers[header::AUTHORIZATION], "Bearer user-key");
+        let (url, _, _) = build_request(
+            &LLMProvider::Ollama, "llama3", "", "sys", "user", &[], Some("http://box:11434"), false, &none,
+        )
+        .unwrap();
+        assert_eq!(url, "http://box:11434/v1/chat/completions");
+    }
+
+    #[test]
+    fn test_flagged_models_get_the_system_prompt_in_the_user_turn() {
+        let system = "List every Action Item as a table row.";
+        let user = "Transcript: Sam will send the deck.";
+        let merged = ProviderOverride {
+            merged_system_prompt_models: ["llama2".to_string()].into_iter().collect(),
+            ..ProviderOverride::default()
+        };
+
+        let (_, _, body) = build_request(
+            &LLMProvider::Ollama, "llama2", "", system, user, &[], None, false, &merged,
+        )
+        .unwrap();
+        let messages = body["messages"].as_array().unwrap();
+        assert_eq!(messages.len(), 1);
+        assert!(messages.iter().all(|m| m["role"] != "system"));
+        assert_eq!(messages[0]["role"], "user");
+        assert_eq!(
+            messages[0]["content"],
+            format!("{}\n\n{}", system, user)
+        );
+
+        // Other models of the provider keep their system turn
+        let (_, _, body) = build_request(
+            &LLMProvider::Ollama, "llama3.1", "", system, user, &[], None, false, &merged,
+        )
+        .unwrap();
+        assert_eq!(body["messages"][0]["role"], "system");
+        assert_eq!(body["messages"][1]["content"], user);
+
+        // `*` flags every model, and providers with their own system field drop it
+        let all = ProviderOverride {
+            merged_system_prompt_models: ["*".to_string()].into_iter().collect(),
+            ..ProviderOverride::default()
+        };
+        let (_, _, body) = build_request(
+            &LLMProvider::Claude, "claude-3", "key", system, user, &[], None, false, &all,
+        )
+        .unwrap();
+        assert!(body.get("system").is_none());
+        assert!(body["messages"][0]["content"].as_str().unwrap().starts_with(system));
+        let (_, _, body) = build_request(
+            &LLMProvider::Gemini, "gemini-pro", "key", system, user, &[], None, false, &all,
+        )
+        .unwrap();
+        assert!(body.get("system_instruction").is_none());
+        assert!(body["contents"][0]["parts"][0]["text"]
+            .as_str()
+            .unwrap()
+            .starts_with(system));
+    }
+
+    #[test]
+    fn test_few_shot_examples_precede_the_user_turn() {
+        let examples = [FewShotExample {
+            user: "Sample transcript".to_string(),
+            assistant: "## Action Items\n\n| **Owner** | Task | Due |".to_string(),
+        }];
+        let none = ProviderOverride::default();
+
+        let (_, _, body) = build_request(
+            &LLMProvider::Ollama, "llama3.2:1b", "", "sys", "Transcript", &examples, None, false, &none,
+        )
+        .unwrap();
+        let roles: Vec<&str> = body["messages"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m["role"].as_str().unwrap())
+            .collect();
+        assert_eq!(roles, vec!["system", "user", "assistant", "user"]);
+        assert_eq!(body["messages"][2]["content"], examples[0].assistant);
+        assert_eq!(body["messages"][3]["content"], "Transcript");
+
+        let (_, _, body) = build_request(
+            &LLMProvider::Claude, "claude-3", "key", "sys", "Transcript", &examples, None, false, &none,
+        )
+        .unwrap();
+        assert_eq!(body["messages"].as_array().unwrap().len(), 3);
+        assert_eq!(body["messages"][1]["role"], "assistant");
+
+        let (_, _, body) = build_request(
+            &LLMProvider::Gemini, "gemini-pro", "key", "sys", "Transcript", &examples, None, false, &none,
+        )
+        .unwrap();
+        assert_eq!(body["contents"][1]["role"], "model");
+        assert_eq!(body["contents"][2]["parts"][0]["text"], "Transcript");
+    }
+}
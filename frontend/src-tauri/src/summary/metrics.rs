@@ -0,0 +1,277 @@
+//! Aggregate metrics over a user's summary runs
+//!
+//! Everything is computed in SQL over `summary_processes`, which keeps the
+//! latest run of each meeting. Models are compared by processing time per
+//! chunk, so meetings of different lengths can be set side by side.
+
+use crate::database::models::{SummaryFailureKind, SummaryModelMetrics, SummaryRunTotals};
+use crate::database::repositories::summary::SummaryProcessesRepository;
+use crate::summary::llm_client::{REQUEST_FAILED_PREFIX, SEND_FAILED_PREFIX};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::SqlitePool;
+
+/// Time span the metrics cover, counted back from now
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricsPeriod {
+    Week,
+    Month,
+    Year,
+    All,
+}
+
+impl MetricsPeriod {
+    /// Earliest end time of a run that is counted; None counts every run
+    pub fn since(self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let days = match self {
+            MetricsPeriod::Week => 7,
+            MetricsPeriod::Month => 30,
+            MetricsPeriod::Year => 365,
+            MetricsPeriod::All => return None,
+        };
+        Some(now - Duration::days(days))
+    }
+}
+
+/// What a failed summary run ran into, stored as `error_kind`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SummaryErrorKind {
+    /// Replaced by a newer run of the same meeting
+    Cancelled,
+    /// The provider couldn't be reached or the request timed out
+    Network,
+    RateLimited,
+    /// The provider answered with a 5xx
+    ServerError,
+    /// The provider rejected the API key (401/403)
+    Auth,
+    /// Any other HTTP error from the provider
+    RequestRejected,
+    /// The provider's response couldn't be read or was empty
+    BadResponse,
+    Other,
+}
+
+impl SummaryErrorKind {
+    /// Sorts an error message of the summary pipeline into a kind
+    pub fn classify(error: &str) -> Self {
+        if error.starts_with("Process cancelled") {
+            return SummaryErrorKind::Cancelled;
+        }
+        if error.contains(SEND_FAILED_PREFIX) {
+            return SummaryErrorKind::Network;
+        }
+        if let Some(pos) = error.find(REQUEST_FAILED_PREFIX) {
+            let status = &error[pos + REQUEST_FAILED_PREFIX.len()..];
+            return if status.starts_with("429") {
+                SummaryErrorKind::RateLimited
+            } else if status.starts_with('5') {
+                SummaryErrorKind::ServerError
+            } else if status.starts_with("401") || status.starts_with("403") {
+                SummaryErrorKind::Auth
+            } else {
+                SummaryErrorKind::RequestRejected
+            };
+        }
+        let bad_response = [
+            "Failed to parse",
+            "Failed to read LLM",
+            "No content",
+            "No text content",
+        ];
+        if bad_response.iter().any(|marker| error.contains(marker)) {
+            return SummaryErrorKind::BadResponse;
+        }
+        SummaryErrorKind::Other
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SummaryErrorKind::Cancelled => "cancelled",
+            SummaryErrorKind::Network => "network",
+            SummaryErrorKind::RateLimited => "rate_limited",
+            SummaryErrorKind::ServerError => "server_error",
+            SummaryErrorKind::Auth => "auth",
+            SummaryErrorKind::RequestRejected => "request_rejected",
+            SummaryErrorKind::BadResponse => "bad_response",
+            SummaryErrorKind::Other => "other",
+        }
+    }
+}
+
+/// Validation warnings stored in a summary result, stored as `warning_count`
+///
+/// None for results without the `validation_warnings` list.
+pub fn warning_count(result: &Value) -> Option<i64> {
+    result
+        .get("validation_warnings")
+        .and_then(Value::as_array)
+        .map(|warnings| warnings.len() as i64)
+}
+
+/// How the summary pipeline did over a period
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SummaryMetrics {
+    pub period: MetricsPeriod,
+    pub totals: SummaryRunTotals,
+    pub failures: Vec<SummaryFailureKind>,
+    /// Slowest model per chunk first
+    pub models: Vec<SummaryModelMetrics>,
+}
+
+/// Metrics of the runs that finished within `period` before `now`
+pub async fn summary_metrics(
+    pool: &SqlitePool,
+    period: MetricsPeriod,
+    now: DateTime<Utc>,
+) -> Result<SummaryMetrics, sqlx::Error> {
+    let since = period.since(now);
+    let totals = SummaryProcessesRepository::run_totals(pool, since).await?;
+    let failures = SummaryProcessesRepository::failure_kinds(pool, since, totals.runs).await?;
+    let models = SummaryProcessesRepository::model_metrics(pool, since).await?;
+    Ok(SummaryMetrics {
+        period,
+        totals,
+        failures,
+        models,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::{seed_meetings, test_pool};
+    use serde_json::json;
+
+    fn completed(provider: &str, model: &str, warnings: usize) -> Value {
+        let warnings = vec!["Found placeholder 'TBD'"; warnings];
+        json!({
+            "markdown": "## Summary",
+            "generated_by": { "provider": provider, "model": model },
+            "validation_warnings": warnings,
+        })
+    }
+
+    /// Six meetings: llama3.2 takes 4x longer per chunk than Groq, two runs
+    /// failed and one run finished long before the others
+    async fn fixture() -> SqlitePool {
+        let pool = test_pool().await;
+        seed_meetings(&pool, 6, 0).await;
+        let runs = [
+            ("meeting-0", Some(("ollama", "llama3.2:latest", 1)), 4, 80.0),
+            ("meeting-1", Some(("ollama", "llama3.2:latest", 3)), 2, 40.0),
+            ("meeting-2", Some(("groq", "llama-3.3-70b", 0)), 4, 20.0),
+            ("meeting-3", None, 0, 0.0),
+            ("meeting-4", None, 0, 0.0),
+            ("meeting-5", Some(("groq", "llama-3.3-70b", 2)), 1, 5.0),
+        ];
+        for (meeting_id, generated, chunks, seconds) in runs {
+            SummaryProcessesRepository::create_or_reset_process(&pool, meeting_id)
+                .await
+                .unwrap();
+            if let Some((provider, model, warnings)) = generated {
+                let result = completed(provider, model, warnings);
+                SummaryProcessesRepository::update_process_completed(
+                    &pool, meeting_id, result, chunks, seconds,
+                )
+                .await
+                .unwrap();
+            }
+        }
+        let errors = [
+            ("meeting-3", "LLM API request failed (HTTP 429 Too Many Requests): slow down"),
+            ("meeting-4", "Failed to send request to LLM after 30s: timed out"),
+        ];
+        for (meeting_id, error) in errors {
+            SummaryProcessesRepository::update_process_failed(&pool, meeting_id, error)
+                .await
+                .unwrap();
+        }
+        sqlx::query("UPDATE summary_processes SET end_time = ? WHERE meeting_id = 'meeting-5'")
+            .bind(Utc::now() - Duration::days(60))
+            .execute(&pool)
+            .await
+            .unwrap();
+        pool
+    }
+
+    #[test]
+    fn test_classify_errors() {
+        let cases = [
+            ("Process cancelled: New regeneration started", "cancelled"),
+            ("Failed to send request to LLM after 3s: connection refused (URL: x)", "network"),
+            ("LLM API request failed (HTTP 429 Too Many Requests): slow down", "rate_limited"),
+            ("LLM API request failed (HTTP 503 Service Unavailable): busy", "server_error"),
+            ("LLM API request failed (HTTP 401 Unauthorized): bad key", "auth"),
+            ("LLM API request failed (HTTP 400 Bad Request): too long", "request_rejected"),
+            ("Failed to parse LLM response: expected value", "bad_response"),
+            ("Transcript text is empty", "other"),
+        ];
+        for (error, kind) in cases {
+            assert_eq!(SummaryErrorKind::classify(error).as_str(), kind, "{}", error);
+        }
+        assert_eq!(warning_count(&completed("groq", "m", 2)), Some(2));
+        assert_eq!(warning_count(&json!({ "markdown": "" })), None);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_over_all_runs() {
+        let pool = fixture().await;
+        let metrics = summary_metrics(&pool, MetricsPeriod::All, Utc::now()).await.unwrap();
+
+        assert_eq!(
+            metrics.totals,
+            SummaryRunTotals {
+                runs: 6,
+                completed: 4,
+                failed: 2,
+                failure_rate: 2.0 / 6.0,
+                avg_chunks: Some(11.0 / 4.0),
+                avg_processing_time: Some(145.0 / 4.0),
+                avg_warning_count: Some(6.0 / 4.0),
+            }
+        );
+        let failures: Vec<(&str, i64)> = metrics
+            .failures
+            .iter()
+            .map(|failure| (failure.kind.as_str(), failure.count))
+            .collect();
+        assert_eq!(failures, vec![("network", 1), ("rate_limited", 1)]);
+        assert_eq!(metrics.failures[0].rate, 1.0 / 6.0);
+
+        let [llama, groq] = metrics.models.as_slice() else {
+            panic!("expected two models: {:?}", metrics.models);
+        };
+        assert_eq!((llama.model.as_str(), llama.runs), ("llama3.2:latest", 2));
+        assert_eq!(llama.avg_seconds_per_chunk, 20.0);
+        assert_eq!(llama.avg_warning_count, Some(2.0));
+        assert_eq!(llama.relative_time, Some(4.0));
+        assert_eq!((groq.provider.as_str(), groq.runs), ("groq", 2));
+        assert_eq!(groq.avg_seconds_per_chunk, 5.0);
+        assert_eq!(groq.relative_time, Some(1.0));
+    }
+
+    #[tokio::test]
+    async fn test_metrics_period_leaves_out_older_runs() {
+        let pool = fixture().await;
+        let metrics = summary_metrics(&pool, MetricsPeriod::Month, Utc::now()).await.unwrap();
+
+        assert_eq!((metrics.totals.runs, metrics.totals.completed), (5, 3));
+        let groq = metrics
+            .models
+            .iter()
+            .find(|model| model.provider == "groq")
+            .unwrap();
+        assert_eq!((groq.runs, groq.avg_processing_time), (1, 20.0));
+
+        // No runs at all still gives a report
+        let empty = test_pool().await;
+        let metrics = summary_metrics(&empty, MetricsPeriod::Week, Utc::now()).await.unwrap();
+        assert_eq!(metrics.totals.runs, 0);
+        assert_eq!(metrics.totals.failure_rate, 0.0);
+        assert_eq!(metrics.totals.avg_chunks, None);
+        assert!(metrics.failures.is_empty() && metrics.models.is_empty());
+    }
+}
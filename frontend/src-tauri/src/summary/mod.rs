@@ -2,18 +2,65 @@
 ///
 /// This module contains:
 /// - LLM client for communicating with various AI providers (OpenAI, Claude, Groq, Ollama, OpenRouter)
+/// - Per-provider base URL and header overrides for corporate proxies
 /// - Processor for chunking transcripts and generating summaries
+/// - Settings for how chunk summaries of long transcripts are merged
+/// - Named pipelines bundling settings switched together, e.g. "Quick" and "Detailed"
+/// - Optional choice of provider by transcript size, e.g. local for short meetings
+/// - Per-meeting background context added to prompts as reference material
+/// - Custom prompts stripped of prompt tags and cut to a token budget
+/// - Parsing of the Action Items table into typed items
+/// - Optional structured (JSON) reports validated against a fixed schema
+/// - Optional few-shot example exchange showing small models the template layout
+/// - Shared markdown table helpers, also used to clean up edited summaries
+/// - Optional language filter for summaries shared externally
+/// - Optional anonymization replacing participant names with role pseudonyms
+/// - Optional provenance markers linking summary statements to transcript chunks
+/// - Optional `[t=mm:ss]` citations checked against transcript segment times
+/// - Lookup of the summary's quotes and factual claims in the transcript
+/// - Removal of near-duplicate transcript segments before summarization
+/// - Progress estimates for streamed single-pass summaries
+/// - A 0-100 quality score stored with each generated summary
+/// - Aggregate metrics over past summary runs for diagnostics
+/// - Live drafts updated incrementally while a meeting is recorded
+/// - Configurable wording and examples of the clarifying question prompt
+/// - Holding back clarifying question popups while the user presents
+/// - Tracking of clarifying questions sent to the meeting chat
 /// - Service layer for orchestrating summary generation
 /// - Templates for structured meeting summary generation
 /// - Tauri commands for frontend integration
 
+pub mod action_items;
+pub mod anonymize;
+pub mod citations;
 pub mod commands;
+pub mod content_filter;
+pub mod context;
+pub mod few_shot;
+pub mod live;
 pub mod llm_client;
+pub mod metrics;
+pub mod named_pipelines;
+pub mod pipeline_config;
 pub mod processor;
+pub mod progress;
+pub mod provenance;
+pub mod provider_overrides;
+pub mod quality;
+pub mod quote_check;
+pub mod queue;
+pub mod routing;
+pub mod segment_dedup;
 pub mod service;
+pub mod structured;
+pub mod table;
 pub mod template_commands;
 pub mod templates;
+pub mod tickets;
+pub mod question_delivery;
 pub mod question_generator;
+pub mod question_prompt;
+pub mod question_suppression;
 
 // Re-export Tauri commands (with their generated __cmd__ variants)
 pub use commands::{
@@ -30,7 +77,7 @@ pub use template_commands::{
 // Re-export commonly used items
 pub use llm_client::LLMProvider;
 pub use processor::{
-    chunk_text, clean_llm_markdown_output, extract_meeting_name_from_markdown,
-    generate_meeting_summary, rough_token_count,
+    chunk_text, clean_llm_markdown_output, compact_transcript, extract_meeting_name_from_markdown,
+    generate_meeting_summary, rough_token_count, strip_chunk_artifacts, ChunkMarkers,
 };
 pub use service::SummaryService;
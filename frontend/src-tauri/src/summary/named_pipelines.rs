@@ -0,0 +1,175 @@
+//! Applying named pipelines to summary requests
+//!
+//! A named pipeline bundles settings users switch together. Settings the
+//! request gives explicitly win over the pipeline's; what neither gives is
+//! left to the global settings: the meeting's preferences and model config
+//! for the template and model, the summary pipeline config for refinement
+//! and structured output, and off for the remaining options.
+
+use crate::database::models::{NamedPipeline, PipelineOutputFormat};
+use crate::summary::pipeline_config::SummaryPipelineConfig;
+use crate::summary::processor::ChunkMerge;
+
+/// Settings of a summary request; None leaves a setting to the pipeline
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RequestedSummarySettings {
+    pub template_id: Option<String>,
+    pub model_provider: Option<String>,
+    pub model_name: Option<String>,
+    pub compact_transcript: Option<bool>,
+    pub provenance: Option<bool>,
+    pub citations: Option<bool>,
+    pub anonymize: Option<bool>,
+    pub skim: Option<bool>,
+}
+
+fn given(value: Option<String>) -> Option<String> {
+    value.filter(|v| !v.trim().is_empty())
+}
+
+impl RequestedSummarySettings {
+    /// Fills the settings the request leaves open from `pipeline`
+    pub fn with_pipeline(self, pipeline: &NamedPipeline) -> Self {
+        let model_provider = given(self.model_provider);
+        let model_name = given(self.model_name);
+        // The model is taken as a pair, so an explicit provider never gets
+        // the pipeline's model of another provider
+        let (model_provider, model_name) = if model_provider.is_none() && model_name.is_none() {
+            (pipeline.model_provider.clone(), pipeline.model_name.clone())
+        } else {
+            (model_provider, model_name)
+        };
+        Self {
+            template_id: given(self.template_id).or_else(|| pipeline.template_id.clone()),
+            model_provider,
+            model_name,
+            compact_transcript: self.compact_transcript.or(pipeline.compact_transcript),
+            provenance: self.provenance.or(pipeline.provenance),
+            citations: self.citations.or(pipeline.citations),
+            anonymize: self.anonymize.or(pipeline.anonymize),
+            skim: self.skim.or(pipeline
+                .output_format
+                .map(|format| format == PipelineOutputFormat::Skim)),
+        }
+    }
+}
+
+/// Overrides the global refinement and structured output settings with the
+/// ones `pipeline` sets
+pub fn apply_to_config(pipeline: &NamedPipeline, config: &mut SummaryPipelineConfig) {
+    if let Some(refine) = pipeline.refine {
+        config.chunk_merge = if refine {
+            ChunkMerge::Combine
+        } else {
+            ChunkMerge::Concatenate
+        };
+    }
+    if let Some(format) = pipeline.output_format {
+        config.structured_output = format == PipelineOutputFormat::Structured;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::repositories::Repositories;
+    use crate::database::test_utils::segment;
+    use crate::summary::service::SummaryService;
+
+    fn pipeline() -> NamedPipeline {
+        NamedPipeline {
+            id: "pipeline-1".to_string(),
+            name: "Retro".to_string(),
+            template_id: Some("retrospective".to_string()),
+            model_provider: Some("ollama".to_string()),
+            model_name: Some("llama3.2:latest".to_string()),
+            compact_transcript: Some(true),
+            provenance: None,
+            citations: Some(true),
+            anonymize: None,
+            refine: Some(false),
+            output_format: Some(PipelineOutputFormat::Skim),
+            temperature: Some(0.3),
+            max_tokens: None,
+            webhook_ids: Vec::new(),
+            built_in: false,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_explicit_settings_win_over_pipeline_over_global() {
+        let repos = Repositories::in_memory();
+        let segment = segment("s1", "Sam will fix the webhook by Friday", 0.0, 5.0);
+        let meeting_id = repos
+            .transcripts
+            .save_transcript("Retro", &[segment], None)
+            .await
+            .unwrap();
+        repos
+            .settings
+            .save_model_config("claude", "claude-3-5-sonnet-latest", "large-v3", None)
+            .await
+            .unwrap();
+        let resolve = |settings: RequestedSummarySettings| {
+            let (repos, meeting_id) = (&repos, &meeting_id);
+            async move {
+                SummaryService::resolve_settings(
+                    repos,
+                    meeting_id,
+                    settings.model_provider,
+                    settings.model_name,
+                    settings.template_id,
+                )
+                .await
+                .unwrap()
+            }
+        };
+
+        // The pipeline fills everything the request leaves open
+        let settings = RequestedSummarySettings::default().with_pipeline(&pipeline());
+        assert_eq!(settings.compact_transcript, Some(true));
+        assert_eq!(settings.provenance, None);
+        assert_eq!(settings.skim, Some(true));
+        let resolved = resolve(settings).await;
+        assert_eq!(resolved.model_provider, "ollama");
+        assert_eq!(resolved.template_id, "retrospective");
+
+        // Explicit values win; a provider alone doesn't take the pipeline's
+        // model, so the global config supplies it
+        let settings = RequestedSummarySettings {
+            template_id: Some("standard_meeting".to_string()),
+            model_provider: Some("claude".to_string()),
+            model_name: Some(String::new()),
+            skim: Some(false),
+            ..Default::default()
+        }
+        .with_pipeline(&pipeline());
+        assert_eq!(settings.skim, Some(false));
+        assert_eq!(settings.citations, Some(true));
+        let resolved = resolve(settings).await;
+        assert_eq!(resolved.model_provider, "claude");
+        assert_eq!(resolved.model_name, "claude-3-5-sonnet-latest");
+        assert_eq!(resolved.template_id, "standard_meeting");
+
+        // Settings the pipeline leaves open fall back to the global ones
+        let open = NamedPipeline {
+            model_provider: None,
+            model_name: None,
+            refine: None,
+            output_format: None,
+            ..pipeline()
+        };
+        let settings = RequestedSummarySettings::default().with_pipeline(&open);
+        assert_eq!(settings.skim, None);
+        assert_eq!(resolve(settings).await.model_provider, "claude");
+
+        let mut config = SummaryPipelineConfig::default();
+        apply_to_config(&open, &mut config);
+        assert_eq!(config, SummaryPipelineConfig::default());
+        apply_to_config(&pipeline(), &mut config);
+        assert_eq!(config.chunk_merge, ChunkMerge::Concatenate);
+        assert!(!config.structured_output);
+    }
+}
@@ -0,0 +1,329 @@
+use crate::summary::context::{DEFAULT_CONTEXT_SHARE, DEFAULT_USER_CONTEXT_TOKENS};
+use crate::summary::few_shot::FewShotMode;
+use crate::summary::processor::{
+    ChunkMarkers, ChunkMerge, ChunkSettings, DEFAULT_SMALL_MODEL_MAX_PARAMS_B,
+};
+use crate::summary::quality::QualityThresholds;
+use crate::summary::quote_check::QuoteCheckConfig;
+use crate::summary::question_generator::MIN_QUESTION_WINDOW_TOKENS;
+use crate::summary::routing::ProviderRoutingConfig;
+use crate::summary::segment_dedup::SegmentDedupConfig;
+use crate::summary::tickets::TicketCoverageConfig;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+
+const CONFIG_STORE: &str = "summary-pipeline.json";
+const CONFIG_KEY: &str = "config";
+
+/// Bounds of the configurable minimum chunk size
+const MIN_CHUNK_TOKENS_LOWER: usize = 128;
+const MIN_CHUNK_TOKENS_UPPER: usize = 8192;
+const MAX_PROMPT_OVERHEAD_TOKENS: usize = 4000;
+const MAX_CHUNKS_LIMIT: usize = 200;
+const DEFAULT_SKIM_MAX_BULLETS: usize = 5;
+const MAX_SKIM_BULLETS: usize = 20;
+const MAX_SMALL_MODEL_PARAMS_B: f64 = 100.0;
+const MIN_USER_CONTEXT_TOKENS: usize = 100;
+const MAX_USER_CONTEXT_TOKENS: usize = 32_000;
+
+/// How long transcripts are processed when they have to be chunked
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SummaryPipelineConfig {
+    /// `combine` merges chunk summaries in an extra call before the final
+    /// report; `concatenate` leaves the merging to the final report
+    pub chunk_merge: ChunkMerge,
+    /// Separator between chunk summaries and the tags wrapping transcript
+    /// text in prompts; leaked ones are stripped from the final summary
+    pub chunk_markers: ChunkMarkers,
+    /// Strip filler words and repeated words from transcripts before
+    /// summarizing, for requests and pipelines that don't set it themselves
+    pub compact_transcript: bool,
+    /// Largest share of the model's context window a meeting's background
+    /// context may take (0.0 - 1.0); the transcript always comes first
+    pub context_share: f64,
+    /// Tokens a request's custom prompt may take; longer ones are cut with a
+    /// "[context truncated]" marker and a warning in the summary result
+    pub max_user_context_tokens: usize,
+    /// Context window assumed for clarifying question prompts; None uses the
+    /// Ollama model's own window, or a default for cloud providers
+    pub question_window_tokens: Option<usize>,
+    /// Append each question prompt, raw response and result to
+    /// `question_debug.txt` in the app log directory
+    pub question_debug_log: bool,
+    /// Tokens each chunk repeats from the end of the one before it
+    pub chunk_overlap_tokens: usize,
+    /// Taken off the model's token threshold to leave room for the chunk prompt
+    pub prompt_overhead_tokens: usize,
+    /// Most chunks a transcript is split into; longer transcripts get larger
+    /// chunks instead
+    pub max_chunks: usize,
+    /// Smallest chunk a model's context has to leave room for; summaries
+    /// with smaller-context models fail instead of making tiny chunks. The
+    /// overlap has to stay below it so every chunk moves the window forward
+    pub min_chunk_tokens: usize,
+    /// Bullets kept per section in skim summaries
+    pub skim_max_bullets: usize,
+    /// Models with fewer parameters than this (in billions) get the
+    /// simplified small-model prompt; 0 turns it off
+    pub small_model_max_params_b: f64,
+    /// Scores rated good and ok in the summary quality indicator
+    pub quality_thresholds: QualityThresholds,
+    /// Ticket IDs looked for in the transcript and whether the ones the
+    /// summary misses are appended to it
+    pub ticket_coverage: TicketCoverageConfig,
+    /// Whether generated summaries' quotes and claims are looked up in the
+    /// transcript, and how many may go unfound before a validation warning
+    pub quote_check: QuoteCheckConfig,
+    /// Whether near-duplicate segments from overlapping microphone and
+    /// system capture are dropped before summarizing, and how alike they are
+    pub segment_dedup: SegmentDedupConfig,
+    /// Ask the model for the meeting's attendees before summarizing, store
+    /// them and list them in the prompt for Action Item owners
+    pub extract_participants: bool,
+    /// Ask for single-template reports as JSON in the `StructuredSummary`
+    /// schema, repaired once if it doesn't match, before falling back to the
+    /// markdown report; the result keeps the JSON next to its markdown
+    pub structured_output: bool,
+    /// Which models get an example exchange built from the template before
+    /// the final report prompt; costs a few hundred tokens per report
+    pub few_shot_examples: FewShotMode,
+    /// Picks the provider and model by transcript size for requests that
+    /// don't select one
+    pub provider_routing: ProviderRoutingConfig,
+}
+
+impl Default for SummaryPipelineConfig {
+    fn default() -> Self {
+        let chunk_settings = ChunkSettings::default();
+        Self {
+            chunk_merge: ChunkMerge::default(),
+            chunk_markers: ChunkMarkers::default(),
+            compact_transcript: false,
+            context_share: DEFAULT_CONTEXT_SHARE,
+            max_user_context_tokens: DEFAULT_USER_CONTEXT_TOKENS,
+            question_window_tokens: None,
+            question_debug_log: false,
+            chunk_overlap_tokens: chunk_settings.overlap_tokens,
+            prompt_overhead_tokens: chunk_settings.prompt_overhead_tokens,
+            max_chunks: chunk_settings.max_chunks,
+            min_chunk_tokens: chunk_settings.min_chunk_tokens,
+            skim_max_bullets: DEFAULT_SKIM_MAX_BULLETS,
+            small_model_max_params_b: DEFAULT_SMALL_MODEL_MAX_PARAMS_B,
+            quality_thresholds: QualityThresholds::default(),
+            ticket_coverage: TicketCoverageConfig::default(),
+            quote_check: QuoteCheckConfig::default(),
+            segment_dedup: SegmentDedupConfig::default(),
+            extract_participants: true,
+            structured_output: false,
+            few_shot_examples: FewShotMode::default(),
+            provider_routing: ProviderRoutingConfig::default(),
+        }
+    }
+}
+
+impl SummaryPipelineConfig {
+    pub fn chunk_settings(&self) -> ChunkSettings {
+        ChunkSettings {
+            overlap_tokens: self.chunk_overlap_tokens,
+            prompt_overhead_tokens: self.prompt_overhead_tokens,
+            max_chunks: self.max_chunks,
+            min_chunk_tokens: self.min_chunk_tokens,
+        }
+    }
+
+    /// Checks the values are usable before they are saved
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.context_share) {
+            return Err("Background context share must be between 0 and 1".to_string());
+        }
+        if !(MIN_USER_CONTEXT_TOKENS..=MAX_USER_CONTEXT_TOKENS)
+            .contains(&self.max_user_context_tokens)
+        {
+            return Err(format!(
+                "Custom context limit must be between {} and {} tokens",
+                MIN_USER_CONTEXT_TOKENS, MAX_USER_CONTEXT_TOKENS
+            ));
+        }
+        if self
+            .question_window_tokens
+            .is_some_and(|tokens| tokens < MIN_QUESTION_WINDOW_TOKENS)
+        {
+            return Err(format!(
+                "Question context window must be at least {} tokens",
+                MIN_QUESTION_WINDOW_TOKENS
+            ));
+        }
+        if !(MIN_CHUNK_TOKENS_LOWER..=MIN_CHUNK_TOKENS_UPPER).contains(&self.min_chunk_tokens) {
+            return Err(format!(
+                "Minimum chunk size must be between {} and {} tokens",
+                MIN_CHUNK_TOKENS_LOWER, MIN_CHUNK_TOKENS_UPPER
+            ));
+        }
+        if self.chunk_overlap_tokens >= self.min_chunk_tokens {
+            return Err(format!(
+                "Chunk overlap must be less than the {} token minimum chunk size",
+                self.min_chunk_tokens
+            ));
+        }
+        if self.prompt_overhead_tokens > MAX_PROMPT_OVERHEAD_TOKENS {
+            return Err(format!(
+                "Prompt overhead must be at most {} tokens",
+                MAX_PROMPT_OVERHEAD_TOKENS
+            ));
+        }
+        if !(1..=MAX_CHUNKS_LIMIT).contains(&self.max_chunks) {
+            return Err(format!(
+                "Maximum chunks must be between 1 and {}",
+                MAX_CHUNKS_LIMIT
+            ));
+        }
+        if !(1..=MAX_SKIM_BULLETS).contains(&self.skim_max_bullets) {
+            return Err(format!(
+                "Skim bullets per section must be between 1 and {}",
+                MAX_SKIM_BULLETS
+            ));
+        }
+        if !(0.0..=MAX_SMALL_MODEL_PARAMS_B).contains(&self.small_model_max_params_b) {
+            return Err(format!(
+                "Small model threshold must be between 0 and {}B parameters",
+                MAX_SMALL_MODEL_PARAMS_B
+            ));
+        }
+        self.chunk_markers.validate()?;
+        self.quality_thresholds.validate()?;
+        self.ticket_coverage.validate()?;
+        self.quote_check.validate()?;
+        self.segment_dedup.validate()?;
+        self.provider_routing.validate()?;
+        Ok(())
+    }
+}
+
+pub fn load_summary_pipeline_config<R: Runtime>(app: &AppHandle<R>) -> SummaryPipelineConfig {
+    let stored = app
+        .store(CONFIG_STORE)
+        .ok()
+        .and_then(|store| store.get(CONFIG_KEY));
+    match stored.map(serde_json::from_value::<SummaryPipelineConfig>) {
+        Some(Ok(config)) => config,
+        Some(Err(e)) => {
+            warn!(
+                "⚠️ Invalid summary pipeline config in store, using defaults: {}",
+                e
+            );
+            SummaryPipelineConfig::default()
+        }
+        None => SummaryPipelineConfig::default(),
+    }
+}
+
+pub fn save_summary_pipeline_config<R: Runtime>(
+    app: &AppHandle<R>,
+    config: &SummaryPipelineConfig,
+) -> Result<(), String> {
+    let store = app
+        .store(CONFIG_STORE)
+        .map_err(|e| format!("Failed to open summary pipeline store: {}", e))?;
+    let value = serde_json::to_value(config)
+        .map_err(|e| format!("Failed to serialize summary pipeline config: {}", e))?;
+    store.set(CONFIG_KEY, value);
+    store
+        .save()
+        .map_err(|e| format!("Failed to save summary pipeline config: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::summary::processor::DEFAULT_MIN_CHUNK_TOKENS;
+
+    #[test]
+    fn test_validate_pipeline_config() {
+        let valid = SummaryPipelineConfig::default();
+        assert_eq!(valid.validate(), Ok(()));
+        assert_eq!(valid.chunk_settings(), ChunkSettings::default());
+
+        let invalid = [
+            SummaryPipelineConfig {
+                context_share: 1.5,
+                ..valid.clone()
+            },
+            SummaryPipelineConfig {
+                max_user_context_tokens: MAX_USER_CONTEXT_TOKENS + 1,
+                ..valid.clone()
+            },
+            SummaryPipelineConfig {
+                question_window_tokens: Some(100),
+                ..valid.clone()
+            },
+            SummaryPipelineConfig {
+                chunk_overlap_tokens: DEFAULT_MIN_CHUNK_TOKENS,
+                ..valid.clone()
+            },
+            SummaryPipelineConfig {
+                min_chunk_tokens: MIN_CHUNK_TOKENS_LOWER - 1,
+                ..valid.clone()
+            },
+            SummaryPipelineConfig {
+                prompt_overhead_tokens: MAX_PROMPT_OVERHEAD_TOKENS + 1,
+                ..valid.clone()
+            },
+            SummaryPipelineConfig {
+                max_chunks: 0,
+                ..valid.clone()
+            },
+            SummaryPipelineConfig {
+                skim_max_bullets: MAX_SKIM_BULLETS + 1,
+                ..valid.clone()
+            },
+            SummaryPipelineConfig {
+                small_model_max_params_b: -1.0,
+                ..valid.clone()
+            },
+            SummaryPipelineConfig {
+                chunk_markers: ChunkMarkers {
+                    separator: "---".to_string(),
+                    ..ChunkMarkers::default()
+                },
+                ..valid.clone()
+            },
+            SummaryPipelineConfig {
+                quality_thresholds: QualityThresholds { good: 40, ok: 60 },
+                ..valid.clone()
+            },
+            SummaryPipelineConfig {
+                ticket_coverage: TicketCoverageConfig {
+                    patterns: Vec::new(),
+                    ..TicketCoverageConfig::default()
+                },
+                ..valid.clone()
+            },
+            SummaryPipelineConfig {
+                quote_check: QuoteCheckConfig {
+                    max_unverified_share: 1.5,
+                    ..QuoteCheckConfig::default()
+                },
+                ..valid.clone()
+            },
+            SummaryPipelineConfig {
+                segment_dedup: SegmentDedupConfig {
+                    similarity: 0.1,
+                    ..SegmentDedupConfig::default()
+                },
+                ..valid.clone()
+            },
+        ];
+        for config in invalid {
+            assert!(config.validate().is_err(), "{:?}", config);
+        }
+
+        // Settings saved before the chunking fields existed get the defaults
+        let stored: SummaryPipelineConfig =
+            serde_json::from_value(serde_json::json!({"chunk_merge": "concatenate"})).unwrap();
+        assert_eq!(stored.chunk_settings(), ChunkSettings::default());
+        assert_eq!(stored.validate(), Ok(()));
+    }
+}
@@ -0,0 +1,135 @@
+use crate::summary::processor::rough_token_count;
+use crate::summary::templates::Template;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Reported until the summary is actually saved
+pub const MAX_STREAMING_PERCENT: u8 = 99;
+
+/// Weight of the newest estimate; lower is smoother but lags more
+const SMOOTHING: f64 = 0.3;
+
+/// Typical output per section format, in tokens
+const TITLE_TOKENS: usize = 20;
+const PARAGRAPH_TOKENS: usize = 150;
+const LIST_TOKENS: usize = 100;
+const STRING_TOKENS: usize = 30;
+/// Per column, covering the header and a handful of rows
+const TABLE_COLUMN_TOKENS: usize = 50;
+
+/// Progress of running summaries by meeting id, read by `api_get_summary`
+static PROGRESS: Lazy<Mutex<HashMap<String, u8>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Expected length of a summary written with `template`
+pub fn expected_output_tokens(template: &Template) -> usize {
+    TITLE_TOKENS
+        + template
+            .sections
+            .iter()
+            .map(|section| match section.format.as_str() {
+                "paragraph" => PARAGRAPH_TOKENS,
+                "list" => LIST_TOKENS,
+                "table" => {
+                    let columns = template
+                        .table_schema(section)
+                        .map_or(0, |schema| schema.columns.len());
+                    TABLE_COLUMN_TOKENS * columns.max(1)
+                }
+                _ => STRING_TOKENS,
+            })
+            .sum::<usize>()
+}
+
+/// Turns streamed tokens into a smoothed percentage of the expected output
+///
+/// The percentage never goes down and stays at `MAX_STREAMING_PERCENT`
+/// when the model writes more than expected.
+#[derive(Debug)]
+pub struct ProgressEstimator {
+    expected_tokens: usize,
+    received_tokens: usize,
+    smoothed: f64,
+    reported: u8,
+}
+
+impl ProgressEstimator {
+    pub fn new(expected_tokens: usize) -> Self {
+        Self {
+            expected_tokens: expected_tokens.max(1),
+            received_tokens: 0,
+            smoothed: 0.0,
+            reported: 0,
+        }
+    }
+
+    pub fn for_template(template: &Template) -> Self {
+        Self::new(expected_output_tokens(template))
+    }
+
+    /// Adds a streamed delta; returns the new percentage when it moved
+    pub fn on_delta(&mut self, delta: &str) -> Option<u8> {
+        self.received_tokens += rough_token_count(delta);
+        let raw = (100.0 * self.received_tokens as f64 / self.expected_tokens as f64)
+            .min(MAX_STREAMING_PERCENT as f64);
+        self.smoothed += SMOOTHING * (raw - self.smoothed);
+        let percent = (self.smoothed.round() as u8).min(MAX_STREAMING_PERCENT);
+        if percent > self.reported {
+            self.reported = percent;
+            Some(percent)
+        } else {
+            None
+        }
+    }
+}
+
+/// Records a meeting's summary progress; never moves it backwards
+pub fn report_progress(meeting_id: &str, percent: u8) {
+    let mut progress = PROGRESS.lock().unwrap();
+    let entry = progress.entry(meeting_id.to_string()).or_insert(0);
+    *entry = (*entry).max(percent);
+}
+
+pub fn current_progress(meeting_id: &str) -> Option<u8> {
+    PROGRESS.lock().unwrap().get(meeting_id).copied()
+}
+
+pub fn clear_progress(meeting_id: &str) {
+    PROGRESS.lock().unwrap().remove(meeting_id);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_deltas_advance_progress_monotonically() {
+        let mut estimator = ProgressEstimator::new(200);
+        let mut reported = Vec::new();
+        // 40-character deltas are 10 tokens each; 30 of them overshoot the estimate
+        for _ in 0..30 {
+            if let Some(percent) = estimator.on_delta(&"x".repeat(40)) {
+                reported.push(percent);
+            }
+        }
+
+        assert!(!reported.is_empty());
+        assert!(reported.windows(2).all(|pair| pair[0] < pair[1]), "{:?}", reported);
+        // Smoothing lags the raw 5% per delta at first
+        assert!(reported[0] < 5, "{:?}", reported);
+        // Overshooting the estimate holds at 99 rather than reaching 100
+        assert_eq!(reported.last(), Some(&MAX_STREAMING_PERCENT));
+        assert_eq!(estimator.on_delta("more text"), None);
+        // Empty deltas (keep-alives) don't move it
+        assert_eq!(ProgressEstimator::new(200).on_delta(""), None);
+    }
+
+    #[test]
+    fn test_reported_progress_never_goes_back() {
+        report_progress("progress-test", 40);
+        report_progress("progress-test", 25);
+        assert_eq!(current_progress("progress-test"), Some(40));
+        clear_progress("progress-test");
+        assert_eq!(current_progress("progress-test"), None);
+    }
+}
@@ -0,0 +1,432 @@
+use crate::api::TranscriptSegment;
+use crate::summary::action_items::{format_timestamp_range, parse_timestamp_range};
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Tokens per tagged chunk when the transcript is summarized in a single pass
+pub const PROVENANCE_CHUNK_TOKENS: usize = 500;
+
+/// Asks the combine and final prompts to carry chunk tags through
+pub const PROVENANCE_INSTRUCTION: &str = "The source text is split into chunks, each starting with a bracketed chunk tag such as [C2]. End every factual statement you write (including each Action Items row) with the tag(s) of the chunks it comes from, e.g. \"Sam owns the webhook fix [C2]\" or \"Launch moved to Friday [C1][C3]\". Copy tags exactly as written, never invent new ones, and never put tags in headings.";
+
+/// A run of adjacent chunk tags: "[C1]", "[C1][C3]" or "[C1, C3]", with the
+/// whitespace before it in group 1 (kept before a marker, dropped with the tag)
+static TAG_RUN_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(\s*)(?:\[C\d+(?:\s*,\s*C?\d+)*\]\s*)*\[C\d+(?:\s*,\s*C?\d+)*\]").unwrap()
+});
+static TAG_INDEX_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\d+").unwrap());
+
+/// "(Transcript Chunk 2)", "(Transcript Chunks 2-4)", "[see transcript chunk 3]"
+static CHUNK_REFERENCE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)[ \t]*[(\[][ \t]*(?:see[ \t]+)?transcript[ \t]+chunks?[ \t]+(\d+(?:[ \t]*(?:-|–|to|,|and)[ \t]*\d+)*)[ \t]*[)\]]")
+        .unwrap()
+});
+/// Time range or time a prompt transcript line starts with
+pub(crate) static LINE_TIME_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\[\d{1,2}:\d{2}(?::\d{2})?(?:\s*[–-]\s*\d{1,2}:\d{2}(?::\d{2})?)?\]").unwrap()
+});
+
+/// Recording time (start, end in seconds) covered by each chunk, by index
+pub type ChunkTimes = HashMap<usize, (f64, f64)>;
+
+/// Character range of the summarized transcript covered by one chunk
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChunkRange {
+    /// 1-based, as shown in the chunk tag
+    pub index: usize,
+    pub char_start: usize,
+    pub char_end: usize,
+}
+
+/// Where a transcript segment sits in the summarized text
+#[derive(Debug, Clone, PartialEq)]
+pub struct SegmentSpan {
+    pub char_start: usize,
+    pub char_end: usize,
+    pub audio_start_time: Option<f64>,
+    pub audio_end_time: Option<f64>,
+}
+
+/// What a footnote marker in the summary points back to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProvenanceRef {
+    pub chunks: Vec<usize>,
+    pub char_start: usize,
+    pub char_end: usize,
+    pub audio_start_time: Option<f64>,
+    pub audio_end_time: Option<f64>,
+}
+
+/// Footnote marker number -> source, stored as `provenance` in the result JSON
+pub type ProvenanceMap = BTreeMap<usize, ProvenanceRef>;
+
+pub fn chunk_tag(index: usize) -> String {
+    format!("[C{}]", index)
+}
+
+/// Prefixes chunk content with its tag and character range
+pub fn tag_chunk(range: &ChunkRange, content: &str) -> String {
+    format!(
+        "{} (transcript chars {}-{})\n{}",
+        chunk_tag(range.index),
+        range.char_start,
+        range.char_end,
+        content.trim()
+    )
+}
+
+/// Finds each segment's text in the transcript, in order
+///
+/// Segments that can't be found (edited or compacted differently) are
+/// skipped; the search resumes after the last segment found.
+pub fn locate_segments(text: &str, segments: &[TranscriptSegment]) -> Vec<SegmentSpan> {
+    let mut spans = Vec::new();
+    let mut cursor_byte = 0;
+    let mut cursor_char = 0;
+    for segment in segments {
+        let needle = segment.text.trim();
+        if needle.is_empty() {
+            continue;
+        }
+        let Some(offset) = text[cursor_byte..].find(needle) else {
+            continue;
+        };
+        let found_byte = cursor_byte + offset;
+        let char_start = cursor_char + text[cursor_byte..found_byte].chars().count();
+        let char_end = char_start + needle.chars().count();
+        spans.push(SegmentSpan {
+            char_start,
+            char_end,
+            audio_start_time: segment.audio_start_time,
+            audio_end_time: segment.audio_end_time,
+        });
+        cursor_byte = found_byte + needle.len();
+        cursor_char = char_end;
+    }
+    spans
+}
+
+/// Audio time covered by the segments overlapping a character range
+pub fn audio_range(
+    char_start: usize,
+    char_end: usize,
+    spans: &[SegmentSpan],
+) -> Option<(f64, f64)> {
+    let mut range: Option<(f64, f64)> = None;
+    for span in spans {
+        if span.char_start >= char_end || span.char_end <= char_start {
+            continue;
+        }
+        let Some(start) = span.audio_start_time else {
+            continue;
+        };
+        let end = span.audio_end_time.unwrap_or(start);
+        range = Some(match range {
+            Some((lo, hi)) => (lo.min(start), hi.max(end)),
+            None => (start, end),
+        });
+    }
+    range
+}
+
+fn provenance_ref(
+    chunks: Vec<usize>,
+    ranges: &[&ChunkRange],
+    spans: &[SegmentSpan],
+) -> ProvenanceRef {
+    let char_start = ranges.iter().map(|r| r.char_start).min().unwrap_or(0);
+    let char_end = ranges.iter().map(|r| r.char_end).max().unwrap_or(0);
+    // Chunks cited together need not be adjacent, so each is mapped on its own
+    let audio = ranges
+        .iter()
+        .filter_map(|r| audio_range(r.char_start, r.char_end, spans))
+        .reduce(|(lo, hi), (start, end)| (lo.min(start), hi.max(end)));
+    ProvenanceRef {
+        chunks,
+        char_start,
+        char_end,
+        audio_start_time: audio.map(|(start, _)| start),
+        audio_end_time: audio.map(|(_, end)| end),
+    }
+}
+
+/// Recording time of each chunk, read from the line timestamps inside its
+/// character range of `text`
+///
+/// Chunks without timestamped lines are left out.
+pub fn chunk_time_ranges(text: &str, ranges: &[ChunkRange]) -> ChunkTimes {
+    ranges
+        .iter()
+        .filter_map(|range| {
+            let chunk: String = text
+                .chars()
+                .skip(range.char_start)
+                .take(range.char_end.saturating_sub(range.char_start))
+                .collect();
+            LINE_TIME_RE
+                .find_iter(&chunk)
+                .filter_map(|m| parse_timestamp_range(m.as_str()))
+                .reduce(|(lo, hi), (start, end)| (lo.min(start), hi.max(end)))
+                .map(|times| (range.index, times))
+        })
+        .collect()
+}
+
+/// Rewrites "(Transcript Chunk N)" references the model copied from the
+/// chunk summaries into the recording time they cover
+///
+/// A range such as "(Transcript Chunks 2-4)" cites every chunk in it.
+/// References to chunks without a known time are removed together with the
+/// space before them and any punctuation they leave dangling.
+pub fn rewrite_chunk_references(markdown: &str, times: &ChunkTimes) -> String {
+    let mut output = String::with_capacity(markdown.len());
+    let mut rest = 0;
+    for caps in CHUNK_REFERENCE_RE.captures_iter(markdown) {
+        let reference = caps.get(0).unwrap();
+        output.push_str(&markdown[rest..reference.start()]);
+        rest = reference.end();
+
+        let chunks: Vec<usize> = TAG_INDEX_RE
+            .find_iter(&caps[1])
+            .filter_map(|m| m.as_str().parse().ok())
+            .collect();
+        let first = chunks.iter().copied().min().unwrap_or(0);
+        let last = chunks.iter().copied().max().unwrap_or(0);
+        let cited = (first..=last)
+            .filter_map(|index| times.get(&index).copied())
+            .reduce(|(lo, hi), (start, end)| (lo.min(start), hi.max(end)));
+
+        if let Some((start, end)) = cited {
+            if reference.as_str().starts_with([' ', '\t']) {
+                output.push(' ');
+            }
+            output.push_str(&format!("[{}]", format_timestamp_range(start, end)));
+            continue;
+        }
+
+        let after = &markdown[rest..];
+        let next = after.trim_start_matches([' ', '\t']);
+        let dangling = next.starts_with(['.', ',', ';', ':', '!', '?']);
+        if dangling || output.is_empty() || output.ends_with('\n') {
+            rest += after.len() - next.len();
+        }
+        if dangling {
+            let kept = output.trim_end_matches([',', ';', ':']).len();
+            output.truncate(kept);
+        }
+    }
+    output.push_str(&markdown[rest..]);
+    output
+}
+
+/// Replaces chunk tags with numbered `[n]` markers and maps each marker to
+/// the transcript range and audio time it cites
+///
+/// Markers are numbered in order of first appearance and reused when the
+/// same chunks are cited again. Tags naming unknown chunks, and any tags in
+/// headings, are dropped.
+pub fn apply_footnotes(
+    markdown: &str,
+    ranges: &[ChunkRange],
+    spans: &[SegmentSpan],
+) -> (String, ProvenanceMap) {
+    let by_index: HashMap<usize, &ChunkRange> = ranges.iter().map(|r| (r.index, r)).collect();
+    let mut markers: HashMap<Vec<usize>, usize> = HashMap::new();
+    let mut map = ProvenanceMap::new();
+
+    let lines: Vec<String> = markdown
+        .lines()
+        .map(|line| {
+            if line.trim_start().starts_with('#') {
+                return TAG_RUN_RE.replace_all(line, "").into_owned();
+            }
+            TAG_RUN_RE
+                .replace_all(line, |caps: &Captures| {
+                    let chunks: Vec<usize> = TAG_INDEX_RE
+                        .find_iter(&caps[0])
+                        .filter_map(|m| m.as_str().parse().ok())
+                        .filter(|index| by_index.contains_key(index))
+                        .collect::<BTreeSet<_>>()
+                        .into_iter()
+                        .collect();
+                    if chunks.is_empty() {
+                        return String::new();
+                    }
+                    let next = markers.len() + 1;
+                    let marker = *markers.entry(chunks.clone()).or_insert_with(|| {
+                        let cited: Vec<&ChunkRange> = chunks.iter().map(|i| by_index[i]).collect();
+                        map.insert(next, provenance_ref(chunks.clone(), &cited, spans));
+                        next
+                    });
+                    format!("{}[{}]", &caps[1], marker)
+                })
+                .into_owned()
+        })
+        .collect();
+
+    let mut output = lines.join("\n");
+    if markdown.ends_with('\n') {
+        output.push('\n');
+    }
+    (output, map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::segment;
+
+    #[test]
+    fn test_chunk_range_maps_to_overlapping_segment_times() {
+        // "Héllo team. " is 12 chars but 13 bytes, so offsets must be in chars
+        let text = "Héllo team. Sam fixes the webhook. Launch is Friday.";
+        let segments = vec![
+            segment("s1", "Héllo team.", 0.0, 2.5),
+            segment("s2", "Sam fixes the webhook.", 2.5, 7.0),
+            segment("s3", "not in the text", 7.0, 8.0),
+            segment("s4", "Launch is Friday.", 8.0, 11.0),
+        ];
+
+        let spans = locate_segments(text, &segments);
+
+        assert_eq!(
+            spans
+                .iter()
+                .map(|s| (s.char_start, s.char_end))
+                .collect::<Vec<_>>(),
+            vec![(0, 11), (12, 34), (35, 52)]
+        );
+        // Touches only the middle segment
+        assert_eq!(audio_range(12, 34, &spans), Some((2.5, 7.0)));
+        // Straddles the last two
+        assert_eq!(audio_range(20, 40, &spans), Some((2.5, 11.0)));
+        // End is exclusive: a range ending where a segment starts doesn't include it
+        assert_eq!(audio_range(0, 12, &spans), Some((0.0, 2.5)));
+        assert_eq!(audio_range(60, 80, &spans), None);
+    }
+
+    #[test]
+    fn test_tags_become_numbered_footnotes() {
+        let ranges = vec![
+            ChunkRange {
+                index: 1,
+                char_start: 0,
+                char_end: 20,
+            },
+            ChunkRange {
+                index: 2,
+                char_start: 15,
+                char_end: 40,
+            },
+        ];
+        let spans = vec![
+            SegmentSpan {
+                char_start: 0,
+                char_end: 10,
+                audio_start_time: Some(0.0),
+                audio_end_time: Some(4.0),
+            },
+            SegmentSpan {
+                char_start: 10,
+                char_end: 25,
+                audio_start_time: Some(4.0),
+                audio_end_time: Some(9.0),
+            },
+            SegmentSpan {
+                char_start: 25,
+                char_end: 40,
+                audio_start_time: Some(9.0),
+                audio_end_time: Some(15.0),
+            },
+        ];
+        let markdown = "## Summary [C1]\n\nLaunch moved to Friday [C2]. Sam owns the fix [C1][C2] and QA signs off [C2, C1].\n\n| Owner | Task |\n|---|---|\n| Sam | Fix webhook [C1] |\n| Ana | Invented [C9] |\n";
+
+        let (output, map) = apply_footnotes(markdown, &ranges, &spans);
+
+        assert_eq!(
+            output,
+            "## Summary\n\nLaunch moved to Friday [1]. Sam owns the fix [2] and QA signs off [2].\n\n| Owner | Task |\n|---|---|\n| Sam | Fix webhook [3] |\n| Ana | Invented |\n"
+        );
+        assert_eq!(map.len(), 3);
+        assert_eq!(
+            map[&1],
+            ProvenanceRef {
+                chunks: vec![2],
+                char_start: 15,
+                char_end: 40,
+                audio_start_time: Some(4.0),
+                audio_end_time: Some(15.0),
+            }
+        );
+        assert_eq!(map[&2].chunks, vec![1, 2]);
+        assert_eq!((map[&2].char_start, map[&2].char_end), (0, 40));
+        assert_eq!(
+            (map[&2].audio_start_time, map[&2].audio_end_time),
+            (Some(0.0), Some(15.0))
+        );
+        assert_eq!(map[&3].chunks, vec![1]);
+        assert_eq!(
+            (map[&3].audio_start_time, map[&3].audio_end_time),
+            (Some(0.0), Some(9.0))
+        );
+    }
+
+    #[test]
+    fn test_chunk_references_become_recording_times() {
+        let text = "[00:00:05–00:00:09] Alex: Hi.\n[00:01:00–00:01:30] Sam: Webhook.\nno time here\n[00:02:00–00:02:45] Ana: Launch.";
+        let chunk = |index: usize, char_start: usize, char_end: usize| ChunkRange {
+            index,
+            char_start,
+            char_end,
+        };
+        let times = chunk_time_ranges(
+            text,
+            &[chunk(1, 0, 30), chunk(2, 30, 64), chunk(3, 64, 77), chunk(4, 77, 200)],
+        );
+        assert_eq!(times.get(&1), Some(&(5.0, 9.0)));
+        assert_eq!(times.get(&2), Some(&(60.0, 90.0)));
+        assert_eq!(times.get(&3), None);
+        assert_eq!(times.get(&4), Some(&(120.0, 165.0)));
+
+        let markdown = "Sam will fix the webhook (Transcript Chunk 2) before launch.\n\
+            Launch moved (Transcript Chunks 2-4).\n\
+            | Sam | Fix webhook (Transcript Chunk 2) | Friday |";
+        assert_eq!(
+            rewrite_chunk_references(markdown, &times),
+            "Sam will fix the webhook [00:01:00–00:01:30] before launch.\n\
+             Launch moved [00:01:00–00:02:45].\n\
+             | Sam | Fix webhook [00:01:00–00:01:30] | Friday |"
+        );
+    }
+
+    #[test]
+    fn test_unmapped_chunk_references_are_stripped() {
+        let times = ChunkTimes::new();
+        let strip = |markdown: &str| rewrite_chunk_references(markdown, &times);
+
+        assert_eq!(
+            strip("Sam (Transcript Chunk 2) will fix the webhook."),
+            "Sam will fix the webhook."
+        );
+        assert_eq!(strip("Fix the webhook (Transcript Chunk 2)."), "Fix the webhook.");
+        assert_eq!(
+            strip("Launch moved, (transcript chunks 2 - 4) ; QA next"),
+            "Launch moved; QA next"
+        );
+        assert_eq!(
+            strip("| Sam | Fix webhook (Transcript Chunk 2) | (Transcript Chunk 3) |"),
+            "| Sam | Fix webhook | |"
+        );
+        assert_eq!(
+            strip("## Notes\n(Transcript Chunk 1) Kickoff went well"),
+            "## Notes\nKickoff went well"
+        );
+        // Mentions outside brackets are left for the validator to report
+        assert_eq!(
+            strip("As noted in Transcript Chunk 2, launch moved."),
+            "As noted in Transcript Chunk 2, launch moved."
+        );
+    }
+}
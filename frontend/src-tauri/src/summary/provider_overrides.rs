@@ -0,0 +1,503 @@
+//! Per-provider base URL and extra headers for LLM requests
+//!
+//! Meant for corporate proxies and internal gateways: the base URL replaces
+//! the provider's default host and version prefix, and the extra headers are
+//! added after the provider's own, replacing any with the same name. Header
+//! values marked secret are stored encrypted, and no header value is ever
+//! written to the logs.
+//!
+//! Some OpenAI-compatible servers and older Ollama models ignore or reject a
+//! separate system message, dropping the instructions in it. Models listed
+//! in `merged_system_prompt_models` get the system prompt at the top of the
+//! user message instead; `*` lists every model of the provider.
+
+use crate::export::config::local_secret_key;
+use crate::settings_bundle::crypto::{decrypt_local, encrypt_local, EncryptedSecrets};
+use crate::summary::llm_client::{generate_completion, LLMProvider};
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use reqwest::header::{HeaderName, HeaderValue};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::sync::RwLock;
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+
+const CONFIG_STORE: &str = "provider-overrides.json";
+const CONFIG_KEY: &str = "config";
+
+/// Entry of `merged_system_prompt_models` matching every model
+pub const ALL_MODELS: &str = "*";
+
+/// Base URL, extra headers and prompt handling applied to one provider's
+/// requests
+///
+/// `Debug` lists header names only, so the value can be logged.
+#[derive(Clone, Default, PartialEq)]
+pub struct ProviderOverride {
+    pub base_url: Option<String>,
+    pub headers: BTreeMap<String, String>,
+    /// Models sent without a system turn, `*` for all of them
+    pub merged_system_prompt_models: BTreeSet<String>,
+}
+
+impl ProviderOverride {
+    pub fn is_empty(&self) -> bool {
+        self.base_url.is_none()
+            && self.headers.is_empty()
+            && self.merged_system_prompt_models.is_empty()
+    }
+
+    /// Whether `model_name` gets the system prompt merged into the user turn
+    pub fn merges_system_prompt(&self, model_name: &str) -> bool {
+        self.merged_system_prompt_models
+            .iter()
+            .any(|model| model == ALL_MODELS || model.eq_ignore_ascii_case(model_name.trim()))
+    }
+
+    /// Base URL with any trailing slash removed, or `default`
+    pub fn base_url_or(&self, default: String) -> String {
+        match &self.base_url {
+            Some(url) => url.trim_end_matches('/').to_string(),
+            None => default,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(base_url) = &self.base_url {
+            let url = url::Url::parse(base_url)
+                .map_err(|e| format!("Invalid base URL '{}': {}", base_url, e))?;
+            if url.scheme() != "http" && url.scheme() != "https" {
+                return Err(format!("Base URL '{}' must use http or https", base_url));
+            }
+        }
+        for (name, value) in &self.headers {
+            HeaderName::from_bytes(name.as_bytes())
+                .map_err(|_| format!("'{}' is not a valid header name", name))?;
+            // The value is left out on purpose, it may be a secret
+            HeaderValue::from_str(value)
+                .map_err(|_| format!("The value of header '{}' is not valid", name))?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for ProviderOverride {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProviderOverride")
+            .field("base_url", &self.base_url)
+            .field("headers", &self.headers.keys().collect::<Vec<_>>())
+            .field(
+                "merged_system_prompt_models",
+                &self.merged_system_prompt_models,
+            )
+            .finish()
+    }
+}
+
+/// One provider's override as saved in the store
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StoredProviderOverride {
+    pub base_url: Option<String>,
+    /// Headers whose values are shown in the settings
+    pub headers: BTreeMap<String, String>,
+    /// Names of the headers in `secret_headers`
+    pub secret_header_names: Vec<String>,
+    /// JSON object of secret header values, encrypted with the local key
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret_headers: Option<EncryptedSecrets>,
+    pub merged_system_prompt_models: BTreeSet<String>,
+}
+
+impl fmt::Debug for StoredProviderOverride {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StoredProviderOverride")
+            .field("base_url", &self.base_url)
+            .field("headers", &self.headers.keys().collect::<Vec<_>>())
+            .field("secret_header_names", &self.secret_header_names)
+            .field(
+                "merged_system_prompt_models",
+                &self.merged_system_prompt_models,
+            )
+            .finish()
+    }
+}
+
+impl StoredProviderOverride {
+    /// Builds the stored form, encrypting `secret_headers` with `key`
+    ///
+    /// A header present in both maps keeps the secret value.
+    pub fn new(
+        base_url: Option<String>,
+        headers: BTreeMap<String, String>,
+        secret_headers: &BTreeMap<String, String>,
+        key: &str,
+    ) -> Result<Self, String> {
+        let mut headers = headers;
+        headers.retain(|name, _| !secret_headers.contains_key(name));
+        let encrypted = if secret_headers.is_empty() {
+            None
+        } else {
+            let json = serde_json::to_vec(secret_headers)
+                .map_err(|e| format!("Failed to serialize secret headers: {}", e))?;
+            Some(encrypt_local(&json, key)?)
+        };
+        Ok(Self {
+            base_url,
+            headers,
+            secret_header_names: secret_headers.keys().cloned().collect(),
+            secret_headers: encrypted,
+            merged_system_prompt_models: BTreeSet::new(),
+        })
+    }
+
+    pub fn decrypt_secret_headers(&self, key: &str) -> Result<BTreeMap<String, String>, String> {
+        let Some(secrets) = &self.secret_headers else {
+            return Ok(BTreeMap::new());
+        };
+        let bytes = decrypt_local(secrets, key).map_err(|_| {
+            "Stored secret headers can't be decrypted; enter them again".to_string()
+        })?;
+        serde_json::from_slice(&bytes)
+            .map_err(|_| "Stored secret headers are corrupted".to_string())
+    }
+
+    /// The override applied to requests; secret headers win over plain ones
+    pub fn resolve(&self, key: &str) -> Result<ProviderOverride, String> {
+        let mut headers = self.headers.clone();
+        headers.extend(self.decrypt_secret_headers(key)?);
+        Ok(ProviderOverride {
+            base_url: self.base_url.clone(),
+            headers,
+            merged_system_prompt_models: self.merged_system_prompt_models.clone(),
+        })
+    }
+}
+
+/// Overrides saved for each provider, keyed by `LLMProvider::as_str`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProviderOverridesConfig {
+    pub providers: BTreeMap<String, StoredProviderOverride>,
+}
+
+/// A provider's override as shown in the settings, without secret values
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderOverrideSettings {
+    pub provider: String,
+    pub base_url: Option<String>,
+    pub headers: BTreeMap<String, String>,
+    pub secret_header_names: Vec<String>,
+    pub merged_system_prompt_models: BTreeSet<String>,
+}
+
+impl ProviderOverrideSettings {
+    fn new(provider: &str, stored: &StoredProviderOverride) -> Self {
+        Self {
+            provider: provider.to_string(),
+            base_url: stored.base_url.clone(),
+            headers: stored.headers.clone(),
+            secret_header_names: stored.secret_header_names.clone(),
+            merged_system_prompt_models: stored.merged_system_prompt_models.clone(),
+        }
+    }
+}
+
+/// Decrypted overrides used by `llm_client`, kept in sync with the store
+static OVERRIDES: Lazy<RwLock<HashMap<String, ProviderOverride>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// The override for `provider`, empty when none is saved
+pub fn override_for(provider: &LLMProvider) -> ProviderOverride {
+    OVERRIDES
+        .read()
+        .ok()
+        .and_then(|overrides| overrides.get(provider.as_str()).cloned())
+        .unwrap_or_default()
+}
+
+fn set_override(provider: &LLMProvider, value: ProviderOverride) {
+    if let Ok(mut overrides) = OVERRIDES.write() {
+        if value.is_empty() {
+            overrides.remove(provider.as_str());
+        } else {
+            overrides.insert(provider.as_str().to_string(), value);
+        }
+    }
+}
+
+pub fn load_provider_overrides_config<R: Runtime>(app: &AppHandle<R>) -> ProviderOverridesConfig {
+    let stored = app
+        .store(CONFIG_STORE)
+        .ok()
+        .and_then(|store| store.get(CONFIG_KEY));
+    match stored.map(serde_json::from_value::<ProviderOverridesConfig>) {
+        Some(Ok(config)) => config,
+        Some(Err(e)) => {
+            warn!("⚠️ Invalid provider overrides in store, using none: {}", e);
+            ProviderOverridesConfig::default()
+        }
+        None => ProviderOverridesConfig::default(),
+    }
+}
+
+pub fn save_provider_overrides_config<R: Runtime>(
+    app: &AppHandle<R>,
+    config: &ProviderOverridesConfig,
+) -> Result<(), String> {
+    let store = app
+        .store(CONFIG_STORE)
+        .map_err(|e| format!("Failed to open provider overrides store: {}", e))?;
+    let value = serde_json::to_value(config)
+        .map_err(|e| format!("Failed to serialize provider overrides: {}", e))?;
+    store.set(CONFIG_KEY, value);
+    store
+        .save()
+        .map_err(|e| format!("Failed to save provider overrides: {}", e))
+}
+
+/// Loads the saved overrides so requests made from now on use them
+pub fn init_provider_overrides<R: Runtime>(app: &AppHandle<R>) {
+    let config = load_provider_overrides_config(app);
+    if config.providers.is_empty() {
+        return;
+    }
+    let key = match local_secret_key(app) {
+        Ok(key) => key,
+        Err(e) => {
+            warn!("⚠️ Provider overrides not loaded: {}", e);
+            return;
+        }
+    };
+    for (name, stored) in &config.providers {
+        let provider = match LLMProvider::from_str(name) {
+            Ok(provider) => provider,
+            Err(e) => {
+                warn!("⚠️ Skipping provider override: {}", e);
+                continue;
+            }
+        };
+        match stored.resolve(&key) {
+            Ok(value) => {
+                info!("🔀 Using {} override: {:?}", name, value);
+                set_override(&provider, value);
+            }
+            Err(e) => warn!("⚠️ Skipping {} override: {}", name, e),
+        }
+    }
+}
+
+pub fn list_provider_overrides<R: Runtime>(app: &AppHandle<R>) -> Vec<ProviderOverrideSettings> {
+    load_provider_overrides_config(app)
+        .providers
+        .iter()
+        .map(|(provider, stored)| ProviderOverrideSettings::new(provider, stored))
+        .collect()
+}
+
+/// Saves and applies `provider`'s override
+///
+/// `secret_headers` and `merged_system_prompt_models` replace the stored
+/// ones; None keeps them. An empty base URL with no headers and no merged
+/// models removes the override.
+pub fn set_provider_override<R: Runtime>(
+    app: &AppHandle<R>,
+    provider: &str,
+    base_url: Option<String>,
+    headers: BTreeMap<String, String>,
+    secret_headers: Option<BTreeMap<String, String>>,
+    merged_system_prompt_models: Option<BTreeSet<String>>,
+) -> Result<ProviderOverrideSettings, String> {
+    let llm_provider = LLMProvider::from_str(provider)?;
+    let name = llm_provider.as_str();
+    let base_url = base_url
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty());
+    let key = local_secret_key(app)?;
+
+    let mut config = load_provider_overrides_config(app);
+    let secret_headers = match secret_headers {
+        Some(secret_headers) => secret_headers,
+        None => match config.providers.get(name) {
+            Some(stored) => stored.decrypt_secret_headers(&key)?,
+            None => BTreeMap::new(),
+        },
+    };
+    let merged_system_prompt_models = match merged_system_prompt_models {
+        Some(models) => models
+            .iter()
+            .map(|model| model.trim().to_string())
+            .filter(|model| !model.is_empty())
+            .collect(),
+        None => config
+            .providers
+            .get(name)
+            .map(|stored| stored.merged_system_prompt_models.clone())
+            .unwrap_or_default(),
+    };
+    let stored = StoredProviderOverride {
+        merged_system_prompt_models,
+        ..StoredProviderOverride::new(base_url, headers, &secret_headers, &key)?
+    };
+    let resolved = stored.resolve(&key)?;
+    resolved.validate()?;
+
+    if resolved.is_empty() {
+        config.providers.remove(name);
+    } else {
+        config.providers.insert(name.to_string(), stored.clone());
+    }
+    save_provider_overrides_config(app, &config)?;
+    info!("🔀 Saved {} override: {:?}", name, resolved);
+    set_override(&llm_provider, resolved);
+    Ok(ProviderOverrideSettings::new(name, &stored))
+}
+
+/// Sends a one-line prompt through `provider`'s current override
+///
+/// Returns the model's reply, or the error with a hint that the override
+/// was in use.
+pub async fn test_override(
+    client: &Client,
+    provider: &LLMProvider,
+    model_name: &str,
+    api_key: &str,
+    ollama_endpoint: Option<&str>,
+) -> Result<String, String> {
+    let current = override_for(provider);
+    info!(
+        "🔀 Testing {} override with model {}: {:?}",
+        provider.as_str(),
+        model_name,
+        current
+    );
+    generate_completion(
+        client,
+        provider,
+        model_name,
+        api_key,
+        "You are checking a connection. Answer in one word.",
+        "Reply with OK.",
+        ollama_endpoint,
+    )
+    .await
+    .map(|completion| completion.text)
+    .map_err(|e| {
+        if current.is_empty() {
+            e.to_string()
+        } else {
+            format!(
+                "Test call through the {} override failed: {}",
+                provider.as_str(),
+                e
+            )
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "test-local-key";
+
+    fn headers(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_secret_headers_are_encrypted_and_win() {
+        let stored = StoredProviderOverride::new(
+            Some("https://gateway.corp.example/openai/v1/".to_string()),
+            headers(&[
+                ("X-Trace-Id", "meetings-app"),
+                ("Proxy-Authorization", "plain"),
+            ]),
+            &headers(&[("Proxy-Authorization", "Basic c2VjcmV0")]),
+            KEY,
+        )
+        .unwrap();
+
+        let json = serde_json::to_string(&stored).unwrap();
+        assert!(!json.contains("c2VjcmV0"), "{}", json);
+        assert!(json.contains("meetings-app"));
+        assert!(!stored.headers.contains_key("Proxy-Authorization"));
+
+        let restored: StoredProviderOverride = serde_json::from_str(&json).unwrap();
+        let resolved = restored.resolve(KEY).unwrap();
+        assert_eq!(resolved.headers["Proxy-Authorization"], "Basic c2VjcmV0");
+        assert_eq!(resolved.headers["X-Trace-Id"], "meetings-app");
+        assert_eq!(
+            resolved.base_url_or("https://api.openai.com/v1".to_string()),
+            "https://gateway.corp.example/openai/v1"
+        );
+        assert!(restored.resolve("another-key").is_err());
+    }
+
+    #[test]
+    fn test_debug_output_never_contains_header_values() {
+        let value = ProviderOverride {
+            base_url: None,
+            headers: headers(&[("X-Api-Token", "tok-123")]),
+            ..Default::default()
+        };
+        let logged = format!("{:?}", value);
+        assert!(logged.contains("X-Api-Token"));
+        assert!(!logged.contains("tok-123"), "{}", logged);
+
+        let stored = StoredProviderOverride::new(
+            None,
+            headers(&[("X-Trace-Id", "trace-456")]),
+            &headers(&[("X-Api-Token", "tok-123")]),
+            KEY,
+        )
+        .unwrap();
+        let logged = format!("{:?}", stored);
+        assert!(
+            !logged.contains("trace-456") && !logged.contains("tok-123"),
+            "{}",
+            logged
+        );
+
+        let invalid = ProviderOverride {
+            base_url: None,
+            headers: headers(&[("X-Api-Token", "tok-123\n")]),
+            ..Default::default()
+        };
+        let error = invalid.validate().unwrap_err();
+        assert!(!error.contains("tok-123"), "{}", error);
+    }
+
+    #[test]
+    fn test_validate_override() {
+        let valid = ProviderOverride {
+            base_url: Some("http://localhost:8080/v1".to_string()),
+            headers: headers(&[("X-Trace-Id", "abc")]),
+            ..Default::default()
+        };
+        assert_eq!(valid.validate(), Ok(()));
+        for invalid in [
+            ProviderOverride {
+                base_url: Some("gateway.corp.example".to_string()),
+                ..Default::default()
+            },
+            ProviderOverride {
+                base_url: Some("ftp://gateway.corp.example".to_string()),
+                ..Default::default()
+            },
+            ProviderOverride {
+                base_url: None,
+                headers: headers(&[("Bad Header", "x")]),
+                ..Default::default()
+            },
+        ] {
+            assert!(invalid.validate().is_err(), "{:?}", invalid);
+        }
+    }
+}
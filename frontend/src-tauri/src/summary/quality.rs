@@ -0,0 +1,468 @@
+//! A single 0-100 quality score for generated summaries
+//!
+//! `validate_summary_quality` lists what's wrong; this condenses its findings
+//! and a few other weighted signals into one number users can act on. The
+//! score only looks at the markdown and the transcript it came from, so the
+//! same input always scores the same.
+
+use crate::summary::action_items::parse_action_items;
+use crate::summary::processor::{extract_section_content, ValidationResult};
+use crate::summary::table::{is_separator_row, is_table_line};
+use crate::summary::templates::Template;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Values models put in place of missing information
+static PLACEHOLDER_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(?:not specified|to be determined|unassigned|tbd|none)\b|\bn/a\b").unwrap()
+});
+
+/// Ticket references like PROJ-404
+static TASK_ID_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b[A-Z][A-Z0-9]{1,9}-\d+\b").unwrap());
+
+const ACTION_ITEMS_WEIGHT: u8 = 25;
+const PLACEHOLDERS_WEIGHT: u8 = 15;
+const SECTIONS_WEIGHT: u8 = 20;
+const LENGTH_WEIGHT: u8 = 10;
+const TASK_IDS_WEIGHT: u8 = 15;
+const VALIDATION_WEIGHT: u8 = 15;
+
+/// Share of the validation weight each finding costs; two errors or five
+/// warnings take all of it
+const VALIDATION_ERROR_PENALTY: f64 = 0.5;
+const VALIDATION_WARNING_PENALTY: f64 = 0.2;
+
+/// Transcripts shorter than this are too short to judge the summary length
+const MIN_LENGTH_CHECK_WORDS: usize = 200;
+/// Summary/transcript word ratios in this range count as sane
+const MIN_LENGTH_RATIO: f64 = 0.02;
+const MAX_LENGTH_RATIO: f64 = 0.5;
+/// Ratio at which an overly long summary scores nothing
+const ZERO_LENGTH_RATIO: f64 = 1.5;
+
+/// Lowest scores rated good and ok; anything below `ok` is poor
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QualityThresholds {
+    pub good: u8,
+    pub ok: u8,
+}
+
+impl Default for QualityThresholds {
+    fn default() -> Self {
+        Self { good: 80, ok: 50 }
+    }
+}
+
+impl QualityThresholds {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.ok > self.good || self.good > 100 {
+            return Err("Quality thresholds must satisfy ok <= good <= 100".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn rate(&self, score: u8) -> QualityRating {
+        if score >= self.good {
+            QualityRating::Good
+        } else if score >= self.ok {
+            QualityRating::Ok
+        } else {
+            QualityRating::Poor
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QualityRating {
+    Good,
+    Ok,
+    Poor,
+}
+
+/// One weighted signal of the score
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QualityComponent {
+    pub name: String,
+    /// Points the component is worth out of 100
+    pub weight: u8,
+    /// Share of the weight earned (0.0 - 1.0)
+    pub score: f64,
+    pub detail: String,
+}
+
+/// Score of a summary with the signals it was built from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SummaryQuality {
+    pub score: u8,
+    pub rating: QualityRating,
+    pub components: Vec<QualityComponent>,
+}
+
+fn component(name: &str, weight: u8, score: f64, detail: String) -> QualityComponent {
+    QualityComponent {
+        name: name.to_string(),
+        weight,
+        score: score.clamp(0.0, 1.0),
+        detail,
+    }
+}
+
+fn is_specified(value: &str) -> bool {
+    let value = value.trim();
+    !value.is_empty()
+        && PLACEHOLDER_RE
+            .replace_all(value, "")
+            .trim()
+            .chars()
+            .any(char::is_alphanumeric)
+}
+
+/// Share of action item owners and due dates that are filled in
+fn action_items_component(markdown: &str) -> QualityComponent {
+    let items = parse_action_items(markdown);
+    if items.is_empty() {
+        let detail = "No action items".to_string();
+        return component("action_items", ACTION_ITEMS_WEIGHT, 1.0, detail);
+    }
+    let owners = items
+        .iter()
+        .filter(|item| is_specified(&item.owner))
+        .count();
+    let dues = items.iter().filter(|item| is_specified(&item.due)).count();
+    let score = (owners + dues) as f64 / (2 * items.len()) as f64;
+    let detail = format!(
+        "{}/{} owners and {}/{} due dates specified",
+        owners,
+        items.len(),
+        dues,
+        items.len()
+    );
+    component("action_items", ACTION_ITEMS_WEIGHT, score, detail)
+}
+
+/// Lines that hold content rather than headings or table separators
+fn content_lines(markdown: &str) -> impl Iterator<Item = &str> {
+    markdown
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#') && !is_separator_row(line))
+}
+
+/// Share of content lines free of placeholders
+fn placeholders_component(markdown: &str) -> QualityComponent {
+    let (total, with_placeholder) = content_lines(markdown).fold((0, 0), |(total, found), line| {
+        (
+            total + 1,
+            found + usize::from(PLACEHOLDER_RE.is_match(line)),
+        )
+    });
+    if total == 0 {
+        let detail = "Summary has no content".to_string();
+        return component("placeholders", PLACEHOLDERS_WEIGHT, 0.0, detail);
+    }
+    let score = 1.0 - with_placeholder as f64 / total as f64;
+    let detail = format!("{} of {} lines hold placeholders", with_placeholder, total);
+    component("placeholders", PLACEHOLDERS_WEIGHT, score, detail)
+}
+
+/// Whether a section has anything beyond its heading, table header and placeholders
+fn is_section_filled(section: &str) -> bool {
+    let mut table_header_seen = false;
+    section.lines().skip(1).any(|line| {
+        if is_table_line(line) && !table_header_seen {
+            table_header_seen = true;
+            return false;
+        }
+        if is_separator_row(line) {
+            return false;
+        }
+        let line = line.trim().trim_start_matches(['-', '*', '+']);
+        is_specified(&line.replace('|', " "))
+    })
+}
+
+/// Share of the template's sections present with real content
+fn sections_component(markdown: &str, template: &Template) -> QualityComponent {
+    if template.sections.is_empty() {
+        let detail = "Template has no sections".to_string();
+        return component("sections", SECTIONS_WEIGHT, 1.0, detail);
+    }
+    let missing: Vec<&str> = template
+        .sections
+        .iter()
+        .map(|section| section.title.as_str())
+        .filter(|title| {
+            !extract_section_content(markdown, title, true)
+                .is_some_and(|content| is_section_filled(&content))
+        })
+        .collect();
+    let total = template.sections.len();
+    let score = (total - missing.len()) as f64 / total as f64;
+    let detail = if missing.is_empty() {
+        format!("All {} sections filled", total)
+    } else {
+        format!(
+            "{}/{} sections filled; missing or empty: {}",
+            total - missing.len(),
+            total,
+            missing.join(", ")
+        )
+    };
+    component("sections", SECTIONS_WEIGHT, score, detail)
+}
+
+fn word_count(text: &str) -> usize {
+    text.split_whitespace()
+        .filter(|word| word.chars().any(char::is_alphanumeric))
+        .count()
+}
+
+/// 1.0 inside the sane ratio band, falling off linearly outside it
+fn length_ratio_score(summary_words: usize, transcript_words: usize) -> f64 {
+    if transcript_words < MIN_LENGTH_CHECK_WORDS {
+        return 1.0;
+    }
+    let ratio = summary_words as f64 / transcript_words as f64;
+    if ratio < MIN_LENGTH_RATIO {
+        summary_words as f64 / (transcript_words as f64 * MIN_LENGTH_RATIO)
+    } else if ratio > MAX_LENGTH_RATIO {
+        ((ZERO_LENGTH_RATIO - ratio) / (ZERO_LENGTH_RATIO - MAX_LENGTH_RATIO)).clamp(0.0, 1.0)
+    } else {
+        1.0
+    }
+}
+
+fn length_component(markdown: &str, transcript: &str) -> QualityComponent {
+    let summary_words = word_count(markdown);
+    let transcript_words = word_count(transcript);
+    let detail = if transcript_words < MIN_LENGTH_CHECK_WORDS {
+        format!("Transcript too short to judge ({} words)", transcript_words)
+    } else {
+        format!(
+            "{} summary words for {} transcript words",
+            summary_words, transcript_words
+        )
+    };
+    let score = length_ratio_score(summary_words, transcript_words);
+    component("length", LENGTH_WEIGHT, score, detail)
+}
+
+fn task_ids(text: &str) -> BTreeSet<&str> {
+    TASK_ID_RE.find_iter(text).map(|m| m.as_str()).collect()
+}
+
+/// Share of the transcript's task IDs the summary mentions
+fn task_ids_component(markdown: &str, transcript: &str) -> QualityComponent {
+    let mentioned = task_ids(transcript);
+    if mentioned.is_empty() {
+        let detail = "No task IDs in the transcript".to_string();
+        return component("task_ids", TASK_IDS_WEIGHT, 1.0, detail);
+    }
+    let covered = task_ids(markdown);
+    let missing: Vec<&str> = mentioned.difference(&covered).copied().collect();
+    let score = (mentioned.len() - missing.len()) as f64 / mentioned.len() as f64;
+    let detail = if missing.is_empty() {
+        format!("All {} task IDs covered", mentioned.len())
+    } else {
+        format!(
+            "{}/{} task IDs covered; missing: {}",
+            mentioned.len() - missing.len(),
+            mentioned.len(),
+            missing.join(", ")
+        )
+    };
+    component("task_ids", TASK_IDS_WEIGHT, score, detail)
+}
+
+/// What's left after `validate_summary_quality`'s findings, errors costing
+/// more than warnings
+fn validation_component(validation: &ValidationResult) -> QualityComponent {
+    let errors = validation.errors.len();
+    let warnings = validation.warnings.len();
+    let penalty = errors as f64 * VALIDATION_ERROR_PENALTY
+        + warnings as f64 * VALIDATION_WARNING_PENALTY;
+    let detail = format!("{} validation errors and {} warnings", errors, warnings);
+    component("validation", VALIDATION_WEIGHT, 1.0 - penalty, detail)
+}
+
+/// Scores a summary from 0 to 100 and rates it against `thresholds`
+///
+/// Weighted signals: filled in action item owners and due dates, placeholder
+/// density, template section completeness, summary/transcript length ratio,
+/// coverage of the transcript's task IDs and the findings of
+/// `validate_summary_quality` (passed in as `validation`).
+pub fn score_summary(
+    markdown: &str,
+    template: &Template,
+    transcript: &str,
+    validation: &ValidationResult,
+    thresholds: &QualityThresholds,
+) -> SummaryQuality {
+    let components = vec![
+        action_items_component(markdown),
+        placeholders_component(markdown),
+        sections_component(markdown, template),
+        length_component(markdown, transcript),
+        task_ids_component(markdown, transcript),
+        validation_component(validation),
+    ];
+    let points: f64 = components
+        .iter()
+        .map(|component| component.weight as f64 * component.score)
+        .sum();
+    let score = points.round().clamp(0.0, 100.0) as u8;
+    SummaryQuality {
+        score,
+        rating: thresholds.rate(score),
+        components,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::summary::processor::validate_summary_quality;
+    use crate::summary::templates::{SectionMode, TemplateSection};
+
+    fn template() -> Template {
+        let section = |title: &str, format: &str| TemplateSection {
+            title: title.to_string(),
+            instruction: String::new(),
+            format: format.to_string(),
+            item_format: None,
+            example_item_format: None,
+            table_columns: None,
+        };
+        Template {
+            name: "Sync".to_string(),
+            description: "Sync".to_string(),
+            sections: vec![
+                section("Summary", "paragraph"),
+                section("Action Items", "list"),
+            ],
+            preserve_heading_decorations: false,
+            omit_reference_columns: false,
+            system_prompt_override: None,
+            section_mode: SectionMode::default(),
+        }
+    }
+
+    fn transcript(extra: &str) -> String {
+        format!(
+            "Sam: we ship PROJ-404 on Friday. {}{}",
+            extra,
+            "filler talk ".repeat(200)
+        )
+    }
+
+    const GOOD_SUMMARY: &str = "## Summary\n\nThe team agreed to ship the PROJ-404 billing fix on Friday.\n\n## Action Items\n\n| Owner | Task | Due |\n|---|---|---|\n| Sam | Ship PROJ-404 | Friday |";
+
+    const POOR_SUMMARY: &str = "## Summary\n\nNot specified\n\n## Action Items\n\n| Owner | Task | Due |\n|---|---|---|\n| Not specified | Fix login | TBD |\n| Sam | Update docs | Not specified |";
+
+    fn score(markdown: &str, transcript: &str) -> SummaryQuality {
+        score_summary(
+            markdown,
+            &template(),
+            transcript,
+            &validate_summary_quality(markdown, &[]),
+            &QualityThresholds::default(),
+        )
+    }
+
+    fn points(quality: &SummaryQuality) -> Vec<(String, f64)> {
+        quality
+            .components
+            .iter()
+            .map(|c| (c.name.clone(), c.weight as f64 * c.score))
+            .collect()
+    }
+
+    #[test]
+    fn test_complete_summary_scores_full_marks() {
+        let quality = score(GOOD_SUMMARY, &transcript(""));
+        assert_eq!(quality.score, 100, "{:?}", quality);
+        assert_eq!(quality.rating, QualityRating::Good);
+    }
+
+    #[test]
+    fn test_missing_task_id_costs_its_share() {
+        let quality = score(GOOD_SUMMARY, &transcript("OPS-12 is blocked. "));
+        // 25 + 15 + 20 + 10 + 7.5 + 15
+        assert_eq!(quality.score, 93);
+        assert_eq!(
+            quality.components[4].detail,
+            "1/2 task IDs covered; missing: OPS-12"
+        );
+
+        let strict = QualityThresholds { good: 95, ok: 90 };
+        assert_eq!(strict.rate(quality.score), QualityRating::Ok);
+    }
+
+    #[test]
+    fn test_placeholder_summary_scores_poor() {
+        let quality = score(POOR_SUMMARY, &transcript("OPS-12 is blocked. "));
+        assert_eq!(
+            points(&quality),
+            vec![
+                // One of two owners, no due dates
+                ("action_items".to_string(), 6.25),
+                // Three of four content lines hold placeholders
+                ("placeholders".to_string(), 3.75),
+                // The Summary section holds only a placeholder
+                ("sections".to_string(), 10.0),
+                ("length".to_string(), 10.0),
+                ("task_ids".to_string(), 0.0),
+                // One warning, for the TBD
+                ("validation".to_string(), 12.0),
+            ]
+        );
+        assert_eq!(quality.score, 42);
+        assert_eq!(quality.rating, QualityRating::Poor);
+        assert_eq!(
+            quality.components[2].detail,
+            "1/2 sections filled; missing or empty: Summary"
+        );
+    }
+
+    #[test]
+    fn test_validation_errors_cost_more_than_warnings() {
+        let findings = |errors: usize, warnings: usize| ValidationResult {
+            errors: vec!["error".to_string(); errors],
+            warnings: vec!["warning".to_string(); warnings],
+        };
+        let clean = validation_component(&findings(0, 0));
+        let warned = validation_component(&findings(0, 1));
+        let failed = validation_component(&findings(1, 0));
+        assert_eq!(clean.score, 1.0);
+        assert!(failed.score < warned.score && warned.score < clean.score);
+        assert_eq!(failed.detail, "1 validation errors and 0 warnings");
+        // Never below nothing
+        assert_eq!(validation_component(&findings(3, 10)).score, 0.0);
+    }
+
+    #[test]
+    fn test_length_ratio_score() {
+        // Short transcripts aren't judged
+        assert_eq!(length_ratio_score(500, 100), 1.0);
+        assert_eq!(length_ratio_score(100, 1000), 1.0);
+        // Too terse or too long falls off linearly
+        assert_eq!(length_ratio_score(10, 1000), 0.5);
+        assert_eq!(length_ratio_score(1000, 1000), 0.5);
+        assert_eq!(length_ratio_score(2000, 1000), 0.0);
+    }
+
+    #[test]
+    fn test_quality_thresholds() {
+        let thresholds = QualityThresholds::default();
+        assert_eq!(thresholds.rate(80), QualityRating::Good);
+        assert_eq!(thresholds.rate(79), QualityRating::Ok);
+        assert_eq!(thresholds.rate(49), QualityRating::Poor);
+        assert!(thresholds.validate().is_ok());
+        assert!(QualityThresholds { good: 40, ok: 60 }.validate().is_err());
+        assert!(QualityThresholds { good: 101, ok: 60 }.validate().is_err());
+    }
+}
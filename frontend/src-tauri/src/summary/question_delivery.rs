@@ -0,0 +1,146 @@
+//! Tracking clarifying questions sent to the meeting chat
+//!
+//! Each stored question sent through the browser extension gets a delivery
+//! id. The extension reports back per delivery whether it posted the
+//! question and on which platform; the app polls the backend for these
+//! reports, and `api_confirm_question_delivered` takes them directly. A
+//! delivery nobody confirms within the configured timeout becomes "unknown".
+
+use crate::database::repositories::question::QuestionsRepository;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+
+const CONFIG_STORE: &str = "question-delivery.json";
+const CONFIG_KEY: &str = "config";
+
+const MIN_TIMEOUT_SECS: u64 = 5;
+const MAX_TIMEOUT_SECS: u64 = 3600;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuestionDeliveryConfig {
+    /// How long a delivery waits for the extension's confirmation
+    pub confirmation_timeout_secs: u64,
+    /// How often the backend is asked for confirmations meanwhile
+    pub poll_interval_secs: u64,
+}
+
+impl Default for QuestionDeliveryConfig {
+    fn default() -> Self {
+        Self {
+            confirmation_timeout_secs: 60,
+            poll_interval_secs: 3,
+        }
+    }
+}
+
+impl QuestionDeliveryConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(MIN_TIMEOUT_SECS..=MAX_TIMEOUT_SECS).contains(&self.confirmation_timeout_secs) {
+            return Err(format!(
+                "Delivery confirmation timeout must be between {} and {} seconds",
+                MIN_TIMEOUT_SECS, MAX_TIMEOUT_SECS
+            ));
+        }
+        if self.poll_interval_secs == 0 || self.poll_interval_secs > self.confirmation_timeout_secs
+        {
+            return Err(
+                "Delivery poll interval must be at least 1 second and within the timeout"
+                    .to_string(),
+            );
+        }
+        Ok(())
+    }
+}
+
+pub fn load_question_delivery_config<R: Runtime>(app: &AppHandle<R>) -> QuestionDeliveryConfig {
+    let stored = app
+        .store(CONFIG_STORE)
+        .ok()
+        .and_then(|store| store.get(CONFIG_KEY));
+    match stored.map(serde_json::from_value::<QuestionDeliveryConfig>) {
+        Some(Ok(config)) => config,
+        Some(Err(e)) => {
+            warn!(
+                "⚠️ Invalid question delivery config in store, using defaults: {}",
+                e
+            );
+            QuestionDeliveryConfig::default()
+        }
+        None => QuestionDeliveryConfig::default(),
+    }
+}
+
+pub fn save_question_delivery_config<R: Runtime>(
+    app: &AppHandle<R>,
+    config: &QuestionDeliveryConfig,
+) -> Result<(), String> {
+    let store = app
+        .store(CONFIG_STORE)
+        .map_err(|e| format!("Failed to open question delivery store: {}", e))?;
+    let value = serde_json::to_value(config)
+        .map_err(|e| format!("Failed to serialize question delivery config: {}", e))?;
+    store.set(CONFIG_KEY, value);
+    store
+        .save()
+        .map_err(|e| format!("Failed to save question delivery config: {}", e))
+}
+
+/// A question as sent to the extension
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuestionDelivery {
+    pub delivery_id: String,
+    pub text: String,
+}
+
+/// The extension's report on one delivery
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeliveryConfirmation {
+    pub delivery_id: String,
+    #[serde(default)]
+    pub platform: Option<String>,
+    pub success: bool,
+}
+
+/// Reports the backend has received so far; deliveries still waiting are
+/// left out
+#[derive(Debug, Default, Deserialize)]
+pub struct DeliveryStatusResponse {
+    #[serde(default)]
+    pub deliveries: Vec<DeliveryConfirmation>,
+}
+
+/// Records `confirmations`; returns the delivery ids that matched a question
+pub async fn apply_confirmations(
+    pool: &SqlitePool,
+    confirmations: &[DeliveryConfirmation],
+) -> Result<Vec<String>, String> {
+    let mut confirmed = Vec::new();
+    for confirmation in confirmations {
+        let platform = confirmation
+            .platform
+            .as_deref()
+            .map(str::trim)
+            .filter(|platform| !platform.is_empty());
+        let found = QuestionsRepository::confirm_delivery(
+            pool,
+            &confirmation.delivery_id,
+            platform,
+            confirmation.success,
+        )
+        .await
+        .map_err(|e| format!("Failed to record question delivery: {}", e))?;
+        if found {
+            confirmed.push(confirmation.delivery_id.clone());
+        } else {
+            warn!(
+                "⚠️ Confirmation for unknown question delivery {}",
+                confirmation.delivery_id
+            );
+        }
+    }
+    Ok(confirmed)
+}
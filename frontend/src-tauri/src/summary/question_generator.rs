@@ -1,181 +1,312 @@
+use crate::summary::context::{
+    background_context_block, BACKGROUND_CONTEXT_TAG, FIXED_CONTEXT_TOKENS,
+};
+use crate::http::client_factory::http_client;
+use crate::logging::log_preview;
 use crate::summary::llm_client::{LLMProvider, generate_summary};
+use crate::summary::processor::rough_token_count;
+use crate::summary::question_prompt::QuestionPromptConfig;
+use crate::summary::service::METADATA_CACHE;
 use std::str::FromStr;
-use crate::database::repositories::setting::SettingsRepository;
-use sqlx::SqlitePool;
+use crate::database::repositories::SettingsRepo;
 use log::{info, warn, error};
 use serde::{Deserialize, Serialize};
-use std::fs::{File, OpenOptions};
+use crate::summary::pipeline_config::load_summary_pipeline_config;
+use std::fs::OpenOptions;
 use std::io::Write;
-use std::path::PathBuf;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager, Runtime};
+
+/// Context window assumed for cloud providers; questions only need the
+/// recent part of the meeting, so this stays well below their real limits
+const CLOUD_QUESTION_WINDOW_TOKENS: usize = 16000;
+
+/// Used when an Ollama model's window can't be fetched
+const FALLBACK_QUESTION_WINDOW_TOKENS: usize = 4000;
+
+/// Smallest window a configured override may set
+pub const MIN_QUESTION_WINDOW_TOKENS: usize = 1024;
+
+/// Left free for the JSON array of questions
+const QUESTION_RESPONSE_TOKENS: usize = 300;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Question {
     pub text: String,
+    /// What the question asks about: "owner", "deadline" or "general"
+    #[serde(default = "general_category")]
+    pub category: String,
     pub context: String, // The transcript chunk that triggered the question
 }
 
-/// Save questions and inputs to a text file for debugging
-fn save_question_debug(
+fn general_category() -> String {
+    "general".to_string()
+}
+
+/// Sorts a question by the gap it asks about, from its wording
+pub fn question_category(text: &str) -> &'static str {
+    let lower = text.to_lowercase();
+    let words: Vec<&str> = lower
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect();
+    let has_any = |candidates: &[&str]| words.iter().any(|word| candidates.contains(word));
+    if has_any(&["who", "whom", "owner", "owns", "responsible", "assignee", "assigned"]) {
+        "owner"
+    } else if has_any(&["when", "deadline", "due", "timeline", "date", "eta"]) {
+        "deadline"
+    } else {
+        "general"
+    }
+}
+
+/// Debug log file name inside the app log directory
+const QUESTION_DEBUG_FILE: &str = "question_debug.txt";
+
+/// Held while a block is appended so blocks of concurrent meetings stay whole
+static QUESTION_DEBUG_LOCK: Mutex<()> = Mutex::new(());
+
+/// Where `generate_questions` logs its inputs and output; off unless the
+/// pipeline config enables it
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuestionDebugLog {
+    pub path: PathBuf,
+    /// Written in each block header; None while the meeting is still recording
+    pub meeting_id: Option<String>,
+}
+
+/// The debug log in the app log directory, if enabled in the pipeline config
+pub fn question_debug_log<R: Runtime>(
+    app: &AppHandle<R>,
+    meeting_id: Option<&str>,
+) -> Option<QuestionDebugLog> {
+    if !load_summary_pipeline_config(app).question_debug_log {
+        return None;
+    }
+    match app.path().app_log_dir() {
+        Ok(dir) => Some(QuestionDebugLog {
+            path: dir.join(QUESTION_DEBUG_FILE),
+            meeting_id: meeting_id.map(str::to_string),
+        }),
+        Err(e) => {
+            warn!("⚠️ [Question Gen] No app log directory for the question debug log: {}", e);
+            None
+        }
+    }
+}
+
+/// One debug block: the inputs, prompt, raw response and parsed questions
+fn format_question_debug_block(
+    meeting_id: Option<&str>,
+    timestamp: u64,
     transcript_chunk: &str,
     recent_context: &str,
     prompt: &str,
     llm_response: &str,
     questions: &[Question],
-) {
-    // Try to save to a debug file - try multiple locations
-    let mut path = None;
-    
-    // Try current directory first
-    if let Ok(mut current_path) = std::env::current_dir() {
-        current_path.push("question_debug.txt");
-        if OpenOptions::new().create(true).append(true).open(&current_path).is_ok() {
-            path = Some(current_path);
-        }
+) -> String {
+    let separator = "=".repeat(80);
+    let mut block = format!(
+        "\n{}\nTIMESTAMP: {}\nMEETING: {}\n",
+        separator,
+        timestamp,
+        meeting_id.unwrap_or("(recording)")
+    );
+    let sections = [
+        ("TRANSCRIPT CHUNK", transcript_chunk),
+        ("RECENT CONTEXT", recent_context),
+        ("PROMPT SENT TO LLM", prompt),
+        ("LLM RAW RESPONSE", llm_response),
+    ];
+    for (title, text) in sections {
+        block.push_str(&format!("\n--- {} ({} chars) ---\n{}\n", title, text.len(), text));
     }
-    
-    // Try home directory if current dir failed
-    if path.is_none() {
-        if let Some(home) = std::env::var_os("HOME") {
-            let mut home_path = PathBuf::from(home);
-            home_path.push("question_debug.txt");
-            if OpenOptions::new().create(true).append(true).open(&home_path).is_ok() {
-                path = Some(home_path);
-            }
-        }
+    block.push_str(&format!("\n--- GENERATED QUESTIONS ({} total) ---\n", questions.len()));
+    if questions.is_empty() {
+        block.push_str("NO QUESTIONS GENERATED\n");
     }
-    
-    // Try temp directory as last resort
-    if path.is_none() {
-        if let Ok(temp) = std::env::var("TMPDIR") {
-            let mut temp_path = PathBuf::from(temp);
-            temp_path.push("question_debug.txt");
-            if OpenOptions::new().create(true).append(true).open(&temp_path).is_ok() {
-                path = Some(temp_path);
-            }
-        } else if let Ok(temp) = std::env::var("TEMP") {
-            let mut temp_path = PathBuf::from(temp);
-            temp_path.push("question_debug.txt");
-            if OpenOptions::new().create(true).append(true).open(&temp_path).is_ok() {
-                path = Some(temp_path);
-            }
-        }
+    for (idx, q) in questions.iter().enumerate() {
+        block.push_str(&format!("{}. {}\n", idx + 1, q.text));
     }
-    
-    let path = match path {
-        Some(p) => p,
-        None => {
-            warn!("⚠️ [Question Gen] Could not open debug file in any location");
-            return;
-        }
-    };
-    
-    let mut file = match OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(&path)
-    {
-        Ok(f) => f,
-        Err(e) => {
-            warn!("⚠️ [Question Gen] Failed to open debug file: {}", e);
-            return;
-        }
-    };
-    
+    block.push_str(&format!("\n{}\n\n", separator));
+    block
+}
+
+/// Appends `block` in a single write while holding the debug log lock
+fn append_question_debug(path: &Path, block: &str) -> std::io::Result<()> {
+    let _guard = QUESTION_DEBUG_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(block.as_bytes())
+}
+
+/// Save questions and inputs to the debug log
+fn save_question_debug(
+    log: &QuestionDebugLog,
+    transcript_chunk: &str,
+    recent_context: &str,
+    prompt: &str,
+    llm_response: &str,
+    questions: &[Question],
+) {
     let timestamp = SystemTime::now()
         .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_secs();
-    
-    if let Err(e) = writeln!(file, "\n{}", "=".repeat(80)) {
-        warn!("⚠️ [Question Gen] Failed to write to debug file: {}", e);
-        return;
-    }
-    
-    if let Err(e) = writeln!(file, "TIMESTAMP: {}", timestamp) {
-        warn!("⚠️ [Question Gen] Failed to write timestamp: {}", e);
-        return;
-    }
-    
-    if let Err(e) = writeln!(file, "\n--- TRANSCRIPT CHUNK ({} chars) ---", transcript_chunk.len()) {
-        warn!("⚠️ [Question Gen] Failed to write transcript chunk header: {}", e);
-        return;
-    }
-    if let Err(e) = writeln!(file, "{}", transcript_chunk) {
-        warn!("⚠️ [Question Gen] Failed to write transcript chunk: {}", e);
-        return;
-    }
-    
-    if let Err(e) = writeln!(file, "\n--- RECENT CONTEXT ({} chars) ---", recent_context.len()) {
-        warn!("⚠️ [Question Gen] Failed to write recent context header: {}", e);
-        return;
-    }
-    if let Err(e) = writeln!(file, "{}", recent_context) {
-        warn!("⚠️ [Question Gen] Failed to write recent context: {}", e);
-        return;
+        .map_or(0, |elapsed| elapsed.as_secs());
+    let block = format_question_debug_block(
+        log.meeting_id.as_deref(),
+        timestamp,
+        transcript_chunk,
+        recent_context,
+        prompt,
+        llm_response,
+        questions,
+    );
+    match append_question_debug(&log.path, &block) {
+        Ok(()) => info!("📁 [Question Gen] Saved debug info to: {}", log.path.display()),
+        Err(e) => warn!(
+            "⚠️ [Question Gen] Failed to write debug file {}: {}",
+            log.path.display(),
+            e
+        ),
     }
-    
-    if let Err(e) = writeln!(file, "\n--- PROMPT SENT TO LLM ({} chars) ---", prompt.len()) {
-        warn!("⚠️ [Question Gen] Failed to write prompt header: {}", e);
-        return;
+}
+
+/// Keeps the end of `text` within `max_tokens`, starting at a line break
+/// when one is close
+fn keep_recent_tokens(text: &str, max_tokens: usize) -> &str {
+    if rough_token_count(text) <= max_tokens {
+        return text;
     }
-    if let Err(e) = writeln!(file, "{}", prompt) {
-        warn!("⚠️ [Question Gen] Failed to write prompt: {}", e);
-        return;
+    let total_chars = text.chars().count();
+    let skip = total_chars.saturating_sub(max_tokens * 4);
+    let start = text
+        .char_indices()
+        .nth(skip)
+        .map_or(text.len(), |(index, _)| index);
+    let kept = &text[start..];
+    match kept.find('\n') {
+        Some(line_break) if line_break < kept.len() / 2 => &kept[line_break + 1..],
+        _ => kept,
     }
-    
-    if let Err(e) = writeln!(file, "\n--- LLM RAW RESPONSE ({} chars) ---", llm_response.len()) {
-        warn!("⚠️ [Question Gen] Failed to write LLM response header: {}", e);
-        return;
+}
+
+/// Fits the transcript into `budget_tokens`
+///
+/// The current chunk is kept whole and the recent context gets what is
+/// left, dropping its oldest part first. A chunk larger than the budget on
+/// its own is cut to its most recent part and the recent context dropped.
+/// Returns (recent_context, transcript_chunk).
+fn fit_question_transcript<'a>(
+    recent_context: &'a str,
+    transcript_chunk: &'a str,
+    budget_tokens: usize,
+) -> (&'a str, &'a str) {
+    let chunk_tokens = rough_token_count(transcript_chunk);
+    if chunk_tokens >= budget_tokens {
+        return ("", keep_recent_tokens(transcript_chunk, budget_tokens));
     }
-    if let Err(e) = writeln!(file, "{}", llm_response) {
-        warn!("⚠️ [Question Gen] Failed to write LLM response: {}", e);
-        return;
+    (
+        keep_recent_tokens(recent_context, budget_tokens - chunk_tokens),
+        transcript_chunk,
+    )
+}
+
+/// Context window for question prompts: the configured override, else the
+/// Ollama model's window from the metadata cache
+async fn question_window_tokens(
+    provider: &LLMProvider,
+    model_name: &str,
+    ollama_endpoint: Option<&str>,
+    window_override: Option<usize>,
+) -> usize {
+    if let Some(tokens) = window_override {
+        return tokens;
     }
-    
-    if let Err(e) = writeln!(file, "\n--- GENERATED QUESTIONS ({} total) ---", questions.len()) {
-        warn!("⚠️ [Question Gen] Failed to write questions header: {}", e);
-        return;
+    if provider != &LLMProvider::Ollama {
+        return CLOUD_QUESTION_WINDOW_TOKENS;
     }
-    if questions.is_empty() {
-        if let Err(e) = writeln!(file, "NO QUESTIONS GENERATED") {
-            warn!("⚠️ [Question Gen] Failed to write no questions message: {}", e);
-            return;
-        }
-    } else {
-        for (idx, q) in questions.iter().enumerate() {
-            if let Err(e) = writeln!(file, "{}. {}", idx + 1, q.text) {
-                warn!("⚠️ [Question Gen] Failed to write question {}: {}", idx + 1, e);
-                return;
-            }
+    match METADATA_CACHE.get_or_fetch(model_name, ollama_endpoint).await {
+        Ok(metadata) => metadata.context_size,
+        Err(e) => {
+            warn!(
+                "⚠️ [Question Gen] Failed to fetch context for {}: {}. Using default {}",
+                model_name, e, FALLBACK_QUESTION_WINDOW_TOKENS
+            );
+            FALLBACK_QUESTION_WINDOW_TOKENS
         }
     }
-    
-    if let Err(e) = writeln!(file, "\n{}\n", "=".repeat(80)) {
-        warn!("⚠️ [Question Gen] Failed to write separator: {}", e);
-        return;
-    }
-    
-    info!("✅ [Question Gen] Saved debug info to: {:?}", path);
-    info!("📁 [Question Gen] Debug file location: {}", path.display());
-    warn!("📁 [Question Gen] ⚠️ IMPORTANT: Question debug file saved to: {}", path.display());
-    eprintln!("📁 [Question Gen] ⚠️ IMPORTANT: Question debug file saved to: {}", path.display());
+}
+
+/// Prompt asking the model for clarifying questions about the transcript,
+/// worded by `prompt_config`
+fn build_question_prompt(
+    prompt_config: &QuestionPromptConfig,
+    recent_context: &str,
+    transcript_chunk: &str,
+    background_section: &str,
+) -> String {
+    // General prompt for meeting facilitation - similar to backend implementation
+    // CRITICAL: Make prompt more direct and ensure questions are always generated
+    format!(
+        r#"{}
+
+Recent context:
+{}
+Current transcript:
+{}
+{}
+IMPORTANT: You MUST generate at least 1 clarifying question. Even if everything seems clear, find something to ask about.
+
+Analyze the meeting content and generate 2-5 concise clarifying questions that should be asked to the meeting participants.
+
+{}IMPORTANT GUIDELINES:
+- ALWAYS generate at least 1 question, even if you have to be creative
+- Questions should be SHORT and DIRECT (1-2 sentences max)
+- Questions should be suitable for posting in a meeting chat
+- Questions should be actionable - asking for specific information
+- Use names if mentioned in the transcript
+- Format questions conversationally, as if you're asking in the meeting
+- End each question with a question mark "?"
+
+{}{}"#,
+        prompt_config.preamble.trim(),
+        recent_context,
+        transcript_chunk,
+        background_section,
+        prompt_config.focus_section(),
+        prompt_config.examples_section(),
+        prompt_config.output_instructions.trim()
+    )
 }
 
 /// Generate clarifying questions from transcript chunks
 /// Returns questions when context is unclear (missing deadlines, owners, etc.)
+/// `background_context` is the meeting's reference notes, empty if none
+/// `window_tokens` overrides the context window the prompt is fitted into
+/// `debug_log` receives the prompt, raw response and questions when set
+/// `prompt_config` words the prompt; the default is the built-in prompt
 pub async fn generate_questions(
-    pool: &SqlitePool,
+    settings: &dyn SettingsRepo,
+    prompt_config: &QuestionPromptConfig,
     transcript_chunk: &str,
     recent_context: &str, // Last few chunks for context
+    background_context: &str,
+    window_tokens: Option<usize>,
+    debug_log: Option<&QuestionDebugLog>,
 ) -> Result<Vec<Question>, String> {
     // Log what we received
     info!("🔍 [Question Gen] Received transcript_chunk: {} chars, recent_context: {} chars", 
           transcript_chunk.len(), recent_context.len());
     info!("🔍 [Question Gen] transcript_chunk preview: {}", 
-          &transcript_chunk[..transcript_chunk.len().min(200)]);
+          log_preview(transcript_chunk));
     info!("🔍 [Question Gen] recent_context preview: {}", 
-          &recent_context[..recent_context.len().min(200)]);
+          log_preview(recent_context));
     
     // RELAXED: Allow very short chunks (minimum 5 chars) for popup display
     if transcript_chunk.trim().len() < 5 {
@@ -183,12 +314,13 @@ pub async fn generate_questions(
         // Return a generic question instead of empty
         return Ok(vec![Question {
             text: "What should we clarify about this?".to_string(),
+            category: general_category(),
             context: transcript_chunk.to_string(),
         }]);
     }
 
     // Get model config
-    let config = SettingsRepository::get_model_config(pool)
+    let config = settings.get_model_config()
         .await
         .map_err(|e| {
             warn!("❌ [Question Gen] Failed to get model config from database: {}", e);
@@ -215,7 +347,7 @@ pub async fn generate_questions(
         info!("ℹ️ [Question Gen] Using Ollama provider (no API key required)");
         String::new()
     } else {
-        SettingsRepository::get_api_key(pool, &config.provider)
+        settings.get_api_key(&config.provider)
             .await
             .map_err(|e| {
                 warn!("❌ [Question Gen] Failed to get API key for provider '{}': {}", config.provider, e);
@@ -234,59 +366,57 @@ pub async fn generate_questions(
         info!("✅ [Question Gen] API key loaded (length: {} chars)", api_key.len());
     }
 
-    // General prompt for meeting facilitation - similar to backend implementation
-    // CRITICAL: Make prompt more direct and ensure questions are always generated
-    let prompt = format!(
-        r#"You are a meeting facilitator analyzing a transcript to identify items that need clarification from meeting participants.
-
-Recent context:
-{}
-Current transcript:
-{}
-
-IMPORTANT: You MUST generate at least 1 clarifying question. Even if everything seems clear, find something to ask about.
-
-Analyze the meeting content and generate 2-5 concise clarifying questions that should be asked to the meeting participants.
-
-Focus on identifying:
-1. **Missing Assignees**: Action items or tasks mentioned without a clear owner
-2. **Unclear Deadlines**: Tasks without specific due dates or vague timelines ("soon", "later")
-3. **Ambiguous Requirements**: Items that need more specific definition or acceptance criteria
-4. **Missing Priorities**: Tasks that lack urgency/importance classification
-5. **Unclear Dependencies**: References to blockers or prerequisites that aren't well defined
-6. **Next Steps**: What should happen next?
-7. **Decisions**: What decisions need to be made?
-
-IMPORTANT GUIDELINES:
-- ALWAYS generate at least 1 question, even if you have to be creative
-- Questions should be SHORT and DIRECT (1-2 sentences max)
-- Questions should be suitable for posting in a meeting chat
-- Questions should be actionable - asking for specific information
-- Use names if mentioned in the transcript
-- Format questions conversationally, as if you're asking in the meeting
-- End each question with a question mark "?"
-
-EXAMPLE QUESTIONS:
-- "Who will be handling the Stripe webhook fix?"
-- "What's the deadline for the API documentation?"
-- "Can we confirm the priority for the VPN issue - is it blocking the release?"
-- "Is the database migration dependent on the auth service being ready?"
-- "What are the next steps for this project?"
+    // The notes get a fixed cap and count as part of the fixed prompt below
+    let background_section = background_context_block(
+        background_context,
+        FIXED_CONTEXT_TOKENS,
+        &[BACKGROUND_CONTEXT_TAG],
+    )
+    .map(|block| format!("\n{}\n", block))
+    .unwrap_or_default();
 
-Return ONLY a JSON array of question strings. Example:
-["Who should be assigned to this task?", "What is the deadline for this?"]
+    // Fit the transcript into what the fixed prompt and the answer leave free
+    let window = question_window_tokens(
+        &provider,
+        &config.model,
+        config.ollama_endpoint.as_deref(),
+        window_tokens,
+    )
+    .await;
+    let fixed_tokens = rough_token_count(&build_question_prompt(
+        prompt_config,
+        "",
+        "",
+        &background_section,
+    ));
+    let budget = window.saturating_sub(fixed_tokens + QUESTION_RESPONSE_TOKENS);
+    let (original_recent_context, original_chunk) = (recent_context, transcript_chunk);
+    let (recent_context, transcript_chunk) =
+        fit_question_transcript(recent_context, transcript_chunk, budget);
+    if recent_context.len() < original_recent_context.len()
+        || transcript_chunk.len() < original_chunk.len()
+    {
+        warn!(
+            "✂️ [Question Gen] Trimmed transcript to fit {} tokens (window {}): recent context {} -> {} chars, chunk {} -> {} chars",
+            budget,
+            window,
+            original_recent_context.len(),
+            recent_context.len(),
+            original_chunk.len(),
+            transcript_chunk.len()
+        );
+    }
 
-CRITICAL: Always return at least 1 question. Never return an empty array."#,
+    let prompt = build_question_prompt(
+        prompt_config,
         recent_context,
-        transcript_chunk
+        transcript_chunk,
+        &background_section,
     );
 
     // Use lightweight model for quick question generation
     // Create HTTP client with extended timeout for long-running LLM requests
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(1800)) // 30 minutes
-        .build()
-        .unwrap_or_else(|_| reqwest::Client::new()); // Fallback to default if builder fails
+    let client = http_client(Duration::from_secs(1800))?; // 30 minutes
     
     info!("🚀 [Question Gen] Calling LLM with provider={:?}, model={}, endpoint={:?}", 
           provider, config.model, config.ollama_endpoint);
@@ -310,27 +440,17 @@ CRITICAL: Always return at least 1 question. Never return an empty array."#,
 
     // Parse response - expect JSON array, but handle various formats
     info!("🔍 [Question Gen] Raw LLM response length: {} chars", response.len());
-    info!("🔍 [Question Gen] Raw LLM response preview: {}", &response[..response.len().min(200)]);
+    info!("🔍 [Question Gen] Raw LLM response preview: {}", log_preview(&response));
     
     // Store response for fallback use
     let response_clone = response.clone();
     
-    let questions: Vec<String> = {
-        // Try to parse as JSON first
-        let trimmed = response.trim();
-        
-        // Try to extract JSON array from markdown code blocks or other formatting
-        let json_start = trimmed.find('[').unwrap_or(0);
-        let json_end = trimmed.rfind(']').map(|i| i + 1).unwrap_or(trimmed.len());
-        let json_candidate = &trimmed[json_start..json_end];
-        
-        match serde_json::from_str::<Vec<String>>(json_candidate) {
-            Ok(parsed) => parsed,
-            Err(e) => {
-                warn!("⚠️ [Question Gen] Failed to parse as JSON: {}. Trying text extraction.", e);
-                // If not JSON, try to extract questions from text
-                extract_questions_from_text(&response)
-            }
+    let questions: Vec<String> = match parse_questions_json(&response) {
+        Some(parsed) => parsed,
+        None => {
+            warn!("⚠️ [Question Gen] Failed to parse questions JSON array. Trying text extraction.");
+            // If not JSON, try to extract questions from text
+            extract_questions_from_text(&response)
         }
     };
 
@@ -352,9 +472,9 @@ CRITICAL: Always return at least 1 question. Never return an empty array."#,
             let passes = !trimmed.is_empty() && trimmed.len() <= 1000;
             
             if !passes {
-                warn!("🚫 [Question Gen] Filtered out (empty or too long): '{}'", &trimmed[..trimmed.len().min(50)]);
+                warn!("🚫 [Question Gen] Filtered out (empty or too long): '{}'", log_preview(trimmed));
             } else {
-                info!("✅ [Question Gen] Question accepted: '{}'", &trimmed[..trimmed.len().min(100)]);
+                info!("✅ [Question Gen] Question accepted: '{}'", log_preview(trimmed));
             }
             passes
         })
@@ -373,7 +493,7 @@ CRITICAL: Always return at least 1 question. Never return an empty array."#,
                 } else {
                     trimmed_q.to_string()
                 };
-                info!("✅ [Question Gen] Using raw question (no filtering): '{}'", &final_q[..final_q.len().min(100)]);
+                info!("✅ [Question Gen] Using raw question (no filtering): '{}'", log_preview(&final_q));
                 filtered_questions.push(final_q);
                 break; // Take first one
             }
@@ -391,7 +511,7 @@ CRITICAL: Always return at least 1 question. Never return an empty array."#,
                 } else {
                     trimmed_q.to_string()
                 };
-                info!("✅ [Question Gen] Using extracted question: '{}'", &final_q[..final_q.len().min(100)]);
+                info!("✅ [Question Gen] Using extracted question: '{}'", log_preview(&final_q));
                 filtered_questions.push(final_q);
                 break;
             }
@@ -417,6 +537,7 @@ CRITICAL: Always return at least 1 question. Never return an empty array."#,
         // This should never happen due to fallbacks, but just in case
         vec![Question {
             text: "What needs clarification?".to_string(),
+            category: general_category(),
             context: transcript_chunk.to_string(),
         }]
     } else {
@@ -424,6 +545,7 @@ CRITICAL: Always return at least 1 question. Never return an empty array."#,
             .into_iter()
             .map(|text| {
                 Question {
+                    category: question_category(&text).to_string(),
                     text: text.to_string(),
                     context: transcript_chunk.to_string(),
                 }
@@ -436,7 +558,7 @@ CRITICAL: Always return at least 1 question. Never return an empty array."#,
     
     // Log the full prompt being sent
     info!("🔍 [Question Gen] Full prompt length: {} chars", prompt.len());
-    info!("🔍 [Question Gen] Prompt preview: {}", &prompt[..prompt.len().min(500)]);
+    info!("🔍 [Question Gen] Prompt preview: {}", log_preview(&prompt));
     
     if !questions.is_empty() {
         info!("✅ [Question Gen] Generated {} clarifying question(s)", questions.len());
@@ -447,18 +569,42 @@ CRITICAL: Always return at least 1 question. Never return an empty array."#,
         info!("ℹ️ [Question Gen] No questions generated (all filtered out or LLM returned empty)");
     }
     
-    // Save to debug file
-    save_question_debug(
-        transcript_chunk,
-        recent_context,
-        &prompt,
-        &response,
-        &questions,
-    );
+    if let Some(log) = debug_log {
+        save_question_debug(log, transcript_chunk, recent_context, &prompt, &response, &questions);
+    }
 
     Ok(questions)
 }
 
+/// Returns the body of the first markdown code fence (```json / ``` ...) in the
+/// text, or None if the text contains no complete fence
+pub(crate) fn extract_fenced_block(text: &str) -> Option<&str> {
+    let fence_start = text.find("```")?;
+    let after_fence = &text[fence_start + 3..];
+    // Skip the optional language tag (e.g. "json") up to the end of the line
+    let body_start = after_fence.find('\n').map(|i| i + 1).unwrap_or(0);
+    let body = &after_fence[body_start..];
+    let body_end = body.find("```")?;
+    Some(body[..body_end].trim())
+}
+
+/// Parses the questions JSON array from an LLM response
+///
+/// Code fences are stripped first, then the first JSON array in the remaining
+/// text is parsed; any prose before or after the array is ignored.
+fn parse_questions_json(response: &str) -> Option<Vec<String>> {
+    let trimmed = response.trim();
+    let candidate = extract_fenced_block(trimmed)
+        .filter(|block| block.contains('['))
+        .unwrap_or(trimmed);
+
+    let json_start = candidate.find('[')?;
+    serde_json::Deserializer::from_str(&candidate[json_start..])
+        .into_iter::<Vec<String>>()
+        .next()?
+        .ok()
+}
+
 fn extract_questions_from_text(text: &str) -> Vec<String> {
     // Improved extraction: look for questions in various formats
     let mut questions = Vec::new();
@@ -503,3 +649,192 @@ fn extract_questions_from_text(text: &str) -> Vec<String> {
     questions.dedup();
     questions
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_oversized_context_is_trimmed_to_budget() {
+        let recent = (0..200)
+            .map(|i| format!("Line {}: we talked about the Stripe webhook retry", i))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let chunk = "Sam: Who owns the billing migration?";
+
+        // Within budget nothing changes
+        assert_eq!(fit_question_transcript("Earlier: hi", chunk, 500), ("Earlier: hi", chunk));
+
+        // The chunk is kept whole and the newest context fills the rest
+        let (kept, current) = fit_question_transcript(&recent, chunk, 500);
+        assert_eq!(current, chunk);
+        assert!(rough_token_count(kept) + rough_token_count(current) <= 500);
+        assert!(kept.ends_with("Line 199: we talked about the Stripe webhook retry"));
+        assert!(kept.starts_with("Line "), "cut mid-line: {}", &kept[..20]);
+        assert!(!kept.contains("Line 0:"));
+
+        // A chunk over the budget on its own keeps its most recent part
+        let (kept, current) = fit_question_transcript(&recent, &recent, 100);
+        assert_eq!(kept, "");
+        assert!(rough_token_count(current) <= 100);
+        assert!(current.ends_with("Line 199: we talked about the Stripe webhook retry"));
+    }
+
+    #[test]
+    fn test_default_prompt_config_keeps_built_in_prompt() {
+        let prompt = build_question_prompt(
+            &QuestionPromptConfig::default(),
+            "Earlier: hi",
+            "Sam: ship it",
+            "",
+        );
+        assert!(prompt.starts_with(
+            "You are a meeting facilitator analyzing a transcript to identify items that need clarification from meeting participants.\n\nRecent context:\nEarlier: hi\nCurrent transcript:\nSam: ship it\n"
+        ));
+        assert!(prompt.contains(
+            "participants.\n\nFocus on identifying:\n1. **Missing Assignees**: Action items or tasks mentioned without a clear owner\n"
+        ));
+        assert!(prompt.contains(
+            "7. **Decisions**: What decisions need to be made?\n\nIMPORTANT GUIDELINES:\n"
+        ));
+        assert!(prompt.contains(
+            "question mark \"?\"\n\nEXAMPLE QUESTIONS:\n- \"Who will be handling the Stripe webhook fix?\"\n"
+        ));
+        assert!(prompt.ends_with(
+            "- \"What are the next steps for this project?\"\n\nReturn ONLY a JSON array of question strings. Example:\n[\"Who should be assigned to this task?\", \"What is the deadline for this?\"]\n\nCRITICAL: Always return at least 1 question. Never return an empty array."
+        ));
+    }
+
+    #[test]
+    fn test_prompt_assembled_from_custom_config() {
+        let config = QuestionPromptConfig {
+            preamble: "You help a finance team close open points.".to_string(),
+            focus_areas: vec!["**Budget Owners**: Spend without an approver".to_string()],
+            examples: vec![
+                "Who signs off on the Q3 marketing spend?".to_string(),
+                "Which cost center does the new laptop order go to?".to_string(),
+            ],
+            output_instructions: "Return ONLY a JSON array of strings.".to_string(),
+        };
+        let prompt = build_question_prompt(&config, "", "Ana: we need ten laptops", "");
+
+        assert!(prompt.starts_with("You help a finance team close open points.\n"));
+        assert!(prompt.contains(
+            "Focus on identifying:\n1. **Budget Owners**: Spend without an approver\n\nIMPORTANT GUIDELINES:"
+        ));
+        assert!(prompt.contains(
+            "EXAMPLE QUESTIONS:\n- \"Who signs off on the Q3 marketing spend?\"\n- \"Which cost center does the new laptop order go to?\"\n\nReturn ONLY a JSON array of strings."
+        ));
+        assert!(!prompt.contains("Stripe webhook"));
+        assert!(!prompt.contains("Missing Assignees"));
+
+        // Without focus areas or examples their sections are left out
+        let config = QuestionPromptConfig {
+            focus_areas: Vec::new(),
+            examples: Vec::new(),
+            ..config
+        };
+        let prompt = build_question_prompt(&config, "", "Ana: we need ten laptops", "");
+        assert!(!prompt.contains("Focus on identifying"));
+        assert!(!prompt.contains("EXAMPLE QUESTIONS"));
+        assert!(prompt.contains("meeting participants.\n\nIMPORTANT GUIDELINES:"));
+        assert!(prompt
+            .contains("meeting\n- End each question with a question mark \"?\"\n\nReturn ONLY"));
+    }
+
+    #[test]
+    fn test_concurrent_debug_blocks_do_not_interleave() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logs").join(QUESTION_DEBUG_FILE);
+        let writers: Vec<_> = ["meeting-a", "meeting-b"]
+            .into_iter()
+            .map(|meeting_id| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    let chunk = format!("{}\n", meeting_id).repeat(2000);
+                    let questions = [Question {
+                        text: format!("Who owns {}?", meeting_id),
+                        category: "owner".to_string(),
+                        context: String::new(),
+                    }];
+                    for _ in 0..20 {
+                        let block = format_question_debug_block(
+                            Some(meeting_id), 0, &chunk, "", "prompt", "[]", &questions,
+                        );
+                        append_question_debug(&path, &block).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let log = std::fs::read_to_string(&path).unwrap();
+        let blocks: Vec<&str> = log.split("\nTIMESTAMP: ").skip(1).collect();
+        assert_eq!(blocks.len(), 40);
+        for block in blocks {
+            let header = block.lines().nth(1).unwrap();
+            let meeting_id = header.strip_prefix("MEETING: ").unwrap();
+            let other = if meeting_id == "meeting-a" { "meeting-b" } else { "meeting-a" };
+            assert!(!block.contains(other), "block of {} interleaved", meeting_id);
+            assert_eq!(block.matches(meeting_id).count(), 2000 + 2);
+        }
+    }
+
+    #[test]
+    fn test_question_categories() {
+        assert_eq!(question_category("Who will fix the webhook?"), "owner");
+        assert_eq!(question_category("Is Sam responsible for PROJ-404?"), "owner");
+        assert_eq!(question_category("When should the migration ship?"), "deadline");
+        assert_eq!(question_category("What's the deadline for PROJ-404?"), "deadline");
+        assert_eq!(question_category("Which API version do we target?"), "general");
+        // Words only count whole: "update" isn't a date, "procedure" isn't due
+        assert_eq!(question_category("Should the update procedure change?"), "general");
+    }
+
+    #[test]
+    fn test_parse_plain_json_array() {
+        let parsed = parse_questions_json(r#"["Who owns this?", "When is it due?"]"#);
+        assert_eq!(
+            parsed,
+            Some(vec!["Who owns this?".to_string(), "When is it due?".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_parse_fenced_array_with_surrounding_prose() {
+        let response = "Here are the questions I came up with:\n\n```json\n[\"Who will fix the webhook?\", \"What's the deadline for PROJ-404?\"]\n```\n\nThese cover the [main] gaps in the discussion.";
+        assert_eq!(
+            parse_questions_json(response),
+            Some(vec![
+                "Who will fix the webhook?".to_string(),
+                "What's the deadline for PROJ-404?".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_untagged_fence() {
+        let response = "```\n[\"Is the migration blocked?\"]\n```";
+        assert_eq!(
+            parse_questions_json(response),
+            Some(vec!["Is the migration blocked?".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_parse_array_with_trailing_commentary_inside_fence() {
+        let response = "```json\n[\"Who is the owner?\"]\nNote: [optional] follow-ups omitted.\n```";
+        assert_eq!(
+            parse_questions_json(response),
+            Some(vec!["Who is the owner?".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_parse_returns_none_without_array() {
+        assert_eq!(parse_questions_json("Who owns the release?"), None);
+        assert_eq!(parse_questions_json("```json\nnot json\n```"), None);
+    }
+}
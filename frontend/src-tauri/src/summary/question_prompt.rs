@@ -0,0 +1,223 @@
+//! Wording of the clarifying question prompt
+//!
+//! The instruction preamble, the areas the model is asked to focus on, the
+//! few-shot example questions and the output instructions can be tuned per
+//! team, e.g. to ask about budget owners instead of deadlines. The transcript
+//! sections and the general guidelines stay built in, and the output
+//! instructions have to keep asking for a JSON array, which is what the
+//! response parser expects.
+
+use log::warn;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+
+const CONFIG_STORE: &str = "question-prompt.json";
+const CONFIG_KEY: &str = "config";
+
+const MAX_FOCUS_AREAS: usize = 20;
+const MAX_EXAMPLES: usize = 20;
+
+/// Phrase the output instructions must contain, compared case-insensitively
+const JSON_ARRAY_PHRASE: &str = "json array";
+
+const DEFAULT_PREAMBLE: &str = "You are a meeting facilitator analyzing a transcript to identify items that need clarification from meeting participants.";
+
+const DEFAULT_FOCUS_AREAS: [&str; 7] = [
+    "**Missing Assignees**: Action items or tasks mentioned without a clear owner",
+    "**Unclear Deadlines**: Tasks without specific due dates or vague timelines (\"soon\", \"later\")",
+    "**Ambiguous Requirements**: Items that need more specific definition or acceptance criteria",
+    "**Missing Priorities**: Tasks that lack urgency/importance classification",
+    "**Unclear Dependencies**: References to blockers or prerequisites that aren't well defined",
+    "**Next Steps**: What should happen next?",
+    "**Decisions**: What decisions need to be made?",
+];
+
+const DEFAULT_EXAMPLES: [&str; 5] = [
+    "Who will be handling the Stripe webhook fix?",
+    "What's the deadline for the API documentation?",
+    "Can we confirm the priority for the VPN issue - is it blocking the release?",
+    "Is the database migration dependent on the auth service being ready?",
+    "What are the next steps for this project?",
+];
+
+const DEFAULT_OUTPUT_INSTRUCTIONS: &str = r#"Return ONLY a JSON array of question strings. Example:
+["Who should be assigned to this task?", "What is the deadline for this?"]
+
+CRITICAL: Always return at least 1 question. Never return an empty array."#;
+
+/// Saved wording of the question prompt; fields left out of the stored
+/// config keep the built-in text
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuestionPromptConfig {
+    /// Opening instruction telling the model its role
+    pub preamble: String,
+    /// Listed, numbered, under "Focus on identifying"; empty leaves the list out
+    pub focus_areas: Vec<String>,
+    /// Few-shot example questions; empty leaves the examples out
+    pub examples: Vec<String>,
+    /// Closing instructions on the response format; must ask for a JSON array
+    pub output_instructions: String,
+}
+
+impl Default for QuestionPromptConfig {
+    fn default() -> Self {
+        Self {
+            preamble: DEFAULT_PREAMBLE.to_string(),
+            focus_areas: DEFAULT_FOCUS_AREAS.iter().map(|s| s.to_string()).collect(),
+            examples: DEFAULT_EXAMPLES.iter().map(|s| s.to_string()).collect(),
+            output_instructions: DEFAULT_OUTPUT_INSTRUCTIONS.to_string(),
+        }
+    }
+}
+
+impl QuestionPromptConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.preamble.trim().is_empty() {
+            return Err("Question prompt preamble must not be empty".to_string());
+        }
+        if !self
+            .output_instructions
+            .to_lowercase()
+            .contains(JSON_ARRAY_PHRASE)
+        {
+            return Err(
+                "Question prompt output instructions must ask for a JSON array of questions"
+                    .to_string(),
+            );
+        }
+        if self.focus_areas.len() > MAX_FOCUS_AREAS {
+            return Err(format!(
+                "Question prompt allows at most {} focus areas",
+                MAX_FOCUS_AREAS
+            ));
+        }
+        if self.examples.len() > MAX_EXAMPLES {
+            return Err(format!(
+                "Question prompt allows at most {} example questions",
+                MAX_EXAMPLES
+            ));
+        }
+        if self
+            .focus_areas
+            .iter()
+            .chain(&self.examples)
+            .any(|item| item.trim().is_empty())
+        {
+            return Err("Question prompt focus areas and examples must not be empty".to_string());
+        }
+        Ok(())
+    }
+
+    /// "Focus on identifying" section, ending in a blank line, or empty
+    pub fn focus_section(&self) -> String {
+        if self.focus_areas.is_empty() {
+            return String::new();
+        }
+        let items: Vec<String> = self
+            .focus_areas
+            .iter()
+            .enumerate()
+            .map(|(i, area)| format!("{}. {}", i + 1, area.trim()))
+            .collect();
+        format!("Focus on identifying:\n{}\n\n", items.join("\n"))
+    }
+
+    /// "EXAMPLE QUESTIONS" section, ending in a blank line, or empty
+    pub fn examples_section(&self) -> String {
+        if self.examples.is_empty() {
+            return String::new();
+        }
+        let items: Vec<String> = self
+            .examples
+            .iter()
+            .map(|example| format!("- \"{}\"", example.trim()))
+            .collect();
+        format!("EXAMPLE QUESTIONS:\n{}\n\n", items.join("\n"))
+    }
+}
+
+pub fn load_question_prompt_config<R: Runtime>(app: &AppHandle<R>) -> QuestionPromptConfig {
+    let stored = app
+        .store(CONFIG_STORE)
+        .ok()
+        .and_then(|store| store.get(CONFIG_KEY));
+    let config = match stored.map(serde_json::from_value::<QuestionPromptConfig>) {
+        Some(Ok(config)) => config,
+        Some(Err(e)) => {
+            warn!(
+                "⚠️ Invalid question prompt config in store, using defaults: {}",
+                e
+            );
+            return QuestionPromptConfig::default();
+        }
+        None => return QuestionPromptConfig::default(),
+    };
+    // Edited by hand into something the response parser can't rely on
+    if let Err(e) = config.validate() {
+        warn!("⚠️ {}, using the built-in question prompt", e);
+        return QuestionPromptConfig::default();
+    }
+    config
+}
+
+pub fn save_question_prompt_config<R: Runtime>(
+    app: &AppHandle<R>,
+    config: &QuestionPromptConfig,
+) -> Result<(), String> {
+    let store = app
+        .store(CONFIG_STORE)
+        .map_err(|e| format!("Failed to open question prompt store: {}", e))?;
+    let value = serde_json::to_value(config)
+        .map_err(|e| format!("Failed to serialize question prompt config: {}", e))?;
+    store.set(CONFIG_KEY, value);
+    store
+        .save()
+        .map_err(|e| format!("Failed to save question prompt config: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config_is_valid_and_partial_configs_keep_defaults() {
+        assert_eq!(QuestionPromptConfig::default().validate(), Ok(()));
+
+        let config: QuestionPromptConfig =
+            serde_json::from_str(r#"{"examples": ["Who owns the budget?"]}"#).unwrap();
+        assert_eq!(config.preamble, DEFAULT_PREAMBLE);
+        assert_eq!(config.focus_areas.len(), DEFAULT_FOCUS_AREAS.len());
+        assert_eq!(
+            config.examples_section(),
+            "EXAMPLE QUESTIONS:\n- \"Who owns the budget?\"\n\n"
+        );
+    }
+
+    #[test]
+    fn test_validation_requires_json_array_output() {
+        let config = QuestionPromptConfig {
+            output_instructions: "Answer with a bulleted list of questions.".to_string(),
+            ..Default::default()
+        };
+        assert!(config.validate().unwrap_err().contains("JSON array"));
+
+        let config = QuestionPromptConfig {
+            output_instructions: "Reply with a json ARRAY of strings only.".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(config.validate(), Ok(()));
+
+        let config = QuestionPromptConfig {
+            examples: vec!["  ".to_string()],
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+        let config = QuestionPromptConfig {
+            preamble: String::new(),
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+}
@@ -0,0 +1,558 @@
+//! Holding back clarifying question popups while the user presents
+//!
+//! Popups are suppressed while recording or while the screen is shared,
+//! depending on the mode, and during daily focus hours. Questions generated
+//! meanwhile are buffered and released, without repeats, once suppression
+//! lifts; their status in `clarifying_questions` follows along.
+
+use crate::database::repositories::question::QuestionsRepository;
+use crate::state::AppState;
+use crate::summary::question_generator::Question;
+use chrono::{Local, NaiveTime};
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tauri_plugin_store::StoreExt;
+
+const CONFIG_STORE: &str = "question-suppression.json";
+const CONFIG_KEY: &str = "config";
+
+/// How often screen sharing and focus hours are checked
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Emitted with the `SuppressionState` whenever it changes
+pub const SUPPRESSION_CHANGED_EVENT: &str = "question-suppression-changed";
+/// Emitted with the held back questions once suppression lifts
+pub const QUESTIONS_RELEASED_EVENT: &str = "clarifying-questions-released";
+
+/// When question popups are held back, besides focus hours
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SuppressionMode {
+    /// Questions pop up as soon as they are generated
+    #[default]
+    Off,
+    /// Held back while a recording runs and released when it stops
+    WhileRecording,
+    /// Held back while the screen is shared
+    WhileScreenSharing,
+}
+
+/// Daily do-not-disturb window in local time; an `end` before `start`
+/// spans midnight
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FocusWindow {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl FocusWindow {
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct QuestionSuppressionConfig {
+    pub mode: SuppressionMode,
+    pub focus_hours: Vec<FocusWindow>,
+}
+
+impl QuestionSuppressionConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self
+            .focus_hours
+            .iter()
+            .any(|window| window.start == window.end)
+        {
+            return Err("Focus hours must start and end at different times".to_string());
+        }
+        Ok(())
+    }
+}
+
+pub fn load_question_suppression_config<R: Runtime>(
+    app: &AppHandle<R>,
+) -> QuestionSuppressionConfig {
+    let stored = app
+        .store(CONFIG_STORE)
+        .ok()
+        .and_then(|store| store.get(CONFIG_KEY));
+    match stored.map(serde_json::from_value::<QuestionSuppressionConfig>) {
+        Some(Ok(config)) => config,
+        Some(Err(e)) => {
+            warn!(
+                "⚠️ Invalid question suppression config in store, using defaults: {}",
+                e
+            );
+            QuestionSuppressionConfig::default()
+        }
+        None => QuestionSuppressionConfig::default(),
+    }
+}
+
+fn save_question_suppression_config<R: Runtime>(
+    app: &AppHandle<R>,
+    config: &QuestionSuppressionConfig,
+) -> Result<(), String> {
+    let store = app
+        .store(CONFIG_STORE)
+        .map_err(|e| format!("Failed to open question suppression store: {}", e))?;
+    let value = serde_json::to_value(config)
+        .map_err(|e| format!("Failed to serialize question suppression config: {}", e))?;
+    store.set(CONFIG_KEY, value);
+    store
+        .save()
+        .map_err(|e| format!("Failed to save question suppression config: {}", e))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SuppressionReason {
+    Recording,
+    ScreenSharing,
+    FocusHours,
+}
+
+/// What the frontend shows about suppression
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuppressionState {
+    pub mode: SuppressionMode,
+    pub focus_hours: Vec<FocusWindow>,
+    pub suppressed: bool,
+    pub reason: Option<SuppressionReason>,
+    /// Questions waiting for suppression to lift
+    pub buffered: usize,
+}
+
+/// Stored as `status` in `clarifying_questions`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuestionStatus {
+    Shown,
+    Suppressed,
+    Released,
+    /// Held back and dropped on release, as it repeated another question
+    Duplicate,
+    /// Sent to the meeting chat, waiting for the extension to confirm it
+    Sending,
+    /// Posted in the meeting chat
+    Sent,
+    /// The extension couldn't post it
+    Failed,
+    /// No confirmation arrived in time
+    Unknown,
+}
+
+impl QuestionStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            QuestionStatus::Shown => "shown",
+            QuestionStatus::Suppressed => "suppressed",
+            QuestionStatus::Released => "released",
+            QuestionStatus::Duplicate => "duplicate",
+            QuestionStatus::Sending => "sending",
+            QuestionStatus::Sent => "sent",
+            QuestionStatus::Failed => "failed",
+            QuestionStatus::Unknown => "unknown",
+        }
+    }
+}
+
+/// A question held back while popups are suppressed
+#[derive(Debug, Serialize)]
+pub struct PendingQuestion {
+    /// Row in `clarifying_questions`; None for questions raised while
+    /// recording, which aren't stored
+    pub id: Option<String>,
+    #[serde(flatten)]
+    pub question: Question,
+}
+
+/// Held back questions let out once suppression lifted
+#[derive(Debug, Default)]
+pub struct Release {
+    pub questions: Vec<PendingQuestion>,
+    /// Repeats of a released question, which are dropped
+    pub duplicates: Vec<PendingQuestion>,
+}
+
+/// Decides whether questions pop up now or wait
+#[derive(Debug, Default)]
+pub struct QuestionScheduler {
+    config: QuestionSuppressionConfig,
+    recording: bool,
+    screen_sharing: bool,
+    buffered: Vec<PendingQuestion>,
+}
+
+impl QuestionScheduler {
+    pub fn new(config: QuestionSuppressionConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    pub fn config(&self) -> &QuestionSuppressionConfig {
+        &self.config
+    }
+
+    pub fn set_config(&mut self, config: QuestionSuppressionConfig) {
+        self.config = config;
+    }
+
+    pub fn set_recording(&mut self, recording: bool) {
+        self.recording = recording;
+    }
+
+    pub fn set_screen_sharing(&mut self, screen_sharing: bool) {
+        self.screen_sharing = screen_sharing;
+    }
+
+    /// Why popups are suppressed at `now`, or None if they aren't
+    pub fn reason(&self, now: NaiveTime) -> Option<SuppressionReason> {
+        match self.config.mode {
+            SuppressionMode::WhileRecording if self.recording => {
+                return Some(SuppressionReason::Recording)
+            }
+            SuppressionMode::WhileScreenSharing if self.screen_sharing => {
+                return Some(SuppressionReason::ScreenSharing)
+            }
+            _ => {}
+        }
+        self.config
+            .focus_hours
+            .iter()
+            .any(|window| window.contains(now))
+            .then_some(SuppressionReason::FocusHours)
+    }
+
+    pub fn state(&self, now: NaiveTime) -> SuppressionState {
+        let reason = self.reason(now);
+        SuppressionState {
+            mode: self.config.mode,
+            focus_hours: self.config.focus_hours.clone(),
+            suppressed: reason.is_some(),
+            reason,
+            buffered: self.buffered.len(),
+        }
+    }
+
+    /// Buffers questions until `release` lets them out
+    pub fn hold(&mut self, questions: impl IntoIterator<Item = PendingQuestion>) {
+        self.buffered.extend(questions);
+    }
+
+    /// The held back questions, oldest first and without repeats, once
+    /// suppression has lifted
+    pub fn release(&mut self, now: NaiveTime) -> Option<Release> {
+        if self.buffered.is_empty() || self.reason(now).is_some() {
+            return None;
+        }
+        let mut release = Release::default();
+        let mut seen = HashSet::new();
+        for pending in self.buffered.drain(..) {
+            if seen.insert(dedup_key(&pending.question.text)) {
+                release.questions.push(pending);
+            } else {
+                release.duplicates.push(pending);
+            }
+        }
+        Some(release)
+    }
+}
+
+/// Question text without case and punctuation, so rephrasings that only
+/// differ in those count as repeats
+fn dedup_key(text: &str) -> String {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+static SCHEDULER: Lazy<Mutex<QuestionScheduler>> =
+    Lazy::new(|| Mutex::new(QuestionScheduler::default()));
+static EMITTED_STATE: Mutex<Option<SuppressionState>> = Mutex::new(None);
+
+/// Known processes that only run while the screen is shared: Zoom's share
+/// host and the macOS Screen Sharing agents
+#[cfg(target_os = "macos")]
+const SCREEN_SHARING_PROCESSES: &str = "^(CptHost|ScreensharingAgent|screensharingd)$";
+
+#[cfg(target_os = "macos")]
+fn detect_screen_sharing() -> bool {
+    std::process::Command::new("pgrep")
+        .arg(SCREEN_SHARING_PROCESSES)
+        .stdout(std::process::Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+#[cfg(not(target_os = "macos"))]
+fn detect_screen_sharing() -> bool {
+    false
+}
+
+/// Loads the saved settings and starts watching for screen sharing and
+/// focus hours
+pub fn init_question_suppression<R: Runtime>(app: &AppHandle<R>) {
+    let config = load_question_suppression_config(app);
+    info!("🔕 Question suppression: {:?}", config);
+    if let Ok(mut scheduler) = SCHEDULER.lock() {
+        scheduler.set_config(config);
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            let watch_sharing = SCHEDULER
+                .lock()
+                .map(|scheduler| scheduler.config().mode == SuppressionMode::WhileScreenSharing)
+                .unwrap_or(false);
+            let sharing = watch_sharing
+                && tokio::task::spawn_blocking(detect_screen_sharing)
+                    .await
+                    .unwrap_or(false);
+            update(&app, |scheduler| scheduler.set_screen_sharing(sharing)).await;
+        }
+    });
+}
+
+pub fn current_state() -> SuppressionState {
+    let now = Local::now().time();
+    SCHEDULER
+        .lock()
+        .map(|scheduler| scheduler.state(now))
+        .unwrap_or_else(|poisoned| poisoned.into_inner().state(now))
+}
+
+pub fn is_suppressed() -> bool {
+    current_state().suppressed
+}
+
+/// Saves new settings; questions held back are released if they no longer
+/// apply
+pub async fn set_question_suppression<R: Runtime>(
+    app: &AppHandle<R>,
+    mode: SuppressionMode,
+    focus_hours: Option<Vec<FocusWindow>>,
+) -> Result<SuppressionState, String> {
+    let mut config = load_question_suppression_config(app);
+    config.mode = mode;
+    if let Some(focus_hours) = focus_hours {
+        config.focus_hours = focus_hours;
+    }
+    config.validate()?;
+    save_question_suppression_config(app, &config)?;
+    update(app, |scheduler| scheduler.set_config(config)).await;
+    Ok(current_state())
+}
+
+pub async fn set_recording<R: Runtime>(app: &AppHandle<R>, recording: bool) {
+    update(app, |scheduler| scheduler.set_recording(recording)).await;
+}
+
+/// Holds questions back until suppression lifts
+pub async fn hold_questions<R: Runtime>(app: &AppHandle<R>, questions: Vec<PendingQuestion>) {
+    info!("🔕 Holding back {} clarifying question(s)", questions.len());
+    update(app, |scheduler| scheduler.hold(questions)).await;
+}
+
+/// Applies a change, then releases held back questions and emits the state
+/// if it changed
+async fn update<R: Runtime>(app: &AppHandle<R>, change: impl FnOnce(&mut QuestionScheduler)) {
+    let now = Local::now().time();
+    let (state, release) = {
+        let mut scheduler = SCHEDULER
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        change(&mut scheduler);
+        let release = scheduler.release(now);
+        (scheduler.state(now), release)
+    };
+
+    if let Some(release) = release {
+        publish_release(app, release).await;
+    }
+
+    let changed = {
+        let mut emitted = EMITTED_STATE
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let changed = emitted.as_ref() != Some(&state);
+        *emitted = Some(state.clone());
+        changed
+    };
+    if changed {
+        if let Err(e) = app.emit(SUPPRESSION_CHANGED_EVENT, &state) {
+            warn!("⚠️ Failed to emit {}: {}", SUPPRESSION_CHANGED_EVENT, e);
+        }
+    }
+}
+
+async fn publish_release<R: Runtime>(app: &AppHandle<R>, release: Release) {
+    info!(
+        "🔔 Releasing {} held back question(s), {} duplicate(s) dropped",
+        release.questions.len(),
+        release.duplicates.len()
+    );
+    if let Some(state) = app.try_state::<AppState>() {
+        let pool = state.db_manager.pool();
+        let stored_ids = |questions: &[PendingQuestion]| -> Vec<String> {
+            questions.iter().filter_map(|q| q.id.clone()).collect()
+        };
+        let updates = [
+            (stored_ids(&release.questions), QuestionStatus::Released),
+            (stored_ids(&release.duplicates), QuestionStatus::Duplicate),
+        ];
+        for (ids, status) in updates {
+            if ids.is_empty() {
+                continue;
+            }
+            if let Err(e) = QuestionsRepository::set_status(pool, &ids, status).await {
+                warn!("⚠️ Failed to mark questions as {}: {}", status.as_str(), e);
+            }
+        }
+    }
+
+    let payload = serde_json::json!({ "questions": release.questions });
+    if let Err(e) = app.emit(QUESTIONS_RELEASED_EVENT, payload) {
+        warn!("⚠️ Failed to emit {}: {}", QUESTIONS_RELEASED_EVENT, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    fn pending(id: &str, text: &str) -> PendingQuestion {
+        PendingQuestion {
+            id: Some(id.to_string()),
+            question: Question {
+                text: text.to_string(),
+                category: "owner".to_string(),
+                context: "Sam: we ship the Stripe fix".to_string(),
+            },
+        }
+    }
+
+    fn ids(questions: &[PendingQuestion]) -> Vec<&str> {
+        questions.iter().filter_map(|q| q.id.as_deref()).collect()
+    }
+
+    #[test]
+    fn test_recording_buffers_until_it_stops() {
+        let mut scheduler = QuestionScheduler::new(QuestionSuppressionConfig {
+            mode: SuppressionMode::WhileRecording,
+            focus_hours: Vec::new(),
+        });
+        let noon = time(12, 0);
+        assert_eq!(scheduler.reason(noon), None);
+
+        scheduler.set_recording(true);
+        assert_eq!(scheduler.reason(noon), Some(SuppressionReason::Recording));
+        scheduler.hold([pending("a", "Who owns the Stripe fix?")]);
+        scheduler.hold([
+            pending("b", "When is it due?"),
+            pending("c", "who owns the Stripe fix"),
+        ]);
+        assert_eq!(scheduler.state(noon).buffered, 3);
+        assert!(scheduler.release(noon).is_none());
+
+        // Screen sharing doesn't matter in this mode
+        scheduler.set_screen_sharing(true);
+        scheduler.set_recording(false);
+        let release = scheduler.release(noon).unwrap();
+        assert_eq!(ids(&release.questions), vec!["a", "b"]);
+        assert_eq!(ids(&release.duplicates), vec!["c"]);
+        assert_eq!(scheduler.state(noon).buffered, 0);
+        assert!(scheduler.release(noon).is_none());
+    }
+
+    #[test]
+    fn test_screen_sharing_and_mode_changes() {
+        let mut scheduler = QuestionScheduler::new(QuestionSuppressionConfig {
+            mode: SuppressionMode::WhileScreenSharing,
+            focus_hours: Vec::new(),
+        });
+        let noon = time(12, 0);
+        scheduler.set_recording(true);
+        assert!(!scheduler.state(noon).suppressed);
+
+        scheduler.set_screen_sharing(true);
+        let state = scheduler.state(noon);
+        assert_eq!(
+            (state.suppressed, state.reason),
+            (true, Some(SuppressionReason::ScreenSharing))
+        );
+        scheduler.hold([pending("a", "Who owns the Stripe fix?")]);
+
+        // Turning suppression off lets the buffered questions out
+        scheduler.set_config(QuestionSuppressionConfig::default());
+        let release = scheduler.release(noon).unwrap();
+        assert_eq!(ids(&release.questions), vec!["a"]);
+    }
+
+    #[test]
+    fn test_focus_hours() {
+        let mut scheduler = QuestionScheduler::new(QuestionSuppressionConfig {
+            mode: SuppressionMode::Off,
+            focus_hours: vec![
+                FocusWindow {
+                    start: time(9, 0),
+                    end: time(10, 30),
+                },
+                FocusWindow {
+                    start: time(22, 0),
+                    end: time(6, 0),
+                },
+            ],
+        });
+        let suppressed = [time(9, 0), time(10, 29), time(23, 0), time(1, 0)];
+        for now in suppressed {
+            assert_eq!(
+                scheduler.reason(now),
+                Some(SuppressionReason::FocusHours),
+                "{}",
+                now
+            );
+        }
+        for now in [time(8, 59), time(10, 30), time(12, 0), time(6, 0)] {
+            assert_eq!(scheduler.reason(now), None, "{}", now);
+        }
+
+        scheduler.hold([pending("a", "Who owns it?")]);
+        assert!(scheduler.release(time(9, 30)).is_none());
+        assert_eq!(
+            ids(&scheduler.release(time(10, 45)).unwrap().questions),
+            vec!["a"]
+        );
+
+        let empty_window = QuestionSuppressionConfig {
+            mode: SuppressionMode::Off,
+            focus_hours: vec![FocusWindow {
+                start: time(9, 0),
+                end: time(9, 0),
+            }],
+        };
+        assert!(empty_window.validate().is_err());
+    }
+}
@@ -0,0 +1,23 @@
+use once_cell::sync::Lazy;
+use std::sync::Arc;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Summaries allowed to talk to the LLM at the same time
+pub const MAX_CONCURRENT_SUMMARIES: usize = 2;
+
+static SUMMARY_SLOTS: Lazy<Arc<Semaphore>> =
+    Lazy::new(|| Arc::new(Semaphore::new(MAX_CONCURRENT_SUMMARIES)));
+
+/// The semaphore shared by every summary run
+pub fn summary_slots() -> Arc<Semaphore> {
+    SUMMARY_SLOTS.clone()
+}
+
+/// Waits for a free slot; used by full summaries, which must run
+pub async fn acquire_summary_slot() -> OwnedSemaphorePermit {
+    SUMMARY_SLOTS
+        .clone()
+        .acquire_owned()
+        .await
+        .expect("summary semaphore is never closed")
+}
@@ -0,0 +1,752 @@
+//! Checks the summary's quotes and factual claims against the transcript
+//!
+//! Quoted strings, and summary lines stating numbers or dates, are looked up
+//! in the transcript so an auditor can see which of them nobody actually
+//! said. Matching is fuzzy to allow for transcription: words are compared
+//! lowercased and without punctuation, small spelling differences are
+//! tolerated, and compounds match whether written together or apart
+//! ("webhook" and "web hook"). Numbers and dates have to match exactly.
+
+use crate::api::TranscriptSegment;
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Transcript timestamps, `[t=mm:ss]` citations and `[3]` footnote markers
+static MARKER_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"\[(?:t\s*=\s*)?\d{1,2}:\d{2}(?::\d{2})?(?:\s*[-–]\s*\d{1,2}:\d{2}(?::\d{2})?)?\]|\[\^?\d+\]",
+    )
+    .unwrap()
+});
+static LIST_MARKER_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(?:[-*+]|\d+[.)])\s+").unwrap());
+/// "straight" and “curly” quotes
+static QUOTE_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r#""([^"\n]+)"|“([^”\n]+)”"#).unwrap());
+/// 2024-03-15, compared as "march 15" since speakers rarely say the year
+static ISO_DATE_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b\d{4}-(\d{2})-(\d{2})\b").unwrap());
+/// 80,000 and 1,250,000
+static DIGIT_GROUP_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b\d{1,3}(?:,\d{3})+\b").unwrap());
+
+const MONTHS: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+const WEEKDAYS: [&str; 7] = [
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+    "sunday",
+];
+/// Words that say nothing about whether a claim was made
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "has", "have", "in", "is",
+    "it", "its", "of", "on", "or", "that", "the", "their", "this", "to", "was", "were", "will",
+    "with", "we", "they", "he", "she", "i", "our", "per", "about", "around", "than", "into",
+    "after", "before", "until", "next", "last", "all", "also", "team",
+];
+
+/// Quotes need this many words; shorter quoted strings are names or terms
+const MIN_QUOTE_WORDS: usize = 3;
+/// Share of a quote's words a passage must contain
+const QUOTE_FOUND_SHARE: f64 = 0.85;
+const QUOTE_PARTIAL_SHARE: f64 = 0.5;
+/// Share of a claim's other words a passage must contain besides its facts
+const CLAIM_FOUND_SHARE: f64 = 0.5;
+const CLAIM_PARTIAL_SHARE: f64 = 0.25;
+
+/// Whether and how strictly generated summaries are checked
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuoteCheckConfig {
+    /// Check each generated summary and store the result with it
+    pub enabled: bool,
+    /// Share of the checked quotes and claims (0.0 - 1.0) that may go
+    /// unfound before the summary gets a validation warning
+    pub max_unverified_share: f64,
+}
+
+impl Default for QuoteCheckConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_unverified_share: 0.2,
+        }
+    }
+}
+
+impl QuoteCheckConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.max_unverified_share) {
+            return Err("Unverified claim share must be between 0 and 1".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClaimKind {
+    /// A quoted string, expected close to verbatim
+    Quote,
+    /// A line stating numbers or dates, usually paraphrased
+    Fact,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Claim {
+    pub kind: ClaimKind,
+    /// The quote without quotation marks, or the summary line
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    NotFound,
+    PartiallyFound,
+    Found,
+}
+
+/// How well the transcript backs up one claim
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClaimCheck {
+    pub kind: ClaimKind,
+    pub text: String,
+    pub verdict: Verdict,
+    /// How much of the claim the best matching passage covers (0.0 - 1.0)
+    pub score: f64,
+    /// First segment of the best matching passage; None when not found
+    pub segment_id: Option<String>,
+    pub timestamp: Option<String>,
+    pub audio_start_time: Option<f64>,
+    /// Numbers and dates of the claim the passage doesn't contain
+    pub missing: Vec<String>,
+}
+
+/// Every checked claim in summary order, stored as `quote_check` in the
+/// result JSON
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QuoteCheck {
+    pub claims: Vec<ClaimCheck>,
+    pub found: usize,
+    pub partially_found: usize,
+    pub not_found: usize,
+}
+
+/// End of the validation warning added for too many unfound claims
+const WARNING_SUFFIX: &str = "quotes and claims could not be found in the transcript";
+
+impl QuoteCheck {
+    /// A validation warning when more claims went unfound than allowed
+    pub fn warning(&self, config: &QuoteCheckConfig) -> Option<String> {
+        let total = self.claims.len();
+        (total > 0 && self.not_found as f64 / total as f64 > config.max_unverified_share)
+            .then(|| format!("{} of {} {}", self.not_found, total, WARNING_SUFFIX))
+    }
+
+    /// Whether a stored validation warning came from an earlier check
+    pub fn is_warning(warning: &str) -> bool {
+        warning.ends_with(WARNING_SUFFIX)
+    }
+}
+
+/// "15th" -> "15", "3pm" -> "3" "pm", "05" -> "5"
+fn split_number(word: &str) -> Vec<String> {
+    let digits_end = word
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(word.len());
+    if digits_end == 0 {
+        return vec![word.to_string()];
+    }
+    let number = match word[..digits_end].trim_start_matches('0') {
+        "" => "0",
+        number => number,
+    };
+    match &word[digits_end..] {
+        "" | "st" | "nd" | "rd" | "th" => vec![number.to_string()],
+        unit => vec![number.to_string(), unit.to_string()],
+    }
+}
+
+/// Lowercased words without punctuation, with dates and numbers written
+/// the same way on both sides
+fn normalize(text: &str) -> Vec<String> {
+    let text = text.to_lowercase();
+    let text = ISO_DATE_RE.replace_all(&text, |caps: &Captures| {
+        let month = caps[1].parse::<usize>().ok();
+        match month.and_then(|month| MONTHS.get(month.checked_sub(1)?)) {
+            Some(month) => format!("{} {}", month, &caps[2]),
+            None => caps[0].to_string(),
+        }
+    });
+    let text = DIGIT_GROUP_RE.replace_all(&text, |caps: &Captures| caps[0].replace(',', ""));
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .flat_map(split_number)
+        .collect()
+}
+
+fn is_number(word: &str) -> bool {
+    word.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Numbers and dates, which have to match exactly
+fn is_fact(word: &str) -> bool {
+    // "may" is more often the verb than the month
+    is_number(word) || (MONTHS.contains(&word) && word != "may") || WEEKDAYS.contains(&word)
+}
+
+/// Edits tolerated between two spellings of a word this long; short words
+/// have too many neighbours ("ship", "shop") to allow any
+fn allowed_edits(len: usize) -> usize {
+    match len {
+        0..=4 => 0,
+        5..=8 => 1,
+        _ => 2,
+    }
+}
+
+/// Edit distance in characters, counting two swapped neighbours
+/// ("recieve") as one edit
+fn edit_distance(a: &str, b: &str) -> usize {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let substitution = d[i - 1][j - 1] + usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = substitution.min(d[i - 1][j] + 1).min(d[i][j - 1] + 1);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+/// Whether two words are likely the same word transcribed differently
+fn similar(a: &str, b: &str) -> bool {
+    if a == b {
+        return true;
+    }
+    if is_fact(a) || is_fact(b) {
+        return false;
+    }
+    let (len_a, len_b) = (a.chars().count(), b.chars().count());
+    let allowed = allowed_edits(len_a.min(len_b));
+    allowed > 0 && len_a.abs_diff(len_b) <= allowed && edit_distance(a, b) <= allowed
+}
+
+/// A passage of two adjacent segments, so quotes split across a segment
+/// boundary are found too
+struct Passage<'a> {
+    segment: &'a TranscriptSegment,
+    /// The words, and each pair of adjacent words written together
+    words: HashSet<String>,
+}
+
+fn passages(segments: &[TranscriptSegment]) -> Vec<Passage<'_>> {
+    let segments: Vec<(&TranscriptSegment, Vec<String>)> = segments
+        .iter()
+        .map(|segment| (segment, normalize(&segment.text)))
+        .filter(|(_, words)| !words.is_empty())
+        .collect();
+    (0..segments.len())
+        .map(|i| {
+            let words: Vec<&String> = segments[i..(i + 2).min(segments.len())]
+                .iter()
+                .flat_map(|(_, words)| words)
+                .collect();
+            let joined = words
+                .windows(2)
+                .map(|pair| format!("{}{}", pair[0], pair[1]));
+            Passage {
+                segment: segments[i].0,
+                words: words
+                    .iter()
+                    .map(|word| word.to_string())
+                    .chain(joined)
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+/// A claim word and the transcript words it may have been transcribed as
+struct Needle {
+    word: String,
+    variants: HashSet<String>,
+    /// Variants of this word written together with the next one
+    joined_variants: HashSet<String>,
+}
+
+fn needles(words: &[String], vocabulary: &HashSet<&str>) -> Vec<Needle> {
+    let variants = |word: &str| -> HashSet<String> {
+        vocabulary
+            .iter()
+            .filter(|candidate| similar(word, candidate))
+            .map(|candidate| candidate.to_string())
+            .collect()
+    };
+    words
+        .iter()
+        .enumerate()
+        .map(|(i, word)| Needle {
+            word: word.clone(),
+            variants: variants(word),
+            joined_variants: match words.get(i + 1) {
+                Some(next) if !is_fact(word) && !is_fact(next) => {
+                    variants(&format!("{}{}", word, next))
+                }
+                _ => HashSet::new(),
+            },
+        })
+        .collect()
+}
+
+/// Which of the needles the passage contains
+fn hits(needles: &[Needle], passage: &Passage) -> Vec<bool> {
+    let contains = |variants: &HashSet<String>| variants.iter().any(|v| passage.words.contains(v));
+    let mut hits = vec![false; needles.len()];
+    let mut i = 0;
+    while i < needles.len() {
+        if i + 1 < needles.len() && contains(&needles[i].joined_variants) {
+            hits[i] = true;
+            hits[i + 1] = true;
+            i += 2;
+            continue;
+        }
+        hits[i] = contains(&needles[i].variants);
+        i += 1;
+    }
+    hits
+}
+
+fn share(hits: impl Iterator<Item = bool>) -> Option<f64> {
+    let (found, total) = hits.fold((0usize, 0usize), |(found, total), hit| {
+        (found + usize::from(hit), total + 1)
+    });
+    (total > 0).then(|| found as f64 / total as f64)
+}
+
+/// The verdict and score of a claim against one passage
+fn judge(kind: ClaimKind, needles: &[Needle], hits: &[bool]) -> (Verdict, f64) {
+    match kind {
+        ClaimKind::Quote => {
+            let score = share(hits.iter().copied()).unwrap_or(0.0);
+            let verdict = if score >= QUOTE_FOUND_SHARE {
+                Verdict::Found
+            } else if score >= QUOTE_PARTIAL_SHARE {
+                Verdict::PartiallyFound
+            } else {
+                Verdict::NotFound
+            };
+            (verdict, score)
+        }
+        ClaimKind::Fact => {
+            let of = |fact: bool| {
+                needles
+                    .iter()
+                    .zip(hits)
+                    .filter(move |(needle, _)| {
+                        is_fact(&needle.word) == fact && !STOPWORDS.contains(&needle.word.as_str())
+                    })
+                    .map(|(_, hit)| *hit)
+            };
+            let facts = share(of(true)).unwrap_or(0.0);
+            let context = share(of(false)).unwrap_or(1.0);
+            let verdict = if facts == 1.0 && context >= CLAIM_FOUND_SHARE {
+                Verdict::Found
+            } else if facts > 0.0 && context >= CLAIM_PARTIAL_SHARE {
+                Verdict::PartiallyFound
+            } else {
+                Verdict::NotFound
+            };
+            (verdict, (facts + context) / 2.0)
+        }
+    }
+}
+
+/// Quoted strings and lines stating numbers or dates, in summary order
+///
+/// Headings, timestamps, citations and footnote markers are skipped, and
+/// quotes are taken out of the line before it is checked for facts.
+pub fn extract_claims(markdown: &str) -> Vec<Claim> {
+    let mut claims: Vec<Claim> = Vec::new();
+    let push = |claims: &mut Vec<Claim>, kind: ClaimKind, text: &str| {
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !claims
+            .iter()
+            .any(|claim| claim.kind == kind && claim.text == text)
+        {
+            claims.push(Claim { kind, text });
+        }
+    };
+
+    for line in markdown.lines() {
+        let line = line.trim();
+        let is_table_rule =
+            line.starts_with('|') && line.chars().all(|c| matches!(c, '|' | '-' | ':' | ' '));
+        if line.is_empty() || line.starts_with('#') || line.starts_with("[^") || is_table_rule {
+            continue;
+        }
+        let line = MARKER_RE.replace_all(line, "");
+        let line = line.replace("**", "").replace("__", "").replace('`', "");
+        let line = LIST_MARKER_RE.replace(line.trim_start(), "").into_owned();
+        let line = if line.starts_with('|') {
+            let cells: Vec<&str> = line
+                .split('|')
+                .map(str::trim)
+                .filter(|c| !c.is_empty())
+                .collect();
+            cells.join("; ")
+        } else {
+            line
+        };
+
+        let rest = QUOTE_RE.replace_all(&line, |caps: &Captures| {
+            let quote = caps
+                .get(1)
+                .or_else(|| caps.get(2))
+                .map_or("", |m| m.as_str())
+                .trim();
+            if normalize(quote).len() >= MIN_QUOTE_WORDS {
+                push(&mut claims, ClaimKind::Quote, quote);
+                String::new()
+            } else {
+                quote.to_string()
+            }
+        });
+        if normalize(&rest).iter().any(|word| is_fact(word)) {
+            push(&mut claims, ClaimKind::Fact, rest.trim());
+        }
+    }
+    claims
+}
+
+/// Looks up each claim in the transcript
+pub fn check_claims(claims: &[Claim], segments: &[TranscriptSegment]) -> QuoteCheck {
+    let passages = passages(segments);
+    let vocabulary: HashSet<&str> = passages
+        .iter()
+        .flat_map(|passage| passage.words.iter().map(String::as_str))
+        .collect();
+
+    let mut check = QuoteCheck::default();
+    for claim in claims {
+        let needles = needles(&normalize(&claim.text), &vocabulary);
+        let mut best: Option<(&Passage, Verdict, f64, Vec<bool>)> = None;
+        for passage in &passages {
+            let hits = hits(&needles, passage);
+            let (verdict, score) = judge(claim.kind, &needles, &hits);
+            // Later passages win ties, so a match within one segment points
+            // at that segment rather than the one before it
+            if best
+                .as_ref()
+                .map_or(true, |(_, v, s, _)| (verdict, score) >= (*v, *s))
+            {
+                best = Some((passage, verdict, score, hits));
+            }
+        }
+
+        let verdict = best
+            .as_ref()
+            .map_or(Verdict::NotFound, |(_, verdict, _, _)| *verdict);
+        let located = best
+            .as_ref()
+            .filter(|_| verdict != Verdict::NotFound)
+            .map(|(passage, ..)| passage.segment);
+        let missing = needles
+            .iter()
+            .enumerate()
+            .filter(|(i, needle)| {
+                is_fact(&needle.word) && !best.as_ref().is_some_and(|(.., hits)| hits[*i])
+            })
+            .map(|(_, needle)| needle.word.clone())
+            .collect();
+        match verdict {
+            Verdict::Found => check.found += 1,
+            Verdict::PartiallyFound => check.partially_found += 1,
+            Verdict::NotFound => check.not_found += 1,
+        }
+        check.claims.push(ClaimCheck {
+            kind: claim.kind,
+            text: claim.text.clone(),
+            verdict,
+            score: best.as_ref().map_or(0.0, |(_, _, score, _)| *score),
+            segment_id: located.map(|segment| segment.id.clone()),
+            timestamp: located.map(|segment| segment.timestamp.clone()),
+            audio_start_time: located.and_then(|segment| segment.audio_start_time),
+            missing,
+        });
+    }
+    check
+}
+
+/// Extracts the summary's quotes and claims and looks them up in the transcript
+pub fn verify_quotes(markdown: &str, segments: &[TranscriptSegment]) -> QuoteCheck {
+    check_claims(&extract_claims(markdown), segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::segment;
+
+    /// A 5-second segment said at wall-clock `timestamp`
+    fn segment_at(id: &str, timestamp: &str, start: f64, text: &str) -> TranscriptSegment {
+        TranscriptSegment {
+            timestamp: timestamp.to_string(),
+            ..segment(id, text, start, start + 5.0)
+        }
+    }
+
+    fn transcript() -> Vec<TranscriptSegment> {
+        vec![
+            segment_at(
+                "s1",
+                "10:00:00",
+                0.0,
+                "Morning everyone, let's get started.",
+            ),
+            segment_at(
+                "s2",
+                "10:00:05",
+                5.0,
+                "Sam: The stripe web hook keeps timing out, I'll fix it by Friday.",
+            ),
+            segment_at(
+                "s3",
+                "10:00:10",
+                10.0,
+                "Ana: I don't think we should ship this without more tests.",
+            ),
+            segment_at(
+                "s4",
+                "10:00:15",
+                15.0,
+                "Ana: Marketing needs 80,000 dollars for the",
+            ),
+            segment_at("s5", "10:00:20", 20.0, "launch campaign on March 15th."),
+            segment_at(
+                "s6",
+                "10:00:25",
+                25.0,
+                "Sam: Churn went down to 3 percent last quarter.",
+            ),
+        ]
+    }
+
+    fn check_one(kind: ClaimKind, text: &str) -> ClaimCheck {
+        let claim = Claim {
+            kind,
+            text: text.to_string(),
+        };
+        check_claims(&[claim], &transcript()).claims.remove(0)
+    }
+
+    #[test]
+    fn test_normalize_writes_numbers_and_dates_alike() {
+        assert_eq!(
+            normalize("$80,000 on 2025-03-15"),
+            ["80000", "on", "march", "15"]
+        );
+        assert_eq!(
+            normalize("March 15th at 3pm, 05 items"),
+            ["march", "15", "at", "3", "pm", "5", "items"]
+        );
+    }
+
+    #[test]
+    fn test_similar_allows_small_misspellings_but_not_other_numbers() {
+        assert!(similar("recieve", "receive"));
+        assert!(similar("timing", "timin"));
+        assert!(!similar("ship", "shop"));
+        assert!(!similar("launching", "launch"));
+        assert!(!similar("80000", "8000"));
+        assert!(!similar("friday", "fridays"));
+    }
+
+    #[test]
+    fn test_verbatim_quote_is_found_with_its_segment() {
+        let check = check_one(
+            ClaimKind::Quote,
+            "I don't think we should ship this without more tests",
+        );
+        assert_eq!(check.verdict, Verdict::Found);
+        assert_eq!(check.score, 1.0);
+        assert_eq!(check.segment_id.as_deref(), Some("s3"));
+        assert_eq!(check.timestamp.as_deref(), Some("10:00:10"));
+        assert_eq!(check.audio_start_time, Some(10.0));
+    }
+
+    #[test]
+    fn test_quote_matches_despite_transcription_spelling() {
+        // "webhook" against "web hook", "timeing" against "timing"
+        let check = check_one(ClaimKind::Quote, "The Stripe webhook keeps timeing out");
+        assert_eq!(check.verdict, Verdict::Found, "{:?}", check);
+        assert_eq!(check.segment_id.as_deref(), Some("s2"));
+
+        // And the other way round
+        let segments = vec![segment("s1", "The stripe webhook is flaky", 0.0, 5.0)];
+        let claim = Claim {
+            kind: ClaimKind::Quote,
+            text: "stripe web hook is flaky".to_string(),
+        };
+        assert_eq!(
+            check_claims(&[claim], &segments).claims[0].verdict,
+            Verdict::Found
+        );
+    }
+
+    #[test]
+    fn test_quote_across_a_segment_boundary_is_found() {
+        let check = check_one(
+            ClaimKind::Quote,
+            "Marketing needs 80,000 dollars for the launch campaign",
+        );
+        assert_eq!(check.verdict, Verdict::Found, "{:?}", check);
+        assert_eq!(check.segment_id.as_deref(), Some("s4"));
+    }
+
+    #[test]
+    fn test_paraphrased_quote_is_partially_found() {
+        let check = check_one(ClaimKind::Quote, "we will never ship without tests");
+        assert_eq!(check.verdict, Verdict::PartiallyFound, "{:?}", check);
+        assert_eq!(check.segment_id.as_deref(), Some("s3"));
+        assert!(check.score < QUOTE_FOUND_SHARE);
+    }
+
+    #[test]
+    fn test_invented_quote_is_not_found() {
+        let check = check_one(
+            ClaimKind::Quote,
+            "Security signed off on the rollout yesterday",
+        );
+        assert_eq!(check.verdict, Verdict::NotFound, "{:?}", check);
+        assert_eq!(check.segment_id, None);
+        assert_eq!(check.timestamp, None);
+    }
+
+    #[test]
+    fn test_paraphrased_fact_is_found() {
+        let check = check_one(
+            ClaimKind::Fact,
+            "Marketing budget of $80,000 for the campaign launching 2025-03-15",
+        );
+        assert_eq!(check.verdict, Verdict::Found, "{:?}", check);
+        assert!(check.missing.is_empty());
+
+        let check = check_one(
+            ClaimKind::Fact,
+            "Sam fixes the Stripe webhook timeouts by Friday",
+        );
+        assert_eq!(check.verdict, Verdict::Found, "{:?}", check);
+        assert_eq!(check.segment_id.as_deref(), Some("s2"));
+    }
+
+    #[test]
+    fn test_fact_with_a_changed_number_is_only_partially_found() {
+        let check = check_one(
+            ClaimKind::Fact,
+            "Marketing needs $80,000 for the launch campaign on March 20",
+        );
+        assert_eq!(check.verdict, Verdict::PartiallyFound, "{:?}", check);
+        assert_eq!(check.missing, ["20"]);
+    }
+
+    #[test]
+    fn test_absent_facts_are_not_found() {
+        let check = check_one(
+            ClaimKind::Fact,
+            "Churn dropped to 7% after the pricing change",
+        );
+        assert_eq!(check.verdict, Verdict::NotFound, "{:?}", check);
+        assert_eq!(check.missing, ["7"]);
+
+        let check = check_one(ClaimKind::Fact, "Headcount grows by 12 engineers in June");
+        assert_eq!(check.verdict, Verdict::NotFound, "{:?}", check);
+        assert_eq!(check.missing, ["12", "june"]);
+    }
+
+    #[test]
+    fn test_extract_claims_skips_markup_and_short_quotes() {
+        let markdown = "## Decisions 2025\n\n\
+            1. Ana: \"I don't think we should ship this\" [t=00:10]\n\
+            - Launch of \"Project Falcon\" on **March 15** [2]\n\
+            - Sam will look into the webhook\n\
+            | Task | Owner | Due | Segment Time stamp |\n\
+            | --- | --- | --- | --- |\n\
+            | Fix webhook | Sam | 2025-03-14 | [00:05–00:10] |\n\
+            [^2]: Chunk 2, 00:15-00:25\n";
+
+        let claims = extract_claims(markdown);
+
+        assert_eq!(
+            claims,
+            [
+                Claim {
+                    kind: ClaimKind::Quote,
+                    text: "I don't think we should ship this".to_string(),
+                },
+                Claim {
+                    kind: ClaimKind::Fact,
+                    text: "Launch of Project Falcon on March 15".to_string(),
+                },
+                Claim {
+                    kind: ClaimKind::Fact,
+                    text: "Fix webhook; Sam; 2025-03-14".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_warning_once_unverified_share_is_exceeded() {
+        let markdown = "- Ana said \"we should ship this without more tests\"\n\
+            - Churn went down to 3% last quarter\n\
+            - Revenue grew 40% in May\n";
+        let config = QuoteCheckConfig::default();
+
+        let check = verify_quotes(markdown, &transcript());
+
+        assert_eq!(
+            (check.found, check.partially_found, check.not_found),
+            (2, 0, 1)
+        );
+        let warning = check.warning(&config).unwrap();
+        assert_eq!(
+            warning,
+            "1 of 3 quotes and claims could not be found in the transcript"
+        );
+        assert!(QuoteCheck::is_warning(&warning));
+        let lenient = QuoteCheckConfig {
+            max_unverified_share: 0.5,
+            ..config
+        };
+        assert_eq!(check.warning(&lenient), None);
+        assert_eq!(
+            QuoteCheck::default().warning(&QuoteCheckConfig::default()),
+            None
+        );
+    }
+}
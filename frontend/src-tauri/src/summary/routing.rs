@@ -0,0 +1,172 @@
+//! Picking the summary provider by transcript size
+//!
+//! Local models are fine for short meetings but crawl through long ones in
+//! many chunk passes. With routing on, requests that don't name a provider
+//! and model send transcripts below the threshold to the short-transcript
+//! model (e.g. Ollama) and larger ones to the long-transcript model (e.g. a
+//! cloud provider). Off by default, leaving the manually selected model.
+
+use crate::summary::llm_client::LLMProvider;
+use crate::summary::processor::rough_token_count;
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_THRESHOLD_TOKENS: usize = 8000;
+const MIN_THRESHOLD_TOKENS: usize = 500;
+
+/// Provider and model a routing rule sends transcripts to
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelRoute {
+    pub provider: String,
+    pub model: String,
+}
+
+impl ModelRoute {
+    fn validate(&self, label: &str) -> Result<(), String> {
+        if self.provider.trim().is_empty() || self.model.trim().is_empty() {
+            return Err(format!("Choose a provider and model for {}", label));
+        }
+        LLMProvider::from_str(&self.provider)
+            .map(|_| ())
+            .map_err(|e| format!("Invalid provider for {}: {}", label, e))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProviderRoutingConfig {
+    pub enabled: bool,
+    /// Transcripts of at least this many (estimated) tokens are long
+    pub threshold_tokens: usize,
+    pub short_transcripts: ModelRoute,
+    pub long_transcripts: ModelRoute,
+}
+
+impl Default for ProviderRoutingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold_tokens: DEFAULT_THRESHOLD_TOKENS,
+            short_transcripts: ModelRoute::default(),
+            long_transcripts: ModelRoute::default(),
+        }
+    }
+}
+
+/// Which rule a transcript fell under
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptSize {
+    Short,
+    Long,
+}
+
+/// The routing decision for one summary, stored with its result
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RoutingDecision {
+    pub size: TranscriptSize,
+    pub transcript_tokens: usize,
+    pub threshold_tokens: usize,
+    pub provider: String,
+    pub model: String,
+}
+
+impl ProviderRoutingConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if self.threshold_tokens < MIN_THRESHOLD_TOKENS {
+            return Err(format!(
+                "Routing threshold must be at least {} tokens",
+                MIN_THRESHOLD_TOKENS
+            ));
+        }
+        self.short_transcripts.validate("short transcripts")?;
+        self.long_transcripts.validate("long transcripts")
+    }
+
+    /// The model for a transcript of `transcript_tokens`; None when routing is off
+    pub fn route(&self, transcript_tokens: usize) -> Option<RoutingDecision> {
+        if !self.enabled {
+            return None;
+        }
+        let (size, route) = if transcript_tokens >= self.threshold_tokens {
+            (TranscriptSize::Long, &self.long_transcripts)
+        } else {
+            (TranscriptSize::Short, &self.short_transcripts)
+        };
+        Some(RoutingDecision {
+            size,
+            transcript_tokens,
+            threshold_tokens: self.threshold_tokens,
+            provider: route.provider.clone(),
+            model: route.model.clone(),
+        })
+    }
+
+    /// Routes `text` unless the request already names a provider or model
+    pub fn route_unselected(
+        &self,
+        model_provider: Option<&str>,
+        model_name: Option<&str>,
+        text: &str,
+    ) -> Option<RoutingDecision> {
+        let given = |value: Option<&str>| value.is_some_and(|v| !v.trim().is_empty());
+        if given(model_provider) || given(model_name) {
+            return None;
+        }
+        self.route(rough_token_count(text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> ProviderRoutingConfig {
+        ProviderRoutingConfig {
+            enabled: true,
+            threshold_tokens: 1000,
+            short_transcripts: ModelRoute {
+                provider: "ollama".to_string(),
+                model: "llama3.1:8b".to_string(),
+            },
+            long_transcripts: ModelRoute {
+                provider: "claude".to_string(),
+                model: "claude-3-5-sonnet-latest".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_large_transcript_routes_to_cloud_provider() {
+        let config = config();
+        assert_eq!(config.validate(), Ok(()));
+        let long = "word ".repeat(2000);
+
+        let decision = config.route_unselected(None, Some(" "), &long).unwrap();
+        assert_eq!(decision.size, TranscriptSize::Long);
+        assert_eq!(decision.provider, "claude");
+        assert_eq!(decision.model, "claude-3-5-sonnet-latest");
+        assert_eq!(decision.transcript_tokens, 2500);
+
+        let short = config
+            .route_unselected(None, None, "Sam sends the deck")
+            .unwrap();
+        assert_eq!(
+            (short.size, short.provider.as_str()),
+            (TranscriptSize::Short, "ollama")
+        );
+
+        // A manual selection, or routing being off, leaves the model alone
+        assert_eq!(config.route_unselected(Some("openai"), None, &long), None);
+        assert_eq!(ProviderRoutingConfig::default().route(1_000_000), None);
+        assert_eq!(ProviderRoutingConfig::default().validate(), Ok(()));
+
+        let incomplete = ProviderRoutingConfig {
+            long_transcripts: ModelRoute::default(),
+            ..config
+        };
+        assert!(incomplete.validate().is_err());
+    }
+}
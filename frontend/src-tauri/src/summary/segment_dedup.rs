@@ -0,0 +1,247 @@
+//! Drops near-duplicate transcript segments before summarization
+//!
+//! When the microphone and system audio both pick up the same speech, the
+//! transcript gets two segments with nearly the same text over the same
+//! stretch of the recording, and the model reads everything twice. A segment
+//! is dropped when another one overlapping it in time has text at least
+//! `similarity` alike.
+//!
+//! Stored segments don't carry the transcriber's confidence, so of two
+//! duplicates the one with more text is kept, as the more complete
+//! transcription; on a tie the earlier one.
+
+use crate::api::TranscriptSegment;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Share of the shorter segment's time range the two have to overlap by
+const MIN_TIME_OVERLAP: f64 = 0.5;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SegmentDedupConfig {
+    pub enabled: bool,
+    /// How alike (0.0 - 1.0) two overlapping segments' text has to be for
+    /// one of them to be dropped
+    pub similarity: f64,
+}
+
+impl Default for SegmentDedupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            similarity: 0.85,
+        }
+    }
+}
+
+impl SegmentDedupConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.5..=1.0).contains(&self.similarity) {
+            return Err("Duplicate segment similarity must be between 0.5 and 1".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Lowercased words of `text`, punctuation removed
+fn normalize(text: &str) -> String {
+    text.chars()
+        .map(|c| {
+            if c.is_alphanumeric() {
+                c.to_lowercase().next().unwrap_or(c)
+            } else {
+                ' '
+            }
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Dice coefficient of the character pairs of two normalized texts
+fn similarity(a: &str, b: &str) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+    let pairs = |text: &str| {
+        let chars: Vec<char> = text.chars().collect();
+        let mut counts: HashMap<(char, char), usize> = HashMap::new();
+        for pair in chars.windows(2) {
+            *counts.entry((pair[0], pair[1])).or_default() += 1;
+        }
+        (counts, chars.len().saturating_sub(1))
+    };
+    let (a_pairs, a_total) = pairs(a);
+    let (b_pairs, b_total) = pairs(b);
+    if a_total + b_total == 0 {
+        return 0.0;
+    }
+    let shared: usize = a_pairs
+        .iter()
+        .map(|(pair, count)| (*count).min(b_pairs.get(pair).copied().unwrap_or(0)))
+        .sum();
+    2.0 * shared as f64 / (a_total + b_total) as f64
+}
+
+fn time_range(segment: &TranscriptSegment) -> Option<(f64, f64)> {
+    let start = segment.audio_start_time?;
+    let end = segment
+        .audio_end_time
+        .or_else(|| segment.duration.map(|duration| start + duration))
+        .unwrap_or(start);
+    Some((start, end.max(start)))
+}
+
+/// Whether two ranges overlap by at least `MIN_TIME_OVERLAP` of the shorter
+fn overlaps(a: (f64, f64), b: (f64, f64)) -> bool {
+    let overlap = a.1.min(b.1) - a.0.max(b.0);
+    let shorter = (a.1 - a.0).min(b.1 - b.0);
+    if shorter <= 0.0 {
+        // A segment without a duration overlaps when it starts inside the other
+        return overlap >= 0.0;
+    }
+    overlap / shorter >= MIN_TIME_OVERLAP
+}
+
+/// Segments left after removing duplicates, and the ids removed
+#[derive(Debug, Clone, Default)]
+pub struct DedupedSegments {
+    pub segments: Vec<TranscriptSegment>,
+    pub removed: Vec<String>,
+}
+
+/// Removes near-duplicate segments from segments in recording order
+///
+/// Segments without a recording time are always kept.
+pub fn dedup_segments(
+    segments: Vec<TranscriptSegment>,
+    config: &SegmentDedupConfig,
+) -> DedupedSegments {
+    if !config.enabled {
+        return DedupedSegments {
+            segments,
+            removed: Vec::new(),
+        };
+    }
+    let ranges: Vec<Option<(f64, f64)>> = segments.iter().map(time_range).collect();
+    let texts: Vec<String> = segments.iter().map(|s| normalize(&s.text)).collect();
+    let mut removed = vec![false; segments.len()];
+
+    for i in 0..segments.len() {
+        let Some(range) = ranges[i] else { continue };
+        for j in i + 1..segments.len() {
+            if removed[i] {
+                break;
+            }
+            let Some(other) = ranges[j] else { continue };
+            if removed[j] || !overlaps(range, other) {
+                continue;
+            }
+            if texts[i].is_empty() || similarity(&texts[i], &texts[j]) < config.similarity {
+                continue;
+            }
+            if texts[j].len() > texts[i].len() {
+                removed[i] = true;
+            } else {
+                removed[j] = true;
+            }
+        }
+    }
+
+    let mut result = DedupedSegments::default();
+    for (segment, removed) in segments.into_iter().zip(removed) {
+        if removed {
+            result.removed.push(segment.id);
+        } else {
+            result.segments.push(segment);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::segment;
+
+    fn ids(deduped: &DedupedSegments) -> Vec<&str> {
+        deduped.segments.iter().map(|s| s.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_overlapping_duplicates_are_removed() {
+        let segments = vec![
+            segment("mic", "We ship the billing fix on Friday.", 10.0, 14.0),
+            segment("system", "we ship the billing fix on friday", 10.4, 14.2),
+            segment("mic-2", "Ana owns the rollout plan", 15.0, 18.0),
+            segment("system-2", "Ana owns the rollout plan, yeah.", 15.1, 18.6),
+        ];
+
+        let deduped = dedup_segments(segments, &SegmentDedupConfig::default());
+
+        assert_eq!(ids(&deduped), ["mic", "system-2"]);
+        assert_eq!(deduped.removed, ["system", "mic-2"]);
+    }
+
+    #[test]
+    fn test_distinct_back_to_back_segments_are_kept() {
+        let segments = vec![
+            // Same words, one right after the other: said twice, not captured twice
+            segment("a", "Can everyone hear me?", 0.0, 2.0),
+            segment("b", "Can everyone hear me?", 2.0, 4.0),
+            // Overlapping in time but different speech
+            segment("c", "The release is on Friday", 4.0, 7.0),
+            segment("d", "I still need the test results", 4.5, 7.5),
+            TranscriptSegment {
+                audio_start_time: None,
+                ..segment("note", "The release is on Friday", 0.0, 0.0)
+            },
+        ];
+
+        let deduped = dedup_segments(segments, &SegmentDedupConfig::default());
+        assert_eq!(ids(&deduped), ["a", "b", "c", "d", "note"]);
+        assert!(deduped.removed.is_empty());
+
+        // Turned off, overlapping duplicates stay too
+        let off = SegmentDedupConfig {
+            enabled: false,
+            ..SegmentDedupConfig::default()
+        };
+        let duplicates = vec![
+            segment("mic", "Ship it", 0.0, 1.0),
+            segment("system", "Ship it", 0.0, 1.0),
+        ];
+        assert_eq!(dedup_segments(duplicates, &off).segments.len(), 2);
+    }
+
+    #[test]
+    fn test_similarity_threshold_is_configurable() {
+        assert_eq!(
+            similarity(&normalize("billing fix"), &normalize("Billing, fix!")),
+            1.0
+        );
+        let loose = SegmentDedupConfig {
+            similarity: 0.5,
+            ..SegmentDedupConfig::default()
+        };
+        let segments = vec![
+            segment("mic", "we ship the billing fix", 0.0, 3.0),
+            segment("system", "we ship the billing fix on friday", 0.0, 3.0),
+        ];
+        assert_eq!(
+            dedup_segments(segments.clone(), &SegmentDedupConfig::default())
+                .segments
+                .len(),
+            2
+        );
+        assert_eq!(ids(&dedup_segments(segments, &loose)), ["system"]);
+        assert!(SegmentDedupConfig {
+            similarity: 1.5,
+            ..SegmentDedupConfig::default()
+        }
+        .validate()
+        .is_err());
+    }
+}
@@ -1,20 +1,357 @@
-use crate::database::repositories::{
-    meeting::MeetingsRepository, setting::SettingsRepository, summary::SummaryProcessesRepository,
+use crate::database::models::{ChunkBoundary, NamedPipeline, ParticipantMap};
+use crate::database::repositories::{Repositories, SummaryProcessesRepo};
+use crate::export::transcript::{render_transcript, TranscriptTextOptions};
+use crate::http::client_factory::{bypass_proxy_for, http_client};
+use crate::logging::log_preview;
+use crate::api::TranscriptSegment;
+use crate::summary::action_items::{
+    action_items_prompt, normalize_action_item_timestamps, parse_extracted_action_items,
+    ExtractedActionItems, ACTION_ITEMS_SYSTEM_PROMPT,
 };
-use crate::summary::llm_client::LLMProvider;
-use crate::summary::processor::{extract_meeting_name_from_markdown, generate_meeting_summary};
+use crate::people::participants::{attendees_prompt_note, merge_participants};
+use crate::summary::anonymize::{
+    anonymize_participants, assign_pseudonyms, detect_participants, DetectedParticipant,
+};
+use crate::summary::content_filter::{load_content_filter, sanitize_summary, ContentFilterConfig};
+use crate::summary::citations::verify_citations;
+use crate::summary::context::{prepare_user_context, reserved_tags, BackgroundContext};
+use crate::summary::llm_client::{generate_summary, LLMProvider};
+use crate::summary::named_pipelines::apply_to_config;
+use crate::summary::processor::{
+    classify_model_size, compact_transcript, extract_meeting_name_from_markdown, generate_meeting_summaries,
+    generate_meeting_summary, preview_chunking, preview_summary_prompts, rough_token_count,
+    uses_single_pass, validate_summary_quality, with_fallback, ChunkingPreview, GeneratedSummaries,
+    RetryPolicy, SummaryMode, SummaryPromptPreview, SummaryTarget, TemplateReport, TranscriptChunking,
+};
+use crate::summary::pipeline_config::{load_summary_pipeline_config, SummaryPipelineConfig};
+use crate::summary::progress::{clear_progress, report_progress};
+use crate::summary::provenance::{apply_footnotes, locate_segments, ChunkRange, SegmentSpan};
+use crate::summary::quality::score_summary;
+use crate::summary::quote_check::{verify_quotes, QuoteCheck};
+use crate::summary::routing::RoutingDecision;
+use crate::summary::tickets::{coverage_note, ticket_coverage, TicketCoverage, TicketMatcher};
+use crate::summary::queue::acquire_summary_slot;
+use crate::summary::segment_dedup::dedup_segments;
+use crate::summary::structured::StructuredSummary;
+use crate::summary::templates;
 use crate::ollama::metadata::ModelMetadataCache;
-use sqlx::SqlitePool;
+use crate::webhooks::delivery::notify_summary_completed;
+use futures_util::future::join_all;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::time::{Duration, Instant};
 use tauri::AppHandle;
-use tracing::{error, info, warn};
+use tracing::{error, info, info_span, warn, Instrument};
 use once_cell::sync::Lazy;
 
 // Global cache for model metadata (5 minute TTL)
-static METADATA_CACHE: Lazy<ModelMetadataCache> = Lazy::new(|| {
+pub(crate) static METADATA_CACHE: Lazy<ModelMetadataCache> = Lazy::new(|| {
     ModelMetadataCache::new(Duration::from_secs(300))
 });
 
+/// Template used when neither the request nor the meeting specifies one
+const DEFAULT_TEMPLATE_ID: &str = "daily_standup";
+
+/// Provider, model and template to use for one summary run
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedSummarySettings {
+    pub model_provider: String,
+    pub model_name: String,
+    pub template_id: String,
+}
+
+/// A provider and model to summarize with in a comparison
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelChoice {
+    pub provider: String,
+    pub model: String,
+}
+
+/// One model's summary in a side by side comparison
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SummaryComparison {
+    pub provider: String,
+    pub model: String,
+    /// None if the run failed
+    pub markdown: Option<String>,
+    pub error: Option<String>,
+    /// Seconds spent generating, not counting the wait for a summary slot
+    pub processing_time: f64,
+    pub num_chunks: i64,
+    /// Rough token estimates (4 characters per token) of the transcript and
+    /// the summary
+    pub input_tokens: usize,
+    pub output_tokens: usize,
+    /// Whether the model stopped at its output limit
+    pub truncated: bool,
+}
+
+/// Treats empty strings from the frontend the same as a missing value
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|v| !v.trim().is_empty())
+}
+
+/// Template ids to summarize with: the resolved one first, then the other
+/// requested ones without duplicates
+fn run_templates(resolved: String, requested: &[String]) -> Vec<String> {
+    let mut template_ids = vec![resolved];
+    for id in requested {
+        if !id.trim().is_empty() && !template_ids.contains(id) {
+            template_ids.push(id.clone());
+        }
+    }
+    template_ids
+}
+
+/// Providers a run tries in order, from `SummaryService::with_fallback_targets`
+struct RunTargets {
+    targets: Vec<SummaryTarget>,
+    /// The primary couldn't be used, so the first target is already a fallback
+    primary_skipped: bool,
+}
+
+/// Run-wide inputs for turning one template's report into the stored result
+struct ReportFinisher<'a> {
+    meeting_id: &'a str,
+    /// The text that was summarized
+    text: &'a str,
+    segments: &'a [TranscriptSegment],
+    chunk_ranges: &'a [ChunkRange],
+    /// Where the transcript segments are in `text`; None without provenance
+    segment_spans: Option<&'a [SegmentSpan]>,
+    /// Check the report's `[t=mm:ss]` citations against `segments`
+    citations: bool,
+    chunking: &'a TranscriptChunking,
+    generated_by: &'a SummaryTarget,
+    fallback: bool,
+    anonymized: bool,
+    skim: bool,
+    /// The named pipeline the run was started with
+    pipeline_id: Option<&'a str>,
+    /// Why the run's provider was picked, when routing picked it
+    routing: Option<&'a RoutingDecision>,
+    /// Set when the custom prompt had to be cut to fit its budget
+    user_context_warning: Option<&'a str>,
+    pipeline: &'a SummaryPipelineConfig,
+    content_filter: &'a ContentFilterConfig,
+}
+
+impl ReportFinisher<'_> {
+    /// Adds provenance markers, checks time citations, filters, scores and
+    /// checks ticket coverage
+    ///
+    /// Returns the result JSON and the meeting title taken from the report,
+    /// if it had one.
+    fn finish(
+        &self,
+        template_id: &str,
+        mut final_markdown: String,
+        truncated: bool,
+        structured: Option<&StructuredSummary>,
+    ) -> (Value, Option<String>) {
+        let meeting_id = self.meeting_id;
+        let pipeline = self.pipeline;
+        info!("final markdown ({}) is {}", template_id, &final_markdown);
+
+        // Chunk tags become numbered markers; tags in the title are dropped
+        let provenance_map = self.segment_spans.map(|spans| {
+            let (markdown, map) = apply_footnotes(&final_markdown, self.chunk_ranges, spans);
+            info!(
+                "🔗 Provenance for meeting_id {} ({}): {} markers",
+                meeting_id,
+                template_id,
+                map.len()
+            );
+            final_markdown = markdown;
+            map
+        });
+
+        // Fabricated time citations are removed and flagged in the map
+        let citation_map = self.citations.then(|| {
+            let (markdown, map) = verify_citations(&final_markdown, self.segments);
+            let fabricated = map.iter().filter(|citation| citation.fabricated).count();
+            if fabricated > 0 {
+                warn!(
+                    "⚠️ Removed {} of {} time citations for meeting_id {} ({}) that match no transcript segment",
+                    fabricated,
+                    map.len(),
+                    meeting_id,
+                    template_id
+                );
+            } else {
+                info!(
+                    "🕒 Time citations for meeting_id {} ({}): {} verified",
+                    meeting_id,
+                    template_id,
+                    map.len()
+                );
+            }
+            final_markdown = markdown;
+            map
+        });
+
+        // Filter before the title is extracted so it is covered too
+        final_markdown = sanitize_summary(&final_markdown, self.content_filter);
+        final_markdown = normalize_action_item_timestamps(&final_markdown, self.segments);
+
+        // Extract the meeting name if present
+        let title = extract_meeting_name_from_markdown(&final_markdown).filter(|name| !name.is_empty());
+        if title.is_some() {
+            // Strip the title line from markdown
+            info!("✂️ Stripping title from final_markdown");
+            if let Some(hash_pos) = final_markdown.find('#') {
+                // Find end of first line after '#'
+                let body_start = if let Some(line_end) = final_markdown[hash_pos..].find('\n') {
+                    hash_pos + line_end
+                } else {
+                    final_markdown.len() // No newline, whole string is title
+                };
+
+                final_markdown = final_markdown[body_start..].trim_start().to_string();
+            } else {
+                // No '#' found, clear the string
+                final_markdown.clear();
+            }
+        }
+
+        let template = templates::get_template(template_id);
+        let validated = template.as_ref().map(|template| {
+            let validation = validate_summary_quality(&final_markdown, &template.table_sections());
+            (template, validation)
+        });
+        let quality = match &validated {
+            Ok((template, validation)) => Some(score_summary(
+                &final_markdown,
+                template,
+                self.text,
+                validation,
+                &pipeline.quality_thresholds,
+            )),
+            Err(e) => {
+                warn!("⚠️ Not scoring summary for {}: {}", meeting_id, e);
+                None
+            }
+        };
+        // Stored with the result and counted in the summary metrics
+        let mut validation_warnings = validated.ok().map(|(_, validation)| validation.warnings);
+
+        // Pseudonyms in an anonymized summary wouldn't match the transcript
+        let quote_check = (pipeline.quote_check.enabled && !self.anonymized)
+            .then(|| verify_quotes(&final_markdown, self.segments))
+            .filter(|check| !check.claims.is_empty());
+        if let Some(check) = &quote_check {
+            info!(
+                "🔎 Quote check for meeting_id {} ({}): {} found, {} partially found, {} not found",
+                meeting_id, template_id, check.found, check.partially_found, check.not_found
+            );
+            if let Some(warning) = check.warning(&pipeline.quote_check) {
+                warn!("⚠️ Summary for meeting_id {}: {}", meeting_id, warning);
+                validation_warnings.get_or_insert_with(Vec::new).push(warning);
+            }
+        }
+
+        if let Some(warning) = self.user_context_warning {
+            validation_warnings
+                .get_or_insert_with(Vec::new)
+                .push(warning.to_string());
+        }
+
+        // Scored before the note is added, which would count as coverage
+        let ticket_coverage = match TicketMatcher::new(&pipeline.ticket_coverage) {
+            Ok(matcher) => Some(ticket_coverage(self.text, &final_markdown, &matcher)),
+            Err(e) => {
+                warn!("⚠️ Not checking ticket coverage for {}: {}", meeting_id, e);
+                None
+            }
+        };
+        if let Some(coverage) = &ticket_coverage {
+            if !coverage.missing.is_empty() {
+                info!(
+                    "🎫 Summary for meeting_id {} misses {} of {} referenced tickets",
+                    meeting_id,
+                    coverage.missing.len(),
+                    coverage.mentioned.len()
+                );
+                if pipeline.ticket_coverage.append_note {
+                    final_markdown = format!(
+                        "{}\n\n{}",
+                        final_markdown.trim_end(),
+                        coverage_note(&coverage.missing)
+                    );
+                }
+            }
+        }
+
+        // Create result JSON with markdown only (summary_json will be added on first edit)
+        let generated_by = self.generated_by;
+        let mut result_json = serde_json::json!({
+            "markdown": final_markdown,
+            "template_id": template_id,
+            "generated_by": {
+                "provider": generated_by.provider.as_str(),
+                "model": generated_by.model_name,
+                "fallback": self.fallback,
+                "model_size": generated_by.model_size,
+            },
+        });
+        if let Some(map) = provenance_map {
+            result_json["provenance"] = serde_json::json!(map);
+        }
+        if let Some(map) = citation_map {
+            result_json["citations"] = serde_json::json!(map);
+        }
+        if let Some(quality) = &quality {
+            info!(
+                "📊 Summary quality for meeting_id {} ({}): {} ({:?})",
+                meeting_id, template_id, quality.score, quality.rating
+            );
+            result_json["quality"] = serde_json::json!(quality);
+        }
+        if let Some(warnings) = validation_warnings {
+            result_json["validation_warnings"] = serde_json::json!(warnings);
+        }
+        if let Some(check) = quote_check {
+            result_json["quote_check"] = serde_json::json!(check);
+        }
+        if let Some(coverage) = ticket_coverage.filter(|c| !c.mentioned.is_empty()) {
+            result_json["ticket_coverage"] = serde_json::json!(coverage);
+        }
+        if self.anonymized {
+            result_json["anonymized"] = serde_json::json!(true);
+        }
+        if self.skim {
+            result_json["skim"] = serde_json::json!(true);
+        }
+        if let Some(pipeline_id) = self.pipeline_id {
+            result_json["pipeline_id"] = serde_json::json!(pipeline_id);
+        }
+        if let Some(routing) = self.routing {
+            result_json["routing"] = serde_json::json!(routing);
+        }
+        if let Some(structured) = structured {
+            result_json["structured"] = serde_json::json!(structured);
+        }
+        // The UI warns that the summary may be incomplete
+        if truncated {
+            warn!(
+                "⚠️ Summary for meeting {} hit the model's output limit and may be incomplete",
+                meeting_id
+            );
+            result_json["truncated"] = serde_json::json!(true);
+        }
+        if let Some(requested) = self.chunking.coarsened_from {
+            result_json["chunking"] = serde_json::json!({
+                "coarsened": true,
+                "requested_chunk_size_tokens": requested,
+                "chunk_size_tokens": self.chunking.chunk_size_tokens,
+                "chunks": self.chunking.ranges.len(),
+                "max_chunks": pipeline.max_chunks,
+            });
+        }
+        (result_json, title)
+    }
+}
+
 /// Summary service - handles all summary generation logic
 pub struct SummaryService;
 
@@ -25,23 +362,109 @@ impl SummaryService {
     /// the main thread. It updates the database with progress and results.
     ///
     /// # Arguments
-    /// * `_app` - Tauri app handle (for future use)
-    /// * `pool` - SQLx connection pool
+    /// * `app` - Tauri app handle, used to read the content filter
+    /// * `repos` - Repositories used to read settings and record progress
     /// * `meeting_id` - Unique identifier for the meeting
     /// * `text` - Full transcript text
-    /// * `model_provider` - LLM provider name (e.g., "ollama", "openai")
-    /// * `model_name` - Specific model (e.g., "gpt-4", "llama3.2:latest")
+    /// * `model_provider` - LLM provider name (e.g., "ollama", "openai"); None uses the meeting's preference
+    /// * `model_name` - Specific model (e.g., "gpt-4", "llama3.2:latest"); None uses the meeting's preference
     /// * `custom_prompt` - Optional user-provided context
-    /// * `template_id` - Template identifier (e.g., "daily_standup", "standard_meeting")
+    /// * `template_ids` - Template identifiers (e.g., "daily_standup", "standard_meeting"); empty uses the meeting's preference.
+    ///   With several, the chunks are summarized once and each template's report is stored as a version
+    /// * `compact` - Strip filler words and repeated words before summarizing
+    /// * `provenance` - Add `[n]` markers citing the transcript chunks behind each statement
+    /// * `citations` - Ask for `[t=mm:ss]` citations on key claims and drop those matching no segment
+    /// * `stream` - Stream single-pass summaries and report estimated progress
+    /// * `anonymize` - Replace participant names with role pseudonyms before summarizing
+    /// * `skim` - Write every section as a few short bullets
+    /// * `named_pipeline` - Named pipeline the request was resolved with; its
+    ///   refinement and output format override the summary pipeline config and
+    ///   its webhooks, if any, are the only ones the summary is sent to
+    /// * `routing` - The routing decision that picked the provider, stored with the result
+    ///
+    /// Chunk summaries of long transcripts are merged, and the meeting's
+    /// background context sized, as set in the summary pipeline config.
     pub async fn process_transcript_background<R: tauri::Runtime>(
-        _app: AppHandle<R>,
-        pool: SqlitePool,
+        app: AppHandle<R>,
+        repos: Repositories,
+        meeting_id: String,
+        text: String,
+        model_provider: Option<String>,
+        model_name: Option<String>,
+        custom_prompt: String,
+        template_ids: Vec<String>,
+        compact: bool,
+        provenance: bool,
+        citations: bool,
+        stream: bool,
+        anonymize: bool,
+        skim: bool,
+        named_pipeline: Option<NamedPipeline>,
+        routing: Option<RoutingDecision>,
+    ) {
+        let content_filter = load_content_filter(&app);
+        let mut pipeline = load_summary_pipeline_config(&app);
+        if let Some(named) = &named_pipeline {
+            apply_to_config(named, &mut pipeline);
+        }
+        // Structured logs carry the meeting id of every line the run writes
+        let span = info_span!("summary", meeting_id = %meeting_id);
+        let started_at = chrono::Utc::now();
+        Self::process_transcript(
+            &repos,
+            meeting_id.clone(),
+            text,
+            model_provider,
+            model_name,
+            custom_prompt,
+            template_ids,
+            compact,
+            provenance,
+            citations,
+            stream,
+            anonymize,
+            skim,
+            named_pipeline.as_ref().map(|named| named.id.clone()),
+            routing,
+            &pipeline,
+            &content_filter,
+        )
+        .instrument(span)
+        .await;
+
+        // Only a summary this run saved is sent to the webhooks
+        if let Ok(Some(process)) = repos.summaries.get_summary_data(&meeting_id).await {
+            if process.status == "completed" && process.updated_at >= started_at {
+                if let Some(result) = &process.result {
+                    let webhook_ids = named_pipeline
+                        .as_ref()
+                        .map_or(&[][..], |named| &named.webhook_ids[..]);
+                    notify_summary_completed(&app, &meeting_id, result, webhook_ids).await;
+                }
+            }
+        }
+    }
+
+    /// Runs the summary pipeline for a meeting; independent of the Tauri runtime
+    /// so it can be driven directly in tests
+    pub(crate) async fn process_transcript(
+        repos: &Repositories,
         meeting_id: String,
         text: String,
-        model_provider: String,
-        model_name: String,
+        model_provider: Option<String>,
+        model_name: Option<String>,
         custom_prompt: String,
-        template_id: String,
+        template_ids: Vec<String>,
+        compact: bool,
+        provenance: bool,
+        citations: bool,
+        stream: bool,
+        anonymize: bool,
+        skim: bool,
+        pipeline_id: Option<String>,
+        routing: Option<RoutingDecision>,
+        pipeline: &SummaryPipelineConfig,
+        content_filter: &ContentFilterConfig,
     ) {
         let start_time = Instant::now();
         info!(
@@ -51,7 +474,7 @@ impl SummaryService {
 
         // Update status to processing when background task actually starts
         // But first check if this process has been cancelled (status is not PENDING)
-        let current_process = SummaryProcessesRepository::get_summary_data(&pool, &meeting_id).await;
+        let current_process = repos.summaries.get_summary_data(&meeting_id).await;
         match current_process {
             Ok(Some(proc)) if proc.status != "PENDING" => {
                 warn!(
@@ -70,7 +493,7 @@ impl SummaryService {
             _ => {} // Process is PENDING, continue
         }
         
-        if let Err(e) = SummaryProcessesRepository::update_process_processing(&pool, &meeting_id).await {
+        if let Err(e) = repos.summaries.update_process_processing(&meeting_id).await {
             error!(
                 "⚠️ Failed to update status to processing for {}: {}",
                 meeting_id, e
@@ -79,143 +502,251 @@ impl SummaryService {
             info!("✓ Status updated to 'processing' for meeting_id: {}", meeting_id);
         }
 
-        // Parse provider
-        let provider = match LLMProvider::from_str(&model_provider) {
-            Ok(p) => p,
+        // Fill in anything not explicitly requested from the meeting's preferences
+        let ResolvedSummarySettings {
+            model_provider,
+            model_name,
+            template_id,
+        } = match Self::resolve_settings(
+            repos,
+            &meeting_id,
+            model_provider,
+            model_name,
+            template_ids.first().cloned(),
+        )
+        .await
+        {
+            Ok(settings) => settings,
             Err(e) => {
-                Self::update_process_failed(&pool, &meeting_id, &e).await;
+                Self::update_process_failed(repos.summaries.as_ref(), &meeting_id, &e).await;
                 return;
             }
         };
+        let template_ids = run_templates(template_id, &template_ids);
 
-        // Validate and setup api_key, Flexible for Ollama
-        let api_key = match SettingsRepository::get_api_key(&pool, &model_provider).await {
-            Ok(Some(key)) if !key.is_empty() => key,
-            Ok(None) | Ok(Some(_)) => {
-                if provider != LLMProvider::Ollama {
-                    let err_msg = format!("Api key not found for {}", &model_provider);
-                    Self::update_process_failed(&pool, &meeting_id, &err_msg).await;
-                    return;
-                }
-                String::new()
-            }
+        // An unusable primary (e.g. Ollama not running) leaves the fallbacks to try
+        let primary = Self::resolve_target(repos, &model_provider, &model_name, pipeline).await;
+        let RunTargets {
+            targets,
+            primary_skipped,
+        } = match Self::with_fallback_targets(repos, primary, pipeline).await {
+            Ok(run_targets) => run_targets,
             Err(e) => {
-                let err_msg = format!("Failed to retrieve api key for {} : {}", &model_provider, e);
-                Self::update_process_failed(&pool, &meeting_id, &err_msg).await;
+                Self::update_process_failed(repos.summaries.as_ref(), &meeting_id, &e).await;
                 return;
             }
         };
 
-        // Get Ollama endpoint if provider is Ollama
-        let ollama_endpoint = if provider == LLMProvider::Ollama {
-            match SettingsRepository::get_model_config(&pool).await {
-                Ok(Some(config)) => config.ollama_endpoint,
-                Ok(None) => None,
-                Err(e) => {
-                    info!("Failed to retrieve Ollama endpoint: {}, using default", e);
-                    None
-                }
-            }
+        let text = if compact {
+            let original_tokens = rough_token_count(&text);
+            let compacted = compact_transcript(&text);
+            let compacted_tokens = rough_token_count(&compacted);
+            let reduction = if original_tokens > 0 {
+                100.0 * original_tokens.saturating_sub(compacted_tokens) as f64 / original_tokens as f64
+            } else {
+                0.0
+            };
+            info!(
+                "🗜️ Compacted transcript for meeting_id {}: {} -> {} tokens ({:.1}% reduction)",
+                meeting_id, original_tokens, compacted_tokens, reduction
+            );
+            compacted
         } else {
-            None
+            text
         };
 
-        // Verify Ollama connectivity if using Ollama
-        if provider == LLMProvider::Ollama {
-            let endpoint = ollama_endpoint.as_deref().unwrap_or("http://localhost:11434");
-            info!("🔍 Verifying Ollama connectivity at: {}", endpoint);
-            let test_client = reqwest::Client::builder()
-                .timeout(std::time::Duration::from_secs(5))
-                .build()
-                .unwrap_or_else(|_| reqwest::Client::new());
-            
-            match test_client.get(&format!("{}/api/tags", endpoint)).send().await {
-                Ok(resp) if resp.status().is_success() => {
-                    info!("✓ Ollama is reachable at {}", endpoint);
-                }
-                Ok(resp) => {
-                    let error_msg = format!("Ollama returned error status {} at {}", resp.status(), endpoint);
-                    error!("❌ {}", error_msg);
-                    Self::update_process_failed(&pool, &meeting_id, &error_msg).await;
-                    return;
-                }
-                Err(e) => {
-                    let error_msg = format!("Cannot connect to Ollama at {}: {}. Please ensure Ollama is running.", endpoint, e);
-                    error!("❌ {}", error_msg);
-                    Self::update_process_failed(&pool, &meeting_id, &error_msg).await;
-                    return;
-                }
+        // A context too small to chunk in fails before participants are
+        // detected or a summary slot is taken
+        if let Err(e) =
+            targets[0].check_chunk_room(rough_token_count(&text), &pipeline.chunk_settings())
+        {
+            error!("❌ {}", e);
+            Self::update_process_failed(repos.summaries.as_ref(), &meeting_id, &e).await;
+            return;
+        }
+
+        // Create HTTP client with extended timeout for long-running LLM requests
+        // 30 minutes timeout to match frontend polling timeout
+        let client = match http_client(Duration::from_secs(1800)) {
+            Ok(client) => client,
+            Err(e) => {
+                error!("❌ {}", e);
+                Self::update_process_failed(repos.summaries.as_ref(), &meeting_id, &e).await;
+                return;
             }
+        };
+
+        // Attendees are found before names are anonymized; the model gets the
+        // list so Action Item owners use names the directory can resolve
+        let detected = if pipeline.extract_participants {
+            Self::extract_participants(repos, &client, &targets, &meeting_id, &text).await
+        } else {
+            None
+        };
+        let attendees_note = detected
+            .as_deref()
+            .and_then(|detected| attendees_prompt_note(&merge_participants(detected)));
+        // The user's own context can't close its tag or crowd out the transcript
+        let user_context = prepare_user_context(
+            &custom_prompt,
+            pipeline.max_user_context_tokens,
+            &reserved_tags(&pipeline.chunk_markers),
+        );
+        if let Some(warning) = &user_context.warning {
+            warn!("⚠️ Meeting_id {}: {}", meeting_id, warning);
         }
+        let custom_prompt = user_context.text;
+        let custom_prompt = match attendees_note {
+            Some(note) if custom_prompt.trim().is_empty() => note,
+            Some(note) => format!("{}\n\n{}", custom_prompt, note),
+            None => custom_prompt,
+        };
 
-        // Dynamically fetch context size for Ollama models
-        let token_threshold = if provider == LLMProvider::Ollama {
-            match METADATA_CACHE.get_or_fetch(&model_name, ollama_endpoint.as_deref()).await {
-                Ok(metadata) => {
-                    // Reserve 300 tokens for prompt overhead
-                    let optimal = metadata.context_size.saturating_sub(300);
+        // Names are replaced before any of the transcript reaches the summary prompt
+        let participant_map = if anonymize {
+            match Self::load_participant_map(
+                repos,
+                &client,
+                &targets,
+                &meeting_id,
+                &text,
+                detected.as_deref(),
+            )
+            .await
+            {
+                Ok(map) => {
                     info!(
-                        "✓ Using dynamic context for {}: {} tokens (chunk size: {})",
-                        model_name, metadata.context_size, optimal
+                        "🕵️ Anonymizing {} participant(s) for meeting_id: {}",
+                        map.len(),
+                        meeting_id
                     );
-                    optimal
+                    Some(map)
                 }
                 Err(e) => {
-                    warn!(
-                        "⚠️ Failed to fetch context for {}: {}. Using default 4000",
-                        model_name, e
-                    );
-                    4000  // Fallback to safe default
+                    Self::update_process_failed(
+                        repos.summaries.as_ref(),
+                        &meeting_id,
+                        &format!("Failed to anonymize participants: {}", e),
+                    )
+                    .await;
+                    return;
                 }
             }
         } else {
-            // Cloud providers (OpenAI, Claude, Groq) handle large contexts automatically
-            100000  // Effectively unlimited for single-pass processing
+            None
+        };
+        let background_context = Self::load_background_context(repos, &meeting_id, pipeline).await;
+        let (text, custom_prompt, background_context) = match &participant_map {
+            Some(map) => (
+                anonymize_participants(&text, map),
+                anonymize_participants(&custom_prompt, map),
+                background_context.map(|context| BackgroundContext {
+                    text: anonymize_participants(&context.text, map),
+                    ..context
+                }),
+            ),
+            None => (text, custom_prompt, background_context),
         };
 
         // Generate summary
-        // Create HTTP client with extended timeout for long-running LLM requests
-        // 30 minutes timeout to match frontend polling timeout
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(1800)) // 30 minutes
-            .build()
-            .unwrap_or_else(|_| reqwest::Client::new()); // Fallback to default if builder fails
-        
-        let text_preview = if text.len() > 200 {
-            format!("{}...", &text[..200])
-        } else {
-            text.clone()
-        };
         info!(
-            "📝 Starting summary generation: provider={:?}, model={}, text_length={}, token_threshold={}",
-            provider, model_name, text.len(), token_threshold
+            "📝 Starting summary generation: provider={:?}, model={}, text_length={}, token_threshold={}, fallbacks={}",
+            targets[0].provider,
+            targets[0].model_name,
+            text.len(),
+            targets[0].token_threshold,
+            targets.len() - 1
         );
-        info!("📝 Transcript preview in service: {}", text_preview);
+        info!("📝 Transcript preview in service: {}", log_preview(&text));
         if text.is_empty() {
             error!("❌ CRITICAL: Transcript text is EMPTY in process_transcript_background!");
         }
         
-        let result = generate_meeting_summary(
-            &client,
-            &provider,
-            &model_name,
-            &api_key,
-            &text,
-            &custom_prompt,
-            &template_id,
-            token_threshold,
-            ollama_endpoint.as_deref(),
-        )
-        .await;
+        // Wait for a slot so full summaries and live drafts don't overload the provider
+        let mode = if skim {
+            SummaryMode::Skim {
+                max_bullets: pipeline.skim_max_bullets,
+            }
+        } else if pipeline.structured_output {
+            SummaryMode::Structured
+        } else {
+            SummaryMode::Standard
+        };
+        let summary_slot = acquire_summary_slot().await;
+        let report = |percent: u8| report_progress(&meeting_id, percent);
+        let result = if let [template_id] = template_ids.as_slice() {
+            generate_meeting_summary(
+                &client,
+                &targets,
+                &text,
+                &custom_prompt,
+                template_id,
+                &pipeline.chunk_markers,
+                provenance,
+                citations,
+                pipeline.chunk_merge,
+                &pipeline.chunk_settings(),
+                mode,
+                background_context.as_ref(),
+                stream.then_some(&report as &(dyn Fn(u8) + Send + Sync)),
+            )
+            .await
+            .map(|summary| GeneratedSummaries {
+                reports: vec![TemplateReport {
+                    template_id: template_id.clone(),
+                    markdown: Ok(summary.markdown),
+                    truncated: summary.truncated,
+                    structured: summary.structured,
+                }],
+                num_chunks: summary.num_chunks,
+                chunk_ranges: summary.chunk_ranges,
+                chunking: summary.chunking,
+                target_index: summary.target_index,
+                truncated: false,
+            })
+        } else {
+            // Chunks are summarized once; only the final report runs per template
+            info!(
+                "📝 Generating {} template summaries for meeting_id {}: {}",
+                template_ids.len(),
+                meeting_id,
+                template_ids.join(", ")
+            );
+            generate_meeting_summaries(
+                &client,
+                &targets,
+                &text,
+                &custom_prompt,
+                &template_ids,
+                &pipeline.chunk_markers,
+                provenance,
+                citations,
+                pipeline.chunk_merge,
+                &pipeline.chunk_settings(),
+                mode,
+                background_context.as_ref(),
+            )
+            .await
+        };
+        drop(summary_slot);
+        // The process status takes over from here
+        clear_progress(&meeting_id);
         
         info!("📝 Summary generation call completed for meeting_id: {}", meeting_id);
 
         let duration = start_time.elapsed().as_secs_f64();
 
         match result {
-            Ok((mut final_markdown, num_chunks)) => {
+            Ok(GeneratedSummaries {
+                reports,
+                num_chunks,
+                chunk_ranges,
+                chunking,
+                target_index,
+                truncated,
+            }) => {
                 // Before saving results, verify this process hasn't been cancelled
-                let current_process = SummaryProcessesRepository::get_summary_data(&pool, &meeting_id).await;
+                let current_process = repos.summaries.get_summary_data(&meeting_id).await;
                 match current_process {
                     Ok(Some(proc)) if proc.status != "processing" => {
                         warn!(
@@ -234,9 +765,9 @@ impl SummaryService {
                     _ => {} // Process is still processing, continue
                 }
                 
-                if num_chunks == 0 && final_markdown.is_empty() {
+                if num_chunks == 0 && reports.iter().all(|r| r.markdown.as_ref().map_or(true, String::is_empty)) {
                     Self::update_process_failed(
-                        &pool,
+                        repos.summaries.as_ref(),
                         &meeting_id,
                         "Summary generation failed: No content was processed.",
                     )
@@ -244,52 +775,178 @@ impl SummaryService {
                     return;
                 }
 
+                // Chunk count and duration cover the shared chunk phase once,
+                // however many templates were reported on
                 info!(
-                    "✓ Successfully processed {} chunks for meeting_id: {}. Duration: {:.2}s",
-                    num_chunks, meeting_id, duration
+                    "✓ Successfully processed {} chunks for {} template(s) for meeting_id: {}. Duration: {:.2}s",
+                    num_chunks,
+                    reports.len(),
+                    meeting_id,
+                    duration
                 );
-                info!("final markdown is {}", &final_markdown);
-
-                // Extract and update meeting name if present
-                if let Some(name) = extract_meeting_name_from_markdown(&final_markdown) {
-                    if !name.is_empty() {
-                        info!(
-                            "📝 Updating meeting name to '{}' for meeting_id: {}",
-                            name, meeting_id
+                let generated_by = &targets[target_index];
+                let fallback = primary_skipped || target_index > 0;
+                if fallback {
+                    warn!(
+                        "⚠️ Summary for meeting_id {} was generated by fallback {:?} / {}",
+                        meeting_id, generated_by.provider, generated_by.model_name
+                    );
+                }
+
+                // Keep where the summarized text was split next to the text itself
+                let boundaries: Vec<ChunkBoundary> = chunking
+                    .ranges
+                    .iter()
+                    .map(|range| ChunkBoundary {
+                        chunk_index: range.index,
+                        char_start: range.char_start,
+                        char_end: range.char_end,
+                    })
+                    .collect();
+                match repos
+                    .transcripts
+                    .save_chunk_layout(
+                        &meeting_id,
+                        &text,
+                        chunking.chunk_size_tokens,
+                        chunking.overlap_tokens,
+                        &boundaries,
+                    )
+                    .await
+                {
+                    Ok(true) => {}
+                    Ok(false) => warn!(
+                        "⚠️ No transcript data saved for meeting_id {}, chunk boundaries not recorded",
+                        meeting_id
+                    ),
+                    Err(e) => warn!(
+                        "⚠️ Failed to record chunk boundaries for {}: {}",
+                        meeting_id, e
+                    ),
+                }
+
+                // Action item timestamps are checked against the segments' timing
+                let segments = match repos.transcripts.get_transcript_segments(&meeting_id).await {
+                    Ok(segments) => segments,
+                    Err(e) => {
+                        warn!(
+                            "⚠️ Failed to load segments for timestamps and provenance of {}: {}",
+                            meeting_id, e
                         );
-                        if let Err(e) =
-                            MeetingsRepository::update_meeting_title(&pool, &meeting_id, &name).await
-                        {
-                            error!("⚠️ Failed to update meeting name for {}: {}", meeting_id, e);
-                        }
+                        Vec::new()
+                    }
+                };
+                let segment_spans = if provenance {
+                    // Chunk ranges refer to the text that was summarized
+                    let segments: Vec<_> = segments
+                        .iter()
+                        .cloned()
+                        .map(|mut segment| {
+                            if compact {
+                                segment.text = compact_transcript(&segment.text);
+                            }
+                            if let Some(map) = &participant_map {
+                                segment.text = anonymize_participants(&segment.text, map);
+                            }
+                            segment
+                        })
+                        .collect();
+                    let spans = locate_segments(&text, &segments);
+                    info!(
+                        "🔗 Provenance for meeting_id {}: {}/{} segments located",
+                        meeting_id,
+                        spans.len(),
+                        segments.len()
+                    );
+                    Some(spans)
+                } else {
+                    None
+                };
 
-                        // Strip the title line from markdown
-                        info!("✂️ Stripping title from final_markdown");
-                        if let Some(hash_pos) = final_markdown.find('#') {
-                            // Find end of first line after '#'
-                            let body_start =
-                                if let Some(line_end) = final_markdown[hash_pos..].find('\n') {
-                                    hash_pos + line_end
-                                } else {
-                                    final_markdown.len() // No newline, whole string is title
-                                };
-
-                            final_markdown = final_markdown[body_start..].trim_start().to_string();
-                        } else {
-                            // No '#' found, clear the string
-                            final_markdown.clear();
+                let finisher = ReportFinisher {
+                    meeting_id: &meeting_id,
+                    text: &text,
+                    segments: &segments,
+                    chunk_ranges: &chunk_ranges,
+                    segment_spans: segment_spans.as_deref(),
+                    citations,
+                    chunking: &chunking,
+                    generated_by,
+                    fallback,
+                    anonymized: participant_map.is_some(),
+                    skim,
+                    pipeline_id: pipeline_id.as_deref(),
+                    routing: routing.as_ref(),
+                    user_context_warning: user_context.warning.as_deref(),
+                    pipeline,
+                    content_filter,
+                };
+                let multiple = reports.len() > 1;
+                let mut completed: Option<(Value, Option<String>)> = None;
+                let mut last_error = None;
+                for TemplateReport {
+                    template_id,
+                    markdown,
+                    truncated: report_truncated,
+                    structured,
+                } in reports
+                {
+                    let finished = markdown.map(|markdown| {
+                        finisher.finish(
+                            &template_id,
+                            markdown,
+                            truncated || report_truncated,
+                            structured.as_ref(),
+                        )
+                    });
+                    // Each template of a multi-template run is kept as its own version
+                    if multiple {
+                        let saved = match &finished {
+                            Ok((result_json, _)) => {
+                                repos
+                                    .summaries
+                                    .save_summary_version(&meeting_id, &template_id, Some(result_json), None)
+                                    .await
+                            }
+                            Err(e) => {
+                                repos
+                                    .summaries
+                                    .save_summary_version(&meeting_id, &template_id, None, Some(e.as_str()))
+                                    .await
+                            }
+                        };
+                        if let Err(e) = saved {
+                            error!(
+                                "⚠️ Failed to save {} summary version for {}: {}",
+                                template_id, meeting_id, e
+                            );
                         }
                     }
+                    match finished {
+                        Ok(finished) if completed.is_none() => completed = Some(finished),
+                        Ok(_) => {}
+                        Err(e) => last_error = Some(format!("Template '{}' failed: {}", template_id, e)),
+                    }
                 }
 
-                // Create result JSON with markdown only (summary_json will be added on first edit)
-                let result_json = serde_json::json!({
-                    "markdown": final_markdown,
-                });
+                // The first template that succeeded becomes the meeting's summary
+                let Some((result_json, title)) = completed else {
+                    let error = last_error.unwrap_or_else(|| "No summary was generated".to_string());
+                    Self::update_process_failed(repos.summaries.as_ref(), &meeting_id, &error).await;
+                    return;
+                };
+                if let Some(name) = title {
+                    info!(
+                        "📝 Updating meeting name to '{}' for meeting_id: {}",
+                        name, meeting_id
+                    );
+                    if let Err(e) = repos.meetings.update_meeting_title(&meeting_id, &name).await {
+                        error!("⚠️ Failed to update meeting name for {}: {}", meeting_id, e);
+                    }
+                }
 
                 // Update database with completed status
-                if let Err(e) = SummaryProcessesRepository::update_process_completed(
-                    &pool,
+                if let Err(e) = repos.summaries.update_process_completed(
                     &meeting_id,
                     result_json,
                     num_chunks,
@@ -306,28 +963,756 @@ impl SummaryService {
                         "💾 Summary saved successfully for meeting_id: {}",
                         meeting_id
                     );
+                    // The final summary replaces the draft built during recording
+                    if let Err(e) = repos.summaries.delete_live_summaries(&meeting_id).await {
+                        warn!("⚠️ Failed to remove live summary draft for {}: {}", meeting_id, e);
+                    }
+                }
+            }
+            Err(e) => {
+                Self::update_process_failed(repos.summaries.as_ref(), &meeting_id, &e).await;
+            }
+        }
+    }
+
+    /// Looks up the key, endpoint and context size needed to summarize with a
+    /// provider/model, checking that Ollama is reachable
+    ///
+    /// The model's size decides whether it gets the simplified small-model
+    /// prompt, against the pipeline config's threshold.
+    async fn resolve_target(
+        repos: &Repositories,
+        model_provider: &str,
+        model_name: &str,
+        pipeline: &SummaryPipelineConfig,
+    ) -> Result<SummaryTarget, String> {
+        let provider = LLMProvider::from_str(model_provider)?;
+
+        // Validate and setup api_key, Flexible for Ollama
+        let api_key = match repos.settings.get_api_key(model_provider).await {
+            Ok(Some(key)) if !key.is_empty() => key,
+            Ok(None) | Ok(Some(_)) => {
+                if provider != LLMProvider::Ollama {
+                    return Err(format!("Api key not found for {}", model_provider));
+                }
+                String::new()
+            }
+            Err(e) => {
+                return Err(format!(
+                    "Failed to retrieve api key for {} : {}",
+                    model_provider, e
+                ));
+            }
+        };
+
+        // Get Ollama endpoint if provider is Ollama
+        let ollama_endpoint = if provider == LLMProvider::Ollama {
+            match repos.settings.get_model_config().await {
+                Ok(Some(config)) => config.ollama_endpoint,
+                Ok(None) => None,
+                Err(e) => {
+                    info!("Failed to retrieve Ollama endpoint: {}, using default", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        // Verify Ollama connectivity if using Ollama
+        if provider == LLMProvider::Ollama {
+            let endpoint = ollama_endpoint.as_deref().unwrap_or("http://localhost:11434");
+            info!("🔍 Verifying Ollama connectivity at: {}", endpoint);
+            bypass_proxy_for(endpoint);
+            let test_client = http_client(Duration::from_secs(5))?;
+
+            match test_client.get(&format!("{}/api/tags", endpoint)).send().await {
+                Ok(resp) if resp.status().is_success() => {
+                    info!("✓ Ollama is reachable at {}", endpoint);
+                }
+                Ok(resp) => {
+                    let error_msg = format!("Ollama returned error status {} at {}", resp.status(), endpoint);
+                    error!("❌ {}", error_msg);
+                    return Err(error_msg);
+                }
+                Err(e) => {
+                    let error_msg = format!("Cannot connect to Ollama at {}: {}. Please ensure Ollama is running.", endpoint, e);
+                    error!("❌ {}", error_msg);
+                    return Err(error_msg);
+                }
+            }
+        }
+
+        // Dynamically fetch context size and parameter count for Ollama models
+        let mut model_metadata = None;
+        let token_threshold = if provider == LLMProvider::Ollama {
+            match METADATA_CACHE.get_or_fetch(model_name, ollama_endpoint.as_deref()).await {
+                Ok(metadata) => {
+                    // Reserve 300 tokens for prompt overhead
+                    let optimal = metadata.context_size.saturating_sub(300);
+                    info!(
+                        "✓ Using dynamic context for {}: {} tokens (chunk size: {})",
+                        model_name, metadata.context_size, optimal
+                    );
+                    model_metadata = Some(metadata);
+                    optimal
+                }
+                Err(e) => {
+                    warn!(
+                        "⚠️ Failed to fetch context for {}: {}. Using default 4000",
+                        model_name, e
+                    );
+                    4000  // Fallback to safe default
+                }
+            }
+        } else {
+            // Cloud providers (OpenAI, Claude, Groq) handle large contexts automatically
+            100000  // Effectively unlimited for single-pass processing
+        };
+
+        let model_size = classify_model_size(
+            model_name,
+            model_metadata.as_ref(),
+            pipeline.small_model_max_params_b,
+        );
+        // Only Ollama cuts prompts down silently; cloud providers reject them
+        let context_tokens = model_metadata.as_ref().map(|metadata| metadata.context_size);
+        info!(
+            "📏 {} size: {:?}B parameters (from {:?}), small model prompt: {}",
+            model_name, model_size.parameters_billions, model_size.source, model_size.small
+        );
+        let few_shot = pipeline.few_shot_examples.applies(model_size.small);
+
+        Ok(SummaryTarget {
+            provider,
+            model_name: model_name.to_string(),
+            api_key,
+            ollama_endpoint,
+            token_threshold,
+            context_tokens,
+            model_size,
+            few_shot,
+        })
+    }
+
+    /// Detects the meeting's participants with the model and stores them as
+    /// its attendee list
+    ///
+    /// The list only helps attribution, so failures are logged and the
+    /// summary goes ahead without it.
+    async fn extract_participants(
+        repos: &Repositories,
+        client: &reqwest::Client,
+        targets: &[SummaryTarget],
+        meeting_id: &str,
+        text: &str,
+    ) -> Option<Vec<DetectedParticipant>> {
+        let detected = match detect_participants(client, targets, text).await {
+            Ok(detected) => detected,
+            Err(e) => {
+                warn!("⚠️ Participant extraction failed for {}: {}", meeting_id, e);
+                return None;
+            }
+        };
+        let attendees = merge_participants(&detected);
+        match repos.meetings.set_participants(meeting_id, &attendees).await {
+            Ok(_) => info!(
+                "👥 Stored {} attendee(s) for meeting_id: {}",
+                attendees.len(),
+                meeting_id
+            ),
+            Err(e) => warn!("⚠️ Failed to store attendees for {}: {}", meeting_id, e),
+        }
+        Some(detected)
+    }
+
+    /// The meeting's stored name -> pseudonym map, detecting participants
+    /// with the model (unless `detected` already has them) and storing the
+    /// result when none is stored yet
+    async fn load_participant_map(
+        repos: &Repositories,
+        client: &reqwest::Client,
+        targets: &[SummaryTarget],
+        meeting_id: &str,
+        text: &str,
+        detected: Option<&[DetectedParticipant]>,
+    ) -> Result<ParticipantMap, String> {
+        let stored = repos
+            .meetings
+            .get_participant_map(meeting_id)
+            .await
+            .map_err(|e| format!("Failed to load participant map: {}", e))?
+            .unwrap_or_default();
+        if !stored.is_empty() {
+            return Ok(stored);
+        }
+
+        let detected = match detected {
+            Some(detected) => detected.to_vec(),
+            None => detect_participants(client, targets, text).await?,
+        };
+        let map = assign_pseudonyms(&stored, &detected);
+        repos
+            .meetings
+            .set_participant_map(meeting_id, &map)
+            .await
+            .map_err(|e| format!("Failed to save participant map: {}", e))?;
+        Ok(map)
+    }
+
+    /// The meeting's background context sized by the pipeline config
+    ///
+    /// Missing notes shouldn't fail the summary, so errors are only logged.
+    async fn load_background_context(
+        repos: &Repositories,
+        meeting_id: &str,
+        pipeline: &SummaryPipelineConfig,
+    ) -> Option<BackgroundContext> {
+        match repos.meetings.get_meeting_context(meeting_id).await {
+            Ok(context) => context.map(|context| BackgroundContext {
+                text: context.text,
+                window_share: pipeline.context_share,
+            }),
+            Err(e) => {
+                warn!(
+                    "⚠️ Failed to load background context for {}, summarizing without it: {}",
+                    meeting_id, e
+                );
+                None
+            }
+        }
+    }
+
+    /// The meeting's transcript segments rendered as for the summary prompt
+    async fn stored_transcript_text(
+        repos: &Repositories,
+        meeting_id: &str,
+        pipeline: &SummaryPipelineConfig,
+    ) -> Result<String, String> {
+        let segments = repos
+            .transcripts
+            .get_transcript_segments(meeting_id)
+            .await
+            .map_err(|e| format!("Failed to load transcript: {}", e))?;
+        if segments.is_empty() {
+            return Err(format!("Meeting {} has no transcript", meeting_id));
+        }
+        Ok(Self::prompt_transcript(meeting_id, segments, pipeline))
+    }
+
+    /// The transcript text summaries are made from, without the duplicate
+    /// segments overlapping microphone and system capture left
+    pub fn prompt_transcript(
+        meeting_id: &str,
+        segments: Vec<TranscriptSegment>,
+        pipeline: &SummaryPipelineConfig,
+    ) -> String {
+        let deduped = dedup_segments(segments, &pipeline.segment_dedup);
+        if !deduped.removed.is_empty() {
+            info!(
+                "🧹 Dropped {} duplicate transcript segment(s) for meeting_id {}: {}",
+                deduped.removed.len(),
+                meeting_id,
+                deduped.removed.join(", ")
+            );
+        }
+        render_transcript(&deduped.segments, &TranscriptTextOptions::for_prompt())
+    }
+
+    /// The prompts summarizing the meeting would send, without calling the model
+    ///
+    /// Uses the same settings resolution, background context and chunking as
+    /// `process_transcript`. With `anonymize`, the meeting's stored
+    /// participant map is applied; detecting participants needs a model call,
+    /// so a meeting without one is an error.
+    pub async fn preview_summary_prompt(
+        repos: &Repositories,
+        meeting_id: &str,
+        template_id: Option<String>,
+        custom_prompt: Option<String>,
+        anonymize: bool,
+        pipeline: &SummaryPipelineConfig,
+    ) -> Result<SummaryPromptPreview, String> {
+        let text = Self::stored_transcript_text(repos, meeting_id, pipeline).await?;
+        let settings = Self::resolve_settings(repos, meeting_id, None, None, template_id).await?;
+        let target = Self::resolve_target(
+            repos,
+            &settings.model_provider,
+            &settings.model_name,
+            pipeline,
+        )
+        .await?;
+        let template = templates::get_template(&settings.template_id).map_err(|e| {
+            format!("Failed to load template '{}': {}", settings.template_id, e)
+        })?;
+        let custom_prompt = prepare_user_context(
+            &custom_prompt.unwrap_or_default(),
+            pipeline.max_user_context_tokens,
+            &reserved_tags(&pipeline.chunk_markers),
+        )
+        .text;
+        let background_context = Self::load_background_context(repos, meeting_id, pipeline).await;
+
+        let (text, custom_prompt, background_context) = if anonymize {
+            let map = repos
+                .meetings
+                .get_participant_map(meeting_id)
+                .await
+                .map_err(|e| format!("Failed to load participant map: {}", e))?
+                .filter(|map| !map.is_empty())
+                .ok_or_else(|| {
+                    format!(
+                        "Participants of meeting {} haven't been detected yet; generate an anonymized summary first",
+                        meeting_id
+                    )
+                })?;
+            (
+                anonymize_participants(&text, &map),
+                anonymize_participants(&custom_prompt, &map),
+                background_context.map(|context| BackgroundContext {
+                    text: anonymize_participants(&context.text, &map),
+                    ..context
+                }),
+            )
+        } else {
+            (text, custom_prompt, background_context)
+        };
+
+        preview_summary_prompts(
+            &target,
+            &text,
+            &custom_prompt,
+            &template,
+            &pipeline.chunk_markers,
+            false,
+            pipeline.chunk_merge,
+            &pipeline.chunk_settings(),
+            SummaryMode::Standard,
+            background_context.as_ref(),
+        )
+    }
+
+    /// How the meeting's stored transcript would be split with its resolved
+    /// model, without calling it
+    ///
+    /// Compaction and anonymization aren't applied, so a run using them may
+    /// come out slightly smaller.
+    pub async fn preview_chunking(
+        repos: &Repositories,
+        meeting_id: &str,
+        pipeline: &SummaryPipelineConfig,
+    ) -> Result<ChunkingPreview, String> {
+        let text = Self::stored_transcript_text(repos, meeting_id, pipeline).await?;
+        let settings = Self::resolve_settings(repos, meeting_id, None, None, None).await?;
+        let target = Self::resolve_target(
+            repos,
+            &settings.model_provider,
+            &settings.model_name,
+            pipeline,
+        )
+        .await?;
+        preview_chunking(
+            &text,
+            &target.provider,
+            target.token_threshold,
+            &pipeline.chunk_settings(),
+        )
+    }
+
+    /// Extracts only the action items of the meeting's stored transcript
+    ///
+    /// One focused prompt instead of the full summary pipeline, so the
+    /// transcript has to fit the model's context in a single call. The
+    /// provider and model resolve as for a summary, falling back the same way.
+    pub async fn extract_action_items(
+        repos: &Repositories,
+        meeting_id: &str,
+        model_provider: Option<String>,
+        model_name: Option<String>,
+        pipeline: &SummaryPipelineConfig,
+    ) -> Result<ExtractedActionItems, String> {
+        let text = Self::stored_transcript_text(repos, meeting_id, pipeline).await?;
+        let settings =
+            Self::resolve_settings(repos, meeting_id, model_provider, model_name, None).await?;
+        let primary = Self::resolve_target(
+            repos,
+            &settings.model_provider,
+            &settings.model_name,
+            pipeline,
+        )
+        .await;
+        let targets = Self::with_fallback_targets(repos, primary, pipeline)
+            .await?
+            .targets;
+        let total_tokens = rough_token_count(&text);
+        if !uses_single_pass(
+            &targets[0].provider,
+            total_tokens,
+            targets[0].token_threshold,
+        ) {
+            return Err(format!(
+                "Transcript ({} tokens) is too long for a single action item prompt with {}; generate a full summary instead",
+                total_tokens, targets[0].model_name
+            ));
+        }
+
+        let client = http_client(Duration::from_secs(600))?;
+        let prompt = action_items_prompt(&text, &pipeline.chunk_markers);
+        let (client, prompt) = (&client, prompt.as_str());
+        let (response, target_index) = with_fallback(&targets, RetryPolicy::NONE, |target| {
+            generate_summary(
+                client,
+                &target.provider,
+                &target.model_name,
+                &target.api_key,
+                ACTION_ITEMS_SYSTEM_PROMPT,
+                prompt,
+                target.ollama_endpoint.as_deref(),
+            )
+        })
+        .await?;
+
+        let extracted = parse_extracted_action_items(&response);
+        info!(
+            "✅ Extracted {} action item(s) for meeting_id {} with {:?} / {}",
+            extracted.items.len(),
+            meeting_id,
+            targets[target_index].provider,
+            targets[target_index].model_name
+        );
+        Ok(extracted)
+    }
+
+    /// Ticket IDs of the meeting's stored transcript its current summary
+    /// doesn't mention
+    ///
+    /// Checks the summary as stored, so hand edits count; an appended
+    /// coverage note does not.
+    pub async fn ticket_coverage(
+        repos: &Repositories,
+        meeting_id: &str,
+        pipeline: &SummaryPipelineConfig,
+    ) -> Result<TicketCoverage, String> {
+        let matcher = TicketMatcher::new(&pipeline.ticket_coverage)?;
+        let text = Self::stored_transcript_text(repos, meeting_id, pipeline).await?;
+        let process = repos
+            .summaries
+            .get_summary_data(meeting_id)
+            .await
+            .map_err(|e| format!("Failed to load summary: {}", e))?;
+        let markdown = process
+            .and_then(|process| process.result)
+            .and_then(|result| serde_json::from_str::<serde_json::Value>(&result).ok())
+            .and_then(|result| result.get("markdown")?.as_str().map(str::to_string))
+            .ok_or_else(|| format!("Meeting {} has no summary", meeting_id))?;
+        Ok(ticket_coverage(&text, &markdown, &matcher))
+    }
+
+    /// Looks up the quotes and claims of the meeting's current summary in its
+    /// transcript
+    ///
+    /// Checks the summary as stored, so hand edits count. With `attach`, the
+    /// result replaces any earlier one in the stored summary, along with its
+    /// validation warning.
+    pub async fn verify_quotes(
+        repos: &Repositories,
+        meeting_id: &str,
+        pipeline: &SummaryPipelineConfig,
+        attach: bool,
+    ) -> Result<QuoteCheck, String> {
+        let segments = repos
+            .transcripts
+            .get_transcript_segments(meeting_id)
+            .await
+            .map_err(|e| format!("Failed to load transcript: {}", e))?;
+        if segments.is_empty() {
+            return Err(format!("Meeting {} has no transcript", meeting_id));
+        }
+        let process = repos
+            .summaries
+            .get_summary_data(meeting_id)
+            .await
+            .map_err(|e| format!("Failed to load summary: {}", e))?;
+        let mut result = process
+            .and_then(|process| process.result)
+            .and_then(|result| serde_json::from_str::<Value>(&result).ok())
+            .filter(|result| result.get("markdown").is_some_and(Value::is_string))
+            .ok_or_else(|| format!("Meeting {} has no summary", meeting_id))?;
+        let markdown = result["markdown"].as_str().unwrap_or_default();
+
+        let check = verify_quotes(markdown, &segments);
+        info!(
+            "🔎 Quote check for meeting_id {}: {} found, {} partially found, {} not found",
+            meeting_id, check.found, check.partially_found, check.not_found
+        );
+        if attach {
+            let mut warnings: Vec<Value> = result
+                .get("validation_warnings")
+                .and_then(Value::as_array)
+                .cloned()
+                .unwrap_or_default();
+            warnings.retain(|warning| !warning.as_str().is_some_and(QuoteCheck::is_warning));
+            if let Some(warning) = check.warning(&pipeline.quote_check) {
+                warnings.push(Value::String(warning));
+            }
+            result["validation_warnings"] = serde_json::json!(warnings);
+            result["quote_check"] = serde_json::json!(check);
+            let saved = repos
+                .summaries
+                .update_meeting_summary(meeting_id, &result)
+                .await
+                .map_err(|e| format!("Failed to save quote check: {}", e))?;
+            if !saved {
+                return Err(format!("Meeting {} not found", meeting_id));
+            }
+        }
+        Ok(check)
+    }
+
+    /// Summarizes the meeting's stored transcript with each model for a side
+    /// by side comparison
+    ///
+    /// The stored summary is left alone. Runs take summary slots like any
+    /// other summary and use no fallbacks; a model that fails gets an error
+    /// entry without affecting the others. Results are in `models` order.
+    pub async fn compare_summaries(
+        repos: &Repositories,
+        meeting_id: &str,
+        models: Vec<ModelChoice>,
+        template_id: Option<String>,
+        pipeline: &SummaryPipelineConfig,
+    ) -> Result<Vec<SummaryComparison>, String> {
+        let first = models
+            .first()
+            .ok_or_else(|| "Select at least one model to compare".to_string())?;
+        let text = Self::stored_transcript_text(repos, meeting_id, pipeline).await?;
+        let settings = Self::resolve_settings(
+            repos,
+            meeting_id,
+            Some(first.provider.clone()),
+            Some(first.model.clone()),
+            template_id,
+        )
+        .await?;
+        let background_context = Self::load_background_context(repos, meeting_id, pipeline).await;
+        let client = http_client(Duration::from_secs(1800))?; // 30 minutes
+        info!(
+            "⚖️ Comparing {} model(s) on meeting_id {} with template {}",
+            models.len(),
+            meeting_id,
+            settings.template_id
+        );
+
+        let (client, text, template_id, background_context) = (
+            &client,
+            text.as_str(),
+            settings.template_id.as_str(),
+            background_context.as_ref(),
+        );
+        let runs = models.into_iter().map(|choice| async move {
+            let target =
+                Self::resolve_target(repos, &choice.provider, &choice.model, pipeline).await;
+            let (result, seconds) = match target {
+                Ok(target) => {
+                    let _summary_slot = acquire_summary_slot().await;
+                    let started = Instant::now();
+                    let result = generate_meeting_summary(
+                        client,
+                        std::slice::from_ref(&target),
+                        text,
+                        "",
+                        template_id,
+                        &pipeline.chunk_markers,
+                        false,
+                        false,
+                        pipeline.chunk_merge,
+                        &pipeline.chunk_settings(),
+                        SummaryMode::Standard,
+                        background_context,
+                        None,
+                    )
+                    .await;
+                    (result, started.elapsed().as_secs_f64())
                 }
+                Err(e) => (Err(e), 0.0),
+            };
+            match &result {
+                Ok(_) => info!(
+                    "✅ Comparison summary by {} / {} took {:.1}s",
+                    choice.provider, choice.model, seconds
+                ),
+                Err(e) => warn!(
+                    "⚠️ Comparison summary by {} / {} failed: {}",
+                    choice.provider, choice.model, e
+                ),
+            }
+
+            let (summary, error) = match result {
+                Ok(summary) => (Some(summary), None),
+                Err(e) => (None, Some(e)),
+            };
+            SummaryComparison {
+                provider: choice.provider,
+                model: choice.model,
+                num_chunks: summary.as_ref().map_or(0, |summary| summary.num_chunks),
+                input_tokens: rough_token_count(text),
+                output_tokens: summary
+                    .as_ref()
+                    .map_or(0, |summary| rough_token_count(&summary.markdown)),
+                truncated: summary.as_ref().is_some_and(|summary| summary.truncated),
+                markdown: summary.map(|summary| summary.markdown),
+                error,
+                processing_time: seconds,
+            }
+        });
+        Ok(join_all(runs).await)
+    }
+
+    /// Appends the configured fallback providers to the primary target
+    ///
+    /// A primary that failed to resolve is treated like an unavailable
+    /// provider: it is left out and the fallbacks are tried instead. Fallbacks
+    /// that can't be used (no API key, Ollama not running) or that repeat an
+    /// earlier entry are skipped with a warning. Fails with the primary's
+    /// error when no target is left.
+    async fn with_fallback_targets(
+        repos: &Repositories,
+        primary: Result<SummaryTarget, String>,
+        pipeline: &SummaryPipelineConfig,
+    ) -> Result<RunTargets, String> {
+        let mut targets = Vec::new();
+        let primary_error = match primary {
+            Ok(target) => {
+                targets.push(target);
+                None
             }
             Err(e) => {
-                Self::update_process_failed(&pool, &meeting_id, &e).await;
+                warn!(
+                    "⚠️ Primary summary provider unavailable, trying the fallbacks: {}",
+                    e
+                );
+                Some(e)
+            }
+        };
+        let fallbacks = match repos.settings.get_summary_fallbacks().await {
+            Ok(fallbacks) => fallbacks,
+            Err(e) => {
+                warn!("⚠️ Failed to load summary fallbacks: {}", e);
+                Vec::new()
+            }
+        };
+        for fallback in fallbacks {
+            let duplicate = targets.iter().any(|target| {
+                target.provider.as_str() == fallback.provider.to_lowercase()
+                    && target.model_name == fallback.model
+            });
+            if duplicate {
+                continue;
+            }
+            match Self::resolve_target(repos, &fallback.provider, &fallback.model, pipeline).await {
+                Ok(target) => targets.push(target),
+                Err(e) => warn!(
+                    "⚠️ Skipping summary fallback {} / {}: {}",
+                    fallback.provider, fallback.model, e
+                ),
             }
         }
+        match primary_error {
+            Some(e) if targets.is_empty() => Err(e),
+            primary_error => Ok(RunTargets {
+                targets,
+                primary_skipped: primary_error.is_some(),
+            }),
+        }
+    }
+
+    /// Resolves the provider, model and template for a summary run
+    ///
+    /// Explicit values win, then the meeting's stored preferences, then the
+    /// global model config (and "daily_standup" for the template). Provider and
+    /// model are resolved as a pair so a model name is never sent to a provider
+    /// it doesn't belong to.
+    pub async fn resolve_settings(
+        repos: &Repositories,
+        meeting_id: &str,
+        model_provider: Option<String>,
+        model_name: Option<String>,
+        template_id: Option<String>,
+    ) -> Result<ResolvedSummarySettings, String> {
+        let preferences = repos
+            .meetings
+            .get_meeting_preferences(meeting_id)
+            .await
+            .map_err(|e| format!("Failed to load meeting preferences: {}", e))?
+            .unwrap_or_default();
+
+        let template_id = non_empty(template_id)
+            .or(non_empty(preferences.template_id))
+            .unwrap_or_else(|| DEFAULT_TEMPLATE_ID.to_string());
+
+        let model_provider = non_empty(model_provider);
+        let model_name = non_empty(model_name);
+        let (model_provider, model_name) = match (model_provider, model_name) {
+            (Some(provider), Some(name)) => (provider, name),
+            // A model name only means something together with its provider
+            (None, Some(name)) => {
+                return Err(format!(
+                    "Model '{}' was given without a provider; select a provider as well",
+                    name
+                ))
+            }
+            (model_provider, None) => {
+                let mut candidates = Vec::new();
+                if let (Some(provider), Some(name)) = (
+                    non_empty(preferences.model_provider),
+                    non_empty(preferences.model_name),
+                ) {
+                    candidates.push((provider, name));
+                }
+                match repos.settings.get_model_config().await {
+                    Ok(Some(config)) => candidates.push((config.provider, config.model)),
+                    Ok(None) => {}
+                    Err(e) => warn!("⚠️ Failed to load model config: {}", e),
+                }
+
+                let fallback = candidates.into_iter().find(|(provider, _)| {
+                    model_provider.as_deref().map_or(true, |p| p == provider)
+                });
+                match fallback {
+                    Some((provider, name)) => (model_provider.unwrap_or(provider), name),
+                    None => {
+                        return Err(format!(
+                            "No model configured for meeting {}; select a provider and model",
+                            meeting_id
+                        ))
+                    }
+                }
+            }
+        };
+
+        Ok(ResolvedSummarySettings {
+            model_provider,
+            model_name,
+            template_id,
+        })
     }
 
     /// Updates the summary process status to failed with error message
     ///
     /// # Arguments
-    /// * `pool` - SQLx connection pool
+    /// * `summaries` - Summary processes repository
     /// * `meeting_id` - Meeting identifier
     /// * `error_msg` - Error message to store
-    async fn update_process_failed(pool: &SqlitePool, meeting_id: &str, error_msg: &str) {
+    async fn update_process_failed(
+        summaries: &dyn SummaryProcessesRepo,
+        meeting_id: &str,
+        error_msg: &str,
+    ) {
         error!(
             "❌ Processing failed for meeting_id {}: {}",
             meeting_id, error_msg
         );
-        if let Err(e) =
-            SummaryProcessesRepository::update_process_failed(pool, meeting_id, error_msg).await
-        {
+        if let Err(e) = summaries.update_process_failed(meeting_id, error_msg).await {
             error!(
                 "⚠️ Failed to update DB status to failed for {}: {}",
                 meeting_id, e
@@ -335,3 +1720,406 @@ impl SummaryService {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::models::MeetingPreferences;
+    use crate::database::test_utils::segment;
+    use crate::http::test_server::{json_response, serve};
+
+    async fn pending_meeting(repos: &Repositories) -> String {
+        let segment = segment("s1", "Sam will fix the webhook by Friday", 0.0, 5.0);
+        let meeting_id = repos
+            .transcripts
+            .save_transcript("Standup", &[segment], None)
+            .await
+            .unwrap();
+        repos
+            .summaries
+            .create_or_reset_process(&meeting_id)
+            .await
+            .unwrap();
+        meeting_id
+    }
+
+    async fn run(repos: &Repositories, meeting_id: &str, provider: &str) {
+        SummaryService::process_transcript(
+            repos,
+            meeting_id.to_string(),
+            "Sam will fix the webhook by Friday".to_string(),
+            Some(provider.to_string()),
+            Some("gpt-4o".to_string()),
+            String::new(),
+            vec!["daily_standup".to_string()],
+            false,
+            false,
+            false,
+            false,
+            false,
+            false,
+            None,
+            None,
+            &SummaryPipelineConfig::default(),
+            &ContentFilterConfig::default(),
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_process_is_left_untouched() {
+        let repos = Repositories::in_memory();
+        let meeting_id = pending_meeting(&repos).await;
+        repos
+            .summaries
+            .update_process_failed(&meeting_id, "Process cancelled: New regeneration started")
+            .await
+            .unwrap();
+
+        run(&repos, &meeting_id, "openai").await;
+
+        let process = repos.summaries.get_summary_data(&meeting_id).await.unwrap().unwrap();
+        assert_eq!(process.status, "failed");
+        assert_eq!(
+            process.error.as_deref(),
+            Some("Process cancelled: New regeneration started")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_missing_process_is_not_created() {
+        let repos = Repositories::in_memory();
+
+        run(&repos, "meeting-unknown", "openai").await;
+
+        assert!(repos
+            .summaries
+            .get_summary_data("meeting-unknown")
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_unknown_provider_marks_process_failed() {
+        let repos = Repositories::in_memory();
+        let meeting_id = pending_meeting(&repos).await;
+
+        run(&repos, &meeting_id, "not-a-provider").await;
+
+        let process = repos.summaries.get_summary_data(&meeting_id).await.unwrap().unwrap();
+        assert_eq!(process.status, "failed");
+        assert!(process.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_missing_api_key_marks_process_failed() {
+        let repos = Repositories::in_memory();
+        let meeting_id = pending_meeting(&repos).await;
+        // A key for another provider doesn't count
+        repos.settings.save_api_key("claude", "sk-ant").await.unwrap();
+
+        run(&repos, &meeting_id, "openai").await;
+
+        let process = repos.summaries.get_summary_data(&meeting_id).await.unwrap().unwrap();
+        assert_eq!(process.status, "failed");
+        assert_eq!(process.error.as_deref(), Some("Api key not found for openai"));
+    }
+
+    #[tokio::test]
+    async fn test_unusable_primary_falls_through_to_fallbacks() {
+        let repos = Repositories::in_memory();
+        let pipeline = SummaryPipelineConfig::default();
+        repos.settings.save_api_key("claude", "sk-ant").await.unwrap();
+        let fallbacks = vec![crate::database::models::SummaryFallback {
+            provider: "claude".to_string(),
+            model: "claude-3-5-sonnet".to_string(),
+        }];
+        repos.settings.save_summary_fallbacks(&fallbacks).await.unwrap();
+
+        // No OpenAI key, so the primary can't be resolved
+        let primary = SummaryService::resolve_target(&repos, "openai", "gpt-4o", &pipeline).await;
+        let run = SummaryService::with_fallback_targets(&repos, primary, &pipeline)
+            .await
+            .unwrap();
+
+        assert!(run.primary_skipped);
+        assert_eq!(run.targets.len(), 1);
+        assert_eq!(run.targets[0].model_name, "claude-3-5-sonnet");
+
+        // Without a usable fallback the primary's error is reported
+        repos.settings.save_summary_fallbacks(&[]).await.unwrap();
+        let primary = SummaryService::resolve_target(&repos, "openai", "gpt-4o", &pipeline).await;
+        let error = SummaryService::with_fallback_targets(&repos, primary, &pipeline)
+            .await
+            .err();
+        assert_eq!(error.as_deref(), Some("Api key not found for openai"));
+    }
+
+    #[tokio::test]
+    async fn test_regeneration_uses_stored_preferences() {
+        let repos = Repositories::in_memory();
+        let meeting_id = pending_meeting(&repos).await;
+        repos
+            .settings
+            .save_model_config("claude", "claude-3-5-sonnet-latest", "large-v3", None)
+            .await
+            .unwrap();
+        repos.settings.save_api_key("claude", "sk-ant").await.unwrap();
+        repos
+            .meetings
+            .set_meeting_preferences(
+                &meeting_id,
+                &MeetingPreferences {
+                    template_id: Some("standard_meeting".to_string()),
+                    model_provider: Some("openai".to_string()),
+                    model_name: Some("gpt-4o".to_string()),
+                },
+            )
+            .await
+            .unwrap();
+
+        // No overrides: the stored OpenAI preference is used instead of the
+        // global Claude config, so the run fails on the missing OpenAI key
+        SummaryService::process_transcript(
+            &repos,
+            meeting_id.clone(),
+            "Sam will fix the webhook by Friday".to_string(),
+            None,
+            None,
+            String::new(),
+            Vec::new(),
+            false,
+            false,
+            false,
+            false,
+            false,
+            false,
+            None,
+            None,
+            &SummaryPipelineConfig::default(),
+            &ContentFilterConfig::default(),
+        )
+        .await;
+
+        let process = repos.summaries.get_summary_data(&meeting_id).await.unwrap().unwrap();
+        assert_eq!(process.status, "failed");
+        assert_eq!(process.error.as_deref(), Some("Api key not found for openai"));
+    }
+
+    #[tokio::test]
+    async fn test_preview_chunking_uses_resolved_model() {
+        let repos = Repositories::in_memory();
+        let pipeline = SummaryPipelineConfig::default();
+
+        assert!(SummaryService::preview_chunking(&repos, "meeting-unknown", &pipeline)
+            .await
+            .unwrap_err()
+            .contains("no transcript"));
+
+        let meeting_id = pending_meeting(&repos).await;
+        repos
+            .settings
+            .save_model_config("openai", "gpt-4o", "large-v3", None)
+            .await
+            .unwrap();
+        repos.settings.save_api_key("openai", "sk-test").await.unwrap();
+
+        // Cloud models take the whole transcript in one call
+        let preview = SummaryService::preview_chunking(&repos, &meeting_id, &pipeline)
+            .await
+            .unwrap();
+        assert!(preview.single_pass);
+        assert_eq!(preview.chunk_tokens, vec![preview.total_tokens]);
+        assert_eq!(preview.coarsened_from, None);
+        // Nothing was generated
+        let process = repos.summaries.get_summary_data(&meeting_id).await.unwrap().unwrap();
+        assert_eq!(process.status, "PENDING");
+    }
+
+    #[tokio::test]
+    async fn test_resolve_settings_precedence() {
+        let repos = Repositories::in_memory();
+        let meeting_id = pending_meeting(&repos).await;
+
+        // Nothing configured anywhere
+        assert!(SummaryService::resolve_settings(&repos, &meeting_id, None, None, None)
+            .await
+            .is_err());
+
+        repos
+            .settings
+            .save_model_config("claude", "claude-3-5-sonnet-latest", "large-v3", None)
+            .await
+            .unwrap();
+        let resolved = SummaryService::resolve_settings(&repos, &meeting_id, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            resolved,
+            ResolvedSummarySettings {
+                model_provider: "claude".to_string(),
+                model_name: "claude-3-5-sonnet-latest".to_string(),
+                template_id: "daily_standup".to_string(),
+            }
+        );
+
+        repos
+            .meetings
+            .set_meeting_preferences(
+                &meeting_id,
+                &MeetingPreferences {
+                    template_id: Some("standard_meeting".to_string()),
+                    model_provider: Some("ollama".to_string()),
+                    model_name: Some("llama3.2:latest".to_string()),
+                },
+            )
+            .await
+            .unwrap();
+        let resolved = SummaryService::resolve_settings(&repos, &meeting_id, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(resolved.model_provider, "ollama");
+        assert_eq!(resolved.model_name, "llama3.2:latest");
+        assert_eq!(resolved.template_id, "standard_meeting");
+
+        // Explicit values win; a provider-only override takes the model from
+        // the matching global config rather than the ollama preference
+        let resolved = SummaryService::resolve_settings(
+            &repos,
+            &meeting_id,
+            Some("claude".to_string()),
+            Some(String::new()),
+            Some("daily_standup".to_string()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(resolved.model_provider, "claude");
+        assert_eq!(resolved.model_name, "claude-3-5-sonnet-latest");
+        assert_eq!(resolved.template_id, "daily_standup");
+
+        // No model known for this provider
+        assert!(SummaryService::resolve_settings(
+            &repos,
+            &meeting_id,
+            Some("groq".to_string()),
+            None,
+            None
+        )
+        .await
+        .is_err());
+
+        // A model alone is never paired with a saved provider
+        let err = SummaryService::resolve_settings(
+            &repos,
+            &meeting_id,
+            None,
+            Some("gpt-4o".to_string()),
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert!(err.contains("without a provider"), "{}", err);
+    }
+
+    /// Ollama that is always up and summarizes with a sentence naming the model
+    async fn mock_ollama() -> String {
+        let (url, _) = serve(|request| {
+            let model = request
+                .split_once("\"model\":\"")
+                .and_then(|(_, rest)| rest.split_once('"'))
+                .map(|(model, _)| model.to_string());
+            let body = match model {
+                Some(model) if request.contains("/v1/chat/completions") => serde_json::json!({
+                    "choices": [{
+                        "message": {
+                            "content": format!("# Standup\n\n## Summary\n\nSummarized by {}", model)
+                        },
+                        "finish_reason": "stop"
+                    }]
+                })
+                .to_string(),
+                _ => "{}".to_string(),
+            };
+            json_response(200, &body)
+        })
+        .await;
+        url
+    }
+
+    #[tokio::test]
+    async fn test_compare_summaries_runs_each_model_independently() {
+        let repos = Repositories::in_memory();
+        let meeting_id = pending_meeting(&repos).await;
+        let endpoint = mock_ollama().await;
+        repos
+            .settings
+            .save_model_config("ollama", "compare-a", "large-v3", Some(endpoint.as_str()))
+            .await
+            .unwrap();
+
+        let models = ["compare-a", "compare-b"]
+            .into_iter()
+            .map(|model| ModelChoice {
+                provider: "ollama".to_string(),
+                model: model.to_string(),
+            })
+            .chain(std::iter::once(ModelChoice {
+                provider: "openai".to_string(),
+                model: "gpt-4o".to_string(),
+            }))
+            .collect();
+        let results = SummaryService::compare_summaries(
+            &repos,
+            &meeting_id,
+            models,
+            Some("standard_meeting".to_string()),
+            &SummaryPipelineConfig::default(),
+        )
+        .await
+        .unwrap();
+
+        let [a, b, openai] = results.as_slice() else {
+            panic!("expected three results: {:?}", results);
+        };
+        for (result, model) in [(a, "compare-a"), (b, "compare-b")] {
+            assert_eq!(result.model, model);
+            assert_eq!(result.error, None);
+            let markdown = result.markdown.as_deref().unwrap();
+            assert!(
+                markdown.contains(&format!("Summarized by {}", model)),
+                "{}",
+                markdown
+            );
+            assert!(result.input_tokens > 0 && result.output_tokens > 0);
+        }
+        // A model without an API key fails on its own
+        assert_eq!(openai.markdown, None);
+        assert!(openai
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("Api key not found"));
+
+        // The stored summary process is untouched
+        let process = repos
+            .summaries
+            .get_summary_data(&meeting_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(process.status, "PENDING");
+        assert_eq!(process.result, None);
+
+        let error = SummaryService::compare_summaries(
+            &repos,
+            &meeting_id,
+            Vec::new(),
+            None,
+            &SummaryPipelineConfig::default(),
+        )
+        .await
+        .unwrap_err();
+        assert!(error.contains("at least one model"));
+    }
+}
@@ -0,0 +1,377 @@
+//! Structured summaries: the final report as JSON in a fixed schema
+//!
+//! With `structured_output` on, the final report is requested as a JSON
+//! object and deserialized into `StructuredSummary`. When the response
+//! doesn't match, one repair call gives the model its output and the error
+//! back; if that doesn't match either, the run falls back to the markdown
+//! report. The schema is the same for every provider, so this works without
+//! provider-specific JSON modes.
+
+use crate::summary::llm_client::{LlmCompletion, LlmError};
+use crate::summary::question_generator::extract_fenced_block;
+use crate::summary::table::escape_cell;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+
+/// Shown to the model in the request and in the repair call
+pub const STRUCTURED_SUMMARY_SCHEMA: &str = r#"{
+  "type": "object",
+  "required": ["summary", "key_points", "decisions", "action_items", "open_questions"],
+  "properties": {
+    "title": {"type": "string", "description": "Short meeting title"},
+    "summary": {"type": "string", "description": "What was discussed, as a paragraph"},
+    "key_points": {"type": "array", "items": {"type": "string"}},
+    "decisions": {"type": "array", "items": {"type": "string"}},
+    "action_items": {
+      "type": "array",
+      "items": {
+        "type": "object",
+        "required": ["task"],
+        "properties": {
+          "task": {"type": "string"},
+          "owner": {"type": ["string", "null"]},
+          "deadline": {"type": ["string", "null"]}
+        }
+      }
+    },
+    "open_questions": {"type": "array", "items": {"type": "string"}}
+  }
+}"#;
+
+const STRUCTURED_SYSTEM_PROMPT: &str = r#"You are an expert meeting summarizer. Read the transcript and report on it as a single JSON object that matches this JSON schema:
+
+{schema}
+
+**RULES:**
+1. Output ONLY the JSON object: no markdown, no code fences, no text before or after it.
+2. Use specific names, dates, task IDs and deadlines from the transcript.
+3. Use empty arrays for lists with nothing to report, and null for an unknown owner or deadline. Never write placeholders like "TBD" or "None"."#;
+
+const REPAIR_SYSTEM_PROMPT: &str = r#"You fix JSON so it matches a JSON schema. Keep the content, change only what the schema requires. Output ONLY the corrected JSON object: no markdown, no code fences, no explanations.
+
+Schema:
+{schema}"#;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StructuredActionItem {
+    pub task: String,
+    #[serde(default)]
+    pub owner: Option<String>,
+    #[serde(default)]
+    pub deadline: Option<String>,
+}
+
+/// The final report as `STRUCTURED_SUMMARY_SCHEMA` describes it; fields the
+/// model adds beyond the schema are ignored
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StructuredSummary {
+    #[serde(default)]
+    pub title: Option<String>,
+    pub summary: String,
+    pub key_points: Vec<String>,
+    pub decisions: Vec<String>,
+    pub action_items: Vec<StructuredActionItem>,
+    pub open_questions: Vec<String>,
+}
+
+impl StructuredSummary {
+    /// Checks what the types alone don't
+    pub fn validate(&self) -> Result<(), String> {
+        if self.summary.trim().is_empty() {
+            return Err("\"summary\" must not be empty".to_string());
+        }
+        if let Some(index) = self
+            .action_items
+            .iter()
+            .position(|item| item.task.trim().is_empty())
+        {
+            return Err(format!(
+                "\"action_items[{}].task\" must not be empty",
+                index
+            ));
+        }
+        Ok(())
+    }
+
+    /// The report as markdown, stored like any other summary; the title
+    /// becomes the `# ` heading the meeting name is read from
+    pub fn to_markdown(&self) -> String {
+        let mut sections = Vec::new();
+        if let Some(title) = self
+            .title
+            .as_deref()
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+        {
+            sections.push(format!("# {}", title));
+        }
+        sections.push(format!("## Summary\n\n{}", self.summary.trim()));
+        for (heading, items) in [
+            ("Key Points", &self.key_points),
+            ("Key Decisions", &self.decisions),
+        ] {
+            if let Some(list) = bullet_list(items) {
+                sections.push(format!("## {}\n\n{}", heading, list));
+            }
+        }
+        if !self.action_items.is_empty() {
+            let cell = |value: &Option<String>| {
+                value
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|v| !v.is_empty())
+                    .map(escape_cell)
+                    .unwrap_or_default()
+            };
+            let rows: Vec<String> = self
+                .action_items
+                .iter()
+                .map(|item| {
+                    format!(
+                        "| {} | {} | {} |",
+                        escape_cell(item.task.trim()),
+                        cell(&item.owner),
+                        cell(&item.deadline)
+                    )
+                })
+                .collect();
+            sections.push(format!(
+                "## Action Items\n\n| Task | Owner | Deadline |\n| --- | --- | --- |\n{}",
+                rows.join("\n")
+            ));
+        }
+        if let Some(list) = bullet_list(&self.open_questions) {
+            sections.push(format!("## Open Questions\n\n{}", list));
+        }
+        sections.join("\n\n")
+    }
+}
+
+fn bullet_list(items: &[String]) -> Option<String> {
+    let bullets: Vec<String> = items
+        .iter()
+        .map(|item| item.trim())
+        .filter(|item| !item.is_empty())
+        .map(|item| format!("- {}", item))
+        .collect();
+    (!bullets.is_empty()).then(|| bullets.join("\n"))
+}
+
+/// System prompt asking for the report as JSON
+pub fn structured_system_prompt() -> String {
+    STRUCTURED_SYSTEM_PROMPT.replace("{schema}", STRUCTURED_SUMMARY_SCHEMA)
+}
+
+/// Parses and validates a model response
+///
+/// A code fence around the object and prose before or after it are
+/// tolerated; the error says what didn't match the schema.
+pub fn parse_structured_summary(response: &str) -> Result<StructuredSummary, String> {
+    let trimmed = response.trim();
+    let candidate = extract_fenced_block(trimmed)
+        .filter(|block| block.contains('{'))
+        .unwrap_or(trimmed);
+    let start = candidate
+        .find('{')
+        .ok_or_else(|| "The response contains no JSON object".to_string())?;
+    let summary = serde_json::Deserializer::from_str(&candidate[start..])
+        .into_iter::<StructuredSummary>()
+        .next()
+        .ok_or_else(|| "The response contains no JSON object".to_string())?
+        .map_err(|e| format!("The JSON doesn't match the schema: {}", e))?;
+    summary.validate()?;
+    Ok(summary)
+}
+
+/// Prompts of the repair call for a response that failed with `error`
+fn repair_prompts(response: &str, error: &str) -> (String, String) {
+    (
+        REPAIR_SYSTEM_PROMPT.replace("{schema}", STRUCTURED_SUMMARY_SCHEMA),
+        format!(
+            "This JSON was rejected: {}\n\n<json>\n{}\n</json>",
+            error,
+            response.trim()
+        ),
+    )
+}
+
+/// A report that matched the schema
+#[derive(Debug)]
+pub struct StructuredReport {
+    pub summary: StructuredSummary,
+    /// It took the repair call
+    pub repaired: bool,
+    /// The call that produced it stopped at the output limit
+    pub truncated: bool,
+}
+
+/// Requests the report as JSON with `user_prompt`, repairing it once if it
+/// doesn't match the schema
+///
+/// `complete` is called with the system and user prompt. Returns None when
+/// the repaired response doesn't match either, for the caller to fall back
+/// to markdown; a failed first call is returned as the error, so provider
+/// fallbacks still apply.
+pub async fn generate_structured_summary<F, Fut>(
+    user_prompt: &str,
+    complete: F,
+) -> Result<Option<StructuredReport>, LlmError>
+where
+    F: Fn(String, String) -> Fut,
+    Fut: Future<Output = Result<LlmCompletion, LlmError>>,
+{
+    let completion = complete(structured_system_prompt(), user_prompt.to_string()).await?;
+    let error = match parse_structured_summary(&completion.text) {
+        Ok(summary) => {
+            info!("🧩 Structured summary matched the schema");
+            return Ok(Some(StructuredReport {
+                summary,
+                repaired: false,
+                truncated: completion.truncated(),
+            }));
+        }
+        Err(e) => e,
+    };
+
+    warn!(
+        "⚠️ Structured summary rejected ({}), asking the model to repair it",
+        error
+    );
+    let (system, user) = repair_prompts(&completion.text, &error);
+    let repaired = match complete(system, user).await {
+        Ok(repaired) => repaired,
+        Err(e) => {
+            warn!("⚠️ Structured summary repair call failed: {}", e);
+            return Ok(None);
+        }
+    };
+    match parse_structured_summary(&repaired.text) {
+        Ok(summary) => {
+            info!("🧩 Repaired structured summary matched the schema");
+            Ok(Some(StructuredReport {
+                summary,
+                repaired: true,
+                truncated: repaired.truncated(),
+            }))
+        }
+        Err(e) => {
+            warn!("⚠️ Repaired structured summary still rejected: {}", e);
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    const VALID: &str = r#"{
+        "title": "Billing Sync",
+        "summary": "The team planned the Stripe webhook fix.",
+        "key_points": ["Retries fail after 3 attempts"],
+        "decisions": ["Ship the fix on Friday"],
+        "action_items": [{"task": "Fix webhook retries (PAY-42)", "owner": "Sam", "deadline": "Friday"}],
+        "open_questions": [],
+        "sentiment": "positive"
+    }"#;
+
+    /// Answers each call with the next response and records the prompts
+    struct ScriptedModel {
+        responses: Mutex<Vec<&'static str>>,
+        calls: Mutex<Vec<(String, String)>>,
+    }
+
+    impl ScriptedModel {
+        fn new(responses: &[&'static str]) -> Self {
+            Self {
+                responses: Mutex::new(responses.iter().rev().copied().collect()),
+                calls: Mutex::new(Vec::new()),
+            }
+        }
+
+        async fn complete(&self, system: String, user: String) -> Result<LlmCompletion, LlmError> {
+            self.calls.lock().unwrap().push((system, user));
+            let text = self
+                .responses
+                .lock()
+                .unwrap()
+                .pop()
+                .ok_or("no more responses")?;
+            Ok(LlmCompletion {
+                text: text.to_string(),
+                finish_reason: Some("stop".to_string()),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_valid_payload_is_used_without_repair() {
+        let model = ScriptedModel::new(&[VALID]);
+        let report = generate_structured_summary("<transcript>...</transcript>", |s, u| {
+            model.complete(s, u)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+        assert!(!report.repaired);
+        assert_eq!(model.calls.lock().unwrap().len(), 1);
+        assert_eq!(report.summary.action_items[0].owner.as_deref(), Some("Sam"));
+
+        let markdown = report.summary.to_markdown();
+        assert!(markdown.starts_with("# Billing Sync\n\n## Summary\n\nThe team planned"));
+        assert!(markdown.contains("## Key Decisions\n\n- Ship the fix on Friday"));
+        assert!(markdown.contains(
+            "| Task | Owner | Deadline |\n| --- | --- | --- |\n| Fix webhook retries (PAY-42) | Sam | Friday |"
+        ));
+        assert!(!markdown.contains("Open Questions"));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_payload_is_repaired_once() {
+        // Fenced, with a string where the schema wants a list
+        let broken = "Here is the summary:\n```json\n{\"summary\": \"Stripe fix planned.\", \"key_points\": \"retries\", \"decisions\": [], \"action_items\": [], \"open_questions\": []}\n```";
+        let model = ScriptedModel::new(&[broken, VALID]);
+        let report = generate_structured_summary("<transcript>...</transcript>", |s, u| {
+            model.complete(s, u)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+        assert!(report.repaired);
+        assert_eq!(report.summary.title.as_deref(), Some("Billing Sync"));
+
+        let calls = model.calls.lock().unwrap();
+        assert_eq!(calls.len(), 2);
+        let (repair_system, repair_user) = &calls[1];
+        assert!(repair_system.contains("\"open_questions\""));
+        assert!(repair_user.contains("key_points"), "{}", repair_user);
+        assert!(repair_user.contains("\"retries\""));
+    }
+
+    #[tokio::test]
+    async fn test_unrepairable_payload_falls_back() {
+        let model = ScriptedModel::new(&[
+            "## Summary\n\nThe team planned the fix.",
+            r#"{"summary": "  ", "key_points": [], "decisions": [], "action_items": [], "open_questions": []}"#,
+        ]);
+        let report = generate_structured_summary("<transcript>...</transcript>", |s, u| {
+            model.complete(s, u)
+        })
+        .await
+        .unwrap();
+        assert!(report.is_none());
+        assert_eq!(model.calls.lock().unwrap().len(), 2);
+
+        // A failing first call is a provider error, not a schema problem
+        let model = ScriptedModel::new(&[]);
+        assert!(
+            generate_structured_summary("<transcript>...</transcript>", |s, u| {
+                model.complete(s, u)
+            })
+            .await
+            .is_err()
+        );
+    }
+}
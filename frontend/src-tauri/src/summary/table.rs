@@ -0,0 +1,281 @@
+//! Markdown table helpers shared by the summary post-processing, the
+//! Action Items parser and the normalizer for hand-edited summaries
+
+use crate::summary::templates::TableSchema;
+
+pub fn is_table_line(line: &str) -> bool {
+    line.trim_start().starts_with('|')
+}
+
+/// Opening or closing line of a fenced code block (```` ``` ```` or `~~~`)
+pub fn is_fence_line(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with("```") || trimmed.starts_with("~~~")
+}
+
+pub fn is_separator_row(line: &str) -> bool {
+    let line = line.trim();
+    line.starts_with('|')
+        && line.contains('-')
+        && line.chars().all(|c| matches!(c, '|' | '-' | ':' | ' '))
+}
+
+/// Splits a row on its pipes; escaped pipes (`\|`) stay in the cell
+pub fn split_cells(line: &str) -> Vec<String> {
+    let line = line.trim();
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut escaped = false;
+    for c in line.chars() {
+        if c == '|' && !escaped {
+            cells.push(std::mem::take(&mut cell));
+        } else {
+            cell.push(c);
+        }
+        escaped = c == '\\' && !escaped;
+    }
+    cells.push(cell);
+
+    // Outer pipes leave an empty cell at either end
+    if line.starts_with('|') {
+        cells.remove(0);
+    }
+    if cells.len() > 1 && line.ends_with('|') && !line.ends_with("\\|") {
+        cells.pop();
+    }
+    cells
+        .into_iter()
+        .map(|cell| cell.trim().to_string())
+        .collect()
+}
+
+/// Makes text safe for a single cell: pipes are escaped and line breaks
+/// become spaces. Already escaped pipes are left alone.
+pub fn escape_cell(text: &str) -> String {
+    let mut cell = String::with_capacity(text.len());
+    let mut escaped = false;
+    for c in text.chars() {
+        match c {
+            '|' if !escaped => cell.push_str("\\|"),
+            '\r' => {}
+            '\n' => cell.push(' '),
+            _ => cell.push(c),
+        }
+        escaped = c == '\\' && !escaped;
+    }
+    cell
+}
+
+/// Cell text with escaped pipes turned back into plain ones
+pub fn unescape_cell(cell: &str) -> String {
+    cell.replace("\\|", "|")
+}
+
+/// A row of a `columns` wide table that lost its leading pipe while being
+/// edited ("Sam | Fix login |"). It has to split into exactly the table's
+/// columns, so prose that merely contains a pipe ends the table instead.
+fn is_unpiped_row(line: &str, columns: usize) -> bool {
+    let trimmed = line.trim();
+    !trimmed.is_empty()
+        && !trimmed.starts_with('#')
+        && trimmed.contains('|')
+        && split_cells(trimmed).len() == columns
+}
+
+/// What `normalize_tables` changed, counted across all tables
+#[derive(Debug, Default, PartialEq)]
+struct TableFixes {
+    separators: usize,
+    filled_cells: usize,
+    merged_rows: usize,
+    empty_rows: usize,
+    repiped_rows: usize,
+    blank_lines: usize,
+}
+
+impl TableFixes {
+    fn describe(&self) -> Vec<String> {
+        let mut fixes = Vec::new();
+        let mut note = |count: usize, text: &str| {
+            if count > 0 {
+                fixes.push(format!("{} ({})", text, count));
+            }
+        };
+        note(self.separators, "Added or repaired table separator rows");
+        note(self.filled_cells, "Filled missing table cells with \"Not specified\"");
+        note(self.merged_rows, "Merged extra cells into the last column");
+        note(self.empty_rows, "Removed empty table rows");
+        note(self.repiped_rows, "Restored missing pipes on table rows");
+        note(self.blank_lines, "Added blank lines around tables");
+        fixes
+    }
+}
+
+/// Rewrites every table so each row has exactly the header's columns
+///
+/// Short rows are padded with "Not specified", cells past the last column
+/// are joined into it with " / ", empty rows and stray separators are
+/// dropped, and the separator row is rebuilt to match the header. Tables in
+/// fenced code blocks are left alone. Returns the markdown and a description
+/// of each kind of fix applied.
+pub fn normalize_tables(markdown: &str) -> (String, Vec<String>) {
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut output: Vec<String> = Vec::new();
+    let mut fixes = TableFixes::default();
+    let mut table: Option<TableSchema> = None;
+    let mut in_fence = false;
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+
+        // A fence line ends any open table below, so this only runs outside tables
+        if table.is_none() && (in_fence || is_fence_line(line)) {
+            in_fence ^= is_fence_line(line);
+            output.push(line.to_string());
+            i += 1;
+            continue;
+        }
+
+        let Some(schema) = &table else {
+            if !is_table_line(line) || is_separator_row(line) {
+                output.push(line.to_string());
+                i += 1;
+                continue;
+            }
+            // Header row starts a table
+            if output.last().map_or(false, |previous| !previous.trim().is_empty()) {
+                output.push(String::new());
+                fixes.blank_lines += 1;
+            }
+            let header = split_cells(line);
+            let schema = TableSchema::from_header(&header);
+            output.push(format!("| {} |", header.join(" | ")));
+            let separator = schema.separator();
+            match lines.get(i + 1) {
+                Some(next) if is_separator_row(next) => {
+                    if split_cells(next).len() != header.len() {
+                        fixes.separators += 1;
+                    }
+                    i += 1;
+                }
+                _ => fixes.separators += 1,
+            }
+            output.push(separator);
+            table = Some(schema);
+            i += 1;
+            continue;
+        };
+
+        if !is_table_line(line) && !is_unpiped_row(line, schema.columns.len()) {
+            // End of the table
+            if !line.trim().is_empty() {
+                output.push(String::new());
+                fixes.blank_lines += 1;
+            }
+            table = None;
+            continue;
+        }
+        i += 1;
+
+        if is_separator_row(line) {
+            fixes.separators += 1;
+            continue;
+        }
+        let mut cells = split_cells(line);
+        if cells.iter().all(|cell| cell.is_empty()) {
+            fixes.empty_rows += 1;
+            continue;
+        }
+        if !is_table_line(line) {
+            fixes.repiped_rows += 1;
+        }
+
+        let columns = schema.columns.len();
+        while cells.len() > columns && cells.last().map_or(false, |c| c.is_empty()) {
+            cells.pop();
+        }
+        if cells.len() > columns {
+            let extra: Vec<String> = cells.split_off(columns);
+            let last = &mut cells[columns - 1];
+            let joined = std::iter::once(last.as_str())
+                .chain(extra.iter().map(String::as_str))
+                .filter(|cell| !cell.is_empty())
+                .collect::<Vec<_>>()
+                .join(" / ");
+            *last = joined;
+            fixes.merged_rows += 1;
+        }
+        fixes.filled_cells += (0..columns)
+            .filter(|&c| cells.get(c).map_or(true, |cell| cell.is_empty()))
+            .count();
+        output.push(schema.row(&cells));
+    }
+
+    let mut result = output.join("\n");
+    if markdown.ends_with('\n') {
+        result.push('\n');
+    }
+    (result, fixes.describe())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_misaligned_table_is_repaired() {
+        let markdown = "## Action Items\n| **Owner** | Task | Due |\n|---|---|\n| Sam | Fix PROJ-404 webhook |\n| Ana | Review | Friday | | |\nPriya | Update docs | Monday |\n| | | |\n|---|---|---|\n| Lee | Ship | release | Monday |\nNext steps follow.";
+
+        let (output, fixes) = normalize_tables(markdown);
+
+        assert_eq!(
+            output,
+            "## Action Items\n\n| **Owner** | Task | Due |\n| --- | --- | --- |\n| Sam | Fix PROJ-404 webhook | Not specified |\n| Ana | Review | Friday |\n| Priya | Update docs | Monday |\n| Lee | Ship | release / Monday |\n\nNext steps follow."
+        );
+        assert_eq!(
+            fixes,
+            vec![
+                "Added or repaired table separator rows (2)",
+                "Filled missing table cells with \"Not specified\" (1)",
+                "Merged extra cells into the last column (1)",
+                "Removed empty table rows (1)",
+                "Restored missing pipes on table rows (1)",
+                "Added blank lines around tables (2)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_escaped_pipes_stay_in_their_cell() {
+        let cell = escape_cell("Compare A|B\nthen pick \\| one");
+        assert_eq!(cell, "Compare A\\|B then pick \\| one");
+        assert_eq!(escape_cell(&cell), cell);
+
+        let cells = split_cells(&format!("| Sam | {} | ends in \\| |", cell));
+        assert_eq!(cells, vec!["Sam", cell.as_str(), "ends in \\|"]);
+        assert_eq!(unescape_cell(&cells[1]), "Compare A|B then pick | one");
+        assert_eq!(split_cells("|a||b|"), vec!["a", "", "b"]);
+        assert_eq!(split_cells("| |"), vec![""]);
+    }
+
+    #[test]
+    fn test_code_blocks_and_prose_with_pipes_are_left_alone() {
+        let markdown = "| A | B |\n| --- | --- |\n| 1 | 2 |\nUse `a | b | c` to pipe it.\n\n```\n| raw | table\n|---|\n```\n~~~text\nx | y\n~~~";
+
+        let (output, _) = normalize_tables(markdown);
+
+        assert_eq!(
+            output,
+            "| A | B |\n| --- | --- |\n| 1 | 2 |\n\nUse `a | b | c` to pipe it.\n\n```\n| raw | table\n|---|\n```\n~~~text\nx | y\n~~~"
+        );
+    }
+
+    #[test]
+    fn test_well_formed_table_is_unchanged() {
+        let markdown = "Intro\n\n| A | B |\n| --- | --- |\n| 1 | 2 |\n";
+        let (output, fixes) = normalize_tables(markdown);
+        assert_eq!(output, markdown);
+        assert!(fixes.is_empty());
+    }
+}
@@ -1,4 +1,5 @@
 use crate::summary::templates;
+use crate::summary::templates::remote::{self, RemoteTemplateConfig, RemoteTemplateRefresh};
 use serde::{Deserialize, Serialize};
 use tauri::Runtime;
 use tracing::{info, warn};
@@ -30,6 +31,9 @@ pub struct TemplateDetails {
 
     /// List of section titles in order
     pub sections: Vec<String>,
+
+    /// Custom system prompt for the final summary, if the template has one
+    pub system_prompt_override: Option<String>,
 }
 
 /// Lists all available templates
@@ -88,6 +92,7 @@ pub async fn api_get_template_details<R: Runtime>(
         name: template.name,
         description: template.description,
         sections: section_titles,
+        system_prompt_override: template.system_prompt_override,
     };
 
     info!("Retrieved template details for '{}'", details.name);
@@ -123,6 +128,53 @@ pub async fn api_validate_template<R: Runtime>(
     }
 }
 
+#[tauri::command]
+pub async fn api_get_remote_template_config<R: Runtime>(
+    app: tauri::AppHandle<R>,
+) -> Result<RemoteTemplateConfig, String> {
+    Ok(remote::load_remote_template_config(&app))
+}
+
+/// Saves the template registry URL and loads its templates; without a URL
+/// the remote templates are removed
+#[tauri::command]
+pub async fn api_set_remote_template_config<R: Runtime>(
+    app: tauri::AppHandle<R>,
+    config: RemoteTemplateConfig,
+) -> Result<Option<RemoteTemplateRefresh>, String> {
+    info!("api_set_remote_template_config called: {:?}", config);
+    let config = RemoteTemplateConfig {
+        url: config
+            .url
+            .map(|url| url.trim().to_string())
+            .filter(|url| !url.is_empty()),
+    };
+    config.validate()?;
+    remote::save_remote_template_config(&app, &config)?;
+    match config.url {
+        Some(url) => Ok(Some(remote::refresh_remote_templates(&url).await)),
+        None => {
+            remote::clear_remote_templates();
+            Ok(None)
+        }
+    }
+}
+
+/// Fetches the organization's templates again
+///
+/// When the registry can't be reached the cached templates stay in use and
+/// the result says so.
+#[tauri::command]
+pub async fn api_refresh_remote_templates<R: Runtime>(
+    app: tauri::AppHandle<R>,
+) -> Result<RemoteTemplateRefresh, String> {
+    info!("api_refresh_remote_templates called");
+    let url = remote::load_remote_template_config(&app)
+        .url
+        .ok_or_else(|| "No template registry URL is configured".to_string())?;
+    Ok(remote::refresh_remote_templates(&url).await)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
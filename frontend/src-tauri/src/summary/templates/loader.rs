@@ -1,4 +1,5 @@
 use super::defaults;
+use super::remote;
 use super::types::Template;
 use std::path::PathBuf;
 use tracing::{debug, info, warn};
@@ -22,7 +23,7 @@ pub fn set_bundled_templates_dir(path: PathBuf) {
 /// - macOS: ~/Library/Application Support/str8_2task/templates/
 /// - Windows: %APPDATA%\str8_2task\templates\
 /// - Linux: ~/.config/str8_2task/templates/
-fn get_custom_templates_dir() -> Option<PathBuf> {
+pub fn get_custom_templates_dir() -> Option<PathBuf> {
     let mut path = dirs::data_dir()?;
     path.push("str8_2task");
     path.push("templates");
@@ -83,9 +84,10 @@ fn load_custom_template(template_id: &str) -> Option<String> {
 ///
 /// This function implements a fallback strategy:
 /// 1. Check user's custom templates directory
-/// 2. Check bundled resources directory (app templates)
-/// 3. Fall back to built-in embedded templates
-/// 4. Return error if not found in any location
+/// 2. Check the organization's remote templates (already validated)
+/// 3. Check bundled resources directory (app templates)
+/// 4. Fall back to built-in embedded templates
+/// 5. Return error if not found in any location
 ///
 /// # Arguments
 /// * `template_id` - Template identifier (e.g., "daily_standup", "standard_meeting")
@@ -95,10 +97,13 @@ fn load_custom_template(template_id: &str) -> Option<String> {
 pub fn get_template(template_id: &str) -> Result<Template, String> {
     info!("Loading template: {}", template_id);
 
-    // Try custom template first, then bundled, then built-in
+    // Try custom template first, then remote, then bundled, then built-in
     let json_content = if let Some(custom_content) = load_custom_template(template_id) {
         debug!("Using custom template for '{}'", template_id);
         custom_content
+    } else if let Some(template) = remote::remote_template(template_id) {
+        debug!("Using remote template for '{}'", template_id);
+        return Ok(template);
     } else if let Some(bundled_content) = load_bundled_template(template_id) {
         debug!("Using bundled template for '{}'", template_id);
         bundled_content
@@ -126,19 +131,71 @@ pub fn get_template(template_id: &str) -> Result<Template, String> {
 /// Parsed and validated Template struct
 pub fn validate_and_parse_template(json_content: &str) -> Result<Template, String> {
     let template: Template = serde_json::from_str(json_content)
-        .map_err(|e| format!("Failed to parse template JSON: {}", e))?;
+        .map_err(|e| format!("Failed to parse template JSON: {}", describe_parse_error(&e)))?;
 
     template.validate()?;
 
     Ok(template)
 }
 
+/// Turns a serde error into a message for template authors, e.g.
+/// "unknown field 'sectins' at line 4 — did you mean 'sections'?"
+fn describe_parse_error(error: &serde_json::Error) -> String {
+    let message = error.to_string();
+    let location = format!(" at line {} column {}", error.line(), error.column());
+    let message = message.strip_suffix(&location).unwrap_or(&message);
+
+    let at_line = if error.line() > 0 {
+        format!(" at line {}", error.line())
+    } else {
+        String::new()
+    };
+
+    // serde quotes names in backticks: "unknown field `x`, expected one of `a`, `b`"
+    if let Some(fields) = message.strip_prefix("unknown field ") {
+        let mut names = fields.split('`').skip(1).step_by(2);
+        if let Some(unknown) = names.next() {
+            let suggestion = closest_field(unknown, names)
+                .map(|field| format!(" — did you mean '{}'?", field))
+                .unwrap_or_default();
+            return format!("unknown field '{}'{}{}", unknown, at_line, suggestion);
+        }
+    }
+    format!("{}{}", message.replace('`', "'"), at_line)
+}
+
+/// The expected field a typo most likely meant, if any is close enough
+fn closest_field<'a>(unknown: &str, expected: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    let max_distance = (unknown.chars().count() / 3).max(2);
+    expected
+        .map(|field| (edit_distance(unknown, field), field))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, field)| field)
+}
+
+/// Levenshtein distance between two strings, counted in characters
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
 /// List all available template identifiers
 ///
 /// Returns only "standard_meeting" - other templates are disabled
 /// - Built-in template IDs (filtered to only standard_meeting)
 /// - Bundled template IDs (filtered to only standard_meeting)
 /// - Custom template IDs (filtered to only standard_meeting)
+/// - Remote template IDs, all of them
 pub fn list_template_ids() -> Vec<String> {
     let mut ids: Vec<String> = defaults::list_builtin_template_ids()
         .into_iter()
@@ -195,6 +252,12 @@ pub fn list_template_ids() -> Vec<String> {
         }
     }
 
+    for id in remote::remote_template_ids() {
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+
     ids.sort();
     ids
 }
@@ -251,4 +314,54 @@ mod tests {
         let result = validate_and_parse_template("invalid json");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_unknown_fields_suggest_the_closest_name() {
+        let json = r#"{
+    "name": "Typo",
+    "description": "Misspelled sections",
+    "sectins": []
+}"#;
+        let error = validate_and_parse_template(json).unwrap_err();
+        assert_eq!(
+            error,
+            "Failed to parse template JSON: unknown field 'sectins' at line 4 — did you mean 'sections'?"
+        );
+
+        // Nested sections and columns reject unknown fields too
+        let json = r#"{"name": "T", "description": "D", "sections": [
+            {"title": "Notes", "instruction": "Take notes", "format": "list", "fromat": "list"}
+        ]}"#;
+        let error = validate_and_parse_template(json).unwrap_err();
+        assert!(error.ends_with("did you mean 'format'?"), "{}", error);
+
+        let json = r#"{"name": "T", "description": "D", "unrelated": true, "sections": []}"#;
+        let error = validate_and_parse_template(json).unwrap_err();
+        assert_eq!(error, "Failed to parse template JSON: unknown field 'unrelated' at line 1");
+    }
+
+    #[test]
+    fn test_wrong_types_are_described() {
+        let json = r#"{
+    "name": "T",
+    "description": "D",
+    "sections": [{"title": 5, "instruction": "I", "format": "list"}]
+}"#;
+        let error = validate_and_parse_template(json).unwrap_err();
+        assert_eq!(
+            error,
+            "Failed to parse template JSON: invalid type: integer '5', expected a string at line 4"
+        );
+
+        let error = validate_and_parse_template(r#"{"name": "T", "description": "D"}"#).unwrap_err();
+        assert!(error.contains("missing field 'sections'"), "{}", error);
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "ab"), 2);
+        assert_eq!(closest_field("titel", ["title", "instruction"].into_iter()), Some("title"));
+        assert_eq!(closest_field("xyz", ["name", "sections"].into_iter()), None);
+    }
 }
@@ -8,7 +8,10 @@
 //!
 //! - **Built-in templates**: JSON files in `frontend/src-tauri/templates/` embedded at compile time
 //! - **Custom templates**: JSON files in platform-specific app data directory
-//! - **Fallback strategy**: Custom templates override built-in templates with the same ID
+//! - **Remote templates**: an organization's shared templates, fetched from a
+//!   configured URL and cached locally (see `remote`)
+//! - **Fallback strategy**: Custom templates override remote templates, which override
+//!   bundled and built-in templates with the same ID
 //!
 //! # Usage
 //!
@@ -36,17 +39,20 @@
 //! - Linux: `~/.config/str8_2task/templates/`
 //!
 //! Custom templates must follow the JSON schema defined in `types::Template`.
+//! Unknown fields are rejected, so a misspelled field is reported (with the
+//! closest known name) instead of silently ignored.
 
 mod defaults;
 mod loader;
+pub mod remote;
 mod types;
 
 // Re-export public API
 pub use loader::{
-    get_template, list_template_ids, list_templates, set_bundled_templates_dir,
-    validate_and_parse_template,
+    get_custom_templates_dir, get_template, list_template_ids, list_templates,
+    set_bundled_templates_dir, validate_and_parse_template,
 };
-pub use types::{Template, TemplateSection};
+pub use types::{SectionMode, TableColumn, TableSchema, Template, TemplateSection};
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,367 @@
+//! Templates shared by an organization from a URL
+//!
+//! The URL returns a JSON array of templates, each with an `id` next to the
+//! usual template fields (or an object with that array under `templates`).
+//! Every template is checked with `Template::validate` before it is added;
+//! the accepted ones are cached so the last good set is used while the URL
+//! can't be reached.
+
+use super::loader::validate_and_parse_template;
+use super::types::Template;
+use crate::http::client_factory;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use std::time::Duration;
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+use tracing::{info, warn};
+
+const CONFIG_STORE: &str = "remote-templates.json";
+const CONFIG_KEY: &str = "config";
+const CACHE_FILE: &str = "remote_templates_cache.json";
+const FETCH_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Remote templates by id, as last fetched or read from the cache
+static REMOTE_TEMPLATES: Lazy<RwLock<BTreeMap<String, Template>>> =
+    Lazy::new(|| RwLock::new(BTreeMap::new()));
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RemoteTemplateConfig {
+    /// None turns the remote source off
+    pub url: Option<String>,
+}
+
+impl RemoteTemplateConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        match self.url.as_deref() {
+            Some(url) if !(url.starts_with("https://") || url.starts_with("http://")) => {
+                Err(format!(
+                    "Template registry URL must start with http:// or https://: {}",
+                    url
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+pub fn load_remote_template_config<R: Runtime>(app: &AppHandle<R>) -> RemoteTemplateConfig {
+    let stored = app
+        .store(CONFIG_STORE)
+        .ok()
+        .and_then(|store| store.get(CONFIG_KEY));
+    match stored.map(serde_json::from_value::<RemoteTemplateConfig>) {
+        Some(Ok(config)) => config,
+        Some(Err(e)) => {
+            warn!(
+                "⚠️ Invalid remote template config in store, using defaults: {}",
+                e
+            );
+            RemoteTemplateConfig::default()
+        }
+        None => RemoteTemplateConfig::default(),
+    }
+}
+
+pub fn save_remote_template_config<R: Runtime>(
+    app: &AppHandle<R>,
+    config: &RemoteTemplateConfig,
+) -> Result<(), String> {
+    let store = app
+        .store(CONFIG_STORE)
+        .map_err(|e| format!("Failed to open remote template store: {}", e))?;
+    let value = serde_json::to_value(config)
+        .map_err(|e| format!("Failed to serialize remote template config: {}", e))?;
+    store.set(CONFIG_KEY, value);
+    store
+        .save()
+        .map_err(|e| format!("Failed to save remote template config: {}", e))
+}
+
+/// A template from the registry that was not added
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RejectedTemplate {
+    /// The entry's id, or its position when it has none
+    pub id: String,
+    pub error: String,
+}
+
+/// Outcome of `refresh_remote_templates`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RemoteTemplateRefresh {
+    /// Ids of the remote templates now in use
+    pub loaded: Vec<String>,
+    pub rejected: Vec<RejectedTemplate>,
+    /// The fetch failed and the cached templates are used instead
+    pub from_cache: bool,
+    pub error: Option<String>,
+}
+
+fn cache_path() -> Option<PathBuf> {
+    Some(dirs::data_dir()?.join("str8_2task").join(CACHE_FILE))
+}
+
+fn valid_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Validates every template in a registry response
+///
+/// Returns the accepted templates with the JSON they were read from, for
+/// the cache, and the rejected ones with the reason.
+pub fn parse_remote_templates(
+    body: &str,
+) -> Result<(Vec<(String, Template, Value)>, Vec<RejectedTemplate>), String> {
+    let value: Value = serde_json::from_str(body)
+        .map_err(|e| format!("Template registry did not return JSON: {}", e))?;
+    let entries = match value {
+        Value::Array(entries) => entries,
+        Value::Object(mut object) => match object.remove("templates") {
+            Some(Value::Array(entries)) => entries,
+            _ => return Err("Template registry response has no template list".to_string()),
+        },
+        _ => return Err("Template registry response has no template list".to_string()),
+    };
+
+    let mut accepted: Vec<(String, Template, Value)> = Vec::new();
+    let mut rejected = Vec::new();
+    for (index, entry) in entries.into_iter().enumerate() {
+        let mut fields = entry;
+        let id = fields
+            .as_object_mut()
+            .and_then(|object| object.remove("id"))
+            .and_then(|id| id.as_str().map(str::to_string));
+        let label = id.clone().unwrap_or_else(|| format!("#{}", index + 1));
+        let result = match id {
+            None => Err("Template has no id".to_string()),
+            Some(ref id) if !valid_id(id) => Err(format!(
+                "Template id '{}' may only contain letters, digits, '_' and '-'",
+                id
+            )),
+            Some(ref id) if accepted.iter().any(|(seen, _, _)| seen == id) => {
+                Err(format!("Template id '{}' is listed twice", id))
+            }
+            Some(_) => validate_and_parse_template(&fields.to_string()),
+        };
+        match result {
+            Ok(template) => {
+                if let Some(object) = fields.as_object_mut() {
+                    object.insert("id".to_string(), Value::String(label.clone()));
+                }
+                accepted.push((label, template, fields));
+            }
+            Err(error) => {
+                warn!("⚠️ Remote template {} rejected: {}", label, error);
+                rejected.push(RejectedTemplate { id: label, error });
+            }
+        }
+    }
+    Ok((accepted, rejected))
+}
+
+fn install(templates: Vec<(String, Template, Value)>) -> Vec<String> {
+    let ids: Vec<String> = templates.iter().map(|(id, _, _)| id.clone()).collect();
+    if let Ok(mut registry) = REMOTE_TEMPLATES.write() {
+        *registry = templates
+            .into_iter()
+            .map(|(id, template, _)| (id, template))
+            .collect();
+    }
+    ids
+}
+
+/// Puts the cached templates into the registry; they are validated again in
+/// case the cache was edited or written by an older version
+fn load_cache(path: &Path) -> Result<Vec<String>, String> {
+    let body = std::fs::read_to_string(path)
+        .map_err(|e| format!("No cached remote templates at {}: {}", path.display(), e))?;
+    let (accepted, _) = parse_remote_templates(&body)?;
+    Ok(install(accepted))
+}
+
+fn write_cache(path: &Path, templates: &[(String, Template, Value)]) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+    let entries: Vec<&Value> = templates.iter().map(|(_, _, json)| json).collect();
+    let json = serde_json::to_string_pretty(&entries)
+        .map_err(|e| format!("Failed to serialize remote templates: {}", e))?;
+    std::fs::write(path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+async fn fetch(url: &str) -> Result<String, String> {
+    let response = client_factory::http_client(FETCH_TIMEOUT)?
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch templates from {}: {}", url, e))?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "Template registry {} answered {}",
+            url,
+            response.status()
+        ));
+    }
+    response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read templates from {}: {}", url, e))
+}
+
+/// Applies a registry response, or the cache when there is none
+fn apply_response(response: Result<String, String>, cache: Option<&Path>) -> RemoteTemplateRefresh {
+    match response.and_then(|body| parse_remote_templates(&body)) {
+        Ok((accepted, rejected)) => {
+            if let Some(cache) = cache {
+                if let Err(e) = write_cache(cache, &accepted) {
+                    warn!("⚠️ Remote templates not cached: {}", e);
+                }
+            }
+            let loaded = install(accepted);
+            info!(
+                "📋 Loaded {} remote templates, {} rejected",
+                loaded.len(),
+                rejected.len()
+            );
+            RemoteTemplateRefresh {
+                loaded,
+                rejected,
+                from_cache: false,
+                error: None,
+            }
+        }
+        Err(error) => {
+            warn!("⚠️ {}; using cached remote templates", error);
+            let loaded = match cache.map(load_cache) {
+                Some(Ok(loaded)) => loaded,
+                // Keeps whatever is in the registry already
+                _ => remote_template_ids(),
+            };
+            RemoteTemplateRefresh {
+                loaded,
+                rejected: Vec::new(),
+                from_cache: true,
+                error: Some(error),
+            }
+        }
+    }
+}
+
+/// Fetches the registry at `url`, falling back to the cached templates
+pub async fn refresh_remote_templates(url: &str) -> RemoteTemplateRefresh {
+    let response = fetch(url).await;
+    apply_response(response, cache_path().as_deref())
+}
+
+/// Removes the remote templates, when the remote source is turned off
+pub fn clear_remote_templates() {
+    install(Vec::new());
+}
+
+/// Loads the cached remote templates and refreshes them in the background
+pub fn init_remote_templates<R: Runtime>(app: &AppHandle<R>) {
+    let Some(url) = load_remote_template_config(app).url else {
+        return;
+    };
+    if let Some(Err(e)) = cache_path().as_deref().map(load_cache) {
+        info!("ℹ️ {}", e);
+    }
+    tauri::async_runtime::spawn(async move {
+        refresh_remote_templates(&url).await;
+    });
+}
+
+pub fn remote_template(id: &str) -> Option<Template> {
+    REMOTE_TEMPLATES.read().ok()?.get(id).cloned()
+}
+
+pub fn remote_template_ids() -> Vec<String> {
+    REMOTE_TEMPLATES
+        .read()
+        .map(|registry| registry.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::summary::templates::get_template;
+
+    const REGISTRY: &str = r#"[
+        {"id": "remote_test_retro", "name": "Retro", "description": "Team retrospective",
+         "sections": [{"title": "Went well", "instruction": "What went well", "format": "list"}]},
+        {"id": "remote_test_empty", "name": "Empty", "description": "No sections", "sections": []},
+        {"id": "remote_test_typo", "name": "Typo", "description": "D",
+         "sectins": [{"title": "Notes", "instruction": "Notes", "format": "list"}]},
+        {"id": "../escape", "name": "Escape", "description": "D",
+         "sections": [{"title": "Notes", "instruction": "Notes", "format": "list"}]},
+        {"name": "No id", "description": "D",
+         "sections": [{"title": "Notes", "instruction": "Notes", "format": "list"}]}
+    ]"#;
+
+    #[test]
+    fn test_remote_templates_are_validated_before_being_added() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = dir.path().join(CACHE_FILE);
+
+        let refresh = apply_response(Ok(REGISTRY.to_string()), Some(&cache));
+
+        assert_eq!(refresh.loaded, ["remote_test_retro"]);
+        let rejected: Vec<&str> = refresh.rejected.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(
+            rejected,
+            ["remote_test_empty", "remote_test_typo", "../escape", "#5"]
+        );
+        assert!(refresh.rejected[0].error.contains("at least one section"));
+        assert!(refresh.rejected[1]
+            .error
+            .contains("did you mean 'sections'?"));
+
+        assert_eq!(get_template("remote_test_retro").unwrap().name, "Retro");
+        assert!(get_template("remote_test_empty").is_err());
+        assert!(get_template("remote_test_typo").is_err());
+
+        // The registry can't be reached: the cached set stays in use
+        clear_remote_templates();
+        let refresh = apply_response(Err("connection refused".to_string()), Some(&cache));
+        assert!(refresh.from_cache);
+        assert_eq!(refresh.error.as_deref(), Some("connection refused"));
+        assert_eq!(refresh.loaded, ["remote_test_retro"]);
+        assert_eq!(get_template("remote_test_retro").unwrap().sections.len(), 1);
+
+        // So does an answer that isn't a template list
+        let refresh = apply_response(Ok("<html>login</html>".to_string()), Some(&cache));
+        assert!(refresh.from_cache);
+        assert_eq!(refresh.loaded, ["remote_test_retro"]);
+        clear_remote_templates();
+    }
+
+    #[test]
+    fn test_registry_object_and_duplicate_ids() {
+        let body = r#"{"templates": [
+            {"id": "a", "name": "A", "description": "D",
+             "sections": [{"title": "Notes", "instruction": "Notes", "format": "list"}]},
+            {"id": "a", "name": "A again", "description": "D",
+             "sections": [{"title": "Notes", "instruction": "Notes", "format": "list"}]}
+        ]}"#;
+        let (accepted, rejected) = parse_remote_templates(body).unwrap();
+        assert_eq!(accepted.len(), 1);
+        assert_eq!(accepted[0].2["id"], "a");
+        assert_eq!(rejected[0].error, "Template id 'a' is listed twice");
+        assert!(parse_remote_templates(r#"{"items": []}"#).is_err());
+        assert!(RemoteTemplateConfig {
+            url: Some("ftp://templates".to_string())
+        }
+        .validate()
+        .is_err());
+    }
+}
@@ -1,7 +1,14 @@
+use crate::summary::table::{escape_cell, is_separator_row, is_table_line, split_cells};
 use serde::{Deserialize, Serialize};
 
+/// Replaced in `Template::system_prompt_override` with the per-section
+/// instructions and the Markdown outline the model fills in
+pub const SECTION_INSTRUCTIONS_PLACEHOLDER: &str = "{section_instructions}";
+pub const TEMPLATE_MARKDOWN_PLACEHOLDER: &str = "{template_markdown}";
+
 /// Represents a single section in a meeting template
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct TemplateSection {
     /// Section title (e.g., "Summary", "Action Items")
     pub title: String,
@@ -19,10 +26,16 @@ pub struct TemplateSection {
     /// Alternative formatting hint
     #[serde(skip_serializing_if = "Option::is_none")]
     pub example_item_format: Option<String>,
+
+    /// Columns of a table section; an "Action Items" section without it
+    /// uses the canonical layout (see `Template::omit_reference_columns`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub table_columns: Option<Vec<TableColumn>>,
 }
 
 /// Represents a complete meeting template
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct Template {
     /// Template display name
     pub name: String,
@@ -32,9 +45,326 @@ pub struct Template {
 
     /// List of sections in the template
     pub sections: Vec<TemplateSection>,
+
+    /// Keep emoji/decorations the model puts on headings ("## 📋 Action Items")
+    /// in the final summary. They are always stripped while post-processing.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub preserve_heading_decorations: bool,
+
+    /// Use the 3-column canonical Action Items table (Owner | Task | Due),
+    /// without the "Reference Transcript Segment" and "Segment Time stamp"
+    /// columns. Sections with their own `table_columns` are not affected.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub omit_reference_columns: bool,
+
+    /// Replaces the built-in system prompt of the final summary call. Must
+    /// contain `{section_instructions}` and `{template_markdown}`; chunk and
+    /// combine prompts are not affected.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_prompt_override: Option<String>,
+
+    /// Whether post-processing adds the template's missing sections
+    #[serde(default)]
+    pub section_mode: SectionMode,
+}
+
+/// Content lines up to which a lenient summary without headings counts as
+/// empty
+pub const DEFAULT_MINIMAL_RESPONSE_LINES: usize = 3;
+
+/// How closely the final summary has to follow the template's sections
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SectionMode {
+    /// Every template section is present and in template order; missing
+    /// ones are added empty
+    Strict,
+    /// Trust the model's sections. They are only added when the response
+    /// has no section headings and at most `minimal_lines` lines of content
+    Lenient { minimal_lines: usize },
+}
+
+impl Default for SectionMode {
+    fn default() -> Self {
+        SectionMode::Lenient {
+            minimal_lines: DEFAULT_MINIMAL_RESPONSE_LINES,
+        }
+    }
+}
+
+/// A column of a table section
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TableColumn {
+    /// Canonical header text
+    pub name: String,
+
+    /// Rows must fill this column; empty or placeholder values are flagged
+    #[serde(default)]
+    pub required: bool,
+
+    /// Other headers models use for this column (e.g. "Deadline" for "Due")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub synonyms: Vec<String>,
+
+    /// Receives the unlabeled text when a list item is converted into a row;
+    /// the first column is used if no column is marked
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub primary: bool,
+}
+
+/// Traceability columns of the canonical Action Items table
+pub const REFERENCE_SEGMENT_COLUMN: &str = "Reference Transcript Segment";
+pub const SEGMENT_TIMESTAMP_COLUMN: &str = "Segment Time stamp";
+
+/// Lowercased header text without emphasis, for comparing column names
+fn normalize_header(cell: &str) -> String {
+    cell.trim()
+        .trim_matches('*')
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+impl TableColumn {
+    fn new(name: &str, required: bool, synonyms: &[&str]) -> Self {
+        Self {
+            name: name.to_string(),
+            required,
+            synonyms: synonyms.iter().map(|s| s.to_string()).collect(),
+            primary: false,
+        }
+    }
+
+    /// Name followed by synonyms
+    pub fn labels(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.name.as_str()).chain(self.synonyms.iter().map(String::as_str))
+    }
+
+    /// True if a header cell names this column
+    pub fn matches(&self, header: &str) -> bool {
+        let header = normalize_header(header);
+        !header.is_empty() && self.labels().any(|label| normalize_header(label) == header)
+    }
+
+    /// The "Reference Transcript Segment" / "Segment Time stamp" traceability columns
+    pub fn is_reference(&self) -> bool {
+        let name = normalize_header(&self.name);
+        [REFERENCE_SEGMENT_COLUMN, SEGMENT_TIMESTAMP_COLUMN]
+            .iter()
+            .any(|reference| normalize_header(reference) == name)
+    }
+}
+
+/// Column layout of a table section
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableSchema {
+    pub columns: Vec<TableColumn>,
+}
+
+impl TableSchema {
+    /// The canonical Action Items layout:
+    /// Owner | Task | Due | Reference Transcript Segment | Segment Time stamp
+    pub fn action_items() -> Self {
+        let mut task = TableColumn::new("Task", true, &["Action", "Action Item", "Task ID", "Description"]);
+        task.primary = true;
+        Self {
+            columns: vec![
+                TableColumn::new("Owner", true, &["Assignee", "Responsible", "Who"]),
+                task,
+                TableColumn::new("Due", true, &["Due Date", "Deadline", "When"]),
+                TableColumn::new(REFERENCE_SEGMENT_COLUMN, false, &["Reference", "Transcript Segment", "Quote"]),
+                TableColumn::new(SEGMENT_TIMESTAMP_COLUMN, false, &["Timestamp", "Time stamp", "Time"]),
+            ],
+        }
+    }
+
+    /// The canonical Action Items layout without the traceability columns:
+    /// Owner | Task | Due
+    pub fn compact_action_items() -> Self {
+        let mut schema = Self::action_items();
+        schema.columns.retain(|c| !c.is_reference());
+        schema
+    }
+
+    /// Column names for prompt text, e.g. "Owner | Task | Due"
+    pub fn names(&self) -> String {
+        self.columns
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+
+    /// Header row; the first column is bold, as in "| **Owner** | Task | Due |"
+    pub fn header(&self) -> String {
+        let cells: Vec<String> = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, c)| if i == 0 { format!("**{}**", c.name) } else { c.name.clone() })
+            .collect();
+        format!("| {} |", cells.join(" | "))
+    }
+
+    /// Layout taken from an existing header row, with no required columns or synonyms
+    pub fn from_header(cells: &[String]) -> Self {
+        Self {
+            columns: cells
+                .iter()
+                .map(|cell| TableColumn {
+                    name: cell.trim().trim_matches('*').trim().to_string(),
+                    required: false,
+                    synonyms: Vec::new(),
+                    primary: false,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn separator(&self) -> String {
+        format!("|{}", " --- |".repeat(self.columns.len()))
+    }
+
+    /// Builds a row in column order; missing or empty cells become "Not specified"
+    ///
+    /// Pipes inside a cell are escaped so long free-text cells can't split the row.
+    pub fn row<S: AsRef<str>>(&self, cells: &[S]) -> String {
+        let cells: Vec<String> = (0..self.columns.len())
+            .map(|i| {
+                cells
+                    .get(i)
+                    .map(|c| c.as_ref().trim())
+                    .filter(|c| !c.is_empty())
+                    .map_or_else(|| "Not specified".to_string(), escape_cell)
+            })
+            .collect();
+        format!("| {} |", cells.join(" | "))
+    }
+
+    /// Index of the column a header cell names
+    pub fn column_for(&self, header: &str) -> Option<usize> {
+        self.columns.iter().position(|c| c.matches(header))
+    }
+
+    /// Column that receives a list item's unlabeled text
+    pub fn primary_index(&self) -> usize {
+        self.columns.iter().position(|c| c.primary).unwrap_or(0)
+    }
+
+    /// True if a row of cells looks like this table's header
+    pub fn is_header(&self, cells: &[String]) -> bool {
+        let matched = cells.iter().filter(|c| self.column_for(c).is_some()).count();
+        matched > 0 && matched >= self.columns.len().min(2)
+    }
+}
+
+/// Checks an `item_format` hint: it is pasted into the prompt as is, so
+/// placeholders would reach the model unfilled, and a table layout needs a
+/// header and a separator row with matching columns
+fn validate_item_format(format: &str) -> Result<(), String> {
+    if let Some(start) = format.find('{') {
+        let end = format[start..]
+            .find('}')
+            .map_or(format.len(), |end| start + end + 1);
+        return Err(format!(
+            "placeholder '{}' is never filled in; describe the item instead",
+            &format[start..end]
+        ));
+    }
+
+    let rows: Vec<&str> = format.lines().filter(|line| is_table_line(line)).collect();
+    let header = match rows.as_slice() {
+        [] => return Ok(()),
+        [header, separator, ..] if !is_separator_row(header) && is_separator_row(separator) => {
+            split_cells(header)
+        }
+        _ => {
+            return Err(
+                "a table needs a header row followed by a '| --- |' separator row".to_string(),
+            )
+        }
+    };
+    if header
+        .iter()
+        .any(|cell| cell.trim_matches('*').trim().is_empty())
+    {
+        return Err("the table header has an empty column".to_string());
+    }
+    for row in &rows[1..] {
+        let cells = split_cells(row).len();
+        if cells != header.len() {
+            return Err(format!(
+                "the table header has {} columns but '{}' has {}",
+                header.len(),
+                row.trim(),
+                cells
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// True for an "Action Items" / "Action Item" heading, ignoring case,
+/// emphasis and decorations like "📋" or a trailing colon. Titles that only
+/// contain the word ("Transactions", "Actionable Risks") don't count.
+fn is_action_items_title(title: &str) -> bool {
+    let mut words = title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase);
+    matches!(
+        (
+            words.next().as_deref(),
+            words.next().as_deref(),
+            words.next()
+        ),
+        (Some("action"), Some("item" | "items"), None)
+    )
 }
 
 impl Template {
+    /// Table layout of a section, or None if it isn't a table section
+    pub fn table_schema(&self, section: &TemplateSection) -> Option<TableSchema> {
+        match &section.table_columns {
+            Some(columns) => Some(TableSchema {
+                columns: columns.clone(),
+            }),
+            None if is_action_items_title(&section.title) => {
+                Some(self.default_action_items_schema())
+            }
+            None => None,
+        }
+    }
+
+    /// The canonical Action Items layout this template asks for
+    fn default_action_items_schema(&self) -> TableSchema {
+        if self.omit_reference_columns {
+            TableSchema::compact_action_items()
+        } else {
+            TableSchema::action_items()
+        }
+    }
+
+    /// Sections rendered as tables, with their layouts
+    pub fn table_sections(&self) -> Vec<(&str, TableSchema)> {
+        self.sections
+            .iter()
+            .filter_map(|s| self.table_schema(s).map(|schema| (s.title.as_str(), schema)))
+            .collect()
+    }
+
+    /// Layout of the Action Items table, which the summary prompts describe;
+    /// the canonical one if the template has no Action Items section
+    pub fn action_items_schema(&self) -> TableSchema {
+        self.sections
+            .iter()
+            .find(|s| is_action_items_title(&s.title))
+            .and_then(|s| self.table_schema(s))
+            .unwrap_or_else(|| self.default_action_items_schema())
+    }
+
     /// Validates the template structure
     pub fn validate(&self) -> Result<(), String> {
         if self.name.is_empty() {
@@ -65,11 +395,58 @@ impl Template {
                     section.title, other
                 )),
             }
+
+            if let Some(format) = &section.item_format {
+                validate_item_format(format).map_err(|e| {
+                    format!("Section '{}' has an invalid item_format: {}", section.title, e)
+                })?;
+            }
+
+            if let Some(columns) = &section.table_columns {
+                if columns.is_empty() {
+                    return Err(format!("Section '{}' has an empty table_columns list", section.title));
+                }
+                for (j, column) in columns.iter().enumerate() {
+                    if column.name.trim().is_empty() {
+                        return Err(format!("Section '{}' has a table column with an empty name", section.title));
+                    }
+                    if columns[..j].iter().any(|other| other.labels().any(|label| column.matches(label))) {
+                        return Err(format!(
+                            "Section '{}' has more than one table column named '{}'",
+                            section.title, column.name
+                        ));
+                    }
+                }
+            }
+        }
+
+        if let Some(prompt) = &self.system_prompt_override {
+            let missing: Vec<&str> = [SECTION_INSTRUCTIONS_PLACEHOLDER, TEMPLATE_MARKDOWN_PLACEHOLDER]
+                .into_iter()
+                .filter(|placeholder| !prompt.contains(placeholder))
+                .collect();
+            if !missing.is_empty() {
+                return Err(format!(
+                    "System prompt override must contain {}",
+                    missing.join(" and ")
+                ));
+            }
         }
 
         Ok(())
     }
 
+    /// The system prompt override with its placeholders filled in, or None
+    /// to use the built-in prompt
+    pub fn render_system_prompt_override(&self) -> Option<String> {
+        let prompt = self.system_prompt_override.as_ref()?;
+        Some(
+            prompt
+                .replace(SECTION_INSTRUCTIONS_PLACEHOLDER, &self.to_section_instructions())
+                .replace(TEMPLATE_MARKDOWN_PLACEHOLDER, &self.to_markdown_structure()),
+        )
+    }
+
     /// Generates a clean markdown template structure
     pub fn to_markdown_structure(&self) -> String {
         let mut markdown = String::from("# <Add Title here>\n\n");
@@ -110,9 +487,19 @@ impl Template {
                 _ => {}
             }
 
+            let is_action_items = is_action_items_title(&section.title);
+            let schema = self.table_schema(section);
+
             // Add item format instructions if present
-            let item_format = section.item_format.as_ref()
-                .or(section.example_item_format.as_ref());
+            let mut item_format = section.item_format.clone()
+                .or_else(|| section.example_item_format.clone());
+
+            // A configured layout wins over a stock item format that would contradict it
+            if let Some(schema) = &schema {
+                if section.table_columns.is_some() || self.omit_reference_columns {
+                    item_format = Some(format!("{}\n{}", schema.header(), schema.separator()));
+                }
+            }
 
             if let Some(format) = item_format {
                 instructions.push_str(&format!(
@@ -121,23 +508,56 @@ impl Template {
                 ));
             }
 
+            let schema = match schema {
+                Some(schema) => schema,
+                None => continue,
+            };
+            let header = schema.header();
+            instructions.push_str(&format!(
+                "  - **CRITICAL TABLE FORMAT - MUST USE EXACT COLUMN NAMES:**\n\
+                 The table header MUST be EXACTLY: {header}\n\
+                 Keep the columns in this order; do not rename, add or drop columns.\n",
+                header = header,
+            ));
+            let required: Vec<&str> = schema
+                .columns
+                .iter()
+                .filter(|c| c.required)
+                .map(|c| c.name.as_str())
+                .collect();
+            if !required.is_empty() {
+                instructions.push_str(&format!(
+                    "  - Required columns (never leave empty): {}.\n",
+                    required.join(", ")
+                ));
+            }
+
             // Add validation examples for Action Items sections
-            if section.title.to_lowercase().contains("action") {
-                instructions.push_str(
-                    "  - **CRITICAL TABLE FORMAT - MUST USE EXACT COLUMN NAMES:**\n\
-                     The table header MUST be EXACTLY: | **Owner** | Task | Due | Reference Transcript Segment | Segment Time stamp |\n\
-                     DO NOT use: 'Action', 'Task ID (if noted)', 'Task ID', or any other column names.\n\
-                     The FIRST column MUST be 'Owner' (or '**Owner**'), the SECOND column MUST be 'Task', the THIRD column MUST be 'Due'.\n\
+            if is_action_items {
+                let example: Vec<&str> = schema
+                    .columns
+                    .iter()
+                    .map(|c| match normalize_header(&c.name).as_str() {
+                        "owner" => "Two developers",
+                        "task" => "Fix Stripe webhook (PROJ-404)",
+                        "due" => "Before noon today",
+                        _ => "Not specified",
+                    })
+                    .collect();
+                instructions.push_str(&format!(
+                    "  - DO NOT use: 'Action', 'Task ID (if noted)', 'Task ID', or any other column names.\n\
                      - **VALIDATION EXAMPLES:**\n\
-                     * CORRECT HEADER: | **Owner** | Task | Due | Reference Transcript Segment | Segment Time stamp |\n\
-                     * CORRECT ROW: | Two developers | Fix Stripe webhook (PROJ-404) | Before noon today | Not specified | Not specified |\n\
+                     * CORRECT HEADER: {header}\n\
+                     * CORRECT ROW: {example_row}\n\
                      * WRONG HEADER: | Action | Task ID (if noted) | Due | ... | (Missing Owner column!)\n\
                      * WRONG HEADER: | Task | Owner | Due | ... | (Wrong column order!)\n\
                      * BAD ROW: | No blocker | Stripe debugging continues | None | ... |\n\
                      * BAD ROW: | None | Task description | TBD | ... |\n\
                      * NEVER use: 'No blocker', 'None', 'TBD', 'N/A', 'None noted in this section', or transcript chunk references as values.\n\
-                     * If information is missing, use 'Not specified' (not 'None' or 'TBD').\n"
-                );
+                     * If information is missing, use 'Not specified' (not 'None' or 'TBD').\n",
+                    header = header,
+                    example_row = schema.row(&example),
+                ));
             }
         }
 
@@ -161,11 +581,184 @@ mod tests {
                     format: "paragraph".to_string(),
                     item_format: None,
                     example_item_format: None,
+                    table_columns: None,
+                },
+            ],
+            preserve_heading_decorations: false,
+            omit_reference_columns: false,
+            system_prompt_override: None,
+            section_mode: SectionMode::default(),
+        };
+
+        assert!(template.validate().is_ok());
+    }
+
+    #[test]
+    fn test_section_instructions_follow_action_items_columns() {
+        let mut template = Template {
+            name: "Sync".to_string(),
+            description: "Sync".to_string(),
+            sections: vec![TemplateSection {
+                title: "Action Items".to_string(),
+                instruction: "List the action items".to_string(),
+                format: "list".to_string(),
+                item_format: Some(format!(
+                    "{}\n{}",
+                    TableSchema::action_items().header(),
+                    TableSchema::action_items().separator()
+                )),
+                example_item_format: None,
+                table_columns: None,
+            }],
+            preserve_heading_decorations: false,
+            omit_reference_columns: false,
+            system_prompt_override: None,
+            section_mode: SectionMode::default(),
+        };
+        let full = template.to_section_instructions();
+        assert!(full.contains("MUST be EXACTLY: | **Owner** | Task | Due | Reference Transcript Segment | Segment Time stamp |"));
+
+        template.omit_reference_columns = true;
+        let compact = template.to_section_instructions();
+        assert!(compact.contains("MUST be EXACTLY: | **Owner** | Task | Due |\n"), "{}", compact);
+        assert!(compact.contains("CORRECT ROW: | Two developers | Fix Stripe webhook (PROJ-404) | Before noon today |\n"));
+        assert!(!compact.contains("Reference Transcript Segment"));
+    }
+
+    #[test]
+    fn test_standard_template_declares_default_action_items_columns() {
+        let template: Template =
+            serde_json::from_str(super::super::defaults::STANDARD_MEETING).unwrap();
+        let action_items = template
+            .sections
+            .iter()
+            .find(|s| s.title == "Action Items")
+            .unwrap();
+
+        assert_eq!(template.table_schema(action_items), Some(TableSchema::action_items()));
+        assert_eq!(template.table_sections().len(), 1);
+        assert!(template.validate().is_ok());
+    }
+
+    #[test]
+    fn test_only_action_items_titles_get_the_default_table() {
+        for title in ["Action Items", "action item", "📋 **Action Items:**"] {
+            assert!(is_action_items_title(title), "{}", title);
+        }
+        for title in [
+            "Transactions",
+            "Reactions",
+            "Actionable risks",
+            "Action Items Review",
+            "Actions",
+        ] {
+            assert!(!is_action_items_title(title), "{}", title);
+        }
+
+        // The compact layout only replaces the default, not configured columns
+        let mut template = Template {
+            name: "Finance".to_string(),
+            description: "Finance review".to_string(),
+            sections: vec![
+                TemplateSection {
+                    title: "Transactions".to_string(),
+                    instruction: "List the transactions".to_string(),
+                    format: "list".to_string(),
+                    item_format: None,
+                    example_item_format: None,
+                    table_columns: None,
+                },
+                TemplateSection {
+                    title: "Audit Log".to_string(),
+                    instruction: "List the audited changes".to_string(),
+                    format: "list".to_string(),
+                    item_format: None,
+                    example_item_format: None,
+                    table_columns: Some(vec![
+                        TableColumn::new("Change", true, &[]),
+                        TableColumn::new(SEGMENT_TIMESTAMP_COLUMN, false, &[]),
+                    ]),
                 },
             ],
+            preserve_heading_decorations: false,
+            omit_reference_columns: true,
+            system_prompt_override: None,
+            section_mode: SectionMode::default(),
         };
+        assert_eq!(template.table_schema(&template.sections[0]), None);
+        assert_eq!(template.table_schema(&template.sections[1]).unwrap().columns.len(), 2);
+        assert_eq!(template.action_items_schema(), TableSchema::compact_action_items());
+
+        template.omit_reference_columns = false;
+        assert_eq!(template.action_items_schema(), TableSchema::action_items());
+    }
+
+    #[test]
+    fn test_table_columns_validation_and_matching() {
+        let schema = TableSchema::action_items();
+        assert_eq!(schema.column_for("**Deadline**"), Some(2));
+        assert_eq!(schema.column_for(" task id "), Some(1));
+        assert_eq!(schema.column_for("Priority"), None);
+        assert_eq!(schema.row(&["Alex", "", "Friday"]), "| Alex | Not specified | Friday | Not specified | Not specified |");
 
+        let mut template = Template {
+            name: "Risks".to_string(),
+            description: "Risk review".to_string(),
+            sections: vec![TemplateSection {
+                title: "Risks".to_string(),
+                instruction: "List the risks".to_string(),
+                format: "list".to_string(),
+                item_format: None,
+                example_item_format: None,
+                table_columns: Some(vec![
+                    TableColumn::new("Risk", true, &[]),
+                    TableColumn::new("Impact", false, &["Severity"]),
+                ]),
+            }],
+            preserve_heading_decorations: false,
+            omit_reference_columns: false,
+            system_prompt_override: None,
+            section_mode: SectionMode::default(),
+        };
         assert!(template.validate().is_ok());
+        assert!(template.to_section_instructions().contains("MUST be EXACTLY: | **Risk** | Impact |"));
+
+        template.sections[0].table_columns = Some(vec![
+            TableColumn::new("Impact", false, &[]),
+            TableColumn::new("Severity", false, &["impact"]),
+        ]);
+        assert!(template.validate().is_err());
+
+        template.sections[0].table_columns = Some(vec![]);
+        assert!(template.validate().is_err());
+    }
+
+    #[test]
+    fn test_system_prompt_override() {
+        let mut template: Template =
+            serde_json::from_str(super::super::defaults::STANDARD_MEETING).unwrap();
+        assert_eq!(template.render_system_prompt_override(), None);
+
+        template.system_prompt_override = Some(
+            "Summarize for the legal team.\n{section_instructions}\n<template>\n{template_markdown}\n</template>"
+                .to_string(),
+        );
+        assert!(template.validate().is_ok());
+        let prompt = template.render_system_prompt_override().unwrap();
+        assert!(prompt.starts_with("Summarize for the legal team.\n- **For the main title"));
+        assert!(prompt.contains(&format!("<template>\n{}\n</template>", template.to_markdown_structure())));
+        assert!(!prompt.contains(SECTION_INSTRUCTIONS_PLACEHOLDER));
+
+        template.system_prompt_override = Some("Summarize for the legal team.\n{section_instructions}".to_string());
+        assert_eq!(
+            template.validate(),
+            Err("System prompt override must contain {template_markdown}".to_string())
+        );
+        template.system_prompt_override = Some("Just summarize it.".to_string());
+        assert_eq!(
+            template.validate(),
+            Err("System prompt override must contain {section_instructions} and {template_markdown}".to_string())
+        );
     }
 
     #[test]
@@ -174,6 +767,10 @@ mod tests {
             name: "".to_string(),
             description: "A test template".to_string(),
             sections: vec![],
+            preserve_heading_decorations: false,
+            omit_reference_columns: false,
+            system_prompt_override: None,
+            section_mode: SectionMode::default(),
         };
 
         assert!(template.validate().is_err());
@@ -191,10 +788,58 @@ mod tests {
                     format: "invalid".to_string(),
                     item_format: None,
                     example_item_format: None,
+                    table_columns: None,
                 },
             ],
+            preserve_heading_decorations: false,
+            omit_reference_columns: false,
+            system_prompt_override: None,
+            section_mode: SectionMode::default(),
         };
 
         assert!(template.validate().is_err());
     }
+
+    #[test]
+    fn test_validate_item_format() {
+        let valid = [
+            "Short sentence naming the owner",
+            "| **Owner** | **Task** | Due |\n| --- | --- | --- |",
+            "| Risk | Impact |\n|:---|---:|\n| Outage | High |",
+        ];
+        for format in valid {
+            assert_eq!(validate_item_format(format), Ok(()), "{}", format);
+        }
+
+        let invalid = [
+            ("| Owner | {task} |\n| --- | --- |", "placeholder '{task}'"),
+            ("| Owner | Task |", "header row followed by"),
+            ("| --- | --- |\n| Owner | Task |", "header row followed by"),
+            ("| Owner | Task |\n| --- |", "has 1"),
+            ("| Owner | ** |\n| --- | --- |", "empty column"),
+        ];
+        for (format, expected) in invalid {
+            let error = validate_item_format(format).unwrap_err();
+            assert!(error.contains(expected), "{}: {}", format, error);
+        }
+
+        let template = Template {
+            name: "Test".to_string(),
+            description: "Test".to_string(),
+            sections: vec![TemplateSection {
+                title: "Risks".to_string(),
+                instruction: "List risks".to_string(),
+                format: "list".to_string(),
+                item_format: Some("| Risk | {impact} |".to_string()),
+                example_item_format: None,
+                table_columns: None,
+            }],
+            preserve_heading_decorations: false,
+            omit_reference_columns: false,
+            system_prompt_override: None,
+            section_mode: SectionMode::default(),
+        };
+        let error = template.validate().unwrap_err();
+        assert!(error.starts_with("Section 'Risks' has an invalid item_format"), "{}", error);
+    }
 }
@@ -0,0 +1,212 @@
+//! Ticket IDs mentioned in the meeting but missing from its summary
+//!
+//! Models tend to drop ticket references when condensing a meeting. The
+//! transcript is scanned for ticket-like identifiers and any the summary
+//! doesn't mention are listed in a note at its end, or only reported.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// First line of the note listing uncovered tickets
+pub const COVERAGE_NOTE_TITLE: &str = "**Referenced tickets not covered above:**";
+
+/// Recording timestamps, clock times and dates; IDs are never taken from these
+static BOILERPLATE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\[\d{1,2}:\d{2}(?::\d{2})?\]|\b\d{4}-\d{2}-\d{2}(?:[T ]\d{2}:\d{2}(?::\d{2})?)?\b")
+        .unwrap()
+});
+
+/// Which identifiers count as tickets and what happens to uncovered ones
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TicketCoverageConfig {
+    /// Regexes matching a ticket ID
+    pub patterns: Vec<String>,
+    /// Matches that look like tickets but aren't (compared case-insensitively)
+    pub ignore: Vec<String>,
+    /// Append the uncovered tickets to the summary; otherwise they are only
+    /// stored next to it
+    pub append_note: bool,
+}
+
+impl Default for TicketCoverageConfig {
+    fn default() -> Self {
+        Self {
+            patterns: vec![r"[A-Z]{2,10}-\d+".to_string()],
+            ignore: [
+                "UTF-8", "UTF-16", "ISO-8601", "SHA-256", "AES-256", "COVID-19",
+            ]
+            .iter()
+            .map(|id| id.to_string())
+            .collect(),
+            append_note: true,
+        }
+    }
+}
+
+impl TicketCoverageConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        TicketMatcher::new(self).map(|_| ())
+    }
+}
+
+/// Compiled `TicketCoverageConfig`
+pub struct TicketMatcher {
+    patterns: Vec<Regex>,
+    ignore: HashSet<String>,
+}
+
+impl TicketMatcher {
+    pub fn new(config: &TicketCoverageConfig) -> Result<Self, String> {
+        if config.patterns.is_empty() {
+            return Err("At least one ticket ID pattern is required".to_string());
+        }
+        let patterns = config
+            .patterns
+            .iter()
+            .map(|pattern| {
+                Regex::new(pattern)
+                    .map_err(|e| format!("Invalid ticket ID pattern '{}': {}", pattern, e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            patterns,
+            ignore: config.ignore.iter().map(|id| id.to_uppercase()).collect(),
+        })
+    }
+
+    /// Ticket IDs in `text` in order of first mention, deduplicated
+    /// case-insensitively
+    ///
+    /// Matches glued to more letters, digits or hyphens (UUID pieces, longer
+    /// codes) are skipped, as are timestamps and dates.
+    pub fn find(&self, text: &str) -> Vec<String> {
+        let text = BOILERPLATE_RE.replace_all(text, " ");
+        let mut found: Vec<(usize, String)> = Vec::new();
+        let mut seen = HashSet::new();
+        for pattern in &self.patterns {
+            for m in pattern.find_iter(&text) {
+                let glued = |c: char| c.is_alphanumeric() || c == '-';
+                let before = text[..m.start()].chars().next_back();
+                let after = text[m.end()..].chars().next();
+                if before.is_some_and(glued) || after.is_some_and(glued) {
+                    continue;
+                }
+                let key = m.as_str().to_uppercase();
+                if !self.ignore.contains(&key) && seen.insert(key) {
+                    found.push((m.start(), m.as_str().to_string()));
+                }
+            }
+        }
+        found.sort_by_key(|(start, _)| *start);
+        found.into_iter().map(|(_, id)| id).collect()
+    }
+}
+
+/// Ticket IDs of the transcript and which of them the summary mentions
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TicketCoverage {
+    pub mentioned: Vec<String>,
+    pub covered: Vec<String>,
+    pub missing: Vec<String>,
+}
+
+/// Whether `id` appears in `text` as a whole token, ignoring case
+fn mentions(text: &str, id: &str) -> bool {
+    let glued = |c: char| c.is_alphanumeric() || c == '-';
+    text.match_indices(id).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + id.len()..].chars().next();
+        !before.is_some_and(glued) && !after.is_some_and(glued)
+    })
+}
+
+/// Compares the transcript's ticket IDs with the summary
+///
+/// IDs count as covered however the summary capitalizes them. A coverage
+/// note already in `summary` is ignored, so stored summaries can be checked
+/// again.
+pub fn ticket_coverage(transcript: &str, summary: &str, matcher: &TicketMatcher) -> TicketCoverage {
+    let summary = strip_coverage_note(summary).to_uppercase();
+    let mentioned = matcher.find(transcript);
+    let (covered, missing) = mentioned
+        .iter()
+        .cloned()
+        .partition(|id| mentions(&summary, &id.to_uppercase()));
+    TicketCoverage {
+        mentioned,
+        covered,
+        missing,
+    }
+}
+
+/// The note listing `missing`, to go at the end of the summary
+pub fn coverage_note(missing: &[String]) -> String {
+    let items: Vec<String> = missing.iter().map(|id| format!("- {}", id)).collect();
+    format!("{}\n\n{}", COVERAGE_NOTE_TITLE, items.join("\n"))
+}
+
+/// The summary without a trailing coverage note
+pub fn strip_coverage_note(summary: &str) -> &str {
+    match summary.rfind(COVERAGE_NOTE_TITLE) {
+        Some(start) => summary[..start].trim_end(),
+        None => summary,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matcher() -> TicketMatcher {
+        TicketMatcher::new(&TicketCoverageConfig::default()).unwrap()
+    }
+
+    #[test]
+    fn test_find_ticket_ids_skips_lookalikes() {
+        let transcript = "[00:01:05] Alex: PROJ-404 blocks OPS-12, and proj-404 again.\n\
+             [00:01:30] Sam: The request id was 7F3A9C2E-AB-1234-9F0E, logged 2024-05-01.\n\
+             [00:02:00] Priya: Files must be UTF-8; see DATA-7 and BILLING-1200.\n\
+             [00:02:10] Ana: XPROJ-404X is not a ticket, nor is A-1.";
+
+        assert_eq!(
+            matcher().find(transcript),
+            vec!["PROJ-404", "OPS-12", "DATA-7", "BILLING-1200"]
+        );
+
+        // Extra formats from the config, e.g. GitHub issues
+        let config = TicketCoverageConfig {
+            patterns: vec![r"[A-Z]{2,10}-\d+".to_string(), r"#\d{2,6}\b".to_string()],
+            ..TicketCoverageConfig::default()
+        };
+        let matcher = TicketMatcher::new(&config).unwrap();
+        assert_eq!(
+            matcher.find("Fixed in #1234 and PROJ-9; meet at [10:30]"),
+            vec!["#1234", "PROJ-9"]
+        );
+
+        let invalid = TicketCoverageConfig {
+            patterns: vec!["[A-Z".to_string()],
+            ..TicketCoverageConfig::default()
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_ticket_coverage_and_note() {
+        let transcript = "[00:00:05] Alex: PROJ-404 blocks OPS-12.\n[00:00:09] Sam: DATA-7 too.";
+        let summary = "## Summary\n\nThe team discussed proj-404.";
+
+        let coverage = ticket_coverage(transcript, summary, &matcher());
+        assert_eq!(coverage.covered, vec!["PROJ-404"]);
+        assert_eq!(coverage.missing, vec!["OPS-12", "DATA-7"]);
+
+        let noted = format!("{}\n\n{}", summary, coverage_note(&coverage.missing));
+        assert!(noted.ends_with("**Referenced tickets not covered above:**\n\n- OPS-12\n- DATA-7"));
+        // The note itself doesn't count as coverage when checked again
+        assert_eq!(ticket_coverage(transcript, &noted, &matcher()), coverage);
+        assert_eq!(strip_coverage_note(&noted), summary);
+    }
+}
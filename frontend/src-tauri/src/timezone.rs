@@ -0,0 +1,259 @@
+//! The user's time zone, for showing and filtering meetings by day
+//!
+//! Meeting timestamps are stored in UTC as RFC3339. The calendar day a
+//! meeting falls on depends on where the user is, so the meeting commands
+//! add a `local_date` in the saved time zone (an IANA name such as
+//! "Europe/Berlin", or the system's when none is saved) and date filters
+//! take whole days in that zone.
+
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+
+const CONFIG_STORE: &str = "timezone.json";
+const CONFIG_KEY: &str = "config";
+
+/// Format of `local_date` and of date filter inputs
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Longest stretch of local time a DST change skips
+const MAX_GAP_MINUTES: i64 = 3 * 60;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TimezoneConfig {
+    /// IANA time zone name; None follows the system time zone
+    pub timezone: Option<String>,
+}
+
+impl TimezoneConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        self.resolve().map(|_| ())
+    }
+
+    pub fn resolve(&self) -> Result<UserTimezone, String> {
+        match self.timezone.as_deref().map(str::trim) {
+            None | Some("") => Ok(UserTimezone::System),
+            Some(name) => name
+                .parse::<Tz>()
+                .map(UserTimezone::Named)
+                .map_err(|_| format!("Unknown time zone '{}'", name)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UserTimezone {
+    System,
+    Named(Tz),
+}
+
+impl UserTimezone {
+    /// Calendar day of `at` in this zone
+    pub fn local_date(&self, at: DateTime<Utc>) -> NaiveDate {
+        match self {
+            UserTimezone::System => at.with_timezone(&Local).date_naive(),
+            UserTimezone::Named(tz) => at.with_timezone(tz).date_naive(),
+        }
+    }
+
+    /// `local_date` as sent to the frontend
+    pub fn format_local_date(&self, at: DateTime<Utc>) -> String {
+        self.local_date(at).format(DATE_FORMAT).to_string()
+    }
+
+    /// First instant of `date` in this zone
+    pub fn start_of_day(&self, date: NaiveDate) -> DateTime<Utc> {
+        match self {
+            UserTimezone::System => start_of_day_in(&Local, date),
+            UserTimezone::Named(tz) => start_of_day_in(tz, date),
+        }
+    }
+
+    /// UTC bounds, start inclusive and end exclusive, of the days `from`
+    /// through `to`; an open side stays None
+    pub fn day_range(
+        &self,
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+    ) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+        (
+            from.map(|date| self.start_of_day(date)),
+            to.and_then(|date| date.succ_opt())
+                .map(|date| self.start_of_day(date)),
+        )
+    }
+}
+
+/// Days on which a zone switches to summer time at midnight (e.g.
+/// America/Santiago) have no 00:00, so they start at the first local time
+/// that exists; a day that repeats its first hour starts at the earlier one
+fn start_of_day_in<Z: TimeZone>(tz: &Z, date: NaiveDate) -> DateTime<Utc> {
+    let midnight = date.and_time(NaiveTime::MIN);
+    (0..=MAX_GAP_MINUTES)
+        .find_map(|minutes| {
+            tz.from_local_datetime(&(midnight + Duration::minutes(minutes)))
+                .earliest()
+        })
+        .map(|start| start.with_timezone(&Utc))
+        .unwrap_or_else(|| Utc.from_utc_datetime(&midnight))
+}
+
+/// A date filter input, "YYYY-MM-DD"
+pub fn parse_date(input: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(input.trim(), DATE_FORMAT)
+        .map_err(|_| format!("Invalid date '{}', expected YYYY-MM-DD", input))
+}
+
+pub fn load_timezone_config<R: Runtime>(app: &AppHandle<R>) -> TimezoneConfig {
+    let stored = app
+        .store(CONFIG_STORE)
+        .ok()
+        .and_then(|store| store.get(CONFIG_KEY));
+    match stored.map(serde_json::from_value::<TimezoneConfig>) {
+        Some(Ok(config)) => config,
+        Some(Err(e)) => {
+            warn!("⚠️ Invalid timezone config in store, using defaults: {}", e);
+            TimezoneConfig::default()
+        }
+        None => TimezoneConfig::default(),
+    }
+}
+
+pub fn save_timezone_config<R: Runtime>(
+    app: &AppHandle<R>,
+    config: &TimezoneConfig,
+) -> Result<(), String> {
+    let store = app
+        .store(CONFIG_STORE)
+        .map_err(|e| format!("Failed to open timezone store: {}", e))?;
+    let value = serde_json::to_value(config)
+        .map_err(|e| format!("Failed to serialize timezone config: {}", e))?;
+    store.set(CONFIG_KEY, value);
+    store
+        .save()
+        .map_err(|e| format!("Failed to save timezone config: {}", e))
+}
+
+/// The saved time zone, or the system's when none is saved or the saved
+/// name is no longer known
+pub fn user_timezone<R: Runtime>(app: &AppHandle<R>) -> UserTimezone {
+    load_timezone_config(app).resolve().unwrap_or_else(|e| {
+        warn!("⚠️ {}, using the system time zone", e);
+        UserTimezone::System
+    })
+}
+
+#[tauri::command]
+pub async fn api_get_timezone_config<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<TimezoneConfig, String> {
+    Ok(load_timezone_config(&app))
+}
+
+/// Saves the time zone meetings are dated and filtered in
+#[tauri::command]
+pub async fn api_set_timezone_config<R: Runtime>(
+    app: AppHandle<R>,
+    config: TimezoneConfig,
+) -> Result<TimezoneConfig, String> {
+    log::info!("api_set_timezone_config called: {:?}", config);
+    config.validate()?;
+    save_timezone_config(&app, &config)?;
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn named(name: &str) -> UserTimezone {
+        TimezoneConfig {
+            timezone: Some(name.to_string()),
+        }
+        .resolve()
+        .unwrap()
+    }
+
+    fn utc(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn date(input: &str) -> NaiveDate {
+        parse_date(input).unwrap()
+    }
+
+    #[test]
+    fn test_config_resolves_iana_names_and_defaults_to_system() {
+        assert_eq!(
+            TimezoneConfig::default().resolve(),
+            Ok(UserTimezone::System)
+        );
+        assert_eq!(
+            named(" Europe/Berlin "),
+            UserTimezone::Named(chrono_tz::Europe::Berlin)
+        );
+        assert_eq!(
+            TimezoneConfig {
+                timezone: Some("Mars/Olympus_Mons".to_string())
+            }
+            .validate(),
+            Err("Unknown time zone 'Mars/Olympus_Mons'".to_string())
+        );
+        assert!(parse_date("2024-13-01").is_err());
+    }
+
+    #[test]
+    fn test_local_date_and_day_range_across_dst_changes() {
+        let new_york = named("America/New_York");
+
+        // 23:30 on the evening before clocks go forward is still that day
+        assert_eq!(
+            new_york.format_local_date(utc("2024-03-10T04:30:00Z")),
+            "2024-03-09"
+        );
+        assert_eq!(
+            new_york.format_local_date(utc("2024-03-10T05:00:00Z")),
+            "2024-03-10"
+        );
+
+        // The spring-forward day is 23 hours long, the fall-back day 25
+        let (start, end) = new_york.day_range(Some(date("2024-03-10")), Some(date("2024-03-10")));
+        assert_eq!(start, Some(utc("2024-03-10T05:00:00Z")));
+        assert_eq!(end, Some(utc("2024-03-11T04:00:00Z")));
+        let (start, end) = new_york.day_range(Some(date("2024-11-03")), Some(date("2024-11-03")));
+        assert_eq!(start, Some(utc("2024-11-03T04:00:00Z")));
+        assert_eq!(end, Some(utc("2024-11-04T05:00:00Z")));
+
+        // In the repeated hour of the fall-back day both instants share a date
+        assert_eq!(
+            new_york.format_local_date(utc("2024-11-03T05:30:00Z")),
+            "2024-11-03"
+        );
+        assert_eq!(
+            new_york.format_local_date(utc("2024-11-03T06:30:00Z")),
+            "2024-11-03"
+        );
+
+        assert_eq!(new_york.day_range(None, None), (None, None));
+    }
+
+    #[test]
+    fn test_day_without_midnight_starts_at_first_valid_time() {
+        // Chile moves from 00:00 -04 straight to 01:00 -03
+        let santiago = named("America/Santiago");
+        assert_eq!(
+            santiago.start_of_day(date("2024-09-08")),
+            utc("2024-09-08T04:00:00Z")
+        );
+        assert_eq!(
+            santiago.start_of_day(date("2024-09-07")),
+            utc("2024-09-07T04:00:00Z")
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use super::config::{
+    encrypt_webhook_secret, load_webhooks_config, save_webhooks_config, validate_webhook_secret,
+    validate_webhook_url, Webhook, WebhookSettings,
+};
+use super::delivery::redeliver;
+use crate::database::models::WebhookDelivery;
+use crate::database::repositories::webhook_delivery::WebhookDeliveriesRepository;
+use crate::state::AppState;
+use log::info as log_info;
+use serde::Deserialize;
+use tauri::{AppHandle, Runtime};
+
+/// Deliveries listed when no limit is given, and the most ever listed
+const DEFAULT_DELIVERY_LIMIT: i64 = 50;
+const MAX_DELIVERY_LIMIT: i64 = 500;
+
+/// A webhook as edited in the settings; `secret` may be left out to keep
+/// the saved one, and is required for a new webhook
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebhookInput {
+    pub id: Option<String>,
+    pub url: String,
+    pub enabled: bool,
+    #[serde(default)]
+    pub allow_insecure: bool,
+    pub secret: Option<String>,
+}
+
+#[tauri::command]
+pub async fn api_get_webhooks<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<Vec<WebhookSettings>, String> {
+    let config = load_webhooks_config(&app);
+    Ok(config.webhooks.iter().map(WebhookSettings::from).collect())
+}
+
+/// Adds a webhook, or updates the one with the given id
+#[tauri::command]
+pub async fn api_save_webhook<R: Runtime>(
+    app: AppHandle<R>,
+    webhook: WebhookInput,
+) -> Result<WebhookSettings, String> {
+    log_info!(
+        "api_save_webhook called: id={:?}, allow_insecure={}",
+        webhook.id,
+        webhook.allow_insecure
+    );
+    let url = webhook.url.trim().to_string();
+    validate_webhook_url(&url, webhook.allow_insecure)?;
+    let secret = match webhook.secret.filter(|secret| !secret.trim().is_empty()) {
+        Some(secret) => {
+            validate_webhook_secret(&secret)?;
+            Some(encrypt_webhook_secret(&app, &secret)?)
+        }
+        None => None,
+    };
+
+    let mut config = load_webhooks_config(&app);
+    let saved = match webhook.id {
+        Some(id) => {
+            let existing = config
+                .webhooks
+                .iter_mut()
+                .find(|existing| existing.id == id)
+                .ok_or_else(|| format!("Webhook {} not found", id))?;
+            existing.url = url;
+            existing.enabled = webhook.enabled;
+            existing.allow_insecure = webhook.allow_insecure;
+            if let Some(secret) = secret {
+                existing.secret = secret;
+            }
+            existing.clone()
+        }
+        None => {
+            let secret = secret.ok_or("A signing secret is required for a new webhook")?;
+            let created = Webhook {
+                id: uuid::Uuid::new_v4().to_string(),
+                url,
+                enabled: webhook.enabled,
+                allow_insecure: webhook.allow_insecure,
+                secret,
+            };
+            config.webhooks.push(created.clone());
+            created
+        }
+    };
+    save_webhooks_config(&app, &config)?;
+    Ok(WebhookSettings::from(&saved))
+}
+
+/// Removes a webhook; its delivery log is kept
+#[tauri::command]
+pub async fn api_delete_webhook<R: Runtime>(
+    app: AppHandle<R>,
+    webhook_id: String,
+) -> Result<(), String> {
+    log_info!("api_delete_webhook called for {}", webhook_id);
+    let mut config = load_webhooks_config(&app);
+    let before = config.webhooks.len();
+    config.webhooks.retain(|webhook| webhook.id != webhook_id);
+    if config.webhooks.len() == before {
+        return Err(format!("Webhook {} not found", webhook_id));
+    }
+    save_webhooks_config(&app, &config)
+}
+
+/// The webhook's latest deliveries with their response status and latency,
+/// newest first
+#[tauri::command]
+pub async fn api_list_webhook_deliveries(
+    state: tauri::State<'_, AppState>,
+    webhook_id: String,
+    limit: Option<i64>,
+) -> Result<Vec<WebhookDelivery>, String> {
+    let limit = limit
+        .unwrap_or(DEFAULT_DELIVERY_LIMIT)
+        .clamp(1, MAX_DELIVERY_LIMIT);
+    WebhookDeliveriesRepository::list(state.db_manager.pool(), &webhook_id, limit)
+        .await
+        .map_err(|e| format!("Failed to list webhook deliveries: {}", e))
+}
+
+/// Sends a logged delivery again under a new id, and returns the new one
+#[tauri::command]
+pub async fn api_redeliver_webhook<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    delivery_id: String,
+) -> Result<WebhookDelivery, String> {
+    log_info!("api_redeliver_webhook called for {}", delivery_id);
+    redeliver(&app, state.db_manager.pool(), &delivery_id).await
+}
@@ -0,0 +1,155 @@
+use crate::export::config::local_secret_key;
+use crate::settings_bundle::crypto::{decrypt_local, encrypt_local, EncryptedSecrets};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+
+const CONFIG_STORE: &str = "webhooks.json";
+const CONFIG_KEY: &str = "config";
+
+/// Shortest signing secret accepted
+const MIN_SECRET_LEN: usize = 16;
+
+/// A webhook as saved in the store
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Webhook {
+    pub id: String,
+    pub url: String,
+    pub enabled: bool,
+    /// Allows a plain http URL, e.g. a receiver on the local network
+    #[serde(default)]
+    pub allow_insecure: bool,
+    /// Signing secret, encrypted with the local key
+    pub secret: EncryptedSecrets,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebhooksConfig {
+    pub webhooks: Vec<Webhook>,
+}
+
+impl WebhooksConfig {
+    pub fn webhook(&self, id: &str) -> Option<&Webhook> {
+        self.webhooks.iter().find(|webhook| webhook.id == id)
+    }
+}
+
+/// A webhook as shown in the settings, without its secret
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookSettings {
+    pub id: String,
+    pub url: String,
+    pub enabled: bool,
+    pub allow_insecure: bool,
+}
+
+impl From<&Webhook> for WebhookSettings {
+    fn from(webhook: &Webhook) -> Self {
+        Self {
+            id: webhook.id.clone(),
+            url: webhook.url.clone(),
+            enabled: webhook.enabled,
+            allow_insecure: webhook.allow_insecure,
+        }
+    }
+}
+
+/// Deliveries go over https only, unless `allow_insecure` is set
+pub fn validate_webhook_url(url: &str, allow_insecure: bool) -> Result<(), String> {
+    let parsed =
+        url::Url::parse(url.trim()).map_err(|e| format!("Invalid webhook URL '{}': {}", url, e))?;
+    match parsed.scheme() {
+        "https" => Ok(()),
+        "http" if allow_insecure => Ok(()),
+        "http" => Err(format!(
+            "Webhook URL '{}' must use https; allow insecure delivery to use http",
+            url
+        )),
+        scheme => Err(format!(
+            "Webhook URL '{}' must use https, not {}",
+            url, scheme
+        )),
+    }
+}
+
+pub fn validate_webhook_secret(secret: &str) -> Result<(), String> {
+    if secret.trim().chars().count() < MIN_SECRET_LEN {
+        return Err(format!(
+            "Webhook secret must be at least {} characters",
+            MIN_SECRET_LEN
+        ));
+    }
+    Ok(())
+}
+
+pub fn encrypt_webhook_secret<R: Runtime>(
+    app: &AppHandle<R>,
+    secret: &str,
+) -> Result<EncryptedSecrets, String> {
+    encrypt_local(secret.trim().as_bytes(), &local_secret_key(app)?)
+}
+
+pub fn decrypt_webhook_secret<R: Runtime>(
+    app: &AppHandle<R>,
+    webhook: &Webhook,
+) -> Result<String, String> {
+    let bytes = decrypt_local(&webhook.secret, &local_secret_key(app)?).map_err(|_| {
+        format!(
+            "Secret of webhook {} can't be decrypted; enter it again",
+            webhook.id
+        )
+    })?;
+    String::from_utf8(bytes).map_err(|_| format!("Secret of webhook {} is corrupted", webhook.id))
+}
+
+pub fn load_webhooks_config<R: Runtime>(app: &AppHandle<R>) -> WebhooksConfig {
+    let stored = app
+        .store(CONFIG_STORE)
+        .ok()
+        .and_then(|store| store.get(CONFIG_KEY));
+    match stored.map(serde_json::from_value::<WebhooksConfig>) {
+        Some(Ok(config)) => config,
+        Some(Err(e)) => {
+            warn!("⚠️ Invalid webhooks config in store, using defaults: {}", e);
+            WebhooksConfig::default()
+        }
+        None => WebhooksConfig::default(),
+    }
+}
+
+pub fn save_webhooks_config<R: Runtime>(
+    app: &AppHandle<R>,
+    config: &WebhooksConfig,
+) -> Result<(), String> {
+    let store = app
+        .store(CONFIG_STORE)
+        .map_err(|e| format!("Failed to open webhooks store: {}", e))?;
+    let value = serde_json::to_value(config)
+        .map_err(|e| format!("Failed to serialize webhooks config: {}", e))?;
+    store.set(CONFIG_KEY, value);
+    store
+        .save()
+        .map_err(|e| format!("Failed to save webhooks config: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_webhook_urls_must_be_https_unless_insecure_is_allowed() {
+        assert!(validate_webhook_url("https://hooks.example.com/meetings", false).is_ok());
+        assert!(validate_webhook_url("http://192.168.1.20:8080/hook", false)
+            .unwrap_err()
+            .contains("allow insecure"));
+        assert!(validate_webhook_url("http://192.168.1.20:8080/hook", true).is_ok());
+        // Only http(s), whatever the flag
+        assert!(validate_webhook_url("ftp://example.com/hook", true).is_err());
+        assert!(validate_webhook_url("not a url", true).is_err());
+
+        assert!(validate_webhook_secret("short").is_err());
+        assert!(validate_webhook_secret("whsec_0123456789abcdef").is_ok());
+    }
+}
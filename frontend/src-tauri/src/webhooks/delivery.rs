@@ -0,0 +1,356 @@
+use super::config::{decrypt_webhook_secret, load_webhooks_config, validate_webhook_url, Webhook};
+use super::signing::{sign, ID_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+use crate::database::models::WebhookDelivery;
+use crate::database::repositories::webhook_delivery::WebhookDeliveriesRepository;
+use crate::http::client_factory;
+use crate::state::AppState;
+use chrono::Utc;
+use log::{error, info, warn};
+use reqwest::{header, Client};
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::SqlitePool;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, Runtime};
+
+/// Event sent when a meeting's summary is saved
+pub const SUMMARY_COMPLETED_EVENT: &str = "summary.completed";
+
+/// Upper bound for a receiver to answer one delivery
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Body of a delivery; the id and timestamp repeat the headers so the
+/// signature covers them
+#[derive(Serialize)]
+struct Envelope<'a> {
+    id: &'a str,
+    timestamp: i64,
+    event: &'a str,
+    data: &'a Value,
+}
+
+/// Where a delivery goes and what it carries
+pub struct DeliveryRequest<'a> {
+    pub webhook_id: &'a str,
+    pub url: &'a str,
+    pub secret: &'a str,
+    pub event: &'a str,
+    pub meeting_id: Option<&'a str>,
+    pub data: &'a Value,
+    pub redelivery_of: Option<&'a str>,
+}
+
+/// Signs and sends one delivery under a new id, and logs it
+pub async fn deliver(
+    pool: &SqlitePool,
+    client: &Client,
+    request: DeliveryRequest<'_>,
+) -> Result<WebhookDelivery, String> {
+    let id = uuid::Uuid::new_v4().to_string();
+    let now = Utc::now();
+    let timestamp = now.timestamp();
+    let body = serde_json::to_string(&Envelope {
+        id: &id,
+        timestamp,
+        event: request.event,
+        data: request.data,
+    })
+    .map_err(|e| format!("Failed to serialize webhook payload: {}", e))?;
+
+    let start = Instant::now();
+    let response = client
+        .post(request.url)
+        .header(header::CONTENT_TYPE, "application/json")
+        .header(ID_HEADER, &id)
+        .header(TIMESTAMP_HEADER, timestamp.to_string())
+        .header(
+            SIGNATURE_HEADER,
+            sign(request.secret, &id, timestamp, &body),
+        )
+        .body(body)
+        .timeout(DELIVERY_TIMEOUT)
+        .send()
+        .await;
+    let latency_ms = start.elapsed().as_millis() as i64;
+    let (status, error) = match response {
+        Ok(response) if response.status().is_success() => {
+            (Some(response.status().as_u16() as i64), None)
+        }
+        Ok(response) => (
+            Some(response.status().as_u16() as i64),
+            Some(format!(
+                "Receiver answered with status {}",
+                response.status()
+            )),
+        ),
+        // The URL may hold a token, so it stays out of the log
+        Err(e) => (
+            None,
+            Some(format!("Could not deliver: {}", e.without_url())),
+        ),
+    };
+
+    let delivery = WebhookDelivery {
+        id,
+        webhook_id: request.webhook_id.to_string(),
+        event: request.event.to_string(),
+        meeting_id: request.meeting_id.map(str::to_string),
+        payload: request.data.to_string(),
+        status,
+        latency_ms,
+        error,
+        redelivery_of: request.redelivery_of.map(str::to_string),
+        created_at: now,
+    };
+    WebhookDeliveriesRepository::insert(pool, &delivery)
+        .await
+        .map_err(|e| format!("Failed to log webhook delivery: {}", e))?;
+    Ok(delivery)
+}
+
+async fn deliver_to<R: Runtime>(
+    app: &AppHandle<R>,
+    pool: &SqlitePool,
+    webhook: &Webhook,
+    event: &str,
+    meeting_id: Option<&str>,
+    data: &Value,
+    redelivery_of: Option<&str>,
+) -> Result<WebhookDelivery, String> {
+    // Checked again in case the store was edited by hand
+    validate_webhook_url(&webhook.url, webhook.allow_insecure)?;
+    // Secrets saved before the local format still run the passphrase KDF
+    let secret = if webhook.secret.uses_passphrase_kdf() {
+        let (app, stored) = (app.clone(), webhook.clone());
+        tokio::task::spawn_blocking(move || decrypt_webhook_secret(&app, &stored))
+            .await
+            .map_err(|e| format!("Failed to decrypt webhook secret: {}", e))??
+    } else {
+        decrypt_webhook_secret(app, webhook)?
+    };
+    let delivery = deliver(
+        pool,
+        &client_factory::client(),
+        DeliveryRequest {
+            webhook_id: &webhook.id,
+            url: &webhook.url,
+            secret: &secret,
+            event,
+            meeting_id,
+            data,
+            redelivery_of,
+        },
+    )
+    .await?;
+    match &delivery.error {
+        None => info!(
+            "📬 Delivered {} webhook {} in {}ms",
+            event, webhook.id, delivery.latency_ms
+        ),
+        Some(e) => warn!("⚠️ {} webhook {} failed: {}", event, webhook.id, e),
+    }
+    Ok(delivery)
+}
+
+/// Sends a saved summary to every enabled webhook, or to the enabled ones
+/// among `webhook_ids` when it isn't empty
+pub async fn notify_summary_completed<R: Runtime>(
+    app: &AppHandle<R>,
+    meeting_id: &str,
+    result: &str,
+    webhook_ids: &[String],
+) {
+    let config = load_webhooks_config(app);
+    let webhooks: Vec<&Webhook> = config
+        .webhooks
+        .iter()
+        .filter(|w| w.enabled && (webhook_ids.is_empty() || webhook_ids.contains(&w.id)))
+        .collect();
+    if webhooks.is_empty() {
+        return;
+    }
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    let summary = serde_json::from_str::<Value>(result).unwrap_or(Value::Null);
+    let data = serde_json::json!({ "meeting_id": meeting_id, "summary": summary });
+    for webhook in webhooks {
+        let delivered = deliver_to(
+            app,
+            state.db_manager.pool(),
+            webhook,
+            SUMMARY_COMPLETED_EVENT,
+            Some(meeting_id),
+            &data,
+            None,
+        )
+        .await;
+        if let Err(e) = delivered {
+            error!(
+                "❌ Summary webhook {} for {}: {}",
+                webhook.id, meeting_id, e
+            );
+        }
+    }
+}
+
+/// Sends a logged delivery's data again, under a new id and timestamp
+pub async fn redeliver<R: Runtime>(
+    app: &AppHandle<R>,
+    pool: &SqlitePool,
+    delivery_id: &str,
+) -> Result<WebhookDelivery, String> {
+    let original = WebhookDeliveriesRepository::get(pool, delivery_id)
+        .await
+        .map_err(|e| format!("Failed to load webhook delivery: {}", e))?
+        .ok_or_else(|| format!("Webhook delivery {} not found", delivery_id))?;
+    let config = load_webhooks_config(app);
+    let webhook = config
+        .webhook(&original.webhook_id)
+        .ok_or_else(|| format!("Webhook {} no longer exists", original.webhook_id))?;
+    let data: Value = serde_json::from_str(&original.payload)
+        .map_err(|e| format!("Logged webhook payload is corrupted: {}", e))?;
+    deliver_to(
+        app,
+        pool,
+        webhook,
+        &original.event,
+        original.meeting_id.as_deref(),
+        &data,
+        Some(&original.id),
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_utils::test_pool;
+    use crate::http::test_server::{closed_url, mock_server, Requests};
+    use crate::webhooks::signing::{verify, DEFAULT_TOLERANCE_SECS};
+
+    /// Answers every request with `status` and records the raw requests
+    async fn receiver(status: u16) -> (String, Requests) {
+        let (url, requests) = mock_server(status, "").await;
+        (format!("{}/hook", url), requests)
+    }
+
+    fn header<'a>(request: &'a str, name: &str) -> &'a str {
+        request
+            .lines()
+            .find_map(|line| {
+                let (key, value) = line.split_once(':')?;
+                key.eq_ignore_ascii_case(name).then(|| value.trim())
+            })
+            .unwrap()
+    }
+
+    fn body(request: &str) -> &str {
+        request.split_once("\r\n\r\n").unwrap().1
+    }
+
+    #[tokio::test]
+    async fn test_deliveries_are_signed_and_logged() {
+        let pool = test_pool().await;
+        let (url, requests) = receiver(200).await;
+        let data = serde_json::json!({ "meeting_id": "meeting-1", "summary": { "markdown": "## Summary" } });
+        let request = || DeliveryRequest {
+            webhook_id: "hook-1",
+            url: &url,
+            secret: "whsec_test_secret_123",
+            event: SUMMARY_COMPLETED_EVENT,
+            meeting_id: Some("meeting-1"),
+            data: &data,
+            redelivery_of: None,
+        };
+
+        let first = deliver(&pool, &Client::new(), request()).await.unwrap();
+        assert_eq!(first.status, Some(200));
+        assert_eq!(first.error, None);
+
+        let sent = requests.lock().unwrap()[0].clone();
+        let id = header(&sent, ID_HEADER);
+        let timestamp: i64 = header(&sent, TIMESTAMP_HEADER).parse().unwrap();
+        assert_eq!(id, first.id);
+        let envelope: Value = serde_json::from_str(body(&sent)).unwrap();
+        assert_eq!(envelope["id"], first.id.as_str());
+        assert_eq!(envelope["timestamp"], timestamp);
+        assert_eq!(envelope["event"], SUMMARY_COMPLETED_EVENT);
+        assert_eq!(envelope["data"], data);
+        assert_eq!(
+            verify(
+                "whsec_test_secret_123",
+                id,
+                timestamp,
+                body(&sent),
+                header(&sent, SIGNATURE_HEADER),
+                Utc::now().timestamp(),
+                DEFAULT_TOLERANCE_SECS,
+            ),
+            Ok(())
+        );
+
+        // A redelivery carries the same data under a new id
+        let again = deliver(
+            &pool,
+            &Client::new(),
+            DeliveryRequest {
+                redelivery_of: Some(&first.id),
+                ..request()
+            },
+        )
+        .await
+        .unwrap();
+        assert_ne!(again.id, first.id);
+        assert_eq!(again.payload, first.payload);
+
+        let logged = WebhookDeliveriesRepository::list(&pool, "hook-1", 10)
+            .await
+            .unwrap();
+        assert_eq!(logged.len(), 2);
+        assert_eq!(logged[0].id, again.id);
+        assert_eq!(logged[0].redelivery_of.as_deref(), Some(first.id.as_str()));
+        assert_eq!(
+            WebhookDeliveriesRepository::list(&pool, "hook-1", 1)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(WebhookDeliveriesRepository::list(&pool, "hook-2", 10)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_failed_deliveries_record_status_or_error() {
+        let pool = test_pool().await;
+        let (url, _) = receiver(503).await;
+        let data = serde_json::json!({});
+        let request = |url: &str| DeliveryRequest {
+            webhook_id: "hook-1",
+            url,
+            secret: "whsec_test_secret_123",
+            event: SUMMARY_COMPLETED_EVENT,
+            meeting_id: None,
+            data: &data,
+            redelivery_of: None,
+        };
+
+        let rejected = deliver(&pool, &Client::new(), request(&url)).await.unwrap();
+        assert_eq!(rejected.status, Some(503));
+        assert!(rejected.error.unwrap().contains("503"));
+
+        let closed_url = format!("{}/hook", closed_url().await);
+        let unreachable = deliver(&pool, &Client::new(), request(&closed_url))
+            .await
+            .unwrap();
+        assert_eq!(unreachable.status, None);
+        assert!(unreachable.error.unwrap().starts_with("Could not deliver"));
+        assert!(WebhookDeliveriesRepository::get(&pool, &unreachable.id)
+            .await
+            .unwrap()
+            .is_some());
+    }
+}
@@ -0,0 +1,14 @@
+/// Webhooks module - sends completed summaries to external services
+///
+/// This module contains:
+/// - The webhook settings, with https required unless a webhook allows
+///   insecure delivery, and signing secrets stored encrypted
+/// - HMAC signing over each delivery's id, timestamp and body, so receivers
+///   can reject forged and replayed deliveries
+/// - Delivery with a log of every attempt's response status and latency,
+///   and redelivery of a logged attempt under a new id
+/// - Tauri commands for the settings, the delivery log and redelivery
+pub mod commands;
+pub mod config;
+pub mod delivery;
+pub mod signing;
@@ -1,3 +1,4 @@
+use crate::audio::transcription::{preflight_check, HostResources};
 use crate::whisper_engine::{ModelInfo, WhisperEngine};
 use std::sync::{Arc, Mutex};
 use std::path::PathBuf;
@@ -317,6 +318,10 @@ pub async fn whisper_validate_model_ready_with_config<R: tauri::Runtime>(
             available_models[0].name.clone()
         };
 
+        // Fail before loading rather than running out of memory mid-recording
+        let resources = HostResources::probe(&engine.get_models_directory().await);
+        preflight_check(&model_name, false, &resources)?;
+
         engine
             .load_model(&model_name)
             .await
@@ -373,6 +378,9 @@ pub async fn whisper_download_model(
     };
 
     if let Some(engine) = engine {
+        let resources = HostResources::probe(&engine.get_models_directory().await);
+        preflight_check(&model_name, true, &resources)?;
+
         // Create progress callback that emits events
         let app_handle_clone = app_handle.clone();
         let model_name_clone = model_name.clone();
@@ -7,7 +7,8 @@ use tokio::sync::RwLock;
 use whisper_rs::{WhisperContext, WhisperContextParameters, FullParams, SamplingStrategy};
 use serde::{Serialize, Deserialize};
 use anyhow::{Result, anyhow};
-use reqwest::Client;
+use crate::audio::transcription::model_registry::WHISPER_MODELS;
+use crate::http::client_factory;
 use tokio::fs;
 use tokio::io::AsyncWriteExt;
 use crate::{perf_debug, perf_trace};
@@ -169,28 +170,10 @@ impl WhisperEngine {
     pub async fn discover_models(&self) -> Result<Vec<ModelInfo>> {
         let models_dir = &self.models_dir;
         let mut models = Vec::new();
-                // Using standard ggerganov/whisper.cpp GGML models
-        let model_configs = [
-            // Standard f16 models (full precision)
-            ("tiny", "ggml-tiny.bin", 39, "Decent", "Very Fast", "Fastest processing, good for real-time use"),
-            ("base", "ggml-base.bin", 142, "Good", "Fast", "Good balance of speed and accuracy"),
-            ("small", "ggml-small.bin", 466, "Good", "Medium", "Better accuracy, moderate speed"),
-            ("medium", "ggml-medium.bin", 1420, "High", "Slow", "High accuracy for professional use"),
-            ("large-v3-turbo", "ggml-large-v3-turbo.bin", 809, "High", "Medium", "Best accuracy with improved speed"),
-            ("large-v3", "ggml-large-v3.bin", 2870, "High", "Slow", "Best accuracy, latest large model"),
-
-            // Q5_0 quantized models (balanced speed/accuracy)
-            ("tiny-q5_0", "ggml-tiny-q5_0.bin", 26, "Decent", "Very Fast", "Quantized tiny model, ~50% faster processing"),
-            ("base-q5_0", "ggml-base-q5_0.bin", 85, "Good", "Fast", "Quantized base model, good speed/accuracy balance"),
-            ("small-q5_0", "ggml-small-q5_0.bin", 280, "Good", "Fast", "Quantized small model, faster than f16 version"),
-            ("medium-q5_0", "ggml-medium-q5_0.bin", 852, "High", "Medium", "Quantized medium model, professional quality"),
-            ("large-v3-turbo-q5_0", "ggml-large-v3-turbo-q5_0.bin", 574, "High", "Medium", "Quantized large model, best balance"),
-            ("large-v3-q5_0", "ggml-large-v3-q5_0.bin", 1050, "High", "Slow", "Quantized large model, high accuracy"),
-
-           ];
-        
-        for (name, filename, size_mb, accuracy, speed, description) in model_configs {
-            let model_path = models_dir.join(filename);
+        for model in WHISPER_MODELS {
+            let (name, size_mb) = (model.name, model.download_size_mb);
+            let filename = model.filename();
+            let model_path = models_dir.join(&filename);
             let status = if model_path.exists() {
                 // Check if file size is reasonable (at least 1MB for a valid model)
                 match std::fs::metadata(&model_path) {
@@ -254,10 +237,10 @@ impl WhisperEngine {
                 name: name.to_string(),
                 path: model_path,
                 size_mb: size_mb as u32,
-                accuracy: accuracy.to_string(),
-                speed: speed.to_string(),
+                accuracy: model.accuracy.to_string(),
+                speed: model.speed.to_string(),
                 status,
-                description: description.to_string(),
+                description: model.description.to_string(),
             };
             
             models.push(model_info);
@@ -977,7 +960,7 @@ impl WhisperEngine {
         }
         
         log::info!("Creating HTTP client and starting request...");
-        let client = Client::new();
+        let client = client_factory::client();
         
         log::info!("Sending GET request to: {}", model_url);
         let response = client.get(model_url).send().await
@@ -762,10 +762,11 @@ export default function Home() {
           message: string;
           folder_path?: string;
           meeting_name?: string;
+          live_session_id?: string;
         }>('recording-stopped', async (event) => {
           console.log('Recording stopped event received:', event.payload);
 
-          const { folder_path, meeting_name } = event.payload;
+          const { folder_path, meeting_name, live_session_id } = event.payload;
 
           // Store folder_path and meeting_name for later use in handleRecordingStop2
           if (folder_path) {
@@ -776,6 +777,9 @@ export default function Home() {
             sessionStorage.setItem('last_recording_meeting_name', meeting_name);
             console.log('✅ Stored meeting_name for frontend save:', meeting_name);
           }
+          if (live_session_id) {
+            sessionStorage.setItem('last_recording_live_session_id', live_session_id);
+          }
 
         });
         console.log('Recording stopped listener setup complete');
@@ -1140,6 +1144,7 @@ export default function Home() {
         // Get folder_path and meeting_name from recording-stopped event
         const folderPath = sessionStorage.getItem('last_recording_folder_path');
         const savedMeetingName = sessionStorage.getItem('last_recording_meeting_name');
+        const liveSessionId = sessionStorage.getItem('last_recording_live_session_id');
 
         console.log('💾 Saving COMPLETE transcripts to database...', {
           transcript_count: freshTranscripts.length,
@@ -1154,6 +1159,7 @@ export default function Home() {
             meetingTitle: meetingTitle || savedMeetingName,
             transcripts: freshTranscripts, 
             folderPath: folderPath, 
+            liveSessionId: liveSessionId,
           }) as any;
 
           const meetingId = responseData.meeting_id;
@@ -1169,6 +1175,7 @@ export default function Home() {
           // Clean up session storage
           sessionStorage.removeItem('last_recording_folder_path');
           sessionStorage.removeItem('last_recording_meeting_name');
+          sessionStorage.removeItem('last_recording_live_session_id');
 
           // Refetch meetings and set current meeting
           await refetchMeetings();
@@ -14,6 +14,7 @@ export interface RecordingPreferences {
   preferred_mic_device: string | null;
   preferred_system_device: string | null;
   filtered_apps?: string[] | null;
+  capture_sample_rate?: number;
 }
 
 interface RecordingSettingsProps {